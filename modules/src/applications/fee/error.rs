@@ -0,0 +1,44 @@
+use flex_error::{define_error, TraceError};
+use tendermint_proto::Error as TendermintProtoError;
+
+use crate::applications::transfer::error::Error as TransferError;
+use crate::core::ics24_host::error::ValidationError;
+use crate::prelude::*;
+use crate::signer::SignerError;
+
+define_error! {
+    #[derive(Debug, PartialEq, Eq)]
+    Error {
+        InvalidFee
+            [ TransferError ]
+            | _ | { "invalid fee coin" },
+
+        MissingFee
+            | _ | { "missing fee" },
+
+        MissingPacketId
+            | _ | { "missing packet identifier" },
+
+        InvalidPortId
+            { port_id: String }
+            [ ValidationError ]
+            | e | { format_args!("invalid port identifier {0}", e.port_id) },
+
+        InvalidChannelId
+            { channel_id: String }
+            [ ValidationError ]
+            | e | { format_args!("invalid channel identifier {0}", e.channel_id) },
+
+        Signer
+            [ SignerError ]
+            | _ | { "failed to parse signer" },
+
+        DecodeRawMsg
+            [ TraceError<TendermintProtoError> ]
+            | _ | { "error decoding raw msg" },
+
+        UnknownMsgType
+            { msg_type: String }
+            | e | { format_args!("unknown msg type: {0}", e.msg_type) },
+    }
+}
@@ -0,0 +1,10 @@
+//! ICS29 relayer incentivization. Fees escrowed on the sending chain of a packet are paid to the
+//! relayers of the packet: the receive fee to the relayer of the packet on the counterparty chain,
+//! and the acknowledgement or timeout fee to the relayer of its acknowledgement or timeout.
+pub mod error;
+pub mod msgs;
+pub mod packet_fee;
+
+/// The version of the fee middleware, wrapped around the application version of the channels it
+/// is enabled on.
+pub const VERSION: &str = "ics29-1";
@@ -0,0 +1,2 @@
+pub mod register_counterparty_payee;
+pub mod register_payee;
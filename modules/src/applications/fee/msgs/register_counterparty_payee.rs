@@ -0,0 +1,134 @@
+//! Message used by relayers to register the account to which the receive fees they earn on a
+//! channel are paid, on the counterparty chain.
+
+use crate::prelude::*;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::applications::fee::v1::MsgRegisterCounterpartyPayee as RawMsgRegisterCounterpartyPayee;
+use tendermint_proto::Protobuf;
+
+use crate::applications::fee::error::Error;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.applications.fee.v1.MsgRegisterCounterpartyPayee";
+
+/// Message registering `counterparty_payee`, an account on the counterparty chain, as the account
+/// to which the receive fees are paid for the packets received by `relayer` on the given channel.
+///
+/// The receive fees are escrowed on the chain the packets are sent from, which only learns about
+/// the relayer of a packet through its acknowledgement, and thus needs an address of its own.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgRegisterCounterpartyPayee {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub relayer: Signer,
+    pub counterparty_payee: Signer,
+}
+
+impl Msg for MsgRegisterCounterpartyPayee {
+    type ValidationError = Error;
+    type Raw = RawMsgRegisterCounterpartyPayee;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl TryFrom<RawMsgRegisterCounterpartyPayee> for MsgRegisterCounterpartyPayee {
+    type Error = Error;
+
+    fn try_from(raw_msg: RawMsgRegisterCounterpartyPayee) -> Result<Self, Self::Error> {
+        Ok(MsgRegisterCounterpartyPayee {
+            port_id: raw_msg
+                .port_id
+                .parse()
+                .map_err(|e| Error::invalid_port_id(raw_msg.port_id.clone(), e))?,
+            channel_id: raw_msg
+                .channel_id
+                .parse()
+                .map_err(|e| Error::invalid_channel_id(raw_msg.channel_id.clone(), e))?,
+            relayer: raw_msg.relayer.parse().map_err(Error::signer)?,
+            counterparty_payee: raw_msg.counterparty_payee.parse().map_err(Error::signer)?,
+        })
+    }
+}
+
+impl From<MsgRegisterCounterpartyPayee> for RawMsgRegisterCounterpartyPayee {
+    fn from(domain_msg: MsgRegisterCounterpartyPayee) -> Self {
+        RawMsgRegisterCounterpartyPayee {
+            port_id: domain_msg.port_id.to_string(),
+            channel_id: domain_msg.channel_id.to_string(),
+            relayer: domain_msg.relayer.to_string(),
+            counterparty_payee: domain_msg.counterparty_payee.to_string(),
+        }
+    }
+}
+
+impl Protobuf<RawMsgRegisterCounterpartyPayee> for MsgRegisterCounterpartyPayee {}
+
+impl TryFrom<Any> for MsgRegisterCounterpartyPayee {
+    type Error = Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            TYPE_URL => {
+                MsgRegisterCounterpartyPayee::decode_vec(&raw.value).map_err(Error::decode_raw_msg)
+            }
+            _ => Err(Error::unknown_msg_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<MsgRegisterCounterpartyPayee> for Any {
+    fn from(msg: MsgRegisterCounterpartyPayee) -> Self {
+        Self {
+            type_url: TYPE_URL.to_string(),
+            value: msg
+                .encode_vec()
+                .expect("encoding to `Any` from `MsgRegisterCounterpartyPayee`"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+    use crate::test_utils::get_dummy_bech32_account;
+
+    #[test]
+    fn to_and_from_any() {
+        let msg = MsgRegisterCounterpartyPayee {
+            port_id: PortId::transfer(),
+            channel_id: ChannelId::new(7),
+            relayer: get_dummy_bech32_account().parse().unwrap(),
+            counterparty_payee: get_dummy_bech32_account().parse().unwrap(),
+        };
+
+        let any = Any::from(msg.clone());
+        assert_eq!(
+            MsgRegisterCounterpartyPayee::try_from(any.clone()).unwrap(),
+            msg
+        );
+
+        let raw = RawMsgRegisterCounterpartyPayee::from(msg);
+        let invalid_channel = RawMsgRegisterCounterpartyPayee {
+            channel_id: "invalid channel".to_string(),
+            ..raw
+        };
+        assert!(MsgRegisterCounterpartyPayee::try_from(invalid_channel).is_err());
+
+        let wrong_type = Any {
+            type_url: crate::applications::fee::msgs::register_payee::TYPE_URL.to_string(),
+            ..any
+        };
+        assert!(MsgRegisterCounterpartyPayee::try_from(wrong_type).is_err());
+    }
+}
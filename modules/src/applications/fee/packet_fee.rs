@@ -0,0 +1,181 @@
+//! Domain types for the fees attached to a packet.
+
+use ibc_proto::cosmos::base::v1beta1::Coin as RawCoin;
+use ibc_proto::ibc::applications::fee::v1::{
+    Fee as RawFee, IdentifiedPacketFees as RawIdentifiedPacketFees, PacketFee as RawPacketFee,
+};
+use ibc_proto::ibc::core::channel::v1::PacketId as RawPacketId;
+
+use crate::applications::fee::error::Error;
+use crate::applications::transfer::BaseCoin;
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::prelude::*;
+use crate::signer::Signer;
+
+/// The fees paid for relaying a packet. Each fee may be made of several denominations.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Fee {
+    /// Paid to the relayer of the packet to the counterparty chain.
+    pub recv_fee: Vec<BaseCoin>,
+    /// Paid to the relayer of the acknowledgement of the packet.
+    pub ack_fee: Vec<BaseCoin>,
+    /// Paid to the relayer of the timeout of the packet.
+    pub timeout_fee: Vec<BaseCoin>,
+}
+
+fn coins_try_from_raw(coins: Vec<RawCoin>) -> Result<Vec<BaseCoin>, Error> {
+    coins
+        .into_iter()
+        .map(|coin| coin.try_into().map_err(Error::invalid_fee))
+        .collect()
+}
+
+impl TryFrom<RawFee> for Fee {
+    type Error = Error;
+
+    fn try_from(raw: RawFee) -> Result<Self, Self::Error> {
+        Ok(Self {
+            recv_fee: coins_try_from_raw(raw.recv_fee)?,
+            ack_fee: coins_try_from_raw(raw.ack_fee)?,
+            timeout_fee: coins_try_from_raw(raw.timeout_fee)?,
+        })
+    }
+}
+
+impl From<Fee> for RawFee {
+    fn from(fee: Fee) -> Self {
+        Self {
+            recv_fee: fee.recv_fee.into_iter().map(Into::into).collect(),
+            ack_fee: fee.ack_fee.into_iter().map(Into::into).collect(),
+            timeout_fee: fee.timeout_fee.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// A fee escrowed for a packet, along with the account to which the unspent part of the fee is
+/// refunded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PacketFee {
+    pub fee: Fee,
+    pub refund_address: Signer,
+    /// The relayers allowed to be paid the fee, any relayer if empty.
+    pub relayers: Vec<Signer>,
+}
+
+impl TryFrom<RawPacketFee> for PacketFee {
+    type Error = Error;
+
+    fn try_from(raw: RawPacketFee) -> Result<Self, Self::Error> {
+        Ok(Self {
+            fee: raw.fee.ok_or_else(Error::missing_fee)?.try_into()?,
+            refund_address: raw.refund_address.parse().map_err(Error::signer)?,
+            relayers: raw
+                .relayers
+                .into_iter()
+                .map(|relayer| relayer.parse().map_err(Error::signer))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<PacketFee> for RawPacketFee {
+    fn from(packet_fee: PacketFee) -> Self {
+        Self {
+            fee: Some(packet_fee.fee.into()),
+            refund_address: packet_fee.refund_address.to_string(),
+            relayers: packet_fee
+                .relayers
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+}
+
+/// All the fees escrowed for the packet sent with `sequence` on the given channel.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdentifiedPacketFees {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub sequence: Sequence,
+    pub packet_fees: Vec<PacketFee>,
+}
+
+impl TryFrom<RawIdentifiedPacketFees> for IdentifiedPacketFees {
+    type Error = Error;
+
+    fn try_from(raw: RawIdentifiedPacketFees) -> Result<Self, Self::Error> {
+        let packet_id = raw.packet_id.ok_or_else(Error::missing_packet_id)?;
+
+        Ok(Self {
+            port_id: packet_id
+                .port_id
+                .parse()
+                .map_err(|e| Error::invalid_port_id(packet_id.port_id.clone(), e))?,
+            channel_id: packet_id
+                .channel_id
+                .parse()
+                .map_err(|e| Error::invalid_channel_id(packet_id.channel_id.clone(), e))?,
+            sequence: packet_id.sequence.into(),
+            packet_fees: raw
+                .packet_fees
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<IdentifiedPacketFees> for RawIdentifiedPacketFees {
+    fn from(fees: IdentifiedPacketFees) -> Self {
+        Self {
+            packet_id: Some(RawPacketId {
+                port_id: fees.port_id.to_string(),
+                channel_id: fees.channel_id.to_string(),
+                sequence: fees.sequence.into(),
+            }),
+            packet_fees: fees.packet_fees.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+    use crate::test_utils::get_dummy_bech32_account;
+
+    #[test]
+    fn identified_packet_fees_to_and_from_raw() {
+        let coin = |amount: u64| BaseCoin {
+            denom: "uatom".parse().unwrap(),
+            amount: amount.into(),
+        };
+
+        let fees = IdentifiedPacketFees {
+            port_id: PortId::transfer(),
+            channel_id: ChannelId::new(3),
+            sequence: 42.into(),
+            packet_fees: vec![PacketFee {
+                fee: Fee {
+                    recv_fee: vec![coin(10)],
+                    ack_fee: vec![coin(5)],
+                    timeout_fee: vec![],
+                },
+                refund_address: get_dummy_bech32_account().parse().unwrap(),
+                relayers: vec![],
+            }],
+        };
+
+        let raw = RawIdentifiedPacketFees::from(fees.clone());
+        assert_eq!(IdentifiedPacketFees::try_from(raw.clone()).unwrap(), fees);
+
+        let missing_packet_id = RawIdentifiedPacketFees {
+            packet_id: None,
+            ..raw
+        };
+        assert!(IdentifiedPacketFees::try_from(missing_packet_id).is_err());
+    }
+}
@@ -0,0 +1,35 @@
+use crate::applications::interchain_query::error::Error;
+use crate::applications::interchain_query::query::{
+    CrossChainQuery, CrossChainQueryResult, QueryId,
+};
+use crate::core::ics02_client::context::ClientReader;
+
+pub trait QueryReader {
+    /// Returns the pending query with the given identifier, if any.
+    fn get_query(&self, query_id: &QueryId) -> Option<CrossChainQuery>;
+
+    /// Returns the result of the query with the given identifier, once it was answered or timed
+    /// out.
+    fn get_query_result(&self, query_id: &QueryId) -> Option<CrossChainQueryResult>;
+
+    /// Returns the number of queries sent so far, from which the identifier of the next query is
+    /// derived.
+    fn query_counter(&self) -> Result<u64, Error>;
+}
+
+pub trait QueryKeeper {
+    fn store_query(&mut self, query: CrossChainQuery) -> Result<(), Error>;
+
+    fn delete_query(&mut self, query_id: &QueryId) -> Result<(), Error>;
+
+    fn store_query_result(&mut self, result: CrossChainQueryResult) -> Result<(), Error>;
+
+    /// Called upon each new query, to increase the counter of queries.
+    fn increase_query_counter(&mut self);
+}
+
+/// Captures all the dependencies which the cross-chain queries application requires, among
+/// which the clients of the queried chains.
+pub trait QueryContext: ClientReader + QueryReader + QueryKeeper {}
+
+impl<T> QueryContext for T where T: ClientReader + QueryReader + QueryKeeper {}
@@ -0,0 +1,90 @@
+use flex_error::{define_error, TraceError};
+use tendermint_proto::Error as TendermintProtoError;
+
+use crate::applications::interchain_query::query::QueryId;
+use crate::core::ics02_client::error as client_error;
+use crate::core::ics24_host::error::ValidationError;
+use crate::core::ics24_host::identifier::ClientId;
+use crate::prelude::*;
+use crate::proofs::ProofError;
+use crate::signer::SignerError;
+use crate::Height;
+
+define_error! {
+    #[derive(Debug, PartialEq, Eq)]
+    Error {
+        Ics02Client
+            [ client_error::Error ]
+            | _ | { "ICS02 client error" },
+
+        InvalidQueryId
+            | _ | { "query identifier cannot be empty" },
+
+        InvalidQueryPath
+            { path: String }
+            | e | {
+                format_args!("invalid query path {0}, expected a store name followed by a key",
+                    e.path)
+            },
+
+        InvalidClientId
+            { client_id: String }
+            [ ValidationError ]
+            | e | { format_args!("invalid client identifier {0}", e.client_id) },
+
+        InvalidHeight
+            [ client_error::Error ]
+            | _ | { "invalid height" },
+
+        InvalidTimeoutTimestamp
+            { timestamp: u64 }
+            | e | { format_args!("invalid timeout timestamp {0}", e.timestamp) },
+
+        InvalidQueryResult
+            { result: i32 }
+            | e | { format_args!("invalid query result {0}", e.result) },
+
+        MissingProofHeight
+            | _ | { "missing proof height" },
+
+        InvalidProof
+            [ ProofError ]
+            | _ | { "invalid proof" },
+
+        Signer
+            [ SignerError ]
+            | _ | { "failed to parse signer" },
+
+        DecodeRawMsg
+            [ TraceError<TendermintProtoError> ]
+            | _ | { "error decoding raw msg" },
+
+        UnknownMsgType
+            { msg_type: String }
+            | e | { format_args!("unknown msg type: {0}", e.msg_type) },
+
+        QueryNotFound
+            { query_id: QueryId }
+            | e | { format_args!("no pending query with identifier {0}", e.query_id) },
+
+        FrozenClient
+            { client_id: ClientId }
+            | e | { format_args!("client {0} is frozen", e.client_id) },
+
+        QueryHeightMismatch
+            { query_id: QueryId, query_height: Height, proof_height: Height }
+            | e | {
+                format_args!("query {0} must be proven at height {1}, but the proof is at height {2}",
+                    e.query_id, e.query_height, e.proof_height)
+            },
+
+        VerificationFailed
+            { query_id: QueryId }
+            [ client_error::Error ]
+            | e | { format_args!("failed to verify the result of query {0}", e.query_id) },
+
+        Store
+            { reason: String }
+            | e | { format_args!("failed to access the query store: {0}", e.reason) },
+    }
+}
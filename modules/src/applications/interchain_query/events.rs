@@ -0,0 +1,224 @@
+//! Types for the events emitted by the cross-chain queries application.
+
+use serde_derive::{Deserialize, Serialize};
+use tendermint::abci::tag::Tag;
+use tendermint::abci::Event as AbciEvent;
+
+use crate::applications::interchain_query::query::{CrossChainQuery, QueryId, QueryPath};
+use crate::applications::interchain_query::MODULE_ID_STR;
+use crate::core::ics02_client::error::Error as Ics02Error;
+use crate::core::ics24_host::identifier::ClientId;
+use crate::events::{
+    extract_attribute, maybe_extract_attribute, Error as EventError, IbcEvent, IbcEventType,
+    ModuleEvent, RawObject,
+};
+use crate::prelude::*;
+use crate::Height;
+
+const QUERY_ID_ATTRIBUTE_KEY: &str = "query_id";
+const CLIENT_ID_ATTRIBUTE_KEY: &str = "client_id";
+const PATH_ATTRIBUTE_KEY: &str = "path";
+const QUERY_HEIGHT_ATTRIBUTE_KEY: &str = "query_height";
+const RESULT_ATTRIBUTE_KEY: &str = "result";
+const PROOF_HEIGHT_ATTRIBUTE_KEY: &str = "proof_height";
+
+const EVENT_TYPE_QUERY_RESULT: &str = "cross_chain_query_result";
+
+/// Emitted when a module sends a cross-chain query, for relayers to answer it.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct SendQuery {
+    pub height: Height,
+    pub query_id: QueryId,
+    pub client_id: ClientId,
+    pub path: QueryPath,
+    pub query_height: Option<Height>,
+}
+
+impl SendQuery {
+    pub fn new(height: Height, query: &CrossChainQuery) -> Self {
+        Self {
+            height,
+            query_id: query.id.clone(),
+            client_id: query.client_id.clone(),
+            path: query.path.clone(),
+            query_height: query.query_height,
+        }
+    }
+
+    pub fn height(&self) -> Height {
+        self.height
+    }
+
+    pub fn set_height(&mut self, height: Height) {
+        self.height = height;
+    }
+
+    fn from_attributes(
+        height: Height,
+        query_id: &str,
+        client_id: &str,
+        path: &str,
+        query_height: Option<&str>,
+    ) -> Option<Self> {
+        let query_height = match query_height {
+            Some(value) if !value.is_empty() => Some(value.parse().ok()?),
+            _ => None,
+        };
+
+        Some(Self {
+            height,
+            query_id: query_id.parse().ok()?,
+            client_id: client_id.parse().ok()?,
+            path: path.parse().ok()?,
+            query_height,
+        })
+    }
+}
+
+impl From<SendQuery> for IbcEvent {
+    fn from(ev: SendQuery) -> Self {
+        IbcEvent::CrossChainQuery(ev)
+    }
+}
+
+/// Convert the event to a Tendermint ABCI event. The query height is left empty when the value
+/// may be proven at any height.
+impl From<SendQuery> for AbciEvent {
+    fn from(ev: SendQuery) -> Self {
+        let query_height = ev
+            .query_height
+            .map(|height| height.to_string())
+            .unwrap_or_default();
+
+        let attributes = [
+            (QUERY_ID_ATTRIBUTE_KEY, ev.query_id.to_string()),
+            (CLIENT_ID_ATTRIBUTE_KEY, ev.client_id.to_string()),
+            (PATH_ATTRIBUTE_KEY, ev.path.to_string()),
+            (QUERY_HEIGHT_ATTRIBUTE_KEY, query_height),
+        ]
+        .into_iter()
+        .map(|(key, value)| Tag {
+            key: key.parse().unwrap(),
+            value: value.parse().unwrap(),
+        })
+        .collect();
+
+        AbciEvent {
+            type_str: IbcEventType::CrossChainQuery.as_str().to_string(),
+            attributes,
+        }
+    }
+}
+
+/// Parses a cross-chain query event from the events of a transaction. The height of the returned
+/// event must be set by the caller.
+pub fn try_from_tx(event: &AbciEvent) -> Option<IbcEvent> {
+    if !matches!(event.type_str.parse(), Ok(IbcEventType::CrossChainQuery)) {
+        return None;
+    }
+
+    let attribute = |key: &str| {
+        event
+            .attributes
+            .iter()
+            .find(|tag| tag.key.as_ref() == key)
+            .map(|tag| tag.value.as_ref())
+    };
+
+    SendQuery::from_attributes(
+        Height::new(0, 1).unwrap(),
+        attribute(QUERY_ID_ATTRIBUTE_KEY)?,
+        attribute(CLIENT_ID_ATTRIBUTE_KEY)?,
+        attribute(PATH_ATTRIBUTE_KEY)?,
+        attribute(QUERY_HEIGHT_ATTRIBUTE_KEY),
+    )
+    .map(IbcEvent::CrossChainQuery)
+}
+
+impl TryFrom<RawObject<'_>> for SendQuery {
+    type Error = EventError;
+
+    fn try_from(obj: RawObject<'_>) -> Result<Self, Self::Error> {
+        let key = |attribute: &str| format!("{}.{}", obj.action, attribute);
+
+        let query_id = extract_attribute(&obj, &key(QUERY_ID_ATTRIBUTE_KEY))?;
+        let client_id = extract_attribute(&obj, &key(CLIENT_ID_ATTRIBUTE_KEY))?;
+        let path = extract_attribute(&obj, &key(PATH_ATTRIBUTE_KEY))?;
+        let query_height = maybe_extract_attribute(&obj, &key(QUERY_HEIGHT_ATTRIBUTE_KEY))
+            .filter(|value| !value.is_empty())
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|e| Ics02Error::invalid_string_as_height(value, e))
+                    .map_err(EventError::client)
+            })
+            .transpose()?;
+
+        Ok(Self {
+            height: obj.height,
+            query_id: query_id
+                .parse()
+                .map_err(|_| EventError::missing_key(key(QUERY_ID_ATTRIBUTE_KEY)))?,
+            client_id: client_id.parse().map_err(EventError::parse)?,
+            path: path
+                .parse()
+                .map_err(|_| EventError::missing_key(key(PATH_ATTRIBUTE_KEY)))?,
+            query_height,
+        })
+    }
+}
+
+/// Emitted when a cross-chain query is answered or times out.
+pub struct QueryResultEvent {
+    pub query_id: QueryId,
+    pub result: String,
+    pub proof_height: Option<Height>,
+}
+
+impl From<QueryResultEvent> for ModuleEvent {
+    fn from(ev: QueryResultEvent) -> Self {
+        let QueryResultEvent {
+            query_id,
+            result,
+            proof_height,
+        } = ev;
+        let proof_height = proof_height
+            .map(|height| height.to_string())
+            .unwrap_or_default();
+        Self {
+            kind: EVENT_TYPE_QUERY_RESULT.to_string(),
+            module_name: MODULE_ID_STR.parse().expect("invalid ModuleId"),
+            attributes: vec![
+                (QUERY_ID_ATTRIBUTE_KEY, query_id).into(),
+                (RESULT_ATTRIBUTE_KEY, result).into(),
+                (PROOF_HEIGHT_ATTRIBUTE_KEY, proof_height).into(),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+
+    #[test]
+    fn send_query_to_and_from_abci() {
+        let expected = [None, Some(Height::new(1, 42).unwrap())].map(|query_height| SendQuery {
+            height: Height::new(0, 1).unwrap(),
+            query_id: QueryId::new(3),
+            client_id: ClientId::default(),
+            path: "acc/balances".parse().unwrap(),
+            query_height,
+        });
+
+        for ev in expected {
+            let abci_event = AbciEvent::from(ev.clone());
+            assert_eq!(
+                try_from_tx(&abci_event),
+                Some(IbcEvent::CrossChainQuery(ev))
+            );
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! Handlers sending cross-chain queries on behalf of the modules of the host chain, and
+//! processing the results submitted by relayers.
+
+use crate::applications::interchain_query::context::QueryContext;
+use crate::applications::interchain_query::error::Error;
+use crate::applications::interchain_query::events::{QueryResultEvent, SendQuery};
+use crate::applications::interchain_query::msgs::MsgSubmitCrossChainQueryResult;
+use crate::applications::interchain_query::query::{
+    CrossChainQuery, CrossChainQueryResult, QueryId, QueryPath, QueryResult,
+};
+use crate::core::ics02_client::client_consensus::ConsensusState;
+use crate::core::ics02_client::client_def::{AnyClient, ClientDef};
+use crate::core::ics02_client::client_state::ClientState;
+use crate::core::ics04_channel::timeout::TimeoutHeight;
+use crate::core::ics24_host::identifier::ClientId;
+use crate::events::{IbcEvent, ModuleEvent};
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::prelude::*;
+use crate::timestamp::Timestamp;
+use crate::Height;
+
+/// Sends a query of the value stored at `path` on the chain tracked by the client `client_id`.
+/// The value must be proven at `query_height` if set, and the query times out once the host chain
+/// is past `local_timeout_height` or `local_timeout_timestamp`. Returns the identifier of the
+/// query, under which its result is stored.
+pub fn send_query<Ctx: QueryContext>(
+    ctx: &mut Ctx,
+    client_id: ClientId,
+    path: QueryPath,
+    query_height: Option<Height>,
+    local_timeout_height: TimeoutHeight,
+    local_timeout_timestamp: Timestamp,
+) -> HandlerResult<QueryId, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let client_state = ctx.client_state(&client_id).map_err(Error::ics02_client)?;
+    if client_state.is_frozen() {
+        return Err(Error::frozen_client(client_id));
+    }
+
+    let query = CrossChainQuery {
+        id: QueryId::new(ctx.query_counter()?),
+        client_id,
+        path,
+        query_height,
+        local_timeout_height,
+        local_timeout_timestamp,
+    };
+
+    output.log(format!(
+        "success: sent query {} of {} with client {}",
+        query.id, query.path, query.client_id
+    ));
+    output.emit(IbcEvent::CrossChainQuery(SendQuery::new(
+        ctx.host_height(),
+        &query,
+    )));
+
+    let query_id = query.id.clone();
+    ctx.store_query(query)?;
+    ctx.increase_query_counter();
+
+    Ok(output.with_result(query_id))
+}
+
+/// Processes the answer of a relayer to a pending query. The value is accepted only if its proof
+/// verifies against the consensus state of the client of the query at the proof height. If the
+/// query timed out in the meantime, a timeout result is recorded instead.
+pub fn submit_query_result<Ctx: QueryContext>(
+    ctx: &mut Ctx,
+    msg: MsgSubmitCrossChainQueryResult,
+) -> HandlerResult<CrossChainQueryResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let query = ctx
+        .get_query(&msg.id)
+        .ok_or_else(|| Error::query_not_found(msg.id.clone()))?;
+
+    let result = if query.has_timed_out(ctx.host_height(), &ctx.host_timestamp()) {
+        output.log(format!("query {} timed out", query.id));
+        CrossChainQueryResult::timeout(query.id.clone())
+    } else {
+        verify_query_result(ctx, &query, &msg)?;
+
+        output.log(format!(
+            "success: query {} answered at height {}",
+            query.id, msg.proof_height
+        ));
+        CrossChainQueryResult {
+            id: query.id.clone(),
+            result: QueryResult::Success,
+            data: msg.data,
+            proof_height: Some(msg.proof_height),
+        }
+    };
+
+    ctx.delete_query(&query.id)?;
+    ctx.store_query_result(result.clone())?;
+
+    let event = QueryResultEvent {
+        query_id: result.id.clone(),
+        result: format!("{:?}", result.result),
+        proof_height: result.proof_height,
+    };
+    output.emit(IbcEvent::AppModule(ModuleEvent::from(event)));
+
+    Ok(output.with_result(result))
+}
+
+fn verify_query_result<Ctx: QueryContext>(
+    ctx: &Ctx,
+    query: &CrossChainQuery,
+    msg: &MsgSubmitCrossChainQueryResult,
+) -> Result<(), Error> {
+    if let Some(query_height) = query.query_height {
+        if query_height != msg.proof_height {
+            return Err(Error::query_height_mismatch(
+                query.id.clone(),
+                query_height,
+                msg.proof_height,
+            ));
+        }
+    }
+
+    let client_state = ctx
+        .client_state(&query.client_id)
+        .map_err(Error::ics02_client)?;
+    if client_state.is_frozen() {
+        return Err(Error::frozen_client(query.client_id.clone()));
+    }
+
+    let consensus_state = ctx
+        .consensus_state(&query.client_id, msg.proof_height)
+        .map_err(Error::ics02_client)?;
+
+    AnyClient::from_client_type(client_state.client_type())
+        .verify_key_value(
+            &client_state,
+            msg.proof_height,
+            &msg.proof,
+            consensus_state.root(),
+            query.path.merkle_path(),
+            msg.data.clone(),
+        )
+        .map_err(|e| Error::verification_failed(query.id.clone(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+    use crate::applications::interchain_query::context::QueryReader;
+    use crate::mock::context::MockContext;
+    use crate::test_utils::get_dummy_account_id;
+
+    fn context_with_query(
+        query_height: Option<Height>,
+        local_timeout_height: TimeoutHeight,
+    ) -> (MockContext, QueryId) {
+        let client_id = ClientId::default();
+        let mut ctx = MockContext::default().with_client(&client_id, Height::new(0, 10).unwrap());
+
+        let query_id = send_query(
+            &mut ctx,
+            client_id,
+            "bank/balances".parse().unwrap(),
+            query_height,
+            local_timeout_height,
+            Timestamp::none(),
+        )
+        .unwrap()
+        .result;
+
+        (ctx, query_id)
+    }
+
+    fn msg(query_id: QueryId, proof_height: Height) -> MsgSubmitCrossChainQueryResult {
+        MsgSubmitCrossChainQueryResult {
+            id: query_id,
+            data: b"value".to_vec(),
+            proof: vec![1].try_into().unwrap(),
+            proof_height,
+            signer: get_dummy_account_id(),
+        }
+    }
+
+    #[test]
+    fn send_query_stores_query() {
+        let (ctx, query_id) = context_with_query(None, TimeoutHeight::Never);
+
+        assert_eq!(query_id, QueryId::new(0));
+        assert_eq!(ctx.query_counter().unwrap(), 1);
+        assert!(ctx.get_query(&query_id).is_some());
+
+        let mut ctx = ctx;
+        let res = send_query(
+            &mut ctx,
+            "07-tendermint-42".parse().unwrap(),
+            "bank/balances".parse().unwrap(),
+            None,
+            TimeoutHeight::Never,
+            Timestamp::none(),
+        );
+        assert!(
+            res.is_err(),
+            "query with an unknown client must be rejected"
+        );
+    }
+
+    #[test]
+    fn submit_query_result_success() {
+        let (mut ctx, query_id) = context_with_query(None, TimeoutHeight::Never);
+
+        let output =
+            submit_query_result(&mut ctx, msg(query_id.clone(), Height::new(0, 10).unwrap()))
+                .unwrap();
+        assert_eq!(output.result.result, QueryResult::Success);
+        assert_eq!(output.result.data, b"value".to_vec());
+        assert!(ctx.get_query(&query_id).is_none());
+        assert_eq!(ctx.get_query_result(&query_id), Some(output.result));
+
+        let res = submit_query_result(&mut ctx, msg(query_id, Height::new(0, 10).unwrap()));
+        assert!(res.is_err(), "answered query must not be answered twice");
+    }
+
+    #[test]
+    fn submit_query_result_checks_height() {
+        let (mut ctx, query_id) =
+            context_with_query(Some(Height::new(0, 5).unwrap()), TimeoutHeight::Never);
+
+        let res = submit_query_result(&mut ctx, msg(query_id.clone(), Height::new(0, 10).unwrap()));
+        assert!(res.is_err(), "proof at another height than requested");
+
+        let res = submit_query_result(&mut ctx, msg(query_id, Height::new(0, 5).unwrap()));
+        assert!(res.is_err(), "no consensus state at the requested height");
+    }
+
+    #[test]
+    fn submit_query_result_timeout() {
+        let (mut ctx, query_id) =
+            context_with_query(None, TimeoutHeight::At(Height::new(0, 1).unwrap()));
+
+        let output =
+            submit_query_result(&mut ctx, msg(query_id.clone(), Height::new(0, 10).unwrap()))
+                .unwrap();
+        assert_eq!(
+            output.result,
+            CrossChainQueryResult::timeout(query_id.clone())
+        );
+        assert!(ctx.get_query(&query_id).is_none());
+    }
+}
@@ -0,0 +1,13 @@
+//! ICS31 cross-chain queries. A module of the querying chain asks for the value stored at a path
+//! of the state of another chain, which one of its light clients tracks. Relayers answer the query
+//! with the value and a proof of it, which is verified against the consensus state of the client,
+//! so that the module does not have to trust the relayer that answered.
+pub mod context;
+pub mod error;
+pub mod events;
+pub mod handler;
+pub mod msgs;
+pub mod query;
+
+/// Module identifier for the cross-chain queries application.
+pub const MODULE_ID_STR: &str = "interchainquery";
@@ -0,0 +1,133 @@
+//! Message used by relayers to answer a pending cross-chain query.
+
+use crate::prelude::*;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::applications::interchain_query::v1::MsgSubmitCrossChainQueryResult as RawMsgSubmitCrossChainQueryResult;
+use tendermint_proto::Protobuf;
+
+use crate::applications::interchain_query::error::Error;
+use crate::applications::interchain_query::query::QueryId;
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+use crate::Height;
+
+pub const TYPE_URL: &str = "/ibc.applications.interchain_query.v1.MsgSubmitCrossChainQueryResult";
+
+/// Message answering the cross-chain query `id` with the value stored at its path on the queried
+/// chain, proven at `proof_height`. An empty `data` asserts that the path holds no value, in
+/// which case `proof` must be a proof of absence.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgSubmitCrossChainQueryResult {
+    pub id: QueryId,
+    pub data: Vec<u8>,
+    pub proof: CommitmentProofBytes,
+    pub proof_height: Height,
+    pub signer: Signer,
+}
+
+impl Msg for MsgSubmitCrossChainQueryResult {
+    type ValidationError = Error;
+    type Raw = RawMsgSubmitCrossChainQueryResult;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl TryFrom<RawMsgSubmitCrossChainQueryResult> for MsgSubmitCrossChainQueryResult {
+    type Error = Error;
+
+    fn try_from(raw_msg: RawMsgSubmitCrossChainQueryResult) -> Result<Self, Self::Error> {
+        Ok(MsgSubmitCrossChainQueryResult {
+            id: raw_msg.id.parse()?,
+            data: raw_msg.data,
+            proof: raw_msg.proof.try_into().map_err(Error::invalid_proof)?,
+            proof_height: raw_msg
+                .proof_height
+                .ok_or_else(Error::missing_proof_height)?
+                .try_into()
+                .map_err(Error::invalid_height)?,
+            signer: raw_msg.signer.parse().map_err(Error::signer)?,
+        })
+    }
+}
+
+impl From<MsgSubmitCrossChainQueryResult> for RawMsgSubmitCrossChainQueryResult {
+    fn from(domain_msg: MsgSubmitCrossChainQueryResult) -> Self {
+        RawMsgSubmitCrossChainQueryResult {
+            id: domain_msg.id.to_string(),
+            data: domain_msg.data,
+            proof: domain_msg.proof.into(),
+            proof_height: Some(domain_msg.proof_height.into()),
+            signer: domain_msg.signer.to_string(),
+        }
+    }
+}
+
+impl Protobuf<RawMsgSubmitCrossChainQueryResult> for MsgSubmitCrossChainQueryResult {}
+
+impl TryFrom<Any> for MsgSubmitCrossChainQueryResult {
+    type Error = Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            TYPE_URL => MsgSubmitCrossChainQueryResult::decode_vec(&raw.value)
+                .map_err(Error::decode_raw_msg),
+            _ => Err(Error::unknown_msg_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<MsgSubmitCrossChainQueryResult> for Any {
+    fn from(msg: MsgSubmitCrossChainQueryResult) -> Self {
+        Self {
+            type_url: TYPE_URL.to_string(),
+            value: msg
+                .encode_vec()
+                .expect("encoding to `Any` from `MsgSubmitCrossChainQueryResult`"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+    use crate::test_utils::get_dummy_bech32_account;
+
+    #[test]
+    fn to_and_from_raw() {
+        let msg = MsgSubmitCrossChainQueryResult {
+            id: QueryId::new(1),
+            data: b"value".to_vec(),
+            proof: vec![1, 2, 3].try_into().unwrap(),
+            proof_height: Height::new(0, 10).unwrap(),
+            signer: get_dummy_bech32_account().parse().unwrap(),
+        };
+
+        let raw = RawMsgSubmitCrossChainQueryResult::from(msg.clone());
+        assert_eq!(
+            MsgSubmitCrossChainQueryResult::try_from(raw.clone()).unwrap(),
+            msg
+        );
+
+        let missing_proof = RawMsgSubmitCrossChainQueryResult {
+            proof: vec![],
+            ..raw.clone()
+        };
+        assert!(MsgSubmitCrossChainQueryResult::try_from(missing_proof).is_err());
+
+        let missing_height = RawMsgSubmitCrossChainQueryResult {
+            proof_height: None,
+            ..raw
+        };
+        assert!(MsgSubmitCrossChainQueryResult::try_from(missing_height).is_err());
+    }
+}
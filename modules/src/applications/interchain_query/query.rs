@@ -0,0 +1,297 @@
+use core::fmt::{Display, Error as FmtError, Formatter};
+use core::str::FromStr;
+
+use ibc_proto::ibc::applications::interchain_query::v1::{
+    CrossChainQuery as RawCrossChainQuery, CrossChainQueryResult as RawCrossChainQueryResult,
+    QueryResult as RawQueryResult,
+};
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+use ibc_proto::ibc::core::commitment::v1::MerklePath;
+use serde::{Deserialize, Serialize};
+use tendermint_proto::Protobuf;
+
+use crate::applications::interchain_query::error::Error;
+use crate::core::ics04_channel::timeout::TimeoutHeight;
+use crate::core::ics24_host::identifier::ClientId;
+use crate::prelude::*;
+use crate::timestamp::{Expiry, Timestamp};
+use crate::Height;
+
+/// The identifier of a cross-chain query, unique on the querying chain.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct QueryId(String);
+
+impl QueryId {
+    const PREFIX: &'static str = "query";
+
+    /// Builds the identifier of the query sent with the given value of the query counter.
+    pub fn new(counter: u64) -> Self {
+        Self(format!("{}-{}", Self::PREFIX, counter))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for QueryId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for QueryId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            Err(Error::invalid_query_id())
+        } else {
+            Ok(Self(s.to_string()))
+        }
+    }
+}
+
+/// The path of a queried value, made of the name of the store holding the value and of the key
+/// of the value within that store, e.g. `bank/balances...`. It is written `{store}/{key}`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct QueryPath {
+    pub store: String,
+    pub key: String,
+}
+
+impl QueryPath {
+    /// The Merkle path of the queried value from the root of the app hash of the queried chain.
+    pub fn merkle_path(&self) -> MerklePath {
+        MerklePath {
+            key_path: vec![self.store.clone(), self.key.clone()],
+        }
+    }
+}
+
+impl Display for QueryPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(f, "{}/{}", self.store, self.key)
+    }
+}
+
+impl FromStr for QueryPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('/') {
+            Some((store, key)) if !store.is_empty() && !key.is_empty() => Ok(Self {
+                store: store.to_string(),
+                key: key.to_string(),
+            }),
+            _ => Err(Error::invalid_query_path(s.to_string())),
+        }
+    }
+}
+
+/// A query pending on the querying chain until a relayer answers it or it times out.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrossChainQuery {
+    pub id: QueryId,
+    /// The client tracking the queried chain, against whose consensus states the results are
+    /// verified.
+    pub client_id: ClientId,
+    pub path: QueryPath,
+    /// The height of the queried chain at which the value must be proven, if any.
+    pub query_height: Option<Height>,
+    pub local_timeout_height: TimeoutHeight,
+    pub local_timeout_timestamp: Timestamp,
+}
+
+impl CrossChainQuery {
+    /// Checks whether the query timed out given the current height and timestamp of the querying
+    /// chain.
+    pub fn has_timed_out(&self, host_height: Height, host_timestamp: &Timestamp) -> bool {
+        let height_timed_out = self.local_timeout_height.has_expired(host_height);
+
+        let timestamp_timed_out = self.local_timeout_timestamp != Timestamp::none()
+            && host_timestamp.check_expiry(&self.local_timeout_timestamp) == Expiry::Expired;
+
+        height_timed_out || timestamp_timed_out
+    }
+}
+
+impl Protobuf<RawCrossChainQuery> for CrossChainQuery {}
+
+impl TryFrom<RawCrossChainQuery> for CrossChainQuery {
+    type Error = Error;
+
+    fn try_from(raw: RawCrossChainQuery) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: raw.id.parse()?,
+            client_id: raw
+                .client_id
+                .parse()
+                .map_err(|e| Error::invalid_client_id(raw.client_id.clone(), e))?,
+            path: raw.path.parse()?,
+            query_height: optional_height(raw.query_height)?,
+            local_timeout_height: raw
+                .local_timeout_height
+                .try_into()
+                .map_err(Error::invalid_height)?,
+            local_timeout_timestamp: Timestamp::from_nanoseconds(raw.local_timeout_timestamp)
+                .map_err(|_| Error::invalid_timeout_timestamp(raw.local_timeout_timestamp))?,
+        })
+    }
+}
+
+impl From<CrossChainQuery> for RawCrossChainQuery {
+    fn from(query: CrossChainQuery) -> Self {
+        Self {
+            id: query.id.to_string(),
+            client_id: query.client_id.to_string(),
+            path: query.path.to_string(),
+            query_height: query.query_height.map(Into::into),
+            local_timeout_height: query.local_timeout_height.into(),
+            local_timeout_timestamp: query.local_timeout_timestamp.nanoseconds(),
+        }
+    }
+}
+
+/// The outcome of a cross-chain query.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QueryResult {
+    /// The value was proven against the consensus state of the client.
+    Success,
+    /// The query timed out before a relayer answered it.
+    Timeout,
+}
+
+impl TryFrom<i32> for QueryResult {
+    type Error = Error;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match RawQueryResult::from_i32(value) {
+            Some(RawQueryResult::Success) => Ok(Self::Success),
+            Some(RawQueryResult::Timeout) => Ok(Self::Timeout),
+            _ => Err(Error::invalid_query_result(value)),
+        }
+    }
+}
+
+impl From<QueryResult> for RawQueryResult {
+    fn from(result: QueryResult) -> Self {
+        match result {
+            QueryResult::Success => Self::Success,
+            QueryResult::Timeout => Self::Timeout,
+        }
+    }
+}
+
+/// The result of a cross-chain query, kept by the querying chain for the module that sent it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrossChainQueryResult {
+    pub id: QueryId,
+    pub result: QueryResult,
+    /// The queried value, empty if the path holds no value or if the query timed out.
+    pub data: Vec<u8>,
+    /// The height of the queried chain at which the value was proven, if it was.
+    pub proof_height: Option<Height>,
+}
+
+impl CrossChainQueryResult {
+    pub fn timeout(id: QueryId) -> Self {
+        Self {
+            id,
+            result: QueryResult::Timeout,
+            data: Vec::new(),
+            proof_height: None,
+        }
+    }
+}
+
+impl Protobuf<RawCrossChainQueryResult> for CrossChainQueryResult {}
+
+impl TryFrom<RawCrossChainQueryResult> for CrossChainQueryResult {
+    type Error = Error;
+
+    fn try_from(raw: RawCrossChainQueryResult) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: raw.id.parse()?,
+            result: raw.result.try_into()?,
+            data: raw.data,
+            proof_height: optional_height(raw.proof_height)?,
+        })
+    }
+}
+
+impl From<CrossChainQueryResult> for RawCrossChainQueryResult {
+    fn from(result: CrossChainQueryResult) -> Self {
+        Self {
+            id: result.id.to_string(),
+            result: RawQueryResult::from(result.result) as i32,
+            data: result.data,
+            proof_height: result.proof_height.map(Into::into),
+        }
+    }
+}
+
+/// Maps both a missing height and the zero height to `None`.
+fn optional_height(raw: Option<RawHeight>) -> Result<Option<Height>, Error> {
+    match raw {
+        Some(height) if height.revision_number != 0 || height.revision_height != 0 => {
+            height.try_into().map(Some).map_err(Error::invalid_height)
+        }
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+
+    #[test]
+    fn parse_query_path() {
+        let path: QueryPath = "bank/balances/cosmos1abc".parse().unwrap();
+        assert_eq!(path.store, "bank");
+        assert_eq!(path.key, "balances/cosmos1abc");
+        assert_eq!(path.to_string(), "bank/balances/cosmos1abc");
+        assert_eq!(
+            path.merkle_path().key_path,
+            vec!["bank", "balances/cosmos1abc"]
+        );
+
+        for invalid in ["", "bank", "bank/", "/balances"] {
+            assert!(invalid.parse::<QueryPath>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn query_to_and_from_raw() {
+        let query = CrossChainQuery {
+            id: QueryId::new(7),
+            client_id: ClientId::default(),
+            path: "bank/balances".parse().unwrap(),
+            query_height: None,
+            local_timeout_height: TimeoutHeight::At(Height::new(0, 10).unwrap()),
+            local_timeout_timestamp: Timestamp::none(),
+        };
+
+        let raw = RawCrossChainQuery::from(query.clone());
+        assert_eq!(raw.id, "query-7");
+        assert_eq!(CrossChainQuery::try_from(raw).unwrap(), query);
+    }
+
+    #[test]
+    fn query_timeout() {
+        let query = CrossChainQuery {
+            id: QueryId::new(0),
+            client_id: ClientId::default(),
+            path: "bank/balances".parse().unwrap(),
+            query_height: None,
+            local_timeout_height: TimeoutHeight::At(Height::new(0, 10).unwrap()),
+            local_timeout_timestamp: Timestamp::none(),
+        };
+
+        let now = Timestamp::now();
+        assert!(!query.has_timed_out(Height::new(0, 10).unwrap(), &now));
+        assert!(query.has_timed_out(Height::new(0, 11).unwrap(), &now));
+    }
+}
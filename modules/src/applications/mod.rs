@@ -1,3 +1,7 @@
 //! Various packet encoding semantics which underpin the various types of transactions.
 
+pub mod fee;
+pub mod interchain_query;
+pub mod packet_forward;
+pub mod rate_limit;
 pub mod transfer;
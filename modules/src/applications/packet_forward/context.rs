@@ -0,0 +1,415 @@
+use sha2::{Digest, Sha256};
+
+use super::error::Error;
+use crate::applications::packet_forward::metadata::PacketMetadata;
+use crate::applications::packet_forward::packet::InFlightPacket;
+use crate::applications::packet_forward::relay::on_ack_packet::process_ack_packet;
+use crate::applications::packet_forward::relay::on_recv_packet::process_recv_packet;
+use crate::applications::packet_forward::relay::on_timeout_packet::process_timeout_packet;
+use crate::applications::transfer::acknowledgement::Acknowledgement;
+use crate::applications::transfer::context::{self as ics20, Ics20Context};
+use crate::applications::transfer::packet::PacketData;
+use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement as GenericAcknowledgement;
+use crate::core::ics04_channel::packet::{Packet, Sequence};
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::core::ics26_routing::context::{ModuleOutputBuilder, OnRecvPacketAck};
+use crate::events::IbcEvent;
+use crate::prelude::*;
+use crate::signer::Signer;
+
+pub trait PacketForwardReader {
+    /// Returns the account that holds the tokens received on the given port and channel on
+    /// behalf of `original_sender`, before they are forwarded to the next hop.
+    fn get_intermediate_account(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        original_sender: &Signer,
+    ) -> Result<Signer, Error>;
+
+    /// Returns the in-flight packet that was forwarded as the packet identified by `key`, if any.
+    fn get_in_flight_packet(&self, key: &(PortId, ChannelId, Sequence)) -> Option<InFlightPacket>;
+}
+
+pub trait PacketForwardKeeper {
+    /// Records that the packet identified by `key` was sent on behalf of `packet`.
+    fn store_in_flight_packet(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+        packet: InFlightPacket,
+    ) -> Result<(), Error>;
+
+    fn delete_in_flight_packet(&mut self, key: (PortId, ChannelId, Sequence)) -> Result<(), Error>;
+
+    /// Hands over the IBC events produced while forwarding a packet or writing a held-back
+    /// acknowledgement. These happen outside of the message handlers' output (e.g. from within
+    /// the `WriteFn` returned by `on_recv_packet`), so hosts must include the events in their
+    /// block results themselves for relayers to pick them up.
+    fn emit_ibc_events(&mut self, events: Vec<IbcEvent>);
+}
+
+/// Captures all the dependencies which the packet forwarding middleware requires on top of the
+/// ones of the ICS20 application it wraps.
+pub trait PacketForwardContext: Ics20Context + PacketForwardReader + PacketForwardKeeper {}
+
+impl<T> PacketForwardContext for T where T: Ics20Context + PacketForwardReader + PacketForwardKeeper {}
+
+/// Derives the intermediate account of a forwarded transfer the way the Cosmos SDK packet
+/// forward middleware does, i.e. `address.Hash("forward", "{channel_id}/{original_sender}")`.
+pub fn cosmos_intermediate_address(channel_id: &ChannelId, original_sender: &Signer) -> Vec<u8> {
+    let contents = format!("{}/{}", channel_id, original_sender);
+
+    let mut hasher = Sha256::new();
+    hasher.update(Sha256::digest(b"forward"));
+    hasher.update(contents.as_bytes());
+    hasher.finalize().to_vec()
+}
+
+/// Handles a received packet. Packets whose memo carries no forwarding instructions are passed
+/// through to the ICS20 application untouched. Forwarded packets are credited to the intermediate
+/// account and sent on to the next hop; their acknowledgement is written asynchronously, once the
+/// next hop resolves.
+pub fn on_recv_packet<Ctx: 'static + PacketForwardContext>(
+    ctx: &Ctx,
+    output: &mut ModuleOutputBuilder,
+    packet: &Packet,
+    relayer: &Signer,
+) -> OnRecvPacketAck {
+    let data = match serde_json::from_slice::<PacketData>(&packet.data) {
+        Ok(data) => data,
+        Err(_) => return ics20::on_recv_packet(ctx, output, packet, relayer),
+    };
+
+    let metadata = match PacketMetadata::from_memo(&data.memo) {
+        Ok(Some(metadata)) => metadata,
+        Ok(None) => return ics20::on_recv_packet(ctx, output, packet, relayer),
+        Err(e) => return OnRecvPacketAck::Failed(Box::new(Acknowledgement::from_error(e))),
+    };
+
+    match process_recv_packet(ctx, output, packet, data, metadata.forward) {
+        Ok(write_fn) => OnRecvPacketAck::Nil(write_fn),
+        Err(e) => OnRecvPacketAck::Failed(Box::new(Acknowledgement::from_error(e))),
+    }
+}
+
+pub fn on_acknowledgement_packet<Ctx: PacketForwardContext>(
+    ctx: &mut Ctx,
+    output: &mut ModuleOutputBuilder,
+    packet: &Packet,
+    acknowledgement: &GenericAcknowledgement,
+    relayer: &Signer,
+) -> Result<(), Error> {
+    ics20::on_acknowledgement_packet(ctx, output, packet, acknowledgement, relayer)
+        .map_err(Error::ics20)?;
+
+    process_ack_packet(ctx, packet, acknowledgement)
+}
+
+pub fn on_timeout_packet<Ctx: PacketForwardContext>(
+    ctx: &mut Ctx,
+    output: &mut ModuleOutputBuilder,
+    packet: &Packet,
+    relayer: &Signer,
+) -> Result<(), Error> {
+    ics20::on_timeout_packet(ctx, output, packet, relayer).map_err(Error::ics20)?;
+
+    process_timeout_packet(ctx, packet)
+}
+
+#[cfg(test)]
+mod tests {
+    use subtle_encoding::bech32;
+
+    use super::{
+        cosmos_intermediate_address, on_acknowledgement_packet, on_recv_packet, on_timeout_packet,
+        PacketForwardReader,
+    };
+    use crate::applications::transfer::acknowledgement::Acknowledgement;
+    use crate::applications::transfer::context::Ics20Reader;
+    use crate::applications::transfer::packet::PacketData;
+    use crate::applications::transfer::{Amount, PrefixedCoin};
+    use crate::core::ics03_connection::connection::{
+        ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+    };
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement as GenericAcknowledgement;
+    use crate::core::ics04_channel::packet::Packet;
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::core::ics26_routing::context::OnRecvPacketAck;
+    use crate::events::IbcEvent;
+    use crate::handler::HandlerOutputBuilder;
+    use crate::mock::context::MockContext;
+    use crate::prelude::*;
+    use crate::signer::Signer;
+    use crate::test_utils::{get_dummy_bech32_account, DummyTransferModule};
+    use crate::timestamp::ZERO_DURATION;
+    use crate::Height;
+
+    const VOUCHER_DENOM: &str = "transfer/channel-0/uatom";
+
+    /// A chain receiving packets from the previous hop on `channel-0`, and forwarding them to the
+    /// next hop on `channel-1`. Without a client of the next hop, forwarding a packet fails.
+    fn forwarding_chain(with_next_hop_client: bool) -> (MockContext, DummyTransferModule) {
+        let client_id = ClientId::default();
+        let connection_id = ConnectionId::default();
+        let channel_end = |counterparty_channel_id| {
+            ChannelEnd::new(
+                State::Open,
+                Order::Unordered,
+                Counterparty::new(
+                    PortId::transfer(),
+                    Some(ChannelId::new(counterparty_channel_id)),
+                ),
+                vec![connection_id.clone()],
+                Version::ics20(),
+            )
+        };
+
+        let mut mock = MockContext::default()
+            .with_channel(PortId::transfer(), ChannelId::new(0), channel_end(7))
+            .with_channel(PortId::transfer(), ChannelId::new(1), channel_end(5))
+            .with_send_sequence(PortId::transfer(), ChannelId::new(1), 1.into());
+        if with_next_hop_client {
+            let connection_end = ConnectionEnd::new(
+                ConnectionState::Open,
+                client_id.clone(),
+                ConnectionCounterparty::new(
+                    client_id.clone(),
+                    Some(connection_id.clone()),
+                    Default::default(),
+                ),
+                get_compatible_versions(),
+                ZERO_DURATION,
+            );
+            mock = mock
+                .with_client(&client_id, Height::new(0, 10).unwrap())
+                .with_connection(connection_id, connection_end);
+        }
+
+        let ctx = DummyTransferModule::new(mock.ibc_store_share());
+        (mock, ctx)
+    }
+
+    fn account() -> Signer {
+        get_dummy_bech32_account().parse().unwrap()
+    }
+
+    /// A packet of the previous hop sending 100uatom to be forwarded on `channel-1`.
+    fn received_packet(retries: u8) -> Packet {
+        let memo = format!(
+            r#"{{"forward":{{"receiver":"{}","port":"transfer","channel":"channel-1","retries":{}}}}}"#,
+            account(),
+            retries
+        );
+        let data = PacketData {
+            token: PrefixedCoin {
+                denom: "uatom".parse().unwrap(),
+                amount: 100u64.into(),
+            },
+            sender: account(),
+            receiver: account(),
+            memo: memo.into(),
+        };
+        Packet {
+            sequence: 1.into(),
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(7),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(0),
+            data: serde_json::to_vec(&data).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    fn recv(ctx: &mut DummyTransferModule, packet: &Packet) {
+        match on_recv_packet(ctx, &mut HandlerOutputBuilder::new(), packet, &account()) {
+            OnRecvPacketAck::Nil(write_fn) => write_fn(ctx).unwrap(),
+            _ => panic!("the packet was not accepted for forwarding"),
+        }
+    }
+
+    fn ack(ctx: &mut DummyTransferModule, packet: &Packet, ack: Acknowledgement) {
+        let ack = GenericAcknowledgement::from(serde_json::to_vec(&ack).unwrap());
+        on_acknowledgement_packet(
+            ctx,
+            &mut HandlerOutputBuilder::new(),
+            packet,
+            &ack,
+            &account(),
+        )
+        .unwrap();
+    }
+
+    fn timeout(ctx: &mut DummyTransferModule, packet: &Packet) {
+        on_timeout_packet(ctx, &mut HandlerOutputBuilder::new(), packet, &account()).unwrap();
+    }
+
+    fn balance(mock: &MockContext, account: &Signer) -> Amount {
+        mock.ibc_store_share()
+            .lock()
+            .unwrap()
+            .balances
+            .get(&(account.clone(), VOUCHER_DENOM.parse().unwrap()))
+            .copied()
+            .unwrap_or_else(|| Amount::from(0))
+    }
+
+    fn intermediate(ctx: &DummyTransferModule) -> Signer {
+        ctx.get_intermediate_account(&PortId::transfer(), &ChannelId::new(0), &account())
+            .unwrap()
+    }
+
+    fn next_hop_escrow(ctx: &DummyTransferModule) -> Signer {
+        ctx.get_channel_escrow_address(&PortId::transfer(), &ChannelId::new(1))
+            .unwrap()
+    }
+
+    /// The packets sent to the next hop, in order.
+    fn forwarded_packets(mock: &MockContext) -> Vec<Packet> {
+        mock.ibc_store_share()
+            .lock()
+            .unwrap()
+            .app_events
+            .iter()
+            .filter_map(|event| match event {
+                IbcEvent::SendPacket(send_packet) => Some(send_packet.packet.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The acknowledgements written for the packets of the previous hop.
+    fn written_acks(mock: &MockContext) -> Vec<Acknowledgement> {
+        mock.ibc_store_share()
+            .lock()
+            .unwrap()
+            .app_events
+            .iter()
+            .filter_map(|event| match event {
+                IbcEvent::WriteAcknowledgement(write_ack) => {
+                    assert_eq!(write_ack.packet.destination_channel, ChannelId::new(0));
+                    Some(serde_json::from_slice(&write_ack.ack).unwrap())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn forward_is_acknowledged_once_next_hop_acknowledges() {
+        let (mock, mut ctx) = forwarding_chain(true);
+
+        recv(&mut ctx, &received_packet(0));
+
+        let forwarded = forwarded_packets(&mock);
+        assert_eq!(forwarded.len(), 1);
+        assert_eq!(forwarded[0].source_channel, ChannelId::new(1));
+        assert_eq!(balance(&mock, &intermediate(&ctx)), 0u64.into());
+        assert_eq!(balance(&mock, &next_hop_escrow(&ctx)), 100u64.into());
+        // the acknowledgement is held back until the next hop resolves
+        assert!(written_acks(&mock).is_empty());
+
+        ack(&mut ctx, &forwarded[0], Acknowledgement::success());
+
+        assert_eq!(written_acks(&mock), vec![Acknowledgement::success()]);
+        assert!(mock
+            .ibc_store_share()
+            .lock()
+            .unwrap()
+            .in_flight_packets
+            .is_empty());
+        assert_eq!(balance(&mock, &next_hop_escrow(&ctx)), 100u64.into());
+    }
+
+    #[test]
+    fn error_ack_of_next_hop_is_relayed_back() {
+        let (mock, mut ctx) = forwarding_chain(true);
+
+        recv(&mut ctx, &received_packet(0));
+        let forwarded = forwarded_packets(&mock);
+        ack(
+            &mut ctx,
+            &forwarded[0],
+            Acknowledgement::Error("next hop failure".to_string()),
+        );
+
+        let acks = written_acks(&mock);
+        assert_eq!(acks.len(), 1);
+        assert!(matches!(acks[0], Acknowledgement::Error(_)));
+        // the vouchers minted upon receipt are burnt for the previous hop to refund the tokens
+        assert_eq!(balance(&mock, &intermediate(&ctx)), 0u64.into());
+        assert_eq!(balance(&mock, &next_hop_escrow(&ctx)), 0u64.into());
+    }
+
+    #[test]
+    fn timed_out_forward_is_retried_then_refunded() {
+        let (mock, mut ctx) = forwarding_chain(true);
+
+        recv(&mut ctx, &received_packet(1));
+        timeout(&mut ctx, &forwarded_packets(&mock)[0]);
+
+        let forwarded = forwarded_packets(&mock);
+        assert_eq!(forwarded.len(), 2);
+        assert_eq!(forwarded[1].sequence, 2.into());
+        assert!(written_acks(&mock).is_empty());
+        assert_eq!(balance(&mock, &next_hop_escrow(&ctx)), 100u64.into());
+
+        // no retries remain
+        timeout(&mut ctx, &forwarded[1]);
+
+        assert_eq!(forwarded_packets(&mock).len(), 2);
+        let acks = written_acks(&mock);
+        assert_eq!(acks.len(), 1);
+        assert!(matches!(acks[0], Acknowledgement::Error(_)));
+        assert_eq!(balance(&mock, &intermediate(&ctx)), 0u64.into());
+        assert_eq!(balance(&mock, &next_hop_escrow(&ctx)), 0u64.into());
+    }
+
+    #[test]
+    fn failed_forward_writes_error_ack() {
+        let (mock, mut ctx) = forwarding_chain(false);
+
+        // the transaction does not fail
+        recv(&mut ctx, &received_packet(0));
+
+        assert!(forwarded_packets(&mock).is_empty());
+        let acks = written_acks(&mock);
+        assert_eq!(acks.len(), 1);
+        assert!(matches!(acks[0], Acknowledgement::Error(_)));
+        assert_eq!(balance(&mock, &intermediate(&ctx)), 0u64.into());
+        assert!(mock
+            .ibc_store_share()
+            .lock()
+            .unwrap()
+            .in_flight_packets
+            .is_empty());
+    }
+
+    #[test]
+    fn intermediate_address_is_per_channel_and_sender() {
+        let sender = "cosmos1wxeyh7zgn4tctjzs0vtqpc6p5cxq5t2muzl7ng"
+            .parse()
+            .unwrap();
+        let other_sender = "cosmos1x54ltnyg88k0ejmk8ytwrhd3ltm84xehrnlslf"
+            .parse()
+            .unwrap();
+
+        let addr = cosmos_intermediate_address(&ChannelId::new(0), &sender);
+        assert_eq!(addr.len(), 32);
+        assert_eq!(
+            addr,
+            cosmos_intermediate_address(&ChannelId::new(0), &sender)
+        );
+        assert_ne!(
+            addr,
+            cosmos_intermediate_address(&ChannelId::new(1), &sender)
+        );
+        assert_ne!(
+            addr,
+            cosmos_intermediate_address(&ChannelId::new(0), &other_sender)
+        );
+        assert!(bech32::encode("cosmos", addr).starts_with("cosmos1"));
+    }
+}
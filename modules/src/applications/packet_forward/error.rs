@@ -0,0 +1,54 @@
+use flex_error::{define_error, DisplayOnly};
+
+use crate::applications::transfer::error::Error as Ics20Error;
+use crate::core::ics04_channel::error as channel_error;
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics24_host::error::ValidationError;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::prelude::*;
+use crate::signer::SignerError;
+
+define_error! {
+    #[derive(Debug, PartialEq, Eq)]
+    Error {
+        Ics20
+            [ Ics20Error ]
+            | _ | { "ICS20 transfer error" },
+
+        Ics04Channel
+            [ channel_error::Error ]
+            | _ | { "ICS04 channel error" },
+
+        MetadataDeserialization
+            [ DisplayOnly<serde_json::Error> ]
+            | _ | { "failed to deserialize forwarding metadata from packet memo" },
+
+        InvalidReceiver
+            [ SignerError ]
+            | _ | { "invalid forward receiver" },
+
+        InvalidPortId
+            { port_id: String }
+            [ ValidationError ]
+            | e | { format_args!("invalid forward port identifier {0}", e.port_id) },
+
+        InvalidChannelId
+            { channel_id: String }
+            [ ValidationError ]
+            | e | { format_args!("invalid forward channel identifier {0}", e.channel_id) },
+
+        InvalidForwardTimeout
+            | _ | { "forward timeout overflows the host timestamp" },
+
+        ForwardTimedOut
+            { port_id: PortId, channel_id: ChannelId, sequence: Sequence }
+            | e | {
+                format_args!("forwarded packet {0} on port {1} and channel {2} timed out",
+                    e.sequence, e.port_id, e.channel_id)
+            },
+
+        Store
+            { reason: String }
+            | e | { format_args!("failed to access the forwarding store: {0}", e.reason) },
+    }
+}
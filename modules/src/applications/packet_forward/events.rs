@@ -0,0 +1,42 @@
+use crate::applications::packet_forward::MODULE_ID_STR;
+use crate::applications::transfer::{Amount, PrefixedDenom};
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::events::ModuleEvent;
+use crate::prelude::*;
+use crate::signer::Signer;
+
+const EVENT_TYPE_FORWARD: &str = "packet_forward";
+
+pub struct ForwardEvent {
+    pub intermediate: Signer,
+    pub receiver: Signer,
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub denom: PrefixedDenom,
+    pub amount: Amount,
+}
+
+impl From<ForwardEvent> for ModuleEvent {
+    fn from(ev: ForwardEvent) -> Self {
+        let ForwardEvent {
+            intermediate,
+            receiver,
+            port_id,
+            channel_id,
+            denom,
+            amount,
+        } = ev;
+        Self {
+            kind: EVENT_TYPE_FORWARD.to_string(),
+            module_name: MODULE_ID_STR.parse().expect("invalid ModuleId"),
+            attributes: vec![
+                ("intermediate", intermediate).into(),
+                ("receiver", receiver).into(),
+                ("port_id", port_id).into(),
+                ("channel_id", channel_id).into(),
+                ("denom", denom).into(),
+                ("amount", amount).into(),
+            ],
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! Forwarding instructions carried in the memo of an ICS20 packet, e.g.
+//!
+//! ```json
+//! {
+//!   "forward": {
+//!     "receiver": "cosmos1...",
+//!     "port": "transfer",
+//!     "channel": "channel-1",
+//!     "timeout": 600000000000,
+//!     "retries": 2,
+//!     "next": { "forward": { ... } }
+//!   }
+//! }
+//! ```
+//!
+//! `timeout` is expressed in nanoseconds. `next`, if present, becomes the memo of the forwarded
+//! packet so that it is forwarded again by the following hop.
+use core::str::FromStr;
+use core::time::Duration;
+
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Serialize};
+
+use super::error::Error;
+use super::{DEFAULT_FORWARD_RETRIES, DEFAULT_FORWARD_TIMEOUT};
+use crate::applications::transfer::Memo;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::prelude::*;
+use crate::signer::Signer;
+
+/// The forwarding instructions found in a packet memo.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PacketMetadata {
+    pub forward: ForwardMetadata,
+}
+
+impl PacketMetadata {
+    /// Extracts the forwarding instructions from a packet memo.
+    ///
+    /// Returns `Ok(None)` if the memo is not a JSON object with a `forward` key, in which case the
+    /// packet is not meant to be forwarded and is handled by the transfer application as usual.
+    pub fn from_memo(memo: &Memo) -> Result<Option<Self>, Error> {
+        #[derive(Deserialize)]
+        struct Probe {
+            forward: Option<IgnoredAny>,
+        }
+
+        match serde_json::from_str::<Probe>(memo.as_ref()) {
+            Ok(Probe { forward: Some(_) }) => serde_json::from_str(memo.as_ref())
+                .map(Some)
+                .map_err(Error::metadata_deserialization),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawForwardMetadata", into = "RawForwardMetadata")]
+pub struct ForwardMetadata {
+    /// The recipient of the tokens on the next hop.
+    pub receiver: Signer,
+    /// The port on which the packet is forwarded.
+    pub port: PortId,
+    /// The channel on which the packet is forwarded.
+    pub channel: ChannelId,
+    /// Timeout of the forwarded packet, relative to the host timestamp at the time it is sent.
+    pub timeout: Duration,
+    /// Number of times the forwarded packet is re-sent after timing out before giving up.
+    pub retries: u8,
+    /// Forwarding instructions for the next hop, if any.
+    pub next: Option<Box<PacketMetadata>>,
+}
+
+impl ForwardMetadata {
+    /// Returns the memo to attach to the forwarded packet.
+    pub fn next_memo(&self) -> Memo {
+        match &self.next {
+            Some(next) => serde_json::to_string(next)
+                .expect("PacketMetadata's infallible Serialize impl failed")
+                .into(),
+            None => Memo::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawForwardMetadata {
+    receiver: String,
+    port: String,
+    channel: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timeout: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    retries: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    next: Option<Box<PacketMetadata>>,
+}
+
+impl TryFrom<RawForwardMetadata> for ForwardMetadata {
+    type Error = Error;
+
+    fn try_from(raw: RawForwardMetadata) -> Result<Self, Self::Error> {
+        Ok(Self {
+            receiver: raw.receiver.parse().map_err(Error::invalid_receiver)?,
+            port: PortId::from_str(&raw.port)
+                .map_err(|e| Error::invalid_port_id(raw.port.clone(), e))?,
+            channel: ChannelId::from_str(&raw.channel)
+                .map_err(|e| Error::invalid_channel_id(raw.channel.clone(), e))?,
+            timeout: raw
+                .timeout
+                .map(Duration::from_nanos)
+                .unwrap_or(DEFAULT_FORWARD_TIMEOUT),
+            retries: raw.retries.unwrap_or(DEFAULT_FORWARD_RETRIES),
+            next: raw.next,
+        })
+    }
+}
+
+impl From<ForwardMetadata> for RawForwardMetadata {
+    fn from(metadata: ForwardMetadata) -> Self {
+        Self {
+            receiver: metadata.receiver.to_string(),
+            port: metadata.port.to_string(),
+            channel: metadata.channel.to_string(),
+            timeout: Some(metadata.timeout.as_nanos() as u64),
+            retries: Some(metadata.retries),
+            next: metadata.next,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::arbitrary::ics20_transfer::arb_packet_metadata;
+
+    fn memo(s: &str) -> Memo {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn memo_without_forward_is_ignored() {
+        assert!(matches!(PacketMetadata::from_memo(&memo("")), Ok(None)));
+        assert!(matches!(
+            PacketMetadata::from_memo(&memo("some text")),
+            Ok(None)
+        ));
+        assert!(matches!(
+            PacketMetadata::from_memo(&memo(r#"{"wasm":{"contract":"cosmos1xyz"}}"#)),
+            Ok(None)
+        ));
+    }
+
+    #[test]
+    fn malformed_forward_is_rejected() {
+        assert!(
+            PacketMetadata::from_memo(&memo(r#"{"forward":{"receiver":"cosmos1xyz"}}"#)).is_err()
+        );
+        assert!(PacketMetadata::from_memo(&memo(
+            r#"{"forward":{"receiver":"cosmos1xyz","port":"transfer","channel":"chan/1"}}"#
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn parse_multi_hop_forward() {
+        let metadata = PacketMetadata::from_memo(&memo(
+            r#"{
+                "forward": {
+                    "receiver": "cosmos1xyz",
+                    "port": "transfer",
+                    "channel": "channel-1",
+                    "retries": 2,
+                    "next": {
+                        "forward": {
+                            "receiver": "osmo1xyz",
+                            "port": "transfer",
+                            "channel": "channel-7",
+                            "timeout": 1000
+                        }
+                    }
+                }
+            }"#,
+        ))
+        .unwrap()
+        .unwrap();
+
+        let forward = metadata.forward;
+        assert_eq!(forward.receiver.as_ref(), "cosmos1xyz");
+        assert_eq!(forward.port, PortId::transfer());
+        assert_eq!(forward.channel, ChannelId::new(1));
+        assert_eq!(forward.timeout, DEFAULT_FORWARD_TIMEOUT);
+        assert_eq!(forward.retries, 2);
+
+        let next = PacketMetadata::from_memo(&forward.next_memo())
+            .unwrap()
+            .unwrap()
+            .forward;
+        assert_eq!(next.receiver.as_ref(), "osmo1xyz");
+        assert_eq!(next.channel, ChannelId::new(7));
+        assert_eq!(next.timeout, Duration::from_nanos(1000));
+        assert_eq!(next.retries, DEFAULT_FORWARD_RETRIES);
+        assert!(next.next_memo().is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn arbitrary_to_and_from_memo(metadata in arb_packet_metadata()) {
+            let memo = Memo::from(serde_json::to_string(&metadata).unwrap());
+            prop_assert_eq!(PacketMetadata::from_memo(&memo).unwrap(), Some(metadata));
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! Packet forwarding middleware for ICS20. Wraps the token transfer application so that a packet
+//! received on this chain may be sent onward to a third chain, following routing instructions
+//! found in the packet memo. The acknowledgement of the incoming packet is held back until the
+//! forwarded packet is acknowledged or times out, so that failures are refunded through every hop.
+use core::time::Duration;
+
+pub mod context;
+pub mod error;
+pub mod events;
+pub mod metadata;
+pub mod packet;
+pub mod relay;
+
+/// Module identifier for the packet forwarding middleware.
+pub const MODULE_ID_STR: &str = "packetforward";
+
+/// Timeout applied to a forwarded packet when the memo does not specify one.
+pub const DEFAULT_FORWARD_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Number of times a forwarded packet is re-sent after a timeout when the memo does not specify
+/// otherwise.
+pub const DEFAULT_FORWARD_RETRIES: u8 = 0;
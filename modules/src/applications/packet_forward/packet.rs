@@ -0,0 +1,20 @@
+use crate::applications::packet_forward::metadata::ForwardMetadata;
+use crate::applications::transfer::PrefixedCoin;
+use crate::core::ics04_channel::packet::Packet;
+use crate::signer::Signer;
+
+/// A packet received from the previous hop whose acknowledgement is held back until the packet it
+/// was forwarded as is acknowledged or times out on the next hop.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InFlightPacket {
+    /// The packet received from the previous hop.
+    pub original_packet: Packet,
+    /// The account holding the received tokens on this chain while they are forwarded.
+    pub intermediate: Signer,
+    /// The tokens as credited to the intermediate account.
+    pub token: PrefixedCoin,
+    /// The forwarding instructions, kept around in order to retry on timeout.
+    pub forward: ForwardMetadata,
+    /// The number of times the packet may still be re-sent after a timeout.
+    pub retries_remaining: u8,
+}
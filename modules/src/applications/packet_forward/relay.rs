@@ -0,0 +1,111 @@
+//! This module implements the processing logic of the packet forwarding middleware.
+use crate::applications::packet_forward::context::PacketForwardContext;
+use crate::applications::packet_forward::error::Error;
+use crate::applications::packet_forward::packet::InFlightPacket;
+use crate::applications::transfer::acknowledgement::Acknowledgement;
+use crate::applications::transfer::error::Error as Ics20Error;
+use crate::applications::transfer::is_receiver_chain_source;
+use crate::applications::transfer::msgs::transfer::MsgTransfer;
+use crate::applications::transfer::relay::increase_total_escrow;
+use crate::applications::transfer::relay::send_transfer::send_transfer;
+use crate::core::ics04_channel::handler::write_acknowledgement;
+use crate::core::ics04_channel::packet::Packet;
+use crate::core::ics04_channel::timeout::TimeoutHeight;
+use crate::handler::{HandlerOutput, HandlerOutputBuilder};
+use crate::prelude::*;
+
+pub mod on_ack_packet;
+pub mod on_recv_packet;
+pub mod on_timeout_packet;
+
+/// Sends the tokens held by the intermediate account on to the next hop, and records the
+/// in-flight packet against the sequence of the forwarded packet.
+fn forward_transfer(
+    ctx: &mut impl PacketForwardContext,
+    in_flight: InFlightPacket,
+) -> Result<(), Error> {
+    let forward = &in_flight.forward;
+    let port_channel_id = (forward.port.clone(), forward.channel.clone());
+
+    let timeout_timestamp =
+        (ctx.host_timestamp() + forward.timeout).map_err(|_| Error::invalid_forward_timeout())?;
+
+    let msg = MsgTransfer {
+        source_port: forward.port.clone(),
+        source_channel: forward.channel.clone(),
+        token: in_flight.token.clone(),
+        sender: in_flight.intermediate.clone(),
+        receiver: forward.receiver.clone(),
+        timeout_height: TimeoutHeight::no_timeout(),
+        timeout_timestamp,
+        memo: forward.next_memo(),
+    };
+
+    let sequence = ctx
+        .get_next_sequence_send(&port_channel_id)
+        .map_err(Error::ics04_channel)?;
+
+    let mut output = HandlerOutputBuilder::new();
+    send_transfer(ctx, &mut output, msg).map_err(Error::ics20)?;
+
+    ctx.store_in_flight_packet((port_channel_id.0, port_channel_id.1, sequence), in_flight)?;
+
+    let HandlerOutput { events, .. } = output.with_result(());
+    ctx.emit_ibc_events(events);
+
+    Ok(())
+}
+
+/// Undoes the crediting of the intermediate account performed when the original packet was
+/// received, so that the tokens can be refunded on the previous hop.
+///
+/// By the time this is called, the ICS20 application has already refunded the forwarded tokens
+/// to the intermediate account.
+fn revert_forward(
+    ctx: &mut impl PacketForwardContext,
+    in_flight: &InFlightPacket,
+) -> Result<(), Error> {
+    let original = &in_flight.original_packet;
+    let intermediate = in_flight
+        .intermediate
+        .clone()
+        .try_into()
+        .map_err(|_| Error::ics20(Ics20Error::parse_account_failure()))?;
+
+    // Vouchers minted upon receipt carry this chain's prefix for the incoming channel.
+    if is_receiver_chain_source(
+        original.destination_port.clone(),
+        original.destination_channel.clone(),
+        &in_flight.token.denom,
+    ) {
+        ctx.burn_coins(&intermediate, &in_flight.token)
+            .map_err(Error::ics20)
+    }
+    // otherwise the tokens were unescrowed, put them back
+    else {
+        let escrow_address = ctx
+            .get_channel_escrow_address(&original.destination_port, &original.destination_channel)
+            .map_err(Error::ics20)?;
+        ctx.send_coins(&intermediate, &escrow_address, &in_flight.token)
+            .and_then(|_| increase_total_escrow(ctx, &in_flight.token))
+            .map_err(Error::ics20)
+    }
+}
+
+/// Writes the held-back acknowledgement of a packet received from the previous hop.
+fn write_ack(
+    ctx: &mut impl PacketForwardContext,
+    packet: &Packet,
+    ack: &Acknowledgement,
+) -> Result<(), Error> {
+    let ack = serde_json::to_vec(ack).expect("Acknowledgement's infallible Serialize impl failed");
+
+    let HandlerOutput { result, events, .. } =
+        write_acknowledgement::process(&*ctx, packet.clone(), ack).map_err(Error::ics04_channel)?;
+
+    ctx.store_packet_result(result)
+        .map_err(Error::ics04_channel)?;
+    ctx.emit_ibc_events(events);
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use crate::applications::packet_forward::context::PacketForwardContext;
+use crate::applications::packet_forward::error::Error;
+use crate::applications::packet_forward::relay::{revert_forward, write_ack};
+use crate::applications::transfer::acknowledgement::Acknowledgement;
+use crate::applications::transfer::error::Error as Ics20Error;
+use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement as GenericAcknowledgement;
+use crate::core::ics04_channel::packet::Packet;
+
+/// Resolves the in-flight packet that `packet` was forwarded for, if any, by relaying the
+/// acknowledgement of the next hop back to the previous one.
+pub fn process_ack_packet(
+    ctx: &mut impl PacketForwardContext,
+    packet: &Packet,
+    acknowledgement: &GenericAcknowledgement,
+) -> Result<(), Error> {
+    let key = (
+        packet.source_port.clone(),
+        packet.source_channel.clone(),
+        packet.sequence,
+    );
+    let in_flight = match ctx.get_in_flight_packet(&key) {
+        Some(in_flight) => in_flight,
+        None => return Ok(()),
+    };
+    ctx.delete_in_flight_packet(key)?;
+
+    let ack = serde_json::from_slice::<Acknowledgement>(acknowledgement.as_ref())
+        .map_err(|_| Error::ics20(Ics20Error::ack_deserialization()))?;
+
+    if matches!(ack, Acknowledgement::Error(_)) {
+        revert_forward(ctx, &in_flight)?;
+    }
+
+    write_ack(ctx, &in_flight.original_packet, &ack)
+}
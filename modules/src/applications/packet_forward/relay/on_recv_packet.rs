@@ -0,0 +1,102 @@
+use crate::applications::packet_forward::context::PacketForwardContext;
+use crate::applications::packet_forward::error::Error;
+use crate::applications::packet_forward::events::ForwardEvent;
+use crate::applications::packet_forward::metadata::ForwardMetadata;
+use crate::applications::packet_forward::packet::InFlightPacket;
+use crate::applications::packet_forward::relay::{forward_transfer, revert_forward, write_ack};
+use crate::applications::transfer::acknowledgement::Acknowledgement;
+use crate::applications::transfer::events::RecvEvent;
+use crate::applications::transfer::packet::PacketData;
+use crate::applications::transfer::relay::on_recv_packet::{
+    process_recv_packet as ics20_process_recv_packet, received_coin,
+};
+use crate::applications::transfer::Memo;
+use crate::core::ics04_channel::error::Error as ChannelError;
+use crate::core::ics04_channel::packet::Packet;
+use crate::core::ics26_routing::context::{ModuleOutputBuilder, WriteFn};
+use crate::prelude::*;
+
+pub fn process_recv_packet<Ctx: 'static + PacketForwardContext>(
+    ctx: &Ctx,
+    output: &mut ModuleOutputBuilder,
+    packet: &Packet,
+    data: PacketData,
+    forward: ForwardMetadata,
+) -> Result<Box<WriteFn>, Error> {
+    // Make sure the next hop is usable before accepting the packet, so that a failure is reported
+    // through the acknowledgement instead of failing the whole transaction later on.
+    let next_channel_end = ctx
+        .channel_end(&(forward.port.clone(), forward.channel.clone()))
+        .map_err(Error::ics04_channel)?;
+    if !next_channel_end.is_open() {
+        return Err(Error::ics04_channel(ChannelError::channel_closed(
+            forward.channel,
+        )));
+    }
+
+    let intermediate = ctx.get_intermediate_account(
+        &packet.destination_port,
+        &packet.destination_channel,
+        &data.sender,
+    )?;
+
+    output.emit(
+        RecvEvent {
+            receiver: intermediate.clone(),
+            denom: data.token.denom.clone(),
+            amount: data.token.amount,
+            success: true,
+        }
+        .into(),
+    );
+
+    let token = received_coin(packet, data.token.clone());
+    output.emit(
+        ForwardEvent {
+            intermediate: intermediate.clone(),
+            receiver: forward.receiver.clone(),
+            port_id: forward.port.clone(),
+            channel_id: forward.channel.clone(),
+            denom: token.denom.clone(),
+            amount: token.amount,
+        }
+        .into(),
+    );
+
+    // The tokens are received into the intermediate account by the ICS20 application.
+    let recv_data = PacketData {
+        receiver: intermediate.clone(),
+        memo: Memo::default(),
+        ..data
+    };
+    let recv_write_fn =
+        ics20_process_recv_packet(ctx, output, packet, recv_data).map_err(Error::ics20)?;
+
+    let in_flight = InFlightPacket {
+        original_packet: packet.clone(),
+        intermediate,
+        token,
+        retries_remaining: forward.retries,
+        forward,
+    };
+
+    Ok(Box::new(move |ctx| {
+        recv_write_fn(ctx)?;
+
+        let ctx = ctx.downcast_mut::<Ctx>().unwrap();
+        // Failing here would fail the whole transaction, leaving the tokens stuck on the previous
+        // hop until the packet times out. Instead, the received tokens are taken back and an error
+        // acknowledgement is written, so that the previous hop refunds them right away.
+        if let Err(e) = forward_transfer(ctx, in_flight.clone()) {
+            revert_forward(ctx, &in_flight).map_err(|e| e.to_string())?;
+            write_ack(
+                ctx,
+                &in_flight.original_packet,
+                &Acknowledgement::from_error(e),
+            )
+            .map_err(|e| e.to_string())?;
+        }
+
+        Ok(())
+    }))
+}
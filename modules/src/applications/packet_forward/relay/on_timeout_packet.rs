@@ -0,0 +1,46 @@
+use crate::applications::packet_forward::context::PacketForwardContext;
+use crate::applications::packet_forward::error::Error;
+use crate::applications::packet_forward::packet::InFlightPacket;
+use crate::applications::packet_forward::relay::{forward_transfer, revert_forward, write_ack};
+use crate::applications::transfer::acknowledgement::Acknowledgement;
+use crate::core::ics04_channel::packet::Packet;
+
+/// Re-sends the in-flight packet that `packet` was forwarded for, if any retries remain.
+/// Otherwise the forward is given up on and an error acknowledgement is written for the original
+/// packet, so that the previous hop refunds the tokens.
+pub fn process_timeout_packet(
+    ctx: &mut impl PacketForwardContext,
+    packet: &Packet,
+) -> Result<(), Error> {
+    let key = (
+        packet.source_port.clone(),
+        packet.source_channel.clone(),
+        packet.sequence,
+    );
+    let in_flight = match ctx.get_in_flight_packet(&key) {
+        Some(in_flight) => in_flight,
+        None => return Ok(()),
+    };
+    ctx.delete_in_flight_packet(key)?;
+
+    if in_flight.retries_remaining > 0 {
+        let retry = InFlightPacket {
+            retries_remaining: in_flight.retries_remaining - 1,
+            ..in_flight
+        };
+        return forward_transfer(ctx, retry);
+    }
+
+    revert_forward(ctx, &in_flight)?;
+
+    let err = Error::forward_timed_out(
+        packet.source_port.clone(),
+        packet.source_channel.clone(),
+        packet.sequence,
+    );
+    write_ack(
+        ctx,
+        &in_flight.original_packet,
+        &Acknowledgement::from_error(err),
+    )
+}
@@ -0,0 +1,584 @@
+use super::error::Error;
+use crate::applications::rate_limit::quota::{FlowDirection, Quota, RateLimit, RateLimitPath};
+use crate::applications::transfer::acknowledgement::Acknowledgement;
+use crate::applications::transfer::context::{self as ics20, Ics20Context};
+use crate::applications::transfer::error::Error as Ics20Error;
+use crate::applications::transfer::msgs::transfer::MsgTransfer;
+use crate::applications::transfer::packet::PacketData;
+use crate::applications::transfer::relay::on_recv_packet::received_coin;
+use crate::applications::transfer::relay::send_transfer::send_transfer as ics20_send_transfer;
+use crate::applications::transfer::PrefixedCoin;
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement as GenericAcknowledgement;
+use crate::core::ics04_channel::packet::{Packet, Sequence};
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::core::ics26_routing::context::{ModuleOutputBuilder, OnRecvPacketAck};
+use crate::handler::HandlerOutputBuilder;
+use crate::prelude::*;
+use crate::signer::Signer;
+use crate::timestamp::Timestamp;
+
+pub trait RateLimitReader {
+    /// Returns the rate limit for the given path, if any. Channels and denominations without a
+    /// rate limit are not limited.
+    fn get_rate_limit(&self, path: &RateLimitPath) -> Option<RateLimit>;
+
+    /// Returns all the rate limits known to the host.
+    fn get_rate_limits(&self) -> Vec<RateLimit>;
+
+    /// Returns the host timestamp at which the outflow of the given packet was recorded, if it
+    /// was sent through a rate limited path and has not been acknowledged or timed out yet.
+    fn get_send_timestamp(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Timestamp>;
+}
+
+pub trait RateLimitKeeper {
+    /// Stores the rate limit, replacing any previous one for the same path.
+    fn store_rate_limit(&mut self, rate_limit: RateLimit) -> Result<(), Error>;
+
+    fn delete_rate_limit(&mut self, path: &RateLimitPath) -> Result<(), Error>;
+
+    fn store_send_timestamp(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+        timestamp: Timestamp,
+    ) -> Result<(), Error>;
+
+    fn delete_send_timestamp(&mut self, key: &(PortId, ChannelId, Sequence)) -> Result<(), Error>;
+}
+
+/// Captures all the dependencies which the rate limiting middleware requires on top of the ones
+/// of the ICS20 application it wraps.
+pub trait RateLimitContext: Ics20Context + RateLimitReader + RateLimitKeeper {}
+
+impl<T> RateLimitContext for T where T: Ics20Context + RateLimitReader + RateLimitKeeper {}
+
+/// Sets up a rate limit for the given path. Fails if one already exists.
+pub fn add_rate_limit(
+    ctx: &mut (impl RateLimitReader + RateLimitKeeper),
+    path: RateLimitPath,
+    quota: Quota,
+) -> Result<(), Error> {
+    if ctx.get_rate_limit(&path).is_some() {
+        return Err(Error::rate_limit_exists(
+            path.port_id,
+            path.channel_id,
+            path.denom,
+        ));
+    }
+
+    ctx.store_rate_limit(RateLimit::new(path, quota)?)
+}
+
+/// Replaces the quota of an existing rate limit, keeping track of the current flow.
+pub fn update_rate_limit(
+    ctx: &mut (impl RateLimitReader + RateLimitKeeper),
+    path: &RateLimitPath,
+    quota: Quota,
+) -> Result<(), Error> {
+    let rate_limit = lookup_rate_limit(ctx, path)?;
+    let rate_limit = RateLimit {
+        flow: rate_limit.flow,
+        ..RateLimit::new(path.clone(), quota)?
+    };
+
+    ctx.store_rate_limit(rate_limit)
+}
+
+pub fn remove_rate_limit(
+    ctx: &mut (impl RateLimitReader + RateLimitKeeper),
+    path: &RateLimitPath,
+) -> Result<(), Error> {
+    lookup_rate_limit(ctx, path)?;
+    ctx.delete_rate_limit(path)
+}
+
+/// Clears the flow of a rate limit, so that its whole quota is available again.
+pub fn reset_rate_limit(
+    ctx: &mut (impl RateLimitReader + RateLimitKeeper),
+    path: &RateLimitPath,
+) -> Result<(), Error> {
+    let mut rate_limit = lookup_rate_limit(ctx, path)?;
+    rate_limit.reset();
+    ctx.store_rate_limit(rate_limit)
+}
+
+/// Returns the rate limit for the given path, with its flow as of the current host time.
+pub fn query_rate_limit(
+    ctx: &(impl ChannelReader + RateLimitReader),
+    path: &RateLimitPath,
+) -> Result<RateLimit, Error> {
+    let mut rate_limit = lookup_rate_limit(ctx, path)?;
+    rate_limit.refresh(ctx.host_timestamp());
+    Ok(rate_limit)
+}
+
+/// Returns all the rate limits of the given channel, with their flow as of the current host time.
+pub fn query_rate_limits_by_channel(
+    ctx: &(impl ChannelReader + RateLimitReader),
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<Vec<RateLimit>, Error> {
+    query_rate_limits(ctx).map(|rate_limits| {
+        rate_limits
+            .into_iter()
+            .filter(|rl| &rl.path.port_id == port_id && &rl.path.channel_id == channel_id)
+            .collect()
+    })
+}
+
+/// Returns all the rate limits, with their flow as of the current host time.
+pub fn query_rate_limits(
+    ctx: &(impl ChannelReader + RateLimitReader),
+) -> Result<Vec<RateLimit>, Error> {
+    let now = ctx.host_timestamp();
+    Ok(ctx
+        .get_rate_limits()
+        .into_iter()
+        .map(|mut rate_limit| {
+            rate_limit.refresh(now);
+            rate_limit
+        })
+        .collect())
+}
+
+/// Sends a transfer through the ICS20 application, failing if this would exceed the outflow
+/// quota of the source channel.
+/// If this method returns an error, the runtime is expected to rollback all state modifications to
+/// the `Ctx` caused by all messages from the transaction that this `msg` is a part of.
+pub fn send_transfer<Ctx, C>(
+    ctx: &mut Ctx,
+    output: &mut HandlerOutputBuilder<()>,
+    msg: MsgTransfer<C>,
+) -> Result<(), Error>
+where
+    Ctx: RateLimitContext,
+    C: TryInto<PrefixedCoin>,
+{
+    let token: PrefixedCoin = msg
+        .token
+        .try_into()
+        .map_err(|_| Error::ics20(Ics20Error::invalid_token()))?;
+
+    let path = RateLimitPath::new(
+        msg.source_port.clone(),
+        msg.source_channel.clone(),
+        token.denom.clone(),
+    );
+    let rate_limit = checked_flow(ctx, &path, FlowDirection::Send, &token)?;
+
+    // The sequence of the packet about to be sent, under which its outflow is recorded so that
+    // it can be taken back from the window it was recorded in if the packet is refunded.
+    let sequence = ctx
+        .get_next_sequence_send(&(path.port_id.clone(), path.channel_id.clone()))
+        .map_err(|e| Error::ics20(Ics20Error::ics04_channel(e)))?;
+
+    let msg = MsgTransfer {
+        source_port: msg.source_port,
+        source_channel: msg.source_channel,
+        token,
+        sender: msg.sender,
+        receiver: msg.receiver,
+        timeout_height: msg.timeout_height,
+        timeout_timestamp: msg.timeout_timestamp,
+        memo: msg.memo,
+    };
+    ics20_send_transfer(ctx, output, msg).map_err(Error::ics20)?;
+
+    if let Some(rate_limit) = rate_limit {
+        ctx.store_rate_limit(rate_limit)?;
+        ctx.store_send_timestamp(
+            (path.port_id, path.channel_id, sequence),
+            ctx.host_timestamp(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Handles a received packet, answering with an error acknowledgement if it would exceed the
+/// inflow quota of the destination channel.
+pub fn on_recv_packet<Ctx: 'static + RateLimitContext>(
+    ctx: &Ctx,
+    output: &mut ModuleOutputBuilder,
+    packet: &Packet,
+    relayer: &Signer,
+) -> OnRecvPacketAck {
+    let data = match serde_json::from_slice::<PacketData>(&packet.data) {
+        Ok(data) => data,
+        Err(_) => return ics20::on_recv_packet(ctx, output, packet, relayer),
+    };
+
+    let coin = received_coin(packet, data.token);
+    let path = RateLimitPath::new(
+        packet.destination_port.clone(),
+        packet.destination_channel.clone(),
+        coin.denom.clone(),
+    );
+    let rate_limit = match checked_flow(ctx, &path, FlowDirection::Recv, &coin) {
+        Ok(rate_limit) => rate_limit,
+        Err(e) => return OnRecvPacketAck::Failed(Box::new(Acknowledgement::from_error(e))),
+    };
+
+    match (
+        ics20::on_recv_packet(ctx, output, packet, relayer),
+        rate_limit,
+    ) {
+        (OnRecvPacketAck::Successful(ack, write_fn), Some(rate_limit)) => {
+            OnRecvPacketAck::Successful(
+                ack,
+                Box::new(move |ctx| {
+                    write_fn(ctx)?;
+
+                    let ctx = ctx.downcast_mut::<Ctx>().unwrap();
+                    ctx.store_rate_limit(rate_limit).map_err(|e| e.to_string())
+                }),
+            )
+        }
+        (ack, _) => ack,
+    }
+}
+
+pub fn on_acknowledgement_packet<Ctx: RateLimitContext>(
+    ctx: &mut Ctx,
+    output: &mut ModuleOutputBuilder,
+    packet: &Packet,
+    acknowledgement: &GenericAcknowledgement,
+    relayer: &Signer,
+) -> Result<(), Error> {
+    ics20::on_acknowledgement_packet(ctx, output, packet, acknowledgement, relayer)
+        .map_err(Error::ics20)?;
+
+    let acknowledgement = serde_json::from_slice::<Acknowledgement>(acknowledgement.as_ref())
+        .map_err(|_| Error::ics20(Ics20Error::ack_deserialization()))?;
+
+    match acknowledgement {
+        Acknowledgement::Error(_) => revert_send(ctx, packet),
+        Acknowledgement::Success(_) => ctx.delete_send_timestamp(&(
+            packet.source_port.clone(),
+            packet.source_channel.clone(),
+            packet.sequence,
+        )),
+    }
+}
+
+pub fn on_timeout_packet<Ctx: RateLimitContext>(
+    ctx: &mut Ctx,
+    output: &mut ModuleOutputBuilder,
+    packet: &Packet,
+    relayer: &Signer,
+) -> Result<(), Error> {
+    ics20::on_timeout_packet(ctx, output, packet, relayer).map_err(Error::ics20)?;
+
+    revert_send(ctx, packet)
+}
+
+fn lookup_rate_limit(ctx: &impl RateLimitReader, path: &RateLimitPath) -> Result<RateLimit, Error> {
+    ctx.get_rate_limit(path).ok_or_else(|| {
+        Error::rate_limit_not_found(
+            path.port_id.clone(),
+            path.channel_id.clone(),
+            path.denom.clone(),
+        )
+    })
+}
+
+/// Returns the rate limit of `path` updated with the flow of `coin`, or `None` if the path is not
+/// rate limited.
+fn checked_flow(
+    ctx: &impl RateLimitContext,
+    path: &RateLimitPath,
+    direction: FlowDirection,
+    coin: &PrefixedCoin,
+) -> Result<Option<RateLimit>, Error> {
+    let mut rate_limit = match ctx.get_rate_limit(path) {
+        Some(rate_limit) => rate_limit,
+        None => return Ok(None),
+    };
+
+    rate_limit.refresh(ctx.host_timestamp());
+    rate_limit.record(direction, coin.amount)?;
+
+    Ok(Some(rate_limit))
+}
+
+/// Takes the tokens of a refunded packet back from the outflow of its source channel, if they
+/// still count against the quota of the current window.
+fn revert_send(ctx: &mut impl RateLimitContext, packet: &Packet) -> Result<(), Error> {
+    let data = serde_json::from_slice::<PacketData>(&packet.data)
+        .map_err(|_| Error::ics20(Ics20Error::packet_data_deserialization()))?;
+
+    let key = (
+        packet.source_port.clone(),
+        packet.source_channel.clone(),
+        packet.sequence,
+    );
+    let sent_at = match ctx.get_send_timestamp(&key) {
+        Some(sent_at) => sent_at,
+        // The packet was sent before the path was rate limited.
+        None => return Ok(()),
+    };
+    ctx.delete_send_timestamp(&key)?;
+
+    let path = RateLimitPath::new(
+        packet.source_port.clone(),
+        packet.source_channel.clone(),
+        data.token.denom,
+    );
+    let mut rate_limit = match ctx.get_rate_limit(&path) {
+        Some(rate_limit) => rate_limit,
+        None => return Ok(()),
+    };
+
+    rate_limit.refresh(ctx.host_timestamp());
+    rate_limit.revert_send(data.token.amount, sent_at);
+
+    ctx.store_rate_limit(rate_limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::{
+        add_rate_limit, on_acknowledgement_packet, on_recv_packet, on_timeout_packet,
+        query_rate_limit, send_transfer,
+    };
+    use crate::applications::rate_limit::quota::{FlowDirection, Quota, RateLimitPath};
+    use crate::applications::transfer::acknowledgement::Acknowledgement;
+    use crate::applications::transfer::context::BankKeeper;
+    use crate::applications::transfer::msgs::transfer::MsgTransfer;
+    use crate::applications::transfer::packet::PacketData;
+    use crate::applications::transfer::{Amount, PrefixedCoin};
+    use crate::core::ics03_connection::connection::{
+        ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+    };
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement as GenericAcknowledgement;
+    use crate::core::ics04_channel::packet::Packet;
+    use crate::core::ics04_channel::timeout::TimeoutHeight;
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::core::ics26_routing::context::OnRecvPacketAck;
+    use crate::events::IbcEvent;
+    use crate::handler::{HandlerOutput, HandlerOutputBuilder};
+    use crate::mock::context::MockContext;
+    use crate::prelude::*;
+    use crate::signer::Signer;
+    use crate::test_utils::{get_dummy_bech32_account, DummyTransferModule};
+    use crate::timestamp::{Timestamp, ZERO_DURATION};
+    use crate::Height;
+
+    /// A chain with an open transfer channel `channel-0`, whose counterparty is `channel-1`.
+    fn chain() -> (MockContext, DummyTransferModule) {
+        let client_id = ClientId::default();
+        let connection_id = ConnectionId::default();
+        let channel_end = ChannelEnd::new(
+            State::Open,
+            Order::Unordered,
+            Counterparty::new(PortId::transfer(), Some(ChannelId::new(1))),
+            vec![connection_id.clone()],
+            Version::ics20(),
+        );
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.clone(),
+            ConnectionCounterparty::new(
+                client_id.clone(),
+                Some(connection_id.clone()),
+                Default::default(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+
+        let mock = MockContext::default()
+            .with_client(&client_id, Height::new(0, 10).unwrap())
+            .with_connection(connection_id, connection_end)
+            .with_channel(PortId::transfer(), ChannelId::new(0), channel_end)
+            .with_send_sequence(PortId::transfer(), ChannelId::new(0), 1.into());
+        let ctx = DummyTransferModule::new(mock.ibc_store_share());
+        (mock, ctx)
+    }
+
+    fn account() -> Signer {
+        get_dummy_bech32_account().parse().unwrap()
+    }
+
+    fn uatom(amount: u64) -> PrefixedCoin {
+        PrefixedCoin {
+            denom: "uatom".parse().unwrap(),
+            amount: amount.into(),
+        }
+    }
+
+    fn limit(ctx: &mut DummyTransferModule, denom: &str, max_send: u64, max_recv: u64) {
+        let path = path(denom);
+        let quota = Quota {
+            max_send: Some(max_send.into()),
+            max_recv: Some(max_recv.into()),
+            window: Duration::from_secs(3600),
+        };
+        add_rate_limit(ctx, path, quota).unwrap();
+    }
+
+    fn path(denom: &str) -> RateLimitPath {
+        RateLimitPath::new(
+            PortId::transfer(),
+            ChannelId::new(0),
+            denom.parse().unwrap(),
+        )
+    }
+
+    fn remaining(ctx: &DummyTransferModule, denom: &str, direction: FlowDirection) -> Amount {
+        query_rate_limit(ctx, &path(denom))
+            .unwrap()
+            .remaining(direction)
+            .unwrap()
+    }
+
+    fn balance(mock: &MockContext, denom: &str) -> Amount {
+        mock.ibc_store_share()
+            .lock()
+            .unwrap()
+            .balances
+            .get(&(account(), denom.parse().unwrap()))
+            .copied()
+            .unwrap_or_else(|| Amount::from(0))
+    }
+
+    /// Sends `amount` uatom over `channel-0`, returning the packet sent if the transfer succeeds.
+    fn send(ctx: &mut DummyTransferModule, amount: u64) -> Option<Packet> {
+        let msg = MsgTransfer {
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(0),
+            token: uatom(amount),
+            sender: account(),
+            receiver: account(),
+            timeout_height: TimeoutHeight::no_timeout(),
+            timeout_timestamp: (Timestamp::now() + Duration::from_secs(600)).unwrap(),
+            memo: Default::default(),
+        };
+
+        let mut output = HandlerOutputBuilder::new();
+        send_transfer(ctx, &mut output, msg).ok()?;
+        let HandlerOutput { events, .. } = output.with_result(());
+        events.into_iter().find_map(|event| match event {
+            IbcEvent::SendPacket(send_packet) => Some(send_packet.packet),
+            _ => None,
+        })
+    }
+
+    fn ack(ctx: &mut DummyTransferModule, packet: &Packet, ack: Acknowledgement) {
+        let ack = GenericAcknowledgement::from(serde_json::to_vec(&ack).unwrap());
+        on_acknowledgement_packet(
+            ctx,
+            &mut HandlerOutputBuilder::new(),
+            packet,
+            &ack,
+            &account(),
+        )
+        .unwrap();
+    }
+
+    /// Receives `amount` vouchers of the counterparty's uatom over `channel-0`, returning whether
+    /// the packet was accepted.
+    fn recv(ctx: &mut DummyTransferModule, sequence: u64, amount: u64) -> bool {
+        let data = PacketData {
+            token: uatom(amount),
+            sender: account(),
+            receiver: account(),
+            memo: Default::default(),
+        };
+        let packet = Packet {
+            sequence: sequence.into(),
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(1),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(0),
+            data: serde_json::to_vec(&data).unwrap(),
+            ..Default::default()
+        };
+
+        match on_recv_packet(ctx, &mut HandlerOutputBuilder::new(), &packet, &account()) {
+            OnRecvPacketAck::Successful(_, write_fn) => {
+                write_fn(ctx).unwrap();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn send_over_quota_is_rejected() {
+        let (mock, mut ctx) = chain();
+        ctx.mint_coins(&account(), &uatom(100)).unwrap();
+        limit(&mut ctx, "uatom", 60, 60);
+
+        assert!(send(&mut ctx, 50).is_some());
+        assert_eq!(remaining(&ctx, "uatom", FlowDirection::Send), 10u64.into());
+
+        assert!(send(&mut ctx, 20).is_none());
+        assert_eq!(remaining(&ctx, "uatom", FlowDirection::Send), 10u64.into());
+        assert_eq!(balance(&mock, "uatom"), 50u64.into());
+
+        assert!(send(&mut ctx, 10).is_some());
+        assert_eq!(remaining(&ctx, "uatom", FlowDirection::Send), 0u64.into());
+    }
+
+    #[test]
+    fn recv_over_quota_is_rejected_with_error_ack() {
+        let (mock, mut ctx) = chain();
+        let voucher = "transfer/channel-0/uatom";
+        limit(&mut ctx, voucher, 100, 100);
+
+        assert!(recv(&mut ctx, 1, 80));
+        assert_eq!(remaining(&ctx, voucher, FlowDirection::Recv), 20u64.into());
+
+        assert!(!recv(&mut ctx, 2, 30));
+        assert_eq!(remaining(&ctx, voucher, FlowDirection::Recv), 20u64.into());
+        assert_eq!(balance(&mock, voucher), 80u64.into());
+    }
+
+    #[test]
+    fn refund_reverses_outflow() {
+        let (mock, mut ctx) = chain();
+        ctx.mint_coins(&account(), &uatom(100)).unwrap();
+        limit(&mut ctx, "uatom", 100, 100);
+
+        // refunded because of an error acknowledgement
+        let packet = send(&mut ctx, 40).unwrap();
+        assert_eq!(remaining(&ctx, "uatom", FlowDirection::Send), 60u64.into());
+        ack(
+            &mut ctx,
+            &packet,
+            Acknowledgement::Error("failed on the counterparty".to_string()),
+        );
+        assert_eq!(remaining(&ctx, "uatom", FlowDirection::Send), 100u64.into());
+        assert_eq!(balance(&mock, "uatom"), 100u64.into());
+
+        // refunded because of a timeout
+        let packet = send(&mut ctx, 70).unwrap();
+        assert_eq!(remaining(&ctx, "uatom", FlowDirection::Send), 30u64.into());
+        on_timeout_packet(
+            &mut ctx,
+            &mut HandlerOutputBuilder::new(),
+            &packet,
+            &account(),
+        )
+        .unwrap();
+        assert_eq!(remaining(&ctx, "uatom", FlowDirection::Send), 100u64.into());
+        assert_eq!(balance(&mock, "uatom"), 100u64.into());
+
+        // a successfully acknowledged send keeps counting against the quota
+        let packet = send(&mut ctx, 30).unwrap();
+        ack(&mut ctx, &packet, Acknowledgement::success());
+        assert_eq!(remaining(&ctx, "uatom", FlowDirection::Send), 70u64.into());
+        assert!(mock
+            .ibc_store_share()
+            .lock()
+            .unwrap()
+            .rate_limit_send_timestamps
+            .is_empty());
+    }
+}
@@ -0,0 +1,54 @@
+use flex_error::define_error;
+
+use crate::applications::rate_limit::quota::FlowDirection;
+use crate::applications::transfer::error::Error as Ics20Error;
+use crate::applications::transfer::{Amount, PrefixedDenom};
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::prelude::*;
+
+define_error! {
+    #[derive(Debug, PartialEq, Eq)]
+    Error {
+        Ics20
+            [ Ics20Error ]
+            | _ | { "ICS20 transfer error" },
+
+        QuotaExceeded
+            {
+                direction: FlowDirection,
+                port_id: PortId,
+                channel_id: ChannelId,
+                denom: PrefixedDenom,
+                amount: Amount,
+                max: Amount,
+            }
+            | e | {
+                format_args!("{0} quota exceeded for denom {1} on port {2} and channel {3}: flow of {4} would exceed the maximum of {5}",
+                    e.direction, e.denom, e.port_id, e.channel_id, e.amount, e.max)
+            },
+
+        AmountOverflow
+            | _ | { "rate limit flow overflowed" },
+
+        ZeroWindow
+            | _ | { "rate limit window must be non-zero" },
+
+        RateLimitNotFound
+            { port_id: PortId, channel_id: ChannelId, denom: PrefixedDenom }
+            | e | {
+                format_args!("no rate limit for denom {0} on port {1} and channel {2}",
+                    e.denom, e.port_id, e.channel_id)
+            },
+
+        RateLimitExists
+            { port_id: PortId, channel_id: ChannelId, denom: PrefixedDenom }
+            | e | {
+                format_args!("a rate limit for denom {0} on port {1} and channel {2} already exists",
+                    e.denom, e.port_id, e.channel_id)
+            },
+
+        Store
+            { reason: String }
+            | e | { format_args!("failed to access the rate limit store: {0}", e.reason) },
+    }
+}
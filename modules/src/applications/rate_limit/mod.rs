@@ -0,0 +1,9 @@
+//! Rate limiting middleware for ICS20. Wraps the token transfer application in order to cap the
+//! amount of each denomination which may flow in and out of a channel within a rolling time
+//! window, thus bounding the damage of a compromised counterparty chain or bridge exploit.
+pub mod context;
+pub mod error;
+pub mod quota;
+
+/// Module identifier for the rate limiting middleware.
+pub const MODULE_ID_STR: &str = "ratelimit";
@@ -0,0 +1,392 @@
+use core::fmt;
+use core::time::Duration;
+
+use super::error::Error;
+use crate::applications::transfer::{Amount, PrefixedDenom};
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::prelude::*;
+use crate::timestamp::Timestamp;
+
+/// The direction of a token flow through a channel, from the perspective of the host chain.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FlowDirection {
+    Send,
+    Recv,
+}
+
+impl fmt::Display for FlowDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlowDirection::Send => write!(f, "send"),
+            FlowDirection::Recv => write!(f, "receive"),
+        }
+    }
+}
+
+/// Identifies the channel and the denomination (as known on the host chain) a rate limit applies
+/// to.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RateLimitPath {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub denom: PrefixedDenom,
+}
+
+impl RateLimitPath {
+    pub fn new(port_id: PortId, channel_id: ChannelId, denom: PrefixedDenom) -> Self {
+        Self {
+            port_id,
+            channel_id,
+            denom,
+        }
+    }
+}
+
+/// The maximum amounts which may flow through a channel within any window of the given duration.
+/// A direction without a maximum is not limited.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Quota {
+    pub max_send: Option<Amount>,
+    pub max_recv: Option<Amount>,
+    pub window: Duration,
+}
+
+/// The number of slots the window of a rate limit is divided into. Flows are accounted per slot,
+/// which bounds the state of a rate limit regardless of the number of transfers, at the cost of
+/// flows leaving the window with the granularity of a slot.
+pub const WINDOW_SLOTS: usize = 24;
+
+/// The amounts which flowed through a channel within a single slot of the window.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SlotFlow {
+    pub inflow: Amount,
+    pub outflow: Amount,
+}
+
+impl SlotFlow {
+    fn new() -> Self {
+        Self {
+            inflow: 0u64.into(),
+            outflow: 0u64.into(),
+        }
+    }
+}
+
+/// The amounts which flowed through a channel within the rolling window ending at the last
+/// refresh. Slots are numbered by the slot durations elapsed since the Unix epoch, and slot `n`
+/// is kept at index `n % WINDOW_SLOTS` of a ring holding the last `WINDOW_SLOTS` slots.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Flow {
+    /// The slot of the last refresh, i.e. the most recent slot of the ring.
+    pub current_slot: u64,
+    pub slots: [SlotFlow; WINDOW_SLOTS],
+}
+
+impl Default for Flow {
+    fn default() -> Self {
+        Self {
+            current_slot: 0,
+            slots: [SlotFlow::new(); WINDOW_SLOTS],
+        }
+    }
+}
+
+impl Flow {
+    pub fn inflow(&self) -> Amount {
+        self.total(FlowDirection::Recv)
+    }
+
+    pub fn outflow(&self) -> Amount {
+        self.total(FlowDirection::Send)
+    }
+
+    /// The recorded flows are within the quota, hence their sum cannot overflow.
+    fn total(&self, direction: FlowDirection) -> Amount {
+        self.slots
+            .iter()
+            .map(|slot| match direction {
+                FlowDirection::Send => slot.outflow,
+                FlowDirection::Recv => slot.inflow,
+            })
+            .fold(0u64.into(), |total: Amount, amount| {
+                total.checked_add(amount).unwrap_or(total)
+            })
+    }
+
+    /// Returns the flow of `slot`, if it is still part of the ring.
+    fn slot_mut(&mut self, slot: u64) -> Option<&mut SlotFlow> {
+        if slot > self.current_slot || self.current_slot - slot >= WINDOW_SLOTS as u64 {
+            return None;
+        }
+        Some(&mut self.slots[(slot % WINDOW_SLOTS as u64) as usize])
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    pub path: RateLimitPath,
+    pub quota: Quota,
+    pub flow: Flow,
+}
+
+impl RateLimit {
+    pub fn new(path: RateLimitPath, quota: Quota) -> Result<Self, Error> {
+        if quota.window.is_zero() {
+            return Err(Error::zero_window());
+        }
+
+        Ok(Self {
+            path,
+            quota,
+            flow: Flow::default(),
+        })
+    }
+
+    /// Returns the slot of the window which `timestamp` falls into.
+    fn slot_of(&self, timestamp: Timestamp) -> u64 {
+        let slot_nanos = (self.quota.window.as_nanos() / WINDOW_SLOTS as u128).max(1);
+        (u128::from(timestamp.nanoseconds()) / slot_nanos) as u64
+    }
+
+    /// Moves the window forward to end at `now`, forgetting the flows of the slots which left it.
+    pub fn refresh(&mut self, now: Timestamp) {
+        let now_slot = self.slot_of(now);
+        if now_slot <= self.flow.current_slot {
+            return;
+        }
+
+        let elapsed = now_slot - self.flow.current_slot;
+        if elapsed >= WINDOW_SLOTS as u64 {
+            self.flow.slots = [SlotFlow::new(); WINDOW_SLOTS];
+        } else {
+            for slot in self.flow.current_slot + 1..=now_slot {
+                self.flow.slots[(slot % WINDOW_SLOTS as u64) as usize] = SlotFlow::new();
+            }
+        }
+        self.flow.current_slot = now_slot;
+    }
+
+    /// Forgets all the recorded flows.
+    pub fn reset(&mut self) {
+        self.flow = Flow {
+            current_slot: self.flow.current_slot,
+            ..Flow::default()
+        };
+    }
+
+    /// Records `amount` flowing in the given direction within the current slot, failing if this
+    /// exceeds the quota. The flow is expected to have been refreshed at the current host time.
+    pub fn record(&mut self, direction: FlowDirection, amount: Amount) -> Result<(), Error> {
+        let max = match direction {
+            FlowDirection::Send => self.quota.max_send,
+            FlowDirection::Recv => self.quota.max_recv,
+        };
+
+        let new_flow = self
+            .flow
+            .total(direction)
+            .checked_add(amount)
+            .ok_or_else(Error::amount_overflow)?;
+
+        if let Some(max) = max {
+            if new_flow > max {
+                return Err(Error::quota_exceeded(
+                    direction,
+                    self.path.port_id.clone(),
+                    self.path.channel_id.clone(),
+                    self.path.denom.clone(),
+                    new_flow,
+                    max,
+                ));
+            }
+        }
+
+        let current_slot = self.flow.current_slot;
+        // The current slot is always part of the ring.
+        if let Some(slot) = self.flow.slot_mut(current_slot) {
+            let flow = match direction {
+                FlowDirection::Send => &mut slot.outflow,
+                FlowDirection::Recv => &mut slot.inflow,
+            };
+            *flow = flow
+                .checked_add(amount)
+                .ok_or_else(Error::amount_overflow)?;
+        }
+
+        Ok(())
+    }
+
+    /// Takes back `amount` from the outflow recorded at `sent_at`, e.g. because the packet it was
+    /// sent in has been refunded. Has no effect if that outflow has left the window since, as it
+    /// no longer counts against the quota.
+    pub fn revert_send(&mut self, amount: Amount, sent_at: Timestamp) {
+        let sent_slot = self.slot_of(sent_at);
+        if let Some(slot) = self.flow.slot_mut(sent_slot) {
+            slot.outflow = slot
+                .outflow
+                .checked_sub(amount)
+                .unwrap_or_else(|| 0u64.into());
+        }
+    }
+
+    /// Returns the amount which may still flow in the given direction within the current window,
+    /// or `None` if that direction is not limited.
+    pub fn remaining(&self, direction: FlowDirection) -> Option<Amount> {
+        let max = match direction {
+            FlowDirection::Send => self.quota.max_send,
+            FlowDirection::Recv => self.quota.max_recv,
+        };
+        let flow = self.flow.total(direction);
+        max.map(|max| max.checked_sub(flow).unwrap_or_else(|| 0u64.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ops::Add;
+    use core::time::Duration;
+
+    use super::*;
+
+    fn rate_limit(max_send: u64, max_recv: u64) -> RateLimit {
+        let path = RateLimitPath::new(
+            PortId::transfer(),
+            ChannelId::new(0),
+            "uatom".parse().unwrap(),
+        );
+        let quota = Quota {
+            max_send: Some(max_send.into()),
+            max_recv: Some(max_recv.into()),
+            window: Duration::from_secs(3600),
+        };
+        RateLimit::new(path, quota).unwrap()
+    }
+
+    #[test]
+    fn zero_window_is_rejected() {
+        let path = RateLimitPath::new(
+            PortId::transfer(),
+            ChannelId::new(0),
+            "uatom".parse().unwrap(),
+        );
+        let quota = Quota {
+            max_send: None,
+            max_recv: None,
+            window: Duration::ZERO,
+        };
+        assert!(RateLimit::new(path, quota).is_err());
+    }
+
+    #[test]
+    fn flow_is_capped_per_direction() {
+        let now = Timestamp::now();
+        let mut rate_limit = rate_limit(100, 50);
+        rate_limit.refresh(now);
+
+        rate_limit
+            .record(FlowDirection::Send, 60u64.into())
+            .unwrap();
+        rate_limit
+            .record(FlowDirection::Recv, 50u64.into())
+            .unwrap();
+        assert_eq!(
+            rate_limit.remaining(FlowDirection::Send),
+            Some(40u64.into())
+        );
+        assert_eq!(rate_limit.remaining(FlowDirection::Recv), Some(0u64.into()));
+
+        assert!(rate_limit
+            .record(FlowDirection::Send, 41u64.into())
+            .is_err());
+        assert!(rate_limit.record(FlowDirection::Recv, 1u64.into()).is_err());
+        // a rejected flow is not recorded
+        assert_eq!(rate_limit.flow.outflow(), 60u64.into());
+
+        rate_limit.revert_send(60u64.into(), now);
+        rate_limit
+            .record(FlowDirection::Send, 100u64.into())
+            .unwrap();
+    }
+
+    #[test]
+    fn flow_leaves_the_rolling_window() {
+        let now = Timestamp::now();
+        let later = |secs| now.add(Duration::from_secs(secs)).unwrap();
+
+        let mut rate_limit = rate_limit(100, 100);
+        rate_limit.refresh(now);
+        rate_limit
+            .record(FlowDirection::Send, 60u64.into())
+            .unwrap();
+
+        rate_limit.refresh(later(1800));
+        rate_limit
+            .record(FlowDirection::Send, 40u64.into())
+            .unwrap();
+        assert_eq!(rate_limit.remaining(FlowDirection::Send), Some(0u64.into()));
+
+        // only the flow of the first hour has left the window
+        rate_limit.refresh(later(3600));
+        assert_eq!(
+            rate_limit.remaining(FlowDirection::Send),
+            Some(60u64.into())
+        );
+
+        rate_limit.refresh(later(5400));
+        assert_eq!(
+            rate_limit.remaining(FlowDirection::Send),
+            Some(100u64.into())
+        );
+    }
+
+    #[test]
+    fn refund_is_credited_to_its_send() {
+        let now = Timestamp::now();
+        let later = |secs| now.add(Duration::from_secs(secs)).unwrap();
+
+        let mut rate_limit = rate_limit(100, 100);
+        rate_limit.refresh(now);
+        rate_limit
+            .record(FlowDirection::Send, 100u64.into())
+            .unwrap();
+
+        rate_limit.refresh(later(3600));
+        rate_limit
+            .record(FlowDirection::Send, 100u64.into())
+            .unwrap();
+
+        // the send of the previous window no longer counts against the quota,
+        // hence its refund does not free any quota of the current window
+        rate_limit.revert_send(100u64.into(), now);
+        assert_eq!(rate_limit.remaining(FlowDirection::Send), Some(0u64.into()));
+        assert!(rate_limit.record(FlowDirection::Send, 1u64.into()).is_err());
+
+        rate_limit.revert_send(100u64.into(), later(3600));
+        assert_eq!(
+            rate_limit.remaining(FlowDirection::Send),
+            Some(100u64.into())
+        );
+    }
+
+    #[test]
+    fn flow_is_accounted_per_slot() {
+        // the window of an hour is divided into slots of 150 seconds
+        let start = Timestamp::from_nanoseconds(1_000 * 150 * 1_000_000_000).unwrap();
+        let at = |secs| start.add(Duration::from_secs(secs)).unwrap();
+
+        // a transfer every minute for two hours
+        let mut rate_limit = rate_limit(1000, 1000);
+        for minute in 0..120 {
+            rate_limit.refresh(at(minute * 60));
+            rate_limit.record(FlowDirection::Send, 1u64.into()).unwrap();
+        }
+
+        // only the transfers of the second hour are within the window
+        assert_eq!(rate_limit.flow.outflow(), 60u64.into());
+
+        rate_limit.revert_send(1u64.into(), at(30 * 60));
+        assert_eq!(rate_limit.flow.outflow(), 60u64.into());
+        rate_limit.revert_send(1u64.into(), at(100 * 60));
+        assert_eq!(rate_limit.flow.outflow(), 59u64.into());
+    }
+}
@@ -2,7 +2,6 @@ use core::fmt::{Display, Formatter};
 
 use serde::{Deserialize, Serialize};
 
-use super::error::Error;
 use crate::core::ics26_routing::context::Acknowledgement as AckTrait;
 use crate::prelude::*;
 
@@ -36,7 +35,7 @@ impl Acknowledgement {
         Self::Success(ConstAckSuccess::Success)
     }
 
-    pub fn from_error(err: Error) -> Self {
+    pub fn from_error(err: impl Display) -> Self {
         Self::Error(format!("{}: {}", ACK_ERR_STR, err))
     }
 }
@@ -7,7 +7,7 @@ use crate::applications::transfer::packet::PacketData;
 use crate::applications::transfer::relay::on_ack_packet::process_ack_packet;
 use crate::applications::transfer::relay::on_recv_packet::process_recv_packet;
 use crate::applications::transfer::relay::on_timeout_packet::process_timeout_packet;
-use crate::applications::transfer::{PrefixedCoin, PrefixedDenom, VERSION};
+use crate::applications::transfer::{Amount, PrefixedCoin, PrefixedDenom, VERSION};
 use crate::core::ics04_channel::channel::{Counterparty, Order};
 use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
 use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement as GenericAcknowledgement;
@@ -22,9 +22,16 @@ pub trait Ics20Keeper:
     ChannelKeeper + BankKeeper<AccountId = <Self as Ics20Keeper>::AccountId>
 {
     type AccountId;
+
+    /// Sets the total amount of `denom` held in escrow across all the channels of the transfer
+    /// port.
+    fn set_total_escrow(&mut self, denom: &PrefixedDenom, amount: Amount)
+        -> Result<(), Ics20Error>;
 }
 
-pub trait Ics20Reader: ChannelReader {
+pub trait Ics20Reader:
+    ChannelReader + BankReader<AccountId = <Self as Ics20Reader>::AccountId>
+{
     type AccountId: TryFrom<Signer>;
 
     /// get_port returns the portID for the transfer module.
@@ -48,6 +55,13 @@ pub trait Ics20Reader: ChannelReader {
     fn denom_hash_string(&self, _denom: &PrefixedDenom) -> Option<String> {
         None
     }
+
+    /// Returns the total amount of `denom` held in escrow across all the channels of the transfer
+    /// port, as tracked by the module.
+    fn get_total_escrow(&self, denom: &PrefixedDenom) -> Amount;
+
+    /// Returns the total escrow of every denomination that was ever escrowed.
+    fn get_all_total_escrows(&self) -> Vec<PrefixedCoin>;
 }
 
 // https://github.com/cosmos/cosmos-sdk/blob/master/docs/architecture/adr-028-public-key-addresses.md
@@ -90,6 +104,13 @@ pub trait BankKeeper {
     ) -> Result<(), Ics20Error>;
 }
 
+pub trait BankReader {
+    type AccountId;
+
+    /// Returns the amount of `denom` held by `account`.
+    fn get_balance(&self, account: &Self::AccountId, denom: &PrefixedDenom) -> Amount;
+}
+
 /// Captures all the dependencies which the ICS20 module requires to be able to dispatch and
 /// process IBC messages.
 pub trait Ics20Context:
@@ -99,6 +120,42 @@ pub trait Ics20Context:
     type AccountId: TryFrom<Signer>;
 }
 
+/// Checks that, for each escrowed denomination, the escrow accounts of the given transfer
+/// `channels` hold at least the total escrow tracked by the module. All the channels of the
+/// transfer port must be given for the check to be meaningful. The balances may exceed the tracked
+/// amounts, since anyone can send tokens to an escrow account directly.
+pub fn check_total_escrow_invariant(
+    ctx: &impl Ics20Context,
+    channels: &[(PortId, ChannelId)],
+) -> Result<(), Ics20Error> {
+    let escrow_addresses = channels
+        .iter()
+        .map(|(port_id, channel_id)| ctx.get_channel_escrow_address(port_id, channel_id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for total_escrow in ctx.get_all_total_escrows() {
+        let escrow_balance =
+            escrow_addresses
+                .iter()
+                .try_fold(Amount::from(0), |sum, address| {
+                    sum.checked_add(ctx.get_balance(address, &total_escrow.denom))
+                        .ok_or_else(|| {
+                            Ics20Error::total_escrow_overflow(total_escrow.denom.clone())
+                        })
+                })?;
+
+        if escrow_balance < total_escrow.amount {
+            return Err(Ics20Error::escrow_invariant_broken(
+                total_escrow.denom,
+                total_escrow.amount,
+                escrow_balance,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 fn validate_transfer_channel_params(
     ctx: &mut impl Ics20Context,
     order: Order,
@@ -201,6 +258,56 @@ pub fn on_chan_close_confirm(
     Ok(())
 }
 
+/// An upgraded transfer channel must keep the transfer channel parameters, so that an upgrade can
+/// only move the channel to new connection hops.
+pub fn on_chan_upgrade_init(
+    ctx: &mut impl Ics20Context,
+    _output: &mut ModuleOutputBuilder,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    order: Order,
+    _connection_hops: &[ConnectionId],
+    version: &Version,
+) -> Result<Version, Ics20Error> {
+    validate_transfer_channel_params(ctx, order, port_id, channel_id, version)?;
+    Ok(version.clone())
+}
+
+pub fn on_chan_upgrade_try(
+    ctx: &mut impl Ics20Context,
+    _output: &mut ModuleOutputBuilder,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    order: Order,
+    _connection_hops: &[ConnectionId],
+    counterparty_version: &Version,
+) -> Result<Version, Ics20Error> {
+    validate_transfer_channel_params(ctx, order, port_id, channel_id, counterparty_version)?;
+    Ok(Version::ics20())
+}
+
+pub fn on_chan_upgrade_ack(
+    _ctx: &mut impl Ics20Context,
+    _output: &mut ModuleOutputBuilder,
+    _port_id: &PortId,
+    _channel_id: &ChannelId,
+    counterparty_version: &Version,
+) -> Result<(), Ics20Error> {
+    validate_counterparty_version(counterparty_version)
+}
+
+pub fn on_chan_upgrade_open(
+    _ctx: &mut impl Ics20Context,
+    _output: &mut ModuleOutputBuilder,
+    _port_id: &PortId,
+    _channel_id: &ChannelId,
+    _order: Order,
+    _connection_hops: &[ConnectionId],
+    _version: &Version,
+) -> Result<(), Ics20Error> {
+    Ok(())
+}
+
 pub fn on_recv_packet<Ctx: 'static + Ics20Context>(
     ctx: &Ctx,
     output: &mut ModuleOutputBuilder,
@@ -284,15 +391,24 @@ pub fn on_timeout_packet(
 pub(crate) mod test {
     use subtle_encoding::bech32;
 
-    use crate::applications::transfer::context::cosmos_adr028_escrow_address;
+    use crate::applications::transfer::context::{
+        check_total_escrow_invariant, cosmos_adr028_escrow_address, on_recv_packet,
+        on_timeout_packet, BankKeeper, BankReader, Ics20Keeper, Ics20Reader,
+    };
     use crate::applications::transfer::error::Error as Ics20Error;
     use crate::applications::transfer::msgs::transfer::MsgTransfer;
+    use crate::applications::transfer::packet::PacketData;
     use crate::applications::transfer::relay::send_transfer::send_transfer;
-    use crate::applications::transfer::PrefixedCoin;
+    use crate::applications::transfer::{PrefixedCoin, PrefixedDenom};
     use crate::core::ics04_channel::error::Error;
+    use crate::core::ics04_channel::packet::Packet;
+    use crate::core::ics24_host::identifier::{ChannelId, PortId};
+    use crate::core::ics26_routing::context::OnRecvPacketAck;
     use crate::handler::HandlerOutputBuilder;
+    use crate::mock::context::MockContext;
     use crate::prelude::*;
-    use crate::test_utils::DummyTransferModule;
+    use crate::signer::Signer;
+    use crate::test_utils::{get_dummy_bech32_account, DummyTransferModule};
 
     pub(crate) fn deliver(
         ctx: &mut DummyTransferModule,
@@ -331,4 +447,92 @@ pub(crate) mod test {
             "cosmos177x69sver58mcfs74x6dg0tv6ls4s3xmmcaw53",
         );
     }
+
+    #[test]
+    fn test_total_escrow_tracking() {
+        let port_id = PortId::transfer();
+        let channel_id = ChannelId::new(0);
+        let counterparty_channel_id = ChannelId::new(1);
+        let channels = [(port_id.clone(), channel_id.clone())];
+
+        let account: Signer = get_dummy_bech32_account().parse().unwrap();
+        let denom: PrefixedDenom = "uatom".parse().unwrap();
+        let coin = |amount: u64| PrefixedCoin {
+            denom: denom.clone(),
+            amount: amount.into(),
+        };
+        let packet_data = |denom: &str, amount: u64| {
+            let data = PacketData {
+                token: PrefixedCoin {
+                    denom: denom.parse().unwrap(),
+                    amount: amount.into(),
+                },
+                sender: account.clone(),
+                receiver: account.clone(),
+                memo: Default::default(),
+            };
+            serde_json::to_vec(&data).unwrap()
+        };
+
+        let mut ctx = DummyTransferModule::new(MockContext::default().ibc_store_share());
+        let escrow_address = ctx
+            .get_channel_escrow_address(&port_id, &channel_id)
+            .unwrap();
+
+        // 100uatom were escrowed by earlier transfers.
+        ctx = ctx.with_balance(&escrow_address, &coin(100));
+        ctx.set_total_escrow(&denom, 100u64.into()).unwrap();
+        assert!(check_total_escrow_invariant(&ctx, &channels).is_ok());
+
+        // A timed out transfer of 10uatom is refunded out of escrow.
+        let sent_packet = Packet {
+            source_port: port_id.clone(),
+            source_channel: channel_id.clone(),
+            destination_port: port_id.clone(),
+            destination_channel: counterparty_channel_id.clone(),
+            data: packet_data("uatom", 10),
+            ..Default::default()
+        };
+        on_timeout_packet(
+            &mut ctx,
+            &mut HandlerOutputBuilder::new(),
+            &sent_packet,
+            &account,
+        )
+        .unwrap();
+        assert_eq!(ctx.get_total_escrow(&denom), 90u64.into());
+        assert_eq!(ctx.get_balance(&account, &denom), 10u64.into());
+        assert!(check_total_escrow_invariant(&ctx, &channels).is_ok());
+
+        // Vouchers for 30uatom sent back by the counterparty are unescrowed.
+        let received_packet = |amount: u64| Packet {
+            source_port: port_id.clone(),
+            source_channel: counterparty_channel_id.clone(),
+            destination_port: port_id.clone(),
+            destination_channel: channel_id.clone(),
+            data: packet_data("transfer/channel-1/uatom", amount),
+            ..Default::default()
+        };
+        let recv = |ctx: &mut DummyTransferModule, packet: &Packet| match on_recv_packet(
+            ctx,
+            &mut HandlerOutputBuilder::new(),
+            packet,
+            &account,
+        ) {
+            OnRecvPacketAck::Successful(_, write_fn) => write_fn(ctx),
+            _ => panic!("receiving the packet failed"),
+        };
+        recv(&mut ctx, &received_packet(30)).unwrap();
+        assert_eq!(ctx.get_total_escrow(&denom), 60u64.into());
+        assert_eq!(ctx.get_balance(&account, &denom), 40u64.into());
+        assert!(check_total_escrow_invariant(&ctx, &channels).is_ok());
+
+        // More than the escrowed amount cannot be unescrowed.
+        assert!(recv(&mut ctx, &received_packet(100)).is_err());
+        assert_eq!(ctx.get_total_escrow(&denom), 60u64.into());
+
+        // Tokens leaving an escrow account outside of the transfer module break the invariant.
+        ctx.burn_coins(&escrow_address, &coin(1)).unwrap();
+        assert!(check_total_escrow_invariant(&ctx, &channels).is_err());
+    }
 }
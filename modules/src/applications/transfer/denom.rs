@@ -358,6 +358,8 @@ impl fmt::Display for PrefixedCoin {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::arbitrary::ics20_transfer::{arb_invalid_raw_denom_trace, arb_prefixed_denom};
+    use crate::test_utils::raw_round_trip_proptests;
 
     #[test]
     fn test_denom_validation() -> Result<(), Error> {
@@ -472,4 +474,11 @@ mod tests {
 
         Ok(())
     }
+
+    raw_round_trip_proptests!(
+        PrefixedDenom,
+        RawDenomTrace,
+        arb_prefixed_denom(),
+        arb_invalid_raw_denom_trace()
+    );
 }
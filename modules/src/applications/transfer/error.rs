@@ -5,6 +5,7 @@ use subtle_encoding::Error as EncodingError;
 use tendermint_proto::Error as TendermintProtoError;
 use uint::FromDecStrErr;
 
+use crate::applications::transfer::denom::{Amount, PrefixedCoin, PrefixedDenom};
 use crate::core::ics04_channel::channel::Order;
 use crate::core::ics04_channel::error as channel_error;
 use crate::core::ics04_channel::Version;
@@ -132,5 +133,21 @@ define_error! {
         UnknownMsgType
             { msg_type: String }
             | e | { format_args!("unknown msg type: {0}", e.msg_type) },
+
+        InsufficientFunds
+            { send_attempt: PrefixedCoin, available_funds: Amount }
+            | e | { format_args!("insufficient funds: tried to send {0}, only {1} available", e.send_attempt, e.available_funds) },
+
+        InsufficientTotalEscrow
+            { denom: PrefixedDenom, amount: Amount, total_escrow: Amount }
+            | e | { format_args!("cannot unescrow {0} {1}: total escrow is only {2}", e.amount, e.denom, e.total_escrow) },
+
+        TotalEscrowOverflow
+            { denom: PrefixedDenom }
+            | e | { format_args!("total escrow of {0} overflows", e.denom) },
+
+        EscrowInvariantBroken
+            { denom: PrefixedDenom, total_escrow: Amount, escrow_balance: Amount }
+            | e | { format_args!("escrow accounts hold {0} {1}, less than its total escrow of {2}", e.escrow_balance, e.denom, e.total_escrow) },
     }
 }
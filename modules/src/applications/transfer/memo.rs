@@ -0,0 +1,54 @@
+use core::convert::Infallible;
+use core::fmt::{self, Display};
+use core::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+/// Arbitrary data attached to an ICS20 transfer, carried along in the packet data.
+///
+/// The transfer application itself ignores the memo; it is interpreted by middleware such as
+/// packet forwarding, which reads routing instructions from it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Memo(String);
+
+impl Memo {
+    /// Returns true if the memo carries no data.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl AsRef<str> for Memo {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Memo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<String> for Memo {
+    fn from(memo: String) -> Self {
+        Self(memo)
+    }
+}
+
+impl From<Memo> for String {
+    fn from(memo: Memo) -> Self {
+        memo.0
+    }
+}
+
+impl FromStr for Memo {
+    type Err = Infallible;
+
+    fn from_str(memo: &str) -> Result<Self, Infallible> {
+        Ok(Self(memo.to_owned()))
+    }
+}
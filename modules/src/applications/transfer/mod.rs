@@ -6,11 +6,13 @@ pub mod context;
 pub mod denom;
 pub mod error;
 pub mod events;
+pub mod memo;
 pub mod msgs;
 pub mod packet;
 pub mod relay;
 
 pub use denom::*;
+pub use memo::Memo;
 
 /// Module identifier for the ICS20 application.
 pub const MODULE_ID_STR: &str = "transfer";
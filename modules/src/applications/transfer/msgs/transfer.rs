@@ -8,6 +8,7 @@ use ibc_proto::ibc::applications::transfer::v1::MsgTransfer as RawMsgTransfer;
 use tendermint_proto::Protobuf;
 
 use crate::applications::transfer::error::Error;
+use crate::applications::transfer::Memo;
 use crate::core::ics04_channel::timeout::TimeoutHeight;
 use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::signer::Signer;
@@ -41,6 +42,8 @@ pub struct MsgTransfer<C = Coin> {
     /// Timeout timestamp relative to the current block timestamp.
     /// The timeout is disabled when set to 0.
     pub timeout_timestamp: Timestamp,
+    /// Optional memo, carried along in the packet data.
+    pub memo: Memo,
 }
 
 impl Msg for MsgTransfer {
@@ -81,6 +84,7 @@ impl TryFrom<RawMsgTransfer> for MsgTransfer {
             receiver: raw_msg.receiver.parse().map_err(Error::signer)?,
             timeout_height,
             timeout_timestamp,
+            memo: raw_msg.memo.into(),
         })
     }
 }
@@ -95,6 +99,7 @@ impl From<MsgTransfer> for RawMsgTransfer {
             receiver: domain_msg.receiver.to_string(),
             timeout_height: domain_msg.timeout_height.into(),
             timeout_timestamp: domain_msg.timeout_timestamp.nanoseconds(),
+            memo: domain_msg.memo.into(),
         }
     }
 }
@@ -159,6 +164,22 @@ pub mod test_util {
             timeout_timestamp: timeout_timestamp
                 .unwrap_or_else(|| Timestamp::now().add(Duration::from_secs(10)).unwrap()),
             timeout_height,
+            memo: Default::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::raw_round_trip_proptests;
+
+    use super::*;
+    use crate::arbitrary::ics20_transfer::{arb_invalid_raw_msg_transfer, arb_msg_transfer};
+
+    raw_round_trip_proptests!(
+        MsgTransfer,
+        RawMsgTransfer,
+        arb_msg_transfer(),
+        arb_invalid_raw_msg_transfer()
+    );
+}
@@ -6,7 +6,7 @@ use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPa
 use serde::{Deserialize, Serialize};
 
 use super::error::Error;
-use super::{Amount, PrefixedCoin, PrefixedDenom};
+use super::{Amount, Memo, PrefixedCoin, PrefixedDenom};
 use crate::signer::Signer;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -15,6 +15,7 @@ pub struct PacketData {
     pub token: PrefixedCoin,
     pub sender: Signer,
     pub receiver: Signer,
+    pub memo: Memo,
 }
 
 impl TryFrom<RawPacketData> for PacketData {
@@ -28,6 +29,7 @@ impl TryFrom<RawPacketData> for PacketData {
             token: PrefixedCoin { denom, amount },
             sender: raw_pkt_data.sender.parse().map_err(Error::signer)?,
             receiver: raw_pkt_data.receiver.parse().map_err(Error::signer)?,
+            memo: raw_pkt_data.memo.into(),
         })
     }
 }
@@ -39,6 +41,23 @@ impl From<PacketData> for RawPacketData {
             amount: pkt_data.token.amount.to_string(),
             sender: pkt_data.sender.to_string(),
             receiver: pkt_data.receiver.to_string(),
+            memo: pkt_data.memo.into(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::raw_round_trip_proptests;
+
+    use super::*;
+    use crate::arbitrary::ics20_transfer::{arb_invalid_raw_packet_data, arb_packet_data};
+    use crate::prelude::*;
+
+    raw_round_trip_proptests!(
+        PacketData,
+        RawPacketData,
+        arb_packet_data(),
+        arb_invalid_raw_packet_data()
+    );
+}
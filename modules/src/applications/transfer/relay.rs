@@ -1,8 +1,8 @@
 //! This module implements the processing logic for ICS20 (token transfer) message.
 use crate::applications::transfer::context::Ics20Context;
 use crate::applications::transfer::error::Error as Ics20Error;
-use crate::applications::transfer::is_sender_chain_source;
 use crate::applications::transfer::packet::PacketData;
+use crate::applications::transfer::{is_sender_chain_source, PrefixedCoin};
 use crate::core::ics04_channel::packet::Packet;
 use crate::prelude::*;
 
@@ -31,10 +31,35 @@ fn refund_packet_token(
         let escrow_address =
             ctx.get_channel_escrow_address(&packet.source_port, &packet.source_channel)?;
 
-        ctx.send_coins(&escrow_address, &sender, &data.token)
+        ctx.send_coins(&escrow_address, &sender, &data.token)?;
+        decrease_total_escrow(ctx, &data.token)
     }
     // mint vouchers back to sender
     else {
         ctx.mint_coins(&sender, &data.token)
     }
 }
+
+/// Adds `coin` to the total escrow of its denomination, upon escrowing it.
+pub(crate) fn increase_total_escrow(
+    ctx: &mut impl Ics20Context,
+    coin: &PrefixedCoin,
+) -> Result<(), Ics20Error> {
+    let total_escrow = ctx
+        .get_total_escrow(&coin.denom)
+        .checked_add(coin.amount)
+        .ok_or_else(|| Ics20Error::total_escrow_overflow(coin.denom.clone()))?;
+    ctx.set_total_escrow(&coin.denom, total_escrow)
+}
+
+/// Removes `coin` from the total escrow of its denomination, upon unescrowing it.
+fn decrease_total_escrow(
+    ctx: &mut impl Ics20Context,
+    coin: &PrefixedCoin,
+) -> Result<(), Ics20Error> {
+    let total_escrow = ctx.get_total_escrow(&coin.denom);
+    let total_escrow = total_escrow.checked_sub(coin.amount).ok_or_else(|| {
+        Ics20Error::insufficient_total_escrow(coin.denom.clone(), coin.amount, total_escrow)
+    })?;
+    ctx.set_total_escrow(&coin.denom, total_escrow)
+}
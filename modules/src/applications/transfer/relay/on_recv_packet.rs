@@ -2,7 +2,8 @@ use crate::applications::transfer::context::Ics20Context;
 use crate::applications::transfer::error::Error as Ics20Error;
 use crate::applications::transfer::events::DenomTraceEvent;
 use crate::applications::transfer::packet::PacketData;
-use crate::applications::transfer::{is_receiver_chain_source, TracePrefix};
+use crate::applications::transfer::relay::decrease_total_escrow;
+use crate::applications::transfer::{is_receiver_chain_source, PrefixedCoin, TracePrefix};
 use crate::core::ics04_channel::packet::Packet;
 use crate::core::ics26_routing::context::{ModuleOutputBuilder, WriteFn};
 use crate::prelude::*;
@@ -42,6 +43,7 @@ pub fn process_recv_packet<Ctx: 'static + Ics20Context>(
         Ok(Box::new(move |ctx| {
             let ctx = ctx.downcast_mut::<Ctx>().unwrap();
             ctx.send_coins(&escrow_address, &receiver_account, &coin)
+                .and_then(|_| decrease_total_escrow(ctx, &coin))
                 .map_err(|e| e.to_string())
         }))
     } else {
@@ -69,3 +71,24 @@ pub fn process_recv_packet<Ctx: 'static + Ics20Context>(
         }))
     }
 }
+
+/// Returns the coin as it is credited on the receiving chain upon receipt of `packet`, i.e. with
+/// the source chain's trace prefix removed if the receiving chain is the source of the token, or
+/// with the receiving chain's trace prefix added otherwise.
+pub fn received_coin(packet: &Packet, mut coin: PrefixedCoin) -> PrefixedCoin {
+    if is_receiver_chain_source(
+        packet.source_port.clone(),
+        packet.source_channel.clone(),
+        &coin.denom,
+    ) {
+        let prefix = TracePrefix::new(packet.source_port.clone(), packet.source_channel.clone());
+        coin.denom.remove_trace_prefix(&prefix);
+    } else {
+        let prefix = TracePrefix::new(
+            packet.destination_port.clone(),
+            packet.destination_channel.clone(),
+        );
+        coin.denom.add_trace_prefix(prefix);
+    }
+    coin
+}
@@ -3,6 +3,7 @@ use crate::applications::transfer::error::Error;
 use crate::applications::transfer::events::TransferEvent;
 use crate::applications::transfer::msgs::transfer::MsgTransfer;
 use crate::applications::transfer::packet::PacketData;
+use crate::applications::transfer::relay::increase_total_escrow;
 use crate::applications::transfer::{is_sender_chain_source, Coin, PrefixedCoin};
 use crate::core::ics04_channel::handler::send_packet::send_packet;
 use crate::core::ics04_channel::packet::Packet;
@@ -61,27 +62,20 @@ where
         .try_into()
         .map_err(|_| Error::parse_account_failure())?;
 
-    if is_sender_chain_source(msg.source_port.clone(), msg.source_channel.clone(), &denom) {
-        let escrow_address =
-            ctx.get_channel_escrow_address(&msg.source_port, &msg.source_channel)?;
-        ctx.send_coins(&sender, &escrow_address, &coin)?;
-    } else {
-        ctx.burn_coins(&sender, &coin)?;
-    }
-
     let data = {
         let data = PacketData {
-            token: coin,
+            token: coin.clone(),
             sender: msg.sender.clone(),
             receiver: msg.receiver.clone(),
+            memo: msg.memo,
         };
         serde_json::to_vec(&data).expect("PacketData's infallible Serialize impl failed")
     };
 
     let packet = Packet {
         sequence,
-        source_port: msg.source_port,
-        source_channel: msg.source_channel,
+        source_port: msg.source_port.clone(),
+        source_channel: msg.source_channel.clone(),
         destination_port,
         destination_channel,
         data,
@@ -95,6 +89,18 @@ where
         events,
     } = send_packet(ctx, packet).map_err(Error::ics04_channel)?;
 
+    // The tokens are only moved once the packet is known to be sendable, so that a failed
+    // transfer leaves no state modification behind, e.g. when it is attempted on behalf of the
+    // packet forwarding middleware.
+    if is_sender_chain_source(msg.source_port.clone(), msg.source_channel.clone(), &denom) {
+        let escrow_address =
+            ctx.get_channel_escrow_address(&msg.source_port, &msg.source_channel)?;
+        ctx.send_coins(&sender, &escrow_address, &coin)?;
+        increase_total_escrow(ctx, &coin)?;
+    } else {
+        ctx.burn_coins(&sender, &coin)?;
+    }
+
     ctx.store_packet_result(result)
         .map_err(Error::ics04_channel)?;
 
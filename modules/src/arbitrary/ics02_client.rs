@@ -0,0 +1,375 @@
+//! Strategies for the ICS 02 client types, the Tendermint client states and consensus states, and
+//! the client messages.
+
+use core::time::Duration;
+
+use ibc_proto::ibc::core::client::v1::{
+    Height as RawHeight, MsgCreateClient as RawMsgCreateClient,
+    MsgUpgradeClient as RawMsgUpgradeClient,
+};
+use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
+use ibc_proto::ibc::lightclients::tendermint::v1::{
+    ClientState as RawClientState, ConsensusState as RawConsensusState,
+};
+use ibc_proto::ics23::commitment_proof::Proof;
+use ibc_proto::ics23::{CommitmentProof, ExistenceProof};
+use proptest::collection::vec;
+use proptest::prelude::*;
+use tendermint::{hash::Algorithm, Hash};
+
+use crate::clients::ics07_tendermint::client_state::{AllowUpdate, ClientState};
+use crate::clients::ics07_tendermint::consensus_state::ConsensusState;
+use crate::core::ics02_client::client_consensus::AnyConsensusState;
+use crate::core::ics02_client::client_state::AnyClientState;
+use crate::core::ics02_client::height::Height;
+use crate::core::ics02_client::msgs::create_client::MsgCreateAnyClient;
+use crate::core::ics02_client::msgs::upgrade_client::MsgUpgradeAnyClient;
+use crate::core::ics02_client::trust_threshold::TrustThreshold;
+use crate::core::ics23_commitment::specs::ProofSpecs;
+use crate::prelude::*;
+
+use crate::arbitrary::ics24_host::{arb_chain_id, arb_client_id, arb_invalid_client_id};
+use crate::arbitrary::{arb_invalid_signer, arb_signer, arb_tm_time};
+
+/// Upper bound, in seconds, of the periods of the generated Tendermint client states.
+const MAX_PERIOD_SECS: u64 = 10 * 365 * 24 * 3600;
+
+/// Returns a strategy for a [`Height`].
+pub fn arb_height() -> impl Strategy<Value = Height> {
+    (any::<u64>(), 1..=u64::MAX).prop_map(|(revision_number, revision_height)| {
+        Height::new(revision_number, revision_height).unwrap()
+    })
+}
+
+/// Returns a strategy for raw heights that are rejected as a [`Height`], i.e. heights with a zero
+/// revision height.
+pub fn arb_invalid_raw_height() -> impl Strategy<Value = RawHeight> {
+    any::<u64>().prop_map(|revision_number| RawHeight {
+        revision_number,
+        revision_height: 0,
+    })
+}
+
+/// Returns a strategy for a non-zero [`TrustThreshold`].
+pub fn arb_trust_threshold() -> impl Strategy<Value = TrustThreshold> {
+    (2..=100u64)
+        .prop_flat_map(|denominator| (1..denominator, Just(denominator)))
+        .prop_map(|(numerator, denominator)| TrustThreshold::new(numerator, denominator).unwrap())
+}
+
+/// Returns a strategy for a Tendermint [`ClientState`], which may be frozen.
+pub fn arb_tm_client_state() -> impl Strategy<Value = ClientState> {
+    (
+        arb_chain_id(),
+        arb_trust_threshold(),
+        (1..MAX_PERIOD_SECS, 1..MAX_PERIOD_SECS, 0..MAX_PERIOD_SECS),
+        arb_height(),
+        vec("[a-zA-Z]{1,16}", 0..3),
+        any::<(bool, bool)>(),
+        proptest::option::of(arb_height()),
+    )
+        .prop_map(
+            |(
+                chain_id,
+                trust_level,
+                (trusting_period, extra_unbonding_period, max_clock_drift),
+                latest_height,
+                upgrade_path,
+                (after_expiry, after_misbehaviour),
+                frozen_height,
+            )| {
+                let client_state = ClientState::new(
+                    chain_id,
+                    trust_level,
+                    Duration::from_secs(trusting_period),
+                    Duration::from_secs(trusting_period + extra_unbonding_period),
+                    Duration::from_secs(max_clock_drift),
+                    latest_height,
+                    ProofSpecs::default(),
+                    upgrade_path,
+                    AllowUpdate {
+                        after_expiry,
+                        after_misbehaviour,
+                    },
+                )
+                .unwrap();
+
+                ClientState {
+                    frozen_height,
+                    ..client_state
+                }
+            },
+        )
+}
+
+/// Returns a strategy for raw Tendermint client states that are rejected as a [`ClientState`]:
+/// valid client states with one of their required fields removed.
+pub fn arb_invalid_raw_tm_client_state() -> impl Strategy<Value = RawClientState> {
+    (arb_tm_client_state(), 0..5usize).prop_map(|(client_state, field)| {
+        let mut raw = RawClientState::from(client_state);
+        match field {
+            0 => raw.trust_level = None,
+            1 => raw.trusting_period = None,
+            2 => raw.unbonding_period = None,
+            3 => raw.max_clock_drift = None,
+            _ => raw.latest_height = None,
+        }
+        raw
+    })
+}
+
+/// Returns a strategy for a Tendermint [`ConsensusState`].
+pub fn arb_tm_consensus_state() -> impl Strategy<Value = ConsensusState> {
+    (arb_tm_time(), vec(any::<u8>(), 0..64), any::<[u8; 32]>()).prop_map(
+        |(timestamp, root, next_validators_hash)| {
+            ConsensusState::new(
+                root.into(),
+                timestamp,
+                Hash::from_bytes(Algorithm::Sha256, &next_validators_hash).unwrap(),
+            )
+        },
+    )
+}
+
+/// Returns a strategy for raw Tendermint consensus states that are rejected as a
+/// [`ConsensusState`]: valid consensus states with a missing field, or with a next validators
+/// hash that is not a SHA-256 digest.
+pub fn arb_invalid_raw_tm_consensus_state() -> impl Strategy<Value = RawConsensusState> {
+    (arb_tm_consensus_state(), 0..3usize).prop_map(|(consensus_state, field)| {
+        let mut raw = RawConsensusState::from(consensus_state);
+        match field {
+            0 => raw.timestamp = None,
+            1 => raw.root = None,
+            _ => {
+                raw.next_validators_hash.pop();
+            }
+        }
+        raw
+    })
+}
+
+/// Returns a strategy for an [`AnyClientState`].
+pub fn arb_any_client_state() -> impl Strategy<Value = AnyClientState> {
+    arb_tm_client_state().prop_map(AnyClientState::Tendermint)
+}
+
+/// Returns a strategy for an [`AnyConsensusState`].
+pub fn arb_any_consensus_state() -> impl Strategy<Value = AnyConsensusState> {
+    arb_tm_consensus_state().prop_map(AnyConsensusState::Tendermint)
+}
+
+/// Returns a strategy for a non-empty raw Merkle proof, as found in `MsgUpgradeAnyClient`.
+pub fn arb_raw_merkle_proof() -> impl Strategy<Value = RawMerkleProof> {
+    vec(
+        (vec(any::<u8>(), 1..32), vec(any::<u8>(), 1..32)).prop_map(|(key, value)| {
+            CommitmentProof {
+                proof: Some(Proof::Exist(ExistenceProof {
+                    key,
+                    value,
+                    leaf: None,
+                    path: vec![],
+                })),
+            }
+        }),
+        1..3,
+    )
+    .prop_map(|proofs| RawMerkleProof { proofs })
+}
+
+/// Returns a strategy for a [`MsgCreateAnyClient`].
+pub fn arb_msg_create_client() -> impl Strategy<Value = MsgCreateAnyClient> {
+    (
+        arb_any_client_state(),
+        arb_any_consensus_state(),
+        arb_signer(),
+    )
+        .prop_map(|(client_state, consensus_state, signer)| {
+            MsgCreateAnyClient::new(client_state, consensus_state, signer).unwrap()
+        })
+}
+
+/// Returns a strategy for raw messages that are rejected as a [`MsgCreateAnyClient`].
+pub fn arb_invalid_raw_msg_create_client() -> impl Strategy<Value = RawMsgCreateClient> {
+    (arb_msg_create_client(), 0..3usize, arb_invalid_signer()).prop_map(|(msg, field, signer)| {
+        let mut raw = RawMsgCreateClient::from(msg);
+        match field {
+            0 => raw.client_state = None,
+            1 => raw.consensus_state = None,
+            _ => raw.signer = signer,
+        }
+        raw
+    })
+}
+
+/// Returns a strategy for a [`MsgUpgradeAnyClient`].
+pub fn arb_msg_upgrade_client() -> impl Strategy<Value = MsgUpgradeAnyClient> {
+    (
+        arb_client_id(),
+        arb_any_client_state(),
+        arb_any_consensus_state(),
+        arb_raw_merkle_proof(),
+        arb_raw_merkle_proof(),
+        arb_signer(),
+    )
+        .prop_map(
+            |(
+                client_id,
+                client_state,
+                consensus_state,
+                proof_upgrade_client,
+                proof_upgrade_consensus_state,
+                signer,
+            )| MsgUpgradeAnyClient {
+                client_id,
+                client_state,
+                consensus_state,
+                proof_upgrade_client,
+                proof_upgrade_consensus_state,
+                signer,
+            },
+        )
+}
+
+/// Returns a strategy for raw messages that are rejected as a [`MsgUpgradeAnyClient`].
+pub fn arb_invalid_raw_msg_upgrade_client() -> impl Strategy<Value = RawMsgUpgradeClient> {
+    (
+        arb_msg_upgrade_client(),
+        0..4usize,
+        arb_invalid_client_id(),
+        arb_invalid_signer(),
+    )
+        .prop_map(|(msg, field, client_id, signer)| {
+            let mut raw = RawMsgUpgradeClient::from(msg);
+            match field {
+                0 => raw.client_id = client_id,
+                1 => raw.proof_upgrade_client = vec![],
+                2 => raw.client_state = None,
+                _ => raw.signer = signer,
+            }
+            raw
+        })
+}
+
+#[cfg(any(test, feature = "mocks"))]
+pub use self::mock::*;
+
+#[cfg(any(test, feature = "mocks"))]
+mod mock {
+    use ibc_proto::ibc::core::client::v1::{
+        MsgSubmitMisbehaviour as RawMsgSubmitMisbehaviour, MsgUpdateClient as RawMsgUpdateClient,
+    };
+    use proptest::prelude::*;
+
+    use crate::core::ics02_client::client_consensus::AnyConsensusState;
+    use crate::core::ics02_client::client_state::AnyClientState;
+    use crate::core::ics02_client::header::AnyHeader;
+    use crate::core::ics02_client::misbehaviour::AnyMisbehaviour;
+    use crate::core::ics02_client::msgs::misbehavior::MsgSubmitAnyMisbehaviour;
+    use crate::core::ics02_client::msgs::update_client::MsgUpdateAnyClient;
+    use crate::mock::client_state::{MockClientState, MockConsensusState};
+    use crate::mock::header::MockHeader;
+    use crate::mock::misbehaviour::Misbehaviour as MockMisbehaviour;
+    use crate::prelude::*;
+
+    use crate::arbitrary::ics02_client::arb_height;
+    use crate::arbitrary::ics24_host::{arb_client_id, arb_invalid_client_id};
+    use crate::arbitrary::{arb_invalid_signer, arb_signer, arb_timestamp};
+
+    /// Returns a strategy for a [`MockHeader`].
+    pub fn arb_mock_header() -> impl Strategy<Value = MockHeader> {
+        (arb_height(), arb_timestamp())
+            .prop_map(|(height, timestamp)| MockHeader { height, timestamp })
+    }
+
+    /// Returns a strategy for an unfrozen [`MockClientState`]. The raw mock client state does not
+    /// carry a frozen height.
+    pub fn arb_mock_client_state() -> impl Strategy<Value = MockClientState> {
+        arb_mock_header().prop_map(MockClientState::new)
+    }
+
+    /// Returns a strategy for a [`MockConsensusState`].
+    pub fn arb_mock_consensus_state() -> impl Strategy<Value = MockConsensusState> {
+        arb_mock_header().prop_map(MockConsensusState::new)
+    }
+
+    /// Returns a strategy for a mock [`MockMisbehaviour`]. The raw mock misbehaviour does not carry
+    /// a client identifier, so the default one is used.
+    pub fn arb_mock_misbehaviour() -> impl Strategy<Value = MockMisbehaviour> {
+        (arb_mock_header(), arb_mock_header()).prop_map(|(header1, header2)| MockMisbehaviour {
+            client_id: Default::default(),
+            header1,
+            header2,
+        })
+    }
+
+    /// Returns a strategy for a mock [`AnyClientState`].
+    pub fn arb_any_mock_client_state() -> impl Strategy<Value = AnyClientState> {
+        arb_mock_client_state().prop_map(AnyClientState::Mock)
+    }
+
+    /// Returns a strategy for a mock [`AnyConsensusState`].
+    pub fn arb_any_mock_consensus_state() -> impl Strategy<Value = AnyConsensusState> {
+        arb_mock_consensus_state().prop_map(AnyConsensusState::Mock)
+    }
+
+    /// Returns a strategy for a [`MsgUpdateAnyClient`] carrying a mock header.
+    pub fn arb_msg_update_client() -> impl Strategy<Value = MsgUpdateAnyClient> {
+        (arb_client_id(), arb_mock_header(), arb_signer()).prop_map(
+            |(client_id, header, signer)| MsgUpdateAnyClient {
+                client_id,
+                header: AnyHeader::Mock(header),
+                signer,
+            },
+        )
+    }
+
+    /// Returns a strategy for raw messages that are rejected as a [`MsgUpdateAnyClient`].
+    pub fn arb_invalid_raw_msg_update_client() -> impl Strategy<Value = RawMsgUpdateClient> {
+        (
+            arb_msg_update_client(),
+            0..3usize,
+            arb_invalid_client_id(),
+            arb_invalid_signer(),
+        )
+            .prop_map(|(msg, field, client_id, signer)| {
+                let mut raw = RawMsgUpdateClient::from(msg);
+                match field {
+                    0 => raw.client_id = client_id,
+                    1 => raw.header = None,
+                    _ => raw.signer = signer,
+                }
+                raw
+            })
+    }
+
+    /// Returns a strategy for a [`MsgSubmitAnyMisbehaviour`] carrying mock misbehaviour.
+    pub fn arb_msg_submit_misbehaviour() -> impl Strategy<Value = MsgSubmitAnyMisbehaviour> {
+        (arb_client_id(), arb_mock_misbehaviour(), arb_signer()).prop_map(
+            |(client_id, misbehaviour, signer)| MsgSubmitAnyMisbehaviour {
+                client_id,
+                misbehaviour: AnyMisbehaviour::Mock(misbehaviour),
+                signer,
+            },
+        )
+    }
+
+    /// Returns a strategy for raw messages that are rejected as a [`MsgSubmitAnyMisbehaviour`].
+    pub fn arb_invalid_raw_msg_submit_misbehaviour(
+    ) -> impl Strategy<Value = RawMsgSubmitMisbehaviour> {
+        (
+            arb_msg_submit_misbehaviour(),
+            0..3usize,
+            arb_invalid_client_id(),
+            arb_invalid_signer(),
+        )
+            .prop_map(|(msg, field, client_id, signer)| {
+                let mut raw = RawMsgSubmitMisbehaviour::from(msg);
+                match field {
+                    0 => raw.client_id = client_id,
+                    1 => raw.misbehaviour = None,
+                    _ => raw.signer = signer,
+                }
+                raw
+            })
+    }
+}
@@ -0,0 +1,304 @@
+//! Strategies for the ICS 03 connection types and messages.
+
+use core::time::Duration;
+
+use ibc_proto::ibc::core::connection::v1::{
+    ConnectionEnd as RawConnectionEnd, MsgConnectionOpenAck as RawMsgConnectionOpenAck,
+    MsgConnectionOpenConfirm as RawMsgConnectionOpenConfirm,
+    MsgConnectionOpenInit as RawMsgConnectionOpenInit,
+    MsgConnectionOpenTry as RawMsgConnectionOpenTry, Version as RawVersion,
+};
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+use crate::core::ics03_connection::connection::{
+    ConnectionEnd, Counterparty, IdentifiedConnectionEnd, State,
+};
+use crate::core::ics03_connection::msgs::conn_open_ack::MsgConnectionOpenAck;
+use crate::core::ics03_connection::msgs::conn_open_confirm::MsgConnectionOpenConfirm;
+use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+use crate::core::ics03_connection::msgs::conn_open_try::MsgConnectionOpenTry;
+use crate::core::ics03_connection::version::Version;
+use crate::prelude::*;
+
+use crate::arbitrary::ics02_client::{arb_any_client_state, arb_invalid_raw_height};
+use crate::arbitrary::ics24_host::{
+    arb_client_id, arb_connection_id, arb_invalid_client_id, arb_invalid_connection_id,
+};
+use crate::arbitrary::{arb_connection_proofs, arb_invalid_signer, arb_proofs, arb_signer};
+
+/// Returns a strategy for a connection [`Version`].
+pub fn arb_version() -> impl Strategy<Value = Version> {
+    ("[a-zA-Z0-9._-]{1,16}", vec("[A-Z_]{1,24}", 0..4)).prop_map(|(identifier, features)| {
+        RawVersion {
+            identifier,
+            features,
+        }
+        .try_into()
+        .unwrap()
+    })
+}
+
+/// Returns a strategy for raw versions that are rejected as a [`Version`], i.e. versions with a
+/// blank identifier or a blank feature.
+pub fn arb_invalid_raw_version() -> impl Strategy<Value = RawVersion> {
+    (arb_version(), any::<bool>(), "[ \t]{0,2}").prop_map(|(version, blank_identifier, blank)| {
+        let mut raw = RawVersion::from(version);
+        if blank_identifier {
+            raw.identifier = blank;
+        } else {
+            raw.features.push(blank);
+        }
+        raw
+    })
+}
+
+/// Returns a strategy for a connection [`State`], other than `Uninitialized`.
+pub fn arb_connection_state() -> impl Strategy<Value = State> {
+    prop_oneof![Just(State::Init), Just(State::TryOpen), Just(State::Open)]
+}
+
+/// Returns a strategy for a connection [`Counterparty`].
+pub fn arb_connection_counterparty() -> impl Strategy<Value = Counterparty> {
+    (
+        arb_client_id(),
+        proptest::option::of(arb_connection_id()),
+        vec(any::<u8>(), 1..16),
+    )
+        .prop_map(|(client_id, connection_id, prefix)| {
+            Counterparty::new(client_id, connection_id, prefix.try_into().unwrap())
+        })
+}
+
+/// Returns a strategy for an initialized [`ConnectionEnd`].
+pub fn arb_connection_end() -> impl Strategy<Value = ConnectionEnd> {
+    (
+        arb_connection_state(),
+        arb_client_id(),
+        arb_connection_counterparty(),
+        vec(arb_version(), 0..3),
+        any::<u64>(),
+    )
+        .prop_map(|(state, client_id, counterparty, versions, delay_period)| {
+            ConnectionEnd::new(
+                state,
+                client_id,
+                counterparty,
+                versions,
+                Duration::from_nanos(delay_period),
+            )
+        })
+}
+
+/// Returns a strategy for raw connection ends that are rejected as a [`ConnectionEnd`].
+pub fn arb_invalid_raw_connection_end() -> impl Strategy<Value = RawConnectionEnd> {
+    (
+        arb_connection_end(),
+        0..4usize,
+        arb_invalid_client_id(),
+        arb_invalid_raw_version(),
+    )
+        .prop_map(|(connection_end, field, client_id, version)| {
+            let mut raw = RawConnectionEnd::from(connection_end);
+            match field {
+                0 => raw.client_id = client_id,
+                1 => raw.counterparty = None,
+                2 => raw.versions.push(version),
+                _ => raw.state = 4,
+            }
+            raw
+        })
+}
+
+/// Returns a strategy for an [`IdentifiedConnectionEnd`].
+pub fn arb_identified_connection_end() -> impl Strategy<Value = IdentifiedConnectionEnd> {
+    (arb_connection_id(), arb_connection_end()).prop_map(|(connection_id, connection_end)| {
+        IdentifiedConnectionEnd::new(connection_id, connection_end)
+    })
+}
+
+/// Returns a strategy for a [`MsgConnectionOpenInit`].
+pub fn arb_msg_connection_open_init() -> impl Strategy<Value = MsgConnectionOpenInit> {
+    (
+        arb_client_id(),
+        arb_connection_counterparty(),
+        proptest::option::of(arb_version()),
+        any::<u64>(),
+        arb_signer(),
+    )
+        .prop_map(|(client_id, counterparty, version, delay_period, signer)| {
+            MsgConnectionOpenInit {
+                client_id,
+                counterparty,
+                version,
+                delay_period: Duration::from_nanos(delay_period),
+                signer,
+            }
+        })
+}
+
+/// Returns a strategy for raw messages that are rejected as a [`MsgConnectionOpenInit`].
+pub fn arb_invalid_raw_msg_connection_open_init() -> impl Strategy<Value = RawMsgConnectionOpenInit>
+{
+    (
+        arb_msg_connection_open_init(),
+        0..4usize,
+        arb_invalid_client_id(),
+        arb_invalid_raw_version(),
+        arb_invalid_signer(),
+    )
+        .prop_map(|(msg, field, client_id, version, signer)| {
+            let mut raw = RawMsgConnectionOpenInit::from(msg);
+            match field {
+                0 => raw.client_id = client_id,
+                1 => raw.counterparty = None,
+                2 => raw.version = Some(version),
+                _ => raw.signer = signer,
+            }
+            raw
+        })
+}
+
+/// Returns a strategy for a [`MsgConnectionOpenTry`].
+pub fn arb_msg_connection_open_try() -> impl Strategy<Value = MsgConnectionOpenTry> {
+    (
+        proptest::option::of(arb_connection_id()),
+        arb_client_id(),
+        proptest::option::of(arb_any_client_state()),
+        arb_connection_counterparty(),
+        vec(arb_version(), 1..3),
+        arb_connection_proofs(),
+        any::<u64>(),
+        arb_signer(),
+    )
+        .prop_map(
+            |(
+                previous_connection_id,
+                client_id,
+                client_state,
+                counterparty,
+                counterparty_versions,
+                proofs,
+                delay_period,
+                signer,
+            )| MsgConnectionOpenTry {
+                previous_connection_id,
+                client_id,
+                client_state,
+                counterparty,
+                counterparty_versions,
+                proofs,
+                delay_period: Duration::from_nanos(delay_period),
+                signer,
+            },
+        )
+}
+
+/// Returns a strategy for raw messages that are rejected as a [`MsgConnectionOpenTry`].
+pub fn arb_invalid_raw_msg_connection_open_try() -> impl Strategy<Value = RawMsgConnectionOpenTry> {
+    (
+        arb_msg_connection_open_try(),
+        0..8usize,
+        arb_invalid_client_id(),
+        arb_invalid_connection_id(),
+        arb_invalid_raw_height(),
+        arb_invalid_signer(),
+    )
+        .prop_map(
+            |(msg, field, client_id, previous_connection_id, proof_height, signer)| {
+                let mut raw = RawMsgConnectionOpenTry::from(msg);
+                match field {
+                    0 => raw.client_id = client_id,
+                    1 => raw.previous_connection_id = previous_connection_id,
+                    2 => raw.counterparty = None,
+                    3 => raw.counterparty_versions = vec![],
+                    4 => raw.proof_init = vec![],
+                    5 => raw.proof_client = vec![],
+                    6 => raw.proof_height = Some(proof_height),
+                    _ => raw.signer = signer,
+                }
+                raw
+            },
+        )
+}
+
+/// Returns a strategy for a [`MsgConnectionOpenAck`].
+pub fn arb_msg_connection_open_ack() -> impl Strategy<Value = MsgConnectionOpenAck> {
+    (
+        arb_connection_id(),
+        arb_connection_id(),
+        proptest::option::of(arb_any_client_state()),
+        arb_connection_proofs(),
+        arb_version(),
+        arb_signer(),
+    )
+        .prop_map(
+            |(connection_id, counterparty_connection_id, client_state, proofs, version, signer)| {
+                MsgConnectionOpenAck {
+                    connection_id,
+                    counterparty_connection_id,
+                    client_state,
+                    proofs,
+                    version,
+                    signer,
+                }
+            },
+        )
+}
+
+/// Returns a strategy for raw messages that are rejected as a [`MsgConnectionOpenAck`].
+pub fn arb_invalid_raw_msg_connection_open_ack() -> impl Strategy<Value = RawMsgConnectionOpenAck> {
+    (
+        arb_msg_connection_open_ack(),
+        0..7usize,
+        arb_invalid_connection_id(),
+        arb_invalid_raw_height(),
+        arb_invalid_signer(),
+    )
+        .prop_map(|(msg, field, connection_id, height, signer)| {
+            let mut raw = RawMsgConnectionOpenAck::from(msg);
+            match field {
+                0 => raw.connection_id = connection_id,
+                1 => raw.counterparty_connection_id = connection_id,
+                2 => raw.version = None,
+                3 => raw.proof_try = vec![],
+                4 => raw.proof_consensus = vec![],
+                5 => raw.consensus_height = Some(height),
+                _ => raw.signer = signer,
+            }
+            raw
+        })
+}
+
+/// Returns a strategy for a [`MsgConnectionOpenConfirm`].
+pub fn arb_msg_connection_open_confirm() -> impl Strategy<Value = MsgConnectionOpenConfirm> {
+    (arb_connection_id(), arb_proofs(), arb_signer()).prop_map(|(connection_id, proofs, signer)| {
+        MsgConnectionOpenConfirm {
+            connection_id,
+            proofs,
+            signer,
+        }
+    })
+}
+
+/// Returns a strategy for raw messages that are rejected as a [`MsgConnectionOpenConfirm`].
+pub fn arb_invalid_raw_msg_connection_open_confirm(
+) -> impl Strategy<Value = RawMsgConnectionOpenConfirm> {
+    (
+        arb_msg_connection_open_confirm(),
+        0..4usize,
+        arb_invalid_connection_id(),
+        arb_invalid_raw_height(),
+        arb_invalid_signer(),
+    )
+        .prop_map(|(msg, field, connection_id, proof_height, signer)| {
+            let mut raw = RawMsgConnectionOpenConfirm::from(msg);
+            match field {
+                0 => raw.connection_id = connection_id,
+                1 => raw.proof_ack = vec![],
+                2 => raw.proof_height = Some(proof_height),
+                _ => raw.signer = signer,
+            }
+            raw
+        })
+}
@@ -0,0 +1,582 @@
+//! Strategies for the ICS 04 channel and packet types and messages.
+
+use ibc_proto::ibc::core::channel::v1::{
+    Channel as RawChannel, MsgAcknowledgement as RawMsgAcknowledgement,
+    MsgChannelCloseConfirm as RawMsgChannelCloseConfirm,
+    MsgChannelCloseInit as RawMsgChannelCloseInit, MsgChannelOpenAck as RawMsgChannelOpenAck,
+    MsgChannelOpenConfirm as RawMsgChannelOpenConfirm, MsgChannelOpenInit as RawMsgChannelOpenInit,
+    MsgChannelOpenTry as RawMsgChannelOpenTry, MsgRecvPacket as RawMsgRecvPacket,
+    MsgTimeout as RawMsgTimeout, MsgTimeoutOnClose as RawMsgTimeoutOnClose, Packet as RawPacket,
+};
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+use crate::core::ics04_channel::channel::{
+    ChannelEnd, Counterparty, IdentifiedChannelEnd, Order, State,
+};
+use crate::core::ics04_channel::msgs::acknowledgement::{Acknowledgement, MsgAcknowledgement};
+use crate::core::ics04_channel::msgs::chan_close_confirm::MsgChannelCloseConfirm;
+use crate::core::ics04_channel::msgs::chan_close_init::MsgChannelCloseInit;
+use crate::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
+use crate::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
+use crate::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+use crate::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
+use crate::core::ics04_channel::msgs::timeout::MsgTimeout;
+use crate::core::ics04_channel::msgs::timeout_on_close::MsgTimeoutOnClose;
+use crate::core::ics04_channel::packet::{Packet, Sequence};
+use crate::core::ics04_channel::timeout::TimeoutHeight;
+use crate::core::ics04_channel::Version;
+use crate::prelude::*;
+
+use crate::arbitrary::ics02_client::{arb_height, arb_invalid_raw_height};
+use crate::arbitrary::ics24_host::{
+    arb_channel_id, arb_connection_id, arb_invalid_channel_id, arb_invalid_connection_id,
+    arb_invalid_port_id, arb_port_id,
+};
+use crate::arbitrary::{
+    arb_invalid_signer, arb_proofs, arb_proofs_with_other, arb_signer, arb_timestamp,
+};
+
+/// Returns a strategy for a channel [`Order`], other than `None`.
+pub fn arb_order() -> impl Strategy<Value = Order> {
+    prop_oneof![
+        Just(Order::Unordered),
+        Just(Order::Ordered),
+        Just(Order::OrderedAllowTimeout),
+    ]
+}
+
+/// Returns a strategy for a channel [`State`], other than `Uninitialized`.
+pub fn arb_channel_state() -> impl Strategy<Value = State> {
+    prop_oneof![
+        Just(State::Init),
+        Just(State::TryOpen),
+        Just(State::Open),
+        Just(State::Closed),
+        Just(State::Flushing),
+        Just(State::FlushComplete),
+    ]
+}
+
+/// Returns a strategy for a channel [`Version`].
+pub fn arb_channel_version() -> impl Strategy<Value = Version> {
+    prop_oneof![
+        Just(Version::ics20()),
+        "[a-z0-9-]{0,16}".prop_map(Version::new)
+    ]
+}
+
+/// Returns a strategy for a channel [`Counterparty`].
+pub fn arb_channel_counterparty() -> impl Strategy<Value = Counterparty> {
+    (arb_port_id(), proptest::option::of(arb_channel_id()))
+        .prop_map(|(port_id, channel_id)| Counterparty::new(port_id, channel_id))
+}
+
+/// Returns a strategy for an initialized [`ChannelEnd`] with a single connection hop.
+pub fn arb_channel_end() -> impl Strategy<Value = ChannelEnd> {
+    (
+        arb_channel_state(),
+        arb_order(),
+        arb_channel_counterparty(),
+        arb_connection_id(),
+        arb_channel_version(),
+    )
+        .prop_map(|(state, ordering, remote, connection_id, version)| {
+            ChannelEnd::new(state, ordering, remote, vec![connection_id], version)
+        })
+}
+
+/// Returns a strategy for raw channel ends that are rejected as a [`ChannelEnd`].
+pub fn arb_invalid_raw_channel_end() -> impl Strategy<Value = RawChannel> {
+    (
+        arb_channel_end(),
+        0..5usize,
+        arb_invalid_connection_id(),
+        arb_invalid_port_id(),
+    )
+        .prop_map(|(channel_end, field, connection_id, port_id)| {
+            let mut raw = RawChannel::from(channel_end);
+            match field {
+                0 => raw.connection_hops.push(connection_id),
+                1 => raw.counterparty = None,
+                2 => raw.counterparty.as_mut().unwrap().port_id = port_id,
+                3 => raw.ordering = 4,
+                _ => raw.state = 7,
+            }
+            raw
+        })
+}
+
+/// Returns a strategy for an [`IdentifiedChannelEnd`].
+pub fn arb_identified_channel_end() -> impl Strategy<Value = IdentifiedChannelEnd> {
+    (arb_port_id(), arb_channel_id(), arb_channel_end()).prop_map(
+        |(port_id, channel_id, channel_end)| {
+            IdentifiedChannelEnd::new(port_id, channel_id, channel_end)
+        },
+    )
+}
+
+/// Returns a strategy for a non-zero packet [`Sequence`].
+pub fn arb_sequence() -> impl Strategy<Value = Sequence> {
+    (1..=u64::MAX).prop_map(Sequence::from)
+}
+
+/// Returns a strategy for a packet [`TimeoutHeight`].
+pub fn arb_timeout_height() -> impl Strategy<Value = TimeoutHeight> {
+    prop_oneof![
+        Just(TimeoutHeight::Never),
+        arb_height().prop_map(TimeoutHeight::At),
+    ]
+}
+
+/// Returns a strategy for a [`Packet`].
+pub fn arb_packet() -> impl Strategy<Value = Packet> {
+    (
+        arb_sequence(),
+        (arb_port_id(), arb_channel_id()),
+        (arb_port_id(), arb_channel_id()),
+        vec(any::<u8>(), 1..128),
+        arb_timeout_height(),
+        arb_timestamp(),
+    )
+        .prop_map(
+            |(
+                sequence,
+                (source_port, source_channel),
+                (destination_port, destination_channel),
+                data,
+                timeout_height,
+                timeout_timestamp,
+            )| Packet {
+                sequence,
+                source_port,
+                source_channel,
+                destination_port,
+                destination_channel,
+                data,
+                timeout_height,
+                timeout_timestamp,
+            },
+        )
+}
+
+/// Returns a strategy for raw packets that are rejected as a [`Packet`].
+pub fn arb_invalid_raw_packet() -> impl Strategy<Value = RawPacket> {
+    (
+        arb_packet(),
+        0..5usize,
+        arb_invalid_port_id(),
+        arb_invalid_channel_id(),
+        arb_invalid_raw_height(),
+    )
+        .prop_map(|(packet, field, port_id, channel_id, timeout_height)| {
+            let mut raw = RawPacket::from(packet);
+            match field {
+                0 => raw.sequence = 0,
+                1 => raw.data = vec![],
+                2 => raw.source_port = port_id,
+                3 => raw.destination_channel = channel_id,
+                // `{0, 0}` stands for no timeout, so only heights with a non-zero revision number
+                // are rejected
+                _ => {
+                    raw.timeout_height = Some(RawHeight {
+                        revision_number: timeout_height.revision_number.max(1),
+                        ..timeout_height
+                    })
+                }
+            }
+            raw
+        })
+}
+
+/// Returns a strategy for a packet [`Acknowledgement`].
+pub fn arb_acknowledgement() -> impl Strategy<Value = Acknowledgement> {
+    vec(any::<u8>(), 1..64).prop_map(Acknowledgement::from_bytes)
+}
+
+/// Returns a strategy for a [`MsgChannelOpenInit`].
+pub fn arb_msg_channel_open_init() -> impl Strategy<Value = MsgChannelOpenInit> {
+    (arb_port_id(), arb_channel_end(), arb_signer()).prop_map(|(port_id, channel, signer)| {
+        MsgChannelOpenInit {
+            port_id,
+            channel,
+            signer,
+        }
+    })
+}
+
+/// Returns a strategy for raw messages that are rejected as a [`MsgChannelOpenInit`].
+pub fn arb_invalid_raw_msg_channel_open_init() -> impl Strategy<Value = RawMsgChannelOpenInit> {
+    (
+        arb_msg_channel_open_init(),
+        0..3usize,
+        arb_invalid_port_id(),
+        arb_invalid_signer(),
+    )
+        .prop_map(|(msg, field, port_id, signer)| {
+            let mut raw = RawMsgChannelOpenInit::from(msg);
+            match field {
+                0 => raw.port_id = port_id,
+                1 => raw.channel = None,
+                _ => raw.signer = signer,
+            }
+            raw
+        })
+}
+
+/// Returns a strategy for a [`MsgChannelOpenTry`], whose channel end knows its counterparty
+/// channel identifier.
+pub fn arb_msg_channel_open_try() -> impl Strategy<Value = MsgChannelOpenTry> {
+    (
+        arb_port_id(),
+        proptest::option::of(arb_channel_id()),
+        arb_channel_end(),
+        arb_channel_id(),
+        arb_channel_version(),
+        arb_proofs(),
+        arb_signer(),
+    )
+        .prop_map(
+            |(
+                port_id,
+                previous_channel_id,
+                mut channel,
+                counterparty_channel_id,
+                counterparty_version,
+                proofs,
+                signer,
+            )| {
+                channel.set_counterparty_channel_id(counterparty_channel_id);
+                MsgChannelOpenTry::new(
+                    port_id,
+                    previous_channel_id,
+                    channel,
+                    counterparty_version,
+                    proofs,
+                    signer,
+                )
+            },
+        )
+}
+
+/// Returns a strategy for raw messages that are rejected as a [`MsgChannelOpenTry`].
+pub fn arb_invalid_raw_msg_channel_open_try() -> impl Strategy<Value = RawMsgChannelOpenTry> {
+    (
+        arb_msg_channel_open_try(),
+        0..6usize,
+        arb_invalid_port_id(),
+        arb_invalid_raw_height(),
+        arb_invalid_signer(),
+    )
+        .prop_map(|(msg, field, port_id, proof_height, signer)| {
+            let mut raw = RawMsgChannelOpenTry::from(msg);
+            match field {
+                0 => raw.port_id = port_id,
+                1 => {
+                    raw.channel
+                        .as_mut()
+                        .unwrap()
+                        .counterparty
+                        .as_mut()
+                        .unwrap()
+                        .channel_id = String::new()
+                }
+                2 => raw.proof_init = vec![],
+                3 => raw.proof_height = Some(proof_height),
+                4 => raw.channel = None,
+                _ => raw.signer = signer,
+            }
+            raw
+        })
+}
+
+/// Returns a strategy for a [`MsgChannelOpenAck`].
+pub fn arb_msg_channel_open_ack() -> impl Strategy<Value = MsgChannelOpenAck> {
+    (
+        arb_port_id(),
+        arb_channel_id(),
+        arb_channel_id(),
+        arb_channel_version(),
+        arb_proofs(),
+        arb_signer(),
+    )
+        .prop_map(
+            |(
+                port_id,
+                channel_id,
+                counterparty_channel_id,
+                counterparty_version,
+                proofs,
+                signer,
+            )| {
+                MsgChannelOpenAck {
+                    port_id,
+                    channel_id,
+                    counterparty_channel_id,
+                    counterparty_version,
+                    proofs,
+                    signer,
+                }
+            },
+        )
+}
+
+/// Returns a strategy for raw messages that are rejected as a [`MsgChannelOpenAck`].
+pub fn arb_invalid_raw_msg_channel_open_ack() -> impl Strategy<Value = RawMsgChannelOpenAck> {
+    (
+        arb_msg_channel_open_ack(),
+        0..5usize,
+        arb_invalid_channel_id(),
+        arb_invalid_raw_height(),
+        arb_invalid_signer(),
+    )
+        .prop_map(|(msg, field, channel_id, proof_height, signer)| {
+            let mut raw = RawMsgChannelOpenAck::from(msg);
+            match field {
+                0 => raw.channel_id = channel_id,
+                1 => raw.counterparty_channel_id = channel_id,
+                2 => raw.proof_try = vec![],
+                3 => raw.proof_height = Some(proof_height),
+                _ => raw.signer = signer,
+            }
+            raw
+        })
+}
+
+/// Returns a strategy for a [`MsgChannelOpenConfirm`].
+pub fn arb_msg_channel_open_confirm() -> impl Strategy<Value = MsgChannelOpenConfirm> {
+    (arb_port_id(), arb_channel_id(), arb_proofs(), arb_signer()).prop_map(
+        |(port_id, channel_id, proofs, signer)| MsgChannelOpenConfirm {
+            port_id,
+            channel_id,
+            proofs,
+            signer,
+        },
+    )
+}
+
+/// Returns a strategy for raw messages that are rejected as a [`MsgChannelOpenConfirm`].
+pub fn arb_invalid_raw_msg_channel_open_confirm() -> impl Strategy<Value = RawMsgChannelOpenConfirm>
+{
+    (
+        arb_msg_channel_open_confirm(),
+        0..4usize,
+        arb_invalid_channel_id(),
+        arb_invalid_raw_height(),
+        arb_invalid_signer(),
+    )
+        .prop_map(|(msg, field, channel_id, proof_height, signer)| {
+            let mut raw = RawMsgChannelOpenConfirm::from(msg);
+            match field {
+                0 => raw.channel_id = channel_id,
+                1 => raw.proof_ack = vec![],
+                2 => raw.proof_height = Some(proof_height),
+                _ => raw.signer = signer,
+            }
+            raw
+        })
+}
+
+/// Returns a strategy for a [`MsgChannelCloseInit`].
+pub fn arb_msg_channel_close_init() -> impl Strategy<Value = MsgChannelCloseInit> {
+    (arb_port_id(), arb_channel_id(), arb_signer()).prop_map(|(port_id, channel_id, signer)| {
+        MsgChannelCloseInit {
+            port_id,
+            channel_id,
+            signer,
+        }
+    })
+}
+
+/// Returns a strategy for raw messages that are rejected as a [`MsgChannelCloseInit`].
+pub fn arb_invalid_raw_msg_channel_close_init() -> impl Strategy<Value = RawMsgChannelCloseInit> {
+    (
+        arb_msg_channel_close_init(),
+        0..3usize,
+        arb_invalid_port_id(),
+        arb_invalid_channel_id(),
+        arb_invalid_signer(),
+    )
+        .prop_map(|(msg, field, port_id, channel_id, signer)| {
+            let mut raw = RawMsgChannelCloseInit::from(msg);
+            match field {
+                0 => raw.port_id = port_id,
+                1 => raw.channel_id = channel_id,
+                _ => raw.signer = signer,
+            }
+            raw
+        })
+}
+
+/// Returns a strategy for a [`MsgChannelCloseConfirm`].
+pub fn arb_msg_channel_close_confirm() -> impl Strategy<Value = MsgChannelCloseConfirm> {
+    (arb_port_id(), arb_channel_id(), arb_proofs(), arb_signer()).prop_map(
+        |(port_id, channel_id, proofs, signer)| MsgChannelCloseConfirm {
+            port_id,
+            channel_id,
+            proofs,
+            signer,
+        },
+    )
+}
+
+/// Returns a strategy for raw messages that are rejected as a [`MsgChannelCloseConfirm`].
+pub fn arb_invalid_raw_msg_channel_close_confirm(
+) -> impl Strategy<Value = RawMsgChannelCloseConfirm> {
+    (
+        arb_msg_channel_close_confirm(),
+        0..4usize,
+        arb_invalid_port_id(),
+        arb_invalid_raw_height(),
+        arb_invalid_signer(),
+    )
+        .prop_map(|(msg, field, port_id, proof_height, signer)| {
+            let mut raw = RawMsgChannelCloseConfirm::from(msg);
+            match field {
+                0 => raw.port_id = port_id,
+                1 => raw.proof_init = vec![],
+                2 => raw.proof_height = Some(proof_height),
+                _ => raw.signer = signer,
+            }
+            raw
+        })
+}
+
+/// Returns a strategy for a [`MsgRecvPacket`].
+pub fn arb_msg_recv_packet() -> impl Strategy<Value = MsgRecvPacket> {
+    (arb_packet(), arb_proofs(), arb_signer()).prop_map(|(packet, proofs, signer)| MsgRecvPacket {
+        packet,
+        proofs,
+        signer,
+    })
+}
+
+/// Returns a strategy for raw messages that are rejected as a [`MsgRecvPacket`].
+pub fn arb_invalid_raw_msg_recv_packet() -> impl Strategy<Value = RawMsgRecvPacket> {
+    (
+        arb_msg_recv_packet(),
+        0..4usize,
+        arb_invalid_raw_packet(),
+        arb_invalid_signer(),
+    )
+        .prop_map(|(msg, field, packet, signer)| {
+            let mut raw = RawMsgRecvPacket::from(msg);
+            match field {
+                0 => raw.packet = Some(packet),
+                1 => raw.packet = None,
+                2 => raw.proof_commitment = vec![],
+                _ => raw.signer = signer,
+            }
+            raw
+        })
+}
+
+/// Returns a strategy for a [`MsgAcknowledgement`].
+pub fn arb_msg_acknowledgement() -> impl Strategy<Value = MsgAcknowledgement> {
+    (
+        arb_packet(),
+        arb_acknowledgement(),
+        arb_proofs(),
+        arb_signer(),
+    )
+        .prop_map(
+            |(packet, acknowledgement, proofs, signer)| MsgAcknowledgement {
+                packet,
+                acknowledgement,
+                proofs,
+                signer,
+            },
+        )
+}
+
+/// Returns a strategy for raw messages that are rejected as a [`MsgAcknowledgement`].
+pub fn arb_invalid_raw_msg_acknowledgement() -> impl Strategy<Value = RawMsgAcknowledgement> {
+    (
+        arb_msg_acknowledgement(),
+        0..4usize,
+        arb_invalid_raw_packet(),
+        arb_invalid_signer(),
+    )
+        .prop_map(|(msg, field, packet, signer)| {
+            let mut raw = RawMsgAcknowledgement::from(msg);
+            match field {
+                0 => raw.packet = Some(packet),
+                1 => raw.packet = None,
+                2 => raw.proof_acked = vec![],
+                _ => raw.signer = signer,
+            }
+            raw
+        })
+}
+
+/// Returns a strategy for a [`MsgTimeout`].
+pub fn arb_msg_timeout() -> impl Strategy<Value = MsgTimeout> {
+    (arb_packet(), arb_sequence(), arb_proofs(), arb_signer()).prop_map(
+        |(packet, next_sequence_recv, proofs, signer)| MsgTimeout {
+            packet,
+            next_sequence_recv,
+            proofs,
+            signer,
+        },
+    )
+}
+
+/// Returns a strategy for raw messages that are rejected as a [`MsgTimeout`].
+pub fn arb_invalid_raw_msg_timeout() -> impl Strategy<Value = RawMsgTimeout> {
+    (
+        arb_msg_timeout(),
+        0..4usize,
+        arb_invalid_raw_packet(),
+        arb_invalid_signer(),
+    )
+        .prop_map(|(msg, field, packet, signer)| {
+            let mut raw = RawMsgTimeout::from(msg);
+            match field {
+                0 => raw.packet = Some(packet),
+                1 => raw.packet = None,
+                2 => raw.proof_unreceived = vec![],
+                _ => raw.signer = signer,
+            }
+            raw
+        })
+}
+
+/// Returns a strategy for a [`MsgTimeoutOnClose`].
+pub fn arb_msg_timeout_on_close() -> impl Strategy<Value = MsgTimeoutOnClose> {
+    (
+        arb_packet(),
+        arb_sequence(),
+        arb_proofs_with_other(),
+        arb_signer(),
+    )
+        .prop_map(
+            |(packet, next_sequence_recv, proofs, signer)| MsgTimeoutOnClose {
+                packet,
+                next_sequence_recv,
+                proofs,
+                signer,
+            },
+        )
+}
+
+/// Returns a strategy for raw messages that are rejected as a [`MsgTimeoutOnClose`].
+pub fn arb_invalid_raw_msg_timeout_on_close() -> impl Strategy<Value = RawMsgTimeoutOnClose> {
+    (
+        arb_msg_timeout_on_close(),
+        0..4usize,
+        arb_invalid_raw_packet(),
+        arb_invalid_signer(),
+    )
+        .prop_map(|(msg, field, packet, signer)| {
+            let mut raw = RawMsgTimeoutOnClose::from(msg);
+            match field {
+                0 => raw.packet = Some(packet),
+                1 => raw.packet = None,
+                2 => raw.proof_unreceived = vec![],
+                _ => raw.signer = signer,
+            }
+            raw
+        })
+}
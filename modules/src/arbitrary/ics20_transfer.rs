@@ -0,0 +1,239 @@
+//! Strategies for the ICS 20 fungible token transfer types and messages, and for the packet
+//! forwarding metadata carried in their memo.
+
+use core::time::Duration;
+
+use ibc_proto::cosmos::base::v1beta1::Coin as RawCoin;
+use ibc_proto::ibc::applications::transfer::v1::{
+    DenomTrace as RawDenomTrace, MsgTransfer as RawMsgTransfer,
+};
+use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData;
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+use crate::applications::packet_forward::metadata::{ForwardMetadata, PacketMetadata};
+use crate::applications::transfer::msgs::transfer::MsgTransfer;
+use crate::applications::transfer::packet::PacketData;
+use crate::applications::transfer::{
+    Amount, BaseDenom, Memo, PrefixedCoin, PrefixedDenom, TracePrefix,
+};
+use crate::bigint::U256;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::prelude::*;
+use crate::signer::Signer;
+
+use crate::arbitrary::ics02_client::arb_invalid_raw_height;
+use crate::arbitrary::ics04_channel::arb_timeout_height;
+use crate::arbitrary::ics24_host::{
+    arb_channel_id, arb_invalid_channel_id, arb_invalid_port_id, arb_port_id,
+};
+use crate::arbitrary::{arb_invalid_signer, arb_signer, arb_timestamp};
+
+/// Returns a strategy for a [`BaseDenom`].
+pub fn arb_base_denom() -> impl Strategy<Value = BaseDenom> {
+    "[a-zA-Z][a-zA-Z0-9]{1,63}".prop_map(|denom| denom.parse().unwrap())
+}
+
+/// Returns a strategy for a [`TracePrefix`].
+pub fn arb_trace_prefix() -> impl Strategy<Value = TracePrefix> {
+    (arb_port_id(), arb_channel_id())
+        .prop_map(|(port_id, channel_id)| TracePrefix::new(port_id, channel_id))
+}
+
+/// Returns a strategy for a [`PrefixedDenom`], with up to three trace prefixes.
+pub fn arb_prefixed_denom() -> impl Strategy<Value = PrefixedDenom> {
+    (arb_base_denom(), vec(arb_trace_prefix(), 0..4)).prop_map(|(base_denom, trace_prefixes)| {
+        let mut denom = PrefixedDenom::from(base_denom);
+        for prefix in trace_prefixes {
+            denom.add_trace_prefix(prefix);
+        }
+        denom
+    })
+}
+
+/// Returns a strategy for raw denomination traces that are rejected as a [`PrefixedDenom`]: traces
+/// with a blank base denomination, an odd number of path elements, or an invalid identifier.
+pub fn arb_invalid_raw_denom_trace() -> impl Strategy<Value = RawDenomTrace> {
+    (
+        arb_prefixed_denom(),
+        0..4usize,
+        "[ \t]{0,2}",
+        arb_invalid_port_id(),
+        arb_invalid_channel_id(),
+    )
+        .prop_map(|(denom, field, blank, port_id, channel_id)| {
+            let mut raw = RawDenomTrace::from(denom);
+            let separator = if raw.path.is_empty() { "" } else { "/" };
+            match field {
+                0 => raw.base_denom = blank,
+                1 => raw.path = format!("{}{}{}", raw.path, separator, PortId::transfer()),
+                2 => raw.path = format!("{}{}{}/channel-0", raw.path, separator, port_id),
+                _ => raw.path = format!("{}{}transfer/{}", raw.path, separator, channel_id),
+            }
+            raw
+        })
+}
+
+/// Returns a strategy for an [`Amount`].
+pub fn arb_amount() -> impl Strategy<Value = Amount> {
+    any::<[u64; 4]>().prop_map(|words| U256(words).into())
+}
+
+/// Returns a strategy for strings that are rejected as an [`Amount`].
+pub fn arb_invalid_amount() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "-[1-9][0-9]{0,8}",
+        "[0-9]{0,4}[a-zA-Z.,][0-9]{0,4}",
+        // one more than the largest 256-bit integer
+        Just(
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+                .to_string()
+        ),
+    ]
+}
+
+/// Returns a strategy for a [`PrefixedCoin`].
+pub fn arb_prefixed_coin() -> impl Strategy<Value = PrefixedCoin> {
+    (arb_prefixed_denom(), arb_amount()).prop_map(|(denom, amount)| PrefixedCoin { denom, amount })
+}
+
+/// Returns a strategy for a [`Memo`], which may be empty.
+pub fn arb_memo() -> impl Strategy<Value = Memo> {
+    any::<String>().prop_map(Memo::from)
+}
+
+/// Returns a strategy for ICS 20 [`PacketData`].
+pub fn arb_packet_data() -> impl Strategy<Value = PacketData> {
+    (arb_prefixed_coin(), arb_signer(), arb_signer(), arb_memo()).prop_map(
+        |(token, sender, receiver, memo)| PacketData {
+            token,
+            sender,
+            receiver,
+            memo,
+        },
+    )
+}
+
+/// Returns a strategy for raw packet data that is rejected as ICS 20 [`PacketData`].
+pub fn arb_invalid_raw_packet_data() -> impl Strategy<Value = RawPacketData> {
+    (
+        arb_packet_data(),
+        0..4usize,
+        arb_invalid_raw_denom_trace(),
+        arb_invalid_amount(),
+        arb_invalid_signer(),
+    )
+        .prop_map(|(packet_data, field, denom_trace, amount, signer)| {
+            let mut raw = RawPacketData::from(packet_data);
+            match field {
+                0 => {
+                    raw.denom = if denom_trace.path.is_empty() {
+                        denom_trace.base_denom
+                    } else {
+                        format!("{}/{}", denom_trace.path, denom_trace.base_denom)
+                    }
+                }
+                1 => raw.amount = amount,
+                2 => raw.sender = signer,
+                _ => raw.receiver = signer,
+            }
+            raw
+        })
+}
+
+/// Returns a strategy for a [`MsgTransfer`], whose token is a valid prefixed coin.
+pub fn arb_msg_transfer() -> impl Strategy<Value = MsgTransfer> {
+    (
+        (arb_port_id(), arb_channel_id()),
+        arb_prefixed_coin(),
+        (arb_signer(), arb_signer()),
+        arb_timeout_height(),
+        arb_timestamp(),
+        arb_memo(),
+    )
+        .prop_map(
+            |(
+                (source_port, source_channel),
+                token,
+                (sender, receiver),
+                timeout_height,
+                timeout_timestamp,
+                memo,
+            )| MsgTransfer {
+                source_port,
+                source_channel,
+                token: RawCoin::from(token),
+                sender,
+                receiver,
+                timeout_height,
+                timeout_timestamp,
+                memo,
+            },
+        )
+}
+
+/// Returns a strategy for raw messages that are rejected as a [`MsgTransfer`].
+pub fn arb_invalid_raw_msg_transfer() -> impl Strategy<Value = RawMsgTransfer> {
+    (
+        arb_msg_transfer(),
+        0..5usize,
+        (arb_invalid_port_id(), arb_invalid_channel_id()),
+        arb_invalid_raw_height(),
+        arb_invalid_signer(),
+    )
+        .prop_map(
+            |(msg, field, (port_id, channel_id), timeout_height, signer)| {
+                let mut raw = RawMsgTransfer::from(msg);
+                match field {
+                    0 => raw.source_port = port_id,
+                    1 => raw.source_channel = channel_id,
+                    2 => raw.token = None,
+                    3 => {
+                        raw.timeout_height = Some(RawHeight {
+                            revision_number: timeout_height.revision_number.max(1),
+                            ..timeout_height
+                        })
+                    }
+                    _ => raw.sender = signer,
+                }
+                raw
+            },
+        )
+}
+
+/// Returns a strategy for [`PacketMetadata`] with up to three nested forwarding hops.
+pub fn arb_packet_metadata() -> impl Strategy<Value = PacketMetadata> {
+    arb_forward_hop()
+        .prop_map(|hop| forward_metadata(hop, None))
+        .prop_recursive(3, 3, 1, |next| {
+            (arb_forward_hop(), next)
+                .prop_map(|(hop, next)| forward_metadata(hop, Some(Box::new(next))))
+        })
+}
+
+fn arb_forward_hop() -> impl Strategy<Value = (Signer, PortId, ChannelId, u64, u8)> {
+    (
+        arb_signer(),
+        arb_port_id(),
+        arb_channel_id(),
+        any::<u64>(),
+        any::<u8>(),
+    )
+}
+
+fn forward_metadata(
+    (receiver, port, channel, timeout, retries): (Signer, PortId, ChannelId, u64, u8),
+    next: Option<Box<PacketMetadata>>,
+) -> PacketMetadata {
+    PacketMetadata {
+        forward: ForwardMetadata {
+            receiver,
+            port,
+            channel,
+            timeout: Duration::from_nanos(timeout),
+            retries,
+            next,
+        },
+    }
+}
@@ -0,0 +1,137 @@
+//! Strategies for the ICS 24 host identifiers.
+
+use proptest::prelude::*;
+use proptest::string::string_regex;
+
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
+use crate::prelude::*;
+
+/// Regex class of the characters allowed in an identifier.
+const IDENTIFIER_CHARS: &str = r"[a-zA-Z0-9._+\-#\[\]<>]";
+
+/// Returns a strategy for identifiers made of allowed characters, with a length between `min`
+/// and `max`.
+fn arb_identifier(min: usize, max: usize) -> impl Strategy<Value = String> {
+    string_regex(&format!("{}{{{},{}}}", IDENTIFIER_CHARS, min, max)).unwrap()
+}
+
+/// Returns a strategy for strings rejected by an identifier validator accepting lengths between
+/// `min` and `max`: non-empty strings that are too short, too long, or that contain a path
+/// separator or a disallowed character. Empty strings are left out since optional identifiers
+/// are encoded as such.
+fn arb_invalid_identifier(min: usize, max: usize) -> impl Strategy<Value = String> {
+    prop_oneof![
+        arb_identifier(1, min - 1),
+        arb_identifier(max + 1, max + 16),
+        (arb_identifier(0, min), arb_identifier(0, min))
+            .prop_map(|(prefix, suffix)| format!("{}/{}", prefix, suffix)),
+        (arb_identifier(min, max - 1), "[ $%&*=?@^|~]")
+            .prop_map(|(id, invalid)| format!("{}{}", id, invalid)),
+    ]
+}
+
+/// Returns a strategy for a [`ChainId`] in the `{name}-{version}` format.
+pub fn arb_chain_id() -> impl Strategy<Value = ChainId> {
+    ("[a-z][a-z0-9]{1,15}", any::<u32>())
+        .prop_map(|(name, version)| ChainId::new(name, version.into()))
+}
+
+/// Returns a strategy for a [`ClientId`], either derived from a client type and a counter or made
+/// of arbitrary allowed characters.
+pub fn arb_client_id() -> impl Strategy<Value = ClientId> {
+    prop_oneof![
+        any::<u64>().prop_map(|counter| ClientId::new(ClientType::Tendermint, counter).unwrap()),
+        arb_identifier(9, 64).prop_map(|id| id.parse().unwrap()),
+    ]
+}
+
+/// Returns a strategy for strings that are rejected as a [`ClientId`].
+pub fn arb_invalid_client_id() -> impl Strategy<Value = String> {
+    arb_invalid_identifier(9, 64)
+}
+
+/// Returns a strategy for a [`ConnectionId`], either derived from a counter or made of arbitrary
+/// allowed characters.
+pub fn arb_connection_id() -> impl Strategy<Value = ConnectionId> {
+    prop_oneof![
+        any::<u64>().prop_map(ConnectionId::new),
+        arb_identifier(10, 64).prop_map(|id| id.parse().unwrap()),
+    ]
+}
+
+/// Returns a strategy for strings that are rejected as a [`ConnectionId`].
+pub fn arb_invalid_connection_id() -> impl Strategy<Value = String> {
+    arb_invalid_identifier(10, 64)
+}
+
+/// Returns a strategy for a [`PortId`].
+pub fn arb_port_id() -> impl Strategy<Value = PortId> {
+    prop_oneof![
+        Just(PortId::transfer()),
+        arb_identifier(2, 128).prop_map(|id| id.parse().unwrap()),
+    ]
+}
+
+/// Returns a strategy for strings that are rejected as a [`PortId`].
+pub fn arb_invalid_port_id() -> impl Strategy<Value = String> {
+    arb_invalid_identifier(2, 128)
+}
+
+/// Returns a strategy for a [`ChannelId`], either derived from a counter or made of arbitrary
+/// allowed characters.
+pub fn arb_channel_id() -> impl Strategy<Value = ChannelId> {
+    prop_oneof![
+        any::<u64>().prop_map(ChannelId::new),
+        arb_identifier(8, 64).prop_map(|id| id.parse().unwrap()),
+    ]
+}
+
+/// Returns a strategy for strings that are rejected as a [`ChannelId`].
+pub fn arb_invalid_channel_id() -> impl Strategy<Value = String> {
+    arb_invalid_identifier(8, 64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ics24_host::validate::{
+        validate_channel_identifier, validate_client_identifier, validate_connection_identifier,
+        validate_port_identifier,
+    };
+
+    proptest! {
+        #[test]
+        fn chain_id_round_trip(chain_id in arb_chain_id()) {
+            let parsed = ChainId::from_string(chain_id.as_str());
+            prop_assert_eq!(parsed.version(), chain_id.version());
+            prop_assert_eq!(parsed, chain_id);
+        }
+
+        #[test]
+        fn valid_identifiers_are_accepted(
+            client_id in arb_client_id(),
+            connection_id in arb_connection_id(),
+            port_id in arb_port_id(),
+            channel_id in arb_channel_id(),
+        ) {
+            prop_assert!(validate_client_identifier(client_id.as_str()).is_ok());
+            prop_assert!(validate_connection_identifier(connection_id.as_str()).is_ok());
+            prop_assert!(validate_port_identifier(port_id.as_str()).is_ok());
+            prop_assert!(validate_channel_identifier(channel_id.as_str()).is_ok());
+        }
+
+        #[test]
+        fn invalid_identifiers_are_rejected(
+            client_id in arb_invalid_client_id(),
+            connection_id in arb_invalid_connection_id(),
+            port_id in arb_invalid_port_id(),
+            channel_id in arb_invalid_channel_id(),
+        ) {
+            prop_assert!(client_id.parse::<ClientId>().is_err());
+            prop_assert!(connection_id.parse::<ConnectionId>().is_err());
+            prop_assert!(port_id.parse::<PortId>().is_err());
+            prop_assert!(channel_id.parse::<ChannelId>().is_err());
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! [`proptest`] strategies for the IBC domain types, enabled by the `proptest` feature.
+//!
+//! Every `arb_*` strategy yields values that are valid by construction: converting them into their
+//! raw (protobuf) counterpart and back succeeds and yields the original value. Every
+//! `arb_invalid_*` strategy yields raw values, i.e. strings or protobuf messages, that the
+//! corresponding domain type rejects on conversion.
+//!
+//! The submodules mirror the layout of the crate. Tendermint headers and misbehaviour evidence
+//! carry signed commits and are not generated; the strategies for update and misbehaviour
+//! messages use the mock client instead and are only available with the `mocks` feature.
+
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::prelude::*;
+use crate::proofs::{ConsensusProof, Proofs};
+use crate::signer::Signer;
+use crate::timestamp::Timestamp;
+
+use crate::arbitrary::ics02_client::arb_height;
+
+pub mod ics02_client;
+pub mod ics03_connection;
+pub mod ics04_channel;
+pub mod ics20_transfer;
+pub mod ics24_host;
+
+/// Returns a strategy for a [`Signer`], shaped like a bech32 account address.
+pub fn arb_signer() -> impl Strategy<Value = Signer> {
+    "[a-z]{2,8}1[02-9ac-hj-np-z]{38}".prop_map(|s| s.parse().unwrap())
+}
+
+/// Returns a strategy for strings that are rejected as a [`Signer`].
+pub fn arb_invalid_signer() -> impl Strategy<Value = String> {
+    "[ \t\n]{0,4}"
+}
+
+/// Returns a strategy for a [`Timestamp`], which may be unset.
+pub fn arb_timestamp() -> impl Strategy<Value = Timestamp> {
+    any::<u64>().prop_map(|nanos| Timestamp::from_nanoseconds(nanos).unwrap())
+}
+
+/// Returns a strategy for a [`tendermint::Time`].
+pub fn arb_tm_time() -> impl Strategy<Value = tendermint::Time> {
+    (1..=u64::MAX).prop_map(|nanos| {
+        Timestamp::from_nanoseconds(nanos)
+            .unwrap()
+            .into_tm_time()
+            .unwrap()
+    })
+}
+
+/// Returns a strategy for a non-empty [`CommitmentProofBytes`].
+pub fn arb_commitment_proof_bytes() -> impl Strategy<Value = CommitmentProofBytes> {
+    vec(any::<u8>(), 1..64).prop_map(|bytes| bytes.try_into().unwrap())
+}
+
+/// Returns a strategy for [`Proofs`] carrying only an object proof, as found in channel and
+/// packet messages, and in `MsgConnectionOpenConfirm`.
+pub fn arb_proofs() -> impl Strategy<Value = Proofs> {
+    (arb_commitment_proof_bytes(), arb_height()).prop_map(|(object_proof, height)| {
+        Proofs::new(object_proof, None, None, None, height).unwrap()
+    })
+}
+
+/// Returns a strategy for [`Proofs`] carrying an object proof along with a client and a
+/// consensus state proof, as found in `MsgConnectionOpenTry` and `MsgConnectionOpenAck`.
+pub fn arb_connection_proofs() -> impl Strategy<Value = Proofs> {
+    (
+        arb_commitment_proof_bytes(),
+        arb_commitment_proof_bytes(),
+        arb_commitment_proof_bytes(),
+        arb_height(),
+        arb_height(),
+    )
+        .prop_map(
+            |(object_proof, client_proof, consensus_proof, consensus_height, height)| {
+                Proofs::new(
+                    object_proof,
+                    Some(client_proof),
+                    Some(ConsensusProof::new(consensus_proof, consensus_height).unwrap()),
+                    None,
+                    height,
+                )
+                .unwrap()
+            },
+        )
+}
+
+/// Returns a strategy for [`Proofs`] carrying an object proof and, optionally, another proof, as
+/// found in `MsgTimeoutOnClose`.
+pub fn arb_proofs_with_other() -> impl Strategy<Value = Proofs> {
+    (
+        arb_commitment_proof_bytes(),
+        proptest::option::of(arb_commitment_proof_bytes()),
+        arb_height(),
+    )
+        .prop_map(|(object_proof, other_proof, height)| {
+            Proofs::new(object_proof, None, None, other_proof, height).unwrap()
+        })
+}
@@ -1,5 +1,6 @@
 use core::convert::TryInto;
 
+use ibc_proto::ibc::core::commitment::v1::MerklePath;
 use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
 use prost::Message;
 use tendermint_light_client_verifier::types::{TrustedBlockState, UntrustedBlockState};
@@ -20,16 +21,19 @@ use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics04_channel::channel::ChannelEnd;
 use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::context::ChannelReader;
-use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics04_channel::packet::{Receipt, Sequence};
+use crate::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use crate::core::ics23_commitment::commitment::{
     CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
 };
-use crate::core::ics23_commitment::merkle::{apply_prefix, MerkleProof};
+use crate::core::ics23_commitment::merkle::MerkleProof;
+use crate::core::ics23_commitment::verify::ProofVerifier;
 use crate::core::ics24_host::identifier::ConnectionId;
 use crate::core::ics24_host::identifier::{ChannelId, ClientId, PortId};
 use crate::core::ics24_host::path::{
-    AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath, CommitmentsPath,
-    ConnectionsPath, ReceiptsPath, SeqRecvsPath,
+    AcksPath, ChannelEndsPath, ChannelUpgradeErrorsPath, ChannelUpgradesPath,
+    ClientConsensusStatePath, ClientStatePath, CommitmentsPath, ConnectionsPath, ReceiptsPath,
+    SeqRecvsPath,
 };
 use crate::core::ics24_host::Path;
 use crate::downcast;
@@ -253,6 +257,46 @@ impl ClientDef for TendermintClient {
         verify_membership(client_state, prefix, proof, root, path, value)
     }
 
+    fn verify_channel_upgrade(
+        &self,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_upgrade: &Upgrade,
+    ) -> Result<(), Ics02Error> {
+        client_state.verify_height(height)?;
+
+        let path = ChannelUpgradesPath(port_id.clone(), channel_id.clone());
+        let value = expected_upgrade
+            .encode_vec()
+            .map_err(Ics02Error::invalid_channel_upgrade)?;
+        verify_membership(client_state, prefix, proof, root, path, value)
+    }
+
+    fn verify_channel_upgrade_error(
+        &self,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_error_receipt: &ErrorReceipt,
+    ) -> Result<(), Ics02Error> {
+        client_state.verify_height(height)?;
+
+        let path = ChannelUpgradeErrorsPath(port_id.clone(), channel_id.clone());
+        let value = expected_error_receipt
+            .encode_vec()
+            .map_err(Ics02Error::invalid_error_receipt)?;
+        verify_membership(client_state, prefix, proof, root, path, value)
+    }
+
     fn verify_client_full_state(
         &self,
         client_state: &Self::ClientState,
@@ -335,6 +379,60 @@ impl ClientDef for TendermintClient {
         )
     }
 
+    fn verify_packet_data_batch(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        connection_end: &ConnectionEnd,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        commitments: Vec<(Sequence, PacketCommitment)>,
+    ) -> Result<(), Ics02Error> {
+        client_state.verify_height(height)?;
+        verify_delay_passed(ctx, height, connection_end)?;
+
+        let merkle_proof =
+            MerkleProof::try_from(proof.clone()).map_err(Ics02Error::invalid_commitment_proof)?;
+
+        ProofVerifier::new(
+            &client_state.proof_specs,
+            connection_end.counterparty().prefix(),
+            root,
+        )
+        .verify_packet_commitments(&merkle_proof, port_id, channel_id, commitments)
+        .map_err(|e| Ics02Error::tendermint(Error::ics23_error(e)))
+    }
+
+    fn verify_packet_acknowledgement_batch(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        connection_end: &ConnectionEnd,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        ack_commitments: Vec<(Sequence, AcknowledgementCommitment)>,
+    ) -> Result<(), Ics02Error> {
+        client_state.verify_height(height)?;
+        verify_delay_passed(ctx, height, connection_end)?;
+
+        let merkle_proof =
+            MerkleProof::try_from(proof.clone()).map_err(Ics02Error::invalid_commitment_proof)?;
+
+        ProofVerifier::new(
+            &client_state.proof_specs,
+            connection_end.counterparty().prefix(),
+            root,
+        )
+        .verify_packet_acknowledgements(&merkle_proof, port_id, channel_id, ack_commitments)
+        .map_err(|e| Ics02Error::tendermint(Error::ics23_error(e)))
+    }
+
     fn verify_next_sequence_recv(
         &self,
         ctx: &dyn ChannelReader,
@@ -366,6 +464,37 @@ impl ClientDef for TendermintClient {
         )
     }
 
+    fn verify_packet_receipt(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        connection_end: &ConnectionEnd,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        receipt: Receipt,
+    ) -> Result<(), Ics02Error> {
+        client_state.verify_height(height)?;
+        verify_delay_passed(ctx, height, connection_end)?;
+
+        let receipt_path = ReceiptsPath {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence,
+        };
+        verify_membership(
+            client_state,
+            connection_end.counterparty().prefix(),
+            proof,
+            root,
+            receipt_path,
+            receipt.as_bytes().to_vec(),
+        )
+    }
+
     fn verify_packet_receipt_absence(
         &self,
         ctx: &dyn ChannelReader,
@@ -395,6 +524,59 @@ impl ClientDef for TendermintClient {
         )
     }
 
+    fn verify_packet_commitment_absence(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        connection_end: &ConnectionEnd,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<(), Ics02Error> {
+        client_state.verify_height(height)?;
+        verify_delay_passed(ctx, height, connection_end)?;
+
+        let commitment_path = CommitmentsPath {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence,
+        };
+        verify_non_membership(
+            client_state,
+            connection_end.counterparty().prefix(),
+            proof,
+            root,
+            commitment_path,
+        )
+    }
+
+    fn verify_key_value(
+        &self,
+        client_state: &Self::ClientState,
+        height: Height,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: MerklePath,
+        value: Vec<u8>,
+    ) -> Result<(), Ics02Error> {
+        client_state.verify_height(height)?;
+
+        let merkle_proof: MerkleProof = RawMerkleProof::try_from(proof.clone())
+            .map_err(Ics02Error::invalid_commitment_proof)?
+            .into();
+
+        let specs = &client_state.proof_specs;
+        if value.is_empty() {
+            merkle_proof.verify_non_membership(specs, root.clone().into(), path)
+        } else {
+            merkle_proof.verify_membership(specs, root.clone().into(), path, value, 0)
+        }
+        .map_err(|e| Ics02Error::tendermint(Error::ics23_error(e)))
+    }
+
     fn verify_upgrade_and_update_state(
         &self,
         _client_state: &Self::ClientState,
@@ -414,19 +596,12 @@ fn verify_membership(
     path: impl Into<Path>,
     value: Vec<u8>,
 ) -> Result<(), Ics02Error> {
-    let merkle_path = apply_prefix(prefix, vec![path.into().to_string()]);
     let merkle_proof: MerkleProof = RawMerkleProof::try_from(proof.clone())
         .map_err(Ics02Error::invalid_commitment_proof)?
         .into();
 
-    merkle_proof
-        .verify_membership(
-            &client_state.proof_specs,
-            root.clone().into(),
-            merkle_path,
-            value,
-            0,
-        )
+    ProofVerifier::new(&client_state.proof_specs, prefix, root)
+        .verify_membership(&merkle_proof, path, value)
         .map_err(|e| Ics02Error::tendermint(Error::ics23_error(e)))
 }
 
@@ -437,13 +612,12 @@ fn verify_non_membership(
     root: &CommitmentRoot,
     path: impl Into<Path>,
 ) -> Result<(), Ics02Error> {
-    let merkle_path = apply_prefix(prefix, vec![path.into().to_string()]);
     let merkle_proof: MerkleProof = RawMerkleProof::try_from(proof.clone())
         .map_err(Ics02Error::invalid_commitment_proof)?
         .into();
 
-    merkle_proof
-        .verify_non_membership(&client_state.proof_specs, root.clone().into(), merkle_path)
+    ProofVerifier::new(&client_state.proof_specs, prefix, root)
+        .verify_non_membership(&merkle_proof, path)
         .map_err(|e| Ics02Error::tendermint(Error::ics23_error(e)))
 }
 
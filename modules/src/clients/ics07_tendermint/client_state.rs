@@ -86,12 +86,10 @@ impl ClientState {
             ));
         }
 
-        // Disallow empty proof-specs
-        if proof_specs.is_empty() {
-            return Err(Error::validation(
-                "ClientState proof-specs cannot be empty".to_string(),
-            ));
-        }
+        // Disallow empty or incoherent proof-specs
+        proof_specs.validate().map_err(|e| {
+            Error::validation(format!("ClientState proof-specs are invalid: {}", e))
+        })?;
 
         Ok(Self {
             chain_id,
@@ -332,12 +330,15 @@ mod tests {
     use ibc_proto::ics23::ProofSpec as Ics23ProofSpec;
     use tendermint_rpc::endpoint::abci_query::AbciQuery;
 
+    use crate::arbitrary::ics02_client::{arb_invalid_raw_tm_client_state, arb_tm_client_state};
     use crate::clients::ics07_tendermint::client_state::{AllowUpdate, ClientState};
     use crate::core::ics02_client::trust_threshold::TrustThreshold;
     use crate::core::ics23_commitment::specs::ProofSpecs;
     use crate::core::ics24_host::identifier::ChainId;
     use crate::test::test_serialization_roundtrip;
+    use crate::test_utils::raw_round_trip_proptests;
     use crate::timestamp::{Timestamp, ZERO_DURATION};
+    use ibc_proto::ibc::lightclients::tendermint::v1::ClientState as RawClientState;
 
     #[derive(Clone, Debug, PartialEq)]
     struct ClientStateParams {
@@ -467,6 +468,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn client_state_decoding_keeps_stored_proof_specs() {
+        let client_state = ClientState::new(
+            ChainId::default(),
+            TrustThreshold::ONE_THIRD,
+            Duration::new(64000, 0),
+            Duration::new(128000, 0),
+            Duration::new(3, 0),
+            Height::new(0, 10).unwrap(),
+            ProofSpecs::default(),
+            vec!["".to_string()],
+            AllowUpdate {
+                after_expiry: false,
+                after_misbehaviour: false,
+            },
+        )
+        .unwrap();
+
+        // Stored client states are decoded without validating their proof specs, so that
+        // the existing clients whose specs `ClientState::new` would reject keep loading.
+        let mut raw = RawClientState::from(client_state);
+        raw.proof_specs[0].leaf_spec = None;
+
+        let decoded = ClientState::try_from(raw.clone()).unwrap();
+        assert!(decoded.proof_specs.validate().is_err());
+        assert_eq!(RawClientState::from(decoded), raw);
+    }
+
     #[test]
     fn client_state_verify_delay_passed() {
         #[derive(Debug, Clone)]
@@ -625,6 +654,13 @@ mod tests {
             );
         }
     }
+
+    raw_round_trip_proptests!(
+        ClientState,
+        RawClientState,
+        arb_tm_client_state(),
+        arb_invalid_raw_tm_client_state()
+    );
 }
 
 #[cfg(any(test, feature = "mocks"))]
@@ -117,7 +117,14 @@ mod tests {
     use tendermint_rpc::endpoint::abci_query::AbciQuery;
     use test_log::test;
 
+    use super::ConsensusState;
+    use crate::arbitrary::ics02_client::{
+        arb_invalid_raw_tm_consensus_state, arb_tm_consensus_state,
+    };
+    use crate::prelude::*;
     use crate::test::test_serialization_roundtrip;
+    use crate::test_utils::raw_round_trip_proptests;
+    use ibc_proto::ibc::lightclients::tendermint::v1::ConsensusState as RawConsensusState;
 
     #[test]
     fn serialization_roundtrip_no_proof() {
@@ -132,4 +139,11 @@ mod tests {
             include_str!("../../../tests/support/query/serialization/consensus_state_proof.json");
         test_serialization_roundtrip::<AbciQuery>(json_data);
     }
+
+    raw_round_trip_proptests!(
+        ConsensusState,
+        RawConsensusState,
+        arb_tm_consensus_state(),
+        arb_invalid_raw_tm_consensus_state()
+    );
 }
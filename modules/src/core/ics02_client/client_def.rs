@@ -1,4 +1,4 @@
-use ibc_proto::ibc::core::commitment::v1::MerkleProof;
+use ibc_proto::ibc::core::commitment::v1::{MerklePath, MerkleProof};
 
 use crate::clients::ics07_tendermint::client_def::TendermintClient;
 use crate::core::ics02_client::client_consensus::{AnyConsensusState, ConsensusState};
@@ -11,7 +11,8 @@ use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics04_channel::channel::ChannelEnd;
 use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::context::ChannelReader;
-use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics04_channel::packet::{Receipt, Sequence};
+use crate::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use crate::core::ics23_commitment::commitment::{
     CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
 };
@@ -92,6 +93,35 @@ pub trait ClientDef: Clone {
         expected_channel_end: &ChannelEnd,
     ) -> Result<(), Error>;
 
+    /// Verify a `proof` that the upgrade of a channel end matches the input `expected_upgrade`.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_channel_upgrade(
+        &self,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_upgrade: &Upgrade,
+    ) -> Result<(), Error>;
+
+    /// Verify a `proof` that the upgrade error receipt of a channel end matches the input
+    /// `expected_error_receipt`.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_channel_upgrade_error(
+        &self,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_error_receipt: &ErrorReceipt,
+    ) -> Result<(), Error>;
+
     /// Verify the client state for this chain that it is stored on the counterparty chain.
     #[allow(clippy::too_many_arguments)]
     fn verify_client_full_state(
@@ -137,6 +167,36 @@ pub trait ClientDef: Clone {
         ack: AcknowledgementCommitment,
     ) -> Result<(), Error>;
 
+    /// Verify a batch `proof` that several packets of a channel have been commited.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_packet_data_batch(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        connection_end: &ConnectionEnd,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        commitments: Vec<(Sequence, PacketCommitment)>,
+    ) -> Result<(), Error>;
+
+    /// Verify a batch `proof` that several packets of a channel have been acknowledged.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_packet_acknowledgement_batch(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        connection_end: &ConnectionEnd,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        ack_commitments: Vec<(Sequence, AcknowledgementCommitment)>,
+    ) -> Result<(), Error>;
+
     /// Verify a `proof` that of the next_seq_received.
     #[allow(clippy::too_many_arguments)]
     fn verify_next_sequence_recv(
@@ -152,6 +212,22 @@ pub trait ClientDef: Clone {
         sequence: Sequence,
     ) -> Result<(), Error>;
 
+    /// Verify a `proof` that a packet has been received with the given `receipt`.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_packet_receipt(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        connection_end: &ConnectionEnd,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        receipt: Receipt,
+    ) -> Result<(), Error>;
+
     /// Verify a `proof` that a packet has not been received.
     #[allow(clippy::too_many_arguments)]
     fn verify_packet_receipt_absence(
@@ -166,6 +242,36 @@ pub trait ClientDef: Clone {
         channel_id: &ChannelId,
         sequence: Sequence,
     ) -> Result<(), Error>;
+
+    /// Verify a `proof` that the commitment of a packet is not stored, i.e., that the packet was
+    /// either acknowledged or timed out, or was never sent.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_packet_commitment_absence(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        connection_end: &ConnectionEnd,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<(), Error>;
+
+    /// Verify a `proof` that `value` is stored at `path` in the state of the chain tracked by the
+    /// client, or that no value is stored there if `value` is empty. Unlike the functions above,
+    /// `path` is not relative to the IBC store, so that the values of any store can be verified
+    /// (e.g., by ICS31 cross-chain queries).
+    fn verify_key_value(
+        &self,
+        client_state: &Self::ClientState,
+        height: Height,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: MerklePath,
+        value: Vec<u8>,
+    ) -> Result<(), Error>;
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -379,6 +485,100 @@ impl ClientDef for AnyClient {
         }
     }
 
+    fn verify_channel_upgrade(
+        &self,
+        client_state: &AnyClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_upgrade: &Upgrade,
+    ) -> Result<(), Error> {
+        match self {
+            Self::Tendermint(client) => {
+                let client_state = downcast!(client_state => AnyClientState::Tendermint)
+                    .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
+
+                client.verify_channel_upgrade(
+                    client_state,
+                    height,
+                    prefix,
+                    proof,
+                    root,
+                    port_id,
+                    channel_id,
+                    expected_upgrade,
+                )
+            }
+
+            #[cfg(any(test, feature = "mocks"))]
+            Self::Mock(client) => {
+                let client_state = downcast!(client_state => AnyClientState::Mock)
+                    .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
+
+                client.verify_channel_upgrade(
+                    client_state,
+                    height,
+                    prefix,
+                    proof,
+                    root,
+                    port_id,
+                    channel_id,
+                    expected_upgrade,
+                )
+            }
+        }
+    }
+
+    fn verify_channel_upgrade_error(
+        &self,
+        client_state: &AnyClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_error_receipt: &ErrorReceipt,
+    ) -> Result<(), Error> {
+        match self {
+            Self::Tendermint(client) => {
+                let client_state = downcast!(client_state => AnyClientState::Tendermint)
+                    .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
+
+                client.verify_channel_upgrade_error(
+                    client_state,
+                    height,
+                    prefix,
+                    proof,
+                    root,
+                    port_id,
+                    channel_id,
+                    expected_error_receipt,
+                )
+            }
+
+            #[cfg(any(test, feature = "mocks"))]
+            Self::Mock(client) => {
+                let client_state = downcast!(client_state => AnyClientState::Mock)
+                    .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
+
+                client.verify_channel_upgrade_error(
+                    client_state,
+                    height,
+                    prefix,
+                    proof,
+                    root,
+                    port_id,
+                    channel_id,
+                    expected_error_receipt,
+                )
+            }
+        }
+    }
+
     fn verify_client_full_state(
         &self,
         client_state: &Self::ClientState,
@@ -540,6 +740,114 @@ impl ClientDef for AnyClient {
         }
     }
 
+    fn verify_packet_data_batch(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        connection_end: &ConnectionEnd,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        commitments: Vec<(Sequence, PacketCommitment)>,
+    ) -> Result<(), Error> {
+        match self {
+            Self::Tendermint(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Tendermint
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
+
+                client.verify_packet_data_batch(
+                    ctx,
+                    client_state,
+                    height,
+                    connection_end,
+                    proof,
+                    root,
+                    port_id,
+                    channel_id,
+                    commitments,
+                )
+            }
+
+            #[cfg(any(test, feature = "mocks"))]
+            Self::Mock(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Mock
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
+
+                client.verify_packet_data_batch(
+                    ctx,
+                    client_state,
+                    height,
+                    connection_end,
+                    proof,
+                    root,
+                    port_id,
+                    channel_id,
+                    commitments,
+                )
+            }
+        }
+    }
+
+    fn verify_packet_acknowledgement_batch(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        connection_end: &ConnectionEnd,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        ack_commitments: Vec<(Sequence, AcknowledgementCommitment)>,
+    ) -> Result<(), Error> {
+        match self {
+            Self::Tendermint(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Tendermint
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
+
+                client.verify_packet_acknowledgement_batch(
+                    ctx,
+                    client_state,
+                    height,
+                    connection_end,
+                    proof,
+                    root,
+                    port_id,
+                    channel_id,
+                    ack_commitments,
+                )
+            }
+
+            #[cfg(any(test, feature = "mocks"))]
+            Self::Mock(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Mock
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
+
+                client.verify_packet_acknowledgement_batch(
+                    ctx,
+                    client_state,
+                    height,
+                    connection_end,
+                    proof,
+                    root,
+                    port_id,
+                    channel_id,
+                    ack_commitments,
+                )
+            }
+        }
+    }
+
     fn verify_next_sequence_recv(
         &self,
         ctx: &dyn ChannelReader,
@@ -593,6 +901,63 @@ impl ClientDef for AnyClient {
             }
         }
     }
+    fn verify_packet_receipt(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        connection_end: &ConnectionEnd,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        receipt: Receipt,
+    ) -> Result<(), Error> {
+        match self {
+            Self::Tendermint(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Tendermint
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
+
+                client.verify_packet_receipt(
+                    ctx,
+                    client_state,
+                    height,
+                    connection_end,
+                    proof,
+                    root,
+                    port_id,
+                    channel_id,
+                    sequence,
+                    receipt,
+                )
+            }
+
+            #[cfg(any(test, feature = "mocks"))]
+            Self::Mock(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Mock
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
+
+                client.verify_packet_receipt(
+                    ctx,
+                    client_state,
+                    height,
+                    connection_end,
+                    proof,
+                    root,
+                    port_id,
+                    channel_id,
+                    sequence,
+                    receipt,
+                )
+            }
+        }
+    }
+
     fn verify_packet_receipt_absence(
         &self,
         ctx: &dyn ChannelReader,
@@ -647,6 +1012,91 @@ impl ClientDef for AnyClient {
         }
     }
 
+    fn verify_packet_commitment_absence(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        connection_end: &ConnectionEnd,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<(), Error> {
+        match self {
+            Self::Tendermint(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Tendermint
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
+
+                client.verify_packet_commitment_absence(
+                    ctx,
+                    client_state,
+                    height,
+                    connection_end,
+                    proof,
+                    root,
+                    port_id,
+                    channel_id,
+                    sequence,
+                )
+            }
+
+            #[cfg(any(test, feature = "mocks"))]
+            Self::Mock(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Mock
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
+
+                client.verify_packet_commitment_absence(
+                    ctx,
+                    client_state,
+                    height,
+                    connection_end,
+                    proof,
+                    root,
+                    port_id,
+                    channel_id,
+                    sequence,
+                )
+            }
+        }
+    }
+
+    fn verify_key_value(
+        &self,
+        client_state: &Self::ClientState,
+        height: Height,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: MerklePath,
+        value: Vec<u8>,
+    ) -> Result<(), Error> {
+        match self {
+            Self::Tendermint(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Tendermint
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Tendermint))?;
+
+                client.verify_key_value(client_state, height, proof, root, path, value)
+            }
+
+            #[cfg(any(test, feature = "mocks"))]
+            Self::Mock(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Mock
+                )
+                .ok_or_else(|| Error::client_args_type_mismatch(ClientType::Mock))?;
+
+                client.verify_key_value(client_state, height, proof, root, path, value)
+            }
+        }
+    }
+
     fn verify_upgrade_and_update_state(
         &self,
         client_state: &Self::ClientState,
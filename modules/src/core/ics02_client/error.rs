@@ -179,6 +179,10 @@ define_error! {
             [ Ics23Error ]
             | _ | { "invalid commitment proof bytes" },
 
+        InvalidProofSpecs
+            [ Ics23Error ]
+            | _ | { "invalid proof specs" },
+
         Tendermint
             [ Ics07Error ]
             | _ | { "tendermint error" },
@@ -264,6 +268,14 @@ define_error! {
             [ TraceError<TendermintProtoError>]
             | _ | { "invalid channel end" },
 
+        InvalidChannelUpgrade
+            [ TraceError<TendermintProtoError>]
+            | _ | { "invalid channel upgrade" },
+
+        InvalidErrorReceipt
+            [ TraceError<TendermintProtoError>]
+            | _ | { "invalid channel upgrade error receipt" },
+
         InvalidAnyClientState
             [ TraceError<TendermintProtoError>]
             | _ | { "invalid any client state" },
@@ -170,3 +170,13 @@ impl FromStr for Height {
         Height::try_from(s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::raw_round_trip_proptests;
+
+    use super::*;
+    use crate::arbitrary::ics02_client::{arb_height, arb_invalid_raw_height};
+
+    raw_round_trip_proptests!(Height, RawHeight, arb_height(), arb_invalid_raw_height());
+}
@@ -69,8 +69,20 @@ impl TryFrom<RawMsgCreateClient> for MsgCreateAnyClient {
             .consensus_state
             .ok_or_else(Error::missing_raw_client_state)?;
 
+        let client_state = AnyClientState::try_from(raw_client_state)?;
+
+        // Reject clients which would not be able to verify any proof. The client states decoded
+        // from the store are not validated, so that the existing clients keep loading, while the
+        // ones built with `ClientState::new` are validated there.
+        if let AnyClientState::Tendermint(client_state) = &client_state {
+            client_state
+                .proof_specs
+                .validate()
+                .map_err(Error::invalid_proof_specs)?;
+        }
+
         MsgCreateAnyClient::new(
-            AnyClientState::try_from(raw_client_state)?,
+            client_state,
             AnyConsensusState::try_from(raw_consensus_state)?,
             raw.signer.parse().map_err(Error::signer)?,
         )
@@ -94,11 +106,17 @@ mod tests {
 
     use ibc_proto::ibc::core::client::v1::MsgCreateClient;
 
+    use crate::arbitrary::ics02_client::{
+        arb_invalid_raw_msg_create_client, arb_msg_create_client,
+    };
     use crate::clients::ics07_tendermint::client_state::test_util::get_dummy_tendermint_client_state;
     use crate::clients::ics07_tendermint::header::test_util::get_dummy_tendermint_header;
     use crate::core::ics02_client::client_consensus::AnyConsensusState;
+    use crate::core::ics02_client::client_state::AnyClientState;
     use crate::core::ics02_client::msgs::MsgCreateAnyClient;
+    use crate::prelude::*;
     use crate::test_utils::get_dummy_account_id;
+    use crate::test_utils::raw_round_trip_proptests;
 
     #[test]
     fn msg_create_client_serialization() {
@@ -120,4 +138,36 @@ mod tests {
         assert_eq!(msg, msg_back);
         assert_eq!(raw, raw_back);
     }
+
+    #[test]
+    fn msg_create_client_invalid_proof_specs() {
+        let tm_header = get_dummy_tendermint_header();
+
+        let mut client_state = get_dummy_tendermint_client_state(tm_header.clone());
+        if let AnyClientState::Tendermint(client_state) = &mut client_state {
+            client_state.proof_specs = vec![ics23::ProofSpec {
+                leaf_spec: None,
+                ..ics23::tendermint_spec()
+            }]
+            .into();
+        }
+
+        let msg = MsgCreateAnyClient::new(
+            client_state,
+            AnyConsensusState::Tendermint(tm_header.try_into().unwrap()),
+            get_dummy_account_id(),
+        )
+        .unwrap();
+
+        // The proof specs are validated when the message is decoded.
+        let raw = MsgCreateClient::from(msg);
+        assert!(MsgCreateAnyClient::try_from(raw).is_err());
+    }
+
+    raw_round_trip_proptests!(
+        MsgCreateAnyClient,
+        MsgCreateClient,
+        arb_msg_create_client(),
+        arb_invalid_raw_msg_create_client()
+    );
 }
@@ -65,3 +65,20 @@ impl From<MsgSubmitAnyMisbehaviour> for RawMsgSubmitMisbehaviour {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::raw_round_trip_proptests;
+
+    use super::*;
+    use crate::arbitrary::ics02_client::{
+        arb_invalid_raw_msg_submit_misbehaviour, arb_msg_submit_misbehaviour,
+    };
+
+    raw_round_trip_proptests!(
+        MsgSubmitAnyMisbehaviour,
+        RawMsgSubmitMisbehaviour,
+        arb_msg_submit_misbehaviour(),
+        arb_invalid_raw_msg_submit_misbehaviour()
+    );
+}
@@ -82,11 +82,17 @@ mod tests {
 
     use ibc_proto::ibc::core::client::v1::MsgUpdateClient;
 
+    use crate::arbitrary::ics02_client::{
+        arb_invalid_raw_msg_update_client, arb_msg_update_client,
+    };
     use crate::clients::ics07_tendermint::header::test_util::get_dummy_ics07_header;
     use crate::core::ics02_client::header::AnyHeader;
     use crate::core::ics02_client::msgs::MsgUpdateAnyClient;
     use crate::core::ics24_host::identifier::ClientId;
+    use crate::prelude::*;
     use crate::test_utils::get_dummy_account_id;
+    use crate::test_utils::raw_round_trip_proptests;
+    use ibc_proto::ibc::core::client::v1::MsgUpdateClient as RawMsgUpdateClient;
 
     #[test]
     fn msg_update_client_serialization() {
@@ -102,4 +108,11 @@ mod tests {
         assert_eq!(msg, msg_back);
         assert_eq!(raw, raw_back);
     }
+
+    raw_round_trip_proptests!(
+        MsgUpdateAnyClient,
+        RawMsgUpdateClient,
+        arb_msg_update_client(),
+        arb_invalid_raw_msg_update_client()
+    );
 }
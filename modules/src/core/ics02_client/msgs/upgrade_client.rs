@@ -165,6 +165,11 @@ mod tests {
 
     use ibc_proto::ibc::core::client::v1::MsgUpgradeClient as RawMsgUpgradeClient;
 
+    use crate::arbitrary::ics02_client::{
+        arb_invalid_raw_msg_upgrade_client, arb_msg_upgrade_client,
+    };
+    use crate::prelude::*;
+    use crate::test_utils::raw_round_trip_proptests;
     use crate::{
         core::{
             ics02_client::{
@@ -208,4 +213,11 @@ mod tests {
         assert_eq!(msg, msg_back);
         assert_eq!(raw, raw_back);
     }
+
+    raw_round_trip_proptests!(
+        MsgUpgradeAnyClient,
+        RawMsgUpgradeClient,
+        arb_msg_upgrade_client(),
+        arb_invalid_raw_msg_upgrade_client()
+    );
 }
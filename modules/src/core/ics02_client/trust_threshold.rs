@@ -128,3 +128,14 @@ impl fmt::Display for TrustThreshold {
         write!(f, "{}/{}", self.numerator, self.denominator)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::raw_round_trip_proptests;
+
+    use super::*;
+    use crate::arbitrary::ics02_client::arb_trust_threshold;
+    use crate::prelude::*;
+
+    raw_round_trip_proptests!(TrustThreshold, Fraction, arb_trust_threshold());
+}
@@ -390,3 +390,18 @@ impl From<State> for i32 {
         value.into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::raw_round_trip_proptests;
+
+    use super::*;
+    use crate::arbitrary::ics03_connection::{arb_connection_end, arb_invalid_raw_connection_end};
+
+    raw_round_trip_proptests!(
+        ConnectionEnd,
+        RawConnectionEnd,
+        arb_connection_end(),
+        arb_invalid_raw_connection_end()
+    );
+}
@@ -174,8 +174,12 @@ mod tests {
     use ibc_proto::ibc::core::client::v1::Height;
     use ibc_proto::ibc::core::connection::v1::MsgConnectionOpenAck as RawMsgConnectionOpenAck;
 
+    use crate::arbitrary::ics03_connection::{
+        arb_invalid_raw_msg_connection_open_ack, arb_msg_connection_open_ack,
+    };
     use crate::core::ics03_connection::msgs::conn_open_ack::test_util::get_dummy_raw_msg_conn_open_ack;
     use crate::core::ics03_connection::msgs::conn_open_ack::MsgConnectionOpenAck;
+    use crate::test_utils::raw_round_trip_proptests;
 
     #[test]
     fn parse_connection_open_ack_msg() {
@@ -259,4 +263,11 @@ mod tests {
         assert_eq!(raw, raw_back);
         assert_eq!(msg, msg_back);
     }
+
+    raw_round_trip_proptests!(
+        MsgConnectionOpenAck,
+        RawMsgConnectionOpenAck,
+        arb_msg_connection_open_ack(),
+        arb_invalid_raw_msg_connection_open_ack()
+    );
 }
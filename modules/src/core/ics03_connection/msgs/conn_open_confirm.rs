@@ -105,8 +105,12 @@ mod tests {
     use ibc_proto::ibc::core::client::v1::Height;
     use ibc_proto::ibc::core::connection::v1::MsgConnectionOpenConfirm as RawMsgConnectionOpenConfirm;
 
+    use crate::arbitrary::ics03_connection::{
+        arb_invalid_raw_msg_connection_open_confirm, arb_msg_connection_open_confirm,
+    };
     use crate::core::ics03_connection::msgs::conn_open_confirm::test_util::get_dummy_raw_msg_conn_open_confirm;
     use crate::core::ics03_connection::msgs::conn_open_confirm::MsgConnectionOpenConfirm;
+    use crate::test_utils::raw_round_trip_proptests;
 
     #[test]
     fn parse_connection_open_confirm_msg() {
@@ -170,4 +174,11 @@ mod tests {
         assert_eq!(raw, raw_back);
         assert_eq!(msg, msg_back);
     }
+
+    raw_round_trip_proptests!(
+        MsgConnectionOpenConfirm,
+        RawMsgConnectionOpenConfirm,
+        arb_msg_connection_open_confirm(),
+        arb_invalid_raw_msg_connection_open_confirm()
+    );
 }
@@ -112,8 +112,12 @@ mod tests {
     use ibc_proto::ibc::core::connection::v1::MsgConnectionOpenInit as RawMsgConnectionOpenInit;
 
     use super::MsgConnectionOpenInit;
+    use crate::arbitrary::ics03_connection::{
+        arb_invalid_raw_msg_connection_open_init, arb_msg_connection_open_init,
+    };
     use crate::core::ics03_connection::msgs::conn_open_init::test_util::get_dummy_raw_msg_conn_open_init;
     use crate::core::ics03_connection::msgs::test_util::get_dummy_raw_counterparty;
+    use crate::test_utils::raw_round_trip_proptests;
 
     #[test]
     fn parse_connection_open_init_msg() {
@@ -180,4 +184,11 @@ mod tests {
         assert_eq!(raw, raw_back);
         assert_eq!(msg, msg_back);
     }
+
+    raw_round_trip_proptests!(
+        MsgConnectionOpenInit,
+        RawMsgConnectionOpenInit,
+        arb_msg_connection_open_init(),
+        arb_invalid_raw_msg_connection_open_init()
+    );
 }
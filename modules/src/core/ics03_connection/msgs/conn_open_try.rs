@@ -240,9 +240,13 @@ mod tests {
     use ibc_proto::ibc::core::connection::v1::Counterparty as RawCounterparty;
     use ibc_proto::ibc::core::connection::v1::MsgConnectionOpenTry as RawMsgConnectionOpenTry;
 
+    use crate::arbitrary::ics03_connection::{
+        arb_invalid_raw_msg_connection_open_try, arb_msg_connection_open_try,
+    };
     use crate::core::ics03_connection::msgs::conn_open_try::test_util::get_dummy_raw_msg_conn_open_try;
     use crate::core::ics03_connection::msgs::conn_open_try::MsgConnectionOpenTry;
     use crate::core::ics03_connection::msgs::test_util::get_dummy_raw_counterparty;
+    use crate::test_utils::raw_round_trip_proptests;
 
     #[test]
     fn parse_connection_open_try_msg() {
@@ -362,4 +366,11 @@ mod tests {
         assert_eq!(raw, raw_back);
         assert_eq!(msg, msg_back);
     }
+
+    raw_round_trip_proptests!(
+        MsgConnectionOpenTry,
+        RawMsgConnectionOpenTry,
+        arb_msg_connection_open_try(),
+        arb_invalid_raw_msg_connection_open_try()
+    );
 }
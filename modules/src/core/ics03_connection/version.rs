@@ -111,8 +111,10 @@ mod tests {
 
     use ibc_proto::ibc::core::connection::v1::Version as RawVersion;
 
+    use crate::arbitrary::ics03_connection::{arb_invalid_raw_version, arb_version};
     use crate::core::ics03_connection::error::Error;
     use crate::core::ics03_connection::version::{get_compatible_versions, pick_version, Version};
+    use crate::test_utils::raw_round_trip_proptests;
 
     fn good_versions() -> Vec<RawVersion> {
         vec![
@@ -306,4 +308,11 @@ mod tests {
         let def_back = def_raw.try_into().unwrap();
         assert_eq!(def, def_back);
     }
+
+    raw_round_trip_proptests!(
+        Version,
+        RawVersion,
+        arb_version(),
+        arb_invalid_raw_version()
+    );
 }
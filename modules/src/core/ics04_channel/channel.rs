@@ -11,7 +11,7 @@ use ibc_proto::ibc::core::channel::v1::{
     IdentifiedChannel as RawIdentifiedChannel,
 };
 
-use crate::core::ics04_channel::{error::Error, Version};
+use crate::core::ics04_channel::{error::Error, packet::Sequence, Version};
 use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -43,6 +43,7 @@ impl TryFrom<RawIdentifiedChannel> for IdentifiedChannelEnd {
             counterparty: value.counterparty,
             connection_hops: value.connection_hops,
             version: value.version,
+            upgrade_sequence: value.upgrade_sequence,
         };
 
         Ok(IdentifiedChannelEnd {
@@ -68,6 +69,7 @@ impl From<IdentifiedChannelEnd> for RawIdentifiedChannel {
             version: value.channel_end.version.to_string(),
             port_id: value.port_id.to_string(),
             channel_id: value.channel_id.to_string(),
+            upgrade_sequence: value.channel_end.upgrade_sequence.into(),
         }
     }
 }
@@ -79,6 +81,9 @@ pub struct ChannelEnd {
     pub remote: Counterparty,
     pub connection_hops: Vec<ConnectionId>,
     pub version: Version,
+    /// The sequence of the latest upgrade attempt of this channel, zero if it was never upgraded.
+    #[serde(default)]
+    pub upgrade_sequence: Sequence,
 }
 
 impl Default for ChannelEnd {
@@ -89,6 +94,7 @@ impl Default for ChannelEnd {
             remote: Counterparty::default(),
             connection_hops: Vec::new(),
             version: Version::default(),
+            upgrade_sequence: Sequence::default(),
         }
     }
 }
@@ -123,13 +129,10 @@ impl TryFrom<RawChannel> for ChannelEnd {
 
         let version = value.version.into();
 
-        Ok(ChannelEnd::new(
-            chan_state,
-            chan_ordering,
-            remote,
-            connection_hops,
-            version,
-        ))
+        Ok(
+            ChannelEnd::new(chan_state, chan_ordering, remote, connection_hops, version)
+                .with_upgrade_sequence(value.upgrade_sequence.into()),
+        )
     }
 }
 
@@ -145,6 +148,7 @@ impl From<ChannelEnd> for RawChannel {
                 .map(|v| v.as_str().to_string())
                 .collect(),
             version: value.version.to_string(),
+            upgrade_sequence: value.upgrade_sequence.into(),
         }
     }
 }
@@ -164,6 +168,15 @@ impl ChannelEnd {
             remote,
             connection_hops,
             version,
+            upgrade_sequence: Sequence::default(),
+        }
+    }
+
+    /// Sets the sequence of the latest upgrade attempt of this channel.
+    pub fn with_upgrade_sequence(self, upgrade_sequence: Sequence) -> Self {
+        Self {
+            upgrade_sequence,
+            ..self
         }
     }
 
@@ -205,6 +218,10 @@ impl ChannelEnd {
         &self.version
     }
 
+    pub fn upgrade_sequence(&self) -> Sequence {
+        self.upgrade_sequence
+    }
+
     pub fn validate_basic(&self) -> Result<(), Error> {
         if self.connection_hops.len() != 1 {
             return Err(Error::invalid_connection_hops_length(
@@ -300,6 +317,9 @@ pub enum Order {
     None = 0,
     Unordered = 1,
     Ordered = 2,
+    /// Packets are delivered in the order in which they were sent, but a packet that timed out
+    /// is skipped by the receiving end instead of closing the channel.
+    OrderedAllowTimeout = 3,
 }
 
 impl Default for Order {
@@ -321,6 +341,18 @@ impl Order {
             Self::None => "UNINITIALIZED",
             Self::Unordered => "ORDER_UNORDERED",
             Self::Ordered => "ORDER_ORDERED",
+            Self::OrderedAllowTimeout => "ORDER_ORDERED_ALLOW_TIMEOUT",
+        }
+    }
+
+    /// Returns the connection version feature a channel with this ordering requires.
+    ///
+    /// Connection versions do not advertise `ORDER_ORDERED_ALLOW_TIMEOUT`, which only relaxes
+    /// the timeout semantics of `ORDER_ORDERED` and so is available wherever the latter is.
+    pub fn connection_feature(&self) -> &'static str {
+        match self {
+            Self::OrderedAllowTimeout => Self::Ordered.as_str(),
+            _ => self.as_str(),
         }
     }
 
@@ -330,6 +362,7 @@ impl Order {
             0 => Ok(Self::None),
             1 => Ok(Self::Unordered),
             2 => Ok(Self::Ordered),
+            3 => Ok(Self::OrderedAllowTimeout),
             _ => Err(Error::unknown_order_type(nr.to_string())),
         }
     }
@@ -343,6 +376,7 @@ impl FromStr for Order {
             "uninitialized" => Ok(Self::None),
             "unordered" => Ok(Self::Unordered),
             "ordered" => Ok(Self::Ordered),
+            "ordered_allow_timeout" => Ok(Self::OrderedAllowTimeout),
             _ => Err(Error::unknown_order_type(s.to_string())),
         }
     }
@@ -355,6 +389,11 @@ pub enum State {
     TryOpen = 2,
     Open = 3,
     Closed = 4,
+    /// The channel accepted an upgrade and waits for its in-flight packets to be flushed.
+    Flushing = 5,
+    /// The channel has no more in-flight packets and waits for the counterparty to do the same
+    /// before opening with the upgraded parameters.
+    FlushComplete = 6,
 }
 
 impl State {
@@ -366,6 +405,8 @@ impl State {
             Self::TryOpen => "TRYOPEN",
             Self::Open => "OPEN",
             Self::Closed => "CLOSED",
+            Self::Flushing => "FLUSHING",
+            Self::FlushComplete => "FLUSHCOMPLETE",
         }
     }
 
@@ -377,6 +418,8 @@ impl State {
             2 => Ok(Self::TryOpen),
             3 => Ok(Self::Open),
             4 => Ok(Self::Closed),
+            5 => Ok(Self::Flushing),
+            6 => Ok(Self::FlushComplete),
             _ => Err(Error::unknown_state(s)),
         }
     }
@@ -386,6 +429,11 @@ impl State {
         self == State::Open
     }
 
+    /// Returns whether or not a channel with this state is in the middle of an upgrade.
+    pub fn is_upgrading(self) -> bool {
+        matches!(self, State::Flushing | State::FlushComplete)
+    }
+
     /// Returns whether or not the channel with this state
     /// has progressed less or the same than the argument.
     ///
@@ -431,6 +479,7 @@ pub mod test_util {
             counterparty: Some(get_dummy_raw_counterparty()),
             connection_hops: vec![ConnectionId::default().to_string()],
             version: "ics20".to_string(), // The version is not validated.
+            upgrade_sequence: 0,
         }
     }
 }
@@ -444,8 +493,10 @@ mod tests {
 
     use ibc_proto::ibc::core::channel::v1::Channel as RawChannel;
 
+    use crate::arbitrary::ics04_channel::{arb_channel_end, arb_invalid_raw_channel_end};
     use crate::core::ics04_channel::channel::test_util::get_dummy_raw_channel_end;
     use crate::core::ics04_channel::channel::ChannelEnd;
+    use crate::test_utils::raw_round_trip_proptests;
 
     #[test]
     fn channel_end_try_from_raw() {
@@ -561,6 +612,11 @@ mod tests {
                 want_res: Order::Ordered,
                 want_err: false,
             },
+            Test {
+                ordering: "ORDER_ORDERED_ALLOW_TIMEOUT",
+                want_res: Order::OrderedAllowTimeout,
+                want_err: false,
+            },
             Test {
                 ordering: "UNKNOWN_ORDER",
                 want_res: Order::None,
@@ -580,4 +636,11 @@ mod tests {
             }
         }
     }
+
+    raw_round_trip_proptests!(
+        ChannelEnd,
+        RawChannel,
+        arb_channel_end(),
+        arb_invalid_raw_channel_end()
+    );
 }
@@ -1,5 +1,6 @@
 //! ICS4 (channel) context. The two traits `ChannelReader ` and `ChannelKeeper` define
-//! the interface that any host chain must implement to be able to process any `ChannelMsg`.
+//! the interface that any host chain must implement to be able to process any `ChannelMsg`
+//! or `ChannelUpgradeMsg`.
 //!
 use core::time::Duration;
 use num_traits::float::FloatCore;
@@ -9,9 +10,11 @@ use crate::core::ics02_client::client_state::AnyClientState;
 use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics04_channel::channel::ChannelEnd;
 use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
+use crate::core::ics04_channel::handler::prune_packets::PrunePacketsResult;
 use crate::core::ics04_channel::handler::recv_packet::RecvPacketResult;
-use crate::core::ics04_channel::handler::{ChannelIdState, ChannelResult};
+use crate::core::ics04_channel::handler::{ChannelIdState, ChannelResult, ChannelUpgradeResult};
 use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement;
+use crate::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use crate::core::ics04_channel::{error::Error, packet::Receipt};
 use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
 use crate::prelude::*;
@@ -68,6 +71,46 @@ pub trait ChannelReader {
         key: &(PortId, ChannelId, Sequence),
     ) -> Result<AcknowledgementCommitment, Error>;
 
+    /// Returns `true` if packets sent on the given channel are still waiting for an
+    /// acknowledgement or a timeout, not counting the packet with sequence `except`.
+    /// This is checked while handling the packets of a flushing channel, so hosts should answer
+    /// without iterating over the packets sent on the channel, e.g., by counting the packet
+    /// commitments stored for each channel.
+    fn has_inflight_packets(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+        except: Option<Sequence>,
+    ) -> Result<bool, Error>;
+
+    /// Returns the sequence of the first packet whose receipt and acknowledgement were not pruned
+    /// on the given channel end, i.e., the sequence `1` if none were pruned.
+    /// See [`prune_packets`](crate::core::ics04_channel::handler::prune_packets()).
+    /// Hosts which support pruning must override this, along with
+    /// [`ChannelKeeper::delete_packet_receipt`] and [`ChannelKeeper::store_pruning_sequence`].
+    fn pruning_sequence(&self, _port_channel_id: &(PortId, ChannelId)) -> Result<Sequence, Error> {
+        Ok(Sequence::from(1))
+    }
+
+    /// Returns the upgrade in progress on the given channel end.
+    fn upgrade(&self, port_channel_id: &(PortId, ChannelId)) -> Result<Upgrade, Error>;
+
+    /// Returns the upgrade of the counterparty channel end, as stored once the upgrade
+    /// handshake reaches the flushing stage on the given channel end.
+    fn counterparty_upgrade(&self, port_channel_id: &(PortId, ChannelId))
+        -> Result<Upgrade, Error>;
+
+    /// Returns the receipt of the last aborted upgrade of the given channel end.
+    fn upgrade_error_receipt(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<ErrorReceipt, Error>;
+
+    /// Returns the time the counterparty is given to complete its side of an upgrade,
+    /// from the moment this chain starts flushing.
+    fn upgrade_timeout(&self) -> Duration {
+        Duration::from_secs(600)
+    }
+
     /// Compute the commitment for a packet.
     /// Note that the absence of `timeout_height` is treated as
     /// `{revision_number: 0, revision_height: 0}` to be consistent with ibc-go,
@@ -199,6 +242,18 @@ pub trait ChannelKeeper {
                     sequence,
                     receipt,
                 } => self.store_packet_receipt((port_id, channel_id, sequence), receipt)?,
+                RecvPacketResult::Timeout {
+                    port_id,
+                    channel_id,
+                    sequence,
+                    next_seq_recv,
+                } => {
+                    self.store_next_sequence_recv(
+                        (port_id.clone(), channel_id.clone()),
+                        next_seq_recv,
+                    )?;
+                    self.store_packet_receipt((port_id, channel_id, sequence), Receipt::Timeout)?
+                }
                 RecvPacketResult::NoOp => unreachable!(),
             },
             PacketResult::WriteAck(res) => {
@@ -208,32 +263,77 @@ pub trait ChannelKeeper {
                 )?;
             }
             PacketResult::Ack(res) => {
-                match res.seq_number {
-                    Some(s) => {
-                        //Ordered Channel
-                        self.store_next_sequence_ack((res.port_id.clone(), res.channel_id), s)?;
-                    }
-                    None => {
-                        //Unordered Channel
-                        self.delete_packet_commitment((
-                            res.port_id.clone(),
-                            res.channel_id,
-                            res.seq,
-                        ))?;
-                    }
+                if let Some(c) = res.channel {
+                    // Upgrading channel done flushing
+                    self.store_channel((res.port_id.clone(), res.channel_id.clone()), &c)?;
+                }
+                if let Some(s) = res.seq_number {
+                    //Ordered Channel
+                    self.store_next_sequence_ack((res.port_id.clone(), res.channel_id.clone()), s)?;
                 }
+                self.delete_packet_commitment((res.port_id, res.channel_id, res.seq))?;
             }
             PacketResult::Timeout(res) => {
                 if let Some(c) = res.channel {
                     //Ordered Channel
                     self.store_channel((res.port_id.clone(), res.channel_id.clone()), &c)?;
                 }
+                if let Some(s) = res.seq_number {
+                    //Ordered Allow Timeout Channel
+                    self.store_next_sequence_ack((res.port_id.clone(), res.channel_id.clone()), s)?;
+                }
                 self.delete_packet_commitment((res.port_id, res.channel_id, res.seq))?;
             }
         }
         Ok(())
     }
 
+    fn store_channel_upgrade_result(&mut self, result: ChannelUpgradeResult) -> Result<(), Error> {
+        let port_channel_id = (result.port_id, result.channel_id);
+
+        self.store_channel(port_channel_id.clone(), &result.channel_end)?;
+
+        match result.upgrade {
+            Some(upgrade) => self.store_upgrade(port_channel_id.clone(), upgrade)?,
+            None => self.delete_upgrade(port_channel_id.clone())?,
+        }
+
+        match result.counterparty_upgrade {
+            Some(upgrade) => self.store_counterparty_upgrade(port_channel_id.clone(), upgrade)?,
+            None => self.delete_counterparty_upgrade(port_channel_id.clone())?,
+        }
+
+        if let Some(error_receipt) = result.error_receipt {
+            self.store_upgrade_error_receipt(port_channel_id.clone(), error_receipt)?;
+        }
+
+        if let Some(seq) = result.next_sequence_recv {
+            self.store_next_sequence_recv(port_channel_id.clone(), seq)?;
+        }
+
+        if let Some(seq) = result.next_sequence_ack {
+            self.store_next_sequence_ack(port_channel_id, seq)?;
+        }
+
+        Ok(())
+    }
+
+    fn store_prune_packets_result(&mut self, result: PrunePacketsResult) -> Result<(), Error> {
+        for seq in result.receipts {
+            self.delete_packet_receipt((result.port_id.clone(), result.channel_id.clone(), seq))?;
+        }
+
+        for seq in result.acknowledgements {
+            self.delete_packet_acknowledgement((
+                result.port_id.clone(),
+                result.channel_id.clone(),
+                seq,
+            ))?;
+        }
+
+        self.store_pruning_sequence((result.port_id, result.channel_id), result.pruning_sequence)
+    }
+
     fn store_packet_commitment(
         &mut self,
         key: (PortId, ChannelId, Sequence),
@@ -249,6 +349,12 @@ pub trait ChannelKeeper {
         receipt: Receipt,
     ) -> Result<(), Error>;
 
+    /// Deletes the receipt of the given packet. Only called when pruning packets, which the
+    /// default implementation does not support.
+    fn delete_packet_receipt(&mut self, _key: (PortId, ChannelId, Sequence)) -> Result<(), Error> {
+        Err(Error::packet_pruning_not_supported())
+    }
+
     fn store_packet_acknowledgement(
         &mut self,
         key: (PortId, ChannelId, Sequence),
@@ -291,6 +397,44 @@ pub trait ChannelKeeper {
         seq: Sequence,
     ) -> Result<(), Error>;
 
+    /// Stores the sequence of the first packet whose receipt and acknowledgement were not pruned
+    /// on the given channel end. Only called when pruning packets, which the default
+    /// implementation does not support.
+    fn store_pruning_sequence(
+        &mut self,
+        _port_channel_id: (PortId, ChannelId),
+        _seq: Sequence,
+    ) -> Result<(), Error> {
+        Err(Error::packet_pruning_not_supported())
+    }
+
+    fn store_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        upgrade: Upgrade,
+    ) -> Result<(), Error>;
+
+    /// Deletes the upgrade of the given channel end, if any.
+    fn delete_upgrade(&mut self, port_channel_id: (PortId, ChannelId)) -> Result<(), Error>;
+
+    fn store_counterparty_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        upgrade: Upgrade,
+    ) -> Result<(), Error>;
+
+    /// Deletes the counterparty upgrade of the given channel end, if any.
+    fn delete_counterparty_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+    ) -> Result<(), Error>;
+
+    fn store_upgrade_error_receipt(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        error_receipt: ErrorReceipt,
+    ) -> Result<(), Error>;
+
     /// Called upon channel identifier creation (Init or Try message processing).
     /// Increases the counter which keeps track of how many channels have been created.
     /// Should never fail.
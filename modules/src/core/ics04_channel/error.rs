@@ -9,7 +9,7 @@ use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, Por
 use crate::prelude::*;
 use crate::proofs::ProofError;
 use crate::signer::SignerError;
-use crate::timestamp::Timestamp;
+use crate::timestamp::{Timestamp, TimestampOverflowError};
 use crate::Height;
 
 use flex_error::{define_error, TraceError};
@@ -332,12 +332,116 @@ define_error! {
                     e.client_id, e.height)
             },
 
+        MissingUpgradeFields
+            | _ | { "missing upgrade fields" },
+
+        MissingUpgrade
+            | _ | { "missing upgrade" },
+
+        MissingErrorReceipt
+            | _ | { "missing upgrade error receipt" },
+
+        MissingCounterpartyChannel
+            | _ | { "missing counterparty channel end" },
+
+        UpgradeFieldsUnchanged
+            | _ | { "the proposed upgrade fields are identical to the current channel fields" },
+
+        UpgradeNotFound
+            { port_id: PortId, channel_id: ChannelId }
+            | e | {
+                format_args!(
+                    "no upgrade in progress for the channel end ({0}, {1})",
+                    e.port_id, e.channel_id)
+            },
+
+        CounterpartyUpgradeNotFound
+            { port_id: PortId, channel_id: ChannelId }
+            | e | {
+                format_args!(
+                    "no counterparty upgrade stored for the channel end ({0}, {1})",
+                    e.port_id, e.channel_id)
+            },
+
+        UpgradeInProgress
+            { channel_id: ChannelId }
+            | e | {
+                format_args!(
+                    "an upgrade is already in progress for channel {0}",
+                    e.channel_id)
+            },
+
+        IncompatibleCounterpartyUpgrade
+            { channel_id: ChannelId }
+            | e | {
+                format_args!(
+                    "the upgrade proposed by the counterparty is incompatible with the one in progress for channel {0}",
+                    e.channel_id)
+            },
+
+        UpgradeNotSupported
+            { port_id: PortId }
+            | e | {
+                format_args!(
+                    "the application bound to port {0} does not support channel upgrades",
+                    e.port_id)
+            },
+
+        InvalidUpgradeSequence
+            {
+                counterparty_sequence: Sequence,
+                sequence: Sequence,
+            }
+            | e | {
+                format_args!(
+                    "counterparty upgrade sequence {0} < upgrade sequence {1}",
+                    e.counterparty_sequence, e.sequence)
+            },
+
+        InvalidCounterpartyChannelState
+            { state: State }
+            | e | {
+                format_args!(
+                    "counterparty channel should not be in state {0}",
+                    e.state)
+            },
+
+        InvalidUpgradeConnectionHops
+            { connection_id: ConnectionId }
+            | e | {
+                format_args!(
+                    "the proposed upgrade connection {0} does not lead to the connection proposed by the counterparty",
+                    e.connection_id)
+            },
+
+        UpgradeTimeoutNotReached
+            | _ | { "the counterparty upgrade has not timed out yet" },
+
+        TimestampOverflow
+            [ TimestampOverflowError ]
+            | _ | { "timestamp overflow" },
+
+        VerifyChannelUpgradeFailed
+            [ client_error::Error ]
+            | _ | {
+                "Error verifying channel upgrade"
+            },
+
+        VerifyUpgradeErrorReceiptFailed
+            [ client_error::Error ]
+            | _ | {
+                "Error verifying channel upgrade error receipt"
+            },
+
         RouteNotFound
             | _ | { "route not found" },
 
         ImplementationSpecific
             | _ | { "implementation specific error" },
 
+        PacketPruningNotSupported
+            | _ | { "the host does not support pruning packets" },
+
         AppModule
             { description: String }
             | e | {
@@ -6,7 +6,7 @@ use tendermint::abci::Event as AbciEvent;
 
 use crate::core::ics02_client::height::Height;
 use crate::core::ics04_channel::error::Error;
-use crate::core::ics04_channel::packet::Packet;
+use crate::core::ics04_channel::packet::{Packet, Sequence};
 use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
 use crate::events::{
     extract_attribute, maybe_extract_attribute, Error as EventError, IbcEvent, IbcEventType,
@@ -23,6 +23,7 @@ const CHANNEL_ID_ATTRIBUTE_KEY: &str = "channel_id";
 const PORT_ID_ATTRIBUTE_KEY: &str = "port_id";
 const COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY: &str = "counterparty_channel_id";
 const COUNTERPARTY_PORT_ID_ATTRIBUTE_KEY: &str = "counterparty_port_id";
+const UPGRADE_SEQUENCE_ATTRIBUTE_KEY: &str = "upgrade_sequence";
 
 /// Packet event attribute keys
 pub const PKT_SEQ_ATTRIBUTE_KEY: &str = "packet_sequence";
@@ -67,6 +68,30 @@ pub fn try_from_tx(event: &tendermint::abci::Event) -> Option<IbcEvent> {
             .map(|res| res.ok().map(IbcEvent::CloseConfirmChannel))
             .ok()
             .flatten(),
+        Ok(IbcEventType::UpgradeInitChannel) => extract_upgrade_attributes_from_tx(event)
+            .map(|attrs| IbcEvent::UpgradeInitChannel(UpgradeInit(attrs)))
+            .ok(),
+        Ok(IbcEventType::UpgradeTryChannel) => extract_upgrade_attributes_from_tx(event)
+            .map(|attrs| IbcEvent::UpgradeTryChannel(UpgradeTry(attrs)))
+            .ok(),
+        Ok(IbcEventType::UpgradeAckChannel) => extract_upgrade_attributes_from_tx(event)
+            .map(|attrs| IbcEvent::UpgradeAckChannel(UpgradeAck(attrs)))
+            .ok(),
+        Ok(IbcEventType::UpgradeConfirmChannel) => extract_upgrade_attributes_from_tx(event)
+            .map(|attrs| IbcEvent::UpgradeConfirmChannel(UpgradeConfirm(attrs)))
+            .ok(),
+        Ok(IbcEventType::UpgradeOpenChannel) => extract_upgrade_attributes_from_tx(event)
+            .map(|attrs| IbcEvent::UpgradeOpenChannel(UpgradeOpen(attrs)))
+            .ok(),
+        Ok(IbcEventType::UpgradeTimeoutChannel) => extract_upgrade_attributes_from_tx(event)
+            .map(|attrs| IbcEvent::UpgradeTimeoutChannel(UpgradeTimeout(attrs)))
+            .ok(),
+        Ok(IbcEventType::UpgradeCancelChannel) => extract_upgrade_attributes_from_tx(event)
+            .map(|attrs| IbcEvent::UpgradeCancelChannel(UpgradeCancel(attrs)))
+            .ok(),
+        Ok(IbcEventType::UpgradeErrorChannel) => extract_upgrade_attributes_from_tx(event)
+            .map(|attrs| IbcEvent::UpgradeErrorChannel(UpgradeError(attrs)))
+            .ok(),
         Ok(IbcEventType::SendPacket) => {
             extract_packet_and_write_ack_from_tx(event)
                 .map(|(packet, write_ack)| {
@@ -143,6 +168,38 @@ fn extract_attributes_from_tx(event: &tendermint::abci::Event) -> Result<Attribu
     Ok(attr)
 }
 
+fn extract_upgrade_attributes_from_tx(
+    event: &tendermint::abci::Event,
+) -> Result<UpgradeAttributes, Error> {
+    let mut attr = UpgradeAttributes::default();
+
+    for tag in &event.attributes {
+        let key = tag.key.as_ref();
+        let value = tag.value.as_ref();
+        match key {
+            PORT_ID_ATTRIBUTE_KEY => attr.port_id = value.parse().map_err(Error::identifier)?,
+            CHANNEL_ID_ATTRIBUTE_KEY => {
+                attr.channel_id = value.parse().map_err(Error::identifier)?;
+            }
+            COUNTERPARTY_PORT_ID_ATTRIBUTE_KEY => {
+                attr.counterparty_port_id = value.parse().map_err(Error::identifier)?;
+            }
+            COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY => {
+                attr.counterparty_channel_id = value.parse().ok();
+            }
+            UPGRADE_SEQUENCE_ATTRIBUTE_KEY => {
+                attr.upgrade_sequence = value
+                    .parse::<u64>()
+                    .map_err(|e| Error::invalid_string_as_sequence(value.to_string(), e))?
+                    .into()
+            }
+            _ => {}
+        }
+    }
+
+    Ok(attr)
+}
+
 fn extract_packet_and_write_ack_from_tx(
     event: &tendermint::abci::Event,
 ) -> Result<(Packet, Vec<u8>), Error> {
@@ -774,6 +831,133 @@ impl_try_from_raw_obj_for_event!(
     CloseConfirm
 );
 
+/// The attributes of the events emitted by the channel upgrade handshake.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UpgradeAttributes {
+    pub height: Height,
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub counterparty_port_id: PortId,
+    pub counterparty_channel_id: Option<ChannelId>,
+    pub upgrade_sequence: Sequence,
+}
+
+impl Default for UpgradeAttributes {
+    fn default() -> Self {
+        Self {
+            height: Height::new(0, 1).unwrap(),
+            port_id: Default::default(),
+            channel_id: Default::default(),
+            counterparty_port_id: Default::default(),
+            counterparty_channel_id: Default::default(),
+            upgrade_sequence: Default::default(),
+        }
+    }
+}
+
+/// Convert upgrade attributes to Tendermint ABCI tags
+impl From<UpgradeAttributes> for Vec<Tag> {
+    fn from(a: UpgradeAttributes) -> Self {
+        let mut attributes = vec![
+            Tag {
+                key: HEIGHT_ATTRIBUTE_KEY.parse().unwrap(),
+                value: a.height.to_string().parse().unwrap(),
+            },
+            Tag {
+                key: PORT_ID_ATTRIBUTE_KEY.parse().unwrap(),
+                value: a.port_id.to_string().parse().unwrap(),
+            },
+            Tag {
+                key: CHANNEL_ID_ATTRIBUTE_KEY.parse().unwrap(),
+                value: a.channel_id.to_string().parse().unwrap(),
+            },
+            Tag {
+                key: COUNTERPARTY_PORT_ID_ATTRIBUTE_KEY.parse().unwrap(),
+                value: a.counterparty_port_id.to_string().parse().unwrap(),
+            },
+        ];
+        if let Some(channel_id) = a.counterparty_channel_id {
+            attributes.push(Tag {
+                key: COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY.parse().unwrap(),
+                value: channel_id.to_string().parse().unwrap(),
+            });
+        }
+        attributes.push(Tag {
+            key: UPGRADE_SEQUENCE_ATTRIBUTE_KEY.parse().unwrap(),
+            value: a.upgrade_sequence.to_string().parse().unwrap(),
+        });
+        attributes
+    }
+}
+
+macro_rules! define_upgrade_events {
+    ($($(#[$doc:meta])* $event:ident => $variant:ident),+ $(,)?) => {
+        $(
+            $(#[$doc])*
+            #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+            pub struct $event(pub UpgradeAttributes);
+
+            impl $event {
+                pub fn attributes(&self) -> &UpgradeAttributes {
+                    &self.0
+                }
+                pub fn port_id(&self) -> &PortId {
+                    &self.0.port_id
+                }
+                pub fn channel_id(&self) -> &ChannelId {
+                    &self.0.channel_id
+                }
+                pub fn height(&self) -> Height {
+                    self.0.height
+                }
+                pub fn set_height(&mut self, height: Height) {
+                    self.0.height = height;
+                }
+            }
+
+            impl From<$event> for IbcEvent {
+                fn from(v: $event) -> Self {
+                    IbcEvent::$variant(v)
+                }
+            }
+
+            impl EventType for $event {
+                fn event_type() -> IbcEventType {
+                    IbcEventType::$variant
+                }
+            }
+
+            impl From<$event> for AbciEvent {
+                fn from(v: $event) -> Self {
+                    AbciEvent {
+                        type_str: <$event>::event_type().as_str().to_string(),
+                        attributes: Vec::<Tag>::from(v.0),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+define_upgrade_events!(
+    /// Emitted when an upgrade is proposed with `MsgChannelUpgradeInit`.
+    UpgradeInit => UpgradeInitChannel,
+    /// Emitted when the counterparty of the proposing end agrees to an upgrade.
+    UpgradeTry => UpgradeTryChannel,
+    /// Emitted when the proposing end starts flushing its in-flight packets.
+    UpgradeAck => UpgradeAckChannel,
+    /// Emitted when an upgrade is confirmed after both ends started flushing.
+    UpgradeConfirm => UpgradeConfirmChannel,
+    /// Emitted when a channel end reopens with the upgraded fields.
+    UpgradeOpen => UpgradeOpenChannel,
+    /// Emitted when an upgrade is aborted because the counterparty timed out.
+    UpgradeTimeout => UpgradeTimeoutChannel,
+    /// Emitted when an upgrade is cancelled after the counterparty aborted it.
+    UpgradeCancel => UpgradeCancelChannel,
+    /// Emitted when an upgrade is aborted and an error receipt is written.
+    UpgradeError => UpgradeErrorChannel,
+);
+
 #[derive(Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub struct SendPacket {
     pub height: Height,
@@ -1219,6 +1403,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn upgrade_event_to_abci_event() {
+        let attributes = UpgradeAttributes {
+            height: Height::new(0, 1).unwrap(),
+            port_id: "test_port".parse().unwrap(),
+            channel_id: "channel-0".parse().unwrap(),
+            counterparty_port_id: "counterparty_test_port".parse().unwrap(),
+            counterparty_channel_id: Some("channel-1".parse().unwrap()),
+            upgrade_sequence: Sequence::from(2),
+        };
+        let abci_events = vec![
+            AbciEvent::from(UpgradeInit(attributes.clone())),
+            AbciEvent::from(UpgradeTry(attributes.clone())),
+            AbciEvent::from(UpgradeAck(attributes.clone())),
+            AbciEvent::from(UpgradeConfirm(attributes.clone())),
+            AbciEvent::from(UpgradeOpen(attributes.clone())),
+            AbciEvent::from(UpgradeTimeout(attributes.clone())),
+            AbciEvent::from(UpgradeCancel(attributes.clone())),
+            AbciEvent::from(UpgradeError(attributes.clone())),
+        ];
+
+        for event in abci_events {
+            let attrs = match try_from_tx(&event) {
+                Some(IbcEvent::UpgradeInitChannel(e)) => e.0,
+                Some(IbcEvent::UpgradeTryChannel(e)) => e.0,
+                Some(IbcEvent::UpgradeAckChannel(e)) => e.0,
+                Some(IbcEvent::UpgradeConfirmChannel(e)) => e.0,
+                Some(IbcEvent::UpgradeOpenChannel(e)) => e.0,
+                Some(IbcEvent::UpgradeTimeoutChannel(e)) => e.0,
+                Some(IbcEvent::UpgradeCancelChannel(e)) => e.0,
+                Some(IbcEvent::UpgradeErrorChannel(e)) => e.0,
+                _ => panic!("converted event was wrong"),
+            };
+            assert_eq!(attrs, attributes);
+        }
+    }
+
     #[test]
     fn packet_event_to_abci_event() {
         let packet = Packet {
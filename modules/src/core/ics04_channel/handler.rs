@@ -1,11 +1,16 @@
 //! This module implements the processing logic for ICS4 (channel) messages.
 
-use crate::core::ics04_channel::channel::ChannelEnd;
-use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics03_connection::connection::{ConnectionEnd, State as ConnectionState};
+use crate::core::ics04_channel::channel::{ChannelEnd, Order, State};
+use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
 use crate::core::ics04_channel::error::Error;
-use crate::core::ics04_channel::msgs::ChannelMsg;
+use crate::core::ics04_channel::events::UpgradeAttributes;
+use crate::core::ics04_channel::handler::prune_packets::CommitmentAbsenceProofs;
+use crate::core::ics04_channel::msgs::{ChannelMsg, ChannelUpgradeMsg};
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use crate::core::ics04_channel::{msgs::PacketMsg, packet::PacketResult};
-use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
 use crate::core::ics26_routing::context::{
     Ics26Context, ModuleId, ModuleOutputBuilder, OnRecvPacketAck, Router,
 };
@@ -18,6 +23,14 @@ pub mod chan_open_ack;
 pub mod chan_open_confirm;
 pub mod chan_open_init;
 pub mod chan_open_try;
+pub mod chan_upgrade_ack;
+pub mod chan_upgrade_cancel;
+pub mod chan_upgrade_confirm;
+pub mod chan_upgrade_init;
+pub mod chan_upgrade_open;
+pub mod chan_upgrade_timeout;
+pub mod chan_upgrade_try;
+pub mod prune_packets;
 pub mod recv_packet;
 pub mod send_packet;
 pub mod timeout;
@@ -44,6 +57,145 @@ pub struct ChannelResult {
     pub channel_end: ChannelEnd,
 }
 
+/// The outcome of processing a message of the channel upgrade handshake.
+#[derive(Clone, Debug)]
+pub struct ChannelUpgradeResult {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub channel_end: ChannelEnd,
+    /// The upgrade of this channel end to store, or `None` to delete the stored one.
+    pub upgrade: Option<Upgrade>,
+    /// The counterparty upgrade to store, or `None` to delete the stored one.
+    pub counterparty_upgrade: Option<Upgrade>,
+    /// Set when the upgrade was aborted.
+    pub error_receipt: Option<ErrorReceipt>,
+    pub next_sequence_recv: Option<Sequence>,
+    pub next_sequence_ack: Option<Sequence>,
+}
+
+impl ChannelUpgradeResult {
+    /// Returns the result of aborting the upgrade of the given channel end, which restores it to
+    /// `OPEN` and records the failure in an error receipt for the counterparty.
+    pub(crate) fn aborted(
+        port_id: PortId,
+        channel_id: ChannelId,
+        mut channel_end: ChannelEnd,
+        error_receipt: ErrorReceipt,
+    ) -> Self {
+        channel_end.set_state(State::Open);
+        Self {
+            port_id,
+            channel_id,
+            channel_end,
+            upgrade: None,
+            counterparty_upgrade: None,
+            error_receipt: Some(error_receipt),
+            next_sequence_recv: None,
+            next_sequence_ack: None,
+        }
+    }
+}
+
+impl ChannelUpgradeResult {
+    /// Returns the result of completing the upgrade of the given channel end, which reopens it
+    /// with the upgraded fields and deletes both upgrades.
+    pub(crate) fn opened(
+        port_id: PortId,
+        channel_id: ChannelId,
+        mut channel_end: ChannelEnd,
+        upgrade: &Upgrade,
+        counterparty_upgrade: &Upgrade,
+    ) -> Self {
+        // The sequences of an ordered channel must pick up where the flushed packets left off.
+        let (next_sequence_recv, next_sequence_ack) = if channel_end
+            .order_matches(&Order::Unordered)
+            && !matches!(upgrade.fields.ordering, Order::Unordered)
+        {
+            (
+                Some(counterparty_upgrade.next_sequence_send),
+                Some(upgrade.next_sequence_send),
+            )
+        } else {
+            (None, None)
+        };
+
+        channel_end.set_state(State::Open);
+        channel_end.ordering = upgrade.fields.ordering;
+        channel_end.connection_hops = upgrade.fields.connection_hops.clone();
+        channel_end.version = upgrade.fields.version.clone();
+
+        Self {
+            port_id,
+            channel_id,
+            channel_end,
+            upgrade: None,
+            counterparty_upgrade: None,
+            error_receipt: None,
+            next_sequence_recv,
+            next_sequence_ack,
+        }
+    }
+}
+
+/// Returns the connection underlying the given connection hops of a channel being upgraded,
+/// which must be OPEN.
+pub(crate) fn upgrade_connection_end(
+    ctx: &dyn ChannelReader,
+    connection_hops: &[ConnectionId],
+) -> Result<ConnectionEnd, Error> {
+    if connection_hops.len() != 1 {
+        return Err(Error::invalid_connection_hops_length(
+            1,
+            connection_hops.len(),
+        ));
+    }
+
+    let conn = ctx.connection_end(&connection_hops[0])?;
+    if !conn.state_matches(&ConnectionState::Open) {
+        return Err(Error::connection_not_open(connection_hops[0].clone()));
+    }
+
+    Ok(conn)
+}
+
+/// Returns the attributes of the events emitted by the upgrade handlers for the given channel end.
+pub(crate) fn upgrade_event_attributes(
+    ctx: &dyn ChannelReader,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    channel_end: &ChannelEnd,
+) -> UpgradeAttributes {
+    UpgradeAttributes {
+        height: ctx.host_height(),
+        port_id: port_id.clone(),
+        channel_id: channel_id.clone(),
+        counterparty_port_id: channel_end.counterparty().port_id().clone(),
+        counterparty_channel_id: channel_end.counterparty().channel_id().cloned(),
+        upgrade_sequence: channel_end.upgrade_sequence(),
+    }
+}
+
+/// Returns the given channel end moved to `FLUSHCOMPLETE` if it is flushing an upgrade agreed
+/// with the counterparty and has no packets in flight other than the one with the given sequence,
+/// whose acknowledgement or timeout is being processed.
+pub(crate) fn flush_complete_channel_end(
+    ctx: &dyn ChannelReader,
+    port_channel_id: &(PortId, ChannelId),
+    channel_end: &ChannelEnd,
+    sequence: Sequence,
+) -> Result<Option<ChannelEnd>, Error> {
+    if !channel_end.state_matches(&State::Flushing)
+        || ctx.counterparty_upgrade(port_channel_id).is_err()
+        || ctx.has_inflight_packets(port_channel_id, Some(sequence))?
+    {
+        return Ok(None);
+    }
+
+    let mut channel_end = channel_end.clone();
+    channel_end.set_state(State::FlushComplete);
+    Ok(Some(channel_end))
+}
+
 pub fn channel_validate<Ctx>(ctx: &Ctx, msg: &ChannelMsg) -> Result<ModuleId, Error>
 where
     Ctx: Ics26Context,
@@ -139,6 +291,113 @@ where
     Ok(result)
 }
 
+pub fn channel_upgrade_validate<Ctx>(ctx: &Ctx, msg: &ChannelUpgradeMsg) -> Result<ModuleId, Error>
+where
+    Ctx: Ics26Context,
+{
+    let module_id = msg.lookup_module(ctx)?;
+    if ctx.router().has_route(&module_id) {
+        Ok(module_id)
+    } else {
+        Err(Error::route_not_found())
+    }
+}
+
+/// General entry point for processing any type of message related to the ICS4 channel upgrade
+/// handshake protocol.
+pub fn channel_upgrade_dispatch<Ctx>(
+    ctx: &Ctx,
+    msg: &ChannelUpgradeMsg,
+) -> Result<(HandlerOutputBuilder<()>, ChannelUpgradeResult), Error>
+where
+    Ctx: ChannelReader,
+{
+    let output = match msg {
+        ChannelUpgradeMsg::ChannelUpgradeInit(msg) => chan_upgrade_init::process(ctx, msg),
+        ChannelUpgradeMsg::ChannelUpgradeTry(msg) => chan_upgrade_try::process(ctx, msg),
+        ChannelUpgradeMsg::ChannelUpgradeAck(msg) => chan_upgrade_ack::process(ctx, msg),
+        ChannelUpgradeMsg::ChannelUpgradeConfirm(msg) => chan_upgrade_confirm::process(ctx, msg),
+        ChannelUpgradeMsg::ChannelUpgradeOpen(msg) => chan_upgrade_open::process(ctx, msg),
+        ChannelUpgradeMsg::ChannelUpgradeTimeout(msg) => chan_upgrade_timeout::process(ctx, msg),
+        ChannelUpgradeMsg::ChannelUpgradeCancel(msg) => chan_upgrade_cancel::process(ctx, msg),
+    }?;
+    let HandlerOutput {
+        result,
+        log,
+        events,
+    } = output;
+    let builder = HandlerOutput::builder().with_log(log).with_events(events);
+    Ok((builder, result))
+}
+
+pub fn channel_upgrade_callback<Ctx>(
+    ctx: &mut Ctx,
+    module_id: &ModuleId,
+    msg: &ChannelUpgradeMsg,
+    mut result: ChannelUpgradeResult,
+    module_output: &mut ModuleOutputBuilder,
+) -> Result<ChannelUpgradeResult, Error>
+where
+    Ctx: Ics26Context,
+{
+    let cb = ctx
+        .router_mut()
+        .get_route_mut(module_id)
+        .ok_or_else(Error::route_not_found)?;
+
+    // An aborted upgrade leaves the application untouched.
+    if result.error_receipt.is_some() {
+        return Ok(result);
+    }
+
+    match msg {
+        ChannelUpgradeMsg::ChannelUpgradeInit(_) => {
+            let upgrade = result.upgrade.as_mut().ok_or_else(Error::missing_upgrade)?;
+            upgrade.fields.version = cb.on_chan_upgrade_init(
+                module_output,
+                &result.port_id,
+                &result.channel_id,
+                upgrade.fields.ordering,
+                &upgrade.fields.connection_hops,
+                &upgrade.fields.version,
+            )?;
+        }
+        ChannelUpgradeMsg::ChannelUpgradeTry(_) => {
+            let upgrade = result.upgrade.as_mut().ok_or_else(Error::missing_upgrade)?;
+            upgrade.fields.version = cb.on_chan_upgrade_try(
+                module_output,
+                &result.port_id,
+                &result.channel_id,
+                upgrade.fields.ordering,
+                &upgrade.fields.connection_hops,
+                &upgrade.fields.version,
+            )?;
+        }
+        ChannelUpgradeMsg::ChannelUpgradeAck(msg) => cb.on_chan_upgrade_ack(
+            module_output,
+            &result.port_id,
+            &result.channel_id,
+            &msg.counterparty_upgrade.fields.version,
+        )?,
+        ChannelUpgradeMsg::ChannelUpgradeConfirm(_) | ChannelUpgradeMsg::ChannelUpgradeOpen(_) => {
+            // A confirmation completes the upgrade right away if both ends are done flushing.
+            if result.channel_end.state_matches(&State::Open) {
+                cb.on_chan_upgrade_open(
+                    module_output,
+                    &result.port_id,
+                    &result.channel_id,
+                    result.channel_end.ordering,
+                    &result.channel_end.connection_hops,
+                    &result.channel_end.version,
+                )?
+            }
+        }
+        ChannelUpgradeMsg::ChannelUpgradeTimeout(_)
+        | ChannelUpgradeMsg::ChannelUpgradeCancel(_) => {}
+    }
+    Ok(result)
+}
+
 pub fn get_module_for_packet_msg<Ctx>(ctx: &Ctx, msg: &PacketMsg) -> Result<ModuleId, Error>
 where
     Ctx: Ics26Context,
@@ -188,6 +447,35 @@ where
     Ok((builder, result))
 }
 
+/// Prunes the receipts and acknowledgements of the packets received on the given channel end,
+/// resuming from where the last call left off, given `proofs` that the counterparty no longer
+/// stores their commitments (e.g., as submitted by a relayer). At most `limit` sequences are
+/// checked, so that hosts can prune incrementally (e.g., at the end of every block).
+/// See [`prune_packets::process`] for the conditions under which a packet is pruned.
+pub fn prune_packets<Ctx>(
+    ctx: &mut Ctx,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    proofs: &CommitmentAbsenceProofs,
+    limit: u64,
+) -> Result<HandlerOutput<()>, Error>
+where
+    Ctx: ChannelReader + ChannelKeeper,
+{
+    let HandlerOutput {
+        result,
+        log,
+        events,
+    } = prune_packets::process(ctx, port_id, channel_id, proofs, limit)?;
+
+    ctx.store_prune_packets_result(result)?;
+
+    Ok(HandlerOutput::builder()
+        .with_log(log)
+        .with_events(events)
+        .with_result(()))
+}
+
 pub fn packet_callback<Ctx>(
     ctx: &mut Ctx,
     module_id: &ModuleId,
@@ -1,7 +1,8 @@
 use crate::core::ics03_connection::connection::State as ConnectionState;
 use crate::core::ics04_channel::channel::State;
-use crate::core::ics04_channel::channel::{Counterparty, Order};
+use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order};
 use crate::core::ics04_channel::events::AcknowledgePacket;
+use crate::core::ics04_channel::handler::flush_complete_channel_end;
 use crate::core::ics04_channel::handler::verify::verify_packet_acknowledgement_proofs;
 use crate::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
 use crate::core::ics04_channel::packet::{PacketResult, Sequence};
@@ -17,6 +18,9 @@ pub struct AckPacketResult {
     pub channel_id: ChannelId,
     pub seq: Sequence,
     pub seq_number: Option<Sequence>,
+    /// The channel end to store, set when the acknowledgement completes the flush of an
+    /// upgrading channel.
+    pub channel: Option<ChannelEnd>,
 }
 
 pub fn process(
@@ -30,7 +34,9 @@ pub fn process(
     let source_channel_end =
         ctx.channel_end(&(packet.source_port.clone(), packet.source_channel.clone()))?;
 
-    if !source_channel_end.state_matches(&State::Open) {
+    if !source_channel_end.state_matches(&State::Open)
+        && !source_channel_end.state_matches(&State::Flushing)
+    {
         return Err(Error::channel_closed(packet.source_channel.clone()));
     }
 
@@ -81,7 +87,16 @@ pub fn process(
         &msg.proofs,
     )?;
 
-    let result = if source_channel_end.order_matches(&Order::Ordered) {
+    let channel = flush_complete_channel_end(
+        ctx,
+        &(packet.source_port.clone(), packet.source_channel.clone()),
+        &source_channel_end,
+        packet.sequence,
+    )?;
+
+    let result = if source_channel_end.order_matches(&Order::Ordered)
+        || source_channel_end.order_matches(&Order::OrderedAllowTimeout)
+    {
         let next_seq_ack = ctx
             .get_next_sequence_ack(&(packet.source_port.clone(), packet.source_channel.clone()))?;
 
@@ -97,6 +112,7 @@ pub fn process(
             channel_id: packet.source_channel.clone(),
             seq: packet.sequence,
             seq_number: Some(next_seq_ack.increment()),
+            channel,
         })
     } else {
         PacketResult::Ack(AckPacketResult {
@@ -104,6 +120,7 @@ pub fn process(
             channel_id: packet.source_channel.clone(),
             seq: packet.sequence,
             seq_number: None,
+            channel,
         })
     };
 
@@ -127,7 +144,7 @@ mod tests {
     use crate::core::ics03_connection::connection::State as ConnectionState;
     use crate::core::ics03_connection::version::get_compatible_versions;
     use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
-    use crate::core::ics04_channel::context::ChannelReader;
+    use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
     use crate::core::ics04_channel::handler::acknowledgement::process;
     use crate::core::ics04_channel::msgs::acknowledgement::test_util::get_dummy_raw_msg_acknowledgement;
     use crate::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
@@ -254,4 +271,81 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn ack_packet_on_ordered_channel_deletes_commitment() {
+        let client_height = Height::new(0, 2).unwrap();
+
+        let msg = MsgAcknowledgement::try_from(get_dummy_raw_msg_acknowledgement(
+            client_height.revision_height(),
+        ))
+        .unwrap();
+        let packet = msg.packet.clone();
+
+        let context = MockContext::default();
+        let data = context.packet_commitment(
+            packet.data.clone(),
+            packet.timeout_height,
+            packet.timeout_timestamp,
+        );
+
+        let source_channel_end = ChannelEnd::new(
+            State::Open,
+            Order::Ordered,
+            Counterparty::new(
+                packet.destination_port.clone(),
+                Some(packet.destination_channel.clone()),
+            ),
+            vec![ConnectionId::default()],
+            Version::ics20(),
+        );
+
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                Default::default(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+
+        let mut context = context
+            .with_client(&ClientId::default(), client_height)
+            .with_connection(ConnectionId::default(), connection_end)
+            .with_channel(
+                packet.source_port.clone(),
+                packet.source_channel.clone(),
+                source_channel_end,
+            )
+            .with_packet_commitment(
+                packet.source_port.clone(),
+                packet.source_channel.clone(),
+                packet.sequence,
+                data,
+            )
+            .with_ack_sequence(
+                packet.source_port.clone(),
+                packet.source_channel.clone(),
+                packet.sequence,
+            );
+
+        let output = process(&context, &msg).unwrap();
+        context.store_packet_result(output.result).unwrap();
+
+        let commitment_key = (
+            packet.source_port.clone(),
+            packet.source_channel.clone(),
+            packet.sequence,
+        );
+        assert!(context.get_packet_commitment(&commitment_key).is_err());
+        assert_eq!(
+            context
+                .get_next_sequence_ack(&(packet.source_port, packet.source_channel))
+                .unwrap(),
+            packet.sequence.increment()
+        );
+    }
 }
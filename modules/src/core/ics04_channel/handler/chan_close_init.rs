@@ -55,6 +55,7 @@ pub(crate) fn process(
     };
 
     let event_attributes = Attributes {
+        port_id: msg.port_id.clone(),
         channel_id: Some(msg.channel_id.clone()),
         height: ctx.host_height(),
         ..Default::default()
@@ -32,7 +32,7 @@ pub(crate) fn process(
         _ => return Err(Error::invalid_version_length_connection()),
     };
 
-    let channel_feature = msg.channel.ordering().to_string();
+    let channel_feature = msg.channel.ordering().connection_feature().to_string();
     if !version.is_supported_feature(channel_feature) {
         return Err(Error::channel_feature_not_suported_by_connection());
     }
@@ -64,6 +64,7 @@ pub(crate) fn process(
     };
 
     let event_attributes = Attributes {
+        port_id: msg.port_id.clone(),
         channel_id: Some(chan_id),
         height: ctx.host_height(),
         ..Default::default()
@@ -0,0 +1,308 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeAck`.
+
+use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, State};
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::events::{UpgradeAck, UpgradeError};
+use crate::core::ics04_channel::handler::verify::{
+    verify_channel_proofs, verify_channel_upgrade_proofs,
+};
+use crate::core::ics04_channel::handler::{
+    upgrade_connection_end, upgrade_event_attributes, ChannelUpgradeResult,
+};
+use crate::core::ics04_channel::msgs::chan_upgrade_ack::MsgChannelUpgradeAck;
+use crate::core::ics04_channel::upgrade::ErrorReceipt;
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::prelude::*;
+
+pub(crate) fn process(
+    ctx: &dyn ChannelReader,
+    msg: &MsgChannelUpgradeAck,
+) -> HandlerResult<ChannelUpgradeResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let port_channel_id = (msg.port_id.clone(), msg.channel_id.clone());
+    let mut channel_end = ctx.channel_end(&port_channel_id)?;
+
+    // The channel is already flushing if it accepted the upgrade of the counterparty while
+    // proposing its own (crossing hellos).
+    if !channel_end.state_matches(&State::Open) && !channel_end.state_matches(&State::Flushing) {
+        return Err(Error::invalid_channel_state(
+            msg.channel_id.clone(),
+            channel_end.state,
+        ));
+    }
+
+    let mut upgrade = ctx.upgrade(&port_channel_id)?;
+
+    let conn = upgrade_connection_end(ctx, channel_end.connection_hops())?;
+
+    // Proof verification in two steps:
+    // 1. Setup: build the channel as we expect to find it on the other party, which started
+    // flushing when it accepted the upgrade.
+    let ccid = conn.counterparty().connection_id().ok_or_else(|| {
+        Error::undefined_connection_counterparty(channel_end.connection_hops()[0].clone())
+    })?;
+
+    let expected_channel_end = ChannelEnd::new(
+        State::Flushing,
+        *channel_end.ordering(),
+        Counterparty::new(msg.port_id.clone(), Some(msg.channel_id.clone())),
+        vec![ccid.clone()],
+        channel_end.version().clone(),
+    )
+    .with_upgrade_sequence(channel_end.upgrade_sequence());
+
+    // 2. Actual proofs are verified now.
+    verify_channel_proofs(
+        ctx,
+        msg.proofs.height(),
+        &channel_end,
+        &conn,
+        &expected_channel_end,
+        &msg.proofs,
+    )?;
+
+    verify_channel_upgrade_proofs(
+        ctx,
+        &channel_end,
+        &conn,
+        &msg.counterparty_upgrade,
+        &msg.proofs,
+    )?;
+
+    // The upgrade is aborted, rather than rejected, if the counterparty settled on different
+    // fields or ran out of time, so that the counterparty can cancel its side of the upgrade.
+    let counterparty_fields = &msg.counterparty_upgrade.fields;
+    let abort_reason = if counterparty_fields.ordering != upgrade.fields.ordering
+        || counterparty_fields.version != upgrade.fields.version
+    {
+        Some("the counterparty upgrade fields are incompatible")
+    } else if msg
+        .counterparty_upgrade
+        .timed_out(ctx.host_height(), &ctx.host_timestamp())
+    {
+        Some("the counterparty upgrade timed out")
+    } else {
+        None
+    };
+
+    if let Some(reason) = abort_reason {
+        output.log(format!("failure: channel upgrade aborted: {}", reason));
+
+        let error_receipt = ErrorReceipt::new(channel_end.upgrade_sequence(), reason);
+        let result = ChannelUpgradeResult::aborted(
+            msg.port_id.clone(),
+            msg.channel_id.clone(),
+            channel_end,
+            error_receipt,
+        );
+
+        output.emit(IbcEvent::UpgradeErrorChannel(UpgradeError(
+            upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &result.channel_end),
+        )));
+
+        return Ok(output.with_result(result));
+    }
+
+    // Start flushing the packets sent before the upgrade.
+    if ctx.has_inflight_packets(&port_channel_id, None)? {
+        channel_end.set_state(State::Flushing);
+    } else {
+        channel_end.set_state(State::FlushComplete);
+    }
+
+    upgrade.timeout_timestamp =
+        (ctx.host_timestamp() + ctx.upgrade_timeout()).map_err(Error::timestamp_overflow)?;
+    upgrade.next_sequence_send = ctx.get_next_sequence_send(&port_channel_id)?;
+
+    output.log("success: channel upgrade ack");
+
+    output.emit(IbcEvent::UpgradeAckChannel(UpgradeAck(
+        upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &channel_end),
+    )));
+
+    let result = ChannelUpgradeResult {
+        port_id: msg.port_id.clone(),
+        channel_id: msg.channel_id.clone(),
+        channel_end,
+        upgrade: Some(upgrade),
+        counterparty_upgrade: Some(msg.counterparty_upgrade.clone()),
+        error_receipt: None,
+        next_sequence_recv: None,
+        next_sequence_ack: None,
+    };
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics03_connection::connection::State as ConnectionState;
+    use crate::core::ics03_connection::msgs::conn_open_init::test_util::get_dummy_raw_msg_conn_open_init;
+    use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::commitment::PacketCommitment;
+    use crate::core::ics04_channel::context::ChannelReader;
+    use crate::core::ics04_channel::handler::chan_upgrade_ack;
+    use crate::core::ics04_channel::msgs::chan_upgrade_ack::test_util::get_dummy_raw_msg_chan_upgrade_ack;
+    use crate::core::ics04_channel::msgs::chan_upgrade_ack::MsgChannelUpgradeAck;
+    use crate::core::ics04_channel::packet::Sequence;
+    use crate::core::ics04_channel::upgrade::Upgrade;
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use crate::events::IbcEvent;
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn chan_upgrade_ack_msg_processing() {
+        struct Test {
+            name: String,
+            ctx: MockContext,
+            msg: MsgChannelUpgradeAck,
+            want_state: Option<State>,
+        }
+
+        let context = MockContext::default();
+        let proof_height = context.host_height();
+
+        let msg = MsgChannelUpgradeAck::try_from(get_dummy_raw_msg_chan_upgrade_ack(
+            proof_height.revision_height(),
+        ))
+        .unwrap();
+
+        let msg_conn_init =
+            MsgConnectionOpenInit::try_from(get_dummy_raw_msg_conn_open_init()).unwrap();
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            msg_conn_init.client_id.clone(),
+            msg_conn_init.counterparty.clone(),
+            get_compatible_versions(),
+            msg_conn_init.delay_period,
+        );
+
+        let chan_end = ChannelEnd::new(
+            State::Open,
+            Order::Unordered,
+            Counterparty::new(PortId::default(), Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            Version::ics20(),
+        )
+        .with_upgrade_sequence(Sequence::from(1));
+
+        let context = context
+            .with_client(&msg_conn_init.client_id, proof_height)
+            .with_connection(ConnectionId::default(), conn_end)
+            .with_channel(PortId::default(), ChannelId::default(), chan_end.clone())
+            .with_send_sequence(PortId::default(), ChannelId::default(), Sequence::from(2));
+
+        let upgrade = Upgrade::new(msg.counterparty_upgrade.fields.clone());
+
+        let mut incompatible_upgrade = upgrade.clone();
+        incompatible_upgrade.fields.version = Version::ics20();
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Processing fails because no upgrade is in progress".to_string(),
+                ctx: context.clone(),
+                msg: msg.clone(),
+                want_state: None,
+            },
+            Test {
+                name: "Upgrade is aborted because the fields are incompatible".to_string(),
+                ctx: context.clone().with_channel_upgrade(
+                    PortId::default(),
+                    ChannelId::default(),
+                    incompatible_upgrade,
+                ),
+                msg: msg.clone(),
+                want_state: Some(State::Open),
+            },
+            Test {
+                name: "Channel starts flushing the packet in flight".to_string(),
+                ctx: context
+                    .clone()
+                    .with_channel_upgrade(PortId::default(), ChannelId::default(), upgrade.clone())
+                    .with_packet_commitment(
+                        PortId::default(),
+                        ChannelId::default(),
+                        Sequence::from(1),
+                        PacketCommitment::from(vec![0]),
+                    ),
+                msg: msg.clone(),
+                want_state: Some(State::Flushing),
+            },
+            Test {
+                name: "Channel already flushing after crossing upgrades".to_string(),
+                ctx: context
+                    .clone()
+                    .with_channel(
+                        PortId::default(),
+                        ChannelId::default(),
+                        ChannelEnd {
+                            state: State::Flushing,
+                            ..chan_end
+                        },
+                    )
+                    .with_channel_upgrade(PortId::default(), ChannelId::default(), upgrade.clone()),
+                msg: msg.clone(),
+                want_state: Some(State::FlushComplete),
+            },
+            Test {
+                name: "Channel completes flushing right away".to_string(),
+                ctx: context.with_channel_upgrade(PortId::default(), ChannelId::default(), upgrade),
+                msg,
+                want_state: Some(State::FlushComplete),
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let res = chan_upgrade_ack::process(&test.ctx, &test.msg);
+            match (res, test.want_state) {
+                (Ok(proto_output), Some(want_state)) => {
+                    let result = proto_output.result;
+                    assert_eq!(result.channel_end.state, want_state, "{}", test.name);
+
+                    if want_state == State::Open {
+                        assert!(result.error_receipt.is_some());
+                        assert!(result.upgrade.is_none());
+                    } else {
+                        assert_eq!(
+                            result.counterparty_upgrade,
+                            Some(test.msg.counterparty_upgrade.clone())
+                        );
+                        assert_eq!(
+                            result.upgrade.unwrap().next_sequence_send,
+                            Sequence::from(2)
+                        );
+                    }
+
+                    for e in proto_output.events.iter() {
+                        assert_eq!(
+                            matches!(e, &IbcEvent::UpgradeErrorChannel(_)),
+                            want_state == State::Open
+                        );
+                        assert_eq!(e.height(), test.ctx.host_height());
+                    }
+                }
+                (Err(_), None) => {}
+                (res, _) => panic!(
+                    "chan_upgrade_ack: unexpected outcome for test: {}, \nparams {:?} {:?} result: {:?}",
+                    test.name,
+                    test.msg,
+                    test.ctx.clone(),
+                    res.map(|output| output.result),
+                ),
+            }
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeCancel`.
+
+use crate::core::ics04_channel::channel::State;
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::events::UpgradeCancel;
+use crate::core::ics04_channel::handler::verify::verify_channel_upgrade_error_proofs;
+use crate::core::ics04_channel::handler::{
+    upgrade_connection_end, upgrade_event_attributes, ChannelUpgradeResult,
+};
+use crate::core::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+use crate::core::ics04_channel::upgrade::ErrorReceipt;
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::prelude::*;
+
+pub(crate) fn process(
+    ctx: &dyn ChannelReader,
+    msg: &MsgChannelUpgradeCancel,
+) -> HandlerResult<ChannelUpgradeResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let port_channel_id = (msg.port_id.clone(), msg.channel_id.clone());
+    let channel_end = ctx.channel_end(&port_channel_id)?;
+
+    // An upgrade can no longer be cancelled once this end completed flushing.
+    if !matches!(channel_end.state, State::Open | State::Flushing) {
+        return Err(Error::invalid_channel_state(
+            msg.channel_id.clone(),
+            channel_end.state,
+        ));
+    }
+
+    ctx.upgrade(&port_channel_id)?;
+
+    // The counterparty must have aborted this upgrade attempt, or a later one.
+    if msg.error_receipt.sequence < channel_end.upgrade_sequence() {
+        return Err(Error::invalid_upgrade_sequence(
+            msg.error_receipt.sequence,
+            channel_end.upgrade_sequence(),
+        ));
+    }
+
+    let conn = upgrade_connection_end(ctx, channel_end.connection_hops())?;
+
+    verify_channel_upgrade_error_proofs(ctx, &channel_end, &conn, &msg.error_receipt, &msg.proofs)?;
+
+    output.log("success: channel upgrade cancelled");
+
+    // Both ends resume from the sequence of the aborted attempt.
+    let channel_end = channel_end.with_upgrade_sequence(msg.error_receipt.sequence);
+    let error_receipt = ErrorReceipt::new(
+        msg.error_receipt.sequence,
+        "the upgrade was cancelled by the counterparty",
+    );
+    let result = ChannelUpgradeResult::aborted(
+        msg.port_id.clone(),
+        msg.channel_id.clone(),
+        channel_end,
+        error_receipt,
+    );
+
+    output.emit(IbcEvent::UpgradeCancelChannel(UpgradeCancel(
+        upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &result.channel_end),
+    )));
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics03_connection::connection::State as ConnectionState;
+    use crate::core::ics03_connection::msgs::conn_open_init::test_util::get_dummy_raw_msg_conn_open_init;
+    use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::context::ChannelReader;
+    use crate::core::ics04_channel::handler::chan_upgrade_cancel;
+    use crate::core::ics04_channel::msgs::chan_upgrade_cancel::test_util::get_dummy_raw_msg_chan_upgrade_cancel;
+    use crate::core::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+    use crate::core::ics04_channel::packet::Sequence;
+    use crate::core::ics04_channel::upgrade::test_util::get_dummy_raw_upgrade;
+    use crate::core::ics04_channel::upgrade::Upgrade;
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use crate::events::IbcEvent;
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn chan_upgrade_cancel_msg_processing() {
+        struct Test {
+            name: String,
+            ctx: MockContext,
+            msg: MsgChannelUpgradeCancel,
+            want_pass: bool,
+        }
+
+        let context = MockContext::default();
+        let proof_height = context.host_height();
+
+        let msg = MsgChannelUpgradeCancel::try_from(get_dummy_raw_msg_chan_upgrade_cancel(
+            proof_height.revision_height(),
+        ))
+        .unwrap();
+
+        let msg_conn_init =
+            MsgConnectionOpenInit::try_from(get_dummy_raw_msg_conn_open_init()).unwrap();
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            msg_conn_init.client_id.clone(),
+            msg_conn_init.counterparty.clone(),
+            get_compatible_versions(),
+            msg_conn_init.delay_period,
+        );
+
+        let chan_end = ChannelEnd::new(
+            State::Flushing,
+            Order::Unordered,
+            Counterparty::new(PortId::default(), Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            Version::ics20(),
+        )
+        .with_upgrade_sequence(Sequence::from(1));
+
+        let context = context
+            .with_client(&msg_conn_init.client_id, proof_height)
+            .with_connection(ConnectionId::default(), conn_end)
+            .with_channel(PortId::default(), ChannelId::default(), chan_end.clone());
+
+        let upgraded_context = context.clone().with_channel_upgrade(
+            PortId::default(),
+            ChannelId::default(),
+            Upgrade::try_from(get_dummy_raw_upgrade()).unwrap(),
+        );
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Processing fails because no upgrade is in progress".to_string(),
+                ctx: context,
+                msg: msg.clone(),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the channel completed flushing".to_string(),
+                ctx: upgraded_context.clone().with_channel(
+                    PortId::default(),
+                    ChannelId::default(),
+                    ChannelEnd {
+                        state: State::FlushComplete,
+                        ..chan_end.clone()
+                    },
+                ),
+                msg: msg.clone(),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the error receipt is stale".to_string(),
+                ctx: upgraded_context.clone().with_channel(
+                    PortId::default(),
+                    ChannelId::default(),
+                    chan_end.with_upgrade_sequence(Sequence::from(2)),
+                ),
+                msg: msg.clone(),
+                want_pass: false,
+            },
+            Test {
+                name: "Good parameters".to_string(),
+                ctx: upgraded_context,
+                msg,
+                want_pass: true,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let res = chan_upgrade_cancel::process(&test.ctx, &test.msg);
+            match res {
+                Ok(proto_output) => {
+                    assert!(
+                        test.want_pass,
+                        "chan_upgrade_cancel: test passed but was supposed to fail for test: {}, \nparams {:?} {:?}",
+                        test.name,
+                        test.msg.clone(),
+                        test.ctx.clone()
+                    );
+
+                    let result = proto_output.result;
+                    assert_eq!(result.channel_end.state, State::Open);
+                    assert!(result.upgrade.is_none());
+                    assert!(result.error_receipt.is_some());
+
+                    for e in proto_output.events.iter() {
+                        assert!(matches!(e, &IbcEvent::UpgradeCancelChannel(_)));
+                        assert_eq!(e.height(), test.ctx.host_height());
+                    }
+                }
+                Err(e) => {
+                    assert!(
+                        !test.want_pass,
+                        "chan_upgrade_cancel: did not pass test: {}, \nparams {:?} {:?} error: {:?}",
+                        test.name,
+                        test.msg,
+                        test.ctx.clone(),
+                        e,
+                    );
+                }
+            }
+        }
+    }
+}
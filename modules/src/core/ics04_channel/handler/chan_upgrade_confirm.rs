@@ -0,0 +1,312 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeConfirm`.
+
+use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, State};
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::events::{UpgradeConfirm, UpgradeError, UpgradeOpen};
+use crate::core::ics04_channel::handler::verify::{
+    verify_channel_proofs, verify_channel_upgrade_proofs,
+};
+use crate::core::ics04_channel::handler::{
+    upgrade_connection_end, upgrade_event_attributes, ChannelUpgradeResult,
+};
+use crate::core::ics04_channel::msgs::chan_upgrade_confirm::MsgChannelUpgradeConfirm;
+use crate::core::ics04_channel::upgrade::ErrorReceipt;
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::prelude::*;
+
+pub(crate) fn process(
+    ctx: &dyn ChannelReader,
+    msg: &MsgChannelUpgradeConfirm,
+) -> HandlerResult<ChannelUpgradeResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let port_channel_id = (msg.port_id.clone(), msg.channel_id.clone());
+    let mut channel_end = ctx.channel_end(&port_channel_id)?;
+
+    if !channel_end.state_matches(&State::Flushing) {
+        return Err(Error::invalid_channel_state(
+            msg.channel_id.clone(),
+            channel_end.state,
+        ));
+    }
+
+    if !matches!(
+        msg.counterparty_channel_state,
+        State::Flushing | State::FlushComplete
+    ) {
+        return Err(Error::invalid_counterparty_channel_state(
+            msg.counterparty_channel_state,
+        ));
+    }
+
+    let upgrade = ctx.upgrade(&port_channel_id)?;
+
+    let conn = upgrade_connection_end(ctx, channel_end.connection_hops())?;
+
+    // Proof verification in two steps:
+    // 1. Setup: build the channel as we expect to find it on the other party.
+    let ccid = conn.counterparty().connection_id().ok_or_else(|| {
+        Error::undefined_connection_counterparty(channel_end.connection_hops()[0].clone())
+    })?;
+
+    let expected_channel_end = ChannelEnd::new(
+        msg.counterparty_channel_state,
+        *channel_end.ordering(),
+        Counterparty::new(msg.port_id.clone(), Some(msg.channel_id.clone())),
+        vec![ccid.clone()],
+        channel_end.version().clone(),
+    )
+    .with_upgrade_sequence(channel_end.upgrade_sequence());
+
+    // 2. Actual proofs are verified now.
+    verify_channel_proofs(
+        ctx,
+        msg.proofs.height(),
+        &channel_end,
+        &conn,
+        &expected_channel_end,
+        &msg.proofs,
+    )?;
+
+    verify_channel_upgrade_proofs(
+        ctx,
+        &channel_end,
+        &conn,
+        &msg.counterparty_upgrade,
+        &msg.proofs,
+    )?;
+
+    if msg
+        .counterparty_upgrade
+        .timed_out(ctx.host_height(), &ctx.host_timestamp())
+    {
+        output.log("failure: channel upgrade aborted: the counterparty upgrade timed out");
+
+        let error_receipt = ErrorReceipt::new(
+            channel_end.upgrade_sequence(),
+            "the counterparty upgrade timed out",
+        );
+        let result = ChannelUpgradeResult::aborted(
+            msg.port_id.clone(),
+            msg.channel_id.clone(),
+            channel_end,
+            error_receipt,
+        );
+
+        output.emit(IbcEvent::UpgradeErrorChannel(UpgradeError(
+            upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &result.channel_end),
+        )));
+
+        return Ok(output.with_result(result));
+    }
+
+    if !ctx.has_inflight_packets(&port_channel_id, None)? {
+        channel_end.set_state(State::FlushComplete);
+    }
+
+    output.log("success: channel upgrade confirm");
+
+    output.emit(IbcEvent::UpgradeConfirmChannel(UpgradeConfirm(
+        upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &channel_end),
+    )));
+
+    // The upgrade completes right away if both ends are done flushing.
+    let result = if channel_end.state_matches(&State::FlushComplete)
+        && msg.counterparty_channel_state == State::FlushComplete
+    {
+        let result = ChannelUpgradeResult::opened(
+            msg.port_id.clone(),
+            msg.channel_id.clone(),
+            channel_end,
+            &upgrade,
+            &msg.counterparty_upgrade,
+        );
+
+        output.log("success: channel upgrade open");
+
+        output.emit(IbcEvent::UpgradeOpenChannel(UpgradeOpen(
+            upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &result.channel_end),
+        )));
+
+        result
+    } else {
+        ChannelUpgradeResult {
+            port_id: msg.port_id.clone(),
+            channel_id: msg.channel_id.clone(),
+            channel_end,
+            upgrade: Some(upgrade),
+            counterparty_upgrade: Some(msg.counterparty_upgrade.clone()),
+            error_receipt: None,
+            next_sequence_recv: None,
+            next_sequence_ack: None,
+        }
+    };
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics03_connection::connection::State as ConnectionState;
+    use crate::core::ics03_connection::msgs::conn_open_init::test_util::get_dummy_raw_msg_conn_open_init;
+    use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::commitment::PacketCommitment;
+    use crate::core::ics04_channel::context::ChannelReader;
+    use crate::core::ics04_channel::handler::chan_upgrade_confirm;
+    use crate::core::ics04_channel::msgs::chan_upgrade_confirm::test_util::get_dummy_raw_msg_chan_upgrade_confirm;
+    use crate::core::ics04_channel::msgs::chan_upgrade_confirm::MsgChannelUpgradeConfirm;
+    use crate::core::ics04_channel::packet::Sequence;
+    use crate::core::ics04_channel::upgrade::Upgrade;
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn chan_upgrade_confirm_msg_processing() {
+        struct Test {
+            name: String,
+            ctx: MockContext,
+            msg: MsgChannelUpgradeConfirm,
+            want_state: Option<State>,
+        }
+
+        let context = MockContext::default();
+        let proof_height = context.host_height();
+
+        let msg = MsgChannelUpgradeConfirm::try_from(get_dummy_raw_msg_chan_upgrade_confirm(
+            proof_height.revision_height(),
+        ))
+        .unwrap();
+
+        let msg_conn_init =
+            MsgConnectionOpenInit::try_from(get_dummy_raw_msg_conn_open_init()).unwrap();
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            msg_conn_init.client_id.clone(),
+            msg_conn_init.counterparty.clone(),
+            get_compatible_versions(),
+            msg_conn_init.delay_period,
+        );
+
+        let chan_end = ChannelEnd::new(
+            State::Flushing,
+            Order::Unordered,
+            Counterparty::new(PortId::default(), Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            Version::ics20(),
+        )
+        .with_upgrade_sequence(Sequence::from(1));
+
+        let upgrade = Upgrade {
+            next_sequence_send: Sequence::from(3),
+            ..msg.counterparty_upgrade.clone()
+        };
+
+        let context = context
+            .with_client(&msg_conn_init.client_id, proof_height)
+            .with_connection(ConnectionId::default(), conn_end)
+            .with_channel(PortId::default(), ChannelId::default(), chan_end.clone())
+            .with_send_sequence(PortId::default(), ChannelId::default(), Sequence::from(3))
+            .with_channel_upgrade(PortId::default(), ChannelId::default(), upgrade);
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Processing fails because the channel is not flushing".to_string(),
+                ctx: context.clone().with_channel(
+                    PortId::default(),
+                    ChannelId::default(),
+                    ChannelEnd {
+                        state: State::Open,
+                        ..chan_end
+                    },
+                ),
+                msg: msg.clone(),
+                want_state: None,
+            },
+            Test {
+                name: "Processing fails because the counterparty channel is open".to_string(),
+                ctx: context.clone(),
+                msg: MsgChannelUpgradeConfirm {
+                    counterparty_channel_state: State::Open,
+                    ..msg.clone()
+                },
+                want_state: None,
+            },
+            Test {
+                name: "Channel keeps flushing the packet in flight".to_string(),
+                ctx: context.clone().with_packet_commitment(
+                    PortId::default(),
+                    ChannelId::default(),
+                    Sequence::from(2),
+                    PacketCommitment::from(vec![0]),
+                ),
+                msg: msg.clone(),
+                want_state: Some(State::Flushing),
+            },
+            Test {
+                name: "Channel completes flushing while the counterparty is still flushing"
+                    .to_string(),
+                ctx: context.clone(),
+                msg: MsgChannelUpgradeConfirm {
+                    counterparty_channel_state: State::Flushing,
+                    ..msg.clone()
+                },
+                want_state: Some(State::FlushComplete),
+            },
+            Test {
+                name: "Channel opens once both ends completed flushing".to_string(),
+                ctx: context,
+                msg: MsgChannelUpgradeConfirm {
+                    counterparty_channel_state: State::FlushComplete,
+                    ..msg
+                },
+                want_state: Some(State::Open),
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let res = chan_upgrade_confirm::process(&test.ctx, &test.msg);
+            match (res, test.want_state) {
+                (Ok(proto_output), Some(want_state)) => {
+                    let result = proto_output.result;
+                    assert_eq!(result.channel_end.state, want_state, "{}", test.name);
+
+                    if want_state == State::Open {
+                        // The upgraded fields are applied and the sequences of the now ordered
+                        // channel pick up after the flushed packets.
+                        assert_eq!(result.channel_end.ordering, Order::Ordered);
+                        assert!(result.upgrade.is_none());
+                        assert_eq!(result.next_sequence_recv, Some(Sequence::from(1)));
+                        assert_eq!(result.next_sequence_ack, Some(Sequence::from(3)));
+                    } else {
+                        assert!(result.upgrade.is_some());
+                        assert!(result.counterparty_upgrade.is_some());
+                    }
+
+                    for e in proto_output.events.iter() {
+                        assert_eq!(e.height(), test.ctx.host_height());
+                    }
+                }
+                (Err(_), None) => {}
+                (res, _) => panic!(
+                    "chan_upgrade_confirm: unexpected outcome for test: {}, \nparams {:?} {:?} result: {:?}",
+                    test.name,
+                    test.msg,
+                    test.ctx.clone(),
+                    res.map(|output| output.result),
+                ),
+            }
+        }
+    }
+}
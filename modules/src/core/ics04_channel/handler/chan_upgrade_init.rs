@@ -0,0 +1,219 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeInit`.
+
+use crate::core::ics04_channel::channel::State;
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::events::UpgradeInit;
+use crate::core::ics04_channel::handler::{
+    upgrade_connection_end, upgrade_event_attributes, ChannelUpgradeResult,
+};
+use crate::core::ics04_channel::msgs::chan_upgrade_init::MsgChannelUpgradeInit;
+use crate::core::ics04_channel::upgrade::{Upgrade, UpgradeFields};
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::prelude::*;
+
+pub(crate) fn process(
+    ctx: &dyn ChannelReader,
+    msg: &MsgChannelUpgradeInit,
+) -> HandlerResult<ChannelUpgradeResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let port_channel_id = (msg.port_id.clone(), msg.channel_id.clone());
+    let channel_end = ctx.channel_end(&port_channel_id)?;
+
+    // Only an open channel with no upgrade in progress can be upgraded.
+    if !channel_end.state_matches(&State::Open) {
+        return Err(Error::invalid_channel_state(
+            msg.channel_id.clone(),
+            channel_end.state,
+        ));
+    }
+
+    if ctx.upgrade(&port_channel_id).is_ok() {
+        return Err(Error::upgrade_in_progress(msg.channel_id.clone()));
+    }
+
+    if msg.fields == UpgradeFields::from_channel_end(&channel_end) {
+        return Err(Error::upgrade_fields_unchanged());
+    }
+
+    // The connection proposed for the upgraded channel must support its ordering.
+    let conn = upgrade_connection_end(ctx, &msg.fields.connection_hops)?;
+    let version = match conn.versions() {
+        [version] => version,
+        _ => return Err(Error::invalid_version_length_connection()),
+    };
+
+    let channel_feature = msg.fields.ordering.connection_feature().to_string();
+    if !version.is_supported_feature(channel_feature) {
+        return Err(Error::channel_feature_not_suported_by_connection());
+    }
+
+    let upgrade_sequence = channel_end.upgrade_sequence().increment();
+    let channel_end = channel_end.with_upgrade_sequence(upgrade_sequence);
+
+    output.log(format!(
+        "success: proposed upgrade with sequence {}",
+        upgrade_sequence
+    ));
+
+    output.emit(IbcEvent::UpgradeInitChannel(UpgradeInit(
+        upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &channel_end),
+    )));
+
+    let result = ChannelUpgradeResult {
+        port_id: msg.port_id.clone(),
+        channel_id: msg.channel_id.clone(),
+        channel_end,
+        upgrade: Some(Upgrade::new(msg.fields.clone())),
+        counterparty_upgrade: None,
+        error_receipt: None,
+        next_sequence_recv: None,
+        next_sequence_ack: None,
+    };
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics03_connection::connection::State as ConnectionState;
+    use crate::core::ics03_connection::msgs::conn_open_init::test_util::get_dummy_raw_msg_conn_open_init;
+    use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::context::ChannelReader;
+    use crate::core::ics04_channel::handler::chan_upgrade_init;
+    use crate::core::ics04_channel::msgs::chan_upgrade_init::test_util::get_dummy_raw_msg_chan_upgrade_init;
+    use crate::core::ics04_channel::msgs::chan_upgrade_init::MsgChannelUpgradeInit;
+    use crate::core::ics04_channel::packet::Sequence;
+    use crate::core::ics04_channel::upgrade::{Upgrade, UpgradeFields};
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use crate::events::IbcEvent;
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn chan_upgrade_init_msg_processing() {
+        struct Test {
+            name: String,
+            ctx: MockContext,
+            msg: MsgChannelUpgradeInit,
+            want_pass: bool,
+        }
+
+        let msg = MsgChannelUpgradeInit::try_from(get_dummy_raw_msg_chan_upgrade_init()).unwrap();
+
+        let msg_conn_init =
+            MsgConnectionOpenInit::try_from(get_dummy_raw_msg_conn_open_init()).unwrap();
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            msg_conn_init.client_id.clone(),
+            msg_conn_init.counterparty.clone(),
+            get_compatible_versions(),
+            msg_conn_init.delay_period,
+        );
+
+        let chan_end = ChannelEnd::new(
+            State::Open,
+            Order::Unordered,
+            Counterparty::new(PortId::default(), Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            Version::ics20(),
+        );
+
+        let context = MockContext::default()
+            .with_connection(ConnectionId::default(), conn_end)
+            .with_channel(PortId::default(), ChannelId::default(), chan_end.clone());
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Processing fails because the channel does not exist".to_string(),
+                ctx: MockContext::default(),
+                msg: msg.clone(),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the channel is not open".to_string(),
+                ctx: context.clone().with_channel(
+                    PortId::default(),
+                    ChannelId::default(),
+                    ChannelEnd {
+                        state: State::Init,
+                        ..chan_end.clone()
+                    },
+                ),
+                msg: msg.clone(),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because an upgrade is already in progress".to_string(),
+                ctx: context.clone().with_channel_upgrade(
+                    PortId::default(),
+                    ChannelId::default(),
+                    Upgrade::new(msg.fields.clone()),
+                ),
+                msg: msg.clone(),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the fields are unchanged".to_string(),
+                ctx: context.clone(),
+                msg: MsgChannelUpgradeInit {
+                    fields: UpgradeFields::from_channel_end(&chan_end),
+                    ..msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Good parameters".to_string(),
+                ctx: context,
+                msg,
+                want_pass: true,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let res = chan_upgrade_init::process(&test.ctx, &test.msg);
+            match res {
+                Ok(proto_output) => {
+                    assert!(
+                        test.want_pass,
+                        "chan_upgrade_init: test passed but was supposed to fail for test: {}, \nparams {:?} {:?}",
+                        test.name,
+                        test.msg.clone(),
+                        test.ctx.clone()
+                    );
+
+                    let result = proto_output.result;
+                    assert_eq!(result.channel_end.state, State::Open);
+                    assert_eq!(result.channel_end.upgrade_sequence(), Sequence::from(1));
+                    assert_eq!(result.upgrade, Some(Upgrade::new(test.msg.fields.clone())));
+
+                    for e in proto_output.events.iter() {
+                        assert!(matches!(e, &IbcEvent::UpgradeInitChannel(_)));
+                        assert_eq!(e.height(), test.ctx.host_height());
+                    }
+                }
+                Err(e) => {
+                    assert!(
+                        !test.want_pass,
+                        "chan_upgrade_init: did not pass test: {}, \nparams {:?} {:?} error: {:?}",
+                        test.name,
+                        test.msg,
+                        test.ctx.clone(),
+                        e,
+                    );
+                }
+            }
+        }
+    }
+}
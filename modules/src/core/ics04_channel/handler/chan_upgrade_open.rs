@@ -0,0 +1,277 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeOpen`.
+
+use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, State};
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::events::UpgradeOpen;
+use crate::core::ics04_channel::handler::verify::verify_channel_proofs;
+use crate::core::ics04_channel::handler::{
+    upgrade_connection_end, upgrade_event_attributes, ChannelUpgradeResult,
+};
+use crate::core::ics04_channel::msgs::chan_upgrade_open::MsgChannelUpgradeOpen;
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::prelude::*;
+
+pub(crate) fn process(
+    ctx: &dyn ChannelReader,
+    msg: &MsgChannelUpgradeOpen,
+) -> HandlerResult<ChannelUpgradeResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let port_channel_id = (msg.port_id.clone(), msg.channel_id.clone());
+    let channel_end = ctx.channel_end(&port_channel_id)?;
+
+    if !channel_end.state_matches(&State::FlushComplete) {
+        return Err(Error::invalid_channel_state(
+            msg.channel_id.clone(),
+            channel_end.state,
+        ));
+    }
+
+    let upgrade = ctx.upgrade(&port_channel_id)?;
+    let counterparty_upgrade = ctx.counterparty_upgrade(&port_channel_id)?;
+
+    let conn = upgrade_connection_end(ctx, channel_end.connection_hops())?;
+
+    // Proof verification in two steps:
+    // 1. Setup: build the channel as we expect to find it on the other party, which either
+    // completed flushing as well or already opened with the upgraded fields.
+    let ccid = conn.counterparty().connection_id().ok_or_else(|| {
+        Error::undefined_connection_counterparty(channel_end.connection_hops()[0].clone())
+    })?;
+
+    let counterparty = Counterparty::new(msg.port_id.clone(), Some(msg.channel_id.clone()));
+
+    let expected_channel_end = match msg.counterparty_channel_state {
+        State::FlushComplete => ChannelEnd::new(
+            State::FlushComplete,
+            *channel_end.ordering(),
+            counterparty,
+            vec![ccid.clone()],
+            channel_end.version().clone(),
+        )
+        .with_upgrade_sequence(channel_end.upgrade_sequence()),
+        State::Open => {
+            if msg.counterparty_upgrade_sequence < channel_end.upgrade_sequence() {
+                return Err(Error::invalid_upgrade_sequence(
+                    msg.counterparty_upgrade_sequence,
+                    channel_end.upgrade_sequence(),
+                ));
+            }
+
+            ChannelEnd::new(
+                State::Open,
+                counterparty_upgrade.fields.ordering,
+                counterparty,
+                counterparty_upgrade.fields.connection_hops.clone(),
+                counterparty_upgrade.fields.version.clone(),
+            )
+            .with_upgrade_sequence(msg.counterparty_upgrade_sequence)
+        }
+        state => return Err(Error::invalid_counterparty_channel_state(state)),
+    };
+
+    // 2. Actual proofs are verified now.
+    verify_channel_proofs(
+        ctx,
+        msg.proofs.height(),
+        &channel_end,
+        &conn,
+        &expected_channel_end,
+        &msg.proofs,
+    )?;
+
+    let result = ChannelUpgradeResult::opened(
+        msg.port_id.clone(),
+        msg.channel_id.clone(),
+        channel_end,
+        &upgrade,
+        &counterparty_upgrade,
+    );
+
+    output.log("success: channel upgrade open");
+
+    output.emit(IbcEvent::UpgradeOpenChannel(UpgradeOpen(
+        upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &result.channel_end),
+    )));
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics03_connection::connection::State as ConnectionState;
+    use crate::core::ics03_connection::msgs::conn_open_init::test_util::get_dummy_raw_msg_conn_open_init;
+    use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::context::ChannelReader;
+    use crate::core::ics04_channel::handler::chan_upgrade_open;
+    use crate::core::ics04_channel::msgs::chan_upgrade_open::test_util::get_dummy_raw_msg_chan_upgrade_open;
+    use crate::core::ics04_channel::msgs::chan_upgrade_open::MsgChannelUpgradeOpen;
+    use crate::core::ics04_channel::packet::Sequence;
+    use crate::core::ics04_channel::upgrade::test_util::get_dummy_raw_upgrade;
+    use crate::core::ics04_channel::upgrade::Upgrade;
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use crate::events::IbcEvent;
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn chan_upgrade_open_msg_processing() {
+        struct Test {
+            name: String,
+            ctx: MockContext,
+            msg: MsgChannelUpgradeOpen,
+            want_pass: bool,
+        }
+
+        let context = MockContext::default();
+        let proof_height = context.host_height();
+
+        let msg = MsgChannelUpgradeOpen::try_from(get_dummy_raw_msg_chan_upgrade_open(
+            proof_height.revision_height(),
+        ))
+        .unwrap();
+
+        let msg_conn_init =
+            MsgConnectionOpenInit::try_from(get_dummy_raw_msg_conn_open_init()).unwrap();
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            msg_conn_init.client_id.clone(),
+            msg_conn_init.counterparty.clone(),
+            get_compatible_versions(),
+            msg_conn_init.delay_period,
+        );
+
+        let chan_end = ChannelEnd::new(
+            State::FlushComplete,
+            Order::Unordered,
+            Counterparty::new(PortId::default(), Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            Version::ics20(),
+        )
+        .with_upgrade_sequence(Sequence::from(1));
+
+        let upgrade = Upgrade::try_from(get_dummy_raw_upgrade()).unwrap();
+
+        let context = context
+            .with_client(&msg_conn_init.client_id, proof_height)
+            .with_connection(ConnectionId::default(), conn_end)
+            .with_channel(PortId::default(), ChannelId::default(), chan_end.clone())
+            .with_channel_upgrade(PortId::default(), ChannelId::default(), upgrade.clone());
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Processing fails because the counterparty upgrade is not stored".to_string(),
+                ctx: context.clone(),
+                msg: msg.clone(),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the channel is still flushing".to_string(),
+                ctx: context
+                    .clone()
+                    .with_channel(
+                        PortId::default(),
+                        ChannelId::default(),
+                        ChannelEnd {
+                            state: State::Flushing,
+                            ..chan_end
+                        },
+                    )
+                    .with_counterparty_upgrade(
+                        PortId::default(),
+                        ChannelId::default(),
+                        upgrade.clone(),
+                    ),
+                msg: msg.clone(),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the counterparty is still flushing".to_string(),
+                ctx: context.clone().with_counterparty_upgrade(
+                    PortId::default(),
+                    ChannelId::default(),
+                    upgrade.clone(),
+                ),
+                msg: MsgChannelUpgradeOpen {
+                    counterparty_channel_state: State::Flushing,
+                    ..msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Good parameters with the counterparty done flushing".to_string(),
+                ctx: context.clone().with_counterparty_upgrade(
+                    PortId::default(),
+                    ChannelId::default(),
+                    upgrade.clone(),
+                ),
+                msg: msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Good parameters with the counterparty already open".to_string(),
+                ctx: context.with_counterparty_upgrade(
+                    PortId::default(),
+                    ChannelId::default(),
+                    upgrade,
+                ),
+                msg: MsgChannelUpgradeOpen {
+                    counterparty_channel_state: State::Open,
+                    ..msg
+                },
+                want_pass: true,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let res = chan_upgrade_open::process(&test.ctx, &test.msg);
+            match res {
+                Ok(proto_output) => {
+                    assert!(
+                        test.want_pass,
+                        "chan_upgrade_open: test passed but was supposed to fail for test: {}, \nparams {:?} {:?}",
+                        test.name,
+                        test.msg.clone(),
+                        test.ctx.clone()
+                    );
+
+                    let result = proto_output.result;
+                    assert_eq!(result.channel_end.state, State::Open);
+                    assert_eq!(result.channel_end.ordering, Order::Ordered);
+                    assert_eq!(
+                        result.channel_end.version,
+                        Version::new("ics20-2".to_string())
+                    );
+                    assert!(result.upgrade.is_none());
+                    assert!(result.counterparty_upgrade.is_none());
+
+                    for e in proto_output.events.iter() {
+                        assert!(matches!(e, &IbcEvent::UpgradeOpenChannel(_)));
+                        assert_eq!(e.height(), test.ctx.host_height());
+                    }
+                }
+                Err(e) => {
+                    assert!(
+                        !test.want_pass,
+                        "chan_upgrade_open: did not pass test: {}, \nparams {:?} {:?} error: {:?}",
+                        test.name,
+                        test.msg,
+                        test.ctx.clone(),
+                        e,
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeTimeout`.
+
+use crate::core::ics04_channel::channel::State;
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::events::UpgradeTimeout;
+use crate::core::ics04_channel::handler::verify::verify_channel_proofs;
+use crate::core::ics04_channel::handler::{
+    upgrade_connection_end, upgrade_event_attributes, ChannelUpgradeResult,
+};
+use crate::core::ics04_channel::msgs::chan_upgrade_timeout::MsgChannelUpgradeTimeout;
+use crate::core::ics04_channel::upgrade::ErrorReceipt;
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::prelude::*;
+
+pub(crate) fn process(
+    ctx: &dyn ChannelReader,
+    msg: &MsgChannelUpgradeTimeout,
+) -> HandlerResult<ChannelUpgradeResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let port_channel_id = (msg.port_id.clone(), msg.channel_id.clone());
+    let channel_end = ctx.channel_end(&port_channel_id)?;
+
+    if !channel_end.state.is_upgrading() {
+        return Err(Error::invalid_channel_state(
+            msg.channel_id.clone(),
+            channel_end.state,
+        ));
+    }
+
+    let upgrade = ctx.upgrade(&port_channel_id)?;
+
+    let conn = upgrade_connection_end(ctx, channel_end.connection_hops())?;
+
+    // The timeout of an upgrade is enforced on the counterparty chain, at the proof height.
+    let proof_height = msg.proofs.height();
+    let consensus_state = ctx.client_consensus_state(conn.client_id(), proof_height)?;
+    if !upgrade.timed_out(proof_height, &consensus_state.timestamp()) {
+        return Err(Error::upgrade_timeout_not_reached());
+    }
+
+    // The counterparty must not have completed its side of the upgrade.
+    let counterparty_state = msg.counterparty_channel.state;
+    if counterparty_state == State::FlushComplete
+        || (counterparty_state == State::Open
+            && msg.counterparty_channel.upgrade_sequence() == channel_end.upgrade_sequence())
+    {
+        return Err(Error::invalid_counterparty_channel_state(
+            counterparty_state,
+        ));
+    }
+
+    verify_channel_proofs(
+        ctx,
+        proof_height,
+        &channel_end,
+        &conn,
+        &msg.counterparty_channel,
+        &msg.proofs,
+    )?;
+
+    output.log("success: channel upgrade timed out");
+
+    let error_receipt = ErrorReceipt::new(
+        channel_end.upgrade_sequence(),
+        "the counterparty upgrade timed out",
+    );
+    let result = ChannelUpgradeResult::aborted(
+        msg.port_id.clone(),
+        msg.channel_id.clone(),
+        channel_end,
+        error_receipt,
+    );
+
+    output.emit(IbcEvent::UpgradeTimeoutChannel(UpgradeTimeout(
+        upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &result.channel_end),
+    )));
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics03_connection::connection::State as ConnectionState;
+    use crate::core::ics03_connection::msgs::conn_open_init::test_util::get_dummy_raw_msg_conn_open_init;
+    use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::context::ChannelReader;
+    use crate::core::ics04_channel::handler::chan_upgrade_timeout;
+    use crate::core::ics04_channel::msgs::chan_upgrade_timeout::test_util::get_dummy_raw_msg_chan_upgrade_timeout;
+    use crate::core::ics04_channel::msgs::chan_upgrade_timeout::MsgChannelUpgradeTimeout;
+    use crate::core::ics04_channel::packet::Sequence;
+    use crate::core::ics04_channel::upgrade::test_util::get_dummy_raw_upgrade;
+    use crate::core::ics04_channel::upgrade::Upgrade;
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use crate::events::IbcEvent;
+    use crate::mock::context::MockContext;
+    use crate::Height;
+
+    #[test]
+    fn chan_upgrade_timeout_msg_processing() {
+        struct Test {
+            name: String,
+            ctx: MockContext,
+            msg: MsgChannelUpgradeTimeout,
+            want_pass: bool,
+        }
+
+        let context = MockContext::default();
+        let proof_height = context.host_height();
+
+        let msg = MsgChannelUpgradeTimeout::try_from(get_dummy_raw_msg_chan_upgrade_timeout(
+            proof_height.revision_height(),
+        ))
+        .unwrap();
+
+        let msg_conn_init =
+            MsgConnectionOpenInit::try_from(get_dummy_raw_msg_conn_open_init()).unwrap();
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            msg_conn_init.client_id.clone(),
+            msg_conn_init.counterparty.clone(),
+            get_compatible_versions(),
+            msg_conn_init.delay_period,
+        );
+
+        let chan_end = ChannelEnd::new(
+            State::Flushing,
+            Order::Unordered,
+            Counterparty::new(PortId::default(), Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            Version::ics20(),
+        )
+        .with_upgrade_sequence(Sequence::from(1));
+
+        let pending_upgrade = Upgrade::try_from(get_dummy_raw_upgrade()).unwrap();
+        let expired_upgrade = Upgrade {
+            timeout_height: Height::new(0, 1).unwrap().into(),
+            ..pending_upgrade.clone()
+        };
+
+        let context = context
+            .with_client(&msg_conn_init.client_id, proof_height)
+            .with_connection(ConnectionId::default(), conn_end)
+            .with_channel(PortId::default(), ChannelId::default(), chan_end.clone());
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Processing fails because the channel is not upgrading".to_string(),
+                ctx: context
+                    .clone()
+                    .with_channel(
+                        PortId::default(),
+                        ChannelId::default(),
+                        ChannelEnd {
+                            state: State::Open,
+                            ..chan_end
+                        },
+                    )
+                    .with_channel_upgrade(
+                        PortId::default(),
+                        ChannelId::default(),
+                        expired_upgrade.clone(),
+                    ),
+                msg: msg.clone(),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the upgrade has not timed out".to_string(),
+                ctx: context.clone().with_channel_upgrade(
+                    PortId::default(),
+                    ChannelId::default(),
+                    pending_upgrade,
+                ),
+                msg: msg.clone(),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the counterparty completed flushing".to_string(),
+                ctx: context.clone().with_channel_upgrade(
+                    PortId::default(),
+                    ChannelId::default(),
+                    expired_upgrade.clone(),
+                ),
+                msg: MsgChannelUpgradeTimeout {
+                    counterparty_channel: ChannelEnd {
+                        state: State::FlushComplete,
+                        ..msg.counterparty_channel.clone()
+                    },
+                    ..msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Good parameters".to_string(),
+                ctx: context.with_channel_upgrade(
+                    PortId::default(),
+                    ChannelId::default(),
+                    expired_upgrade,
+                ),
+                msg,
+                want_pass: true,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let res = chan_upgrade_timeout::process(&test.ctx, &test.msg);
+            match res {
+                Ok(proto_output) => {
+                    assert!(
+                        test.want_pass,
+                        "chan_upgrade_timeout: test passed but was supposed to fail for test: {}, \nparams {:?} {:?}",
+                        test.name,
+                        test.msg.clone(),
+                        test.ctx.clone()
+                    );
+
+                    let result = proto_output.result;
+                    assert_eq!(result.channel_end.state, State::Open);
+                    assert_eq!(result.channel_end.ordering, Order::Unordered);
+                    assert_eq!(
+                        result.error_receipt.map(|receipt| receipt.sequence),
+                        Some(Sequence::from(1))
+                    );
+
+                    for e in proto_output.events.iter() {
+                        assert!(matches!(e, &IbcEvent::UpgradeTimeoutChannel(_)));
+                        assert_eq!(e.height(), test.ctx.host_height());
+                    }
+                }
+                Err(e) => {
+                    assert!(
+                        !test.want_pass,
+                        "chan_upgrade_timeout: did not pass test: {}, \nparams {:?} {:?} error: {:?}",
+                        test.name,
+                        test.msg,
+                        test.ctx.clone(),
+                        e,
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,344 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeTry`.
+
+use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, State};
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::events::UpgradeTry;
+use crate::core::ics04_channel::handler::verify::{
+    verify_channel_proofs, verify_channel_upgrade_proofs,
+};
+use crate::core::ics04_channel::handler::{
+    upgrade_connection_end, upgrade_event_attributes, ChannelUpgradeResult,
+};
+use crate::core::ics04_channel::msgs::chan_upgrade_try::MsgChannelUpgradeTry;
+use crate::core::ics04_channel::timeout::TimeoutHeight;
+use crate::core::ics04_channel::upgrade::{Upgrade, UpgradeFields};
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::prelude::*;
+
+pub(crate) fn process(
+    ctx: &dyn ChannelReader,
+    msg: &MsgChannelUpgradeTry,
+) -> HandlerResult<ChannelUpgradeResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let port_channel_id = (msg.port_id.clone(), msg.channel_id.clone());
+    let mut channel_end = ctx.channel_end(&port_channel_id)?;
+
+    if !channel_end.state_matches(&State::Open) {
+        return Err(Error::invalid_channel_state(
+            msg.channel_id.clone(),
+            channel_end.state,
+        ));
+    }
+
+    if msg.counterparty_upgrade_sequence < channel_end.upgrade_sequence() {
+        return Err(Error::invalid_upgrade_sequence(
+            msg.counterparty_upgrade_sequence,
+            channel_end.upgrade_sequence(),
+        ));
+    }
+
+    let conn = upgrade_connection_end(ctx, channel_end.connection_hops())?;
+
+    // The proposed connection must lead to the connection proposed by the counterparty and
+    // support the proposed ordering.
+    let proposed_conn = upgrade_connection_end(ctx, &msg.proposed_upgrade_connection_hops)?;
+    if proposed_conn.counterparty().connection_id()
+        != msg.counterparty_upgrade_fields.connection_hops.first()
+    {
+        return Err(Error::invalid_upgrade_connection_hops(
+            msg.proposed_upgrade_connection_hops[0].clone(),
+        ));
+    }
+
+    let version = match proposed_conn.versions() {
+        [version] => version,
+        _ => return Err(Error::invalid_version_length_connection()),
+    };
+
+    let channel_feature = msg
+        .counterparty_upgrade_fields
+        .ordering
+        .connection_feature()
+        .to_string();
+    if !version.is_supported_feature(channel_feature) {
+        return Err(Error::channel_feature_not_suported_by_connection());
+    }
+
+    let fields = UpgradeFields::new(
+        msg.counterparty_upgrade_fields.ordering,
+        msg.proposed_upgrade_connection_hops.clone(),
+        msg.counterparty_upgrade_fields.version.clone(),
+    );
+
+    // Both ends proposed an upgrade at the same time (crossing hellos): the handshake carries
+    // on only if both proposals settle on the same fields.
+    if let Ok(upgrade) = ctx.upgrade(&port_channel_id) {
+        if upgrade.fields != fields {
+            return Err(Error::incompatible_counterparty_upgrade(
+                msg.channel_id.clone(),
+            ));
+        }
+    }
+
+    // Proof verification in two steps:
+    // 1. Setup: build the channel and the upgrade as we expect to find them on the other party.
+    let ccid = conn.counterparty().connection_id().ok_or_else(|| {
+        Error::undefined_connection_counterparty(channel_end.connection_hops()[0].clone())
+    })?;
+
+    let expected_channel_end = ChannelEnd::new(
+        State::Open,
+        *channel_end.ordering(),
+        Counterparty::new(msg.port_id.clone(), Some(msg.channel_id.clone())),
+        vec![ccid.clone()],
+        channel_end.version().clone(),
+    )
+    .with_upgrade_sequence(msg.counterparty_upgrade_sequence);
+
+    let expected_upgrade = Upgrade::new(msg.counterparty_upgrade_fields.clone());
+
+    // 2. Actual proofs are verified now.
+    verify_channel_proofs(
+        ctx,
+        msg.proofs.height(),
+        &channel_end,
+        &conn,
+        &expected_channel_end,
+        &msg.proofs,
+    )?;
+
+    verify_channel_upgrade_proofs(ctx, &channel_end, &conn, &expected_upgrade, &msg.proofs)?;
+
+    // Start flushing the packets sent before the upgrade, and give the counterparty until the
+    // upgrade timeout to do the same.
+    channel_end.set_state(State::Flushing);
+    let channel_end = channel_end.with_upgrade_sequence(msg.counterparty_upgrade_sequence);
+
+    let upgrade = Upgrade {
+        fields,
+        timeout_height: TimeoutHeight::Never,
+        timeout_timestamp: (ctx.host_timestamp() + ctx.upgrade_timeout())
+            .map_err(Error::timestamp_overflow)?,
+        next_sequence_send: ctx.get_next_sequence_send(&port_channel_id)?,
+    };
+
+    output.log("success: channel upgrade try");
+
+    output.emit(IbcEvent::UpgradeTryChannel(UpgradeTry(
+        upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &channel_end),
+    )));
+
+    let result = ChannelUpgradeResult {
+        port_id: msg.port_id.clone(),
+        channel_id: msg.channel_id.clone(),
+        channel_end,
+        upgrade: Some(upgrade),
+        counterparty_upgrade: None,
+        error_receipt: None,
+        next_sequence_recv: None,
+        next_sequence_ack: None,
+    };
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics03_connection::connection::State as ConnectionState;
+    use crate::core::ics03_connection::msgs::conn_open_init::test_util::get_dummy_raw_msg_conn_open_init;
+    use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::context::ChannelReader;
+    use crate::core::ics04_channel::handler::chan_upgrade_try;
+    use crate::core::ics04_channel::msgs::chan_upgrade_try::test_util::get_dummy_raw_msg_chan_upgrade_try;
+    use crate::core::ics04_channel::msgs::chan_upgrade_try::MsgChannelUpgradeTry;
+    use crate::core::ics04_channel::packet::Sequence;
+    use crate::core::ics04_channel::upgrade::{Upgrade, UpgradeFields};
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use crate::events::IbcEvent;
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn chan_upgrade_try_msg_processing() {
+        struct Test {
+            name: String,
+            ctx: MockContext,
+            msg: MsgChannelUpgradeTry,
+            want_pass: bool,
+        }
+
+        let context = MockContext::default();
+        let proof_height = context.host_height();
+
+        let msg = MsgChannelUpgradeTry::try_from(get_dummy_raw_msg_chan_upgrade_try(
+            proof_height.revision_height(),
+        ))
+        .unwrap();
+
+        let msg_conn_init =
+            MsgConnectionOpenInit::try_from(get_dummy_raw_msg_conn_open_init()).unwrap();
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            msg_conn_init.client_id.clone(),
+            msg_conn_init.counterparty.clone(),
+            get_compatible_versions(),
+            msg_conn_init.delay_period,
+        );
+
+        let chan_end = ChannelEnd::new(
+            State::Open,
+            Order::Unordered,
+            Counterparty::new(PortId::default(), Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            Version::ics20(),
+        );
+
+        let context = context
+            .with_client(&msg_conn_init.client_id, proof_height)
+            .with_connection(ConnectionId::default(), conn_end)
+            .with_channel(PortId::default(), ChannelId::default(), chan_end.clone())
+            .with_send_sequence(PortId::default(), ChannelId::default(), Sequence::from(1));
+
+        // The upgrade proposed by this end, which crosses the one of the counterparty.
+        let crossing_upgrade = Upgrade::new(UpgradeFields::new(
+            msg.counterparty_upgrade_fields.ordering,
+            msg.proposed_upgrade_connection_hops.clone(),
+            msg.counterparty_upgrade_fields.version.clone(),
+        ));
+        let mut incompatible_upgrade = crossing_upgrade.clone();
+        incompatible_upgrade.fields.version = Version::new("ics20-3".to_string());
+
+        let crossing_context = context.clone().with_channel(
+            PortId::default(),
+            ChannelId::default(),
+            chan_end.clone().with_upgrade_sequence(Sequence::from(1)),
+        );
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Processing fails because the channel does not exist".to_string(),
+                ctx: MockContext::default(),
+                msg: msg.clone(),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the channel is already flushing".to_string(),
+                ctx: context.clone().with_channel(
+                    PortId::default(),
+                    ChannelId::default(),
+                    ChannelEnd {
+                        state: State::Flushing,
+                        ..chan_end.clone()
+                    },
+                ),
+                msg: msg.clone(),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the counterparty upgrade sequence is stale"
+                    .to_string(),
+                ctx: context.clone().with_channel(
+                    PortId::default(),
+                    ChannelId::default(),
+                    chan_end.with_upgrade_sequence(Sequence::from(2)),
+                ),
+                msg: msg.clone(),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the proposed connection does not exist".to_string(),
+                ctx: context.clone(),
+                msg: MsgChannelUpgradeTry {
+                    proposed_upgrade_connection_hops: vec![ConnectionId::new(7)],
+                    ..msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the crossing upgrades are incompatible".to_string(),
+                ctx: crossing_context.clone().with_channel_upgrade(
+                    PortId::default(),
+                    ChannelId::default(),
+                    incompatible_upgrade,
+                ),
+                msg: msg.clone(),
+                want_pass: false,
+            },
+            Test {
+                name: "Crossing upgrades with the same fields".to_string(),
+                ctx: crossing_context.with_channel_upgrade(
+                    PortId::default(),
+                    ChannelId::default(),
+                    crossing_upgrade,
+                ),
+                msg: msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Good parameters".to_string(),
+                ctx: context,
+                msg,
+                want_pass: true,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let res = chan_upgrade_try::process(&test.ctx, &test.msg);
+            match res {
+                Ok(proto_output) => {
+                    assert!(
+                        test.want_pass,
+                        "chan_upgrade_try: test passed but was supposed to fail for test: {}, \nparams {:?} {:?}",
+                        test.name,
+                        test.msg.clone(),
+                        test.ctx.clone()
+                    );
+
+                    let result = proto_output.result;
+                    assert_eq!(result.channel_end.state, State::Flushing);
+                    assert_eq!(
+                        result.channel_end.upgrade_sequence(),
+                        test.msg.counterparty_upgrade_sequence
+                    );
+
+                    let upgrade = result.upgrade.unwrap();
+                    assert_eq!(
+                        upgrade.fields.ordering,
+                        test.msg.counterparty_upgrade_fields.ordering
+                    );
+                    assert_eq!(
+                        upgrade.fields.connection_hops,
+                        test.msg.proposed_upgrade_connection_hops
+                    );
+
+                    for e in proto_output.events.iter() {
+                        assert!(matches!(e, &IbcEvent::UpgradeTryChannel(_)));
+                        assert_eq!(e.height(), test.ctx.host_height());
+                    }
+                }
+                Err(e) => {
+                    assert!(
+                        !test.want_pass,
+                        "chan_upgrade_try: did not pass test: {}, \nparams {:?} {:?} error: {:?}",
+                        test.name,
+                        test.msg,
+                        test.ctx.clone(),
+                        e,
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,373 @@
+//! Protocol logic for pruning the receipts and acknowledgements of the packets received on a
+//! channel end.
+//!
+//! The packets are pruned in order of sequence, from the pruning sequence of the channel end,
+//! which is then advanced past the last pruned packet. The packets with a sequence lower than the
+//! pruning sequence are considered received, such that they cannot be received again, and their
+//! acknowledgement cannot be written again.
+//!
+//! Pruning deletes the proof that a packet was received, which the counterparty could otherwise
+//! use to time out the packet after it was received. A packet is thus only pruned given a proof
+//! that the counterparty no longer stores its commitment, i.e., that it processed the
+//! acknowledgement of the packet or timed it out, and can no longer time it out.
+//!
+//! On unordered channels, a packet which was not received is skipped once its commitment is
+//! proven absent, as it can no longer be received either, but only if a packet with a higher
+//! sequence was received: otherwise, the packet may not have been sent yet.
+use crate::core::ics04_channel::channel::Order;
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::handler::verify::verify_packet_commitment_absence;
+use crate::core::ics04_channel::packet::{Receipt, Sequence};
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::prelude::*;
+use crate::Height;
+
+use alloc::collections::BTreeMap;
+
+#[derive(Clone, Debug)]
+pub struct PrunePacketsResult {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    /// The sequences of the packets whose receipt is pruned.
+    pub receipts: Vec<Sequence>,
+    /// The sequences of the packets whose acknowledgement is pruned.
+    pub acknowledgements: Vec<Sequence>,
+    /// The pruning sequence of the channel end once the packets are pruned.
+    pub pruning_sequence: Sequence,
+}
+
+/// Proofs that the counterparty chain, at the given height, no longer stores the commitments of
+/// the packets with the given sequences.
+#[derive(Clone, Debug)]
+pub struct CommitmentAbsenceProofs {
+    pub height: Height,
+    pub proofs: BTreeMap<Sequence, CommitmentProofBytes>,
+}
+
+/// Prunes the packets received on the given channel end, from its pruning sequence and for as
+/// long as `proofs` prove the absence of their commitment on the counterparty, checking at most
+/// `limit` sequences. Stops at the first packet whose acknowledgement was not written yet.
+pub fn process(
+    ctx: &dyn ChannelReader,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    proofs: &CommitmentAbsenceProofs,
+    limit: u64,
+) -> HandlerResult<PrunePacketsResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let port_channel_id = (port_id.clone(), channel_id.clone());
+    let channel_end = ctx.channel_end(&port_channel_id)?;
+    let connection_end = ctx.connection_end(&channel_end.connection_hops()[0])?;
+
+    // On ordered channels, the packets are received in order of sequence and without receipts
+    // (except for the timeout receipts of `ORDERED_ALLOW_TIMEOUT` channels).
+    let next_seq_recv = if channel_end.order_matches(&Order::Unordered) {
+        None
+    } else {
+        Some(ctx.get_next_sequence_recv(&port_channel_id)?)
+    };
+
+    let mut result = PrunePacketsResult {
+        port_id: port_id.clone(),
+        channel_id: channel_id.clone(),
+        receipts: vec![],
+        acknowledgements: vec![],
+        pruning_sequence: ctx.pruning_sequence(&port_channel_id)?,
+    };
+
+    let mut seq = result.pruning_sequence;
+    let mut checked = 0;
+    let mut pruned = 0;
+    while checked < limit {
+        let proof = match proofs.proofs.get(&seq) {
+            Some(proof) => proof,
+            None => break,
+        };
+        verify_packet_commitment_absence(
+            ctx,
+            &connection_end,
+            &channel_end,
+            seq,
+            proofs.height,
+            proof,
+        )?;
+        checked += 1;
+
+        let key = (port_id.clone(), channel_id.clone(), seq);
+        let receipt = ctx.get_packet_receipt(&key).ok();
+        let received = match next_seq_recv {
+            Some(next_seq_recv) => seq < next_seq_recv,
+            None => receipt.is_some(),
+        };
+
+        // The packets which were not received are only pruned past along with a packet with a
+        // higher sequence.
+        if received {
+            let has_ack = ctx.get_packet_acknowledgement(&key).is_ok();
+
+            // A packet received on an `ORDERED_ALLOW_TIMEOUT` channel after it timed out has no
+            // acknowledgement, but is complete as well.
+            if !has_ack && receipt != Some(Receipt::Timeout) {
+                break;
+            }
+
+            if receipt.is_some() {
+                result.receipts.push(seq);
+            }
+            if has_ack {
+                result.acknowledgements.push(seq);
+            }
+
+            result.pruning_sequence = seq.increment();
+            pruned += 1;
+        }
+
+        seq = seq.increment();
+    }
+
+    output.log(format!(
+        "success: pruned {} packets, pruning sequence is {}",
+        pruned, result.pruning_sequence
+    ));
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics03_connection::connection::Counterparty as ConnectionCounterparty;
+    use crate::core::ics03_connection::connection::State as ConnectionState;
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::commitment::AcknowledgementCommitment;
+    use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
+    use crate::core::ics04_channel::handler::prune_packets::{process, CommitmentAbsenceProofs};
+    use crate::core::ics04_channel::handler::recv_packet::{self, RecvPacketResult};
+    use crate::core::ics04_channel::msgs::recv_packet::test_util::get_dummy_raw_msg_recv_packet;
+    use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
+    use crate::core::ics04_channel::packet::{PacketResult, Receipt, Sequence};
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+    use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::mock::context::MockContext;
+    use crate::relayer::ics18_relayer::context::Ics18Context;
+    use crate::test_utils::get_dummy_proof;
+    use crate::timestamp::ZERO_DURATION;
+    use crate::Height;
+
+    fn connection_end() -> ConnectionEnd {
+        ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                Default::default(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        )
+    }
+
+    fn context_with_channel(order: Order) -> (MockContext, PortId, ChannelId) {
+        let port_id = PortId::default();
+        let channel_id = ChannelId::default();
+        let channel_end = ChannelEnd::new(
+            State::Open,
+            order,
+            Counterparty::new(PortId::default(), Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            Version::ics20(),
+        );
+
+        let ctx = MockContext::default()
+            .with_client(&ClientId::default(), proof_height())
+            .with_connection(ConnectionId::default(), connection_end())
+            .with_channel(port_id.clone(), channel_id.clone(), channel_end);
+
+        (ctx, port_id, channel_id)
+    }
+
+    fn proof_height() -> Height {
+        Height::new(0, 10).unwrap()
+    }
+
+    /// Proofs of absence of the commitments of the packets with the given sequences.
+    fn proofs(seqs: impl IntoIterator<Item = u64>) -> CommitmentAbsenceProofs {
+        CommitmentAbsenceProofs {
+            height: proof_height(),
+            proofs: seqs
+                .into_iter()
+                .map(|seq| {
+                    let proof = CommitmentProofBytes::try_from(get_dummy_proof()).unwrap();
+                    (seq.into(), proof)
+                })
+                .collect(),
+        }
+    }
+
+    fn receive(ctx: &MockContext, port_id: &PortId, channel_id: &ChannelId, seq: u64, ack: bool) {
+        let key = (port_id.clone(), channel_id.clone(), Sequence::from(seq));
+        let mut store = ctx.ibc_store.lock().unwrap();
+        store.packet_receipt.insert(key.clone(), Receipt::Ok);
+        if ack {
+            store
+                .packet_acknowledgement
+                .insert(key, AcknowledgementCommitment::from(vec![1]));
+        }
+    }
+
+    #[test]
+    fn prune_packets_stops_at_pending_acknowledgement() {
+        let (mut ctx, port_id, channel_id) = context_with_channel(Order::Unordered);
+        for seq in 1..=3 {
+            receive(&ctx, &port_id, &channel_id, seq, true);
+        }
+        receive(&ctx, &port_id, &channel_id, 4, false);
+        receive(&ctx, &port_id, &channel_id, 5, true);
+
+        let res = process(&ctx, &port_id, &channel_id, &proofs(1..10), 10)
+            .unwrap()
+            .result;
+        assert_eq!(res.pruning_sequence, Sequence::from(4));
+        assert_eq!(res.receipts, vec![1.into(), 2.into(), 3.into()]);
+        assert_eq!(res.acknowledgements, res.receipts);
+
+        ctx.store_prune_packets_result(res).unwrap();
+        let port_channel_id = (port_id.clone(), channel_id.clone());
+        assert_eq!(
+            ctx.pruning_sequence(&port_channel_id).unwrap(),
+            Sequence::from(4)
+        );
+        for seq in 1..=3 {
+            let key = (port_id.clone(), channel_id.clone(), Sequence::from(seq));
+            assert!(ctx.get_packet_receipt(&key).is_err());
+            assert!(ctx.get_packet_acknowledgement(&key).is_err());
+        }
+        let key = (port_id, channel_id, Sequence::from(5));
+        assert!(ctx.get_packet_acknowledgement(&key).is_ok());
+    }
+
+    #[test]
+    fn prune_packets_skips_packets_which_were_not_received() {
+        let (mut ctx, port_id, channel_id) = context_with_channel(Order::Unordered);
+        for seq in [1, 2, 4, 5] {
+            receive(&ctx, &port_id, &channel_id, seq, true);
+        }
+
+        // The packet with sequence 3 was not received, but its commitment is gone, so it can no
+        // longer be received. The packets from sequence 6 may not have been sent yet.
+        let res = process(&ctx, &port_id, &channel_id, &proofs(1..10), 10)
+            .unwrap()
+            .result;
+        assert_eq!(res.pruning_sequence, Sequence::from(6));
+        assert_eq!(res.receipts, vec![1.into(), 2.into(), 4.into(), 5.into()]);
+        ctx.store_prune_packets_result(res).unwrap();
+
+        let key = (port_id, channel_id, Sequence::from(4));
+        assert!(ctx.get_packet_receipt(&key).is_err());
+    }
+
+    #[test]
+    fn prune_packets_requires_commitment_absence_proofs() {
+        let (ctx, port_id, channel_id) = context_with_channel(Order::Unordered);
+        for seq in 1..=5 {
+            receive(&ctx, &port_id, &channel_id, seq, true);
+        }
+
+        // Pruning stops at the first packet whose commitment is not proven absent.
+        let res = process(&ctx, &port_id, &channel_id, &proofs([1, 2, 4, 5]), 10)
+            .unwrap()
+            .result;
+        assert_eq!(res.pruning_sequence, Sequence::from(3));
+        assert_eq!(res.receipts, vec![1.into(), 2.into()]);
+
+        let res = process(&ctx, &port_id, &channel_id, &proofs([]), 10)
+            .unwrap()
+            .result;
+        assert_eq!(res.pruning_sequence, Sequence::from(1));
+        assert!(res.receipts.is_empty());
+
+        // The proofs are verified against the consensus state of the client at their height.
+        let mut proofs = proofs(1..=5);
+        proofs.height = proof_height().increment();
+        assert!(process(&ctx, &port_id, &channel_id, &proofs, 10).is_err());
+    }
+
+    #[test]
+    fn prune_packets_is_incremental() {
+        let (mut ctx, port_id, channel_id) = context_with_channel(Order::Unordered);
+        for seq in 1..=5 {
+            receive(&ctx, &port_id, &channel_id, seq, true);
+        }
+
+        // At most two sequences are checked at a time.
+        for expected in [3, 5, 6, 6] {
+            let res = process(&ctx, &port_id, &channel_id, &proofs(1..10), 2)
+                .unwrap()
+                .result;
+            assert_eq!(res.pruning_sequence, Sequence::from(expected));
+            ctx.store_prune_packets_result(res).unwrap();
+        }
+    }
+
+    #[test]
+    fn pruned_packets_cannot_be_received_again() {
+        let context = MockContext::default();
+        let host_height = context.query_latest_height().increment();
+        let client_height = host_height.increment();
+
+        let msg = MsgRecvPacket::try_from(get_dummy_raw_msg_recv_packet(
+            client_height.revision_height(),
+        ))
+        .unwrap();
+        let packet = msg.packet.clone();
+
+        let channel_end = ChannelEnd::new(
+            State::Open,
+            Order::Unordered,
+            Counterparty::new(
+                packet.source_port.clone(),
+                Some(packet.source_channel.clone()),
+            ),
+            vec![ConnectionId::default()],
+            Version::ics20(),
+        );
+        let mut ctx = context
+            .with_client(&ClientId::default(), client_height)
+            .with_connection(ConnectionId::default(), connection_end())
+            .with_channel(
+                packet.destination_port.clone(),
+                packet.destination_channel.clone(),
+                channel_end,
+            )
+            .with_height(host_height);
+
+        // The packet is received while its receipt was not pruned yet.
+        let res = recv_packet::process(&ctx, &msg).unwrap();
+        assert!(matches!(
+            res.result,
+            PacketResult::Recv(RecvPacketResult::Unordered { .. })
+        ));
+
+        // Once pruned, it is not received again, even though it has no receipt anymore.
+        let port_channel_id = (packet.destination_port, packet.destination_channel);
+        ctx.store_pruning_sequence(port_channel_id, packet.sequence.increment())
+            .unwrap();
+
+        let res = recv_packet::process(&ctx, &msg).unwrap();
+        assert!(matches!(
+            res.result,
+            PacketResult::Recv(RecvPacketResult::NoOp)
+        ));
+    }
+}
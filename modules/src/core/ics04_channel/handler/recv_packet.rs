@@ -1,7 +1,7 @@
 use crate::core::ics03_connection::connection::State as ConnectionState;
 use crate::core::ics04_channel::channel::{Counterparty, Order, State};
 use crate::core::ics04_channel::context::ChannelReader;
-use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::error::{Error, ErrorDetail};
 use crate::core::ics04_channel::events::ReceivePacket;
 use crate::core::ics04_channel::handler::verify::verify_packet_recv_proofs;
 use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
@@ -25,6 +25,14 @@ pub enum RecvPacketResult {
         channel_id: ChannelId,
         next_seq_recv: Sequence,
     },
+    /// A packet that timed out was received on an `ORDERED_ALLOW_TIMEOUT` channel: it is skipped
+    /// and a timeout receipt is stored in its place, without involving the application.
+    Timeout {
+        port_id: PortId,
+        channel_id: ChannelId,
+        sequence: Sequence,
+        next_seq_recv: Sequence,
+    },
 }
 
 pub fn process(ctx: &dyn ChannelReader, msg: &MsgRecvPacket) -> HandlerResult<PacketResult, Error> {
@@ -37,13 +45,36 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgRecvPacket) -> HandlerResult<Pa
         packet.destination_channel.clone(),
     ))?;
 
-    if !dest_channel_end.state_matches(&State::Open) {
+    if !dest_channel_end.state_matches(&State::Open) && !dest_channel_end.state.is_upgrading() {
         return Err(Error::invalid_channel_state(
             packet.source_channel.clone(),
             dest_channel_end.state,
         ));
     }
 
+    // While the channel is upgrading, only the packets sent by the counterparty before it
+    // started flushing may still be received. The counterparty upgrade is only missing on a
+    // flushing channel end whose counterparty did not acknowledge the upgrade yet, in which case
+    // the counterparty channel end is still open.
+    if dest_channel_end.state.is_upgrading() {
+        match ctx.counterparty_upgrade(&(
+            packet.destination_port.clone(),
+            packet.destination_channel.clone(),
+        )) {
+            Ok(counterparty_upgrade) => {
+                if packet.sequence >= counterparty_upgrade.next_sequence_send {
+                    return Err(Error::invalid_packet_sequence(
+                        packet.sequence,
+                        counterparty_upgrade.next_sequence_send,
+                    ));
+                }
+            }
+            Err(Error(ErrorDetail::CounterpartyUpgradeNotFound(_), _))
+                if dest_channel_end.state_matches(&State::Flushing) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
     let counterparty = Counterparty::new(
         packet.source_port.clone(),
         Some(packet.source_channel.clone()),
@@ -64,8 +95,13 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgRecvPacket) -> HandlerResult<Pa
         ));
     }
 
+    // Timed-out packets are rejected, except on `ORDERED_ALLOW_TIMEOUT` channels where they are
+    // received in order to skip them.
+    let allow_timeout = dest_channel_end.order_matches(&Order::OrderedAllowTimeout);
+
     let latest_height = ctx.host_height();
-    if packet.timeout_height.has_expired(latest_height) {
+    let height_expired = packet.timeout_height.has_expired(latest_height);
+    if height_expired && !allow_timeout {
         return Err(Error::low_packet_height(
             latest_height,
             packet.timeout_height,
@@ -73,7 +109,11 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgRecvPacket) -> HandlerResult<Pa
     }
 
     let latest_timestamp = ctx.host_timestamp();
-    if let Expiry::Expired = latest_timestamp.check_expiry(&packet.timeout_timestamp) {
+    let timestamp_expired = matches!(
+        latest_timestamp.check_expiry(&packet.timeout_timestamp),
+        Expiry::Expired
+    );
+    if timestamp_expired && !allow_timeout {
         return Err(Error::low_packet_timestamp());
     }
 
@@ -85,7 +125,7 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgRecvPacket) -> HandlerResult<Pa
         &msg.proofs,
     )?;
 
-    let result = if dest_channel_end.order_matches(&Order::Ordered) {
+    let result = if dest_channel_end.order_matches(&Order::Ordered) || allow_timeout {
         let next_seq_recv = ctx.get_next_sequence_recv(&(
             packet.destination_port.clone(),
             packet.destination_channel.clone(),
@@ -104,12 +144,35 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgRecvPacket) -> HandlerResult<Pa
             ));
         }
 
-        PacketResult::Recv(RecvPacketResult::Ordered {
-            port_id: packet.destination_port.clone(),
-            channel_id: packet.destination_channel.clone(),
-            next_seq_recv: next_seq_recv.increment(),
-        })
+        if height_expired || timestamp_expired {
+            PacketResult::Recv(RecvPacketResult::Timeout {
+                port_id: packet.destination_port.clone(),
+                channel_id: packet.destination_channel.clone(),
+                sequence: packet.sequence,
+                next_seq_recv: next_seq_recv.increment(),
+            })
+        } else {
+            PacketResult::Recv(RecvPacketResult::Ordered {
+                port_id: packet.destination_port.clone(),
+                channel_id: packet.destination_channel.clone(),
+                next_seq_recv: next_seq_recv.increment(),
+            })
+        }
     } else {
+        // The receipts of the packets below the pruning sequence were pruned, but these packets
+        // were received.
+        let pruning_sequence = ctx.pruning_sequence(&(
+            packet.destination_port.clone(),
+            packet.destination_channel.clone(),
+        ))?;
+        if packet.sequence < pruning_sequence {
+            output.emit(IbcEvent::ReceivePacket(ReceivePacket {
+                height: ctx.host_height(),
+                packet: msg.packet.clone(),
+            }));
+            return Ok(output.with_result(PacketResult::Recv(RecvPacketResult::NoOp)));
+        }
+
         let packet_rec = ctx.get_packet_receipt(&(
             packet.destination_port.clone(),
             packet.destination_channel.clone(),
@@ -215,6 +278,15 @@ mod tests {
             Version::ics20(),
         );
 
+        let mut dest_flushing_channel_end = dest_channel_end.clone();
+        dest_flushing_channel_end.set_state(State::Flushing);
+
+        let mut dest_flush_complete_channel_end = dest_channel_end.clone();
+        dest_flush_complete_channel_end.set_state(State::FlushComplete);
+
+        let mut dest_allow_timeout_channel_end = dest_channel_end.clone();
+        dest_allow_timeout_channel_end.ordering = Order::OrderedAllowTimeout;
+
         let connection_end = ConnectionEnd::new(
             ConnectionState::Open,
             ClientId::default(),
@@ -257,20 +329,90 @@ mod tests {
                         packet.destination_channel.clone(),
                         packet.sequence,
                     ),
-                msg,
+                msg: msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Packet received on a flushing channel whose counterparty is still open"
+                    .to_string(),
+                ctx: context
+                    .clone()
+                    .with_client(&ClientId::default(), client_height)
+                    .with_connection(ConnectionId::default(), connection_end.clone())
+                    .with_channel(
+                        packet.destination_port.clone(),
+                        packet.destination_channel.clone(),
+                        dest_flushing_channel_end,
+                    )
+                    .with_send_sequence(
+                        packet.destination_port.clone(),
+                        packet.destination_channel.clone(),
+                        1.into(),
+                    )
+                    .with_height(host_height)
+                    .with_recv_sequence(
+                        packet.destination_port.clone(),
+                        packet.destination_channel.clone(),
+                        packet.sequence,
+                    ),
+                msg: msg.clone(),
                 want_pass: true,
             },
+            Test {
+                name: "Processing fails on a flush complete channel without counterparty upgrade"
+                    .to_string(),
+                ctx: context
+                    .clone()
+                    .with_client(&ClientId::default(), client_height)
+                    .with_connection(ConnectionId::default(), connection_end.clone())
+                    .with_channel(
+                        packet.destination_port.clone(),
+                        packet.destination_channel.clone(),
+                        dest_flush_complete_channel_end,
+                    )
+                    .with_send_sequence(
+                        packet.destination_port.clone(),
+                        packet.destination_channel.clone(),
+                        1.into(),
+                    )
+                    .with_height(host_height)
+                    .with_recv_sequence(
+                        packet.destination_port.clone(),
+                        packet.destination_channel.clone(),
+                        packet.sequence,
+                    ),
+                msg,
+                want_pass: false,
+            },
             Test {
                 name: "Packet timeout expired".to_string(),
                 ctx: context
+                    .clone()
                     .with_client(&ClientId::default(), client_height)
-                    .with_connection(ConnectionId::default(), connection_end)
+                    .with_connection(ConnectionId::default(), connection_end.clone())
                     .with_channel(PortId::default(), ChannelId::default(), dest_channel_end)
                     .with_send_sequence(PortId::default(), ChannelId::default(), 1.into())
                     .with_height(host_height),
-                msg: msg_packet_old,
+                msg: msg_packet_old.clone(),
                 want_pass: false,
             },
+            Test {
+                name: "Packet timeout expired is skipped on an Ordered Allow Timeout channel"
+                    .to_string(),
+                ctx: context
+                    .with_client(&ClientId::default(), client_height)
+                    .with_connection(ConnectionId::default(), connection_end)
+                    .with_channel(
+                        PortId::default(),
+                        ChannelId::default(),
+                        dest_allow_timeout_channel_end,
+                    )
+                    .with_send_sequence(PortId::default(), ChannelId::default(), 1.into())
+                    .with_recv_sequence(PortId::default(), ChannelId::default(), 1.into())
+                    .with_height(host_height),
+                msg: msg_packet_old,
+                want_pass: true,
+            },
         ]
         .into_iter()
         .collect();
@@ -30,6 +30,14 @@ pub fn send_packet(ctx: &dyn ChannelReader, packet: Packet) -> HandlerResult<Pac
         return Err(Error::channel_closed(packet.source_channel));
     }
 
+    // No new packets may be sent while an upgrade flushes the channel.
+    if source_channel_end.state.is_upgrading() {
+        return Err(Error::invalid_channel_state(
+            packet.source_channel,
+            source_channel_end.state,
+        ));
+    }
+
     let counterparty = Counterparty::new(
         packet.destination_port.clone(),
         Some(packet.destination_channel.clone()),
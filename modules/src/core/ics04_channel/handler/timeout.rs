@@ -1,17 +1,18 @@
 use crate::core::ics04_channel::channel::State;
 use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order};
 use crate::core::ics04_channel::events::TimeoutPacket;
+use crate::core::ics04_channel::handler::flush_complete_channel_end;
 use crate::core::ics04_channel::handler::verify::{
-    verify_next_sequence_recv, verify_packet_receipt_absence,
+    verify_next_sequence_recv, verify_packet_receipt, verify_packet_receipt_absence,
 };
 use crate::core::ics04_channel::msgs::timeout::MsgTimeout;
-use crate::core::ics04_channel::packet::{PacketResult, Sequence};
+use crate::core::ics04_channel::packet::{PacketResult, Receipt, Sequence};
+use crate::core::ics04_channel::timeout::TimeoutHeight;
 use crate::core::ics04_channel::{context::ChannelReader, error::Error};
 use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
 use crate::prelude::*;
-use crate::timestamp::Expiry;
 
 #[derive(Clone, Debug)]
 pub struct TimeoutPacketResult {
@@ -19,6 +20,7 @@ pub struct TimeoutPacketResult {
     pub channel_id: ChannelId,
     pub seq: Sequence,
     pub channel: Option<ChannelEnd>,
+    pub seq_number: Option<Sequence>,
 }
 
 /// TimeoutPacket is called by a module which originally attempted to send a
@@ -34,7 +36,9 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgTimeout) -> HandlerResult<Packe
     let mut source_channel_end =
         ctx.channel_end(&(packet.source_port.clone(), packet.source_channel.clone()))?;
 
-    if !source_channel_end.state_matches(&State::Open) {
+    if !source_channel_end.state_matches(&State::Open)
+        && !source_channel_end.state_matches(&State::Flushing)
+    {
         return Err(Error::channel_closed(packet.source_channel.clone()));
     }
 
@@ -54,26 +58,23 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgTimeout) -> HandlerResult<Packe
 
     let client_id = connection_end.client_id().clone();
 
-    // check that timeout height or timeout timestamp has passed on the other end
     let proof_height = msg.proofs.height();
 
-    if packet.timeout_height.has_expired(proof_height) {
-        return Err(Error::packet_timeout_height_not_reached(
-            packet.timeout_height,
-            proof_height,
-        ));
-    }
-
     let consensus_state = ctx.client_consensus_state(&client_id, proof_height)?;
 
     let proof_timestamp = consensus_state.timestamp();
 
-    let packet_timestamp = packet.timeout_timestamp;
-    if let Expiry::Expired = packet_timestamp.check_expiry(&proof_timestamp) {
-        return Err(Error::packet_timeout_timestamp_not_reached(
-            packet_timestamp,
-            proof_timestamp,
-        ));
+    // check that timeout height or timeout timestamp has passed on the other end
+    if !packet.timed_out(&proof_timestamp, proof_height) {
+        return Err(match packet.timeout_height {
+            TimeoutHeight::At(_) => {
+                Error::packet_timeout_height_not_reached(packet.timeout_height, proof_height)
+            }
+            TimeoutHeight::Never => Error::packet_timeout_timestamp_not_reached(
+                packet.timeout_timestamp,
+                proof_timestamp,
+            ),
+        });
     }
 
     //verify packet commitment
@@ -92,6 +93,13 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgTimeout) -> HandlerResult<Packe
         return Err(Error::incorrect_packet_commitment(packet.sequence));
     }
 
+    let channel = flush_complete_channel_end(
+        ctx,
+        &(packet.source_port.clone(), packet.source_channel.clone()),
+        &source_channel_end,
+        packet.sequence,
+    )?;
+
     let result = if source_channel_end.order_matches(&Order::Ordered) {
         if packet.sequence < msg.next_sequence_recv {
             return Err(Error::invalid_packet_sequence(
@@ -114,6 +122,36 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgTimeout) -> HandlerResult<Packe
             channel_id: packet.source_channel.clone(),
             seq: packet.sequence,
             channel: Some(source_channel_end),
+            seq_number: None,
+        })
+    } else if source_channel_end.order_matches(&Order::OrderedAllowTimeout) {
+        // The counterparty skipped the packet and stored a timeout receipt in its place. Timeouts
+        // are processed in sequence order along with acknowledgements, and keep the channel open.
+        let next_seq_ack = ctx
+            .get_next_sequence_ack(&(packet.source_port.clone(), packet.source_channel.clone()))?;
+
+        if packet.sequence != next_seq_ack {
+            return Err(Error::invalid_packet_sequence(
+                packet.sequence,
+                next_seq_ack,
+            ));
+        }
+
+        verify_packet_receipt(
+            ctx,
+            msg.proofs.height(),
+            &connection_end,
+            packet.clone(),
+            Receipt::Timeout,
+            &msg.proofs,
+        )?;
+
+        PacketResult::Timeout(TimeoutPacketResult {
+            port_id: packet.source_port.clone(),
+            channel_id: packet.source_channel.clone(),
+            seq: packet.sequence,
+            channel,
+            seq_number: Some(next_seq_ack.increment()),
         })
     } else {
         verify_packet_receipt_absence(
@@ -128,7 +166,8 @@ pub fn process(ctx: &dyn ChannelReader, msg: &MsgTimeout) -> HandlerResult<Packe
             port_id: packet.source_port.clone(),
             channel_id: packet.source_channel.clone(),
             seq: packet.sequence,
-            channel: None,
+            channel,
+            seq_number: None,
         })
     };
 
@@ -176,7 +215,7 @@ mod tests {
 
         let msg_proof_height = 2;
         let msg_timeout_height = 5;
-        let timeout_timestamp = 5;
+        let timeout_timestamp = 0;
 
         let client_height = Height::new(0, 2).unwrap();
 
@@ -188,8 +227,9 @@ mod tests {
         .unwrap();
         let packet = msg.packet.clone();
 
+        // The packet times out at a height that the proof height is past.
         let mut msg_ok = msg.clone();
-        msg_ok.packet.timeout_timestamp = Default::default();
+        msg_ok.packet.timeout_height = Height::new(0, 1).unwrap().into();
 
         let data = context.packet_commitment(
             msg_ok.packet.data.clone(),
@@ -211,6 +251,9 @@ mod tests {
         let mut source_ordered_channel_end = source_channel_end.clone();
         source_ordered_channel_end.ordering = Order::Ordered;
 
+        let mut source_allow_timeout_channel_end = source_channel_end.clone();
+        source_allow_timeout_channel_end.ordering = Order::OrderedAllowTimeout;
+
         let connection_end = ConnectionEnd::new(
             ConnectionState::Open,
             ClientId::default(),
@@ -277,24 +320,75 @@ mod tests {
             Test {
                 name: "Good parameters Ordered Channel".to_string(),
                 ctx: context
+                    .clone()
                     .with_client(&ClientId::default(), client_height)
-                    .with_connection(ConnectionId::default(), connection_end)
+                    .with_connection(ConnectionId::default(), connection_end.clone())
                     .with_channel(
                         packet.source_port.clone(),
-                        packet.source_channel,
+                        packet.source_channel.clone(),
                         source_ordered_channel_end,
                     )
                     .with_packet_commitment(
                         msg_ok.packet.source_port.clone(),
                         msg_ok.packet.source_channel.clone(),
                         msg_ok.packet.sequence,
-                        data,
+                        data.clone(),
                     )
                     .with_ack_sequence(
-                         packet.destination_port,
-                         packet.destination_channel,
+                         packet.destination_port.clone(),
+                         packet.destination_channel.clone(),
                          1.into(),
                      ),
+                msg: msg_ok.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Processing fails because the packet is not the next one to be acknowledged on an Ordered Allow Timeout channel"
+                    .to_string(),
+                ctx: context
+                    .clone()
+                    .with_client(&ClientId::default(), client_height)
+                    .with_connection(ConnectionId::default(), connection_end.clone())
+                    .with_channel(
+                        packet.source_port.clone(),
+                        packet.source_channel.clone(),
+                        source_allow_timeout_channel_end.clone(),
+                    )
+                    .with_packet_commitment(
+                        msg_ok.packet.source_port.clone(),
+                        msg_ok.packet.source_channel.clone(),
+                        msg_ok.packet.sequence,
+                        data.clone(),
+                    )
+                    .with_ack_sequence(
+                        packet.source_port.clone(),
+                        packet.source_channel.clone(),
+                        msg_ok.packet.sequence.increment(),
+                    ),
+                msg: msg_ok.clone(),
+                want_pass: false,
+            },
+            Test {
+                name: "Good parameters Ordered Allow Timeout channel".to_string(),
+                ctx: context
+                    .with_client(&ClientId::default(), client_height)
+                    .with_connection(ConnectionId::default(), connection_end)
+                    .with_channel(
+                        packet.source_port.clone(),
+                        packet.source_channel.clone(),
+                        source_allow_timeout_channel_end,
+                    )
+                    .with_packet_commitment(
+                        msg_ok.packet.source_port.clone(),
+                        msg_ok.packet.source_channel.clone(),
+                        msg_ok.packet.sequence,
+                        data,
+                    )
+                    .with_ack_sequence(
+                        packet.source_port,
+                        packet.source_channel,
+                        msg_ok.packet.sequence,
+                    ),
                 msg: msg_ok,
                 want_pass: true,
             },
@@ -335,4 +429,61 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn timeout_packet_on_timestamp_only() {
+        let client_height = Height::new(0, 2).unwrap();
+
+        // The packet times out at a height above the proof height, but at a timestamp that the
+        // counterparty chain is past.
+        let msg = MsgTimeout::try_from(get_dummy_raw_msg_timeout(2, 5, 1)).unwrap();
+        let packet = msg.packet.clone();
+
+        let context = MockContext::default();
+        let data = context.packet_commitment(
+            packet.data.clone(),
+            packet.timeout_height,
+            packet.timeout_timestamp,
+        );
+
+        let source_channel_end = ChannelEnd::new(
+            State::Open,
+            Order::default(),
+            Counterparty::new(
+                packet.destination_port.clone(),
+                Some(packet.destination_channel.clone()),
+            ),
+            vec![ConnectionId::default()],
+            Version::ics20(),
+        );
+
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                Default::default(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+
+        let context = context
+            .with_client(&ClientId::default(), client_height)
+            .with_connection(ConnectionId::default(), connection_end)
+            .with_channel(
+                packet.source_port.clone(),
+                packet.source_channel.clone(),
+                source_channel_end,
+            )
+            .with_packet_commitment(
+                packet.source_port,
+                packet.source_channel,
+                packet.sequence,
+                data,
+            );
+
+        assert!(process(&context, &msg).is_ok());
+    }
 }
@@ -84,7 +84,9 @@ pub fn process(
         &msg.proofs,
     )?;
 
-    let result = if source_channel_end.order_matches(&Order::Ordered) {
+    let result = if source_channel_end.order_matches(&Order::Ordered)
+        || source_channel_end.order_matches(&Order::OrderedAllowTimeout)
+    {
         if packet.sequence < msg.next_sequence_recv {
             return Err(Error::invalid_packet_sequence(
                 packet.sequence,
@@ -105,6 +107,7 @@ pub fn process(
             channel_id: packet.source_channel.clone(),
             seq: packet.sequence,
             channel: Some(source_channel_end),
+            seq_number: None,
         })
     } else {
         verify_packet_receipt_absence(
@@ -120,6 +123,7 @@ pub fn process(
             channel_id: packet.source_channel.clone(),
             seq: packet.sequence,
             channel: None,
+            seq_number: None,
         })
     };
 
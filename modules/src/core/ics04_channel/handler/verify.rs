@@ -6,7 +6,9 @@ use crate::core::ics04_channel::channel::ChannelEnd;
 use crate::core::ics04_channel::context::ChannelReader;
 use crate::core::ics04_channel::error::Error;
 use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement;
-use crate::core::ics04_channel::packet::{Packet, Sequence};
+use crate::core::ics04_channel::packet::{Packet, Receipt, Sequence};
+use crate::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
 use crate::prelude::*;
 use crate::proofs::Proofs;
 use crate::Height;
@@ -176,6 +178,47 @@ pub fn verify_next_sequence_recv(
     Ok(())
 }
 
+/// Entry point for verifying the proof of a packet receipt, e.g. the timeout receipt written for
+/// a timed-out packet on an `ORDERED_ALLOW_TIMEOUT` channel.
+pub fn verify_packet_receipt(
+    ctx: &dyn ChannelReader,
+    height: Height,
+    connection_end: &ConnectionEnd,
+    packet: Packet,
+    receipt: Receipt,
+    proofs: &Proofs,
+) -> Result<(), Error> {
+    let client_id = connection_end.client_id();
+    let client_state = ctx.client_state(client_id)?;
+
+    // The client must not be frozen.
+    if client_state.is_frozen() {
+        return Err(Error::frozen_client(client_id.clone()));
+    }
+
+    let consensus_state = ctx.client_consensus_state(client_id, proofs.height())?;
+
+    let client_def = AnyClient::from_client_type(client_state.client_type());
+
+    // Verify the proof for the packet against the chain store.
+    client_def
+        .verify_packet_receipt(
+            ctx,
+            &client_state,
+            height,
+            connection_end,
+            proofs.object_proof(),
+            consensus_state.root(),
+            &packet.destination_port,
+            &packet.destination_channel,
+            packet.sequence,
+            receipt,
+        )
+        .map_err(|e| Error::packet_verification_failed(packet.sequence, e))?;
+
+    Ok(())
+}
+
 pub fn verify_packet_receipt_absence(
     ctx: &dyn ChannelReader,
     height: Height,
@@ -212,3 +255,131 @@ pub fn verify_packet_receipt_absence(
 
     Ok(())
 }
+
+/// Entry point for verifying the proof that the counterparty of the given channel end no longer
+/// stores the commitment of the packet with the given sequence.
+pub fn verify_packet_commitment_absence(
+    ctx: &dyn ChannelReader,
+    connection_end: &ConnectionEnd,
+    channel_end: &ChannelEnd,
+    sequence: Sequence,
+    height: Height,
+    proof: &CommitmentProofBytes,
+) -> Result<(), Error> {
+    let client_id = connection_end.client_id();
+    let client_state = ctx.client_state(client_id)?;
+
+    // The client must not be frozen.
+    if client_state.is_frozen() {
+        return Err(Error::frozen_client(client_id.clone()));
+    }
+
+    let consensus_state = ctx.client_consensus_state(client_id, height)?;
+
+    let client_def = AnyClient::from_client_type(client_state.client_type());
+
+    let counterparty = channel_end.counterparty();
+    let counterparty_channel_id = counterparty
+        .channel_id()
+        .ok_or_else(Error::missing_channel_id)?;
+
+    client_def
+        .verify_packet_commitment_absence(
+            ctx,
+            &client_state,
+            height,
+            connection_end,
+            proof,
+            consensus_state.root(),
+            counterparty.port_id(),
+            counterparty_channel_id,
+            sequence,
+        )
+        .map_err(|e| Error::packet_verification_failed(sequence, e))?;
+
+    Ok(())
+}
+
+/// Entry point for verifying the proof of the upgrade stored on the counterparty of the given
+/// channel end, bundled as the other proof of a channel upgrade handshake message.
+pub fn verify_channel_upgrade_proofs(
+    ctx: &dyn ChannelReader,
+    channel_end: &ChannelEnd,
+    connection_end: &ConnectionEnd,
+    expected_upgrade: &Upgrade,
+    proofs: &Proofs,
+) -> Result<(), Error> {
+    let client_id = connection_end.client_id();
+    let client_state = ctx.client_state(client_id)?;
+
+    // The client must not be frozen.
+    if client_state.is_frozen() {
+        return Err(Error::frozen_client(client_id.clone()));
+    }
+
+    let consensus_state = ctx.client_consensus_state(client_id, proofs.height())?;
+
+    let client_def = AnyClient::from_client_type(client_state.client_type());
+
+    let proof = proofs
+        .other_proof()
+        .as_ref()
+        .ok_or_else(Error::missing_upgrade)?;
+    let counterparty_channel_id = channel_end
+        .counterparty()
+        .channel_id()
+        .ok_or_else(Error::missing_channel_id)?;
+
+    client_def
+        .verify_channel_upgrade(
+            &client_state,
+            proofs.height(),
+            connection_end.counterparty().prefix(),
+            proof,
+            consensus_state.root(),
+            channel_end.counterparty().port_id(),
+            counterparty_channel_id,
+            expected_upgrade,
+        )
+        .map_err(Error::verify_channel_upgrade_failed)
+}
+
+/// Entry point for verifying the proof of the upgrade error receipt stored on the counterparty of
+/// the given channel end.
+pub fn verify_channel_upgrade_error_proofs(
+    ctx: &dyn ChannelReader,
+    channel_end: &ChannelEnd,
+    connection_end: &ConnectionEnd,
+    expected_error_receipt: &ErrorReceipt,
+    proofs: &Proofs,
+) -> Result<(), Error> {
+    let client_id = connection_end.client_id();
+    let client_state = ctx.client_state(client_id)?;
+
+    // The client must not be frozen.
+    if client_state.is_frozen() {
+        return Err(Error::frozen_client(client_id.clone()));
+    }
+
+    let consensus_state = ctx.client_consensus_state(client_id, proofs.height())?;
+
+    let client_def = AnyClient::from_client_type(client_state.client_type());
+
+    let counterparty_channel_id = channel_end
+        .counterparty()
+        .channel_id()
+        .ok_or_else(Error::missing_channel_id)?;
+
+    client_def
+        .verify_channel_upgrade_error(
+            &client_state,
+            proofs.height(),
+            connection_end.counterparty().prefix(),
+            proofs.object_proof(),
+            consensus_state.root(),
+            channel_end.counterparty().port_id(),
+            counterparty_channel_id,
+            expected_error_receipt,
+        )
+        .map_err(Error::verify_upgrade_error_receipt_failed)
+}
@@ -30,13 +30,22 @@ pub fn process(
         packet.destination_channel.clone(),
     ))?;
 
-    if !dest_channel_end.state_matches(&State::Open) {
+    if !dest_channel_end.state_matches(&State::Open) && !dest_channel_end.state.is_upgrading() {
         return Err(Error::invalid_channel_state(
             packet.source_channel,
             dest_channel_end.state,
         ));
     }
 
+    // The acknowledgements of the packets below the pruning sequence were written and pruned.
+    let pruning_sequence = ctx.pruning_sequence(&(
+        packet.destination_port.clone(),
+        packet.destination_channel.clone(),
+    ))?;
+    if packet.sequence < pruning_sequence {
+        return Err(Error::acknowledgement_exists(packet.sequence));
+    }
+
     // NOTE: IBC app modules might have written the acknowledgement synchronously on
     // the OnRecvPacket callback so we need to check if the acknowledgement is already
     // set on the store and return an error if so.
@@ -56,8 +65,8 @@ pub fn process(
     }
 
     let result = PacketResult::WriteAck(WriteAckPacketResult {
-        port_id: packet.source_port.clone(),
-        channel_id: packet.source_channel.clone(),
+        port_id: packet.destination_port.clone(),
+        channel_id: packet.destination_channel.clone(),
         seq: packet.sequence,
         ack_commitment: ctx.ack_commitment(ack.clone().into()),
     });
@@ -88,6 +97,7 @@ mod tests {
     use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
     use crate::core::ics04_channel::handler::write_acknowledgement::process;
     use crate::core::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::core::ics04_channel::packet::PacketResult;
     use crate::core::ics04_channel::Version;
     use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
     use crate::mock::context::MockContext;
@@ -111,6 +121,8 @@ mod tests {
         let mut packet: Packet = get_dummy_raw_packet(1, 6).try_into().unwrap();
         packet.sequence = 1.into();
         packet.data = vec![0];
+        packet.destination_port = PortId::transfer();
+        packet.destination_channel = ChannelId::new(1);
 
         let ack = vec![0];
         let ack_null = Vec::new();
@@ -194,6 +206,15 @@ mod tests {
                         assert!(matches!(e, &IbcEvent::WriteAcknowledgement(_)));
                         assert_eq!(e.height(), test.ctx.host_height());
                     }
+
+                    // The acknowledgement is written on the receiving end of the packet.
+                    match proto_output.result {
+                        PacketResult::WriteAck(res) => {
+                            assert_eq!(res.port_id, test.packet.destination_port);
+                            assert_eq!(res.channel_id, test.packet.destination_channel);
+                        }
+                        _ => panic!("write_ack: unexpected packet result"),
+                    }
                 }
                 Err(e) => {
                     assert!(
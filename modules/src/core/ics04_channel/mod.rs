@@ -10,6 +10,7 @@ pub mod handler;
 pub mod msgs;
 pub mod packet;
 pub mod timeout;
+pub mod upgrade;
 
 pub mod commitment;
 mod version;
@@ -1,5 +1,5 @@
-//! Message definitions for all ICS4 domain types: channel open & close handshake datagrams, as well
-//! as packets.
+//! Message definitions for all ICS4 domain types: channel open, close & upgrade handshake
+//! datagrams, as well as packets.
 
 use crate::core::ics04_channel::error::Error;
 use crate::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
@@ -9,6 +9,13 @@ use crate::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
 use crate::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
 use crate::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
 use crate::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use crate::core::ics04_channel::msgs::chan_upgrade_ack::MsgChannelUpgradeAck;
+use crate::core::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+use crate::core::ics04_channel::msgs::chan_upgrade_confirm::MsgChannelUpgradeConfirm;
+use crate::core::ics04_channel::msgs::chan_upgrade_init::MsgChannelUpgradeInit;
+use crate::core::ics04_channel::msgs::chan_upgrade_open::MsgChannelUpgradeOpen;
+use crate::core::ics04_channel::msgs::chan_upgrade_timeout::MsgChannelUpgradeTimeout;
+use crate::core::ics04_channel::msgs::chan_upgrade_try::MsgChannelUpgradeTry;
 use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
 use crate::core::ics04_channel::msgs::timeout::MsgTimeout;
 use crate::core::ics04_channel::msgs::timeout_on_close::MsgTimeoutOnClose;
@@ -24,6 +31,15 @@ pub mod chan_open_try;
 pub mod chan_close_confirm;
 pub mod chan_close_init;
 
+// Upgrade handshake messages.
+pub mod chan_upgrade_ack;
+pub mod chan_upgrade_cancel;
+pub mod chan_upgrade_confirm;
+pub mod chan_upgrade_init;
+pub mod chan_upgrade_open;
+pub mod chan_upgrade_timeout;
+pub mod chan_upgrade_try;
+
 // Packet specific messages.
 pub mod acknowledgement;
 pub mod recv_packet;
@@ -67,6 +83,34 @@ impl ChannelMsg {
     }
 }
 
+/// Enumeration of the messages of the ICS4 channel upgrade handshake.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChannelUpgradeMsg {
+    ChannelUpgradeInit(MsgChannelUpgradeInit),
+    ChannelUpgradeTry(MsgChannelUpgradeTry),
+    ChannelUpgradeAck(MsgChannelUpgradeAck),
+    ChannelUpgradeConfirm(MsgChannelUpgradeConfirm),
+    ChannelUpgradeOpen(MsgChannelUpgradeOpen),
+    ChannelUpgradeTimeout(MsgChannelUpgradeTimeout),
+    ChannelUpgradeCancel(MsgChannelUpgradeCancel),
+}
+
+impl ChannelUpgradeMsg {
+    pub(super) fn lookup_module(&self, ctx: &impl Ics26Context) -> Result<ModuleId, Error> {
+        let port_id = match self {
+            ChannelUpgradeMsg::ChannelUpgradeInit(msg) => &msg.port_id,
+            ChannelUpgradeMsg::ChannelUpgradeTry(msg) => &msg.port_id,
+            ChannelUpgradeMsg::ChannelUpgradeAck(msg) => &msg.port_id,
+            ChannelUpgradeMsg::ChannelUpgradeConfirm(msg) => &msg.port_id,
+            ChannelUpgradeMsg::ChannelUpgradeOpen(msg) => &msg.port_id,
+            ChannelUpgradeMsg::ChannelUpgradeTimeout(msg) => &msg.port_id,
+            ChannelUpgradeMsg::ChannelUpgradeCancel(msg) => &msg.port_id,
+        };
+        ctx.lookup_module_by_port(port_id)
+            .map_err(Error::ics05_port)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum PacketMsg {
     RecvPacket(MsgRecvPacket),
@@ -163,10 +163,14 @@ mod test {
 
     use ibc_proto::ibc::core::channel::v1::MsgAcknowledgement as RawMsgAcknowledgement;
 
+    use crate::arbitrary::ics04_channel::{
+        arb_invalid_raw_msg_acknowledgement, arb_msg_acknowledgement,
+    };
     use crate::core::ics04_channel::error::Error;
     use crate::core::ics04_channel::msgs::acknowledgement::test_util::get_dummy_raw_msg_acknowledgement;
     use crate::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
     use crate::test_utils::get_dummy_bech32_account;
+    use crate::test_utils::raw_round_trip_proptests;
 
     #[test]
     fn msg_acknowledgment_try_from_raw() {
@@ -232,4 +236,11 @@ mod test {
             );
         }
     }
+
+    raw_round_trip_proptests!(
+        MsgAcknowledgement,
+        RawMsgAcknowledgement,
+        arb_msg_acknowledgement(),
+        arb_invalid_raw_msg_acknowledgement()
+    );
 }
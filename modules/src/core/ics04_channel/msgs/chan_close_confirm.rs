@@ -121,8 +121,12 @@ mod tests {
     use ibc_proto::ibc::core::channel::v1::MsgChannelCloseConfirm as RawMsgChannelCloseConfirm;
     use ibc_proto::ibc::core::client::v1::Height;
 
+    use crate::arbitrary::ics04_channel::{
+        arb_invalid_raw_msg_channel_close_confirm, arb_msg_channel_close_confirm,
+    };
     use crate::core::ics04_channel::msgs::chan_close_confirm::test_util::get_dummy_raw_msg_chan_close_confirm;
     use crate::core::ics04_channel::msgs::chan_close_confirm::MsgChannelCloseConfirm;
+    use crate::test_utils::raw_round_trip_proptests;
 
     #[test]
     fn parse_channel_close_confirm_msg() {
@@ -231,4 +235,11 @@ mod tests {
         assert_eq!(raw, raw_back);
         assert_eq!(msg, msg_back);
     }
+
+    raw_round_trip_proptests!(
+        MsgChannelCloseConfirm,
+        RawMsgChannelCloseConfirm,
+        arb_msg_channel_close_confirm(),
+        arb_invalid_raw_msg_channel_close_confirm()
+    );
 }
@@ -94,8 +94,12 @@ mod tests {
 
     use ibc_proto::ibc::core::channel::v1::MsgChannelCloseInit as RawMsgChannelCloseInit;
 
+    use crate::arbitrary::ics04_channel::{
+        arb_invalid_raw_msg_channel_close_init, arb_msg_channel_close_init,
+    };
     use crate::core::ics04_channel::msgs::chan_close_init::test_util::get_dummy_raw_msg_chan_close_init;
     use crate::core::ics04_channel::msgs::chan_close_init::MsgChannelCloseInit;
+    use crate::test_utils::raw_round_trip_proptests;
 
     #[test]
     fn parse_channel_close_init_msg() {
@@ -188,4 +192,11 @@ mod tests {
         assert_eq!(raw, raw_back);
         assert_eq!(msg, msg_back);
     }
+
+    raw_round_trip_proptests!(
+        MsgChannelCloseInit,
+        RawMsgChannelCloseInit,
+        arb_msg_channel_close_init(),
+        arb_invalid_raw_msg_channel_close_init()
+    );
 }
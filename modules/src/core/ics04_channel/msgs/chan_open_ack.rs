@@ -138,6 +138,10 @@ mod tests {
     use crate::core::ics04_channel::msgs::chan_open_ack::test_util::get_dummy_raw_msg_chan_open_ack;
     use crate::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
 
+    use crate::arbitrary::ics04_channel::{
+        arb_invalid_raw_msg_channel_open_ack, arb_msg_channel_open_ack,
+    };
+    use crate::test_utils::raw_round_trip_proptests;
     use ibc_proto::ibc::core::client::v1::Height;
 
     #[test]
@@ -299,4 +303,11 @@ mod tests {
         assert_eq!(raw, raw_back);
         assert_eq!(msg, msg_back);
     }
+
+    raw_round_trip_proptests!(
+        MsgChannelOpenAck,
+        RawMsgChannelOpenAck,
+        arb_msg_channel_open_ack(),
+        arb_invalid_raw_msg_channel_open_ack()
+    );
 }
@@ -118,6 +118,10 @@ mod tests {
     use crate::core::ics04_channel::msgs::chan_open_confirm::test_util::get_dummy_raw_msg_chan_open_confirm;
     use crate::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
 
+    use crate::arbitrary::ics04_channel::{
+        arb_invalid_raw_msg_channel_open_confirm, arb_msg_channel_open_confirm,
+    };
+    use crate::test_utils::raw_round_trip_proptests;
     use ibc_proto::ibc::core::client::v1::Height;
 
     #[test]
@@ -231,4 +235,11 @@ mod tests {
         assert_eq!(raw, raw_back);
         assert_eq!(msg, msg_back);
     }
+
+    raw_round_trip_proptests!(
+        MsgChannelOpenConfirm,
+        RawMsgChannelOpenConfirm,
+        arb_msg_channel_open_confirm(),
+        arb_invalid_raw_msg_channel_open_confirm()
+    );
 }
@@ -95,6 +95,10 @@ mod tests {
     use crate::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
     use crate::prelude::*;
 
+    use crate::arbitrary::ics04_channel::{
+        arb_invalid_raw_msg_channel_open_init, arb_msg_channel_open_init,
+    };
+    use crate::test_utils::raw_round_trip_proptests;
     use ibc_proto::ibc::core::channel::v1::MsgChannelOpenInit as RawMsgChannelOpenInit;
     use test_log::test;
 
@@ -157,4 +161,11 @@ mod tests {
         assert_eq!(raw, raw_back);
         assert_eq!(msg, msg_back);
     }
+
+    raw_round_trip_proptests!(
+        MsgChannelOpenInit,
+        RawMsgChannelOpenInit,
+        arb_msg_channel_open_init(),
+        arb_invalid_raw_msg_channel_open_init()
+    );
 }
@@ -163,6 +163,10 @@ mod tests {
     use crate::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
     use crate::prelude::*;
 
+    use crate::arbitrary::ics04_channel::{
+        arb_invalid_raw_msg_channel_open_try, arb_msg_channel_open_try,
+    };
+    use crate::test_utils::raw_round_trip_proptests;
     use ibc_proto::ibc::core::channel::v1::MsgChannelOpenTry as RawMsgChannelOpenTry;
     use ibc_proto::ibc::core::client::v1::Height;
     use test_log::test;
@@ -302,4 +306,11 @@ mod tests {
         assert_eq!(raw, raw_back);
         assert_eq!(msg, msg_back);
     }
+
+    raw_round_trip_proptests!(
+        MsgChannelOpenTry,
+        RawMsgChannelOpenTry,
+        arb_msg_channel_open_try(),
+        arb_invalid_raw_msg_channel_open_try()
+    );
 }
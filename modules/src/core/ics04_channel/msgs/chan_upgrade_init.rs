@@ -0,0 +1,203 @@
+use crate::prelude::*;
+
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::core::channel::v1::MsgChannelUpgradeInit as RawMsgChannelUpgradeInit;
+
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::upgrade::UpgradeFields;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.core.channel.v1.MsgChannelUpgradeInit";
+
+///
+/// Message definition for the first step in the channel upgrade handshake (`ChanUpgradeInit`
+/// datagram).
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgChannelUpgradeInit {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub fields: UpgradeFields,
+    pub signer: Signer,
+}
+
+impl MsgChannelUpgradeInit {
+    pub fn new(
+        port_id: PortId,
+        channel_id: ChannelId,
+        fields: UpgradeFields,
+        signer: Signer,
+    ) -> Self {
+        Self {
+            port_id,
+            channel_id,
+            fields,
+            signer,
+        }
+    }
+}
+
+impl Msg for MsgChannelUpgradeInit {
+    type ValidationError = Error;
+    type Raw = RawMsgChannelUpgradeInit;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgChannelUpgradeInit> for MsgChannelUpgradeInit {}
+
+impl TryFrom<RawMsgChannelUpgradeInit> for MsgChannelUpgradeInit {
+    type Error = Error;
+
+    fn try_from(raw_msg: RawMsgChannelUpgradeInit) -> Result<Self, Self::Error> {
+        let fields: UpgradeFields = raw_msg
+            .fields
+            .ok_or_else(Error::missing_upgrade_fields)?
+            .try_into()?;
+        fields.validate_basic()?;
+
+        Ok(MsgChannelUpgradeInit {
+            port_id: raw_msg.port_id.parse().map_err(Error::identifier)?,
+            channel_id: raw_msg.channel_id.parse().map_err(Error::identifier)?,
+            fields,
+            signer: raw_msg.signer.parse().map_err(Error::signer)?,
+        })
+    }
+}
+
+impl From<MsgChannelUpgradeInit> for RawMsgChannelUpgradeInit {
+    fn from(domain_msg: MsgChannelUpgradeInit) -> Self {
+        RawMsgChannelUpgradeInit {
+            port_id: domain_msg.port_id.to_string(),
+            channel_id: domain_msg.channel_id.to_string(),
+            fields: Some(domain_msg.fields.into()),
+            signer: domain_msg.signer.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use crate::prelude::*;
+    use ibc_proto::ibc::core::channel::v1::MsgChannelUpgradeInit as RawMsgChannelUpgradeInit;
+
+    use crate::core::ics04_channel::upgrade::test_util::get_dummy_raw_upgrade_fields;
+    use crate::core::ics24_host::identifier::{ChannelId, PortId};
+    use crate::test_utils::get_dummy_bech32_account;
+
+    /// Returns a dummy `RawMsgChannelUpgradeInit`, for testing only!
+    pub fn get_dummy_raw_msg_chan_upgrade_init() -> RawMsgChannelUpgradeInit {
+        RawMsgChannelUpgradeInit {
+            port_id: PortId::default().to_string(),
+            channel_id: ChannelId::default().to_string(),
+            fields: Some(get_dummy_raw_upgrade_fields()),
+            signer: get_dummy_bech32_account(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use ibc_proto::ibc::core::channel::v1::{
+        MsgChannelUpgradeInit as RawMsgChannelUpgradeInit, UpgradeFields as RawUpgradeFields,
+    };
+
+    use crate::core::ics04_channel::msgs::chan_upgrade_init::test_util::get_dummy_raw_msg_chan_upgrade_init;
+    use crate::core::ics04_channel::msgs::chan_upgrade_init::MsgChannelUpgradeInit;
+    use crate::core::ics04_channel::upgrade::test_util::get_dummy_raw_upgrade_fields;
+
+    #[test]
+    fn parse_channel_upgrade_init_msg() {
+        struct Test {
+            name: String,
+            raw: RawMsgChannelUpgradeInit,
+            want_pass: bool,
+        }
+
+        let default_raw_msg = get_dummy_raw_msg_chan_upgrade_init();
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Good parameters".to_string(),
+                raw: default_raw_msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Bad channel, name too short".to_string(),
+                raw: RawMsgChannelUpgradeInit {
+                    channel_id: "chshort".to_string(),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Missing upgrade fields".to_string(),
+                raw: RawMsgChannelUpgradeInit {
+                    fields: None,
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "No connection hops".to_string(),
+                raw: RawMsgChannelUpgradeInit {
+                    fields: Some(RawUpgradeFields {
+                        connection_hops: vec![],
+                        ..get_dummy_raw_upgrade_fields()
+                    }),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Ordering none".to_string(),
+                raw: RawMsgChannelUpgradeInit {
+                    fields: Some(RawUpgradeFields {
+                        ordering: 0,
+                        ..get_dummy_raw_upgrade_fields()
+                    }),
+                    ..default_raw_msg
+                },
+                want_pass: false,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let msg = MsgChannelUpgradeInit::try_from(test.raw.clone());
+
+            assert_eq!(
+                test.want_pass,
+                msg.is_ok(),
+                "MsgChanUpgradeInit::try_from failed for test {}, \nmsg {:?} with error {:?}",
+                test.name,
+                test.raw,
+                msg.err(),
+            );
+        }
+    }
+
+    #[test]
+    fn to_and_from() {
+        let raw = get_dummy_raw_msg_chan_upgrade_init();
+        let msg = MsgChannelUpgradeInit::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgChannelUpgradeInit::from(msg.clone());
+        let msg_back = MsgChannelUpgradeInit::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+}
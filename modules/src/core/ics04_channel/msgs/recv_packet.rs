@@ -126,10 +126,12 @@ mod test {
 
     use ibc_proto::ibc::core::channel::v1::MsgRecvPacket as RawMsgRecvPacket;
 
+    use crate::arbitrary::ics04_channel::{arb_invalid_raw_msg_recv_packet, arb_msg_recv_packet};
     use crate::core::ics04_channel::error::Error;
     use crate::core::ics04_channel::msgs::recv_packet::test_util::get_dummy_raw_msg_recv_packet;
     use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
     use crate::test_utils::get_dummy_bech32_account;
+    use crate::test_utils::raw_round_trip_proptests;
 
     #[test]
     fn msg_recv_packet_try_from_raw() {
@@ -196,4 +198,11 @@ mod test {
         assert_eq!(raw, raw_back);
         assert_eq!(msg, msg_back);
     }
+
+    raw_round_trip_proptests!(
+        MsgRecvPacket,
+        RawMsgRecvPacket,
+        arb_msg_recv_packet(),
+        arb_invalid_raw_msg_recv_packet()
+    );
 }
@@ -135,10 +135,12 @@ mod test {
 
     use ibc_proto::ibc::core::channel::v1::MsgTimeout as RawMsgTimeout;
 
+    use crate::arbitrary::ics04_channel::{arb_invalid_raw_msg_timeout, arb_msg_timeout};
     use crate::core::ics04_channel::error::Error;
     use crate::core::ics04_channel::msgs::timeout::test_util::get_dummy_raw_msg_timeout;
     use crate::core::ics04_channel::msgs::timeout::MsgTimeout;
     use crate::test_utils::get_dummy_bech32_account;
+    use crate::test_utils::raw_round_trip_proptests;
 
     #[test]
     fn msg_timeout_try_from_raw() {
@@ -217,4 +219,11 @@ mod test {
         assert_eq!(raw, raw_back);
         assert_eq!(msg, msg_back);
     }
+
+    raw_round_trip_proptests!(
+        MsgTimeout,
+        RawMsgTimeout,
+        arb_msg_timeout(),
+        arb_invalid_raw_msg_timeout()
+    );
 }
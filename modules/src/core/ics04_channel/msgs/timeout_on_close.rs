@@ -57,6 +57,17 @@ impl TryFrom<RawMsgTimeoutOnClose> for MsgTimeoutOnClose {
     type Error = Error;
 
     fn try_from(raw_msg: RawMsgTimeoutOnClose) -> Result<Self, Self::Error> {
+        let close_proof = if raw_msg.proof_close.is_empty() {
+            None
+        } else {
+            Some(
+                raw_msg
+                    .proof_close
+                    .try_into()
+                    .map_err(Error::invalid_proof)?,
+            )
+        };
+
         let proofs = Proofs::new(
             raw_msg
                 .proof_unreceived
@@ -64,7 +75,7 @@ impl TryFrom<RawMsgTimeoutOnClose> for MsgTimeoutOnClose {
                 .map_err(Error::invalid_proof)?,
             None,
             None,
-            None,
+            close_proof,
             raw_msg
                 .proof_height
                 .and_then(|raw_height| raw_height.try_into().ok())
@@ -130,3 +141,38 @@ pub mod test_util {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use ibc_proto::ibc::core::channel::v1::MsgTimeoutOnClose as RawMsgTimeoutOnClose;
+
+    use crate::arbitrary::ics04_channel::{
+        arb_invalid_raw_msg_timeout_on_close, arb_msg_timeout_on_close,
+    };
+    use crate::core::ics04_channel::msgs::timeout_on_close::test_util::get_dummy_raw_msg_timeout_on_close;
+    use crate::core::ics04_channel::msgs::timeout_on_close::MsgTimeoutOnClose;
+    use crate::test_utils::raw_round_trip_proptests;
+
+    #[test]
+    fn to_and_from() {
+        let raw = get_dummy_raw_msg_timeout_on_close(15, 0);
+        let msg = MsgTimeoutOnClose::try_from(raw.clone()).unwrap();
+        assert!(msg.proofs.other_proof().is_some());
+
+        let raw_back = RawMsgTimeoutOnClose::from(msg.clone());
+        let msg_back = MsgTimeoutOnClose::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+
+    raw_round_trip_proptests!(
+        MsgTimeoutOnClose,
+        RawMsgTimeoutOnClose,
+        arb_msg_timeout_on_close(),
+        arb_invalid_raw_msg_timeout_on_close()
+    );
+}
@@ -43,9 +43,22 @@ pub enum PacketResult {
     Timeout(TimeoutPacketResult),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Receipt {
     Ok,
+    /// Stored when a packet that already timed out is received on an `ORDERED_ALLOW_TIMEOUT`
+    /// channel, so that the timeout can be proven to the sending chain.
+    Timeout,
+}
+
+impl Receipt {
+    /// Returns the value stored at the packet receipt path.
+    pub fn as_bytes(&self) -> &'static [u8] {
+        match self {
+            Receipt::Ok => &[1],
+            Receipt::Timeout => &[2],
+        }
+    }
 }
 
 impl core::fmt::Display for PacketMsgType {
@@ -361,8 +374,10 @@ mod tests {
     use ibc_proto::ibc::core::channel::v1::Packet as RawPacket;
     use ibc_proto::ibc::core::client::v1::Height as RawHeight;
 
+    use crate::arbitrary::ics04_channel::{arb_invalid_raw_packet, arb_packet};
     use crate::core::ics04_channel::packet::test_utils::get_dummy_raw_packet;
     use crate::core::ics04_channel::packet::Packet;
+    use crate::test_utils::raw_round_trip_proptests;
 
     #[test]
     fn packet_try_from_raw() {
@@ -536,4 +551,6 @@ mod tests {
         assert_eq!(raw, raw_back);
         assert_eq!(msg, msg_back);
     }
+
+    raw_round_trip_proptests!(Packet, RawPacket, arb_packet(), arb_invalid_raw_packet());
 }
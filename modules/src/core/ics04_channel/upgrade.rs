@@ -0,0 +1,378 @@
+//! Types for the ICS4 channel upgrade handshake, which allows an open channel to change its
+//! ordering, connection hops and version.
+
+use crate::prelude::*;
+
+use core::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::core::channel::v1::{
+    ErrorReceipt as RawErrorReceipt, Timeout as RawTimeout, Upgrade as RawUpgrade,
+    UpgradeFields as RawUpgradeFields,
+};
+
+use crate::core::ics04_channel::channel::{ChannelEnd, Order};
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics04_channel::timeout::TimeoutHeight;
+use crate::core::ics04_channel::Version;
+use crate::core::ics24_host::identifier::ConnectionId;
+use crate::timestamp::{Expiry, Timestamp};
+use crate::Height;
+
+/// The fields of a channel end which may be changed by an upgrade.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpgradeFields {
+    pub ordering: Order,
+    pub connection_hops: Vec<ConnectionId>,
+    pub version: Version,
+}
+
+impl UpgradeFields {
+    pub fn new(ordering: Order, connection_hops: Vec<ConnectionId>, version: Version) -> Self {
+        Self {
+            ordering,
+            connection_hops,
+            version,
+        }
+    }
+
+    /// Returns the fields of the given channel end, as they are before any upgrade.
+    pub fn from_channel_end(channel_end: &ChannelEnd) -> Self {
+        Self::new(
+            channel_end.ordering,
+            channel_end.connection_hops.clone(),
+            channel_end.version.clone(),
+        )
+    }
+
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        if self.connection_hops.len() != 1 {
+            return Err(Error::invalid_connection_hops_length(
+                1,
+                self.connection_hops.len(),
+            ));
+        }
+
+        if matches!(self.ordering, Order::None) {
+            return Err(Error::unknown_order_type(self.ordering.to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+impl Protobuf<RawUpgradeFields> for UpgradeFields {}
+
+impl TryFrom<RawUpgradeFields> for UpgradeFields {
+    type Error = Error;
+
+    fn try_from(value: RawUpgradeFields) -> Result<Self, Self::Error> {
+        let connection_hops = value
+            .connection_hops
+            .iter()
+            .map(|conn_id| ConnectionId::from_str(conn_id))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Error::identifier)?;
+
+        Ok(UpgradeFields::new(
+            Order::from_i32(value.ordering)?,
+            connection_hops,
+            value.version.into(),
+        ))
+    }
+}
+
+impl From<UpgradeFields> for RawUpgradeFields {
+    fn from(value: UpgradeFields) -> Self {
+        RawUpgradeFields {
+            ordering: value.ordering as i32,
+            connection_hops: value
+                .connection_hops
+                .iter()
+                .map(|v| v.as_str().to_string())
+                .collect(),
+            version: value.version.to_string(),
+        }
+    }
+}
+
+/// An upgrade proposed by one of the ends of a channel.
+///
+/// The timeout is a deadline for the counterparty to complete its side of the upgrade, after
+/// which the upgrade can be aborted with `MsgChannelUpgradeTimeout`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Upgrade {
+    pub fields: UpgradeFields,
+    pub timeout_height: TimeoutHeight,
+    pub timeout_timestamp: Timestamp,
+    /// The next sequence to be sent on the channel when the upgrade handshake reached this end,
+    /// which bounds the packets left to flush.
+    pub next_sequence_send: Sequence,
+}
+
+impl Upgrade {
+    /// Returns an upgrade to the given fields, as proposed by `MsgChannelUpgradeInit` before
+    /// the handshake has started flushing packets.
+    pub fn new(fields: UpgradeFields) -> Self {
+        Self {
+            fields,
+            timeout_height: TimeoutHeight::Never,
+            timeout_timestamp: Timestamp::none(),
+            next_sequence_send: Sequence::default(),
+        }
+    }
+
+    /// Returns `true` if the upgrade timed out at the given height and timestamp of the chain
+    /// on which the timeout is enforced.
+    pub fn timed_out(&self, height: Height, timestamp: &Timestamp) -> bool {
+        self.timeout_height.has_expired(height)
+            || matches!(
+                timestamp.check_expiry(&self.timeout_timestamp),
+                Expiry::Expired
+            )
+    }
+}
+
+impl Protobuf<RawUpgrade> for Upgrade {}
+
+impl TryFrom<RawUpgrade> for Upgrade {
+    type Error = Error;
+
+    fn try_from(value: RawUpgrade) -> Result<Self, Self::Error> {
+        let fields = value
+            .fields
+            .ok_or_else(Error::missing_upgrade_fields)?
+            .try_into()?;
+
+        let (timeout_height, timeout_timestamp) = match value.timeout {
+            Some(timeout) => (
+                timeout
+                    .height
+                    .try_into()
+                    .map_err(|_| Error::invalid_timeout_height())?,
+                Timestamp::from_nanoseconds(timeout.timestamp)
+                    .map_err(Error::invalid_packet_timestamp)?,
+            ),
+            None => (TimeoutHeight::Never, Timestamp::none()),
+        };
+
+        Ok(Upgrade {
+            fields,
+            timeout_height,
+            timeout_timestamp,
+            next_sequence_send: value.next_sequence_send.into(),
+        })
+    }
+}
+
+impl From<Upgrade> for RawUpgrade {
+    fn from(value: Upgrade) -> Self {
+        RawUpgrade {
+            fields: Some(value.fields.into()),
+            timeout: Some(RawTimeout {
+                height: value.timeout_height.into(),
+                timestamp: value.timeout_timestamp.nanoseconds(),
+            }),
+            next_sequence_send: value.next_sequence_send.into(),
+        }
+    }
+}
+
+/// Records the failure of the upgrade attempt with the given sequence, so that the counterparty
+/// can cancel its side of the upgrade with `MsgChannelUpgradeCancel`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorReceipt {
+    pub sequence: Sequence,
+    pub message: String,
+}
+
+impl ErrorReceipt {
+    pub fn new(sequence: Sequence, message: impl ToString) -> Self {
+        Self {
+            sequence,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Protobuf<RawErrorReceipt> for ErrorReceipt {}
+
+impl TryFrom<RawErrorReceipt> for ErrorReceipt {
+    type Error = Error;
+
+    fn try_from(value: RawErrorReceipt) -> Result<Self, Self::Error> {
+        Ok(ErrorReceipt {
+            sequence: value.sequence.into(),
+            message: value.message,
+        })
+    }
+}
+
+impl From<ErrorReceipt> for RawErrorReceipt {
+    fn from(value: ErrorReceipt) -> Self {
+        RawErrorReceipt {
+            sequence: value.sequence.into(),
+            message: value.message,
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use crate::core::ics24_host::identifier::ConnectionId;
+    use crate::prelude::*;
+    use ibc_proto::ibc::core::channel::v1::{
+        ErrorReceipt as RawErrorReceipt, Timeout as RawTimeout, Upgrade as RawUpgrade,
+        UpgradeFields as RawUpgradeFields,
+    };
+    use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+
+    /// Returns dummy `RawUpgradeFields`, for testing only!
+    pub fn get_dummy_raw_upgrade_fields() -> RawUpgradeFields {
+        RawUpgradeFields {
+            ordering: 2,
+            connection_hops: vec![ConnectionId::default().to_string()],
+            version: "ics20-2".to_string(),
+        }
+    }
+
+    /// Returns a dummy `RawUpgrade`, for testing only!
+    pub fn get_dummy_raw_upgrade() -> RawUpgrade {
+        RawUpgrade {
+            fields: Some(get_dummy_raw_upgrade_fields()),
+            timeout: Some(RawTimeout {
+                height: Some(RawHeight {
+                    revision_number: 0,
+                    revision_height: 20,
+                }),
+                timestamp: 0,
+            }),
+            next_sequence_send: 1,
+        }
+    }
+
+    /// Returns a dummy `RawErrorReceipt`, for testing only!
+    pub fn get_dummy_raw_error_receipt() -> RawErrorReceipt {
+        RawErrorReceipt {
+            sequence: 1,
+            message: "upgrade aborted".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use ibc_proto::ibc::core::channel::v1::{
+        Upgrade as RawUpgrade, UpgradeFields as RawUpgradeFields,
+    };
+
+    use super::test_util::{get_dummy_raw_upgrade, get_dummy_raw_upgrade_fields};
+    use super::{Upgrade, UpgradeFields};
+    use crate::core::ics02_client::height::Height;
+    use crate::timestamp::Timestamp;
+
+    #[test]
+    fn upgrade_try_from_raw() {
+        let raw = get_dummy_raw_upgrade();
+
+        struct Test {
+            name: String,
+            raw: RawUpgrade,
+            want_pass: bool,
+        }
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Good parameters".to_string(),
+                raw: raw.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "No timeout".to_string(),
+                raw: RawUpgrade {
+                    timeout: None,
+                    ..raw.clone()
+                },
+                want_pass: true,
+            },
+            Test {
+                name: "Missing upgrade fields".to_string(),
+                raw: RawUpgrade {
+                    fields: None,
+                    ..raw.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Bad connection hop".to_string(),
+                raw: RawUpgrade {
+                    fields: Some(RawUpgradeFields {
+                        connection_hops: vec!["connection*".to_string()],
+                        ..get_dummy_raw_upgrade_fields()
+                    }),
+                    ..raw.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Unknown ordering".to_string(),
+                raw: RawUpgrade {
+                    fields: Some(RawUpgradeFields {
+                        ordering: 42,
+                        ..get_dummy_raw_upgrade_fields()
+                    }),
+                    ..raw
+                },
+                want_pass: false,
+            },
+        ];
+
+        for test in tests {
+            let res = Upgrade::try_from(test.raw.clone());
+
+            assert_eq!(
+                test.want_pass,
+                res.is_ok(),
+                "Upgrade::try_from failed for test {}, \nraw {:?} with error {:?}",
+                test.name,
+                test.raw,
+                res.err(),
+            );
+        }
+    }
+
+    #[test]
+    fn upgrade_to_and_from_raw() {
+        let raw = get_dummy_raw_upgrade();
+        let upgrade = Upgrade::try_from(raw.clone()).unwrap();
+        let raw_back = RawUpgrade::from(upgrade.clone());
+        let upgrade_back = Upgrade::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(upgrade, upgrade_back);
+    }
+
+    #[test]
+    fn upgrade_timed_out() {
+        let fields = UpgradeFields::try_from(get_dummy_raw_upgrade_fields()).unwrap();
+        let mut upgrade = Upgrade::new(fields);
+        let timestamp = Timestamp::from_nanoseconds(10).unwrap();
+
+        // An upgrade proposed by `MsgChannelUpgradeInit` never times out.
+        assert!(!upgrade.timed_out(Height::new(0, 100).unwrap(), &timestamp));
+
+        upgrade.timeout_height = Height::new(0, 20).unwrap().into();
+        assert!(!upgrade.timed_out(Height::new(0, 20).unwrap(), &timestamp));
+        assert!(upgrade.timed_out(Height::new(0, 21).unwrap(), &timestamp));
+
+        upgrade.timeout_height = Default::default();
+        upgrade.timeout_timestamp = Timestamp::from_nanoseconds(5).unwrap();
+        assert!(upgrade.timed_out(Height::new(0, 1).unwrap(), &timestamp));
+    }
+}
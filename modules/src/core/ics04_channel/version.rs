@@ -7,7 +7,7 @@ use core::fmt;
 use core::str::FromStr;
 use serde_derive::{Deserialize, Serialize};
 
-use crate::applications::transfer;
+use crate::applications::{fee, transfer};
 use crate::prelude::*;
 
 /// The version field for a `ChannelEnd`.
@@ -30,6 +30,19 @@ impl Version {
     pub fn empty() -> Self {
         Self::new("".to_string())
     }
+
+    /// Returns true if this is the version of a channel on which the ICS29 fee middleware is
+    /// enabled, i.e. a JSON object wrapping the application version along with the fee version.
+    pub fn supports_fee(&self) -> bool {
+        serde_json::from_str::<FeeMetadata>(&self.0)
+            .map_or(false, |metadata| metadata.fee_version == fee::VERSION)
+    }
+}
+
+/// The version of a channel with the ICS29 fee middleware enabled.
+#[derive(Deserialize)]
+struct FeeMetadata {
+    fee_version: String,
 }
 
 impl From<String> for Version {
@@ -58,3 +71,24 @@ impl fmt::Display for Version {
         write!(f, "{}", self.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+
+    #[test]
+    fn supports_fee() {
+        let fee_version =
+            Version::new(r#"{"fee_version":"ics29-1","app_version":"ics20-1"}"#.into());
+        assert!(fee_version.supports_fee());
+
+        let other_fee_version =
+            Version::new(r#"{"fee_version":"ics29-2","app_version":"ics20-1"}"#.into());
+        assert!(!other_fee_version.supports_fee());
+
+        assert!(!Version::ics20().supports_fee());
+        assert!(!Version::empty().supports_fee());
+    }
+}
@@ -109,6 +109,14 @@ impl TryFrom<CommitmentProofBytes> for RawMerkleProof {
     }
 }
 
+impl TryFrom<CommitmentProofBytes> for MerkleProof {
+    type Error = Error;
+
+    fn try_from(value: CommitmentProofBytes) -> Result<Self, Self::Error> {
+        RawMerkleProof::try_from(value).map(Into::into)
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Deserialize, Default)]
 pub struct CommitmentPrefix {
     bytes: Vec<u8>,
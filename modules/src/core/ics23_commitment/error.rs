@@ -1,5 +1,9 @@
+use crate::prelude::*;
 use flex_error::{define_error, TraceError};
 use prost::DecodeError;
+use tendermint_proto::Error as TendermintProtoError;
+
+use crate::core::ics24_host::path::Path;
 
 define_error! {
     #[derive(Debug, PartialEq, Eq)]
@@ -34,6 +38,24 @@ define_error! {
             |_| { "invalid merkle proof" },
 
         VerificationFailure
-            |_| { "proof verification failed" }
+            |_| { "proof verification failed" },
+
+        EmptyProofSpecs
+            |_| { "empty proof specs" },
+
+        InvalidProofSpec
+            { index: usize, reason: String }
+            | e | { format_args!("invalid proof spec at index {0}: {1}", e.index, e.reason) },
+
+        EmptyBatch
+            |_| { "empty batch of keys or proofs" },
+
+        InconsistentBatch
+            |_| { "keys or proofs of a batch do not belong to the same subtree" },
+
+        ValueEncodingFailed
+            { path: Path }
+            [ TraceError<TendermintProtoError> ]
+            | e | { format_args!("failed to encode the expected value at path {0}", e.path) },
     }
 }
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use alloc::collections::BTreeMap;
 use tendermint::merkle::proof::Proof as TendermintProof;
 
 use ibc_proto::ibc::core::commitment::v1::MerklePath;
@@ -6,7 +7,8 @@ use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
 use ibc_proto::ibc::core::commitment::v1::MerkleRoot;
 use ics23::commitment_proof::Proof;
 use ics23::{
-    calculate_existence_root, verify_membership, verify_non_membership, CommitmentProof,
+    batch_entry, calculate_existence_root, compress, decompress, verify_membership,
+    verify_non_membership, BatchEntry, BatchProof, CommitmentProof, ExistenceProof,
     NonExistenceProof,
 };
 
@@ -105,17 +107,18 @@ impl MerkleProof {
             .zip(keys.key_path.iter().rev())
             .skip(start_index)
         {
-            match &proof.proof {
-                Some(Proof::Exist(existence_proof)) => {
-                    subroot = calculate_existence_root(existence_proof)
-                        .map_err(|_| Error::invalid_merkle_proof())?;
-                    if !verify_membership(proof, spec, &subroot, key.as_bytes(), &value) {
-                        return Err(Error::verification_failure());
-                    }
-                    value = subroot.clone();
-                }
-                _ => return Err(Error::invalid_merkle_proof()),
+            let existence_proof = existence_proofs(proof)?
+                .remove(key.as_bytes())
+                .ok_or_else(Error::invalid_merkle_proof)?;
+            subroot = calculate_existence_root(&existence_proof)
+                .map_err(|_| Error::invalid_merkle_proof())?;
+            let proof = CommitmentProof {
+                proof: Some(Proof::Exist(existence_proof)),
+            };
+            if !verify_membership(&proof, spec, &subroot, key.as_bytes(), &value) {
+                return Err(Error::verification_failure());
             }
+            value = subroot.clone();
         }
 
         if root.hash != subroot {
@@ -155,17 +158,153 @@ impl MerkleProof {
             .key_path
             .get(num - 1)
             .ok_or_else(Error::invalid_merkle_proof)?;
-        match &proof.proof {
-            Some(Proof::Nonexist(non_existence_proof)) => {
-                let subroot = calculate_non_existence_root(non_existence_proof)?;
-                if !verify_non_membership(proof, spec, &subroot, key.as_bytes()) {
-                    return Err(Error::verification_failure());
-                }
-                // verify membership proofs starting from index 1 with value = subroot
-                self.verify_membership(specs, root, keys, subroot, 1)
+        let non_existence_proof = non_existence_proofs(proof)?
+            .remove(key.as_bytes())
+            .ok_or_else(Error::invalid_merkle_proof)?;
+        let subroot = calculate_non_existence_root(&non_existence_proof)?;
+        let proof = CommitmentProof {
+            proof: Some(Proof::Nonexist(non_existence_proof)),
+        };
+        if !verify_non_membership(&proof, spec, &subroot, key.as_bytes()) {
+            return Err(Error::verification_failure());
+        }
+        // verify membership proofs starting from index 1 with value = subroot
+        self.verify_membership(specs, root, keys, subroot, 1)
+    }
+
+    /// Verifies that each of the `items` values is stored under its key path, given a proof whose
+    /// lowest level is a (possibly compressed) batch proof of all the keys, such as the ones built
+    /// by [`MerkleProof::batch`]. The key paths must only differ in their last key.
+    pub fn verify_batch_membership(
+        &self,
+        specs: &ProofSpecs,
+        root: MerkleRoot,
+        items: Vec<(MerklePath, Vec<u8>)>,
+    ) -> Result<(), Error> {
+        let (first_keys, _) = items.first().ok_or_else(Error::empty_batch)?;
+        check_batch_keys(first_keys, items.iter().map(|(keys, _)| keys))?;
+        let proof = self.proofs.first().ok_or_else(Error::empty_merkle_proof)?;
+        let mut existence_proofs = existence_proofs(proof)?;
+        let ics23_specs = Vec::<ics23::ProofSpec>::from(specs.clone());
+        let spec = ics23_specs
+            .first()
+            .ok_or_else(Error::number_of_specs_mismatch)?;
+
+        // verify the membership of every key in the lowest subtree, against a common subroot
+        let mut subroot: Option<Vec<u8>> = None;
+        for (keys, value) in &items {
+            if value.is_empty() {
+                return Err(Error::empty_verified_value());
+            }
+            let key = keys
+                .key_path
+                .last()
+                .ok_or_else(Error::number_of_keys_mismatch)?;
+            let existence_proof = existence_proofs
+                .remove(key.as_bytes())
+                .ok_or_else(Error::invalid_merkle_proof)?;
+            let key_root = calculate_existence_root(&existence_proof)
+                .map_err(|_| Error::invalid_merkle_proof())?;
+            if subroot.get_or_insert_with(|| key_root.clone()) != &key_root {
+                return Err(Error::verification_failure());
+            }
+            let proof = CommitmentProof {
+                proof: Some(Proof::Exist(existence_proof)),
+            };
+            if !verify_membership(&proof, spec, &key_root, key.as_bytes(), value) {
+                return Err(Error::verification_failure());
             }
-            _ => Err(Error::invalid_merkle_proof()),
         }
+
+        let subroot = subroot.ok_or_else(Error::empty_batch)?;
+        // verify membership proofs starting from index 1 with value = subroot
+        self.verify_membership(specs, root, first_keys.clone(), subroot, 1)
+    }
+
+    /// Verifies that no value is stored under any of the `keys` paths, given a proof whose lowest
+    /// level is a (possibly compressed) batch proof of all the keys, such as the ones built by
+    /// [`MerkleProof::batch`]. The key paths must only differ in their last key.
+    pub fn verify_batch_non_membership(
+        &self,
+        specs: &ProofSpecs,
+        root: MerkleRoot,
+        keys: Vec<MerklePath>,
+    ) -> Result<(), Error> {
+        let first_keys = keys.first().ok_or_else(Error::empty_batch)?;
+        check_batch_keys(first_keys, keys.iter())?;
+        let proof = self.proofs.first().ok_or_else(Error::empty_merkle_proof)?;
+        let mut non_existence_proofs = non_existence_proofs(proof)?;
+        let ics23_specs = Vec::<ics23::ProofSpec>::from(specs.clone());
+        let spec = ics23_specs
+            .first()
+            .ok_or_else(Error::number_of_specs_mismatch)?;
+
+        // verify the absence of every key in the lowest subtree, against a common subroot
+        let mut subroot: Option<Vec<u8>> = None;
+        for keys in &keys {
+            let key = keys
+                .key_path
+                .last()
+                .ok_or_else(Error::number_of_keys_mismatch)?;
+            let non_existence_proof = non_existence_proofs
+                .remove(key.as_bytes())
+                .ok_or_else(Error::invalid_merkle_proof)?;
+            let key_root = calculate_non_existence_root(&non_existence_proof)?;
+            if subroot.get_or_insert_with(|| key_root.clone()) != &key_root {
+                return Err(Error::verification_failure());
+            }
+            let proof = CommitmentProof {
+                proof: Some(Proof::Nonexist(non_existence_proof)),
+            };
+            if !verify_non_membership(&proof, spec, &key_root, key.as_bytes()) {
+                return Err(Error::verification_failure());
+            }
+        }
+
+        let subroot = subroot.ok_or_else(Error::empty_batch)?;
+        // verify membership proofs starting from index 1 with value = subroot
+        self.verify_membership(specs, root, first_keys.clone(), subroot, 1)
+    }
+
+    /// Combines the proofs of several keys of the same subtree into a single proof, whose lowest
+    /// level is a compressed batch proof of all the keys, and whose upper levels are the ones
+    /// shared by all the given proofs.
+    pub fn batch(proofs: Vec<MerkleProof>) -> Result<MerkleProof, Error> {
+        let mut proofs = proofs.into_iter();
+        let first = proofs.next().ok_or_else(Error::empty_batch)?;
+        let (lowest, upper) = first
+            .proofs
+            .split_first()
+            .ok_or_else(Error::empty_merkle_proof)?;
+
+        let mut entries = vec![batch_entry(lowest)?];
+        for proof in proofs {
+            let (proof_lowest, proof_upper) = proof
+                .proofs
+                .split_first()
+                .ok_or_else(Error::empty_merkle_proof)?;
+            if proof_upper != upper {
+                return Err(Error::inconsistent_batch());
+            }
+            entries.push(batch_entry(proof_lowest)?);
+        }
+
+        let batch = CommitmentProof {
+            proof: Some(Proof::Batch(BatchProof { entries })),
+        };
+        let compressed = compress(&batch).map_err(|_| Error::invalid_merkle_proof())?;
+
+        let mut proofs = vec![compressed];
+        proofs.extend_from_slice(upper);
+        Ok(MerkleProof { proofs })
+    }
+
+    /// Returns whether the lowest level of this proof is a (possibly compressed) batch proof.
+    pub fn is_batch(&self) -> bool {
+        matches!(
+            self.proofs.first().and_then(|p| p.proof.as_ref()),
+            Some(Proof::Batch(_)) | Some(Proof::Compressed(_))
+        )
     }
 }
 
@@ -180,6 +319,74 @@ fn calculate_non_existence_root(proof: &NonExistenceProof) -> Result<Vec<u8>, Er
     }
 }
 
+/// Returns the proofs of existence carried by `proof`, which is either a single proof or a
+/// (possibly compressed) batch proof, indexed by key. The proof is decompressed only once, so that
+/// the keys of a batch can then be looked up at no extra cost.
+fn existence_proofs(proof: &CommitmentProof) -> Result<BTreeMap<Vec<u8>, ExistenceProof>, Error> {
+    let proof = decompress(proof).map_err(|_| Error::invalid_merkle_proof())?;
+    match proof.proof {
+        Some(Proof::Exist(existence_proof)) => Ok(BTreeMap::from([(
+            existence_proof.key.clone(),
+            existence_proof,
+        )])),
+        Some(Proof::Batch(batch)) => Ok(batch
+            .entries
+            .into_iter()
+            .filter_map(|entry| match entry.proof {
+                Some(batch_entry::Proof::Exist(p)) => Some((p.key.clone(), p)),
+                _ => None,
+            })
+            .collect()),
+        _ => Err(Error::invalid_merkle_proof()),
+    }
+}
+
+/// Returns the proofs of non-existence carried by `proof`, which is either a single proof or a
+/// (possibly compressed) batch proof, indexed by key.
+fn non_existence_proofs(
+    proof: &CommitmentProof,
+) -> Result<BTreeMap<Vec<u8>, NonExistenceProof>, Error> {
+    let proof = decompress(proof).map_err(|_| Error::invalid_merkle_proof())?;
+    match proof.proof {
+        Some(Proof::Nonexist(non_existence_proof)) => Ok(BTreeMap::from([(
+            non_existence_proof.key.clone(),
+            non_existence_proof,
+        )])),
+        Some(Proof::Batch(batch)) => Ok(batch
+            .entries
+            .into_iter()
+            .filter_map(|entry| match entry.proof {
+                Some(batch_entry::Proof::Nonexist(p)) => Some((p.key.clone(), p)),
+                _ => None,
+            })
+            .collect()),
+        _ => Err(Error::invalid_merkle_proof()),
+    }
+}
+
+fn batch_entry(proof: &CommitmentProof) -> Result<BatchEntry, Error> {
+    let proof = match &proof.proof {
+        Some(Proof::Exist(p)) => batch_entry::Proof::Exist(p.clone()),
+        Some(Proof::Nonexist(p)) => batch_entry::Proof::Nonexist(p.clone()),
+        _ => return Err(Error::invalid_merkle_proof()),
+    };
+    Ok(BatchEntry { proof: Some(proof) })
+}
+
+/// Checks that all the key paths of a batch only differ from the `first` one in their last key,
+/// i.e. that they are all proven in the same subtree.
+fn check_batch_keys<'a>(
+    first: &MerklePath,
+    mut keys: impl Iterator<Item = &'a MerklePath>,
+) -> Result<(), Error> {
+    let subtree = first.key_path.split_last().map(|(_, prefix)| prefix);
+    if keys.all(|keys| keys.key_path.split_last().map(|(_, prefix)| prefix) == subtree) {
+        Ok(())
+    } else {
+        Err(Error::inconsistent_batch())
+    }
+}
+
 // Merkle Proof serialization notes:
 // "Proof" id currently defined in a number of forms and included in a number of places
 // - TmProof: in tendermint-rs/src/merkle/proof.rs:Proof
@@ -245,3 +452,82 @@ pub fn convert_tm_to_ics_merkle_proof(tm_proof: &TendermintProof) -> Result<Merk
 
     Ok(MerkleProof::from(RawMerkleProof { proofs }))
 }
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+    use crate::core::ics23_commitment::error::ErrorDetail;
+
+    fn dummy_existence_proof(key: &[u8]) -> ExistenceProof {
+        ExistenceProof {
+            key: key.to_vec(),
+            value: b"value".to_vec(),
+            leaf: None,
+            path: vec![],
+        }
+    }
+
+    fn dummy_merkle_proof(key: &[u8], store: &[u8]) -> MerkleProof {
+        let proofs = [key, store]
+            .iter()
+            .map(|key| CommitmentProof {
+                proof: Some(Proof::Exist(dummy_existence_proof(key))),
+            })
+            .collect();
+        MerkleProof { proofs }
+    }
+
+    #[test]
+    fn batch_carries_proofs_of_all_keys() {
+        let keys: [&[u8]; 3] = [b"key-a", b"key-b", b"key-c"];
+        let proofs = keys
+            .iter()
+            .map(|key| dummy_merkle_proof(key, b"ibc"))
+            .collect();
+
+        let batch = MerkleProof::batch(proofs).unwrap();
+        assert!(batch.is_batch());
+        assert_eq!(batch.proofs.len(), 2);
+        assert_eq!(
+            batch.proofs[1],
+            dummy_merkle_proof(b"key-a", b"ibc").proofs[1]
+        );
+
+        let existence_proofs = existence_proofs(&batch.proofs[0]).unwrap();
+        assert_eq!(existence_proofs.len(), keys.len());
+        for key in keys {
+            assert_eq!(existence_proofs[key], dummy_existence_proof(key));
+        }
+    }
+
+    #[test]
+    fn batch_rejects_proofs_of_different_subtrees() {
+        let proofs = vec![
+            dummy_merkle_proof(b"key-a", b"ibc"),
+            dummy_merkle_proof(b"key-b", b"bank"),
+        ];
+
+        let err = MerkleProof::batch(proofs).unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::InconsistentBatch(_)));
+    }
+
+    #[test]
+    fn batch_verification_rejects_keys_of_different_subtrees() {
+        let proof = MerkleProof::batch(vec![dummy_merkle_proof(b"key-a", b"ibc")]).unwrap();
+        let keys = vec![
+            MerklePath {
+                key_path: vec!["ibc".to_string(), "key-a".to_string()],
+            },
+            MerklePath {
+                key_path: vec!["bank".to_string(), "key-b".to_string()],
+            },
+        ];
+
+        let err = proof
+            .verify_batch_non_membership(&ProofSpecs::cosmos(), MerkleRoot { hash: vec![1] }, keys)
+            .unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::InconsistentBatch(_)));
+    }
+}
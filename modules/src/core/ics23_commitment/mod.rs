@@ -6,3 +6,4 @@ pub mod error;
 pub mod merkle;
 pub mod mock;
 pub mod specs;
+pub mod verify;
@@ -1,8 +1,19 @@
+use crate::core::ics23_commitment::error::Error;
 use crate::prelude::*;
-use ibc_proto::ics23::{InnerSpec as IbcInnerSpec, LeafOp as IbcLeafOp, ProofSpec as IbcProofSpec};
-use ics23::{InnerSpec as Ics23InnerSpec, LeafOp as Ics23LeafOp, ProofSpec as Ics23ProofSpec};
+use ibc_proto::ics23::{
+    HashOp as IbcHashOp, InnerSpec as IbcInnerSpec, LeafOp as IbcLeafOp, LengthOp as IbcLengthOp,
+    ProofSpec as IbcProofSpec,
+};
+use ics23::{
+    HashOp, InnerSpec as Ics23InnerSpec, LeafOp as Ics23LeafOp, LengthOp,
+    ProofSpec as Ics23ProofSpec,
+};
 use serde::{Deserialize, Serialize};
 
+/// The maximum depth of a tree that a proof specification can allow for, i.e. one level per bit
+/// of a 32-byte hashed key, as in sparse Merkle trees.
+pub const MAX_PROOF_DEPTH: i32 = 256;
+
 /// An array of proof specifications.
 ///
 /// This type encapsulates different types of proof specifications, mostly predefined, e.g., for
@@ -23,9 +34,41 @@ impl ProofSpecs {
         .into()
     }
 
+    /// Returns the specification for proofs of a single Tendermint simple Merkle tree
+    pub fn tendermint() -> Self {
+        vec![ics23::tendermint_spec()].into()
+    }
+
+    /// Returns the specification for proofs of a sparse Merkle tree (SMT) store, committed
+    /// under a Tendermint simple Merkle tree of stores
+    pub fn smt() -> Self {
+        vec![smt_spec(), ics23::tendermint_spec()].into()
+    }
+
+    /// Returns the specification for proofs of a single Jellyfish Merkle tree (JMT)
+    pub fn jmt() -> Self {
+        vec![jmt_spec()].into()
+    }
+
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Checks that each of the specifications is coherent, i.e. that it specifies both leaf and
+    /// inner nodes with actual hash operations, and consistent depth, prefix length and child
+    /// bounds.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.is_empty() {
+            return Err(Error::empty_proof_specs());
+        }
+
+        for (index, spec) in self.0.iter().enumerate() {
+            spec.validate()
+                .map_err(|reason| Error::invalid_proof_spec(index, reason))?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for ProofSpecs {
@@ -66,6 +109,139 @@ impl From<ProofSpecs> for Vec<IbcProofSpec> {
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 struct ProofSpec(IbcProofSpec);
 
+impl ProofSpec {
+    fn validate(&self) -> Result<(), String> {
+        let spec = &self.0;
+
+        if spec.min_depth < 0 || spec.max_depth < 0 {
+            return Err(format!(
+                "negative depth bounds (min: {}, max: {})",
+                spec.min_depth, spec.max_depth
+            ));
+        }
+        if spec.min_depth > MAX_PROOF_DEPTH || spec.max_depth > MAX_PROOF_DEPTH {
+            return Err(format!(
+                "depth bounds (min: {}, max: {}) exceed the limit of {}",
+                spec.min_depth, spec.max_depth, MAX_PROOF_DEPTH
+            ));
+        }
+        // A zero `max_depth` means that the depth is unbounded
+        if spec.max_depth > 0 && spec.min_depth > spec.max_depth {
+            return Err(format!(
+                "min depth {} is greater than max depth {}",
+                spec.min_depth, spec.max_depth
+            ));
+        }
+
+        let leaf_spec = spec
+            .leaf_spec
+            .as_ref()
+            .ok_or_else(|| "missing leaf spec".to_string())?;
+        validate_hash_op("leaf hash", leaf_spec.hash, false)?;
+        validate_hash_op("leaf key prehash", leaf_spec.prehash_key, true)?;
+        validate_hash_op("leaf value prehash", leaf_spec.prehash_value, true)?;
+        if IbcLengthOp::from_i32(leaf_spec.length).is_none() {
+            return Err(format!("unknown leaf length op {}", leaf_spec.length));
+        }
+
+        let inner_spec = spec
+            .inner_spec
+            .as_ref()
+            .ok_or_else(|| "missing inner spec".to_string())?;
+        validate_hash_op("inner hash", inner_spec.hash, false)?;
+        if inner_spec.min_prefix_length < 0
+            || inner_spec.min_prefix_length > inner_spec.max_prefix_length
+        {
+            return Err(format!(
+                "invalid inner prefix length bounds (min: {}, max: {})",
+                inner_spec.min_prefix_length, inner_spec.max_prefix_length
+            ));
+        }
+        if inner_spec.child_size <= 0 {
+            return Err(format!(
+                "inner child size {} is not positive",
+                inner_spec.child_size
+            ));
+        }
+
+        let mut child_order = inner_spec.child_order.clone();
+        child_order.sort_unstable();
+        if child_order.len() < 2 || !child_order.iter().zip(0..).all(|(&c, i)| c == i) {
+            return Err(format!(
+                "inner child order {:?} is not a permutation of at least two children",
+                inner_spec.child_order
+            ));
+        }
+
+        if !inner_spec.empty_child.is_empty()
+            && inner_spec.empty_child.len() != inner_spec.child_size as usize
+        {
+            return Err(format!(
+                "inner empty child of {} bytes does not match the child size {}",
+                inner_spec.empty_child.len(),
+                inner_spec.child_size
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_hash_op(name: &str, hash_op: i32, allow_no_hash: bool) -> Result<(), String> {
+    match IbcHashOp::from_i32(hash_op) {
+        None => Err(format!("unknown {} op {}", name, hash_op)),
+        Some(IbcHashOp::NoHash) if !allow_no_hash => Err(format!("missing {} op", name)),
+        Some(_) => Ok(()),
+    }
+}
+
+/// The specification of sparse Merkle tree proofs, as in the ICS 23 reference implementation.
+fn smt_spec() -> Ics23ProofSpec {
+    Ics23ProofSpec {
+        leaf_spec: Some(Ics23LeafOp {
+            hash: HashOp::Sha256.into(),
+            prehash_key: HashOp::Sha256.into(),
+            prehash_value: HashOp::Sha256.into(),
+            length: LengthOp::NoPrefix.into(),
+            prefix: vec![0],
+        }),
+        inner_spec: Some(Ics23InnerSpec {
+            child_order: vec![0, 1],
+            child_size: 32,
+            min_prefix_length: 1,
+            max_prefix_length: 1,
+            empty_child: vec![0; 32],
+            hash: HashOp::Sha256.into(),
+        }),
+        max_depth: 256,
+        min_depth: 0,
+    }
+}
+
+/// The specification of Jellyfish Merkle tree proofs, whose nodes are domain-separated by the
+/// `JMT::LeafNode` and `JMT::IntrnalNode` prefixes.
+fn jmt_spec() -> Ics23ProofSpec {
+    Ics23ProofSpec {
+        leaf_spec: Some(Ics23LeafOp {
+            hash: HashOp::Sha256.into(),
+            prehash_key: HashOp::Sha256.into(),
+            prehash_value: HashOp::Sha256.into(),
+            length: LengthOp::NoPrefix.into(),
+            prefix: b"JMT::LeafNode".to_vec(),
+        }),
+        inner_spec: Some(Ics23InnerSpec {
+            child_order: vec![0, 1],
+            child_size: 32,
+            min_prefix_length: 16,
+            max_prefix_length: 16,
+            empty_child: b"SPARSE_MERKLE_PLACEHOLDER_HASH__".to_vec(),
+            hash: HashOp::Sha256.into(),
+        }),
+        max_depth: 64,
+        min_depth: 0,
+    }
+}
+
 impl From<Ics23ProofSpec> for ProofSpec {
     fn from(spec: Ics23ProofSpec) -> Self {
         Self(IbcProofSpec {
@@ -146,3 +322,76 @@ impl From<InnerSpec> for Ics23InnerSpec {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+    use crate::core::ics23_commitment::error::ErrorDetail;
+
+    #[test]
+    fn presets_are_valid() {
+        for specs in [
+            ProofSpecs::cosmos(),
+            ProofSpecs::tendermint(),
+            ProofSpecs::smt(),
+            ProofSpecs::jmt(),
+        ] {
+            assert!(specs.validate().is_ok(), "invalid preset: {:?}", specs);
+        }
+    }
+
+    #[test]
+    fn empty_specs_are_invalid() {
+        let specs = ProofSpecs::from(Vec::<Ics23ProofSpec>::new());
+        assert!(matches!(
+            specs.validate().unwrap_err().detail(),
+            ErrorDetail::EmptyProofSpecs(_)
+        ));
+    }
+
+    #[test]
+    fn incoherent_specs_are_invalid() {
+        type Corruption = fn(&mut Ics23ProofSpec);
+
+        let tests: Vec<(&str, Corruption)> = vec![
+            ("missing leaf spec", |spec| spec.leaf_spec = None),
+            ("missing inner spec", |spec| spec.inner_spec = None),
+            ("inverted depth bounds", |spec| {
+                spec.min_depth = 10;
+                spec.max_depth = 5;
+            }),
+            ("excessive depth", |spec| {
+                spec.max_depth = MAX_PROOF_DEPTH + 1
+            }),
+            ("missing leaf hash", |spec| {
+                spec.leaf_spec.as_mut().unwrap().hash = HashOp::NoHash.into()
+            }),
+            ("unknown inner hash", |spec| {
+                spec.inner_spec.as_mut().unwrap().hash = 42
+            }),
+            ("inverted prefix length bounds", |spec| {
+                spec.inner_spec.as_mut().unwrap().max_prefix_length = 0
+            }),
+            ("invalid child order", |spec| {
+                spec.inner_spec.as_mut().unwrap().child_order = vec![0, 2]
+            }),
+            ("empty child size mismatch", |spec| {
+                spec.inner_spec.as_mut().unwrap().empty_child = vec![0; 31]
+            }),
+        ];
+
+        for (name, corrupt) in tests {
+            let mut spec = smt_spec();
+            corrupt(&mut spec);
+
+            let specs = ProofSpecs::from(vec![ics23::tendermint_spec(), spec]);
+            let err = specs.validate().unwrap_err();
+            match err.detail() {
+                ErrorDetail::InvalidProofSpec(e) => assert_eq!(e.index, 1, "{}", name),
+                _ => panic!("unexpected error for {}: {}", name, err),
+            }
+        }
+    }
+}
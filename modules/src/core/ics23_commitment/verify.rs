@@ -0,0 +1,732 @@
+//! Verification of ICS 23 proofs of the IBC state of a chain, independently of any client.
+//!
+//! This is the verification that light clients such as ICS 07 Tendermint perform on the proofs
+//! carried by IBC messages, exposed so that off-chain processes (e.g., indexers) can check the
+//! proofs returned by a node against the root of a consensus state they trust.
+
+use prost::Message;
+use tendermint_proto::Protobuf;
+
+use crate::core::ics02_client::client_consensus::AnyConsensusState;
+use crate::core::ics02_client::client_state::AnyClientState;
+use crate::core::ics03_connection::connection::ConnectionEnd;
+use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
+use crate::core::ics04_channel::packet::{Receipt, Sequence};
+use crate::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
+use crate::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentRoot};
+use crate::core::ics23_commitment::error::Error;
+use crate::core::ics23_commitment::merkle::{apply_prefix, MerkleProof};
+use crate::core::ics23_commitment::specs::ProofSpecs;
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::core::ics24_host::path::{
+    AcksPath, ChannelEndsPath, ChannelUpgradeErrorsPath, ChannelUpgradesPath,
+    ClientConsensusStatePath, ClientStatePath, CommitmentsPath, ConnectionsPath, Path,
+    ReceiptsPath, SeqRecvsPath,
+};
+use crate::prelude::*;
+use crate::Height;
+
+/// Verifies proofs of the values stored at IBC paths of a chain, given the specifications of the
+/// proofs of that chain, the prefix of its IBC store, and a commitment root of its state (e.g.,
+/// the root of a consensus state of a client tracking that chain).
+#[derive(Clone, Debug)]
+pub struct ProofVerifier<'a> {
+    specs: &'a ProofSpecs,
+    prefix: &'a CommitmentPrefix,
+    root: &'a CommitmentRoot,
+}
+
+impl<'a> ProofVerifier<'a> {
+    pub fn new(
+        specs: &'a ProofSpecs,
+        prefix: &'a CommitmentPrefix,
+        root: &'a CommitmentRoot,
+    ) -> Self {
+        Self {
+            specs,
+            prefix,
+            root,
+        }
+    }
+
+    /// Verifies that `proof` proves that `value` is stored at `path`.
+    pub fn verify_membership(
+        &self,
+        proof: &MerkleProof,
+        path: impl Into<Path>,
+        value: Vec<u8>,
+    ) -> Result<(), Error> {
+        let merkle_path = apply_prefix(self.prefix, vec![path.into().to_string()]);
+        proof.verify_membership(self.specs, self.root.clone().into(), merkle_path, value, 0)
+    }
+
+    /// Verifies that `proof` proves that no value is stored at `path`.
+    pub fn verify_non_membership(
+        &self,
+        proof: &MerkleProof,
+        path: impl Into<Path>,
+    ) -> Result<(), Error> {
+        let merkle_path = apply_prefix(self.prefix, vec![path.into().to_string()]);
+        proof.verify_non_membership(self.specs, self.root.clone().into(), merkle_path)
+    }
+
+    /// Verifies that `proof` proves that each of the `items` values is stored at its path.
+    pub fn verify_batch_membership(
+        &self,
+        proof: &MerkleProof,
+        items: Vec<(Path, Vec<u8>)>,
+    ) -> Result<(), Error> {
+        let items = items
+            .into_iter()
+            .map(|(path, value)| (apply_prefix(self.prefix, vec![path.to_string()]), value))
+            .collect();
+        proof.verify_batch_membership(self.specs, self.root.clone().into(), items)
+    }
+
+    /// Verifies that `proof` proves that no value is stored at any of the `paths`.
+    pub fn verify_batch_non_membership(
+        &self,
+        proof: &MerkleProof,
+        paths: Vec<Path>,
+    ) -> Result<(), Error> {
+        let keys = paths
+            .into_iter()
+            .map(|path| apply_prefix(self.prefix, vec![path.to_string()]))
+            .collect();
+        proof.verify_batch_non_membership(self.specs, self.root.clone().into(), keys)
+    }
+
+    pub fn verify_client_state(
+        &self,
+        proof: &MerkleProof,
+        client_id: &ClientId,
+        expected_client_state: &AnyClientState,
+    ) -> Result<(), Error> {
+        let path = ClientStatePath(client_id.clone());
+        let value = encode(&path, expected_client_state)?;
+        self.verify_membership(proof, path, value)
+    }
+
+    pub fn verify_consensus_state(
+        &self,
+        proof: &MerkleProof,
+        client_id: &ClientId,
+        consensus_height: Height,
+        expected_consensus_state: &AnyConsensusState,
+    ) -> Result<(), Error> {
+        let path = ClientConsensusStatePath {
+            client_id: client_id.clone(),
+            epoch: consensus_height.revision_number(),
+            height: consensus_height.revision_height(),
+        };
+        let value = encode(&path, expected_consensus_state)?;
+        self.verify_membership(proof, path, value)
+    }
+
+    pub fn verify_connection_end(
+        &self,
+        proof: &MerkleProof,
+        connection_id: &ConnectionId,
+        expected_connection_end: &ConnectionEnd,
+    ) -> Result<(), Error> {
+        let path = ConnectionsPath(connection_id.clone());
+        let value = encode(&path, expected_connection_end)?;
+        self.verify_membership(proof, path, value)
+    }
+
+    pub fn verify_channel_end(
+        &self,
+        proof: &MerkleProof,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_channel_end: &ChannelEnd,
+    ) -> Result<(), Error> {
+        let path = ChannelEndsPath(port_id.clone(), channel_id.clone());
+        let value = encode(&path, expected_channel_end)?;
+        self.verify_membership(proof, path, value)
+    }
+
+    pub fn verify_channel_upgrade(
+        &self,
+        proof: &MerkleProof,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_upgrade: &Upgrade,
+    ) -> Result<(), Error> {
+        let path = ChannelUpgradesPath(port_id.clone(), channel_id.clone());
+        let value = encode(&path, expected_upgrade)?;
+        self.verify_membership(proof, path, value)
+    }
+
+    pub fn verify_channel_upgrade_error(
+        &self,
+        proof: &MerkleProof,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_error_receipt: &ErrorReceipt,
+    ) -> Result<(), Error> {
+        let path = ChannelUpgradeErrorsPath(port_id.clone(), channel_id.clone());
+        let value = encode(&path, expected_error_receipt)?;
+        self.verify_membership(proof, path, value)
+    }
+
+    pub fn verify_packet_commitment(
+        &self,
+        proof: &MerkleProof,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        commitment: PacketCommitment,
+    ) -> Result<(), Error> {
+        let path = CommitmentsPath {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence,
+        };
+        self.verify_membership(proof, path, commitment.into_vec())
+    }
+
+    pub fn verify_packet_acknowledgement(
+        &self,
+        proof: &MerkleProof,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        ack_commitment: AcknowledgementCommitment,
+    ) -> Result<(), Error> {
+        let path = AcksPath {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence,
+        };
+        self.verify_membership(proof, path, ack_commitment.into_vec())
+    }
+
+    pub fn verify_packet_commitments(
+        &self,
+        proof: &MerkleProof,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        commitments: Vec<(Sequence, PacketCommitment)>,
+    ) -> Result<(), Error> {
+        let items = commitments
+            .into_iter()
+            .map(|(sequence, commitment)| {
+                let path = CommitmentsPath {
+                    port_id: port_id.clone(),
+                    channel_id: channel_id.clone(),
+                    sequence,
+                };
+                (path.into(), commitment.into_vec())
+            })
+            .collect();
+        self.verify_batch_membership(proof, items)
+    }
+
+    pub fn verify_packet_acknowledgements(
+        &self,
+        proof: &MerkleProof,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        ack_commitments: Vec<(Sequence, AcknowledgementCommitment)>,
+    ) -> Result<(), Error> {
+        let items = ack_commitments
+            .into_iter()
+            .map(|(sequence, ack_commitment)| {
+                let path = AcksPath {
+                    port_id: port_id.clone(),
+                    channel_id: channel_id.clone(),
+                    sequence,
+                };
+                (path.into(), ack_commitment.into_vec())
+            })
+            .collect();
+        self.verify_batch_membership(proof, items)
+    }
+
+    pub fn verify_packet_receipt(
+        &self,
+        proof: &MerkleProof,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        receipt: Receipt,
+    ) -> Result<(), Error> {
+        let path = ReceiptsPath {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence,
+        };
+        self.verify_membership(proof, path, receipt.as_bytes().to_vec())
+    }
+
+    pub fn verify_packet_receipt_absence(
+        &self,
+        proof: &MerkleProof,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<(), Error> {
+        let path = ReceiptsPath {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence,
+        };
+        self.verify_non_membership(proof, path)
+    }
+
+    pub fn verify_next_sequence_recv(
+        &self,
+        proof: &MerkleProof,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<(), Error> {
+        let mut seq_bytes = Vec::new();
+        u64::from(sequence)
+            .encode(&mut seq_bytes)
+            .expect("buffer size too small");
+
+        let path = SeqRecvsPath(port_id.clone(), channel_id.clone());
+        self.verify_membership(proof, path, seq_bytes)
+    }
+}
+
+fn encode<P, T, Raw>(path: &P, value: &T) -> Result<Vec<u8>, Error>
+where
+    P: Into<Path> + Clone,
+    T: Protobuf<Raw> + Clone,
+    Raw: Message + Default + From<T>,
+    <T as TryFrom<Raw>>::Error: core::fmt::Display,
+{
+    value
+        .encode_vec()
+        .map_err(|e| Error::value_encoding_failed(path.clone().into(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use ics23::commitment_proof::Proof;
+    use ics23::{
+        calculate_existence_root, iavl_spec, tendermint_spec, CommitmentProof, ExistenceProof,
+        InnerOp, LeafOp, NonExistenceProof,
+    };
+
+    use super::*;
+    use crate::core::ics04_channel::upgrade::test_util::get_dummy_raw_upgrade;
+    use crate::core::ics23_commitment::commitment::test_util::get_dummy_merkle_proof;
+    use crate::core::ics23_commitment::error::ErrorDetail;
+    use crate::mock::client_state::{MockClientState, MockConsensusState};
+    use crate::mock::header::MockHeader;
+
+    fn dummy_prefix() -> CommitmentPrefix {
+        CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap()
+    }
+
+    fn dummy_path() -> ChannelEndsPath {
+        ChannelEndsPath(PortId::default(), ChannelId::default())
+    }
+
+    fn iavl_leaf(key: &[u8], value: &[u8]) -> ExistenceProof {
+        // height 0, size 1 and version 1
+        let leaf = LeafOp {
+            prefix: vec![0, 2, 2],
+            ..iavl_spec().leaf_spec.unwrap()
+        };
+        ExistenceProof {
+            key: key.to_vec(),
+            value: value.to_vec(),
+            leaf: Some(leaf),
+            path: vec![],
+        }
+    }
+
+    /// Places two IAVL leaves under a common inner node, the root of the IBC store.
+    fn iavl_siblings(
+        mut left: ExistenceProof,
+        mut right: ExistenceProof,
+    ) -> (ExistenceProof, ExistenceProof) {
+        let hash = iavl_spec().inner_spec.unwrap().hash;
+        let left_hash = calculate_existence_root(&left).unwrap();
+        let right_hash = calculate_existence_root(&right).unwrap();
+
+        // height 1, size 2 and version 1, followed by the length prefixed hashes of the children
+        let mut left_prefix = vec![2, 4, 2, 32];
+        let mut left_suffix = vec![32];
+        left_suffix.extend_from_slice(&right_hash);
+        left.path.push(InnerOp {
+            hash,
+            prefix: left_prefix.clone(),
+            suffix: left_suffix,
+        });
+
+        left_prefix.extend_from_slice(&left_hash);
+        left_prefix.push(32);
+        right.path.push(InnerOp {
+            hash,
+            prefix: left_prefix,
+            suffix: vec![],
+        });
+
+        (left, right)
+    }
+
+    /// Commits the root of the IBC store under the `ibc` key of a multistore holding no other
+    /// store, returning the proof of that commitment along with the root of the multistore.
+    fn multistore(store_root: Vec<u8>) -> (CommitmentProof, CommitmentRoot) {
+        let proof = ExistenceProof {
+            key: b"ibc".to_vec(),
+            value: store_root,
+            leaf: tendermint_spec().leaf_spec,
+            path: vec![],
+        };
+        let root = calculate_existence_root(&proof).unwrap();
+        let proof = CommitmentProof {
+            proof: Some(Proof::Exist(proof)),
+        };
+        (proof, CommitmentRoot::from_bytes(&root))
+    }
+
+    /// Returns proofs that the `left` and `right` values are stored under their keys, the only
+    /// ones of the IBC store, along with the root they are verified against.
+    fn prove_siblings(
+        (left_key, left_value): (String, Vec<u8>),
+        (right_key, right_value): (String, Vec<u8>),
+    ) -> (MerkleProof, MerkleProof, CommitmentRoot) {
+        let (left, right) = iavl_siblings(
+            iavl_leaf(left_key.as_bytes(), &left_value),
+            iavl_leaf(right_key.as_bytes(), &right_value),
+        );
+        let (store_proof, root) = multistore(calculate_existence_root(&left).unwrap());
+        let [left, right] = [left, right].map(|proof| {
+            let proof = CommitmentProof {
+                proof: Some(Proof::Exist(proof)),
+            };
+            MerkleProof {
+                proofs: vec![proof, store_proof.clone()],
+            }
+        });
+        (left, right, root)
+    }
+
+    /// Returns a proof that `value` is stored at `path`, along with the root it is verified
+    /// against.
+    fn prove_membership(path: impl Into<Path>, value: Vec<u8>) -> (MerkleProof, CommitmentRoot) {
+        let key = path.into().to_string();
+        let sibling = format!("{}/", key);
+        let (proof, _, root) = prove_siblings((key, value), (sibling, b"sibling".to_vec()));
+        (proof, root)
+    }
+
+    /// Returns a proof that no value is stored at `path`, along with the root it is verified
+    /// against.
+    fn prove_non_membership(path: impl Into<Path>) -> (MerkleProof, CommitmentRoot) {
+        let key = path.into().to_string();
+        let (left_key, right_key) = (&key[..key.len() - 1], format!("{}/", key));
+        let (left, right) = iavl_siblings(
+            iavl_leaf(left_key.as_bytes(), b"left"),
+            iavl_leaf(right_key.as_bytes(), b"right"),
+        );
+        let (store_proof, root) = multistore(calculate_existence_root(&left).unwrap());
+        let proof = CommitmentProof {
+            proof: Some(Proof::Nonexist(NonExistenceProof {
+                key: key.into_bytes(),
+                left: Some(left),
+                right: Some(right),
+            })),
+        };
+        let proofs = vec![proof, store_proof];
+        (MerkleProof { proofs }, root)
+    }
+
+    fn mock_header() -> MockHeader {
+        MockHeader::new(Height::new(0, 10).unwrap())
+    }
+
+    #[test]
+    fn rejects_empty_proof() {
+        let specs = ProofSpecs::cosmos();
+        let prefix = dummy_prefix();
+        let root = CommitmentRoot::from_bytes(&[1; 32]);
+        let verifier = ProofVerifier::new(&specs, &prefix, &root);
+
+        let proof = MerkleProof { proofs: vec![] };
+        let res = verifier.verify_membership(&proof, dummy_path(), vec![1]);
+        assert!(matches!(
+            res.unwrap_err().detail(),
+            ErrorDetail::EmptyMerkleProof(_)
+        ));
+    }
+
+    #[test]
+    fn rejects_proof_not_matching_specs() {
+        let specs = ProofSpecs::cosmos();
+        let prefix = dummy_prefix();
+        let root = CommitmentRoot::from_bytes(&[1; 32]);
+        let verifier = ProofVerifier::new(&specs, &prefix, &root);
+
+        // The Cosmos SDK specs expect two proofs, one for the IBC store and one for the app hash.
+        let proof = MerkleProof::from(get_dummy_merkle_proof());
+        let res = verifier.verify_non_membership(&proof, dummy_path());
+        assert!(matches!(
+            res.unwrap_err().detail(),
+            ErrorDetail::NumberOfSpecsMismatch(_)
+        ));
+    }
+
+    #[test]
+    fn verifies_client_state() {
+        let client_id = ClientId::default();
+        let client_state = AnyClientState::from(MockClientState::new(mock_header()));
+        let (proof, root) = prove_membership(
+            ClientStatePath(client_id.clone()),
+            client_state.encode_vec().unwrap(),
+        );
+
+        let (specs, prefix) = (ProofSpecs::cosmos(), dummy_prefix());
+        let verifier = ProofVerifier::new(&specs, &prefix, &root);
+        assert!(verifier
+            .verify_client_state(&proof, &client_id, &client_state)
+            .is_ok());
+    }
+
+    #[test]
+    fn verifies_consensus_state() {
+        let client_id = ClientId::default();
+        let height = mock_header().height();
+        let consensus_state = AnyConsensusState::from(MockConsensusState::new(mock_header()));
+        let path = ClientConsensusStatePath {
+            client_id: client_id.clone(),
+            epoch: height.revision_number(),
+            height: height.revision_height(),
+        };
+        let (proof, root) = prove_membership(path, consensus_state.encode_vec().unwrap());
+
+        let (specs, prefix) = (ProofSpecs::cosmos(), dummy_prefix());
+        let verifier = ProofVerifier::new(&specs, &prefix, &root);
+        assert!(verifier
+            .verify_consensus_state(&proof, &client_id, height, &consensus_state)
+            .is_ok());
+    }
+
+    #[test]
+    fn verifies_connection_end() {
+        let connection_id = ConnectionId::default();
+        let connection_end = ConnectionEnd::default();
+        let (proof, root) = prove_membership(
+            ConnectionsPath(connection_id.clone()),
+            connection_end.encode_vec().unwrap(),
+        );
+
+        let (specs, prefix) = (ProofSpecs::cosmos(), dummy_prefix());
+        let verifier = ProofVerifier::new(&specs, &prefix, &root);
+        assert!(verifier
+            .verify_connection_end(&proof, &connection_id, &connection_end)
+            .is_ok());
+    }
+
+    #[test]
+    fn verifies_channel_end() {
+        let (port_id, channel_id) = (PortId::default(), ChannelId::default());
+        let channel_end = ChannelEnd::default();
+        let (proof, root) = prove_membership(dummy_path(), channel_end.encode_vec().unwrap());
+
+        let (specs, prefix) = (ProofSpecs::cosmos(), dummy_prefix());
+        let verifier = ProofVerifier::new(&specs, &prefix, &root);
+        assert!(verifier
+            .verify_channel_end(&proof, &port_id, &channel_id, &channel_end)
+            .is_ok());
+    }
+
+    #[test]
+    fn verifies_channel_upgrade() {
+        let (port_id, channel_id) = (PortId::default(), ChannelId::default());
+        let upgrade = Upgrade::try_from(get_dummy_raw_upgrade()).unwrap();
+        let (proof, root) = prove_membership(
+            ChannelUpgradesPath(port_id.clone(), channel_id.clone()),
+            upgrade.encode_vec().unwrap(),
+        );
+
+        let (specs, prefix) = (ProofSpecs::cosmos(), dummy_prefix());
+        let verifier = ProofVerifier::new(&specs, &prefix, &root);
+        assert!(verifier
+            .verify_channel_upgrade(&proof, &port_id, &channel_id, &upgrade)
+            .is_ok());
+    }
+
+    #[test]
+    fn verifies_channel_upgrade_error() {
+        let (port_id, channel_id) = (PortId::default(), ChannelId::default());
+        let error_receipt = ErrorReceipt::new(Sequence::from(1), "upgrade failed");
+        let (proof, root) = prove_membership(
+            ChannelUpgradeErrorsPath(port_id.clone(), channel_id.clone()),
+            error_receipt.encode_vec().unwrap(),
+        );
+
+        let (specs, prefix) = (ProofSpecs::cosmos(), dummy_prefix());
+        let verifier = ProofVerifier::new(&specs, &prefix, &root);
+        assert!(verifier
+            .verify_channel_upgrade_error(&proof, &port_id, &channel_id, &error_receipt)
+            .is_ok());
+    }
+
+    #[test]
+    fn verifies_packet_commitment() {
+        let (port_id, channel_id) = (PortId::default(), ChannelId::default());
+        let sequence = Sequence::from(1);
+        let commitment = PacketCommitment::from(vec![1; 32]);
+        let path = CommitmentsPath {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence,
+        };
+        let (proof, root) = prove_membership(path, commitment.clone().into_vec());
+
+        let (specs, prefix) = (ProofSpecs::cosmos(), dummy_prefix());
+        let verifier = ProofVerifier::new(&specs, &prefix, &root);
+        assert!(verifier
+            .verify_packet_commitment(&proof, &port_id, &channel_id, sequence, commitment)
+            .is_ok());
+
+        // the proof does not prove any other commitment
+        let other = PacketCommitment::from(vec![2; 32]);
+        let res = verifier.verify_packet_commitment(&proof, &port_id, &channel_id, sequence, other);
+        assert!(matches!(
+            res.unwrap_err().detail(),
+            ErrorDetail::VerificationFailure(_)
+        ));
+    }
+
+    #[test]
+    fn verifies_packet_acknowledgement() {
+        let (port_id, channel_id) = (PortId::default(), ChannelId::default());
+        let sequence = Sequence::from(1);
+        let ack_commitment = AcknowledgementCommitment::from(vec![1; 32]);
+        let path = AcksPath {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence,
+        };
+        let (proof, root) = prove_membership(path, ack_commitment.clone().into_vec());
+
+        let (specs, prefix) = (ProofSpecs::cosmos(), dummy_prefix());
+        let verifier = ProofVerifier::new(&specs, &prefix, &root);
+        assert!(verifier
+            .verify_packet_acknowledgement(&proof, &port_id, &channel_id, sequence, ack_commitment)
+            .is_ok());
+    }
+
+    #[test]
+    fn verifies_packet_receipt() {
+        let (port_id, channel_id) = (PortId::default(), ChannelId::default());
+        let sequence = Sequence::from(1);
+        let path = ReceiptsPath {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence,
+        };
+        let (proof, root) = prove_membership(path, Receipt::Ok.as_bytes().to_vec());
+
+        let (specs, prefix) = (ProofSpecs::cosmos(), dummy_prefix());
+        let verifier = ProofVerifier::new(&specs, &prefix, &root);
+        assert!(verifier
+            .verify_packet_receipt(&proof, &port_id, &channel_id, sequence, Receipt::Ok)
+            .is_ok());
+    }
+
+    #[test]
+    fn verifies_packet_receipt_absence() {
+        let (port_id, channel_id) = (PortId::default(), ChannelId::default());
+        let sequence = Sequence::from(2);
+        let path = ReceiptsPath {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence,
+        };
+        let (proof, root) = prove_non_membership(path);
+
+        let (specs, prefix) = (ProofSpecs::cosmos(), dummy_prefix());
+        let verifier = ProofVerifier::new(&specs, &prefix, &root);
+        assert!(verifier
+            .verify_packet_receipt_absence(&proof, &port_id, &channel_id, sequence)
+            .is_ok());
+
+        // the proof does not prove the absence of any other receipt
+        let res = verifier.verify_packet_receipt_absence(
+            &proof,
+            &port_id,
+            &channel_id,
+            Sequence::from(3),
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn verifies_next_sequence_recv() {
+        let (port_id, channel_id) = (PortId::default(), ChannelId::default());
+        let sequence = Sequence::from(5);
+        let mut seq_bytes = Vec::new();
+        u64::from(sequence).encode(&mut seq_bytes).unwrap();
+        let (proof, root) =
+            prove_membership(SeqRecvsPath(port_id.clone(), channel_id.clone()), seq_bytes);
+
+        let (specs, prefix) = (ProofSpecs::cosmos(), dummy_prefix());
+        let verifier = ProofVerifier::new(&specs, &prefix, &root);
+        assert!(verifier
+            .verify_next_sequence_recv(&proof, &port_id, &channel_id, sequence)
+            .is_ok());
+    }
+
+    #[test]
+    fn verifies_compressed_batch_of_packet_commitments() {
+        let (port_id, channel_id) = (PortId::default(), ChannelId::default());
+        let commitments: Vec<_> = [1, 2]
+            .into_iter()
+            .map(|seq| {
+                (
+                    Sequence::from(seq),
+                    PacketCommitment::from(vec![seq as u8; 32]),
+                )
+            })
+            .collect();
+        let [left, right] = [0, 1].map(|i| {
+            let (sequence, commitment) = commitments[i].clone();
+            let path = Path::from(CommitmentsPath {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+                sequence,
+            });
+            (path.to_string(), commitment.into_vec())
+        });
+        let (left, right, root) = prove_siblings(left, right);
+
+        let proof = MerkleProof::batch(vec![left, right]).unwrap();
+        assert!(matches!(proof.proofs[0].proof, Some(Proof::Compressed(_))));
+
+        let (specs, prefix) = (ProofSpecs::cosmos(), dummy_prefix());
+        let verifier = ProofVerifier::new(&specs, &prefix, &root);
+        assert!(verifier
+            .verify_packet_commitments(&proof, &port_id, &channel_id, commitments.clone())
+            .is_ok());
+        assert!(verifier
+            .verify_packet_commitment(
+                &proof,
+                &port_id,
+                &channel_id,
+                Sequence::from(2),
+                commitments[1].1.clone()
+            )
+            .is_ok());
+
+        // the proof does not prove any other commitment
+        let other = vec![(Sequence::from(2), PacketCommitment::from(vec![3; 32]))];
+        let res = verifier.verify_packet_commitments(&proof, &port_id, &channel_id, other);
+        assert!(matches!(
+            res.unwrap_err().detail(),
+            ErrorDetail::VerificationFailure(_)
+        ));
+    }
+}
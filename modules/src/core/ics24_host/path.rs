@@ -44,6 +44,8 @@ pub enum Path {
     Acks(AcksPath),
     Receipts(ReceiptsPath),
     Upgrade(ClientUpgradePath),
+    ChannelUpgrades(ChannelUpgradesPath),
+    ChannelUpgradeErrors(ChannelUpgradeErrorsPath),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
@@ -134,6 +136,14 @@ pub struct ReceiptsPath {
     pub sequence: Sequence,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+#[display(fmt = "channelUpgrades/upgrades/ports/{}/channels/{}", _0, _1)]
+pub struct ChannelUpgradesPath(pub PortId, pub ChannelId);
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+#[display(fmt = "channelUpgrades/upgradeError/ports/{}/channels/{}", _0, _1)]
+pub struct ChannelUpgradeErrorsPath(pub PortId, pub ChannelId);
+
 /// Paths that are specific for client upgrades.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
 pub enum ClientUpgradePath {
@@ -193,6 +203,7 @@ impl FromStr for Path {
             .or_else(|| parse_acks(&components))
             .or_else(|| parse_receipts(&components))
             .or_else(|| parse_upgrades(&components))
+            .or_else(|| parse_channel_upgrades(&components))
             .ok_or_else(|| PathError::parse_failure(s.to_string()))
     }
 }
@@ -570,6 +581,37 @@ fn parse_receipts(components: &[&str]) -> Option<Path> {
     )
 }
 
+fn parse_channel_upgrades(components: &[&str]) -> Option<Path> {
+    if components.len() != 6 {
+        return None;
+    }
+
+    if components[0] != "channelUpgrades" {
+        return None;
+    }
+
+    let port = parse_ports(&components[2..=3]);
+    let channel = parse_channels(&components[4..=5]);
+
+    let port_id = if let Some(Path::Ports(PortsPath(port_id))) = port {
+        port_id
+    } else {
+        return None;
+    };
+
+    let channel_id = if let Some(SubPath::Channels(channel_id)) = channel {
+        channel_id
+    } else {
+        return None;
+    };
+
+    match components[1] {
+        "upgrades" => Some(ChannelUpgradesPath(port_id, channel_id).into()),
+        "upgradeError" => Some(ChannelUpgradeErrorsPath(port_id, channel_id).into()),
+        _ => None,
+    }
+}
+
 fn parse_upgrades(components: &[&str]) -> Option<Path> {
     if components.len() != 3 {
         return None;
@@ -805,6 +847,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_channel_upgrades_fn() {
+        let path = "channelUpgrades/upgrades/ports/defaultPort/channels/channel-0";
+        let components: Vec<&str> = path.split('/').collect();
+
+        assert_eq!(
+            parse_channel_upgrades(&components),
+            Some(Path::ChannelUpgrades(ChannelUpgradesPath(
+                PortId::default(),
+                ChannelId::default()
+            ))),
+        );
+
+        let path = "channelUpgrades/upgradeError/ports/defaultPort/channels/channel-0";
+        let components: Vec<&str> = path.split('/').collect();
+
+        assert_eq!(
+            parse_channel_upgrades(&components),
+            Some(Path::ChannelUpgradeErrors(ChannelUpgradeErrorsPath(
+                PortId::default(),
+                ChannelId::default()
+            ))),
+        );
+
+        let path = "channelUpgrades/counterpartyUpgrade/ports/defaultPort/channels/channel-0";
+        let components: Vec<&str> = path.split('/').collect();
+
+        assert_eq!(parse_channel_upgrades(&components), None);
+    }
+
+    #[test]
+    fn channel_upgrades_path_parses() {
+        let path = "channelUpgrades/upgradeError/ports/defaultPort/channels/channel-0";
+        let path = Path::from_str(path);
+
+        assert!(path.is_ok());
+        assert_eq!(
+            path.unwrap(),
+            Path::ChannelUpgradeErrors(ChannelUpgradeErrorsPath(
+                PortId::default(),
+                ChannelId::default()
+            )),
+        );
+    }
+
     #[test]
     fn test_parse_seqs_fn() {
         let path = "nextSequenceSend/ports/defaultPort/channels/channel-0";
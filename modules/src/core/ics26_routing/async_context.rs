@@ -0,0 +1,431 @@
+//! Async variants of the host context traits, for host chains whose state is only accessible
+//! asynchronously (e.g., a store behind an async database client).
+//!
+//! The reader traits mirror `ClientReader`, `ConnectionReader`, `ChannelReader` and `PortReader`,
+//! except that values which the host does not store are returned as `None` instead of an error.
+//! The keeper traits mirror the primitive (i.e., not `store_*_result`) methods of `ClientKeeper`,
+//! `ConnectionKeeper` and `ChannelKeeper`.
+//!
+//! Messages are processed against an `AsyncIcs26Context` with
+//! [`deliver_async`](crate::core::ics26_routing::handler::deliver_async), which runs the same
+//! validation logic as the synchronous [`deliver`](crate::core::ics26_routing::handler::deliver).
+
+use crate::prelude::*;
+
+use async_trait::async_trait;
+use core::time::Duration;
+
+use crate::core::ics02_client::client_consensus::AnyConsensusState;
+use crate::core::ics02_client::client_state::AnyClientState;
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::error::Error as Ics02Error;
+use crate::core::ics03_connection::connection::ConnectionEnd;
+use crate::core::ics03_connection::error::Error as Ics03Error;
+use crate::core::ics03_connection::version::{get_compatible_versions, pick_version, Version};
+use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
+use crate::core::ics04_channel::error::Error as Ics04Error;
+use crate::core::ics04_channel::packet::{Receipt, Sequence};
+use crate::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
+use crate::core::ics05_port::error::Error as Ics05Error;
+use crate::core::ics23_commitment::commitment::CommitmentPrefix;
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::core::ics26_routing::context::{ModuleId, Router};
+use crate::timestamp::Timestamp;
+use crate::Height;
+
+/// Async variant of `ClientReader`.
+#[async_trait]
+pub trait AsyncClientReader {
+    async fn client_type(&self, client_id: &ClientId) -> Result<Option<ClientType>, Ics02Error>;
+
+    async fn client_state(
+        &self,
+        client_id: &ClientId,
+    ) -> Result<Option<AnyClientState>, Ics02Error>;
+
+    /// Retrieve the consensus state for the given client ID at the specified height.
+    async fn consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<Option<AnyConsensusState>, Ics02Error>;
+
+    /// Search for the lowest consensus state higher than `height`.
+    async fn next_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<Option<AnyConsensusState>, Ics02Error>;
+
+    /// Search for the highest consensus state lower than `height`.
+    async fn prev_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<Option<AnyConsensusState>, Ics02Error>;
+
+    /// Returns the current height of the local chain.
+    async fn host_height(&self) -> Height;
+
+    /// Returns the current timestamp of the local chain.
+    async fn host_timestamp(&self) -> Timestamp {
+        let pending_consensus_state = self
+            .pending_host_consensus_state()
+            .await
+            .expect("host must have pending consensus state");
+        pending_consensus_state.timestamp()
+    }
+
+    /// Returns the `ConsensusState` of the host (local) chain at a specific height.
+    async fn host_consensus_state(
+        &self,
+        height: Height,
+    ) -> Result<Option<AnyConsensusState>, Ics02Error>;
+
+    /// Returns the pending `ConsensusState` of the host (local) chain.
+    async fn pending_host_consensus_state(&self) -> Result<AnyConsensusState, Ics02Error>;
+
+    /// Returns a natural number, counting how many clients have been created thus far.
+    async fn client_counter(&self) -> Result<u64, Ics02Error>;
+}
+
+/// Async variant of `ClientKeeper`.
+#[async_trait]
+pub trait AsyncClientKeeper {
+    async fn store_client_type(
+        &mut self,
+        client_id: ClientId,
+        client_type: ClientType,
+    ) -> Result<(), Ics02Error>;
+
+    async fn store_client_state(
+        &mut self,
+        client_id: ClientId,
+        client_state: AnyClientState,
+    ) -> Result<(), Ics02Error>;
+
+    async fn store_consensus_state(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        consensus_state: AnyConsensusState,
+    ) -> Result<(), Ics02Error>;
+
+    async fn increase_client_counter(&mut self);
+
+    async fn store_update_time(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        timestamp: Timestamp,
+    ) -> Result<(), Ics02Error>;
+
+    async fn store_update_height(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        host_height: Height,
+    ) -> Result<(), Ics02Error>;
+}
+
+/// Async variant of `ConnectionReader`. The client states and consensus states are read through
+/// `AsyncClientReader`.
+#[async_trait]
+pub trait AsyncConnectionReader {
+    /// Returns the ConnectionEnd for the given identifier `conn_id`.
+    async fn connection_end(
+        &self,
+        conn_id: &ConnectionId,
+    ) -> Result<Option<ConnectionEnd>, Ics03Error>;
+
+    /// Returns the oldest height available on the local chain.
+    async fn host_oldest_height(&self) -> Height;
+
+    /// Returns the prefix that the local chain uses in the KV store.
+    async fn commitment_prefix(&self) -> CommitmentPrefix;
+
+    /// Returns the list of all possible versions that the connection handshake protocol supports.
+    fn get_compatible_versions(&self) -> Vec<Version> {
+        get_compatible_versions()
+    }
+
+    /// Returns one version out of the supplied list of versions, which the connection handshake
+    /// protocol prefers.
+    fn pick_version(
+        &self,
+        supported_versions: Vec<Version>,
+        counterparty_candidate_versions: Vec<Version>,
+    ) -> Result<Version, Ics03Error> {
+        pick_version(supported_versions, counterparty_candidate_versions)
+    }
+
+    /// Returns a counter on how many connections have been created thus far.
+    async fn connection_counter(&self) -> Result<u64, Ics03Error>;
+}
+
+/// Async variant of `ConnectionKeeper`.
+#[async_trait]
+pub trait AsyncConnectionKeeper {
+    async fn store_connection(
+        &mut self,
+        connection_id: ConnectionId,
+        connection_end: &ConnectionEnd,
+    ) -> Result<(), Ics03Error>;
+
+    async fn store_connection_to_client(
+        &mut self,
+        connection_id: ConnectionId,
+        client_id: &ClientId,
+    ) -> Result<(), Ics03Error>;
+
+    async fn increase_connection_counter(&mut self);
+}
+
+/// Async variant of `ChannelReader`. The connection ends, client states and consensus states are
+/// read through `AsyncConnectionReader` and `AsyncClientReader`.
+#[async_trait]
+pub trait AsyncChannelReader {
+    /// Returns the ChannelEnd for the given `port_id` and `chan_id`.
+    async fn channel_end(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Option<ChannelEnd>, Ics04Error>;
+
+    async fn connection_channels(
+        &self,
+        cid: &ConnectionId,
+    ) -> Result<Option<Vec<(PortId, ChannelId)>>, Ics04Error>;
+
+    async fn get_next_sequence_send(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Option<Sequence>, Ics04Error>;
+
+    async fn get_next_sequence_recv(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Option<Sequence>, Ics04Error>;
+
+    async fn get_next_sequence_ack(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Option<Sequence>, Ics04Error>;
+
+    async fn get_packet_commitment(
+        &self,
+        key: &(PortId, ChannelId, Sequence),
+    ) -> Result<Option<PacketCommitment>, Ics04Error>;
+
+    async fn get_packet_receipt(
+        &self,
+        key: &(PortId, ChannelId, Sequence),
+    ) -> Result<Option<Receipt>, Ics04Error>;
+
+    async fn get_packet_acknowledgement(
+        &self,
+        key: &(PortId, ChannelId, Sequence),
+    ) -> Result<Option<AcknowledgementCommitment>, Ics04Error>;
+
+    /// Returns `true` if packets sent on the given channel are still waiting for an
+    /// acknowledgement or a timeout, not counting the packet with sequence `except`.
+    async fn has_inflight_packets(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+        except: Option<Sequence>,
+    ) -> Result<bool, Ics04Error>;
+
+    /// Returns the sequence of the first packet received on the given channel end whose receipt
+    /// and acknowledgement were not pruned, which is 1 if no packet was pruned yet.
+    /// Hosts which support pruning must override this, along with
+    /// [`AsyncChannelKeeper::delete_packet_receipt`] and
+    /// [`AsyncChannelKeeper::store_pruning_sequence`].
+    async fn pruning_sequence(
+        &self,
+        _port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Sequence, Ics04Error> {
+        Ok(Sequence::from(1))
+    }
+
+    /// Returns the upgrade in progress on the given channel end.
+    async fn upgrade(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Option<Upgrade>, Ics04Error>;
+
+    /// Returns the upgrade of the counterparty channel end.
+    async fn counterparty_upgrade(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Option<Upgrade>, Ics04Error>;
+
+    /// Returns the receipt of the last aborted upgrade of the given channel end.
+    async fn upgrade_error_receipt(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Option<ErrorReceipt>, Ics04Error>;
+
+    /// Returns the time the counterparty is given to complete its side of an upgrade,
+    /// from the moment this chain starts flushing.
+    fn upgrade_timeout(&self) -> Duration {
+        Duration::from_secs(600)
+    }
+
+    /// A hashing function for packet commitments
+    fn hash(&self, value: Vec<u8>) -> Vec<u8>;
+
+    /// Returns the time when the client state for the given [`ClientId`] was updated with a header for the given [`Height`]
+    async fn client_update_time(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<Option<Timestamp>, Ics04Error>;
+
+    /// Returns the height when the client state for the given [`ClientId`] was updated with a header for the given [`Height`]
+    async fn client_update_height(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<Option<Height>, Ics04Error>;
+
+    /// Returns a counter on the number of channel ids have been created thus far.
+    async fn channel_counter(&self) -> Result<u64, Ics04Error>;
+
+    /// Returns the maximum expected time per block
+    async fn max_expected_time_per_block(&self) -> Duration;
+}
+
+/// Async variant of `ChannelKeeper`.
+#[async_trait]
+pub trait AsyncChannelKeeper {
+    async fn store_packet_commitment(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+        commitment: PacketCommitment,
+    ) -> Result<(), Ics04Error>;
+
+    async fn delete_packet_commitment(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+    ) -> Result<(), Ics04Error>;
+
+    async fn store_packet_receipt(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+        receipt: Receipt,
+    ) -> Result<(), Ics04Error>;
+
+    async fn store_packet_acknowledgement(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+        ack_commitment: AcknowledgementCommitment,
+    ) -> Result<(), Ics04Error>;
+
+    async fn delete_packet_acknowledgement(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+    ) -> Result<(), Ics04Error>;
+
+    async fn delete_packet_receipt(
+        &mut self,
+        _key: (PortId, ChannelId, Sequence),
+    ) -> Result<(), Ics04Error> {
+        Err(Ics04Error::packet_pruning_not_supported())
+    }
+
+    async fn store_pruning_sequence(
+        &mut self,
+        _port_channel_id: (PortId, ChannelId),
+        _seq: Sequence,
+    ) -> Result<(), Ics04Error> {
+        Err(Ics04Error::packet_pruning_not_supported())
+    }
+
+    async fn store_connection_channels(
+        &mut self,
+        conn_id: ConnectionId,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<(), Ics04Error>;
+
+    async fn store_channel(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        channel_end: &ChannelEnd,
+    ) -> Result<(), Ics04Error>;
+
+    async fn store_next_sequence_send(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        seq: Sequence,
+    ) -> Result<(), Ics04Error>;
+
+    async fn store_next_sequence_recv(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        seq: Sequence,
+    ) -> Result<(), Ics04Error>;
+
+    async fn store_next_sequence_ack(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        seq: Sequence,
+    ) -> Result<(), Ics04Error>;
+
+    async fn store_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        upgrade: Upgrade,
+    ) -> Result<(), Ics04Error>;
+
+    async fn delete_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+    ) -> Result<(), Ics04Error>;
+
+    async fn store_counterparty_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        upgrade: Upgrade,
+    ) -> Result<(), Ics04Error>;
+
+    async fn delete_counterparty_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+    ) -> Result<(), Ics04Error>;
+
+    async fn store_upgrade_error_receipt(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        error_receipt: ErrorReceipt,
+    ) -> Result<(), Ics04Error>;
+
+    async fn increase_channel_counter(&mut self);
+}
+
+/// Async variant of `PortReader`.
+#[async_trait]
+pub trait AsyncPortReader {
+    /// Return the module_id associated with a given port_id
+    async fn lookup_module_by_port(&self, port_id: &PortId)
+        -> Result<Option<ModuleId>, Ics05Error>;
+}
+
+/// Async variant of `Ics26Context`. The module callbacks are synchronous, and the router is
+/// accessed as in the synchronous context.
+pub trait AsyncIcs26Context:
+    AsyncClientReader
+    + AsyncClientKeeper
+    + AsyncConnectionReader
+    + AsyncConnectionKeeper
+    + AsyncChannelReader
+    + AsyncChannelKeeper
+    + AsyncPortReader
+    + Send
+    + Sync
+{
+    type Router: Router;
+
+    fn router(&self) -> &Self::Router;
+
+    fn router_mut(&mut self) -> &mut Self::Router;
+}
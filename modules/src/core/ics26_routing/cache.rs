@@ -0,0 +1,1302 @@
+//! A synchronous view over an `AsyncIcs26Context`, which lets the synchronous handlers process
+//! messages against a host whose state is only accessible asynchronously.
+//!
+//! The handlers only ever read the context while validating and processing a message, and only
+//! write to it once that succeeded. The [`ContextCache`] answers the reads from the values it has
+//! fetched so far, and records the reads it could not answer; the caller fetches these with
+//! [`ContextCache::fetch_misses`] and processes the message again, until it is processed without
+//! any miss. The writes are recorded, and applied to the host with [`ContextCache::flush`].
+//!
+//! Once processed, the message is executed over the cache: the module callbacks are called, and
+//! the results are written. Reads made at that point cannot be retried, since the callbacks
+//! already modified the state of the modules, so any value read then must have been read while
+//! processing the message too; otherwise, [`ContextCache::flush`] fails without writing anything.
+
+use crate::prelude::*;
+
+use alloc::collections::BTreeMap;
+use core::cell::RefCell;
+use core::time::Duration;
+
+use crate::core::ics02_client::client_consensus::AnyConsensusState;
+use crate::core::ics02_client::client_state::AnyClientState;
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::context::{ClientKeeper, ClientReader};
+use crate::core::ics02_client::error::Error as Ics02Error;
+use crate::core::ics03_connection::connection::ConnectionEnd;
+use crate::core::ics03_connection::context::{ConnectionKeeper, ConnectionReader};
+use crate::core::ics03_connection::error::Error as Ics03Error;
+use crate::core::ics03_connection::version::Version;
+use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
+use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
+use crate::core::ics04_channel::error::Error as Ics04Error;
+use crate::core::ics04_channel::packet::{Receipt, Sequence};
+use crate::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
+use crate::core::ics05_port::context::PortReader;
+use crate::core::ics05_port::error::Error as Ics05Error;
+use crate::core::ics23_commitment::commitment::CommitmentPrefix;
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::core::ics26_routing::async_context::AsyncIcs26Context;
+use crate::core::ics26_routing::context::{Ics26Context, ModuleId};
+use crate::core::ics26_routing::error::Error;
+use crate::timestamp::Timestamp;
+use crate::Height;
+
+type PortChannelId = (PortId, ChannelId);
+type PacketKey = (PortId, ChannelId, Sequence);
+
+/// A read of the context which the cache could not answer.
+#[derive(Clone, Debug, PartialEq)]
+enum Query {
+    ClientType(ClientId),
+    ClientState(ClientId),
+    ConsensusState(ClientId, Height),
+    NextConsensusState(ClientId, Height),
+    PrevConsensusState(ClientId, Height),
+    HostConsensusState(Height),
+    PendingHostConsensusState,
+    ClientCounter,
+    ConnectionEnd(ConnectionId),
+    ConnectionCounter,
+    ChannelEnd(PortChannelId),
+    ConnectionChannels(ConnectionId),
+    NextSequenceSend(PortChannelId),
+    NextSequenceRecv(PortChannelId),
+    NextSequenceAck(PortChannelId),
+    PacketCommitment(PacketKey),
+    PacketReceipt(PacketKey),
+    PacketAcknowledgement(PacketKey),
+    InflightPackets(PortChannelId, Option<Sequence>),
+    PruningSequence(PortChannelId),
+    Upgrade(PortChannelId),
+    CounterpartyUpgrade(PortChannelId),
+    UpgradeErrorReceipt(PortChannelId),
+    ClientUpdateTime(ClientId, Height),
+    ClientUpdateHeight(ClientId, Height),
+    ChannelCounter,
+    ModuleByPort(PortId),
+}
+
+/// A write to the context, applied to the host once the message was processed.
+enum Write {
+    ClientType(ClientId, ClientType),
+    ClientState(ClientId, AnyClientState),
+    ConsensusState(ClientId, Height, AnyConsensusState),
+    IncreaseClientCounter,
+    UpdateTime(ClientId, Height, Timestamp),
+    UpdateHeight(ClientId, Height, Height),
+    Connection(ConnectionId, ConnectionEnd),
+    ConnectionToClient(ConnectionId, ClientId),
+    IncreaseConnectionCounter,
+    PacketCommitment(PacketKey, PacketCommitment),
+    DeletePacketCommitment(PacketKey),
+    PacketReceipt(PacketKey, Receipt),
+    PacketAcknowledgement(PacketKey, AcknowledgementCommitment),
+    DeletePacketAcknowledgement(PacketKey),
+    DeletePacketReceipt(PacketKey),
+    PruningSequence(PortChannelId, Sequence),
+    ConnectionChannels(ConnectionId, PortChannelId),
+    Channel(PortChannelId, ChannelEnd),
+    NextSequenceSend(PortChannelId, Sequence),
+    NextSequenceRecv(PortChannelId, Sequence),
+    NextSequenceAck(PortChannelId, Sequence),
+    Upgrade(PortChannelId, Upgrade),
+    DeleteUpgrade(PortChannelId),
+    CounterpartyUpgrade(PortChannelId, Upgrade),
+    DeleteCounterpartyUpgrade(PortChannelId),
+    UpgradeErrorReceipt(PortChannelId, ErrorReceipt),
+    IncreaseChannelCounter,
+}
+
+/// Caches the reads and records the writes of the handlers to an `AsyncIcs26Context`.
+///
+/// Keyed values are cached as `None` when the host does not store them, in which case the cache
+/// answers with the same "not found" errors as the synchronous contexts.
+pub(crate) struct ContextCache<'a, Ctx> {
+    host: &'a mut Ctx,
+
+    host_height: Height,
+    host_timestamp: Timestamp,
+    host_oldest_height: Height,
+    commitment_prefix: CommitmentPrefix,
+    max_expected_time_per_block: Duration,
+
+    client_types: BTreeMap<ClientId, Option<ClientType>>,
+    client_states: BTreeMap<ClientId, Option<AnyClientState>>,
+    consensus_states: BTreeMap<(ClientId, Height), Option<AnyConsensusState>>,
+    next_consensus_states: BTreeMap<(ClientId, Height), Option<AnyConsensusState>>,
+    prev_consensus_states: BTreeMap<(ClientId, Height), Option<AnyConsensusState>>,
+    host_consensus_states: BTreeMap<Height, Option<AnyConsensusState>>,
+    pending_host_consensus_state: Option<AnyConsensusState>,
+    client_counter: Option<u64>,
+    connection_ends: BTreeMap<ConnectionId, Option<ConnectionEnd>>,
+    connection_counter: Option<u64>,
+    channel_ends: BTreeMap<PortChannelId, Option<ChannelEnd>>,
+    connection_channels: BTreeMap<ConnectionId, Option<Vec<PortChannelId>>>,
+    next_sequences_send: BTreeMap<PortChannelId, Option<Sequence>>,
+    next_sequences_recv: BTreeMap<PortChannelId, Option<Sequence>>,
+    next_sequences_ack: BTreeMap<PortChannelId, Option<Sequence>>,
+    packet_commitments: BTreeMap<PacketKey, Option<PacketCommitment>>,
+    packet_receipts: BTreeMap<PacketKey, Option<Receipt>>,
+    packet_acknowledgements: BTreeMap<PacketKey, Option<AcknowledgementCommitment>>,
+    inflight_packets: BTreeMap<(PortChannelId, Option<Sequence>), Option<bool>>,
+    pruning_sequences: BTreeMap<PortChannelId, Option<Sequence>>,
+    upgrades: BTreeMap<PortChannelId, Option<Upgrade>>,
+    counterparty_upgrades: BTreeMap<PortChannelId, Option<Upgrade>>,
+    upgrade_error_receipts: BTreeMap<PortChannelId, Option<ErrorReceipt>>,
+    client_update_times: BTreeMap<(ClientId, Height), Option<Timestamp>>,
+    client_update_heights: BTreeMap<(ClientId, Height), Option<Height>>,
+    channel_counter: Option<u64>,
+    port_modules: BTreeMap<PortId, Option<ModuleId>>,
+
+    misses: RefCell<Vec<Query>>,
+    writes: Vec<Write>,
+}
+
+impl<'a, Ctx: AsyncIcs26Context> ContextCache<'a, Ctx> {
+    /// Creates an empty cache over `host`, fetching the parameters of the host chain upfront.
+    pub(crate) async fn new(host: &'a mut Ctx) -> ContextCache<'a, Ctx> {
+        let host_height = host.host_height().await;
+        let host_timestamp = host.host_timestamp().await;
+        let host_oldest_height = host.host_oldest_height().await;
+        let commitment_prefix = host.commitment_prefix().await;
+        let max_expected_time_per_block = host.max_expected_time_per_block().await;
+
+        Self {
+            host,
+            host_height,
+            host_timestamp,
+            host_oldest_height,
+            commitment_prefix,
+            max_expected_time_per_block,
+            client_types: BTreeMap::new(),
+            client_states: BTreeMap::new(),
+            consensus_states: BTreeMap::new(),
+            next_consensus_states: BTreeMap::new(),
+            prev_consensus_states: BTreeMap::new(),
+            host_consensus_states: BTreeMap::new(),
+            pending_host_consensus_state: None,
+            client_counter: None,
+            connection_ends: BTreeMap::new(),
+            connection_counter: None,
+            channel_ends: BTreeMap::new(),
+            connection_channels: BTreeMap::new(),
+            next_sequences_send: BTreeMap::new(),
+            next_sequences_recv: BTreeMap::new(),
+            next_sequences_ack: BTreeMap::new(),
+            packet_commitments: BTreeMap::new(),
+            packet_receipts: BTreeMap::new(),
+            packet_acknowledgements: BTreeMap::new(),
+            inflight_packets: BTreeMap::new(),
+            pruning_sequences: BTreeMap::new(),
+            upgrades: BTreeMap::new(),
+            counterparty_upgrades: BTreeMap::new(),
+            upgrade_error_receipts: BTreeMap::new(),
+            client_update_times: BTreeMap::new(),
+            client_update_heights: BTreeMap::new(),
+            channel_counter: None,
+            port_modules: BTreeMap::new(),
+            misses: RefCell::new(Vec::new()),
+            writes: Vec::new(),
+        }
+    }
+
+    /// Fetches from the host the values which were read but not cached since the last call.
+    /// Returns `false` if there were no such values, i.e., if the reads were all answered.
+    pub(crate) async fn fetch_misses(&mut self) -> Result<bool, Error> {
+        let misses = core::mem::take(self.misses.get_mut());
+        if misses.is_empty() {
+            return Ok(false);
+        }
+
+        for query in misses {
+            self.fetch(query).await?;
+        }
+
+        Ok(true)
+    }
+
+    async fn fetch(&mut self, query: Query) -> Result<(), Error> {
+        let host = &*self.host;
+
+        match query {
+            Query::ClientType(client_id) => {
+                let value = host
+                    .client_type(&client_id)
+                    .await
+                    .map_err(Error::ics02_client)?;
+                self.client_types.insert(client_id, value);
+            }
+            Query::ClientState(client_id) => {
+                let value = host
+                    .client_state(&client_id)
+                    .await
+                    .map_err(Error::ics02_client)?;
+                self.client_states.insert(client_id, value);
+            }
+            Query::ConsensusState(client_id, height) => {
+                let value = host
+                    .consensus_state(&client_id, height)
+                    .await
+                    .map_err(Error::ics02_client)?;
+                self.consensus_states.insert((client_id, height), value);
+            }
+            Query::NextConsensusState(client_id, height) => {
+                let value = host
+                    .next_consensus_state(&client_id, height)
+                    .await
+                    .map_err(Error::ics02_client)?;
+                self.next_consensus_states
+                    .insert((client_id, height), value);
+            }
+            Query::PrevConsensusState(client_id, height) => {
+                let value = host
+                    .prev_consensus_state(&client_id, height)
+                    .await
+                    .map_err(Error::ics02_client)?;
+                self.prev_consensus_states
+                    .insert((client_id, height), value);
+            }
+            Query::HostConsensusState(height) => {
+                let value = host
+                    .host_consensus_state(height)
+                    .await
+                    .map_err(Error::ics02_client)?;
+                self.host_consensus_states.insert(height, value);
+            }
+            Query::PendingHostConsensusState => {
+                let value = host
+                    .pending_host_consensus_state()
+                    .await
+                    .map_err(Error::ics02_client)?;
+                self.pending_host_consensus_state = Some(value);
+            }
+            Query::ClientCounter => {
+                let value = host.client_counter().await.map_err(Error::ics02_client)?;
+                self.client_counter = Some(value);
+            }
+            Query::ConnectionEnd(connection_id) => {
+                let value = host
+                    .connection_end(&connection_id)
+                    .await
+                    .map_err(Error::ics03_connection)?;
+                self.connection_ends.insert(connection_id, value);
+            }
+            Query::ConnectionCounter => {
+                let value = host
+                    .connection_counter()
+                    .await
+                    .map_err(Error::ics03_connection)?;
+                self.connection_counter = Some(value);
+            }
+            Query::ChannelEnd(port_channel_id) => {
+                let value = host
+                    .channel_end(&port_channel_id)
+                    .await
+                    .map_err(Error::ics04_channel)?;
+                self.channel_ends.insert(port_channel_id, value);
+            }
+            Query::ConnectionChannels(connection_id) => {
+                let value = host
+                    .connection_channels(&connection_id)
+                    .await
+                    .map_err(Error::ics04_channel)?;
+                self.connection_channels.insert(connection_id, value);
+            }
+            Query::NextSequenceSend(port_channel_id) => {
+                let value = host
+                    .get_next_sequence_send(&port_channel_id)
+                    .await
+                    .map_err(Error::ics04_channel)?;
+                self.next_sequences_send.insert(port_channel_id, value);
+            }
+            Query::NextSequenceRecv(port_channel_id) => {
+                let value = host
+                    .get_next_sequence_recv(&port_channel_id)
+                    .await
+                    .map_err(Error::ics04_channel)?;
+                self.next_sequences_recv.insert(port_channel_id, value);
+            }
+            Query::NextSequenceAck(port_channel_id) => {
+                let value = host
+                    .get_next_sequence_ack(&port_channel_id)
+                    .await
+                    .map_err(Error::ics04_channel)?;
+                self.next_sequences_ack.insert(port_channel_id, value);
+            }
+            Query::PacketCommitment(key) => {
+                let value = host
+                    .get_packet_commitment(&key)
+                    .await
+                    .map_err(Error::ics04_channel)?;
+                self.packet_commitments.insert(key, value);
+            }
+            Query::PacketReceipt(key) => {
+                let value = host
+                    .get_packet_receipt(&key)
+                    .await
+                    .map_err(Error::ics04_channel)?;
+                self.packet_receipts.insert(key, value);
+            }
+            Query::PacketAcknowledgement(key) => {
+                let value = host
+                    .get_packet_acknowledgement(&key)
+                    .await
+                    .map_err(Error::ics04_channel)?;
+                self.packet_acknowledgements.insert(key, value);
+            }
+            Query::InflightPackets(port_channel_id, except) => {
+                let value = host
+                    .has_inflight_packets(&port_channel_id, except)
+                    .await
+                    .map_err(Error::ics04_channel)?;
+                self.inflight_packets
+                    .insert((port_channel_id, except), Some(value));
+            }
+            Query::PruningSequence(port_channel_id) => {
+                let value = host
+                    .pruning_sequence(&port_channel_id)
+                    .await
+                    .map_err(Error::ics04_channel)?;
+                self.pruning_sequences.insert(port_channel_id, Some(value));
+            }
+            Query::Upgrade(port_channel_id) => {
+                let value = host
+                    .upgrade(&port_channel_id)
+                    .await
+                    .map_err(Error::ics04_channel)?;
+                self.upgrades.insert(port_channel_id, value);
+            }
+            Query::CounterpartyUpgrade(port_channel_id) => {
+                let value = host
+                    .counterparty_upgrade(&port_channel_id)
+                    .await
+                    .map_err(Error::ics04_channel)?;
+                self.counterparty_upgrades.insert(port_channel_id, value);
+            }
+            Query::UpgradeErrorReceipt(port_channel_id) => {
+                let value = host
+                    .upgrade_error_receipt(&port_channel_id)
+                    .await
+                    .map_err(Error::ics04_channel)?;
+                self.upgrade_error_receipts.insert(port_channel_id, value);
+            }
+            Query::ClientUpdateTime(client_id, height) => {
+                let value = host
+                    .client_update_time(&client_id, height)
+                    .await
+                    .map_err(Error::ics04_channel)?;
+                self.client_update_times.insert((client_id, height), value);
+            }
+            Query::ClientUpdateHeight(client_id, height) => {
+                let value = host
+                    .client_update_height(&client_id, height)
+                    .await
+                    .map_err(Error::ics04_channel)?;
+                self.client_update_heights
+                    .insert((client_id, height), value);
+            }
+            Query::ChannelCounter => {
+                let value = host.channel_counter().await.map_err(Error::ics04_channel)?;
+                self.channel_counter = Some(value);
+            }
+            Query::ModuleByPort(port_id) => {
+                let value = host
+                    .lookup_module_by_port(&port_id)
+                    .await
+                    .map_err(|e| Error::ics04_channel(Ics04Error::ics05_port(e)))?;
+                self.port_modules.insert(port_id, value);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies the recorded writes to the host, in the order in which they were made.
+    /// Fails without writing anything if some reads were not answered since the last call to
+    /// [`ContextCache::fetch_misses`].
+    pub(crate) async fn flush(self) -> Result<(), Error> {
+        let misses = self.misses.into_inner();
+        if !misses.is_empty() {
+            return Err(Error::unanswered_reads(format!("{:?}", misses)));
+        }
+
+        let host = self.host;
+
+        for write in self.writes {
+            match write {
+                Write::ClientType(client_id, client_type) => host
+                    .store_client_type(client_id, client_type)
+                    .await
+                    .map_err(Error::ics02_client)?,
+                Write::ClientState(client_id, client_state) => host
+                    .store_client_state(client_id, client_state)
+                    .await
+                    .map_err(Error::ics02_client)?,
+                Write::ConsensusState(client_id, height, consensus_state) => host
+                    .store_consensus_state(client_id, height, consensus_state)
+                    .await
+                    .map_err(Error::ics02_client)?,
+                Write::IncreaseClientCounter => host.increase_client_counter().await,
+                Write::UpdateTime(client_id, height, timestamp) => host
+                    .store_update_time(client_id, height, timestamp)
+                    .await
+                    .map_err(Error::ics02_client)?,
+                Write::UpdateHeight(client_id, height, host_height) => host
+                    .store_update_height(client_id, height, host_height)
+                    .await
+                    .map_err(Error::ics02_client)?,
+                Write::Connection(connection_id, connection_end) => host
+                    .store_connection(connection_id, &connection_end)
+                    .await
+                    .map_err(Error::ics03_connection)?,
+                Write::ConnectionToClient(connection_id, client_id) => host
+                    .store_connection_to_client(connection_id, &client_id)
+                    .await
+                    .map_err(Error::ics03_connection)?,
+                Write::IncreaseConnectionCounter => host.increase_connection_counter().await,
+                Write::PacketCommitment(key, commitment) => host
+                    .store_packet_commitment(key, commitment)
+                    .await
+                    .map_err(Error::ics04_channel)?,
+                Write::DeletePacketCommitment(key) => host
+                    .delete_packet_commitment(key)
+                    .await
+                    .map_err(Error::ics04_channel)?,
+                Write::PacketReceipt(key, receipt) => host
+                    .store_packet_receipt(key, receipt)
+                    .await
+                    .map_err(Error::ics04_channel)?,
+                Write::PacketAcknowledgement(key, ack_commitment) => host
+                    .store_packet_acknowledgement(key, ack_commitment)
+                    .await
+                    .map_err(Error::ics04_channel)?,
+                Write::DeletePacketAcknowledgement(key) => host
+                    .delete_packet_acknowledgement(key)
+                    .await
+                    .map_err(Error::ics04_channel)?,
+                Write::DeletePacketReceipt(key) => host
+                    .delete_packet_receipt(key)
+                    .await
+                    .map_err(Error::ics04_channel)?,
+                Write::PruningSequence(port_channel_id, seq) => host
+                    .store_pruning_sequence(port_channel_id, seq)
+                    .await
+                    .map_err(Error::ics04_channel)?,
+                Write::ConnectionChannels(connection_id, port_channel_id) => host
+                    .store_connection_channels(connection_id, &port_channel_id)
+                    .await
+                    .map_err(Error::ics04_channel)?,
+                Write::Channel(port_channel_id, channel_end) => host
+                    .store_channel(port_channel_id, &channel_end)
+                    .await
+                    .map_err(Error::ics04_channel)?,
+                Write::NextSequenceSend(port_channel_id, seq) => host
+                    .store_next_sequence_send(port_channel_id, seq)
+                    .await
+                    .map_err(Error::ics04_channel)?,
+                Write::NextSequenceRecv(port_channel_id, seq) => host
+                    .store_next_sequence_recv(port_channel_id, seq)
+                    .await
+                    .map_err(Error::ics04_channel)?,
+                Write::NextSequenceAck(port_channel_id, seq) => host
+                    .store_next_sequence_ack(port_channel_id, seq)
+                    .await
+                    .map_err(Error::ics04_channel)?,
+                Write::Upgrade(port_channel_id, upgrade) => host
+                    .store_upgrade(port_channel_id, upgrade)
+                    .await
+                    .map_err(Error::ics04_channel)?,
+                Write::DeleteUpgrade(port_channel_id) => host
+                    .delete_upgrade(port_channel_id)
+                    .await
+                    .map_err(Error::ics04_channel)?,
+                Write::CounterpartyUpgrade(port_channel_id, upgrade) => host
+                    .store_counterparty_upgrade(port_channel_id, upgrade)
+                    .await
+                    .map_err(Error::ics04_channel)?,
+                Write::DeleteCounterpartyUpgrade(port_channel_id) => host
+                    .delete_counterparty_upgrade(port_channel_id)
+                    .await
+                    .map_err(Error::ics04_channel)?,
+                Write::UpgradeErrorReceipt(port_channel_id, error_receipt) => host
+                    .store_upgrade_error_receipt(port_channel_id, error_receipt)
+                    .await
+                    .map_err(Error::ics04_channel)?,
+                Write::IncreaseChannelCounter => host.increase_channel_counter().await,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, Ctx> ContextCache<'a, Ctx> {
+    /// Returns the cached value of `key` in `map`, or the `not_found` error if the host does not
+    /// store it. If the value was not fetched yet, records the miss and returns `not_found` too.
+    fn lookup<K, V, E>(
+        &self,
+        map: &BTreeMap<K, Option<V>>,
+        key: &K,
+        query: impl FnOnce(K) -> Query,
+        not_found: impl FnOnce() -> E,
+    ) -> Result<V, E>
+    where
+        K: Ord + Clone,
+        V: Clone,
+    {
+        match map.get(key) {
+            Some(value) => value.clone().ok_or_else(not_found),
+            None => {
+                self.miss(query(key.clone()));
+                Err(not_found())
+            }
+        }
+    }
+
+    /// Same as `lookup`, for the values which are fetched once per message (e.g., counters).
+    fn lookup_value<V, E>(
+        &self,
+        value: &Option<V>,
+        query: Query,
+        not_fetched: impl FnOnce() -> E,
+    ) -> Result<V, E>
+    where
+        V: Clone,
+    {
+        match value {
+            Some(value) => Ok(value.clone()),
+            None => {
+                self.miss(query);
+                Err(not_fetched())
+            }
+        }
+    }
+
+    fn miss(&self, query: Query) {
+        let mut misses = self.misses.borrow_mut();
+        if !misses.contains(&query) {
+            misses.push(query);
+        }
+    }
+
+    fn client_state_of(&self, client_id: &ClientId) -> Result<AnyClientState, Ics02Error> {
+        self.lookup(&self.client_states, client_id, Query::ClientState, || {
+            Ics02Error::client_not_found(client_id.clone())
+        })
+    }
+
+    fn consensus_state_of(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<AnyConsensusState, Ics02Error> {
+        self.lookup(
+            &self.consensus_states,
+            &(client_id.clone(), height),
+            |(client_id, height)| Query::ConsensusState(client_id, height),
+            || Ics02Error::consensus_state_not_found(client_id.clone(), height),
+        )
+    }
+
+    fn host_consensus_state_at(&self, height: Height) -> Result<AnyConsensusState, Ics02Error> {
+        self.lookup(
+            &self.host_consensus_states,
+            &height,
+            Query::HostConsensusState,
+            || Ics02Error::missing_local_consensus_state(height),
+        )
+    }
+
+    fn pending_host_consensus_state_of(&self) -> Result<AnyConsensusState, Ics02Error> {
+        self.lookup_value(
+            &self.pending_host_consensus_state,
+            Query::PendingHostConsensusState,
+            Ics02Error::implementation_specific,
+        )
+    }
+
+    fn connection_end_of(&self, conn_id: &ConnectionId) -> Result<ConnectionEnd, Ics03Error> {
+        self.lookup(&self.connection_ends, conn_id, Query::ConnectionEnd, || {
+            Ics03Error::connection_not_found(conn_id.clone())
+        })
+    }
+
+    fn lookup_optional<K, V>(
+        &self,
+        map: &BTreeMap<K, Option<V>>,
+        key: K,
+        query: impl FnOnce(K) -> Query,
+    ) -> Option<V>
+    where
+        K: Ord,
+        V: Clone,
+    {
+        match map.get(&key) {
+            Some(value) => value.clone(),
+            None => {
+                self.miss(query(key));
+                None
+            }
+        }
+    }
+}
+
+impl<'a, Ctx: AsyncIcs26Context> ClientReader for ContextCache<'a, Ctx> {
+    fn client_type(&self, client_id: &ClientId) -> Result<ClientType, Ics02Error> {
+        self.lookup(&self.client_types, client_id, Query::ClientType, || {
+            Ics02Error::client_not_found(client_id.clone())
+        })
+    }
+
+    fn client_state(&self, client_id: &ClientId) -> Result<AnyClientState, Ics02Error> {
+        self.client_state_of(client_id)
+    }
+
+    fn consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<AnyConsensusState, Ics02Error> {
+        self.consensus_state_of(client_id, height)
+    }
+
+    fn next_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<Option<AnyConsensusState>, Ics02Error> {
+        Ok(self.lookup_optional(
+            &self.next_consensus_states,
+            (client_id.clone(), height),
+            |(client_id, height)| Query::NextConsensusState(client_id, height),
+        ))
+    }
+
+    fn prev_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<Option<AnyConsensusState>, Ics02Error> {
+        Ok(self.lookup_optional(
+            &self.prev_consensus_states,
+            (client_id.clone(), height),
+            |(client_id, height)| Query::PrevConsensusState(client_id, height),
+        ))
+    }
+
+    fn host_height(&self) -> Height {
+        self.host_height
+    }
+
+    fn host_timestamp(&self) -> Timestamp {
+        self.host_timestamp
+    }
+
+    fn host_consensus_state(&self, height: Height) -> Result<AnyConsensusState, Ics02Error> {
+        self.host_consensus_state_at(height)
+    }
+
+    fn pending_host_consensus_state(&self) -> Result<AnyConsensusState, Ics02Error> {
+        self.pending_host_consensus_state_of()
+    }
+
+    fn client_counter(&self) -> Result<u64, Ics02Error> {
+        self.lookup_value(
+            &self.client_counter,
+            Query::ClientCounter,
+            Ics02Error::implementation_specific,
+        )
+    }
+}
+
+impl<'a, Ctx: AsyncIcs26Context> ClientKeeper for ContextCache<'a, Ctx> {
+    fn store_client_type(
+        &mut self,
+        client_id: ClientId,
+        client_type: ClientType,
+    ) -> Result<(), Ics02Error> {
+        self.writes.push(Write::ClientType(client_id, client_type));
+        Ok(())
+    }
+
+    fn store_client_state(
+        &mut self,
+        client_id: ClientId,
+        client_state: AnyClientState,
+    ) -> Result<(), Ics02Error> {
+        self.writes
+            .push(Write::ClientState(client_id, client_state));
+        Ok(())
+    }
+
+    fn store_consensus_state(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        consensus_state: AnyConsensusState,
+    ) -> Result<(), Ics02Error> {
+        self.writes
+            .push(Write::ConsensusState(client_id, height, consensus_state));
+        Ok(())
+    }
+
+    fn increase_client_counter(&mut self) {
+        self.writes.push(Write::IncreaseClientCounter);
+    }
+
+    fn store_update_time(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        timestamp: Timestamp,
+    ) -> Result<(), Ics02Error> {
+        self.writes
+            .push(Write::UpdateTime(client_id, height, timestamp));
+        Ok(())
+    }
+
+    fn store_update_height(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        host_height: Height,
+    ) -> Result<(), Ics02Error> {
+        self.writes
+            .push(Write::UpdateHeight(client_id, height, host_height));
+        Ok(())
+    }
+}
+
+impl<'a, Ctx: AsyncIcs26Context> ConnectionReader for ContextCache<'a, Ctx> {
+    fn connection_end(&self, conn_id: &ConnectionId) -> Result<ConnectionEnd, Ics03Error> {
+        self.connection_end_of(conn_id)
+    }
+
+    fn client_state(&self, client_id: &ClientId) -> Result<AnyClientState, Ics03Error> {
+        self.client_state_of(client_id)
+            .map_err(Ics03Error::ics02_client)
+    }
+
+    fn host_current_height(&self) -> Height {
+        self.host_height
+    }
+
+    fn host_oldest_height(&self) -> Height {
+        self.host_oldest_height
+    }
+
+    fn commitment_prefix(&self) -> CommitmentPrefix {
+        self.commitment_prefix.clone()
+    }
+
+    fn client_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<AnyConsensusState, Ics03Error> {
+        self.consensus_state_of(client_id, height)
+            .map_err(Ics03Error::ics02_client)
+    }
+
+    fn host_consensus_state(&self, height: Height) -> Result<AnyConsensusState, Ics03Error> {
+        self.host_consensus_state_at(height)
+            .map_err(Ics03Error::ics02_client)
+    }
+
+    fn get_compatible_versions(&self) -> Vec<Version> {
+        self.host.get_compatible_versions()
+    }
+
+    fn pick_version(
+        &self,
+        supported_versions: Vec<Version>,
+        counterparty_candidate_versions: Vec<Version>,
+    ) -> Result<Version, Ics03Error> {
+        self.host
+            .pick_version(supported_versions, counterparty_candidate_versions)
+    }
+
+    fn connection_counter(&self) -> Result<u64, Ics03Error> {
+        self.lookup_value(
+            &self.connection_counter,
+            Query::ConnectionCounter,
+            Ics03Error::implementation_specific,
+        )
+    }
+}
+
+impl<'a, Ctx: AsyncIcs26Context> ConnectionKeeper for ContextCache<'a, Ctx> {
+    fn store_connection(
+        &mut self,
+        connection_id: ConnectionId,
+        connection_end: &ConnectionEnd,
+    ) -> Result<(), Ics03Error> {
+        self.writes
+            .push(Write::Connection(connection_id, connection_end.clone()));
+        Ok(())
+    }
+
+    fn store_connection_to_client(
+        &mut self,
+        connection_id: ConnectionId,
+        client_id: &ClientId,
+    ) -> Result<(), Ics03Error> {
+        self.writes
+            .push(Write::ConnectionToClient(connection_id, client_id.clone()));
+        Ok(())
+    }
+
+    fn increase_connection_counter(&mut self) {
+        self.writes.push(Write::IncreaseConnectionCounter);
+    }
+}
+
+impl<'a, Ctx: AsyncIcs26Context> ChannelReader for ContextCache<'a, Ctx> {
+    fn channel_end(&self, port_channel_id: &PortChannelId) -> Result<ChannelEnd, Ics04Error> {
+        self.lookup(
+            &self.channel_ends,
+            port_channel_id,
+            Query::ChannelEnd,
+            || Ics04Error::channel_not_found(port_channel_id.0.clone(), port_channel_id.1.clone()),
+        )
+    }
+
+    fn connection_end(&self, connection_id: &ConnectionId) -> Result<ConnectionEnd, Ics04Error> {
+        self.connection_end_of(connection_id)
+            .map_err(Ics04Error::ics03_connection)
+    }
+
+    fn connection_channels(&self, cid: &ConnectionId) -> Result<Vec<PortChannelId>, Ics04Error> {
+        self.lookup(
+            &self.connection_channels,
+            cid,
+            Query::ConnectionChannels,
+            Ics04Error::missing_channel,
+        )
+    }
+
+    fn client_state(&self, client_id: &ClientId) -> Result<AnyClientState, Ics04Error> {
+        self.client_state_of(client_id)
+            .map_err(|e| Ics04Error::ics03_connection(Ics03Error::ics02_client(e)))
+    }
+
+    fn client_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<AnyConsensusState, Ics04Error> {
+        self.consensus_state_of(client_id, height)
+            .map_err(|e| Ics04Error::ics03_connection(Ics03Error::ics02_client(e)))
+    }
+
+    fn get_next_sequence_send(
+        &self,
+        port_channel_id: &PortChannelId,
+    ) -> Result<Sequence, Ics04Error> {
+        self.lookup(
+            &self.next_sequences_send,
+            port_channel_id,
+            Query::NextSequenceSend,
+            || Ics04Error::missing_next_send_seq(port_channel_id.clone()),
+        )
+    }
+
+    fn get_next_sequence_recv(
+        &self,
+        port_channel_id: &PortChannelId,
+    ) -> Result<Sequence, Ics04Error> {
+        self.lookup(
+            &self.next_sequences_recv,
+            port_channel_id,
+            Query::NextSequenceRecv,
+            || Ics04Error::missing_next_recv_seq(port_channel_id.clone()),
+        )
+    }
+
+    fn get_next_sequence_ack(
+        &self,
+        port_channel_id: &PortChannelId,
+    ) -> Result<Sequence, Ics04Error> {
+        self.lookup(
+            &self.next_sequences_ack,
+            port_channel_id,
+            Query::NextSequenceAck,
+            || Ics04Error::missing_next_ack_seq(port_channel_id.clone()),
+        )
+    }
+
+    fn get_packet_commitment(&self, key: &PacketKey) -> Result<PacketCommitment, Ics04Error> {
+        self.lookup(
+            &self.packet_commitments,
+            key,
+            Query::PacketCommitment,
+            || Ics04Error::packet_commitment_not_found(key.2),
+        )
+    }
+
+    fn get_packet_receipt(&self, key: &PacketKey) -> Result<Receipt, Ics04Error> {
+        self.lookup(&self.packet_receipts, key, Query::PacketReceipt, || {
+            Ics04Error::packet_receipt_not_found(key.2)
+        })
+    }
+
+    fn get_packet_acknowledgement(
+        &self,
+        key: &PacketKey,
+    ) -> Result<AcknowledgementCommitment, Ics04Error> {
+        self.lookup(
+            &self.packet_acknowledgements,
+            key,
+            Query::PacketAcknowledgement,
+            || Ics04Error::packet_acknowledgement_not_found(key.2),
+        )
+    }
+
+    fn has_inflight_packets(
+        &self,
+        port_channel_id: &PortChannelId,
+        except: Option<Sequence>,
+    ) -> Result<bool, Ics04Error> {
+        self.lookup(
+            &self.inflight_packets,
+            &(port_channel_id.clone(), except),
+            |(port_channel_id, except)| Query::InflightPackets(port_channel_id, except),
+            Ics04Error::implementation_specific,
+        )
+    }
+
+    fn pruning_sequence(&self, port_channel_id: &PortChannelId) -> Result<Sequence, Ics04Error> {
+        self.lookup(
+            &self.pruning_sequences,
+            port_channel_id,
+            Query::PruningSequence,
+            Ics04Error::implementation_specific,
+        )
+    }
+
+    fn upgrade(&self, port_channel_id: &PortChannelId) -> Result<Upgrade, Ics04Error> {
+        self.lookup(&self.upgrades, port_channel_id, Query::Upgrade, || {
+            Ics04Error::upgrade_not_found(port_channel_id.0.clone(), port_channel_id.1.clone())
+        })
+    }
+
+    fn counterparty_upgrade(&self, port_channel_id: &PortChannelId) -> Result<Upgrade, Ics04Error> {
+        self.lookup(
+            &self.counterparty_upgrades,
+            port_channel_id,
+            Query::CounterpartyUpgrade,
+            || {
+                Ics04Error::counterparty_upgrade_not_found(
+                    port_channel_id.0.clone(),
+                    port_channel_id.1.clone(),
+                )
+            },
+        )
+    }
+
+    fn upgrade_error_receipt(
+        &self,
+        port_channel_id: &PortChannelId,
+    ) -> Result<ErrorReceipt, Ics04Error> {
+        self.lookup(
+            &self.upgrade_error_receipts,
+            port_channel_id,
+            Query::UpgradeErrorReceipt,
+            Ics04Error::missing_error_receipt,
+        )
+    }
+
+    fn upgrade_timeout(&self) -> Duration {
+        self.host.upgrade_timeout()
+    }
+
+    fn hash(&self, value: Vec<u8>) -> Vec<u8> {
+        self.host.hash(value)
+    }
+
+    fn host_height(&self) -> Height {
+        self.host_height
+    }
+
+    fn host_timestamp(&self) -> Timestamp {
+        self.host_timestamp
+    }
+
+    fn host_consensus_state(&self, height: Height) -> Result<AnyConsensusState, Ics04Error> {
+        self.host_consensus_state_at(height)
+            .map_err(|e| Ics04Error::ics03_connection(Ics03Error::ics02_client(e)))
+    }
+
+    fn pending_host_consensus_state(&self) -> Result<AnyConsensusState, Ics04Error> {
+        self.pending_host_consensus_state_of()
+            .map_err(|e| Ics04Error::ics03_connection(Ics03Error::ics02_client(e)))
+    }
+
+    fn client_update_time(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<Timestamp, Ics04Error> {
+        self.lookup(
+            &self.client_update_times,
+            &(client_id.clone(), height),
+            |(client_id, height)| Query::ClientUpdateTime(client_id, height),
+            || Ics04Error::processed_time_not_found(client_id.clone(), height),
+        )
+    }
+
+    fn client_update_height(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<Height, Ics04Error> {
+        self.lookup(
+            &self.client_update_heights,
+            &(client_id.clone(), height),
+            |(client_id, height)| Query::ClientUpdateHeight(client_id, height),
+            || Ics04Error::processed_height_not_found(client_id.clone(), height),
+        )
+    }
+
+    fn channel_counter(&self) -> Result<u64, Ics04Error> {
+        self.lookup_value(
+            &self.channel_counter,
+            Query::ChannelCounter,
+            Ics04Error::implementation_specific,
+        )
+    }
+
+    fn max_expected_time_per_block(&self) -> Duration {
+        self.max_expected_time_per_block
+    }
+}
+
+impl<'a, Ctx: AsyncIcs26Context> ChannelKeeper for ContextCache<'a, Ctx> {
+    fn store_packet_commitment(
+        &mut self,
+        key: PacketKey,
+        commitment: PacketCommitment,
+    ) -> Result<(), Ics04Error> {
+        self.writes.push(Write::PacketCommitment(key, commitment));
+        Ok(())
+    }
+
+    fn delete_packet_commitment(&mut self, key: PacketKey) -> Result<(), Ics04Error> {
+        self.writes.push(Write::DeletePacketCommitment(key));
+        Ok(())
+    }
+
+    fn store_packet_receipt(&mut self, key: PacketKey, receipt: Receipt) -> Result<(), Ics04Error> {
+        self.writes.push(Write::PacketReceipt(key, receipt));
+        Ok(())
+    }
+
+    fn store_packet_acknowledgement(
+        &mut self,
+        key: PacketKey,
+        ack_commitment: AcknowledgementCommitment,
+    ) -> Result<(), Ics04Error> {
+        self.writes
+            .push(Write::PacketAcknowledgement(key, ack_commitment));
+        Ok(())
+    }
+
+    fn delete_packet_acknowledgement(&mut self, key: PacketKey) -> Result<(), Ics04Error> {
+        self.writes.push(Write::DeletePacketAcknowledgement(key));
+        Ok(())
+    }
+
+    fn delete_packet_receipt(&mut self, key: PacketKey) -> Result<(), Ics04Error> {
+        self.writes.push(Write::DeletePacketReceipt(key));
+        Ok(())
+    }
+
+    fn store_pruning_sequence(
+        &mut self,
+        port_channel_id: PortChannelId,
+        seq: Sequence,
+    ) -> Result<(), Ics04Error> {
+        self.writes
+            .push(Write::PruningSequence(port_channel_id, seq));
+        Ok(())
+    }
+
+    fn store_connection_channels(
+        &mut self,
+        conn_id: ConnectionId,
+        port_channel_id: &PortChannelId,
+    ) -> Result<(), Ics04Error> {
+        self.writes
+            .push(Write::ConnectionChannels(conn_id, port_channel_id.clone()));
+        Ok(())
+    }
+
+    fn store_channel(
+        &mut self,
+        port_channel_id: PortChannelId,
+        channel_end: &ChannelEnd,
+    ) -> Result<(), Ics04Error> {
+        self.writes
+            .push(Write::Channel(port_channel_id, channel_end.clone()));
+        Ok(())
+    }
+
+    fn store_next_sequence_send(
+        &mut self,
+        port_channel_id: PortChannelId,
+        seq: Sequence,
+    ) -> Result<(), Ics04Error> {
+        self.writes
+            .push(Write::NextSequenceSend(port_channel_id, seq));
+        Ok(())
+    }
+
+    fn store_next_sequence_recv(
+        &mut self,
+        port_channel_id: PortChannelId,
+        seq: Sequence,
+    ) -> Result<(), Ics04Error> {
+        self.writes
+            .push(Write::NextSequenceRecv(port_channel_id, seq));
+        Ok(())
+    }
+
+    fn store_next_sequence_ack(
+        &mut self,
+        port_channel_id: PortChannelId,
+        seq: Sequence,
+    ) -> Result<(), Ics04Error> {
+        self.writes
+            .push(Write::NextSequenceAck(port_channel_id, seq));
+        Ok(())
+    }
+
+    fn store_upgrade(
+        &mut self,
+        port_channel_id: PortChannelId,
+        upgrade: Upgrade,
+    ) -> Result<(), Ics04Error> {
+        self.writes.push(Write::Upgrade(port_channel_id, upgrade));
+        Ok(())
+    }
+
+    fn delete_upgrade(&mut self, port_channel_id: PortChannelId) -> Result<(), Ics04Error> {
+        self.writes.push(Write::DeleteUpgrade(port_channel_id));
+        Ok(())
+    }
+
+    fn store_counterparty_upgrade(
+        &mut self,
+        port_channel_id: PortChannelId,
+        upgrade: Upgrade,
+    ) -> Result<(), Ics04Error> {
+        self.writes
+            .push(Write::CounterpartyUpgrade(port_channel_id, upgrade));
+        Ok(())
+    }
+
+    fn delete_counterparty_upgrade(
+        &mut self,
+        port_channel_id: PortChannelId,
+    ) -> Result<(), Ics04Error> {
+        self.writes
+            .push(Write::DeleteCounterpartyUpgrade(port_channel_id));
+        Ok(())
+    }
+
+    fn store_upgrade_error_receipt(
+        &mut self,
+        port_channel_id: PortChannelId,
+        error_receipt: ErrorReceipt,
+    ) -> Result<(), Ics04Error> {
+        self.writes
+            .push(Write::UpgradeErrorReceipt(port_channel_id, error_receipt));
+        Ok(())
+    }
+
+    fn increase_channel_counter(&mut self) {
+        self.writes.push(Write::IncreaseChannelCounter);
+    }
+}
+
+impl<'a, Ctx: AsyncIcs26Context> PortReader for ContextCache<'a, Ctx> {
+    fn lookup_module_by_port(&self, port_id: &PortId) -> Result<ModuleId, Ics05Error> {
+        self.lookup(&self.port_modules, port_id, Query::ModuleByPort, || {
+            Ics05Error::unknown_port(port_id.clone())
+        })
+    }
+}
+
+impl<'a, Ctx: AsyncIcs26Context> Ics26Context for ContextCache<'a, Ctx> {
+    type Router = Ctx::Router;
+
+    fn router(&self) -> &Self::Router {
+        self.host.router()
+    }
+
+    fn router_mut(&mut self) -> &mut Self::Router {
+        self.host.router_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use super::ContextCache;
+    use crate::core::ics02_client::client_type::ClientType;
+    use crate::core::ics02_client::context::{ClientKeeper, ClientReader};
+    use crate::core::ics24_host::identifier::ClientId;
+    use crate::mock::context::MockContext;
+    use crate::Height;
+
+    #[test]
+    fn missed_reads_are_answered_once_fetched() {
+        let client_id = ClientId::default();
+        let unknown_client_id = ClientId::new(ClientType::Mock, 42).unwrap();
+        let mut host = MockContext::default().with_client(&client_id, Height::new(0, 10).unwrap());
+        let client_state = ClientReader::client_state(&host, &client_id).unwrap();
+
+        futures::executor::block_on(async {
+            let mut cache = ContextCache::new(&mut host).await;
+
+            // Nothing is cached yet, so both reads miss.
+            assert!(ClientReader::client_state(&cache, &client_id).is_err());
+            assert!(ClientReader::client_state(&cache, &unknown_client_id).is_err());
+            assert!(cache.fetch_misses().await.unwrap());
+
+            // Once fetched, the reads are answered as the host would, without missing again.
+            assert_eq!(
+                ClientReader::client_state(&cache, &client_id).unwrap(),
+                client_state
+            );
+            assert!(ClientReader::client_state(&cache, &unknown_client_id).is_err());
+            assert!(!cache.fetch_misses().await.unwrap());
+        });
+    }
+
+    #[test]
+    fn writes_are_not_flushed_after_a_missed_read() {
+        let client_id = ClientId::default();
+        let mut host = MockContext::default();
+        let client_type = ClientType::Mock;
+
+        futures::executor::block_on(async {
+            let mut cache = ContextCache::new(&mut host).await;
+
+            // A read made after the message was processed, e.g., by a module callback, misses
+            // and cannot be retried.
+            cache
+                .store_client_type(client_id.clone(), client_type)
+                .unwrap();
+            assert!(ClientReader::client_type(&cache, &client_id).is_err());
+            assert!(cache.flush().await.is_err());
+        });
+
+        assert!(ClientReader::client_type(&host, &client_id).is_err());
+    }
+}
@@ -160,6 +160,55 @@ pub trait Module: Send + Sync + AsAnyMut {
         Ok(())
     }
 
+    /// Called when an upgrade of the channel is proposed, and returns the version of the upgraded
+    /// channel. Modules do not support upgrades unless they override this and `on_chan_upgrade_try`.
+    fn on_chan_upgrade_init(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _version: &Version,
+    ) -> Result<Version, Error> {
+        Err(Error::upgrade_not_supported(port_id.clone()))
+    }
+
+    fn on_chan_upgrade_try(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _counterparty_version: &Version,
+    ) -> Result<Version, Error> {
+        Err(Error::upgrade_not_supported(port_id.clone()))
+    }
+
+    fn on_chan_upgrade_ack(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty_version: &Version,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called once the channel reopened with the upgraded fields.
+    fn on_chan_upgrade_open(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _version: &Version,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
     fn on_recv_packet(
         &self,
         _output: &mut ModuleOutputBuilder,
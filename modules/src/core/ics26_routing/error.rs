@@ -32,5 +32,9 @@ define_error! {
         MalformedMessageBytes
             [ TraceError<tendermint_proto::Error> ]
             | _ | { "the message is malformed and cannot be decoded" },
+
+        UnansweredReads
+            { queries: String }
+            | e | { format_args!("values missing from the cache were read after processing the message: {0}", e.queries) },
     }
 }
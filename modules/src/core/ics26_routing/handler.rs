@@ -2,23 +2,34 @@ use crate::prelude::*;
 
 use ibc_proto::google::protobuf::Any;
 
-use crate::core::ics02_client::handler::dispatch as ics2_msg_dispatcher;
-use crate::core::ics03_connection::handler::dispatch as ics3_msg_dispatcher;
+use crate::core::ics02_client::handler::{dispatch as ics2_msg_dispatcher, ClientResult};
+use crate::core::ics03_connection::handler::{dispatch as ics3_msg_dispatcher, ConnectionResult};
 use crate::core::ics04_channel::handler::{
     channel_callback as ics4_callback, channel_dispatch as ics4_msg_dispatcher,
-    channel_validate as ics4_validate, recv_packet::RecvPacketResult,
+    channel_upgrade_callback as ics4_upgrade_callback,
+    channel_upgrade_dispatch as ics4_upgrade_msg_dispatcher,
+    channel_upgrade_validate as ics4_upgrade_validate, channel_validate as ics4_validate,
+    recv_packet::RecvPacketResult, ChannelResult, ChannelUpgradeResult,
 };
 use crate::core::ics04_channel::handler::{
     get_module_for_packet_msg, packet_callback as ics4_packet_callback,
     packet_dispatch as ics4_packet_msg_dispatcher,
 };
+use crate::core::ics04_channel::msgs::{ChannelMsg, ChannelUpgradeMsg, PacketMsg};
 use crate::core::ics04_channel::packet::PacketResult;
-use crate::core::ics26_routing::context::{Ics26Context, ModuleOutputBuilder};
+#[cfg(feature = "async")]
+use crate::core::ics26_routing::async_context::AsyncIcs26Context;
+#[cfg(feature = "async")]
+use crate::core::ics26_routing::cache::ContextCache;
+use crate::core::ics26_routing::context::{Ics26Context, ModuleId, ModuleOutputBuilder};
 use crate::core::ics26_routing::error::Error;
 use crate::core::ics26_routing::msgs::Ics26Envelope::{
-    self, Ics2Msg, Ics3Msg, Ics4ChannelMsg, Ics4PacketMsg,
+    self, Ics2Msg, Ics3Msg, Ics4ChannelMsg, Ics4ChannelUpgradeMsg, Ics4PacketMsg,
+};
+use crate::{
+    events::IbcEvent,
+    handler::{HandlerOutput, HandlerOutputBuilder},
 };
-use crate::{events::IbcEvent, handler::HandlerOutput};
 
 /// Result of message execution - comprises of events emitted and logs entries created during the
 /// execution of a transaction message.
@@ -57,10 +68,145 @@ pub fn dispatch<Ctx>(ctx: &mut Ctx, msg: Ics26Envelope) -> Result<HandlerOutput<
 where
     Ctx: Ics26Context,
 {
-    let output = match msg {
+    let processed = process(ctx, msg)?;
+    execute(ctx, processed)
+}
+
+/// Async variant of [`deliver`], for hosts implementing [`AsyncIcs26Context`].
+#[cfg(feature = "async")]
+pub async fn deliver_async<Ctx>(ctx: &mut Ctx, message: Any) -> Result<MsgReceipt, Error>
+where
+    Ctx: AsyncIcs26Context,
+{
+    let envelope = decode(message)?;
+
+    let HandlerOutput { log, events, .. } = dispatch_async(ctx, envelope).await?;
+
+    Ok(MsgReceipt { events, log })
+}
+
+/// Async variant of [`dispatch`], for hosts implementing [`AsyncIcs26Context`].
+///
+/// The message is validated and processed by the same handlers as in [`dispatch`], over a cache
+/// of the host state: whenever the handlers read a value which is not cached yet, the value is
+/// fetched from the host and the message is processed again. The module callbacks are then
+/// called, and the results are written to the host, only once the message was processed without
+/// reading any value missing from the cache. Since the callbacks cannot be called again, the
+/// message fails if they, or the writing of the results, read a value missing from the cache.
+/// As with [`dispatch`], the runtime is expected to rollback the state modifications caused by
+/// the transaction if this method returns an error.
+#[cfg(feature = "async")]
+pub async fn dispatch_async<Ctx>(
+    ctx: &mut Ctx,
+    msg: Ics26Envelope,
+) -> Result<HandlerOutput<()>, Error>
+where
+    Ctx: AsyncIcs26Context,
+{
+    let mut cache = ContextCache::new(ctx).await;
+
+    let processed = loop {
+        let processed = process(&cache, msg.clone());
+        if !cache.fetch_misses().await? {
+            break processed?;
+        }
+    };
+
+    let output = execute(&mut cache, processed)?;
+    cache.flush().await?;
+
+    Ok(output)
+}
+
+/// A message validated and processed by its handler, whose results are not yet applied to the
+/// context.
+enum Processed {
+    Client(HandlerOutput<ClientResult>),
+    Connection(HandlerOutput<ConnectionResult>),
+    Channel {
+        msg: ChannelMsg,
+        module_id: ModuleId,
+        handler_builder: HandlerOutputBuilder<()>,
+        result: ChannelResult,
+    },
+    ChannelUpgrade {
+        msg: ChannelUpgradeMsg,
+        module_id: ModuleId,
+        handler_builder: HandlerOutputBuilder<()>,
+        result: ChannelUpgradeResult,
+    },
+    Packet {
+        msg: PacketMsg,
+        module_id: ModuleId,
+        handler_builder: HandlerOutputBuilder<()>,
+        result: PacketResult,
+    },
+}
+
+/// Validates and processes `msg`, only reading from the context.
+fn process<Ctx>(ctx: &Ctx, msg: Ics26Envelope) -> Result<Processed, Error>
+where
+    Ctx: Ics26Context,
+{
+    let processed = match msg {
         Ics2Msg(msg) => {
-            let handler_output = ics2_msg_dispatcher(ctx, msg).map_err(Error::ics02_client)?;
+            Processed::Client(ics2_msg_dispatcher(ctx, msg).map_err(Error::ics02_client)?)
+        }
+
+        Ics3Msg(msg) => {
+            Processed::Connection(ics3_msg_dispatcher(ctx, msg).map_err(Error::ics03_connection)?)
+        }
+
+        Ics4ChannelMsg(msg) => {
+            let module_id = ics4_validate(ctx, &msg).map_err(Error::ics04_channel)?;
+            let (handler_builder, result) =
+                ics4_msg_dispatcher(ctx, &msg).map_err(Error::ics04_channel)?;
+
+            Processed::Channel {
+                msg,
+                module_id,
+                handler_builder,
+                result,
+            }
+        }
+
+        Ics4ChannelUpgradeMsg(msg) => {
+            let module_id = ics4_upgrade_validate(ctx, &msg).map_err(Error::ics04_channel)?;
+            let (handler_builder, result) =
+                ics4_upgrade_msg_dispatcher(ctx, &msg).map_err(Error::ics04_channel)?;
+
+            Processed::ChannelUpgrade {
+                msg,
+                module_id,
+                handler_builder,
+                result,
+            }
+        }
+
+        Ics4PacketMsg(msg) => {
+            let module_id = get_module_for_packet_msg(ctx, &msg).map_err(Error::ics04_channel)?;
+            let (handler_builder, result) =
+                ics4_packet_msg_dispatcher(ctx, &msg).map_err(Error::ics04_channel)?;
+
+            Processed::Packet {
+                msg,
+                module_id,
+                handler_builder,
+                result,
+            }
+        }
+    };
 
+    Ok(processed)
+}
+
+/// Calls the module callbacks for a processed message, and applies its results to the context.
+fn execute<Ctx>(ctx: &mut Ctx, processed: Processed) -> Result<HandlerOutput<()>, Error>
+where
+    Ctx: Ics26Context,
+{
+    let output = match processed {
+        Processed::Client(handler_output) => {
             // Apply the result to the context (host chain store).
             ctx.store_client_result(handler_output.result)
                 .map_err(Error::ics02_client)?;
@@ -71,9 +217,7 @@ where
                 .with_result(())
         }
 
-        Ics3Msg(msg) => {
-            let handler_output = ics3_msg_dispatcher(ctx, msg).map_err(Error::ics03_connection)?;
-
+        Processed::Connection(handler_output) => {
             // Apply any results to the host chain store.
             ctx.store_connection_result(handler_output.result)
                 .map_err(Error::ics03_connection)?;
@@ -84,11 +228,12 @@ where
                 .with_result(())
         }
 
-        Ics4ChannelMsg(msg) => {
-            let module_id = ics4_validate(ctx, &msg).map_err(Error::ics04_channel)?;
-            let (mut handler_builder, channel_result) =
-                ics4_msg_dispatcher(ctx, &msg).map_err(Error::ics04_channel)?;
-
+        Processed::Channel {
+            msg,
+            module_id,
+            mut handler_builder,
+            result: channel_result,
+        } => {
             let mut module_output = ModuleOutputBuilder::new();
             let cb_result =
                 ics4_callback(ctx, &module_id, &msg, channel_result, &mut module_output);
@@ -102,15 +247,46 @@ where
             handler_builder.with_result(())
         }
 
-        Ics4PacketMsg(msg) => {
-            let module_id = get_module_for_packet_msg(ctx, &msg).map_err(Error::ics04_channel)?;
-            let (mut handler_builder, packet_result) =
-                ics4_packet_msg_dispatcher(ctx, &msg).map_err(Error::ics04_channel)?;
+        Processed::ChannelUpgrade {
+            msg,
+            module_id,
+            mut handler_builder,
+            result: upgrade_result,
+        } => {
+            let mut module_output = ModuleOutputBuilder::new();
+            let cb_result =
+                ics4_upgrade_callback(ctx, &module_id, &msg, upgrade_result, &mut module_output);
+            handler_builder.merge(module_output);
+            let upgrade_result = cb_result.map_err(Error::ics04_channel)?;
+
+            // Apply any results to the host chain store.
+            ctx.store_channel_upgrade_result(upgrade_result)
+                .map_err(Error::ics04_channel)?;
+
+            handler_builder.with_result(())
+        }
 
+        Processed::Packet {
+            msg,
+            module_id,
+            mut handler_builder,
+            result: packet_result,
+        } => {
             if matches!(packet_result, PacketResult::Recv(RecvPacketResult::NoOp)) {
                 return Ok(handler_builder.with_result(()));
             }
 
+            // A timed-out packet received on an `ORDERED_ALLOW_TIMEOUT` channel is only recorded
+            // with a timeout receipt and never delivered to the application.
+            if matches!(
+                packet_result,
+                PacketResult::Recv(RecvPacketResult::Timeout { .. })
+            ) {
+                ctx.store_packet_result(packet_result)
+                    .map_err(Error::ics04_channel)?;
+                return Ok(handler_builder.with_result(()));
+            }
+
             let mut module_output = ModuleOutputBuilder::new();
             let cb_result = ics4_packet_callback(ctx, &module_id, &msg, &mut module_output);
             handler_builder.merge(module_output);
@@ -225,10 +401,20 @@ mod tests {
 
         let transfer_module_id: ModuleId = MODULE_ID_STR.parse().unwrap();
 
+        let sender_funds = {
+            let msg_transfer = get_dummy_msg_transfer(TimeoutHeight::no_timeout(), None);
+            PrefixedCoin {
+                denom: msg_transfer.token.denom,
+                amount: 100u64.into(),
+            }
+        };
+
         // We reuse this same context across all tests. Nothing in particular needs parametrizing.
         let mut ctx = {
             let ctx = MockContext::default();
-            let module = DummyTransferModule::new(ctx.ibc_store_share());
+            let msg_transfer = get_dummy_msg_transfer(TimeoutHeight::no_timeout(), None);
+            let module = DummyTransferModule::new(ctx.ibc_store_share())
+                .with_balance(&msg_transfer.sender, &sender_funds);
             let router = MockRouterBuilder::default()
                 .add_route(transfer_module_id.clone(), module)
                 .unwrap()
@@ -315,6 +501,7 @@ mod tests {
                 },
                 sender: msg_transfer_two.sender.clone(),
                 receiver: msg_transfer_two.receiver.clone(),
+                memo: msg_transfer_two.memo.clone(),
             };
             serde_json::to_vec(&data).expect("PacketData's infallible Serialize impl failed")
         };
@@ -576,5 +763,231 @@ mod tests {
                 res
             );
         }
+
+        // The four transfers were escrowed, and none was refunded by the mock module.
+        assert_eq!(
+            ctx.ibc_store_share()
+                .lock()
+                .unwrap()
+                .total_escrows
+                .get(&sender_funds.denom),
+            Some(&40u64.into())
+        );
+    }
+
+    /// Dispatches `msg` to `ctx` with `dispatch` and to `async_ctx` with `dispatch_async`, checking
+    /// that both fail with the same error, or succeed with the same events.
+    #[cfg(feature = "async")]
+    fn dispatch_both(
+        ctx: &mut MockContext,
+        async_ctx: &mut MockContext,
+        msg: Ics26Envelope,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        use crate::core::ics26_routing::handler::dispatch_async;
+
+        let res = dispatch(ctx, msg.clone());
+        let async_res = futures::executor::block_on(dispatch_async(async_ctx, msg.clone()));
+
+        match (res, async_res) {
+            (Ok(output), Ok(async_output)) => {
+                assert_eq!(output.events, async_output.events, "message {:?}", msg);
+                Ok(output.events)
+            }
+            (Err(e), Err(async_e)) => {
+                assert_eq!(e.to_string(), async_e.to_string(), "message {:?}", msg);
+                Err(e)
+            }
+            (res, async_res) => panic!(
+                "dispatch and dispatch_async disagree on message {:?}: {:?} vs. {:?}",
+                msg, res, async_res
+            ),
+        }
+    }
+
+    /// The parts of the store of `ctx` which do not depend on the time at which the messages were
+    /// delivered.
+    #[cfg(feature = "async")]
+    fn store_snapshot(ctx: &MockContext) -> String {
+        let store = ctx.ibc_store_share();
+        let store = store.lock().unwrap();
+        format!(
+            "{:?}",
+            (
+                (
+                    &store.clients,
+                    &store.client_processed_heights,
+                    &store.connections,
+                    &store.client_connections,
+                    &store.connection_channels,
+                    &store.channels,
+                ),
+                (
+                    &store.next_sequence_send,
+                    &store.next_sequence_recv,
+                    &store.next_sequence_ack,
+                    &store.packet_commitment,
+                    &store.packet_receipt,
+                    &store.packet_acknowledgement,
+                ),
+                (
+                    &store.channel_upgrades,
+                    &store.counterparty_upgrades,
+                    &store.upgrade_error_receipts,
+                    &store.balances,
+                    &store.total_escrows,
+                ),
+            )
+        )
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    /// Delivers the same handshakes, packets and channel upgrade to a context through `dispatch`
+    /// and to another through `dispatch_async`, and checks that both have the same results, events
+    /// and resulting state.
+    fn async_dispatch_matches_dispatch() {
+        use crate::core::ics04_channel::msgs::acknowledgement::{
+            test_util::get_dummy_raw_msg_acknowledgement, MsgAcknowledgement,
+        };
+        use crate::core::ics04_channel::msgs::chan_upgrade_init::{
+            test_util::get_dummy_raw_msg_chan_upgrade_init, MsgChannelUpgradeInit,
+        };
+        use crate::core::ics04_channel::msgs::timeout::{
+            test_util::get_dummy_raw_msg_timeout, MsgTimeout,
+        };
+        use crate::core::ics04_channel::msgs::ChannelUpgradeMsg;
+
+        let client_height = 5;
+        let default_signer = get_dummy_account_id();
+        let transfer_module_id: ModuleId = MODULE_ID_STR.parse().unwrap();
+        let msg_transfer = get_dummy_msg_transfer(Height::new(0, 35).unwrap().into(), None);
+        let msg_chan_init =
+            MsgChannelOpenInit::try_from(get_dummy_raw_msg_chan_open_init()).unwrap();
+
+        let new_ctx = || {
+            let ctx = MockContext::default();
+            let funds = PrefixedCoin {
+                denom: msg_transfer.token.denom.clone(),
+                amount: 100u64.into(),
+            };
+            let module = DummyTransferModule::new(ctx.ibc_store_share())
+                .with_balance(&msg_transfer.sender, &funds);
+            let router = MockRouterBuilder::default()
+                .add_route(transfer_module_id.clone(), module)
+                .unwrap()
+                .build();
+            let mut ctx = ctx.with_router(router);
+            ctx.scope_port_to_module(msg_chan_init.port_id.clone(), transfer_module_id.clone());
+            ctx
+        };
+        let mut ctx = new_ctx();
+        let mut async_ctx = new_ctx();
+
+        let start_client_height = Height::new(0, client_height).unwrap();
+        let create_client_msg = MsgCreateAnyClient::new(
+            AnyClientState::from(MockClientState::new(MockHeader::new(start_client_height))),
+            AnyConsensusState::Mock(MockConsensusState::new(MockHeader::new(
+                start_client_height,
+            ))),
+            default_signer.clone(),
+        )
+        .unwrap();
+        let mut events = dispatch_both(
+            &mut ctx,
+            &mut async_ctx,
+            Ics26Envelope::Ics2Msg(ClientMsg::CreateClient(create_client_msg)),
+        )
+        .unwrap();
+        let client_id = match events.pop() {
+            Some(IbcEvent::CreateClient(create_client)) => create_client.client_id().clone(),
+            event => panic!("unexpected IBC event: {:?}", event),
+        };
+
+        let update_client = |height| {
+            Ics26Envelope::Ics2Msg(ClientMsg::UpdateClient(MsgUpdateAnyClient {
+                client_id: client_id.clone(),
+                header: MockHeader::new(Height::new(0, height).unwrap())
+                    .with_timestamp(Timestamp::now())
+                    .into(),
+                signer: default_signer.clone(),
+            }))
+        };
+
+        // The handshakes, which succeed on both contexts.
+        let handshake = vec![
+            update_client(34),
+            Ics26Envelope::Ics3Msg(ConnectionMsg::ConnectionOpenInit(
+                MsgConnectionOpenInit::try_from(get_dummy_raw_msg_conn_open_init())
+                    .unwrap()
+                    .with_client_id(client_id.clone()),
+            )),
+            Ics26Envelope::Ics3Msg(ConnectionMsg::ConnectionOpenTry(Box::new(
+                MsgConnectionOpenTry::try_from(get_dummy_raw_msg_conn_open_try(
+                    client_height,
+                    client_height,
+                ))
+                .unwrap()
+                .with_client_id(client_id.clone()),
+            ))),
+            Ics26Envelope::Ics3Msg(ConnectionMsg::ConnectionOpenAck(Box::new(
+                MsgConnectionOpenAck::try_from(get_dummy_raw_msg_conn_open_ack(
+                    client_height,
+                    client_height,
+                ))
+                .unwrap(),
+            ))),
+            Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenInit(msg_chan_init.clone())),
+            Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenTry(
+                MsgChannelOpenTry::try_from(get_dummy_raw_msg_chan_open_try(client_height))
+                    .unwrap(),
+            )),
+            Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenAck(
+                MsgChannelOpenAck::try_from(get_dummy_raw_msg_chan_open_ack(client_height))
+                    .unwrap(),
+            )),
+        ];
+        for msg in handshake {
+            dispatch_both(&mut ctx, &mut async_ctx, msg).unwrap();
+        }
+
+        // Token transfers are not delivered through `dispatch`, but the packets they send are
+        // then handled by both.
+        for ctx in [&mut ctx, &mut async_ctx] {
+            let transfer_module = ctx.router_mut().get_route_mut(&transfer_module_id).unwrap();
+            ics20_deliver(
+                transfer_module
+                    .as_any_mut()
+                    .downcast_mut::<DummyTransferModule>()
+                    .unwrap(),
+                &mut HandlerOutputBuilder::new(),
+                msg_transfer.clone(),
+            )
+            .unwrap();
+        }
+
+        // These may fail, but must fail the same way on both contexts.
+        let msgs = vec![
+            update_client(35),
+            Ics26Envelope::Ics4PacketMsg(PacketMsg::RecvPacket(
+                MsgRecvPacket::try_from(get_dummy_raw_msg_recv_packet(35)).unwrap(),
+            )),
+            Ics26Envelope::Ics4PacketMsg(PacketMsg::AckPacket(
+                MsgAcknowledgement::try_from(get_dummy_raw_msg_acknowledgement(36)).unwrap(),
+            )),
+            Ics26Envelope::Ics4PacketMsg(PacketMsg::ToPacket(
+                MsgTimeout::try_from(get_dummy_raw_msg_timeout(36, 35, 0)).unwrap(),
+            )),
+            Ics26Envelope::Ics4ChannelUpgradeMsg(ChannelUpgradeMsg::ChannelUpgradeInit(
+                MsgChannelUpgradeInit::try_from(get_dummy_raw_msg_chan_upgrade_init()).unwrap(),
+            )),
+            Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelCloseInit(
+                MsgChannelCloseInit::try_from(get_dummy_raw_msg_chan_close_init()).unwrap(),
+            )),
+        ];
+        for msg in msgs {
+            let _ = dispatch_both(&mut ctx, &mut async_ctx, msg);
+        }
+
+        assert_eq!(store_snapshot(&ctx), store_snapshot(&async_ctx));
     }
 }
@@ -9,6 +9,7 @@ use serde_derive::{Deserialize, Serialize};
 use tendermint::abci::tag::Tag;
 use tendermint::abci::Event as AbciEvent;
 
+use crate::applications::interchain_query::events as QueryEvents;
 use crate::core::ics02_client::error as client_error;
 use crate::core::ics02_client::events as ClientEvents;
 use crate::core::ics02_client::events::NewBlock;
@@ -17,6 +18,7 @@ use crate::core::ics03_connection::events::Attributes as ConnectionAttributes;
 use crate::core::ics04_channel::error as channel_error;
 use crate::core::ics04_channel::events as ChannelEvents;
 use crate::core::ics04_channel::events::Attributes as ChannelAttributes;
+use crate::core::ics04_channel::events::UpgradeAttributes;
 use crate::core::ics04_channel::packet::Packet;
 use crate::core::ics24_host::error::ValidationError;
 use crate::core::ics26_routing::context::ModuleId;
@@ -111,6 +113,14 @@ const CHANNEL_OPEN_ACK_EVENT: &str = "channel_open_ack";
 const CHANNEL_OPEN_CONFIRM_EVENT: &str = "channel_open_confirm";
 const CHANNEL_CLOSE_INIT_EVENT: &str = "channel_close_init";
 const CHANNEL_CLOSE_CONFIRM_EVENT: &str = "channel_close_confirm";
+const CHANNEL_UPGRADE_INIT_EVENT: &str = "channel_upgrade_init";
+const CHANNEL_UPGRADE_TRY_EVENT: &str = "channel_upgrade_try";
+const CHANNEL_UPGRADE_ACK_EVENT: &str = "channel_upgrade_ack";
+const CHANNEL_UPGRADE_CONFIRM_EVENT: &str = "channel_upgrade_confirm";
+const CHANNEL_UPGRADE_OPEN_EVENT: &str = "channel_upgrade_open";
+const CHANNEL_UPGRADE_TIMEOUT_EVENT: &str = "channel_upgrade_timeout";
+const CHANNEL_UPGRADE_CANCEL_EVENT: &str = "channel_upgrade_cancelled";
+const CHANNEL_UPGRADE_ERROR_EVENT: &str = "channel_upgrade_error";
 /// Packet event types
 const SEND_PACKET_EVENT: &str = "send_packet";
 const RECEIVE_PACKET_EVENT: &str = "receive_packet";
@@ -118,6 +128,8 @@ const WRITE_ACK_EVENT: &str = "write_acknowledgement";
 const ACK_PACKET_EVENT: &str = "acknowledge_packet";
 const TIMEOUT_EVENT: &str = "timeout_packet";
 const TIMEOUT_ON_CLOSE_EVENT: &str = "timeout_packet_on_close";
+/// Cross-chain query event types
+const CROSS_CHAIN_QUERY_EVENT: &str = "cross_chain_query";
 
 /// Events types
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -137,12 +149,21 @@ pub enum IbcEventType {
     OpenConfirmChannel,
     CloseInitChannel,
     CloseConfirmChannel,
+    UpgradeInitChannel,
+    UpgradeTryChannel,
+    UpgradeAckChannel,
+    UpgradeConfirmChannel,
+    UpgradeOpenChannel,
+    UpgradeTimeoutChannel,
+    UpgradeCancelChannel,
+    UpgradeErrorChannel,
     SendPacket,
     ReceivePacket,
     WriteAck,
     AckPacket,
     Timeout,
     TimeoutOnClose,
+    CrossChainQuery,
     AppModule,
     Empty,
     ChainError,
@@ -166,12 +187,21 @@ impl IbcEventType {
             IbcEventType::OpenConfirmChannel => CHANNEL_OPEN_CONFIRM_EVENT,
             IbcEventType::CloseInitChannel => CHANNEL_CLOSE_INIT_EVENT,
             IbcEventType::CloseConfirmChannel => CHANNEL_CLOSE_CONFIRM_EVENT,
+            IbcEventType::UpgradeInitChannel => CHANNEL_UPGRADE_INIT_EVENT,
+            IbcEventType::UpgradeTryChannel => CHANNEL_UPGRADE_TRY_EVENT,
+            IbcEventType::UpgradeAckChannel => CHANNEL_UPGRADE_ACK_EVENT,
+            IbcEventType::UpgradeConfirmChannel => CHANNEL_UPGRADE_CONFIRM_EVENT,
+            IbcEventType::UpgradeOpenChannel => CHANNEL_UPGRADE_OPEN_EVENT,
+            IbcEventType::UpgradeTimeoutChannel => CHANNEL_UPGRADE_TIMEOUT_EVENT,
+            IbcEventType::UpgradeCancelChannel => CHANNEL_UPGRADE_CANCEL_EVENT,
+            IbcEventType::UpgradeErrorChannel => CHANNEL_UPGRADE_ERROR_EVENT,
             IbcEventType::SendPacket => SEND_PACKET_EVENT,
             IbcEventType::ReceivePacket => RECEIVE_PACKET_EVENT,
             IbcEventType::WriteAck => WRITE_ACK_EVENT,
             IbcEventType::AckPacket => ACK_PACKET_EVENT,
             IbcEventType::Timeout => TIMEOUT_EVENT,
             IbcEventType::TimeoutOnClose => TIMEOUT_ON_CLOSE_EVENT,
+            IbcEventType::CrossChainQuery => CROSS_CHAIN_QUERY_EVENT,
             IbcEventType::AppModule => APP_MODULE_EVENT,
             IbcEventType::Empty => EMPTY_EVENT,
             IbcEventType::ChainError => CHAIN_ERROR_EVENT,
@@ -199,12 +229,21 @@ impl FromStr for IbcEventType {
             CHANNEL_OPEN_CONFIRM_EVENT => Ok(IbcEventType::OpenConfirmChannel),
             CHANNEL_CLOSE_INIT_EVENT => Ok(IbcEventType::CloseInitChannel),
             CHANNEL_CLOSE_CONFIRM_EVENT => Ok(IbcEventType::CloseConfirmChannel),
+            CHANNEL_UPGRADE_INIT_EVENT => Ok(IbcEventType::UpgradeInitChannel),
+            CHANNEL_UPGRADE_TRY_EVENT => Ok(IbcEventType::UpgradeTryChannel),
+            CHANNEL_UPGRADE_ACK_EVENT => Ok(IbcEventType::UpgradeAckChannel),
+            CHANNEL_UPGRADE_CONFIRM_EVENT => Ok(IbcEventType::UpgradeConfirmChannel),
+            CHANNEL_UPGRADE_OPEN_EVENT => Ok(IbcEventType::UpgradeOpenChannel),
+            CHANNEL_UPGRADE_TIMEOUT_EVENT => Ok(IbcEventType::UpgradeTimeoutChannel),
+            CHANNEL_UPGRADE_CANCEL_EVENT => Ok(IbcEventType::UpgradeCancelChannel),
+            CHANNEL_UPGRADE_ERROR_EVENT => Ok(IbcEventType::UpgradeErrorChannel),
             SEND_PACKET_EVENT => Ok(IbcEventType::SendPacket),
             RECEIVE_PACKET_EVENT => Ok(IbcEventType::ReceivePacket),
             WRITE_ACK_EVENT => Ok(IbcEventType::WriteAck),
             ACK_PACKET_EVENT => Ok(IbcEventType::AckPacket),
             TIMEOUT_EVENT => Ok(IbcEventType::Timeout),
             TIMEOUT_ON_CLOSE_EVENT => Ok(IbcEventType::TimeoutOnClose),
+            CROSS_CHAIN_QUERY_EVENT => Ok(IbcEventType::CrossChainQuery),
             EMPTY_EVENT => Ok(IbcEventType::Empty),
             CHAIN_ERROR_EVENT => Ok(IbcEventType::ChainError),
             // from_str() for `APP_MODULE_EVENT` MUST fail because a `ModuleEvent`'s type isn't constant
@@ -235,6 +274,15 @@ pub enum IbcEvent {
     CloseInitChannel(ChannelEvents::CloseInit),
     CloseConfirmChannel(ChannelEvents::CloseConfirm),
 
+    UpgradeInitChannel(ChannelEvents::UpgradeInit),
+    UpgradeTryChannel(ChannelEvents::UpgradeTry),
+    UpgradeAckChannel(ChannelEvents::UpgradeAck),
+    UpgradeConfirmChannel(ChannelEvents::UpgradeConfirm),
+    UpgradeOpenChannel(ChannelEvents::UpgradeOpen),
+    UpgradeTimeoutChannel(ChannelEvents::UpgradeTimeout),
+    UpgradeCancelChannel(ChannelEvents::UpgradeCancel),
+    UpgradeErrorChannel(ChannelEvents::UpgradeError),
+
     SendPacket(ChannelEvents::SendPacket),
     ReceivePacket(ChannelEvents::ReceivePacket),
     WriteAcknowledgement(ChannelEvents::WriteAcknowledgement),
@@ -242,6 +290,8 @@ pub enum IbcEvent {
     TimeoutPacket(ChannelEvents::TimeoutPacket),
     TimeoutOnClosePacket(ChannelEvents::TimeoutOnClosePacket),
 
+    CrossChainQuery(QueryEvents::SendQuery),
+
     AppModule(ModuleEvent),
 
     ChainError(String), // Special event, signifying an error on CheckTx or DeliverTx
@@ -281,6 +331,15 @@ impl fmt::Display for IbcEvent {
             IbcEvent::CloseInitChannel(ev) => write!(f, "CloseInitChannelEv({})", ev),
             IbcEvent::CloseConfirmChannel(ev) => write!(f, "CloseConfirmChannelEv({:?})", ev),
 
+            IbcEvent::UpgradeInitChannel(ev) => write!(f, "UpgradeInitChannelEv({:?})", ev),
+            IbcEvent::UpgradeTryChannel(ev) => write!(f, "UpgradeTryChannelEv({:?})", ev),
+            IbcEvent::UpgradeAckChannel(ev) => write!(f, "UpgradeAckChannelEv({:?})", ev),
+            IbcEvent::UpgradeConfirmChannel(ev) => write!(f, "UpgradeConfirmChannelEv({:?})", ev),
+            IbcEvent::UpgradeOpenChannel(ev) => write!(f, "UpgradeOpenChannelEv({:?})", ev),
+            IbcEvent::UpgradeTimeoutChannel(ev) => write!(f, "UpgradeTimeoutChannelEv({:?})", ev),
+            IbcEvent::UpgradeCancelChannel(ev) => write!(f, "UpgradeCancelChannelEv({:?})", ev),
+            IbcEvent::UpgradeErrorChannel(ev) => write!(f, "UpgradeErrorChannelEv({:?})", ev),
+
             IbcEvent::SendPacket(ev) => write!(f, "SendPacketEv({})", ev),
             IbcEvent::ReceivePacket(ev) => write!(f, "ReceivePacketEv({})", ev),
             IbcEvent::WriteAcknowledgement(ev) => write!(f, "WriteAcknowledgementEv({})", ev),
@@ -288,6 +347,8 @@ impl fmt::Display for IbcEvent {
             IbcEvent::TimeoutPacket(ev) => write!(f, "TimeoutPacketEv({})", ev),
             IbcEvent::TimeoutOnClosePacket(ev) => write!(f, "TimeoutOnClosePacketEv({})", ev),
 
+            IbcEvent::CrossChainQuery(ev) => write!(f, "CrossChainQueryEv({:?})", ev),
+
             IbcEvent::AppModule(ev) => write!(f, "AppModuleEv({:?})", ev),
 
             IbcEvent::ChainError(ev) => write!(f, "ChainErrorEv({})", ev),
@@ -314,12 +375,21 @@ impl TryFrom<IbcEvent> for AbciEvent {
             IbcEvent::OpenConfirmChannel(event) => event.into(),
             IbcEvent::CloseInitChannel(event) => event.into(),
             IbcEvent::CloseConfirmChannel(event) => event.into(),
+            IbcEvent::UpgradeInitChannel(event) => event.into(),
+            IbcEvent::UpgradeTryChannel(event) => event.into(),
+            IbcEvent::UpgradeAckChannel(event) => event.into(),
+            IbcEvent::UpgradeConfirmChannel(event) => event.into(),
+            IbcEvent::UpgradeOpenChannel(event) => event.into(),
+            IbcEvent::UpgradeTimeoutChannel(event) => event.into(),
+            IbcEvent::UpgradeCancelChannel(event) => event.into(),
+            IbcEvent::UpgradeErrorChannel(event) => event.into(),
             IbcEvent::SendPacket(event) => event.try_into().map_err(Error::channel)?,
             IbcEvent::ReceivePacket(event) => event.try_into().map_err(Error::channel)?,
             IbcEvent::WriteAcknowledgement(event) => event.try_into().map_err(Error::channel)?,
             IbcEvent::AcknowledgePacket(event) => event.try_into().map_err(Error::channel)?,
             IbcEvent::TimeoutPacket(event) => event.try_into().map_err(Error::channel)?,
             IbcEvent::TimeoutOnClosePacket(event) => event.try_into().map_err(Error::channel)?,
+            IbcEvent::CrossChainQuery(event) => event.into(),
             IbcEvent::AppModule(event) => event.try_into()?,
             IbcEvent::NewBlock(_) | IbcEvent::ChainError(_) => {
                 return Err(Error::incorrect_event_type(event.to_string()))
@@ -340,6 +410,9 @@ pub fn from_tx_response_event(height: Height, event: &tendermint::abci::Event) -
     } else if let Some(mut chan_res) = ChannelEvents::try_from_tx(event) {
         chan_res.set_height(height);
         Some(chan_res)
+    } else if let Some(mut query_res) = QueryEvents::try_from_tx(event) {
+        query_res.set_height(height);
+        Some(query_res)
     } else {
         None
     }
@@ -370,12 +443,21 @@ impl IbcEvent {
             IbcEvent::OpenConfirmChannel(ev) => ev.height(),
             IbcEvent::CloseInitChannel(ev) => ev.height(),
             IbcEvent::CloseConfirmChannel(ev) => ev.height(),
+            IbcEvent::UpgradeInitChannel(ev) => ev.height(),
+            IbcEvent::UpgradeTryChannel(ev) => ev.height(),
+            IbcEvent::UpgradeAckChannel(ev) => ev.height(),
+            IbcEvent::UpgradeConfirmChannel(ev) => ev.height(),
+            IbcEvent::UpgradeOpenChannel(ev) => ev.height(),
+            IbcEvent::UpgradeTimeoutChannel(ev) => ev.height(),
+            IbcEvent::UpgradeCancelChannel(ev) => ev.height(),
+            IbcEvent::UpgradeErrorChannel(ev) => ev.height(),
             IbcEvent::SendPacket(ev) => ev.height(),
             IbcEvent::ReceivePacket(ev) => ev.height(),
             IbcEvent::WriteAcknowledgement(ev) => ev.height(),
             IbcEvent::AcknowledgePacket(ev) => ev.height(),
             IbcEvent::TimeoutPacket(ev) => ev.height(),
             IbcEvent::TimeoutOnClosePacket(ev) => ev.height(),
+            IbcEvent::CrossChainQuery(ev) => ev.height(),
             _ => unimplemented!(),
         }
     }
@@ -397,11 +479,20 @@ impl IbcEvent {
             IbcEvent::OpenConfirmChannel(ev) => ev.set_height(height),
             IbcEvent::CloseInitChannel(ev) => ev.set_height(height),
             IbcEvent::CloseConfirmChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeInitChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeTryChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeAckChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeConfirmChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeOpenChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeTimeoutChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeCancelChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeErrorChannel(ev) => ev.set_height(height),
             IbcEvent::SendPacket(ev) => ev.set_height(height),
             IbcEvent::ReceivePacket(ev) => ev.set_height(height),
             IbcEvent::WriteAcknowledgement(ev) => ev.set_height(height),
             IbcEvent::AcknowledgePacket(ev) => ev.set_height(height),
             IbcEvent::TimeoutPacket(ev) => ev.set_height(height),
+            IbcEvent::CrossChainQuery(ev) => ev.set_height(height),
             _ => unimplemented!(),
         }
     }
@@ -423,12 +514,21 @@ impl IbcEvent {
             IbcEvent::OpenConfirmChannel(_) => IbcEventType::OpenConfirmChannel,
             IbcEvent::CloseInitChannel(_) => IbcEventType::CloseInitChannel,
             IbcEvent::CloseConfirmChannel(_) => IbcEventType::CloseConfirmChannel,
+            IbcEvent::UpgradeInitChannel(_) => IbcEventType::UpgradeInitChannel,
+            IbcEvent::UpgradeTryChannel(_) => IbcEventType::UpgradeTryChannel,
+            IbcEvent::UpgradeAckChannel(_) => IbcEventType::UpgradeAckChannel,
+            IbcEvent::UpgradeConfirmChannel(_) => IbcEventType::UpgradeConfirmChannel,
+            IbcEvent::UpgradeOpenChannel(_) => IbcEventType::UpgradeOpenChannel,
+            IbcEvent::UpgradeTimeoutChannel(_) => IbcEventType::UpgradeTimeoutChannel,
+            IbcEvent::UpgradeCancelChannel(_) => IbcEventType::UpgradeCancelChannel,
+            IbcEvent::UpgradeErrorChannel(_) => IbcEventType::UpgradeErrorChannel,
             IbcEvent::SendPacket(_) => IbcEventType::SendPacket,
             IbcEvent::ReceivePacket(_) => IbcEventType::ReceivePacket,
             IbcEvent::WriteAcknowledgement(_) => IbcEventType::WriteAck,
             IbcEvent::AcknowledgePacket(_) => IbcEventType::AckPacket,
             IbcEvent::TimeoutPacket(_) => IbcEventType::Timeout,
             IbcEvent::TimeoutOnClosePacket(_) => IbcEventType::TimeoutOnClose,
+            IbcEvent::CrossChainQuery(_) => IbcEventType::CrossChainQuery,
             IbcEvent::AppModule(_) => IbcEventType::AppModule,
             IbcEvent::ChainError(_) => IbcEventType::ChainError,
         }
@@ -454,6 +554,20 @@ impl IbcEvent {
         }
     }
 
+    pub fn upgrade_attributes(&self) -> Option<&UpgradeAttributes> {
+        match self {
+            IbcEvent::UpgradeInitChannel(ev) => Some(ev.attributes()),
+            IbcEvent::UpgradeTryChannel(ev) => Some(ev.attributes()),
+            IbcEvent::UpgradeAckChannel(ev) => Some(ev.attributes()),
+            IbcEvent::UpgradeConfirmChannel(ev) => Some(ev.attributes()),
+            IbcEvent::UpgradeOpenChannel(ev) => Some(ev.attributes()),
+            IbcEvent::UpgradeTimeoutChannel(ev) => Some(ev.attributes()),
+            IbcEvent::UpgradeCancelChannel(ev) => Some(ev.attributes()),
+            IbcEvent::UpgradeErrorChannel(ev) => Some(ev.attributes()),
+            _ => None,
+        }
+    }
+
     pub fn packet(&self) -> Option<&Packet> {
         match self {
             IbcEvent::SendPacket(ev) => Some(&ev.packet),
@@ -76,5 +76,8 @@ mod test;
 #[cfg(any(test, feature = "mocks"))]
 pub mod test_utils;
 
+#[cfg(any(test, feature = "proptest"))]
+pub mod arbitrary; // Property-based testing strategies for the IBC domain types.
+
 #[cfg(any(test, feature = "mocks"))]
 pub mod mock; // Context mock, the underlying host chain, and client types: for testing all handlers.
@@ -0,0 +1,473 @@
+//! Implementation of the async host context traits for `MockContext`, by delegation to its
+//! synchronous implementation. Used in testing the async handlers against the synchronous ones.
+
+use crate::prelude::*;
+
+use async_trait::async_trait;
+use core::time::Duration;
+
+use crate::core::ics02_client::client_consensus::AnyConsensusState;
+use crate::core::ics02_client::client_state::AnyClientState;
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::context::{ClientKeeper, ClientReader};
+use crate::core::ics02_client::error::Error as Ics02Error;
+use crate::core::ics03_connection::connection::ConnectionEnd;
+use crate::core::ics03_connection::context::{ConnectionKeeper, ConnectionReader};
+use crate::core::ics03_connection::error::Error as Ics03Error;
+use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
+use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
+use crate::core::ics04_channel::error::Error as Ics04Error;
+use crate::core::ics04_channel::packet::{Receipt, Sequence};
+use crate::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
+use crate::core::ics05_port::context::PortReader;
+use crate::core::ics05_port::error::Error as Ics05Error;
+use crate::core::ics23_commitment::commitment::CommitmentPrefix;
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::core::ics26_routing::async_context::{
+    AsyncChannelKeeper, AsyncChannelReader, AsyncClientKeeper, AsyncClientReader,
+    AsyncConnectionKeeper, AsyncConnectionReader, AsyncIcs26Context, AsyncPortReader,
+};
+use crate::core::ics26_routing::context::{Ics26Context, ModuleId};
+use crate::mock::context::{MockContext, MockRouter};
+use crate::timestamp::Timestamp;
+use crate::Height;
+
+#[async_trait]
+impl AsyncClientReader for MockContext {
+    async fn client_type(&self, client_id: &ClientId) -> Result<Option<ClientType>, Ics02Error> {
+        Ok(ClientReader::client_type(self, client_id).ok())
+    }
+
+    async fn client_state(
+        &self,
+        client_id: &ClientId,
+    ) -> Result<Option<AnyClientState>, Ics02Error> {
+        Ok(ClientReader::client_state(self, client_id).ok())
+    }
+
+    async fn consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<Option<AnyConsensusState>, Ics02Error> {
+        ClientReader::maybe_consensus_state(self, client_id, height)
+    }
+
+    async fn next_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<Option<AnyConsensusState>, Ics02Error> {
+        ClientReader::next_consensus_state(self, client_id, height)
+    }
+
+    async fn prev_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<Option<AnyConsensusState>, Ics02Error> {
+        ClientReader::prev_consensus_state(self, client_id, height)
+    }
+
+    async fn host_height(&self) -> Height {
+        ClientReader::host_height(self)
+    }
+
+    async fn host_timestamp(&self) -> Timestamp {
+        ClientReader::host_timestamp(self)
+    }
+
+    async fn host_consensus_state(
+        &self,
+        height: Height,
+    ) -> Result<Option<AnyConsensusState>, Ics02Error> {
+        Ok(ClientReader::host_consensus_state(self, height).ok())
+    }
+
+    async fn pending_host_consensus_state(&self) -> Result<AnyConsensusState, Ics02Error> {
+        ClientReader::pending_host_consensus_state(self)
+    }
+
+    async fn client_counter(&self) -> Result<u64, Ics02Error> {
+        ClientReader::client_counter(self)
+    }
+}
+
+#[async_trait]
+impl AsyncClientKeeper for MockContext {
+    async fn store_client_type(
+        &mut self,
+        client_id: ClientId,
+        client_type: ClientType,
+    ) -> Result<(), Ics02Error> {
+        ClientKeeper::store_client_type(self, client_id, client_type)
+    }
+
+    async fn store_client_state(
+        &mut self,
+        client_id: ClientId,
+        client_state: AnyClientState,
+    ) -> Result<(), Ics02Error> {
+        ClientKeeper::store_client_state(self, client_id, client_state)
+    }
+
+    async fn store_consensus_state(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        consensus_state: AnyConsensusState,
+    ) -> Result<(), Ics02Error> {
+        ClientKeeper::store_consensus_state(self, client_id, height, consensus_state)
+    }
+
+    async fn increase_client_counter(&mut self) {
+        ClientKeeper::increase_client_counter(self)
+    }
+
+    async fn store_update_time(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        timestamp: Timestamp,
+    ) -> Result<(), Ics02Error> {
+        ClientKeeper::store_update_time(self, client_id, height, timestamp)
+    }
+
+    async fn store_update_height(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        host_height: Height,
+    ) -> Result<(), Ics02Error> {
+        ClientKeeper::store_update_height(self, client_id, height, host_height)
+    }
+}
+
+#[async_trait]
+impl AsyncConnectionReader for MockContext {
+    async fn connection_end(
+        &self,
+        conn_id: &ConnectionId,
+    ) -> Result<Option<ConnectionEnd>, Ics03Error> {
+        Ok(ConnectionReader::connection_end(self, conn_id).ok())
+    }
+
+    async fn host_oldest_height(&self) -> Height {
+        ConnectionReader::host_oldest_height(self)
+    }
+
+    async fn commitment_prefix(&self) -> CommitmentPrefix {
+        ConnectionReader::commitment_prefix(self)
+    }
+
+    async fn connection_counter(&self) -> Result<u64, Ics03Error> {
+        ConnectionReader::connection_counter(self)
+    }
+}
+
+#[async_trait]
+impl AsyncConnectionKeeper for MockContext {
+    async fn store_connection(
+        &mut self,
+        connection_id: ConnectionId,
+        connection_end: &ConnectionEnd,
+    ) -> Result<(), Ics03Error> {
+        ConnectionKeeper::store_connection(self, connection_id, connection_end)
+    }
+
+    async fn store_connection_to_client(
+        &mut self,
+        connection_id: ConnectionId,
+        client_id: &ClientId,
+    ) -> Result<(), Ics03Error> {
+        ConnectionKeeper::store_connection_to_client(self, connection_id, client_id)
+    }
+
+    async fn increase_connection_counter(&mut self) {
+        ConnectionKeeper::increase_connection_counter(self)
+    }
+}
+
+#[async_trait]
+impl AsyncChannelReader for MockContext {
+    async fn channel_end(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Option<ChannelEnd>, Ics04Error> {
+        Ok(ChannelReader::channel_end(self, port_channel_id).ok())
+    }
+
+    async fn connection_channels(
+        &self,
+        cid: &ConnectionId,
+    ) -> Result<Option<Vec<(PortId, ChannelId)>>, Ics04Error> {
+        Ok(ChannelReader::connection_channels(self, cid).ok())
+    }
+
+    async fn get_next_sequence_send(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Option<Sequence>, Ics04Error> {
+        Ok(ChannelReader::get_next_sequence_send(self, port_channel_id).ok())
+    }
+
+    async fn get_next_sequence_recv(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Option<Sequence>, Ics04Error> {
+        Ok(ChannelReader::get_next_sequence_recv(self, port_channel_id).ok())
+    }
+
+    async fn get_next_sequence_ack(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Option<Sequence>, Ics04Error> {
+        Ok(ChannelReader::get_next_sequence_ack(self, port_channel_id).ok())
+    }
+
+    async fn get_packet_commitment(
+        &self,
+        key: &(PortId, ChannelId, Sequence),
+    ) -> Result<Option<PacketCommitment>, Ics04Error> {
+        Ok(ChannelReader::get_packet_commitment(self, key).ok())
+    }
+
+    async fn get_packet_receipt(
+        &self,
+        key: &(PortId, ChannelId, Sequence),
+    ) -> Result<Option<Receipt>, Ics04Error> {
+        Ok(ChannelReader::get_packet_receipt(self, key).ok())
+    }
+
+    async fn get_packet_acknowledgement(
+        &self,
+        key: &(PortId, ChannelId, Sequence),
+    ) -> Result<Option<AcknowledgementCommitment>, Ics04Error> {
+        Ok(ChannelReader::get_packet_acknowledgement(self, key).ok())
+    }
+
+    async fn has_inflight_packets(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+        except: Option<Sequence>,
+    ) -> Result<bool, Ics04Error> {
+        ChannelReader::has_inflight_packets(self, port_channel_id, except)
+    }
+
+    async fn pruning_sequence(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Sequence, Ics04Error> {
+        ChannelReader::pruning_sequence(self, port_channel_id)
+    }
+
+    async fn upgrade(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Option<Upgrade>, Ics04Error> {
+        Ok(ChannelReader::upgrade(self, port_channel_id).ok())
+    }
+
+    async fn counterparty_upgrade(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Option<Upgrade>, Ics04Error> {
+        Ok(ChannelReader::counterparty_upgrade(self, port_channel_id).ok())
+    }
+
+    async fn upgrade_error_receipt(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Option<ErrorReceipt>, Ics04Error> {
+        Ok(ChannelReader::upgrade_error_receipt(self, port_channel_id).ok())
+    }
+
+    fn hash(&self, value: Vec<u8>) -> Vec<u8> {
+        ChannelReader::hash(self, value)
+    }
+
+    async fn client_update_time(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<Option<Timestamp>, Ics04Error> {
+        Ok(ChannelReader::client_update_time(self, client_id, height).ok())
+    }
+
+    async fn client_update_height(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<Option<Height>, Ics04Error> {
+        Ok(ChannelReader::client_update_height(self, client_id, height).ok())
+    }
+
+    async fn channel_counter(&self) -> Result<u64, Ics04Error> {
+        ChannelReader::channel_counter(self)
+    }
+
+    async fn max_expected_time_per_block(&self) -> Duration {
+        ChannelReader::max_expected_time_per_block(self)
+    }
+}
+
+#[async_trait]
+impl AsyncChannelKeeper for MockContext {
+    async fn store_packet_commitment(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+        commitment: PacketCommitment,
+    ) -> Result<(), Ics04Error> {
+        ChannelKeeper::store_packet_commitment(self, key, commitment)
+    }
+
+    async fn delete_packet_commitment(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+    ) -> Result<(), Ics04Error> {
+        ChannelKeeper::delete_packet_commitment(self, key)
+    }
+
+    async fn store_packet_receipt(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+        receipt: Receipt,
+    ) -> Result<(), Ics04Error> {
+        ChannelKeeper::store_packet_receipt(self, key, receipt)
+    }
+
+    async fn store_packet_acknowledgement(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+        ack_commitment: AcknowledgementCommitment,
+    ) -> Result<(), Ics04Error> {
+        ChannelKeeper::store_packet_acknowledgement(self, key, ack_commitment)
+    }
+
+    async fn delete_packet_acknowledgement(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+    ) -> Result<(), Ics04Error> {
+        ChannelKeeper::delete_packet_acknowledgement(self, key)
+    }
+
+    async fn delete_packet_receipt(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+    ) -> Result<(), Ics04Error> {
+        ChannelKeeper::delete_packet_receipt(self, key)
+    }
+
+    async fn store_pruning_sequence(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        seq: Sequence,
+    ) -> Result<(), Ics04Error> {
+        ChannelKeeper::store_pruning_sequence(self, port_channel_id, seq)
+    }
+
+    async fn store_connection_channels(
+        &mut self,
+        conn_id: ConnectionId,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<(), Ics04Error> {
+        ChannelKeeper::store_connection_channels(self, conn_id, port_channel_id)
+    }
+
+    async fn store_channel(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        channel_end: &ChannelEnd,
+    ) -> Result<(), Ics04Error> {
+        ChannelKeeper::store_channel(self, port_channel_id, channel_end)
+    }
+
+    async fn store_next_sequence_send(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        seq: Sequence,
+    ) -> Result<(), Ics04Error> {
+        ChannelKeeper::store_next_sequence_send(self, port_channel_id, seq)
+    }
+
+    async fn store_next_sequence_recv(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        seq: Sequence,
+    ) -> Result<(), Ics04Error> {
+        ChannelKeeper::store_next_sequence_recv(self, port_channel_id, seq)
+    }
+
+    async fn store_next_sequence_ack(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        seq: Sequence,
+    ) -> Result<(), Ics04Error> {
+        ChannelKeeper::store_next_sequence_ack(self, port_channel_id, seq)
+    }
+
+    async fn store_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        upgrade: Upgrade,
+    ) -> Result<(), Ics04Error> {
+        ChannelKeeper::store_upgrade(self, port_channel_id, upgrade)
+    }
+
+    async fn delete_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+    ) -> Result<(), Ics04Error> {
+        ChannelKeeper::delete_upgrade(self, port_channel_id)
+    }
+
+    async fn store_counterparty_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        upgrade: Upgrade,
+    ) -> Result<(), Ics04Error> {
+        ChannelKeeper::store_counterparty_upgrade(self, port_channel_id, upgrade)
+    }
+
+    async fn delete_counterparty_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+    ) -> Result<(), Ics04Error> {
+        ChannelKeeper::delete_counterparty_upgrade(self, port_channel_id)
+    }
+
+    async fn store_upgrade_error_receipt(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        error_receipt: ErrorReceipt,
+    ) -> Result<(), Ics04Error> {
+        ChannelKeeper::store_upgrade_error_receipt(self, port_channel_id, error_receipt)
+    }
+
+    async fn increase_channel_counter(&mut self) {
+        ChannelKeeper::increase_channel_counter(self)
+    }
+}
+
+#[async_trait]
+impl AsyncPortReader for MockContext {
+    async fn lookup_module_by_port(
+        &self,
+        port_id: &PortId,
+    ) -> Result<Option<ModuleId>, Ics05Error> {
+        Ok(PortReader::lookup_module_by_port(self, port_id).ok())
+    }
+}
+
+impl AsyncIcs26Context for MockContext {
+    type Router = MockRouter;
+
+    fn router(&self) -> &Self::Router {
+        Ics26Context::router(self)
+    }
+
+    fn router_mut(&mut self) -> &mut Self::Router {
+        Ics26Context::router_mut(self)
+    }
+}
@@ -1,4 +1,4 @@
-use ibc_proto::ibc::core::commitment::v1::MerkleProof;
+use ibc_proto::ibc::core::commitment::v1::{MerklePath, MerkleProof};
 
 use crate::core::ics02_client::client_consensus::AnyConsensusState;
 use crate::core::ics02_client::client_def::ClientDef;
@@ -9,7 +9,8 @@ use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics04_channel::channel::ChannelEnd;
 use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::context::ChannelReader;
-use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics04_channel::packet::{Receipt, Sequence};
+use crate::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use crate::core::ics23_commitment::commitment::{
     CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
 };
@@ -99,6 +100,34 @@ impl ClientDef for MockClient {
         Ok(())
     }
 
+    fn verify_channel_upgrade(
+        &self,
+        _client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _expected_upgrade: &Upgrade,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn verify_channel_upgrade_error(
+        &self,
+        _client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _expected_error_receipt: &ErrorReceipt,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
     fn verify_client_full_state(
         &self,
         _client_state: &Self::ClientState,
@@ -144,6 +173,36 @@ impl ClientDef for MockClient {
         Ok(())
     }
 
+    fn verify_packet_data_batch(
+        &self,
+        _ctx: &dyn ChannelReader,
+        _client_state: &Self::ClientState,
+        _height: Height,
+        _connection_end: &ConnectionEnd,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _commitments: Vec<(Sequence, PacketCommitment)>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn verify_packet_acknowledgement_batch(
+        &self,
+        _ctx: &dyn ChannelReader,
+        _client_state: &Self::ClientState,
+        _height: Height,
+        _connection_end: &ConnectionEnd,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _ack_commitments: Vec<(Sequence, AcknowledgementCommitment)>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
     fn verify_next_sequence_recv(
         &self,
         _ctx: &dyn ChannelReader,
@@ -159,6 +218,22 @@ impl ClientDef for MockClient {
         Ok(())
     }
 
+    fn verify_packet_receipt(
+        &self,
+        _ctx: &dyn ChannelReader,
+        _client_state: &Self::ClientState,
+        _height: Height,
+        _connection_end: &ConnectionEnd,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _sequence: Sequence,
+        _receipt: Receipt,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
     fn verify_packet_receipt_absence(
         &self,
         _ctx: &dyn ChannelReader,
@@ -174,6 +249,33 @@ impl ClientDef for MockClient {
         Ok(())
     }
 
+    fn verify_packet_commitment_absence(
+        &self,
+        _ctx: &dyn ChannelReader,
+        _client_state: &Self::ClientState,
+        _height: Height,
+        _connection_end: &ConnectionEnd,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _sequence: Sequence,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn verify_key_value(
+        &self,
+        _client_state: &Self::ClientState,
+        _height: Height,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        _path: MerklePath,
+        _value: Vec<u8>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
     fn verify_upgrade_and_update_state(
         &self,
         client_state: &Self::ClientState,
@@ -15,6 +15,14 @@ use ibc_proto::google::protobuf::Any;
 use sha2::Digest;
 use tracing::debug;
 
+use crate::applications::interchain_query::context::{QueryKeeper, QueryReader};
+use crate::applications::interchain_query::error::Error as QueryError;
+use crate::applications::interchain_query::query::{
+    CrossChainQuery, CrossChainQueryResult, QueryId,
+};
+use crate::applications::packet_forward::packet::InFlightPacket;
+use crate::applications::rate_limit::quota::{RateLimit, RateLimitPath};
+use crate::applications::transfer::{Amount, PrefixedDenom};
 use crate::clients::ics07_tendermint::client_state::test_util::get_dummy_tendermint_client_state;
 use crate::core::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
 use crate::core::ics02_client::client_state::AnyClientState;
@@ -30,11 +38,13 @@ use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCo
 use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
 use crate::core::ics04_channel::error::Error as Ics04Error;
 use crate::core::ics04_channel::packet::{Receipt, Sequence};
+use crate::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use crate::core::ics05_port::context::PortReader;
 use crate::core::ics05_port::error::Error as Ics05Error;
 use crate::core::ics05_port::error::Error;
-use crate::core::ics23_commitment::commitment::CommitmentPrefix;
+use crate::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
 use crate::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
+use crate::core::ics24_host::path::Path;
 use crate::core::ics26_routing::context::{Ics26Context, Module, ModuleId, Router, RouterBuilder};
 use crate::core::ics26_routing::handler::{deliver, dispatch, MsgReceipt};
 use crate::core::ics26_routing::msgs::Ics26Envelope;
@@ -402,10 +412,45 @@ impl MockContext {
         self
     }
 
+    /// Associates an upgrade in progress to a channel of this context.
+    pub fn with_channel_upgrade(
+        self,
+        port_id: PortId,
+        chan_id: ChannelId,
+        upgrade: Upgrade,
+    ) -> Self {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .channel_upgrades
+            .insert((port_id, chan_id), upgrade);
+        self
+    }
+
+    /// Associates the upgrade of the counterparty channel end to a channel of this context.
+    pub fn with_counterparty_upgrade(
+        self,
+        port_id: PortId,
+        chan_id: ChannelId,
+        upgrade: Upgrade,
+    ) -> Self {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .counterparty_upgrades
+            .insert((port_id, chan_id), upgrade);
+        self
+    }
+
     pub fn with_router(self, router: MockRouter) -> Self {
         Self { router, ..self }
     }
 
+    /// Accessor for the identifier of the local (host) chain of this context.
+    pub fn host_chain_id(&self) -> &ChainId {
+        &self.host_chain_id
+    }
+
     /// Accessor for a block of the local (host) chain from this context.
     /// Returns `None` if the block at the requested height does not exist.
     pub fn host_block(&self, target_height: Height) -> Option<&HostBlock> {
@@ -593,6 +638,45 @@ pub struct MockIbcStore {
 
     // Used by unordered channel
     pub packet_receipt: BTreeMap<(PortId, ChannelId, Sequence), Receipt>,
+
+    /// The balances of the accounts of the transfer module, per denomination.
+    pub balances: BTreeMap<(Signer, PrefixedDenom), Amount>,
+
+    /// The packets forwarded by the packet forwarding middleware, awaiting the next hop.
+    pub in_flight_packets: BTreeMap<(PortId, ChannelId, Sequence), InFlightPacket>,
+
+    /// The IBC events emitted by the applications outside of the message handlers' output.
+    pub app_events: Vec<IbcEvent>,
+
+    /// The rate limits of the rate limiting middleware.
+    pub rate_limits: BTreeMap<RateLimitPath, RateLimit>,
+
+    /// The host timestamps at which the outflow of the rate limited packets was recorded.
+    pub rate_limit_send_timestamps: BTreeMap<(PortId, ChannelId, Sequence), Timestamp>,
+
+    /// Maps each channel end to the sequence of its first packet which was not pruned.
+    pub pruning_sequences: BTreeMap<(PortId, ChannelId), Sequence>,
+
+    /// The upgrades in progress on the channels in the store.
+    pub channel_upgrades: BTreeMap<(PortId, ChannelId), Upgrade>,
+
+    /// The upgrades of the counterparty channel ends, stored once flushing starts.
+    pub counterparty_upgrades: BTreeMap<(PortId, ChannelId), Upgrade>,
+
+    /// The receipts of the last aborted upgrade of each channel.
+    pub upgrade_error_receipts: BTreeMap<(PortId, ChannelId), ErrorReceipt>,
+
+    /// The cross-chain queries waiting for an answer.
+    pub cross_chain_queries: BTreeMap<QueryId, CrossChainQuery>,
+
+    /// The results of the answered or timed-out cross-chain queries.
+    pub cross_chain_query_results: BTreeMap<QueryId, CrossChainQueryResult>,
+
+    /// Counter for cross-chain query identifiers (see `increase_query_counter`).
+    pub cross_chain_query_counter: u64,
+
+    /// The total amount of each denomination held in escrow by the transfer module.
+    pub total_escrows: BTreeMap<PrefixedDenom, Amount>,
 }
 
 #[derive(Default)]
@@ -776,6 +860,90 @@ impl ChannelReader for MockContext {
         }
     }
 
+    fn has_inflight_packets(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+        except: Option<Sequence>,
+    ) -> Result<bool, Ics04Error> {
+        let (port_id, channel_id) = port_channel_id.clone();
+        Ok(self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .packet_commitment
+            .range((port_id, channel_id, Sequence::from(0))..)
+            .map(|(key, _)| key)
+            .take_while(|(port_id, channel_id, _)| {
+                port_id == &port_channel_id.0 && channel_id == &port_channel_id.1
+            })
+            .any(|(_, _, seq)| Some(*seq) != except))
+    }
+
+    fn pruning_sequence(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Sequence, Ics04Error> {
+        Ok(self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .pruning_sequences
+            .get(port_channel_id)
+            .cloned()
+            .unwrap_or_else(|| Sequence::from(1)))
+    }
+
+    fn upgrade(&self, port_channel_id: &(PortId, ChannelId)) -> Result<Upgrade, Ics04Error> {
+        match self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .channel_upgrades
+            .get(port_channel_id)
+        {
+            Some(upgrade) => Ok(upgrade.clone()),
+            None => Err(Ics04Error::upgrade_not_found(
+                port_channel_id.0.clone(),
+                port_channel_id.1.clone(),
+            )),
+        }
+    }
+
+    fn counterparty_upgrade(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Upgrade, Ics04Error> {
+        match self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .counterparty_upgrades
+            .get(port_channel_id)
+        {
+            Some(upgrade) => Ok(upgrade.clone()),
+            None => Err(Ics04Error::counterparty_upgrade_not_found(
+                port_channel_id.0.clone(),
+                port_channel_id.1.clone(),
+            )),
+        }
+    }
+
+    fn upgrade_error_receipt(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+    ) -> Result<ErrorReceipt, Ics04Error> {
+        match self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .upgrade_error_receipts
+            .get(port_channel_id)
+        {
+            Some(error_receipt) => Ok(error_receipt.clone()),
+            None => Err(Ics04Error::missing_error_receipt()),
+        }
+    }
+
     fn hash(&self, value: Vec<u8>) -> Vec<u8> {
         sha2::Sha256::digest(value).to_vec()
     }
@@ -952,6 +1120,66 @@ impl ChannelKeeper for MockContext {
         Ok(())
     }
 
+    fn store_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        upgrade: Upgrade,
+    ) -> Result<(), Ics04Error> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .channel_upgrades
+            .insert(port_channel_id, upgrade);
+        Ok(())
+    }
+
+    fn delete_upgrade(&mut self, port_channel_id: (PortId, ChannelId)) -> Result<(), Ics04Error> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .channel_upgrades
+            .remove(&port_channel_id);
+        Ok(())
+    }
+
+    fn store_counterparty_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        upgrade: Upgrade,
+    ) -> Result<(), Ics04Error> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .counterparty_upgrades
+            .insert(port_channel_id, upgrade);
+        Ok(())
+    }
+
+    fn delete_counterparty_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+    ) -> Result<(), Ics04Error> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .counterparty_upgrades
+            .remove(&port_channel_id);
+        Ok(())
+    }
+
+    fn store_upgrade_error_receipt(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        error_receipt: ErrorReceipt,
+    ) -> Result<(), Ics04Error> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .upgrade_error_receipts
+            .insert(port_channel_id, error_receipt);
+        Ok(())
+    }
+
     fn increase_channel_counter(&mut self) {
         self.ibc_store.lock().unwrap().channel_ids_counter += 1;
     }
@@ -980,6 +1208,27 @@ impl ChannelKeeper for MockContext {
             .insert(key, receipt);
         Ok(())
     }
+
+    fn delete_packet_receipt(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+    ) -> Result<(), Ics04Error> {
+        self.ibc_store.lock().unwrap().packet_receipt.remove(&key);
+        Ok(())
+    }
+
+    fn store_pruning_sequence(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        seq: Sequence,
+    ) -> Result<(), Ics04Error> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .pruning_sequences
+            .insert(port_channel_id, seq);
+        Ok(())
+    }
 }
 
 impl ConnectionReader for MockContext {
@@ -1278,6 +1527,63 @@ impl ClientKeeper for MockContext {
     }
 }
 
+impl QueryReader for MockContext {
+    fn get_query(&self, query_id: &QueryId) -> Option<CrossChainQuery> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .cross_chain_queries
+            .get(query_id)
+            .cloned()
+    }
+
+    fn get_query_result(&self, query_id: &QueryId) -> Option<CrossChainQueryResult> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .cross_chain_query_results
+            .get(query_id)
+            .cloned()
+    }
+
+    fn query_counter(&self) -> Result<u64, QueryError> {
+        Ok(self.ibc_store.lock().unwrap().cross_chain_query_counter)
+    }
+}
+
+impl QueryKeeper for MockContext {
+    fn store_query(&mut self, query: CrossChainQuery) -> Result<(), QueryError> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .cross_chain_queries
+            .insert(query.id.clone(), query);
+        Ok(())
+    }
+
+    fn delete_query(&mut self, query_id: &QueryId) -> Result<(), QueryError> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .cross_chain_queries
+            .remove(query_id);
+        Ok(())
+    }
+
+    fn store_query_result(&mut self, result: CrossChainQueryResult) -> Result<(), QueryError> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .cross_chain_query_results
+            .insert(result.id.clone(), result);
+        Ok(())
+    }
+
+    fn increase_query_counter(&mut self) {
+        self.ibc_store.lock().unwrap().cross_chain_query_counter += 1;
+    }
+}
+
 impl Ics18Context for MockContext {
     fn query_latest_height(&self) -> Height {
         self.host_current_height()
@@ -1288,11 +1594,45 @@ impl Ics18Context for MockContext {
         ClientReader::client_state(self, client_id).ok()
     }
 
+    fn query_latest_timestamp(&self) -> Timestamp {
+        self.history
+            .last()
+            .expect("history cannot be empty")
+            .timestamp()
+    }
+
     fn query_latest_header(&self) -> Option<AnyHeader> {
         let block_ref = self.host_block(self.host_current_height());
         block_ref.cloned().map(Into::into)
     }
 
+    fn query_connection(&self, connection_id: &ConnectionId) -> Option<ConnectionEnd> {
+        ConnectionReader::connection_end(self, connection_id).ok()
+    }
+
+    fn query_channel(&self, port_id: &PortId, channel_id: &ChannelId) -> Option<ChannelEnd> {
+        ChannelReader::channel_end(self, &(port_id.clone(), channel_id.clone())).ok()
+    }
+
+    fn query_next_sequence_recv(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Option<Sequence> {
+        ChannelReader::get_next_sequence_recv(self, &(port_id.clone(), channel_id.clone())).ok()
+    }
+
+    fn query_commitment_prefix(&self) -> CommitmentPrefix {
+        ConnectionReader::commitment_prefix(self)
+    }
+
+    fn query_proof(&self, path: &Path, height: Height) -> Option<CommitmentProofBytes> {
+        // Mock clients do not verify proofs, so any non-empty bytes will do, as long as the
+        // block the proof refers to is part of the history of this chain.
+        self.host_block(height)?;
+        path.to_string().into_bytes().try_into().ok()
+    }
+
     fn send(&mut self, msgs: Vec<Any>) -> Result<Vec<IbcEvent>, Ics18Error> {
         // Forward call to Ics26 delivery method.
         let mut all_events = vec![];
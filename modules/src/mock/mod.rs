@@ -1,8 +1,11 @@
 //! Implementation of mocks for context, host chain, and client.
 
+#[cfg(feature = "async")]
+pub mod async_context;
 pub mod client_def;
 pub mod client_state;
 pub mod context;
 pub mod header;
 pub mod host;
 pub mod misbehaviour;
+pub mod network;
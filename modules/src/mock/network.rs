@@ -0,0 +1,450 @@
+//! An in-memory network of mock chains, relayed by the ICS18 relaying algorithm.
+//!
+//! A [`MockNetwork`] owns several [`MockContext`]s and forwards the events they emit to their
+//! counterparties with [`relay_event`]. Events are not relayed as soon as they are emitted but are
+//! instead queued as [`PendingEvent`]s, which tests may drop, delay or reorder before calling
+//! [`MockNetwork::step`], so as to script deterministic end-to-end scenarios.
+
+use alloc::collections::btree_map::BTreeMap;
+
+use ibc_proto::google::protobuf::Any;
+
+use crate::core::ics02_client::header::AnyHeader;
+use crate::core::ics02_client::msgs::create_client::MsgCreateAnyClient;
+use crate::core::ics24_host::identifier::{ChainId, ClientId};
+use crate::events::IbcEvent;
+use crate::mock::client_state::{MockClientState, MockConsensusState};
+use crate::mock::context::MockContext;
+use crate::prelude::*;
+use crate::relayer::ics18_relayer::context::Ics18Context;
+use crate::relayer::ics18_relayer::error::Error;
+use crate::relayer::ics18_relayer::relay::{relay_event, relay_target_client};
+use crate::tx_msg::Msg;
+
+/// An event emitted by the chain `src`, waiting to be relayed to the chain `dst`.
+#[derive(Clone, Debug)]
+pub struct PendingEvent {
+    pub src: ChainId,
+    pub dst: ChainId,
+    pub event: IbcEvent,
+    /// Number of steps of the network to wait for before relaying the event.
+    pub delay: u64,
+}
+
+/// A network of mock chains connected by mock clients. Each call to [`MockNetwork::step`] relays
+/// the pending events which are due, then advances every chain by one block.
+#[derive(Clone, Debug, Default)]
+pub struct MockNetwork {
+    chains: Vec<MockContext>,
+    /// Maps a client, identified by its host chain and its identifier, to the chain it tracks.
+    clients: BTreeMap<(ChainId, ClientId), ChainId>,
+    pending: Vec<PendingEvent>,
+}
+
+impl MockNetwork {
+    /// Adds a chain to the network. The chain must have a `Mock` host type, and its router
+    /// should already hold the modules that the test exercises.
+    pub fn with_chain(mut self, ctx: MockContext) -> Self {
+        assert!(
+            self.position(ctx.host_chain_id()).is_none(),
+            "chain {} is already part of the network",
+            ctx.host_chain_id()
+        );
+        self.chains.push(ctx);
+        self
+    }
+
+    /// Accessor for the chain with identifier `chain_id`.
+    pub fn chain(&self, chain_id: &ChainId) -> &MockContext {
+        &self.chains[self.index(chain_id)]
+    }
+
+    /// Mutable accessor for the chain with identifier `chain_id`, e.g., for an application to
+    /// update its state directly. Events emitted in the process should be handed to
+    /// [`MockNetwork::emit`] to get relayed.
+    pub fn chain_mut(&mut self, chain_id: &ChainId) -> &mut MockContext {
+        let index = self.index(chain_id);
+        &mut self.chains[index]
+    }
+
+    /// Creates a mock client for each of the chains `a` and `b` on the other one, and returns
+    /// the identifiers of the client on `a` and of the client on `b`, respectively.
+    pub fn connect(&mut self, a: &ChainId, b: &ChainId) -> Result<(ClientId, ClientId), Error> {
+        let client_on_a = self.create_client(a, b)?;
+        let client_on_b = self.create_client(b, a)?;
+        Ok((client_on_a, client_on_b))
+    }
+
+    /// Submits `msgs` to the chain `chain_id` in a single transaction, queues the emitted events
+    /// which need relaying and returns all the emitted events.
+    pub fn inject(&mut self, chain_id: &ChainId, msgs: Vec<Any>) -> Result<Vec<IbcEvent>, Error> {
+        let events = self.chain_mut(chain_id).send(msgs)?;
+        self.emit(chain_id, events.clone());
+        Ok(events)
+    }
+
+    /// Queues the events among `events`, emitted by the chain `chain_id`, which need relaying.
+    pub fn emit(&mut self, chain_id: &ChainId, events: Vec<IbcEvent>) {
+        let src = self.chain(chain_id);
+        let pending: Vec<_> = events
+            .into_iter()
+            .filter_map(|event| {
+                let client_id = relay_target_client(src, &event)?;
+                let dst = self.clients.get(&(chain_id.clone(), client_id))?.clone();
+                Some(PendingEvent {
+                    src: chain_id.clone(),
+                    dst,
+                    event,
+                    delay: 0,
+                })
+            })
+            .collect();
+        self.pending.extend(pending);
+    }
+
+    /// The events waiting to be relayed, in the order in which they will be relayed.
+    pub fn pending(&self) -> &[PendingEvent] {
+        &self.pending
+    }
+
+    /// Mutable access to the events waiting to be relayed, e.g., to reorder them.
+    pub fn pending_mut(&mut self) -> &mut Vec<PendingEvent> {
+        &mut self.pending
+    }
+
+    /// Drops the pending events matching `predicate`, which are then never relayed, and returns
+    /// them.
+    pub fn drop_pending<P>(&mut self, mut predicate: P) -> Vec<PendingEvent>
+    where
+        P: FnMut(&PendingEvent) -> bool,
+    {
+        let (dropped, kept): (Vec<_>, Vec<_>) = self.pending.drain(..).partition(|p| predicate(p));
+        self.pending = kept;
+        dropped
+    }
+
+    /// Delays the relaying of the pending events matching `predicate` by `steps` more steps.
+    pub fn delay_pending<P>(&mut self, steps: u64, mut predicate: P)
+    where
+        P: FnMut(&PendingEvent) -> bool,
+    {
+        self.pending
+            .iter_mut()
+            .filter(|p| predicate(p))
+            .for_each(|p| p.delay += steps);
+    }
+
+    /// Relays, in order, the pending events which are due, then advances every chain by one
+    /// block. The events emitted while relaying are queued to be relayed in the next step, and
+    /// are also returned, each along with the chain that emitted it.
+    pub fn step(&mut self) -> Result<Vec<(ChainId, IbcEvent)>, Error> {
+        let (due, waiting): (Vec<_>, Vec<_>) = self.pending.drain(..).partition(|p| p.delay == 0);
+        self.pending = waiting
+            .into_iter()
+            .map(|p| PendingEvent {
+                delay: p.delay - 1,
+                ..p
+            })
+            .collect();
+
+        let mut emitted = vec![];
+        for PendingEvent {
+            src, dst, event, ..
+        } in due
+        {
+            let (src_ctx, dst_ctx) = self.pair_mut(&src, &dst);
+            let output = relay_event(src_ctx, dst_ctx, &event)?;
+
+            self.emit(&src, output.src_events.clone());
+            self.emit(&dst, output.dst_events.clone());
+            emitted.extend(output.src_events.into_iter().map(|e| (src.clone(), e)));
+            emitted.extend(output.dst_events.into_iter().map(|e| (dst.clone(), e)));
+        }
+
+        for ctx in self.chains.iter_mut() {
+            ctx.advance_host_chain_height();
+        }
+
+        Ok(emitted)
+    }
+
+    /// Steps the network until no events are pending, or until `max_steps` steps have been
+    /// taken. Returns all the events emitted in the process.
+    pub fn run(&mut self, max_steps: usize) -> Result<Vec<(ChainId, IbcEvent)>, Error> {
+        let mut emitted = vec![];
+        for _ in 0..max_steps {
+            if self.pending.is_empty() {
+                break;
+            }
+            emitted.append(&mut self.step()?);
+        }
+        Ok(emitted)
+    }
+
+    /// Creates a mock client on `host` tracking the latest header of `target`.
+    fn create_client(&mut self, host: &ChainId, target: &ChainId) -> Result<ClientId, Error> {
+        let header = match self.chain(target).query_latest_header() {
+            Some(AnyHeader::Mock(header)) => header,
+            _ => panic!("chain {} must have a Mock host type", target),
+        };
+
+        let msg = MsgCreateAnyClient::new(
+            MockClientState::new(header).into(),
+            MockConsensusState::new(header).into(),
+            self.chain(host).signer(),
+        )
+        .expect("mock client and consensus states have the same type");
+
+        let events = self.chain_mut(host).send(vec![msg.to_any()])?;
+        let client_id = events
+            .iter()
+            .find_map(|event| match event {
+                IbcEvent::CreateClient(ev) => Some(ev.client_id().clone()),
+                _ => None,
+            })
+            .expect("client creation emits a `CreateClient` event");
+
+        self.clients
+            .insert((host.clone(), client_id.clone()), target.clone());
+        Ok(client_id)
+    }
+
+    fn position(&self, chain_id: &ChainId) -> Option<usize> {
+        self.chains
+            .iter()
+            .position(|ctx| ctx.host_chain_id() == chain_id)
+    }
+
+    fn index(&self, chain_id: &ChainId) -> usize {
+        self.position(chain_id)
+            .unwrap_or_else(|| panic!("chain {} is not part of the network", chain_id))
+    }
+
+    /// Returns mutable references to the two distinct chains `a` and `b`.
+    fn pair_mut(&mut self, a: &ChainId, b: &ChainId) -> (&mut MockContext, &mut MockContext) {
+        let (i, j) = (self.index(a), self.index(b));
+        assert_ne!(i, j, "a chain cannot relay to itself");
+
+        if i < j {
+            let (left, right) = self.chains.split_at_mut(j);
+            (&mut left[i], &mut right[0])
+        } else {
+            let (left, right) = self.chains.split_at_mut(i);
+            (&mut right[0], &mut left[j])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::applications::transfer::MODULE_ID_STR;
+    use crate::core::ics03_connection::connection::{
+        Counterparty as ConnCounterparty, State as ConnState,
+    };
+    use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
+    use crate::core::ics04_channel::handler::send_packet::send_packet;
+    use crate::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+    use crate::core::ics04_channel::packet::{Packet, Sequence};
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChainId, ChannelId, ConnectionId, PortId};
+    use crate::core::ics26_routing::context::{ModuleId, RouterBuilder};
+    use crate::events::IbcEvent;
+    use crate::handler::HandlerOutput;
+    use crate::mock::context::{MockContext, MockRouterBuilder};
+    use crate::mock::host::HostType;
+    use crate::mock::network::MockNetwork;
+    use crate::prelude::*;
+    use crate::relayer::ics18_relayer::context::Ics18Context;
+    use crate::test_utils::DummyTransferModule;
+    use crate::timestamp::{Timestamp, ZERO_DURATION};
+    use crate::tx_msg::Msg;
+    use crate::Height;
+
+    fn mock_chain(chain_id: &ChainId) -> MockContext {
+        let ctx = MockContext::new(
+            chain_id.clone(),
+            HostType::Mock,
+            10,
+            Height::new(1, 5).unwrap(),
+        );
+
+        let module_id: ModuleId = MODULE_ID_STR.parse().unwrap();
+        let module = DummyTransferModule::new(ctx.ibc_store_share());
+        let router = MockRouterBuilder::default()
+            .add_route(module_id.clone(), module)
+            .unwrap()
+            .build();
+
+        let mut ctx = ctx.with_router(router);
+        ctx.scope_port_to_module(PortId::transfer(), module_id);
+        ctx
+    }
+
+    /// Builds a network of two chains, with an open connection and an open unordered transfer
+    /// channel between them.
+    fn open_network() -> (MockNetwork, ChainId, ChainId) {
+        let (a, b) = (
+            ChainId::new("mockgaiaA".to_string(), 1),
+            ChainId::new("mockgaiaB".to_string(), 1),
+        );
+        let mut network = MockNetwork::default()
+            .with_chain(mock_chain(&a))
+            .with_chain(mock_chain(&b));
+        let (client_on_a, client_on_b) = network.connect(&a, &b).unwrap();
+
+        let msg = MsgConnectionOpenInit {
+            client_id: client_on_a,
+            counterparty: ConnCounterparty::new(
+                client_on_b,
+                None,
+                network.chain(&b).query_commitment_prefix(),
+            ),
+            version: None,
+            delay_period: ZERO_DURATION,
+            signer: network.chain(&a).signer(),
+        };
+        network.inject(&a, vec![msg.to_any()]).unwrap();
+        network.run(10).unwrap();
+        assert!(network.pending().is_empty());
+
+        for chain_id in [&a, &b] {
+            let conn = network
+                .chain(chain_id)
+                .query_connection(&ConnectionId::new(0))
+                .unwrap();
+            assert_eq!(conn.state(), &ConnState::Open);
+        }
+
+        let msg = MsgChannelOpenInit {
+            port_id: PortId::transfer(),
+            channel: ChannelEnd::new(
+                State::Init,
+                Order::Unordered,
+                Counterparty::new(PortId::transfer(), None),
+                vec![ConnectionId::new(0)],
+                Version::ics20(),
+            ),
+            signer: network.chain(&a).signer(),
+        };
+        network.inject(&a, vec![msg.to_any()]).unwrap();
+        network.run(10).unwrap();
+        assert!(network.pending().is_empty());
+
+        for chain_id in [&a, &b] {
+            let chan = network
+                .chain(chain_id)
+                .query_channel(&PortId::transfer(), &ChannelId::new(0))
+                .unwrap();
+            assert_eq!(chan.state(), &State::Open);
+        }
+
+        (network, a, b)
+    }
+
+    fn dummy_packet(sequence: u64, timeout_height: Height) -> Packet {
+        Packet {
+            sequence: sequence.into(),
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(0),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(0),
+            data: b"ping".to_vec(),
+            timeout_height: timeout_height.into(),
+            timeout_timestamp: Timestamp::none(),
+        }
+    }
+
+    /// Sends `packet` on the chain `chain_id`, as an application would.
+    fn send(network: &mut MockNetwork, chain_id: &ChainId, packet: Packet) {
+        let ctx = network.chain_mut(chain_id);
+        let HandlerOutput { result, events, .. } = send_packet(&*ctx, packet).unwrap();
+        ctx.store_packet_result(result).unwrap();
+        network.emit(chain_id, events);
+    }
+
+    fn packet_key(packet: &Packet) -> (PortId, ChannelId, Sequence) {
+        (
+            packet.source_port.clone(),
+            packet.source_channel.clone(),
+            packet.sequence,
+        )
+    }
+
+    #[test]
+    fn relay_reordered_packets() {
+        let (mut network, a, b) = open_network();
+
+        let packets: Vec<_> = (1..=2)
+            .map(|seq| dummy_packet(seq, Height::new(1, 1000).unwrap()))
+            .collect();
+        for packet in packets.iter() {
+            send(&mut network, &a, packet.clone());
+        }
+        assert_eq!(network.pending().len(), 2);
+        network.pending_mut().reverse();
+
+        let emitted = network.run(10).unwrap();
+        let received: Vec<_> = emitted
+            .iter()
+            .filter_map(|(chain_id, e)| match e {
+                IbcEvent::ReceivePacket(ev) if chain_id == &b => Some(ev.packet.sequence),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(received, vec![2.into(), 1.into()]);
+
+        for packet in packets.iter() {
+            assert!(network
+                .chain(&b)
+                .get_packet_receipt(&packet_key(packet))
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn dropped_packet_is_not_received() {
+        let (mut network, a, b) = open_network();
+
+        let packet = dummy_packet(1, Height::new(1, 1000).unwrap());
+        send(&mut network, &a, packet.clone());
+        let dropped = network.drop_pending(|p| matches!(p.event, IbcEvent::SendPacket(_)));
+        assert_eq!(dropped.len(), 1);
+
+        network.run(10).unwrap();
+        assert!(network
+            .chain(&b)
+            .get_packet_receipt(&packet_key(&packet))
+            .is_err());
+        assert!(network
+            .chain(&a)
+            .get_packet_commitment(&packet_key(&packet))
+            .is_ok());
+    }
+
+    #[test]
+    fn delayed_packet_times_out() {
+        let (mut network, a, b) = open_network();
+
+        let timeout_height = network.chain(&b).query_latest_height().add(2);
+        let packet = dummy_packet(1, timeout_height);
+        send(&mut network, &a, packet.clone());
+        network.delay_pending(3, |p| p.src == a);
+
+        let emitted = network.run(10).unwrap();
+        assert!(emitted
+            .iter()
+            .any(|(chain_id, e)| chain_id == &a && matches!(e, IbcEvent::TimeoutPacket(_))));
+        assert!(network
+            .chain(&b)
+            .get_packet_receipt(&packet_key(&packet))
+            .is_err());
+        assert!(network
+            .chain(&a)
+            .get_packet_commitment(&packet_key(&packet))
+            .is_err());
+    }
+}
@@ -3,11 +3,17 @@ use ibc_proto::google::protobuf::Any;
 
 use crate::core::ics02_client::client_state::AnyClientState;
 use crate::core::ics02_client::header::AnyHeader;
+use crate::core::ics03_connection::connection::ConnectionEnd;
+use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
 use crate::events::IbcEvent;
 
-use crate::core::ics24_host::identifier::ClientId;
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::core::ics24_host::path::Path;
 use crate::relayer::ics18_relayer::error::Error;
 use crate::signer::Signer;
+use crate::timestamp::Timestamp;
 use crate::Height;
 /// Trait capturing all dependencies (i.e., the context) which algorithms in ICS18 require to
 /// relay packets between chains. This trait comprises the dependencies towards a single chain.
@@ -22,9 +28,33 @@ pub trait Ics18Context {
     /// Wrapper over the `/abci_query?path=..` endpoint.
     fn query_client_full_state(&self, client_id: &ClientId) -> Option<AnyClientState>;
 
+    /// Returns the timestamp of the latest block of the chain.
+    fn query_latest_timestamp(&self) -> Timestamp;
+
     /// Returns the most advanced header of this chain.
     fn query_latest_header(&self) -> Option<AnyHeader>;
 
+    /// Returns the connection end with the given `connection_id` on this chain.
+    fn query_connection(&self, connection_id: &ConnectionId) -> Option<ConnectionEnd>;
+
+    /// Returns the channel end identified by `port_id` and `channel_id` on this chain.
+    fn query_channel(&self, port_id: &PortId, channel_id: &ChannelId) -> Option<ChannelEnd>;
+
+    /// Returns the next sequence number this chain expects to receive on the given channel.
+    fn query_next_sequence_recv(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Option<Sequence>;
+
+    /// Returns the prefix under which this chain commits to its IBC store.
+    fn query_commitment_prefix(&self) -> CommitmentPrefix;
+
+    /// Returns a proof of the value (or absence of a value) stored at `path` on this chain, as
+    /// committed to by the block at `height`.
+    /// Wrapper over the `/abci_query?path=..&prove=true` endpoint.
+    fn query_proof(&self, path: &Path, height: Height) -> Option<CommitmentProofBytes>;
+
     /// Interface that the relayer uses to submit a datagram to this chain.
     /// One can think of this as wrapping around the `/broadcast_tx_commit` ABCI endpoint.
     fn send(&mut self, msgs: Vec<Any>) -> Result<Vec<IbcEvent>, Error>;
@@ -1,5 +1,8 @@
-use crate::core::ics24_host::identifier::ClientId;
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::core::ics24_host::path::Path;
 use crate::core::ics26_routing::error::Error as RoutingError;
+use crate::events::IbcEvent;
+use crate::proofs::ProofError;
 use crate::Height;
 use flex_error::define_error;
 
@@ -34,5 +37,32 @@ define_error! {
         TransactionFailed
             [ RoutingError ]
             | _ | { "transaction processing by modules failed" },
+
+        HeaderNotFound
+            | _ | { "the source chain has no latest header" },
+
+        ConnectionNotFound
+            { connection_id: ConnectionId }
+            | e | { format_args!("connection end not found (connection id: {0})", e.connection_id) },
+
+        ChannelNotFound
+            { port_id: PortId, channel_id: ChannelId }
+            | e | { format_args!("channel end not found (port id: {0}, channel id: {1})", e.port_id, e.channel_id) },
+
+        NextSequenceRecvNotFound
+            { port_id: PortId, channel_id: ChannelId }
+            | e | { format_args!("next receive sequence not found (port id: {0}, channel id: {1})", e.port_id, e.channel_id) },
+
+        ProofNotFound
+            { path: Path, height: Height }
+            | e | { format_args!("proof not found (path: {0}, height: {1})", e.path, e.height) },
+
+        MissingIdentifier
+            { event: IbcEvent }
+            | e | { format_args!("event is missing an identifier required for relaying: {0}", e.event) },
+
+        InvalidProof
+            [ ProofError ]
+            | _ | { "failed to assemble proofs" },
     }
 }
@@ -2,4 +2,5 @@
 
 pub mod context;
 pub mod error;
+pub mod relay;
 pub mod utils;
@@ -0,0 +1,862 @@
+//! A transport-free relaying algorithm operating over two [`Ics18Context`]s.
+//!
+//! The algorithm is driven by the events that each chain returns from [`Ics18Context::send`]:
+//! every event that requires a reaction from the counterparty chain (a handshake step, a packet
+//! to be received, acknowledged or timed out) is turned into the corresponding datagram, which is
+//! then submitted to the appropriate chain, preceded by the client updates it depends upon.
+
+use alloc::collections::VecDeque;
+
+use crate::prelude::*;
+
+use crate::core::ics02_client::client_state::AnyClientState;
+use crate::core::ics02_client::header::Header;
+use crate::core::ics02_client::msgs::ClientMsg;
+use crate::core::ics03_connection::connection::{ConnectionEnd, Counterparty as ConnCounterparty};
+use crate::core::ics03_connection::msgs::conn_open_ack::MsgConnectionOpenAck;
+use crate::core::ics03_connection::msgs::conn_open_confirm::MsgConnectionOpenConfirm;
+use crate::core::ics03_connection::msgs::conn_open_try::MsgConnectionOpenTry;
+use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+use crate::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
+use crate::core::ics04_channel::msgs::chan_close_confirm::MsgChannelCloseConfirm;
+use crate::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
+use crate::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
+use crate::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
+use crate::core::ics04_channel::msgs::timeout::MsgTimeout;
+use crate::core::ics04_channel::packet::Packet;
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::core::ics24_host::path::{
+    AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath, CommitmentsPath,
+    ConnectionsPath, Path, ReceiptsPath, SeqRecvsPath,
+};
+use crate::events::IbcEvent;
+use crate::proofs::{ConsensusProof, Proofs};
+use crate::relayer::ics18_relayer::context::Ics18Context;
+use crate::relayer::ics18_relayer::error::{Error, ErrorDetail};
+use crate::relayer::ics18_relayer::utils::build_client_update_datagram;
+use crate::tx_msg::Msg;
+use crate::Height;
+
+/// Identifies one of the two chains between which [`relay`] operates.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Side {
+    A,
+    B,
+}
+
+impl Side {
+    /// Returns the other chain.
+    pub fn counterparty(self) -> Self {
+        match self {
+            Side::A => Side::B,
+            Side::B => Side::A,
+        }
+    }
+}
+
+/// The events emitted by both chains while relaying a single event with [`relay_event`].
+#[derive(Clone, Debug, Default)]
+pub struct RelayOutput {
+    /// Events emitted by the chain on which the relayed event occurred.
+    pub src_events: Vec<IbcEvent>,
+    /// Events emitted by the counterparty chain.
+    pub dst_events: Vec<IbcEvent>,
+}
+
+/// Relays events between the chains `ctx_a` and `ctx_b`, starting from the given `events`, each
+/// tagged with the chain that emitted it. The events emitted while relaying are in turn relayed,
+/// until neither chain has anything left to react to. Returns all the events that were emitted
+/// along the way, each tagged with the chain that emitted it.
+pub fn relay<A, B>(
+    ctx_a: &mut A,
+    ctx_b: &mut B,
+    events: Vec<(Side, IbcEvent)>,
+) -> Result<Vec<(Side, IbcEvent)>, Error>
+where
+    A: Ics18Context,
+    B: Ics18Context,
+{
+    let mut pending: VecDeque<(Side, IbcEvent)> = events.into_iter().collect();
+    let mut emitted = vec![];
+
+    while let Some((side, event)) = pending.pop_front() {
+        let output = match side {
+            Side::A => relay_event(ctx_a, ctx_b, &event)?,
+            Side::B => relay_event(ctx_b, ctx_a, &event)?,
+        };
+
+        let src_events = output.src_events.into_iter().map(|e| (side, e));
+        let dst_events = output
+            .dst_events
+            .into_iter()
+            .map(|e| (side.counterparty(), e));
+
+        for tagged in src_events.chain(dst_events) {
+            pending.push_back(tagged.clone());
+            emitted.push(tagged);
+        }
+    }
+
+    Ok(emitted)
+}
+
+/// Relays a single `event`, emitted by the `src` chain, to the `dst` chain. Events which do not
+/// require any reaction from `dst` are ignored.
+///
+/// Every datagram is preceded by an update of the client on the receiving chain, so that the
+/// proofs it carries can be verified against the latest header of the proving chain.
+pub fn relay_event<Src, Dst>(
+    src: &mut Src,
+    dst: &mut Dst,
+    event: &IbcEvent,
+) -> Result<RelayOutput, Error>
+where
+    Src: Ics18Context,
+    Dst: Ics18Context,
+{
+    let mut output = RelayOutput::default();
+
+    let msg = match event {
+        IbcEvent::OpenInitConnection(ev) => {
+            let src_conn_id = expect_id(ev.attributes().connection_id.as_ref(), event)?;
+            let src_conn = query_connection(src, src_conn_id)?;
+            let (client_state, proofs) =
+                build_conn_proofs(src, dst, src_conn_id, &src_conn, &mut output)?;
+
+            MsgConnectionOpenTry {
+                previous_connection_id: None,
+                client_id: src_conn.counterparty().client_id().clone(),
+                client_state: Some(client_state),
+                counterparty: ConnCounterparty::new(
+                    src_conn.client_id().clone(),
+                    Some(src_conn_id.clone()),
+                    src.query_commitment_prefix(),
+                ),
+                counterparty_versions: src_conn.versions().to_vec(),
+                proofs,
+                delay_period: src_conn.delay_period(),
+                signer: dst.signer(),
+            }
+            .to_any()
+        }
+        IbcEvent::OpenTryConnection(ev) => {
+            let src_conn_id = expect_id(ev.attributes().connection_id.as_ref(), event)?;
+            let src_conn = query_connection(src, src_conn_id)?;
+            let dst_conn_id = expect_id(src_conn.counterparty().connection_id(), event)?;
+            let (client_state, proofs) =
+                build_conn_proofs(src, dst, src_conn_id, &src_conn, &mut output)?;
+
+            MsgConnectionOpenAck {
+                connection_id: dst_conn_id.clone(),
+                counterparty_connection_id: src_conn_id.clone(),
+                client_state: Some(client_state),
+                proofs,
+                version: src_conn.versions()[0].clone(),
+                signer: dst.signer(),
+            }
+            .to_any()
+        }
+        IbcEvent::OpenAckConnection(ev) => {
+            let src_conn_id = expect_id(ev.attributes().connection_id.as_ref(), event)?;
+            let src_conn = query_connection(src, src_conn_id)?;
+            let dst_conn_id = expect_id(src_conn.counterparty().connection_id(), event)?;
+            let height = update_client(
+                src,
+                dst,
+                src_conn.counterparty().client_id(),
+                &mut output.dst_events,
+            )?;
+            let proofs = Proofs::new(
+                query_proof(src, ConnectionsPath(src_conn_id.clone()), height)?,
+                None,
+                None,
+                None,
+                height,
+            )
+            .map_err(Error::invalid_proof)?;
+
+            MsgConnectionOpenConfirm {
+                connection_id: dst_conn_id.clone(),
+                proofs,
+                signer: dst.signer(),
+            }
+            .to_any()
+        }
+        IbcEvent::OpenInitChannel(ev) => {
+            let (port_id, channel_id) = (ev.port_id(), expect_id(ev.channel_id(), event)?);
+            let (src_chan, src_conn, proofs) =
+                build_chan_proofs(src, dst, port_id, channel_id, &mut output)?;
+            let dst_conn_id = expect_id(src_conn.counterparty().connection_id(), event)?;
+
+            MsgChannelOpenTry {
+                port_id: src_chan.counterparty().port_id().clone(),
+                previous_channel_id: None,
+                channel: ChannelEnd::new(
+                    State::TryOpen,
+                    *src_chan.ordering(),
+                    Counterparty::new(port_id.clone(), Some(channel_id.clone())),
+                    vec![dst_conn_id.clone()],
+                    src_chan.version().clone(),
+                ),
+                counterparty_version: src_chan.version().clone(),
+                proofs,
+                signer: dst.signer(),
+            }
+            .to_any()
+        }
+        IbcEvent::OpenTryChannel(ev) => {
+            let (port_id, channel_id) = (ev.port_id(), expect_id(ev.channel_id(), event)?);
+            let (src_chan, _, proofs) =
+                build_chan_proofs(src, dst, port_id, channel_id, &mut output)?;
+            let dst_chan_id = expect_id(src_chan.counterparty().channel_id(), event)?;
+
+            MsgChannelOpenAck {
+                port_id: src_chan.counterparty().port_id().clone(),
+                channel_id: dst_chan_id.clone(),
+                counterparty_channel_id: channel_id.clone(),
+                counterparty_version: src_chan.version().clone(),
+                proofs,
+                signer: dst.signer(),
+            }
+            .to_any()
+        }
+        IbcEvent::OpenAckChannel(ev) => {
+            let (port_id, channel_id) = (ev.port_id(), expect_id(ev.channel_id(), event)?);
+            let (src_chan, _, proofs) =
+                build_chan_proofs(src, dst, port_id, channel_id, &mut output)?;
+            let dst_chan_id = expect_id(src_chan.counterparty().channel_id(), event)?;
+
+            MsgChannelOpenConfirm {
+                port_id: src_chan.counterparty().port_id().clone(),
+                channel_id: dst_chan_id.clone(),
+                proofs,
+                signer: dst.signer(),
+            }
+            .to_any()
+        }
+        IbcEvent::CloseInitChannel(ev) => {
+            let (src_chan, _, proofs) =
+                build_chan_proofs(src, dst, &ev.port_id, &ev.channel_id, &mut output)?;
+            let dst_chan_id = expect_id(src_chan.counterparty().channel_id(), event)?;
+
+            MsgChannelCloseConfirm {
+                port_id: src_chan.counterparty().port_id().clone(),
+                channel_id: dst_chan_id.clone(),
+                proofs,
+                signer: dst.signer(),
+            }
+            .to_any()
+        }
+        IbcEvent::SendPacket(ev) => {
+            relay_send_packet(src, dst, &ev.packet, &mut output)?;
+            return Ok(output);
+        }
+        IbcEvent::WriteAcknowledgement(ev) => {
+            let packet = &ev.packet;
+            let src_client_id =
+                counterparty_client_id(src, &packet.destination_port, &packet.destination_channel)?;
+            let height = update_client(src, dst, &src_client_id, &mut output.dst_events)?;
+            let ack_path = AcksPath {
+                port_id: packet.destination_port.clone(),
+                channel_id: packet.destination_channel.clone(),
+                sequence: packet.sequence,
+            };
+            let proofs = Proofs::new(
+                query_proof(src, ack_path, height)?,
+                None,
+                None,
+                None,
+                height,
+            )
+            .map_err(Error::invalid_proof)?;
+
+            MsgAcknowledgement {
+                packet: packet.clone(),
+                acknowledgement: ev.ack.clone().into(),
+                proofs,
+                signer: dst.signer(),
+            }
+            .to_any()
+        }
+        _ => return Ok(output),
+    };
+
+    output.dst_events.append(&mut dst.send(vec![msg])?);
+
+    Ok(output)
+}
+
+/// Returns the identifier of the client on `ctx` which tracks the chain that `event`, emitted by
+/// `ctx`, must be relayed to, or `None` if [`relay_event`] does not relay this kind of event.
+pub fn relay_target_client<Ctx: Ics18Context>(ctx: &Ctx, event: &IbcEvent) -> Option<ClientId> {
+    let connection_client_id = |connection_id: Option<&ConnectionId>| {
+        let connection = ctx.query_connection(connection_id?)?;
+        Some(connection.client_id().clone())
+    };
+
+    let (port_id, channel_id) = match event {
+        IbcEvent::OpenInitConnection(ev) => return connection_client_id(ev.connection_id()),
+        IbcEvent::OpenTryConnection(ev) => return connection_client_id(ev.connection_id()),
+        IbcEvent::OpenAckConnection(ev) => return connection_client_id(ev.connection_id()),
+        IbcEvent::OpenInitChannel(ev) => (ev.port_id(), ev.channel_id()?),
+        IbcEvent::OpenTryChannel(ev) => (ev.port_id(), ev.channel_id()?),
+        IbcEvent::OpenAckChannel(ev) => (ev.port_id(), ev.channel_id()?),
+        IbcEvent::CloseInitChannel(ev) => (&ev.port_id, &ev.channel_id),
+        IbcEvent::SendPacket(ev) => (&ev.packet.source_port, &ev.packet.source_channel),
+        IbcEvent::WriteAcknowledgement(ev) => {
+            (&ev.packet.destination_port, &ev.packet.destination_channel)
+        }
+        _ => return None,
+    };
+
+    let channel = ctx.query_channel(port_id, channel_id)?;
+    let connection = ctx.query_connection(&channel.connection_hops()[0])?;
+    Some(connection.client_id().clone())
+}
+
+/// Relays a packet sent by `src`: if the packet has already timed out on `dst`, a timeout is
+/// submitted back to `src`, otherwise the packet is delivered to `dst`. On an
+/// `ORDERED_ALLOW_TIMEOUT` channel, a timed-out packet is first delivered to `dst` nonetheless,
+/// so that `dst` records the timeout receipt that the timeout on `src` then proves.
+fn relay_send_packet<Src, Dst>(
+    src: &mut Src,
+    dst: &mut Dst,
+    packet: &Packet,
+    output: &mut RelayOutput,
+) -> Result<(), Error>
+where
+    Src: Ics18Context,
+    Dst: Ics18Context,
+{
+    let src_chan = query_channel(src, &packet.source_port, &packet.source_channel)?;
+    let src_conn = query_connection(src, &src_chan.connection_hops()[0])?;
+    let timed_out = packet.timed_out(&dst.query_latest_timestamp(), dst.query_latest_height());
+
+    if !timed_out || src_chan.order_matches(&Order::OrderedAllowTimeout) {
+        let height = update_client(
+            src,
+            dst,
+            src_conn.counterparty().client_id(),
+            &mut output.dst_events,
+        )?;
+        let commitment_path = CommitmentsPath {
+            port_id: packet.source_port.clone(),
+            channel_id: packet.source_channel.clone(),
+            sequence: packet.sequence,
+        };
+        let proofs = Proofs::new(
+            query_proof(src, commitment_path, height)?,
+            None,
+            None,
+            None,
+            height,
+        )
+        .map_err(Error::invalid_proof)?;
+
+        let msg = MsgRecvPacket {
+            packet: packet.clone(),
+            proofs,
+            signer: dst.signer(),
+        };
+        output.dst_events.append(&mut dst.send(vec![msg.to_any()])?);
+    }
+
+    if !timed_out {
+        return Ok(());
+    }
+
+    let next_sequence_recv = dst
+        .query_next_sequence_recv(&packet.destination_port, &packet.destination_channel)
+        .ok_or_else(|| {
+            Error::next_sequence_recv_not_found(
+                packet.destination_port.clone(),
+                packet.destination_channel.clone(),
+            )
+        })?;
+
+    let height = update_client(dst, src, src_conn.client_id(), &mut output.src_events)?;
+    let unreceived_path: Path = if src_chan.order_matches(&Order::Ordered) {
+        SeqRecvsPath(
+            packet.destination_port.clone(),
+            packet.destination_channel.clone(),
+        )
+        .into()
+    } else {
+        ReceiptsPath {
+            port_id: packet.destination_port.clone(),
+            channel_id: packet.destination_channel.clone(),
+            sequence: packet.sequence,
+        }
+        .into()
+    };
+    let proofs = Proofs::new(
+        query_proof(dst, unreceived_path, height)?,
+        None,
+        None,
+        None,
+        height,
+    )
+    .map_err(Error::invalid_proof)?;
+
+    let msg = MsgTimeout {
+        packet: packet.clone(),
+        next_sequence_recv,
+        proofs,
+        signer: src.signer(),
+    };
+    output.src_events.append(&mut src.send(vec![msg.to_any()])?);
+
+    Ok(())
+}
+
+/// Builds the proofs for a `ConnOpenTry` or `ConnOpenAck` datagram. Besides the connection end
+/// itself, these prove the state of the client on `src` which tracks `dst`. That client is first
+/// updated to the latest header of `dst`, so that `dst` can check the proven consensus state
+/// against its own recent history. Also returns the proven client state.
+fn build_conn_proofs<Src, Dst>(
+    src: &mut Src,
+    dst: &mut Dst,
+    src_conn_id: &ConnectionId,
+    src_conn: &ConnectionEnd,
+    output: &mut RelayOutput,
+) -> Result<(AnyClientState, Proofs), Error>
+where
+    Src: Ics18Context,
+    Dst: Ics18Context,
+{
+    let client_id = src_conn.client_id();
+    update_client(&*dst, src, client_id, &mut output.src_events)?;
+
+    let client_state = src
+        .query_client_full_state(client_id)
+        .ok_or_else(|| Error::client_state_not_found(client_id.clone()))?;
+    let consensus_height = client_state.latest_height();
+
+    let height = update_client(
+        &*src,
+        dst,
+        src_conn.counterparty().client_id(),
+        &mut output.dst_events,
+    )?;
+
+    let consensus_path = ClientConsensusStatePath {
+        client_id: client_id.clone(),
+        epoch: consensus_height.revision_number(),
+        height: consensus_height.revision_height(),
+    };
+    let consensus_proof =
+        ConsensusProof::new(query_proof(src, consensus_path, height)?, consensus_height)
+            .map_err(Error::invalid_proof)?;
+
+    let proofs = Proofs::new(
+        query_proof(src, ConnectionsPath(src_conn_id.clone()), height)?,
+        Some(query_proof(
+            src,
+            ClientStatePath(client_id.clone()),
+            height,
+        )?),
+        Some(consensus_proof),
+        None,
+        height,
+    )
+    .map_err(Error::invalid_proof)?;
+
+    Ok((client_state, proofs))
+}
+
+/// Builds the proofs for a channel handshake datagram, which prove the channel end on `src`
+/// after updating the client on `dst` which tracks `src`. Also returns the channel end and the
+/// connection end it runs on.
+fn build_chan_proofs<Src, Dst>(
+    src: &Src,
+    dst: &mut Dst,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    output: &mut RelayOutput,
+) -> Result<(ChannelEnd, ConnectionEnd, Proofs), Error>
+where
+    Src: Ics18Context,
+    Dst: Ics18Context,
+{
+    let src_chan = query_channel(src, port_id, channel_id)?;
+    let src_conn = query_connection(src, &src_chan.connection_hops()[0])?;
+
+    let height = update_client(
+        src,
+        dst,
+        src_conn.counterparty().client_id(),
+        &mut output.dst_events,
+    )?;
+    let proofs = Proofs::new(
+        query_proof(
+            src,
+            ChannelEndsPath(port_id.clone(), channel_id.clone()),
+            height,
+        )?,
+        None,
+        None,
+        None,
+        height,
+    )
+    .map_err(Error::invalid_proof)?;
+
+    Ok((src_chan, src_conn, proofs))
+}
+
+/// Updates the client with id `client_id` on `dst` to the latest header of `src`, unless it is
+/// already up to date, and collects the events emitted by `dst` into `events`. Returns the height
+/// of that header, which is the height at which `dst` can verify proofs of the state of `src`.
+fn update_client<Src, Dst>(
+    src: &Src,
+    dst: &mut Dst,
+    client_id: &ClientId,
+    events: &mut Vec<IbcEvent>,
+) -> Result<Height, Error>
+where
+    Src: Ics18Context,
+    Dst: Ics18Context,
+{
+    let header = src
+        .query_latest_header()
+        .ok_or_else(Error::header_not_found)?;
+    let height = header.height();
+
+    match build_client_update_datagram(dst, client_id, header) {
+        Ok(ClientMsg::UpdateClient(msg)) => events.append(&mut dst.send(vec![msg.to_any()])?),
+        Ok(_) => {}
+        Err(e) if matches!(e.detail(), ErrorDetail::ClientAlreadyUpToDate(_)) => {}
+        Err(e) => return Err(e),
+    }
+
+    Ok(height)
+}
+
+/// Returns the identifier of the client which tracks `ctx` on the counterparty of the given
+/// channel.
+fn counterparty_client_id<Ctx: Ics18Context>(
+    ctx: &Ctx,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<ClientId, Error> {
+    let channel = query_channel(ctx, port_id, channel_id)?;
+    let connection = query_connection(ctx, &channel.connection_hops()[0])?;
+    Ok(connection.counterparty().client_id().clone())
+}
+
+fn query_connection<Ctx: Ics18Context>(
+    ctx: &Ctx,
+    connection_id: &ConnectionId,
+) -> Result<ConnectionEnd, Error> {
+    ctx.query_connection(connection_id)
+        .ok_or_else(|| Error::connection_not_found(connection_id.clone()))
+}
+
+fn query_channel<Ctx: Ics18Context>(
+    ctx: &Ctx,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<ChannelEnd, Error> {
+    ctx.query_channel(port_id, channel_id)
+        .ok_or_else(|| Error::channel_not_found(port_id.clone(), channel_id.clone()))
+}
+
+fn query_proof<Ctx: Ics18Context>(
+    ctx: &Ctx,
+    path: impl Into<Path>,
+    height: Height,
+) -> Result<CommitmentProofBytes, Error> {
+    let path = path.into();
+    ctx.query_proof(&path, height)
+        .ok_or_else(|| Error::proof_not_found(path, height))
+}
+
+fn expect_id<'a, T>(id: Option<&'a T>, event: &IbcEvent) -> Result<&'a T, Error> {
+    id.ok_or_else(|| Error::missing_identifier(event.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::applications::transfer::MODULE_ID_STR;
+    use crate::core::ics02_client::client_type::ClientType;
+    use crate::core::ics03_connection::connection::{
+        Counterparty as ConnCounterparty, State as ConnState,
+    };
+    use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
+    use crate::core::ics04_channel::handler::{send_packet::send_packet, write_acknowledgement};
+    use crate::core::ics04_channel::msgs::chan_close_init::MsgChannelCloseInit;
+    use crate::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+    use crate::core::ics04_channel::packet::{Packet, Sequence};
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
+    use crate::core::ics26_routing::context::{ModuleId, RouterBuilder};
+    use crate::events::IbcEvent;
+    use crate::handler::HandlerOutput;
+    use crate::mock::context::{MockContext, MockRouterBuilder};
+    use crate::mock::host::HostType;
+    use crate::prelude::*;
+    use crate::relayer::ics18_relayer::context::Ics18Context;
+    use crate::relayer::ics18_relayer::relay::{relay, relay_event, Side};
+    use crate::test_utils::DummyTransferModule;
+    use crate::timestamp::{Timestamp, ZERO_DURATION};
+    use crate::tx_msg::Msg;
+    use crate::Height;
+
+    fn client_id() -> ClientId {
+        ClientId::new(ClientType::Mock, 0).unwrap()
+    }
+
+    /// A mock chain with a mock client for its counterparty and the transfer port bound.
+    fn mock_chain(name: &str) -> MockContext {
+        let height = Height::new(1, 5).unwrap();
+        let ctx = MockContext::new(ChainId::new(name.to_string(), 1), HostType::Mock, 5, height)
+            .with_client(&client_id(), height);
+
+        let module_id: ModuleId = MODULE_ID_STR.parse().unwrap();
+        let module = DummyTransferModule::new(ctx.ibc_store_share());
+        let router = MockRouterBuilder::default()
+            .add_route(module_id.clone(), module)
+            .unwrap()
+            .build();
+
+        let mut ctx = ctx.with_router(router);
+        ctx.scope_port_to_module(PortId::transfer(), module_id);
+        ctx
+    }
+
+    /// Starts the handshake of a connection from chain A to chain B.
+    fn conn_open_init(ctx_a: &mut MockContext, ctx_b: &MockContext) -> Vec<IbcEvent> {
+        let msg = MsgConnectionOpenInit {
+            client_id: client_id(),
+            counterparty: ConnCounterparty::new(client_id(), None, ctx_b.query_commitment_prefix()),
+            version: None,
+            delay_period: ZERO_DURATION,
+            signer: ctx_a.signer(),
+        };
+        ctx_a.send(vec![msg.to_any()]).unwrap()
+    }
+
+    /// Starts the handshake of an unordered transfer channel on the first connection of chain A.
+    fn chan_open_init(ctx_a: &mut MockContext) -> Vec<IbcEvent> {
+        let msg = MsgChannelOpenInit {
+            port_id: PortId::transfer(),
+            channel: ChannelEnd::new(
+                State::Init,
+                Order::Unordered,
+                Counterparty::new(PortId::transfer(), None),
+                vec![ConnectionId::new(0)],
+                Version::ics20(),
+            ),
+            signer: ctx_a.signer(),
+        };
+        ctx_a.send(vec![msg.to_any()]).unwrap()
+    }
+
+    /// Opens a connection between two fresh mock chains, starting from the `Init` step on chain A
+    /// and relaying everything else.
+    fn open_connection() -> (MockContext, MockContext) {
+        let mut ctx_a = mock_chain("mockgaiaA");
+        let mut ctx_b = mock_chain("mockgaiaB");
+
+        let events = conn_open_init(&mut ctx_a, &ctx_b);
+        relay(&mut ctx_a, &mut ctx_b, tag(Side::A, events)).unwrap();
+
+        for ctx in [&ctx_a, &ctx_b] {
+            let conn = ctx.query_connection(&ConnectionId::new(0)).unwrap();
+            assert_eq!(conn.state(), &ConnState::Open);
+        }
+
+        (ctx_a, ctx_b)
+    }
+
+    /// Opens a connection and an unordered transfer channel between two fresh mock chains,
+    /// starting from the `Init` steps on chain A and relaying everything else.
+    fn open_channel() -> (MockContext, MockContext) {
+        let (mut ctx_a, mut ctx_b) = open_connection();
+
+        let events = chan_open_init(&mut ctx_a);
+        relay(&mut ctx_a, &mut ctx_b, tag(Side::A, events)).unwrap();
+
+        for ctx in [&ctx_a, &ctx_b] {
+            let chan = ctx
+                .query_channel(&PortId::transfer(), &ChannelId::new(0))
+                .unwrap();
+            assert_eq!(chan.state(), &State::Open);
+        }
+
+        (ctx_a, ctx_b)
+    }
+
+    fn tag(side: Side, events: Vec<IbcEvent>) -> Vec<(Side, IbcEvent)> {
+        events.into_iter().map(|e| (side, e)).collect()
+    }
+
+    /// Relays a single handshake `event` emitted by `src` with [`relay_event`], and returns the
+    /// handshake event it triggered on `dst`, past the client updates.
+    fn relay_step(src: &mut MockContext, dst: &mut MockContext, event: &IbcEvent) -> IbcEvent {
+        let output = relay_event(src, dst, event).unwrap();
+        output
+            .dst_events
+            .into_iter()
+            .find(|e| !matches!(e, IbcEvent::UpdateClient(_)))
+            .unwrap()
+    }
+
+    fn conn_state(ctx: &MockContext) -> ConnState {
+        *ctx.query_connection(&ConnectionId::new(0)).unwrap().state()
+    }
+
+    fn chan_state(ctx: &MockContext) -> State {
+        *ctx.query_channel(&PortId::transfer(), &ChannelId::new(0))
+            .unwrap()
+            .state()
+    }
+
+    fn dummy_packet(timeout_height: Height) -> Packet {
+        Packet {
+            sequence: 1.into(),
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(0),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(0),
+            data: b"ping".to_vec(),
+            timeout_height: timeout_height.into(),
+            timeout_timestamp: Timestamp::none(),
+        }
+    }
+
+    /// Sends `packet` on `ctx`, as an application would, and returns the emitted events.
+    fn send(ctx: &mut MockContext, packet: Packet) -> Vec<IbcEvent> {
+        let HandlerOutput { result, events, .. } = send_packet(&*ctx, packet).unwrap();
+        ctx.store_packet_result(result).unwrap();
+        events
+    }
+
+    fn packet_key(packet: &Packet) -> (PortId, ChannelId, Sequence) {
+        (
+            packet.source_port.clone(),
+            packet.source_channel.clone(),
+            packet.sequence,
+        )
+    }
+
+    #[test]
+    fn relay_event_connection_handshake() {
+        let mut ctx_a = mock_chain("mockgaiaA");
+        let mut ctx_b = mock_chain("mockgaiaB");
+
+        let init = conn_open_init(&mut ctx_a, &ctx_b).pop().unwrap();
+        assert!(matches!(init, IbcEvent::OpenInitConnection(_)));
+
+        let try_open = relay_step(&mut ctx_a, &mut ctx_b, &init);
+        assert!(matches!(try_open, IbcEvent::OpenTryConnection(_)));
+        assert_eq!(conn_state(&ctx_b), ConnState::TryOpen);
+
+        let ack = relay_step(&mut ctx_b, &mut ctx_a, &try_open);
+        assert!(matches!(ack, IbcEvent::OpenAckConnection(_)));
+        assert_eq!(conn_state(&ctx_a), ConnState::Open);
+
+        let confirm = relay_step(&mut ctx_a, &mut ctx_b, &ack);
+        assert!(matches!(confirm, IbcEvent::OpenConfirmConnection(_)));
+        assert_eq!(conn_state(&ctx_b), ConnState::Open);
+
+        // The handshake is complete, so there is nothing left to relay.
+        let output = relay_event(&mut ctx_b, &mut ctx_a, &confirm).unwrap();
+        assert!(output.src_events.is_empty() && output.dst_events.is_empty());
+    }
+
+    #[test]
+    fn relay_event_channel_handshake() {
+        let (mut ctx_a, mut ctx_b) = open_connection();
+
+        let init = chan_open_init(&mut ctx_a).pop().unwrap();
+        assert!(matches!(init, IbcEvent::OpenInitChannel(_)));
+
+        let try_open = relay_step(&mut ctx_a, &mut ctx_b, &init);
+        assert!(matches!(try_open, IbcEvent::OpenTryChannel(_)));
+        assert_eq!(chan_state(&ctx_b), State::TryOpen);
+
+        let ack = relay_step(&mut ctx_b, &mut ctx_a, &try_open);
+        assert!(matches!(ack, IbcEvent::OpenAckChannel(_)));
+        assert_eq!(chan_state(&ctx_a), State::Open);
+
+        let confirm = relay_step(&mut ctx_a, &mut ctx_b, &ack);
+        assert!(matches!(confirm, IbcEvent::OpenConfirmChannel(_)));
+        assert_eq!(chan_state(&ctx_b), State::Open);
+
+        let output = relay_event(&mut ctx_b, &mut ctx_a, &confirm).unwrap();
+        assert!(output.src_events.is_empty() && output.dst_events.is_empty());
+    }
+
+    #[test]
+    fn relay_event_channel_close() {
+        let (mut ctx_a, mut ctx_b) = open_channel();
+
+        let msg = MsgChannelCloseInit {
+            port_id: PortId::transfer(),
+            channel_id: ChannelId::new(0),
+            signer: ctx_a.signer(),
+        };
+        let init = ctx_a.send(vec![msg.to_any()]).unwrap().pop().unwrap();
+        assert!(matches!(init, IbcEvent::CloseInitChannel(_)));
+        assert_eq!(chan_state(&ctx_a), State::Closed);
+
+        let confirm = relay_step(&mut ctx_a, &mut ctx_b, &init);
+        assert!(matches!(confirm, IbcEvent::CloseConfirmChannel(_)));
+        assert_eq!(chan_state(&ctx_b), State::Closed);
+    }
+
+    #[test]
+    fn relay_packet_and_acknowledgement() {
+        let (mut ctx_a, mut ctx_b) = open_channel();
+
+        let packet = dummy_packet(Height::new(1, 1000).unwrap());
+        let events = send(&mut ctx_a, packet.clone());
+        let emitted = relay(&mut ctx_a, &mut ctx_b, tag(Side::A, events)).unwrap();
+
+        assert!(emitted
+            .iter()
+            .any(|(side, e)| *side == Side::B && matches!(e, IbcEvent::ReceivePacket(_))));
+        assert!(ctx_b.get_packet_receipt(&packet_key(&packet)).is_ok());
+
+        // The application on chain B acknowledges the packet asynchronously.
+        let HandlerOutput { result, events, .. } =
+            write_acknowledgement::process(&ctx_b, packet.clone(), b"pong".to_vec()).unwrap();
+        ctx_b.store_packet_result(result).unwrap();
+        let emitted = relay(&mut ctx_a, &mut ctx_b, tag(Side::B, events)).unwrap();
+
+        assert!(emitted
+            .iter()
+            .any(|(side, e)| *side == Side::A && matches!(e, IbcEvent::AcknowledgePacket(_))));
+        assert!(ctx_a.get_packet_commitment(&packet_key(&packet)).is_err());
+    }
+
+    #[test]
+    fn relay_timeout() {
+        let (mut ctx_a, mut ctx_b) = open_channel();
+
+        let timeout_height = ctx_b.query_latest_height().increment();
+        let packet = dummy_packet(timeout_height);
+        let events = send(&mut ctx_a, packet.clone());
+
+        // Let chain B move past the timeout height before the packet is relayed.
+        ctx_b.advance_host_chain_height();
+        ctx_b.advance_host_chain_height();
+
+        let emitted = relay(&mut ctx_a, &mut ctx_b, tag(Side::A, events)).unwrap();
+
+        assert!(emitted
+            .iter()
+            .any(|(side, e)| *side == Side::A && matches!(e, IbcEvent::TimeoutPacket(_))));
+        assert!(emitted
+            .iter()
+            .all(|(_, e)| !matches!(e, IbcEvent::ReceivePacket(_))));
+        assert!(ctx_a.get_packet_commitment(&packet_key(&packet)).is_err());
+        assert!(ctx_b.get_packet_receipt(&packet_key(&packet)).is_err());
+    }
+}
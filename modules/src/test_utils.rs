@@ -4,10 +4,20 @@ use std::time::Duration;
 use subtle_encoding::bech32;
 use tendermint::{block, consensus, evidence, public_key::Algorithm};
 
+use crate::applications::packet_forward::context::{
+    cosmos_intermediate_address, PacketForwardKeeper, PacketForwardReader,
+};
+use crate::applications::packet_forward::error::Error as PacketForwardError;
+use crate::applications::packet_forward::packet::InFlightPacket;
+use crate::applications::rate_limit::context::{RateLimitKeeper, RateLimitReader};
+use crate::applications::rate_limit::error::Error as RateLimitError;
+use crate::applications::rate_limit::quota::{RateLimit, RateLimitPath};
 use crate::applications::transfer::context::{
-    cosmos_adr028_escrow_address, BankKeeper, Ics20Context, Ics20Keeper, Ics20Reader,
+    cosmos_adr028_escrow_address, BankKeeper, BankReader, Ics20Context, Ics20Keeper, Ics20Reader,
+};
+use crate::applications::transfer::{
+    error::Error as Ics20Error, Amount, PrefixedCoin, PrefixedDenom,
 };
-use crate::applications::transfer::{error::Error as Ics20Error, PrefixedCoin};
 use crate::core::ics02_client::client_consensus::AnyConsensusState;
 use crate::core::ics02_client::client_state::AnyClientState;
 use crate::core::ics02_client::error::Error as Ics02Error;
@@ -18,11 +28,13 @@ use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCo
 use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
 use crate::core::ics04_channel::error::Error;
 use crate::core::ics04_channel::packet::{Receipt, Sequence};
+use crate::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use crate::core::ics04_channel::Version;
 use crate::core::ics05_port::context::PortReader;
 use crate::core::ics05_port::error::Error as PortError;
 use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
 use crate::core::ics26_routing::context::{Module, ModuleId, ModuleOutputBuilder};
+use crate::events::IbcEvent;
 use crate::mock::context::MockIbcStore;
 use crate::prelude::*;
 use crate::signer::Signer;
@@ -63,14 +75,53 @@ pub fn get_dummy_bech32_account() -> String {
     "cosmos1wxeyh7zgn4tctjzs0vtqpc6p5cxq5t2muzl7ng".to_string()
 }
 
+/// Generates the property tests checking that the domain values of the `$valid` strategy
+/// round-trip through their raw type, and that the raw values of the `$invalid` strategy,
+/// if any, are rejected.
+#[cfg(test)]
+macro_rules! raw_round_trip_proptests {
+    ($domain:ty, $raw:ty, $valid:expr) => {
+        proptest::proptest! {
+            #[test]
+            fn arbitrary_to_and_from_raw(value in $valid) {
+                let raw = <$raw>::from(value.clone());
+                assert_eq!(<$domain>::try_from(raw).unwrap(), value);
+            }
+        }
+    };
+    ($domain:ty, $raw:ty, $valid:expr, $invalid:expr) => {
+        $crate::test_utils::raw_round_trip_proptests!($domain, $raw, $valid);
+
+        proptest::proptest! {
+            #[test]
+            fn arbitrary_invalid_raw_is_rejected(raw in $invalid) {
+                assert!(<$domain>::try_from(raw).is_err());
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+pub(crate) use raw_round_trip_proptests;
+
 #[derive(Debug)]
 pub struct DummyTransferModule {
     ibc_store: Arc<Mutex<MockIbcStore>>,
+    host_timestamp: Timestamp,
 }
 
 impl DummyTransferModule {
     pub fn new(ibc_store: Arc<Mutex<MockIbcStore>>) -> Self {
-        Self { ibc_store }
+        Self {
+            ibc_store,
+            host_timestamp: Timestamp::now(),
+        }
+    }
+
+    /// Credits `coin` to `account`, e.g. to fund the sender of a transfer.
+    pub fn with_balance(mut self, account: &Signer, coin: &PrefixedCoin) -> Self {
+        self.mint_coins(account, coin).unwrap();
+        self
     }
 }
 
@@ -88,10 +139,47 @@ impl Module for DummyTransferModule {
     ) -> Result<Version, Error> {
         Ok(counterparty_version.clone())
     }
+
+    fn on_chan_upgrade_init(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        version: &Version,
+    ) -> Result<Version, Error> {
+        Ok(version.clone())
+    }
+
+    fn on_chan_upgrade_try(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        counterparty_version: &Version,
+    ) -> Result<Version, Error> {
+        Ok(counterparty_version.clone())
+    }
 }
 
 impl Ics20Keeper for DummyTransferModule {
     type AccountId = Signer;
+
+    fn set_total_escrow(
+        &mut self,
+        denom: &PrefixedDenom,
+        amount: Amount,
+    ) -> Result<(), Ics20Error> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .total_escrows
+            .insert(denom.clone(), amount);
+        Ok(())
+    }
 }
 
 impl ChannelKeeper for DummyTransferModule {
@@ -125,10 +213,15 @@ impl ChannelKeeper for DummyTransferModule {
 
     fn store_packet_acknowledgement(
         &mut self,
-        _key: (PortId, ChannelId, Sequence),
-        _ack: AcknowledgementCommitment,
+        key: (PortId, ChannelId, Sequence),
+        ack: AcknowledgementCommitment,
     ) -> Result<(), Error> {
-        unimplemented!()
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .packet_acknowledgement
+            .insert(key, ack);
+        Ok(())
     }
 
     fn delete_packet_acknowledgement(
@@ -138,6 +231,18 @@ impl ChannelKeeper for DummyTransferModule {
         unimplemented!()
     }
 
+    fn delete_packet_receipt(&mut self, _key: (PortId, ChannelId, Sequence)) -> Result<(), Error> {
+        unimplemented!()
+    }
+
+    fn store_pruning_sequence(
+        &mut self,
+        _port_channel_id: (PortId, ChannelId),
+        _seq: Sequence,
+    ) -> Result<(), Error> {
+        unimplemented!()
+    }
+
     fn store_connection_channels(
         &mut self,
         _conn_id: ConnectionId,
@@ -183,6 +288,41 @@ impl ChannelKeeper for DummyTransferModule {
         unimplemented!()
     }
 
+    fn store_upgrade(
+        &mut self,
+        _port_channel_id: (PortId, ChannelId),
+        _upgrade: Upgrade,
+    ) -> Result<(), Error> {
+        unimplemented!()
+    }
+
+    fn delete_upgrade(&mut self, _port_channel_id: (PortId, ChannelId)) -> Result<(), Error> {
+        unimplemented!()
+    }
+
+    fn store_counterparty_upgrade(
+        &mut self,
+        _port_channel_id: (PortId, ChannelId),
+        _upgrade: Upgrade,
+    ) -> Result<(), Error> {
+        unimplemented!()
+    }
+
+    fn delete_counterparty_upgrade(
+        &mut self,
+        _port_channel_id: (PortId, ChannelId),
+    ) -> Result<(), Error> {
+        unimplemented!()
+    }
+
+    fn store_upgrade_error_receipt(
+        &mut self,
+        _port_channel_id: (PortId, ChannelId),
+        _error_receipt: ErrorReceipt,
+    ) -> Result<(), Error> {
+        unimplemented!()
+    }
+
     fn increase_channel_counter(&mut self) {
         unimplemented!()
     }
@@ -199,30 +339,62 @@ impl BankKeeper for DummyTransferModule {
 
     fn send_coins(
         &mut self,
-        _from: &Self::AccountId,
-        _to: &Self::AccountId,
-        _amt: &PrefixedCoin,
+        from: &Self::AccountId,
+        to: &Self::AccountId,
+        amt: &PrefixedCoin,
     ) -> Result<(), Ics20Error> {
-        Ok(())
+        self.burn_coins(from, amt)?;
+        self.mint_coins(to, amt)
     }
 
     fn mint_coins(
         &mut self,
-        _account: &Self::AccountId,
-        _amt: &PrefixedCoin,
+        account: &Self::AccountId,
+        amt: &PrefixedCoin,
     ) -> Result<(), Ics20Error> {
+        let mut ibc_store = self.ibc_store.lock().unwrap();
+        let balance = ibc_store
+            .balances
+            .entry((account.clone(), amt.denom.clone()))
+            .or_insert_with(|| Amount::from(0));
+        *balance = balance
+            .checked_add(amt.amount)
+            .ok_or_else(Ics20Error::invalid_token)?;
         Ok(())
     }
 
     fn burn_coins(
         &mut self,
-        _account: &Self::AccountId,
-        _amt: &PrefixedCoin,
+        account: &Self::AccountId,
+        amt: &PrefixedCoin,
     ) -> Result<(), Ics20Error> {
+        let mut ibc_store = self.ibc_store.lock().unwrap();
+        let balance = ibc_store
+            .balances
+            .entry((account.clone(), amt.denom.clone()))
+            .or_insert_with(|| Amount::from(0));
+        let available_funds = *balance;
+        *balance = available_funds
+            .checked_sub(amt.amount)
+            .ok_or_else(|| Ics20Error::insufficient_funds(amt.clone(), available_funds))?;
         Ok(())
     }
 }
 
+impl BankReader for DummyTransferModule {
+    type AccountId = Signer;
+
+    fn get_balance(&self, account: &Self::AccountId, denom: &PrefixedDenom) -> Amount {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .balances
+            .get(&(account.clone(), denom.clone()))
+            .copied()
+            .unwrap_or_else(|| Amount::from(0))
+    }
+}
+
 impl Ics20Reader for DummyTransferModule {
     type AccountId = Signer;
 
@@ -246,6 +418,29 @@ impl Ics20Reader for DummyTransferModule {
     fn is_receive_enabled(&self) -> bool {
         true
     }
+
+    fn get_total_escrow(&self, denom: &PrefixedDenom) -> Amount {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .total_escrows
+            .get(denom)
+            .copied()
+            .unwrap_or_else(|| Amount::from(0))
+    }
+
+    fn get_all_total_escrows(&self) -> Vec<PrefixedCoin> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .total_escrows
+            .iter()
+            .map(|(denom, amount)| PrefixedCoin {
+                denom: denom.clone(),
+                amount: *amount,
+            })
+            .collect()
+    }
 }
 
 impl ChannelReader for DummyTransferModule {
@@ -343,8 +538,54 @@ impl ChannelReader for DummyTransferModule {
 
     fn get_packet_acknowledgement(
         &self,
-        _key: &(PortId, ChannelId, Sequence),
+        key: &(PortId, ChannelId, Sequence),
     ) -> Result<AcknowledgementCommitment, Error> {
+        match self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .packet_acknowledgement
+            .get(key)
+        {
+            Some(ack) => Ok(ack.clone()),
+            None => Err(Error::packet_acknowledgement_not_found(key.2)),
+        }
+    }
+
+    fn has_inflight_packets(
+        &self,
+        _port_channel_id: &(PortId, ChannelId),
+        _except: Option<Sequence>,
+    ) -> Result<bool, Error> {
+        unimplemented!()
+    }
+
+    fn pruning_sequence(&self, port_channel_id: &(PortId, ChannelId)) -> Result<Sequence, Error> {
+        Ok(self
+            .ibc_store
+            .lock()
+            .unwrap()
+            .pruning_sequences
+            .get(port_channel_id)
+            .cloned()
+            .unwrap_or_else(|| Sequence::from(1)))
+    }
+
+    fn upgrade(&self, _port_channel_id: &(PortId, ChannelId)) -> Result<Upgrade, Error> {
+        unimplemented!()
+    }
+
+    fn counterparty_upgrade(
+        &self,
+        _port_channel_id: &(PortId, ChannelId),
+    ) -> Result<Upgrade, Error> {
+        unimplemented!()
+    }
+
+    fn upgrade_error_receipt(
+        &self,
+        _port_channel_id: &(PortId, ChannelId),
+    ) -> Result<ErrorReceipt, Error> {
         unimplemented!()
     }
 
@@ -358,6 +599,10 @@ impl ChannelReader for DummyTransferModule {
         Height::new(0, 1).unwrap()
     }
 
+    fn host_timestamp(&self) -> Timestamp {
+        self.host_timestamp
+    }
+
     fn host_consensus_state(&self, _height: Height) -> Result<AnyConsensusState, Error> {
         unimplemented!()
     }
@@ -394,3 +639,126 @@ impl ChannelReader for DummyTransferModule {
 impl Ics20Context for DummyTransferModule {
     type AccountId = Signer;
 }
+
+impl PacketForwardReader for DummyTransferModule {
+    fn get_intermediate_account(
+        &self,
+        _port_id: &PortId,
+        channel_id: &ChannelId,
+        original_sender: &Signer,
+    ) -> Result<Signer, PacketForwardError> {
+        let addr = cosmos_intermediate_address(channel_id, original_sender);
+        Ok(bech32::encode("cosmos", addr).parse().unwrap())
+    }
+
+    fn get_in_flight_packet(&self, key: &(PortId, ChannelId, Sequence)) -> Option<InFlightPacket> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .in_flight_packets
+            .get(key)
+            .cloned()
+    }
+}
+
+impl PacketForwardKeeper for DummyTransferModule {
+    fn store_in_flight_packet(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+        packet: InFlightPacket,
+    ) -> Result<(), PacketForwardError> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .in_flight_packets
+            .insert(key, packet);
+        Ok(())
+    }
+
+    fn delete_in_flight_packet(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+    ) -> Result<(), PacketForwardError> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .in_flight_packets
+            .remove(&key);
+        Ok(())
+    }
+
+    fn emit_ibc_events(&mut self, events: Vec<IbcEvent>) {
+        self.ibc_store.lock().unwrap().app_events.extend(events);
+    }
+}
+
+impl RateLimitReader for DummyTransferModule {
+    fn get_rate_limit(&self, path: &RateLimitPath) -> Option<RateLimit> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .rate_limits
+            .get(path)
+            .cloned()
+    }
+
+    fn get_rate_limits(&self) -> Vec<RateLimit> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .rate_limits
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    fn get_send_timestamp(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Timestamp> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .rate_limit_send_timestamps
+            .get(key)
+            .copied()
+    }
+}
+
+impl RateLimitKeeper for DummyTransferModule {
+    fn store_rate_limit(&mut self, rate_limit: RateLimit) -> Result<(), RateLimitError> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .rate_limits
+            .insert(rate_limit.path.clone(), rate_limit);
+        Ok(())
+    }
+
+    fn delete_rate_limit(&mut self, path: &RateLimitPath) -> Result<(), RateLimitError> {
+        self.ibc_store.lock().unwrap().rate_limits.remove(path);
+        Ok(())
+    }
+
+    fn store_send_timestamp(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+        timestamp: Timestamp,
+    ) -> Result<(), RateLimitError> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .rate_limit_send_timestamps
+            .insert(key, timestamp);
+        Ok(())
+    }
+
+    fn delete_send_timestamp(
+        &mut self,
+        key: &(PortId, ChannelId, Sequence),
+    ) -> Result<(), RateLimitError> {
+        self.ibc_store
+            .lock()
+            .unwrap()
+            .rate_limit_send_timestamps
+            .remove(key);
+        Ok(())
+    }
+}
@@ -18,13 +18,24 @@ fn mbt() {
 }
 
 fn run_tests() -> Result<(), Error> {
-    // run the test
-    let tla_tests_file = "tests/support/model_based/IBCTests.tla";
-    let tla_config_file = "tests/support/model_based/IBCTests.cfg";
+    // the ICS02 and ICS03 tests, followed by the ICS04 channel and packet tests
+    let tests = [
+        (
+            "tests/support/model_based/IBCTests.tla",
+            "tests/support/model_based/IBCTests.cfg",
+        ),
+        (
+            "tests/support/model_based/IBCChannelTests.tla",
+            "tests/support/model_based/IBCChannelTests.cfg",
+        ),
+    ];
     let runtime = modelator::ModelatorRuntime::default()
         .model_checker_runtime(ModelCheckerRuntime::default().model_checker(ModelChecker::Tlc));
 
-    let mut runner = IbcTestRunner::new();
-    runtime.run_tla_steps(tla_tests_file, tla_config_file, &mut runner)?;
+    // run the tests
+    for (tla_tests_file, tla_config_file) in tests {
+        let mut runner = IbcTestRunner::new();
+        runtime.run_tla_steps(tla_tests_file, tla_config_file, &mut runner)?;
+    }
     Ok(())
 }
@@ -16,7 +16,9 @@ use ibc::core::ics02_client::msgs::create_client::MsgCreateAnyClient;
 use ibc::core::ics02_client::msgs::update_client::MsgUpdateAnyClient;
 use ibc::core::ics02_client::msgs::upgrade_client::MsgUpgradeAnyClient;
 use ibc::core::ics02_client::msgs::ClientMsg;
-use ibc::core::ics03_connection::connection::{Counterparty, State as ConnectionState};
+use ibc::core::ics03_connection::connection::{
+    ConnectionEnd, Counterparty, State as ConnectionState,
+};
 use ibc::core::ics03_connection::error as connection_error;
 use ibc::core::ics03_connection::msgs::conn_open_ack::MsgConnectionOpenAck;
 use ibc::core::ics03_connection::msgs::conn_open_confirm::MsgConnectionOpenConfirm;
@@ -24,24 +26,64 @@ use ibc::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
 use ibc::core::ics03_connection::msgs::conn_open_try::MsgConnectionOpenTry;
 use ibc::core::ics03_connection::msgs::ConnectionMsg;
 use ibc::core::ics03_connection::version::Version;
-use ibc::core::ics04_channel::context::ChannelReader;
+use ibc::core::ics04_channel::channel::{
+    ChannelEnd, Counterparty as ChannelCounterparty, Order, State as ChannelState,
+};
+use ibc::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
+use ibc::core::ics04_channel::error as channel_error;
+use ibc::core::ics04_channel::handler::send_packet::send_packet;
+use ibc::core::ics04_channel::msgs::acknowledgement::{Acknowledgement, MsgAcknowledgement};
+use ibc::core::ics04_channel::msgs::chan_close_confirm::MsgChannelCloseConfirm;
+use ibc::core::ics04_channel::msgs::chan_close_init::MsgChannelCloseInit;
+use ibc::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
+use ibc::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
+use ibc::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+use ibc::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use ibc::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
+use ibc::core::ics04_channel::msgs::timeout::MsgTimeout;
+use ibc::core::ics04_channel::msgs::timeout_on_close::MsgTimeoutOnClose;
+use ibc::core::ics04_channel::msgs::{ChannelMsg, PacketMsg};
+use ibc::core::ics04_channel::packet::{Packet, Sequence};
+use ibc::core::ics04_channel::Version as ChannelVersion;
 use ibc::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
-use ibc::core::ics24_host::identifier::{ChainId, ClientId, ConnectionId};
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
+use ibc::core::ics26_routing::context::{Module, ModuleId, ModuleOutputBuilder, RouterBuilder};
 use ibc::core::ics26_routing::error as routing_error;
 use ibc::core::ics26_routing::msgs::Ics26Envelope;
 use ibc::mock::client_state::{MockClientState, MockConsensusState};
-use ibc::mock::context::MockContext;
+use ibc::mock::context::{MockContext, MockRouterBuilder};
 use ibc::mock::header::MockHeader;
 use ibc::mock::host::HostType;
 use ibc::proofs::{ConsensusProof, Proofs};
 use ibc::relayer::ics18_relayer::context::Ics18Context;
 use ibc::relayer::ics18_relayer::error as relayer_error;
 use ibc::signer::Signer;
-use ibc::timestamp::ZERO_DURATION;
+use ibc::timestamp::{Timestamp, ZERO_DURATION};
 use ibc::Height;
 
 use step::{Action, ActionOutcome, Chain, Step};
 
+/// Application module bound to the port of every channel in the model. It
+/// accepts all channel handshakes and all packets.
+#[derive(Debug, Default)]
+struct MockModule;
+
+impl Module for MockModule {
+    fn on_chan_open_try(
+        &mut self,
+        _output: &mut ModuleOutputBuilder,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &ChannelCounterparty,
+        _version: &ChannelVersion,
+        counterparty_version: &ChannelVersion,
+    ) -> Result<ChannelVersion, channel_error::Error> {
+        Ok(counterparty_version.clone())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct IbcTestRunner {
     // mapping from chain identifier to its context
@@ -61,12 +103,54 @@ impl IbcTestRunner {
         let chain_id_struct = Self::chain_id(chain_id.clone(), initial_height);
         // never GC blocks
         let max_history_size = usize::MAX;
-        let ctx = MockContext::new(
+        // all channels are bound to the same port and module
+        let router = MockRouterBuilder::default()
+            .add_route(Self::module_id(), MockModule)
+            .unwrap()
+            .build();
+        let mut ctx = MockContext::new(
             chain_id_struct,
             HostType::Mock,
             max_history_size,
             initial_height,
-        );
+        )
+        .with_router(router);
+        ctx.scope_port_to_module(Self::port_id(), Self::module_id());
+        self.contexts.insert(chain_id, ctx);
+    }
+
+    /// Store in the `MockContext` of a given `chain_id` the clients and the
+    /// connections that exist in the initial state of the model.
+    /// Panic if the context for `chain_id` is not found.
+    pub fn init_chain_state(&mut self, chain_id: String, chain: Chain) {
+        let mut ctx = self
+            .contexts
+            .remove(&chain_id)
+            .expect("chain context should have been initialized");
+
+        for (client_id, client) in chain.clients {
+            // the client state is set at the highest consensus state
+            if let Some(height) = client.heights.into_iter().max() {
+                ctx = ctx.with_client(&Self::client_id(client_id), height);
+            }
+        }
+
+        for (connection_id, connection) in chain.connections {
+            if connection.state != ConnectionState::Uninitialized {
+                let connection_end = ConnectionEnd::new(
+                    connection.state,
+                    Self::client_id(connection.client_id.unwrap()),
+                    Self::counterparty(
+                        connection.counterparty_client_id.unwrap(),
+                        connection.counterparty_connection_id,
+                    ),
+                    Self::versions(),
+                    Self::delay_period(),
+                );
+                ctx = ctx.with_connection(Self::connection_id(connection_id), connection_end);
+            }
+        }
+
         self.contexts.insert(chain_id, ctx);
     }
 
@@ -121,6 +205,24 @@ impl IbcTestRunner {
         }
     }
 
+    pub fn extract_ics04_error_kind(
+        ics18_result: Result<(), relayer_error::Error>,
+    ) -> channel_error::ErrorDetail {
+        let ics18_error = ics18_result.expect_err("ICS18 error expected");
+
+        match ics18_error.0 {
+            relayer_error::ErrorDetail::TransactionFailed(e) => match e.source {
+                routing_error::ErrorDetail::Ics04Channel(e) => e.source,
+                e => {
+                    panic!("Expected Ics04Channel error, instead got {:?}", e);
+                }
+            },
+            e => {
+                panic!("Expected TransactionFailed error, instead got {:?}", e);
+            }
+        }
+    }
+
     pub fn chain_id(chain_id: String, height: Height) -> ChainId {
         ChainId::new(chain_id, height.revision_number())
     }
@@ -142,6 +244,63 @@ impl IbcTestRunner {
         ConnectionId::new(connection_id)
     }
 
+    pub fn module_id() -> ModuleId {
+        "mockmodule".parse().unwrap()
+    }
+
+    pub fn port_id() -> PortId {
+        PortId::default()
+    }
+
+    pub fn channel_id(channel_id: u64) -> ChannelId {
+        ChannelId::new(channel_id)
+    }
+
+    pub fn channel_version() -> ChannelVersion {
+        ChannelVersion::default()
+    }
+
+    pub fn channel_counterparty(channel_id: Option<u64>) -> ChannelCounterparty {
+        ChannelCounterparty::new(Self::port_id(), channel_id.map(Self::channel_id))
+    }
+
+    pub fn channel_end(
+        state: ChannelState,
+        order: Order,
+        connection_id: u64,
+        counterparty_channel_id: Option<u64>,
+    ) -> ChannelEnd {
+        ChannelEnd::new(
+            state,
+            order,
+            Self::channel_counterparty(counterparty_channel_id),
+            vec![Self::connection_id(connection_id)],
+            Self::channel_version(),
+        )
+    }
+
+    pub fn packet(packet: step::Packet) -> Packet {
+        Packet {
+            sequence: packet.sequence.into(),
+            source_port: Self::port_id(),
+            source_channel: Self::channel_id(packet.source_channel_id),
+            destination_port: Self::port_id(),
+            // the model only sends packets on channels that know their
+            // counterparty, except when the send is expected to fail
+            destination_channel: packet
+                .dest_channel_id
+                .map(Self::channel_id)
+                .unwrap_or_default(),
+            data: vec![0],
+            timeout_height: Self::height(packet.timeout_height).into(),
+            timeout_timestamp: Timestamp::none(),
+        }
+    }
+
+    pub fn acknowledgement() -> Acknowledgement {
+        vec![1].into()
+    }
+
     pub fn height(height: Height) -> Height {
         Height::new(height.revision_number(), height.revision_height()).unwrap()
     }
@@ -293,7 +452,87 @@ impl IbcTestRunner {
                         }
                     });
 
-            heights_match && clients_match && connections_match
+            // check that channels match
+            let packet_commitments = chain.packet_commitments;
+            let packet_receipts = chain.packet_receipts;
+            let channels_match = chain.channels.into_iter().all(|(channel_id, channel)| {
+                if channel.state == ChannelState::Uninitialized {
+                    // if the channel has not yet been initialized, then
+                    // there's nothing to check
+                    return true;
+                }
+
+                let port_channel_id = (Self::port_id(), Self::channel_id(channel_id));
+                let channel_end = match ctx.channel_end(&port_channel_id) {
+                    Ok(channel_end) => channel_end,
+                    // if the channel exists in the model, then it must also
+                    // exist in the implementation; in this case it doesn't, so
+                    // we fail the verification
+                    Err(_) => return false,
+                };
+
+                // states and orderings must match
+                let states_match = *channel_end.state() == channel.state
+                    && *channel_end.ordering() == channel.order;
+
+                // connection ids must match
+                let connection_ids = *channel_end.connection_hops()
+                    == vec![Self::connection_id(channel.connection_id.unwrap())];
+
+                // counterparty channel ids must match
+                let counterparty_channel_ids = channel_end.counterparty().channel_id()
+                    == channel
+                        .counterparty_channel_id
+                        .map(Self::channel_id)
+                        .as_ref();
+
+                // sequence numbers must match
+                let sequences_match = ctx.get_next_sequence_send(&port_channel_id).ok()
+                    == Some(channel.next_send_seq.into())
+                    && ctx.get_next_sequence_recv(&port_channel_id).ok()
+                        == Some(channel.next_recv_seq.into())
+                    && ctx.get_next_sequence_ack(&port_channel_id).ok()
+                        == Some(channel.next_ack_seq.into());
+
+                // a packet sent on this channel must have a commitment if
+                // and only if the model has one (i.e., if it has not been
+                // acknowledged nor timed out)
+                let commitments_match = (1..channel.next_send_seq).all(|sequence| {
+                    let committed = packet_commitments.iter().any(|packet| {
+                        packet.source_channel_id == channel_id && packet.sequence == sequence
+                    });
+                    let key = (
+                        Self::port_id(),
+                        Self::channel_id(channel_id),
+                        Sequence::from(sequence),
+                    );
+                    ctx.get_packet_commitment(&key).is_ok() == committed
+                });
+
+                // each packet received on an unordered channel must have a
+                // receipt (ordered channels are covered by `next_recv_seq`)
+                let receipts_match = channel.order == Order::Ordered
+                    || packet_receipts
+                        .iter()
+                        .filter(|packet| packet.dest_channel_id == Some(channel_id))
+                        .all(|packet| {
+                            let key = (
+                                Self::port_id(),
+                                Self::channel_id(channel_id),
+                                Sequence::from(packet.sequence),
+                            );
+                            ctx.get_packet_receipt(&key).is_ok()
+                        });
+
+                states_match
+                    && connection_ids
+                    && counterparty_channel_ids
+                    && sequences_match
+                    && commitments_match
+                    && receipts_match
+            });
+
+            heights_match && clients_match && connections_match && channels_match
         })
     }
 
@@ -447,6 +686,217 @@ impl IbcTestRunner {
                 ));
                 ctx.deliver(msg)
             }
+            Action::Ics04ChannelOpenInit {
+                chain_id,
+                connection_id,
+                order,
+            } => {
+                // get chain's context
+                let ctx = self.chain_context_mut(chain_id);
+
+                // create ICS26 message and deliver it
+                let msg = Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenInit(
+                    MsgChannelOpenInit {
+                        port_id: Self::port_id(),
+                        channel: Self::channel_end(ChannelState::Init, order, connection_id, None),
+                        signer: Self::signer(),
+                    },
+                ));
+                ctx.deliver(msg)
+            }
+            Action::Ics04ChannelOpenTry {
+                chain_id,
+                connection_id,
+                order,
+                counterparty_channel_id,
+                proof_height,
+            } => {
+                // get chain's context
+                let ctx = self.chain_context_mut(chain_id);
+
+                // create ICS26 message and deliver it
+                let msg =
+                    Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenTry(MsgChannelOpenTry {
+                        port_id: Self::port_id(),
+                        previous_channel_id: None,
+                        channel: Self::channel_end(
+                            ChannelState::TryOpen,
+                            order,
+                            connection_id,
+                            Some(counterparty_channel_id),
+                        ),
+                        counterparty_version: Self::channel_version(),
+                        proofs: Self::proofs(proof_height),
+                        signer: Self::signer(),
+                    }));
+                ctx.deliver(msg)
+            }
+            Action::Ics04ChannelOpenAck {
+                chain_id,
+                channel_id,
+                counterparty_channel_id,
+                proof_height,
+            } => {
+                // get chain's context
+                let ctx = self.chain_context_mut(chain_id);
+
+                // create ICS26 message and deliver it
+                let msg =
+                    Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenAck(MsgChannelOpenAck {
+                        port_id: Self::port_id(),
+                        channel_id: Self::channel_id(channel_id),
+                        counterparty_channel_id: Self::channel_id(counterparty_channel_id),
+                        counterparty_version: Self::channel_version(),
+                        proofs: Self::proofs(proof_height),
+                        signer: Self::signer(),
+                    }));
+                ctx.deliver(msg)
+            }
+            Action::Ics04ChannelOpenConfirm {
+                chain_id,
+                channel_id,
+                proof_height,
+            } => {
+                // get chain's context
+                let ctx = self.chain_context_mut(chain_id);
+
+                // create ICS26 message and deliver it
+                let msg = Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenConfirm(
+                    MsgChannelOpenConfirm {
+                        port_id: Self::port_id(),
+                        channel_id: Self::channel_id(channel_id),
+                        proofs: Self::proofs(proof_height),
+                        signer: Self::signer(),
+                    },
+                ));
+                ctx.deliver(msg)
+            }
+            Action::Ics04ChannelCloseInit {
+                chain_id,
+                channel_id,
+            } => {
+                // get chain's context
+                let ctx = self.chain_context_mut(chain_id);
+
+                // create ICS26 message and deliver it
+                let msg = Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelCloseInit(
+                    MsgChannelCloseInit {
+                        port_id: Self::port_id(),
+                        channel_id: Self::channel_id(channel_id),
+                        signer: Self::signer(),
+                    },
+                ));
+                ctx.deliver(msg)
+            }
+            Action::Ics04ChannelCloseConfirm {
+                chain_id,
+                channel_id,
+                proof_height,
+            } => {
+                // get chain's context
+                let ctx = self.chain_context_mut(chain_id);
+
+                // create ICS26 message and deliver it
+                let msg = Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelCloseConfirm(
+                    MsgChannelCloseConfirm {
+                        port_id: Self::port_id(),
+                        channel_id: Self::channel_id(channel_id),
+                        proofs: Self::proofs(proof_height),
+                        signer: Self::signer(),
+                    },
+                ));
+                ctx.deliver(msg)
+            }
+            Action::Ics04SendPacket { chain_id, packet } => {
+                // get chain's context
+                let ctx = self.chain_context_mut(chain_id);
+
+                // sending a packet is not an ICS26 message; call the handler
+                // and store its result, as an application module would
+                let output = send_packet(&*ctx, Self::packet(packet))
+                    .and_then(|output| ctx.store_packet_result(output.result))
+                    .map_err(|e| {
+                        relayer_error::Error::transaction_failed(
+                            routing_error::Error::ics04_channel(e),
+                        )
+                    });
+
+                // create a new block, as `MockContext::deliver` does
+                if output.is_ok() {
+                    ctx.advance_host_chain_height();
+                }
+                output
+            }
+            Action::Ics04RecvPacket {
+                chain_id,
+                packet,
+                proof_height,
+            } => {
+                // get chain's context
+                let ctx = self.chain_context_mut(chain_id);
+
+                // create ICS26 message and deliver it
+                let msg = Ics26Envelope::Ics4PacketMsg(PacketMsg::RecvPacket(MsgRecvPacket {
+                    packet: Self::packet(packet),
+                    proofs: Self::proofs(proof_height),
+                    signer: Self::signer(),
+                }));
+                ctx.deliver(msg)
+            }
+            Action::Ics04Acknowledgement {
+                chain_id,
+                packet,
+                proof_height,
+            } => {
+                // get chain's context
+                let ctx = self.chain_context_mut(chain_id);
+
+                // create ICS26 message and deliver it
+                let msg = Ics26Envelope::Ics4PacketMsg(PacketMsg::AckPacket(MsgAcknowledgement {
+                    packet: Self::packet(packet),
+                    acknowledgement: Self::acknowledgement(),
+                    proofs: Self::proofs(proof_height),
+                    signer: Self::signer(),
+                }));
+                ctx.deliver(msg)
+            }
+            Action::Ics04Timeout {
+                chain_id,
+                packet,
+                proof_height,
+                next_recv_seq,
+            } => {
+                // get chain's context
+                let ctx = self.chain_context_mut(chain_id);
+
+                // create ICS26 message and deliver it
+                let msg = Ics26Envelope::Ics4PacketMsg(PacketMsg::ToPacket(MsgTimeout {
+                    packet: Self::packet(packet),
+                    next_sequence_recv: next_recv_seq.into(),
+                    proofs: Self::proofs(proof_height),
+                    signer: Self::signer(),
+                }));
+                ctx.deliver(msg)
+            }
+            Action::Ics04TimeoutOnClose {
+                chain_id,
+                packet,
+                proof_height,
+                next_recv_seq,
+            } => {
+                // get chain's context
+                let ctx = self.chain_context_mut(chain_id);
+
+                // create ICS26 message and deliver it
+                let msg =
+                    Ics26Envelope::Ics4PacketMsg(PacketMsg::ToClosePacket(MsgTimeoutOnClose {
+                        packet: Self::packet(packet),
+                        next_sequence_recv: next_recv_seq.into(),
+                        proofs: Self::proofs(proof_height),
+                        signer: Self::signer(),
+                    }));
+                ctx.deliver(msg)
+            }
         }
     }
 }
@@ -461,7 +911,8 @@ impl modelator::step_runner::StepRunner<Step> for IbcTestRunner {
         );
         // initiliaze all chains
         for (chain_id, chain) in step.chains {
-            self.init_chain_context(chain_id, chain.height);
+            self.init_chain_context(chain_id.clone(), chain.height);
+            self.init_chain_state(chain_id, chain);
         }
         Ok(())
     }
@@ -518,6 +969,60 @@ impl modelator::step_runner::StepRunner<Step> for IbcTestRunner {
             ),
             ActionOutcome::Ics03ConnectionOpenAckOk => result.is_ok(),
             ActionOutcome::Ics03ConnectionOpenConfirmOk => result.is_ok(),
+            ActionOutcome::Ics04ChannelOpenInitOk => result.is_ok(),
+            ActionOutcome::Ics04ChannelOpenTryOk => result.is_ok(),
+            ActionOutcome::Ics04ConnectionNotOpen => matches!(
+                Self::extract_ics04_error_kind(result),
+                channel_error::ErrorDetail::ConnectionNotOpen(_)
+            ),
+            ActionOutcome::Ics04InvalidProof => matches!(
+                Self::extract_ics04_error_kind(result),
+                channel_error::ErrorDetail::VerifyChannelFailed(_)
+                    | channel_error::ErrorDetail::PacketVerificationFailed(_)
+            ),
+            ActionOutcome::Ics04ChannelOpenAckOk => result.is_ok(),
+            ActionOutcome::Ics04ChannelNotFound => matches!(
+                Self::extract_ics04_error_kind(result),
+                channel_error::ErrorDetail::ChannelNotFound(_)
+            ),
+            ActionOutcome::Ics04InvalidChannelState => matches!(
+                Self::extract_ics04_error_kind(result),
+                channel_error::ErrorDetail::InvalidChannelState(_)
+            ),
+            ActionOutcome::Ics04ChannelOpenConfirmOk => result.is_ok(),
+            ActionOutcome::Ics04ChannelCloseInitOk => result.is_ok(),
+            ActionOutcome::Ics04ChannelCloseConfirmOk => result.is_ok(),
+            ActionOutcome::Ics04ChannelClosed => matches!(
+                Self::extract_ics04_error_kind(result),
+                channel_error::ErrorDetail::ChannelClosed(_)
+            ),
+            ActionOutcome::Ics04SendPacketOk => result.is_ok(),
+            ActionOutcome::Ics04InvalidPacketCounterparty => matches!(
+                Self::extract_ics04_error_kind(result),
+                channel_error::ErrorDetail::InvalidPacketCounterparty(_)
+            ),
+            ActionOutcome::Ics04LowPacketHeight => matches!(
+                Self::extract_ics04_error_kind(result),
+                channel_error::ErrorDetail::LowPacketHeight(_)
+            ),
+            ActionOutcome::Ics04RecvPacketOk => result.is_ok(),
+            // receiving a packet twice is a no-op, which is not an error
+            ActionOutcome::Ics04PacketAlreadyReceived => result.is_ok(),
+            ActionOutcome::Ics04InvalidPacketSequence => matches!(
+                Self::extract_ics04_error_kind(result),
+                channel_error::ErrorDetail::InvalidPacketSequence(_)
+            ),
+            ActionOutcome::Ics04AcknowledgementOk => result.is_ok(),
+            ActionOutcome::Ics04PacketCommitmentNotFound => matches!(
+                Self::extract_ics04_error_kind(result),
+                channel_error::ErrorDetail::PacketCommitmentNotFound(_)
+            ),
+            ActionOutcome::Ics04TimeoutOk => result.is_ok(),
+            ActionOutcome::Ics04PacketTimeoutHeightNotReached => matches!(
+                Self::extract_ics04_error_kind(result),
+                channel_error::ErrorDetail::PacketTimeoutHeightNotReached(_)
+            ),
+            ActionOutcome::Ics04TimeoutOnCloseOk => result.is_ok(),
         };
 
         // Validate chains
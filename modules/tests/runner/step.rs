@@ -1,6 +1,7 @@
 use alloc::collections::btree_map::BTreeMap as HashMap;
 use core::fmt::Debug;
 use ibc::core::ics03_connection::connection::State as ConnectionState;
+use ibc::core::ics04_channel::channel::{Order, State as ChannelState};
 use serde::{Deserialize, Deserializer};
 
 use ibc::Height;
@@ -115,6 +116,118 @@ pub enum Action {
         #[serde(alias = "counterpartyConnectionId")]
         counterparty_connection_id: u64,
     },
+    Ics04ChannelOpenInit {
+        #[serde(alias = "chainId")]
+        chain_id: String,
+
+        #[serde(alias = "connectionId")]
+        connection_id: u64,
+
+        order: Order,
+    },
+    Ics04ChannelOpenTry {
+        #[serde(alias = "chainId")]
+        chain_id: String,
+
+        #[serde(alias = "connectionId")]
+        connection_id: u64,
+
+        order: Order,
+
+        #[serde(alias = "counterpartyChannelId")]
+        counterparty_channel_id: u64,
+
+        #[serde(alias = "proofHeight")]
+        proof_height: Height,
+    },
+    Ics04ChannelOpenAck {
+        #[serde(alias = "chainId")]
+        chain_id: String,
+
+        #[serde(alias = "channelId")]
+        channel_id: u64,
+
+        #[serde(alias = "counterpartyChannelId")]
+        counterparty_channel_id: u64,
+
+        #[serde(alias = "proofHeight")]
+        proof_height: Height,
+    },
+    Ics04ChannelOpenConfirm {
+        #[serde(alias = "chainId")]
+        chain_id: String,
+
+        #[serde(alias = "channelId")]
+        channel_id: u64,
+
+        #[serde(alias = "proofHeight")]
+        proof_height: Height,
+    },
+    Ics04ChannelCloseInit {
+        #[serde(alias = "chainId")]
+        chain_id: String,
+
+        #[serde(alias = "channelId")]
+        channel_id: u64,
+    },
+    Ics04ChannelCloseConfirm {
+        #[serde(alias = "chainId")]
+        chain_id: String,
+
+        #[serde(alias = "channelId")]
+        channel_id: u64,
+
+        #[serde(alias = "proofHeight")]
+        proof_height: Height,
+    },
+    Ics04SendPacket {
+        #[serde(alias = "chainId")]
+        chain_id: String,
+
+        packet: Packet,
+    },
+    Ics04RecvPacket {
+        #[serde(alias = "chainId")]
+        chain_id: String,
+
+        packet: Packet,
+
+        #[serde(alias = "proofHeight")]
+        proof_height: Height,
+    },
+    Ics04Acknowledgement {
+        #[serde(alias = "chainId")]
+        chain_id: String,
+
+        packet: Packet,
+
+        #[serde(alias = "proofHeight")]
+        proof_height: Height,
+    },
+    Ics04Timeout {
+        #[serde(alias = "chainId")]
+        chain_id: String,
+
+        packet: Packet,
+
+        #[serde(alias = "proofHeight")]
+        proof_height: Height,
+
+        #[serde(alias = "nextRecvSeq")]
+        next_recv_seq: u64,
+    },
+    Ics04TimeoutOnClose {
+        #[serde(alias = "chainId")]
+        chain_id: String,
+
+        packet: Packet,
+
+        #[serde(alias = "proofHeight")]
+        proof_height: Height,
+
+        #[serde(alias = "nextRecvSeq")]
+        next_recv_seq: u64,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
@@ -138,6 +251,29 @@ pub enum ActionOutcome {
     Ics03InvalidProof,
     Ics03ConnectionOpenAckOk,
     Ics03ConnectionOpenConfirmOk,
+
+    Ics04ChannelOpenInitOk,
+    Ics04ChannelOpenTryOk,
+    Ics04ConnectionNotOpen,
+    Ics04InvalidProof,
+    Ics04ChannelOpenAckOk,
+    Ics04ChannelNotFound,
+    Ics04InvalidChannelState,
+    Ics04ChannelOpenConfirmOk,
+    Ics04ChannelCloseInitOk,
+    Ics04ChannelCloseConfirmOk,
+    Ics04ChannelClosed,
+    Ics04SendPacketOk,
+    Ics04InvalidPacketCounterparty,
+    Ics04LowPacketHeight,
+    Ics04RecvPacketOk,
+    Ics04PacketAlreadyReceived,
+    Ics04InvalidPacketSequence,
+    Ics04AcknowledgementOk,
+    Ics04PacketCommitmentNotFound,
+    Ics04TimeoutOk,
+    Ics04PacketTimeoutHeightNotReached,
+    Ics04TimeoutOnCloseOk,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
@@ -147,6 +283,14 @@ pub struct Chain {
     pub clients: HashMap<u64, Client>,
 
     pub connections: HashMap<u64, Connection>,
+
+    pub channels: HashMap<u64, Channel>,
+
+    #[serde(alias = "packetCommitments")]
+    pub packet_commitments: Vec<Packet>,
+
+    #[serde(alias = "packetReceipts")]
+    pub packet_receipts: Vec<Packet>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
@@ -175,8 +319,53 @@ pub struct Connection {
     pub state: ConnectionState,
 }
 
-/// On the model, a non-existing `client_id` and a `connection_id` is
-/// represented with -1.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Channel {
+    pub state: ChannelState,
+
+    pub order: Order,
+
+    #[serde(alias = "connectionId")]
+    #[serde(default, deserialize_with = "deserialize_id")]
+    pub connection_id: Option<u64>,
+
+    #[serde(alias = "counterpartyChannelId")]
+    #[serde(default, deserialize_with = "deserialize_id")]
+    pub counterparty_channel_id: Option<u64>,
+
+    #[serde(alias = "nextSendSeq")]
+    pub next_send_seq: u64,
+
+    #[serde(alias = "nextRecvSeq")]
+    pub next_recv_seq: u64,
+
+    #[serde(alias = "nextAckSeq")]
+    pub next_ack_seq: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Packet {
+    pub sequence: u64,
+
+    #[serde(alias = "sourceChainId")]
+    pub source_chain_id: String,
+
+    #[serde(alias = "sourceChannelId")]
+    pub source_channel_id: u64,
+
+    #[serde(alias = "destChainId")]
+    pub dest_chain_id: String,
+
+    #[serde(alias = "destChannelId")]
+    #[serde(default, deserialize_with = "deserialize_id")]
+    pub dest_channel_id: Option<u64>,
+
+    #[serde(alias = "timeoutHeight")]
+    pub timeout_height: Height,
+}
+
+/// On the model, a non-existing `client_id`, `connection_id` and
+/// `channel_id` is represented with -1.
 /// For this reason, this function maps a `Some(-1)` to a `None`.
 fn deserialize_id<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
 where
@@ -1,6 +1,6 @@
 --------------------------------- MODULE IBC ----------------------------------
 
-EXTENDS ICS02, ICS03
+EXTENDS ICS02, ICS03, ICS04
 
 CONSTANTS
   \* ids of existing chains
@@ -17,12 +17,20 @@ CONSTANTS
   MaxClientsPerChain,
   \* max number of connections to be created per chain
   \* @type: Int;
-  MaxConnectionsPerChain
+  MaxConnectionsPerChain,
+  \* max number of channels to be created per chain
+  \* @type: Int;
+  MaxChannelsPerChain,
+  \* max number of packets to be sent per channel
+  \* @type: Int;
+  MaxPacketsPerChannel
 
 ASSUME MaxRevisionHeight >= 0
 ASSUME MaxRevisionNumber >= 0
 ASSUME MaxClientsPerChain >= 0
 ASSUME MaxConnectionsPerChain >= 0
+ASSUME MaxChannelsPerChain >= 0
+ASSUME MaxPacketsPerChannel >= 0
 
 VARIABLES
   \* mapping from chain id to its data
@@ -51,6 +59,34 @@ ConnectionStates == {
     "TryOpen",
     "Open"
 }
+\* set of possible channel identifiers
+ChannelIds == 0..(MaxChannelsPerChain - 1)
+\* set of possible channel states
+ChannelStates == {
+    "Uninitialized",
+    "Init",
+    "TryOpen",
+    "Open",
+    "Closed"
+}
+\* set of possible channel orderings
+ChannelOrders == {
+    "Unordered",
+    "Ordered"
+}
+\* set of possible packet sequences
+Sequences == 1..MaxPacketsPerChannel
+\* set of possible packets
+Packets == [
+    sequence: Sequences,
+    sourceChainId: ChainIds,
+    sourceChannelId: ChannelIds,
+    destChainId: ChainIds,
+    \* `destChannelId` is none if the channel does not know its counterparty
+    destChannelId: ChannelIds \union {ChannelIdNone},
+    \* `timeoutHeight` contains simply a height
+    timeoutHeight: Heights
+]
 
 \* set of possible actions
 NoneActions == [
@@ -127,10 +163,89 @@ ConnectionActions ==
     ConnectionOpenAckActions \union
     ConnectionOpenConfirmActions
 
+ChannelOpenInitActions == [
+    type: {"Ics04ChannelOpenInit"},
+    chainId: ChainIds,
+    connectionId: ConnectionIds,
+    order: ChannelOrders
+]
+ChannelOpenTryActions == [
+    type: {"Ics04ChannelOpenTry"},
+    chainId: ChainIds,
+    connectionId: ConnectionIds,
+    order: ChannelOrders,
+    counterpartyChannelId: ChannelIds,
+    proofHeight: Heights
+]
+ChannelOpenAckActions == [
+    type: {"Ics04ChannelOpenAck"},
+    chainId: ChainIds,
+    channelId: ChannelIds,
+    counterpartyChannelId: ChannelIds,
+    proofHeight: Heights
+]
+ChannelOpenConfirmActions == [
+    type: {"Ics04ChannelOpenConfirm"},
+    chainId: ChainIds,
+    channelId: ChannelIds,
+    proofHeight: Heights
+]
+ChannelCloseInitActions == [
+    type: {"Ics04ChannelCloseInit"},
+    chainId: ChainIds,
+    channelId: ChannelIds
+]
+ChannelCloseConfirmActions == [
+    type: {"Ics04ChannelCloseConfirm"},
+    chainId: ChainIds,
+    channelId: ChannelIds,
+    proofHeight: Heights
+]
+ChannelActions ==
+    ChannelOpenInitActions \union
+    ChannelOpenTryActions \union
+    ChannelOpenAckActions \union
+    ChannelOpenConfirmActions \union
+    ChannelCloseInitActions \union
+    ChannelCloseConfirmActions
+
+SendPacketActions == [
+    type: {"Ics04SendPacket"},
+    chainId: ChainIds,
+    packet: Packets
+]
+RecvPacketActions == [
+    type: {"Ics04RecvPacket"},
+    chainId: ChainIds,
+    packet: Packets,
+    proofHeight: Heights
+]
+AcknowledgementActions == [
+    type: {"Ics04Acknowledgement"},
+    chainId: ChainIds,
+    packet: Packets,
+    proofHeight: Heights
+]
+TimeoutActions == [
+    type: {"Ics04Timeout", "Ics04TimeoutOnClose"},
+    chainId: ChainIds,
+    packet: Packets,
+    proofHeight: Heights,
+    \* `nextRecvSeq` is the counterparty's next receive sequence
+    nextRecvSeq: Sequences \union {MaxPacketsPerChannel + 1}
+]
+PacketActions ==
+    SendPacketActions \union
+    RecvPacketActions \union
+    AcknowledgementActions \union
+    TimeoutActions
+
 Actions ==
     NoneActions \union
     ClientActions \union 
-    ConnectionActions
+    ConnectionActions \union
+    ChannelActions \union
+    PacketActions
 
 \* set of possible action outcomes
 ActionOutcomes == {
@@ -157,7 +272,40 @@ ActionOutcomes == {
     \* ICS03_ConnectionOpenAck outcomes:
     "Ics03ConnectionOpenAckOk",
     \* ICS03_ConnectionOpenConfirm outcomes:
-    "Ics03ConnectionOpenConfirmOk"
+    "Ics03ConnectionOpenConfirmOk",
+    \* ICS04_ChannelOpenInit outcomes:
+    "Ics04ChannelOpenInitOk",
+    \* ICS04_ChannelOpenTry outcomes:
+    "Ics04ChannelOpenTryOk",
+    "Ics04ConnectionNotOpen",
+    "Ics04InvalidProof",
+    \* ICS04_ChannelOpenAck outcomes:
+    "Ics04ChannelOpenAckOk",
+    "Ics04ChannelNotFound",
+    "Ics04InvalidChannelState",
+    \* ICS04_ChannelOpenConfirm outcomes:
+    "Ics04ChannelOpenConfirmOk",
+    \* ICS04_ChannelCloseInit outcomes:
+    "Ics04ChannelCloseInitOk",
+    \* ICS04_ChannelCloseConfirm outcomes:
+    "Ics04ChannelCloseConfirmOk",
+    "Ics04ChannelClosed",
+    \* ICS04_SendPacket outcomes:
+    "Ics04SendPacketOk",
+    "Ics04InvalidPacketCounterparty",
+    "Ics04LowPacketHeight",
+    \* ICS04_RecvPacket outcomes:
+    "Ics04RecvPacketOk",
+    "Ics04PacketAlreadyReceived",
+    "Ics04InvalidPacketSequence",
+    \* ICS04_Acknowledgement outcomes:
+    "Ics04AcknowledgementOk",
+    "Ics04PacketCommitmentNotFound",
+    \* ICS04_Timeout outcomes:
+    "Ics04TimeoutOk",
+    "Ics04PacketTimeoutHeightNotReached",
+    \* ICS04_TimeoutOnClose outcomes:
+    "Ics04TimeoutOnCloseOk"
 }
 \* TODO: the current generation of tests cannot distinguish between a
 \*       "Ics03ConnectionMismatch" generated in conn open try, one generated
@@ -189,6 +337,27 @@ Connection == [
 Connections == [
     ConnectionIds -> Connection
 ]
+\* data kept per channel
+Channel == [
+    state: ChannelStates,
+    order: ChannelOrders,
+    channelId: ChannelIds \union {ChannelIdNone},
+    connectionId: ConnectionIds \union {ConnectionIdNone},
+    counterpartyChainId: ChainIds \union {ChainIdNone},
+    counterpartyChannelId: ChannelIds \union {ChannelIdNone},
+    nextSendSeq: 1..(MaxPacketsPerChannel + 1),
+    nextRecvSeq: 1..(MaxPacketsPerChannel + 1),
+    nextAckSeq: 1..(MaxPacketsPerChannel + 1)
+]
+\* mapping from channel identifier to its data
+Channels == [
+    ChannelIds -> Channel
+]
+\* proof that a chain stored a channel in a given state
+ChannelProof == [
+    chainId: ChainIds,
+    channel: Channel
+]
 \* data kept per chain
 Chain == [
     height: Heights,
@@ -196,7 +365,12 @@ Chain == [
     clientIdCounter: 0..MaxClientsPerChain,
     connections: Connections,
     connectionIdCounter: 0..MaxConnectionsPerChain,
-    connectionProofs: SUBSET ConnectionActions
+    connectionProofs: SUBSET ConnectionActions,
+    channels: Channels,
+    channelIdCounter: 0..MaxChannelsPerChain,
+    channelProofs: SUBSET ChannelProof,
+    packetCommitments: SUBSET Packets,
+    packetReceipts: SUBSET Packets
 ]
 \* mapping from chain identifier to its data
 Chains == [
@@ -402,6 +576,293 @@ ConnectionOpenConfirm(
     /\ action' = result.action
     /\ actionOutcome' = result.outcome
 
+\* update the counterparty chain with a proof of the channel if outcome was ok
+\* @type: (CHAIN_ID, CHAIN, [channels: CHANNELS, outcome: Str], Str, CHANNEL_ID) => CHAINS;
+UpdateChainsWithChannelProof(chainId, updatedChain, result, okOutcome, channelId) ==
+    IF result.outcome = okOutcome THEN
+        LET channel == result.channels[channelId] IN
+        LET proof == [chainId |-> chainId, channel |-> channel] IN
+        [chains EXCEPT
+            ![chainId] = updatedChain,
+            ![channel.counterpartyChainId] = [@ EXCEPT
+                !.channelProofs = @ \union {proof}
+            ]
+        ]
+    ELSE
+        [chains EXCEPT ![chainId] = updatedChain]
+
+\* latest height of the client underlying `connectionId`; this is the height
+\* at which proofs from the counterparty chain are provided
+\* @type: (CHAIN, CONNECTION_ID) => HEIGHT;
+ConnectionProofHeight(chain, connectionId) ==
+    IF ICS03_ConnectionExists(chain.connections, connectionId) THEN
+        LET connection == ICS03_GetConnection(chain.connections, connectionId) IN
+        FindMaxHeight(ICS02_GetClient(chain.clients, connection.clientId).heights)
+    ELSE
+        \* the handlers fail before looking at the proofs, so any height works
+        chain.height
+
+\* latest height of the client underlying `channelId`
+\* @type: (CHAIN, CHANNEL_ID) => HEIGHT;
+ChannelProofHeight(chain, channelId) ==
+    IF ICS04_ChannelExists(chain.channels, channelId) THEN
+        ConnectionProofHeight(chain, ICS04_GetChannel(chain.channels, channelId).connectionId)
+    ELSE
+        \* the handlers fail before looking at the proofs, so any height works
+        chain.height
+
+ChannelOpenInit(chainId, connectionId, order) ==
+    LET chain == chains[chainId] IN
+    LET result == ICS04_ChannelOpenInit(
+        chain,
+        chainId,
+        connectionId,
+        order
+    ) IN
+    \* update the chain
+    LET updatedChain == [chain EXCEPT
+        !.height = UpdateRevisionHeight(@, result, "Ics04ChannelOpenInitOk"),
+        !.channels = result.channels,
+        !.channelIdCounter = result.channelIdCounter
+    ] IN
+    \* update `chains` (including a proof on the counterparty chain), set the
+    \* `action` and its `actionOutcome`
+    /\ chains' = UpdateChainsWithChannelProof(
+        chainId,
+        updatedChain,
+        result,
+        "Ics04ChannelOpenInitOk",
+        chain.channelIdCounter
+    )
+    /\ action' = result.action
+    /\ actionOutcome' = result.outcome
+
+ChannelOpenTry(chainId, connectionId, order, counterpartyChannelId) ==
+    LET chain == chains[chainId] IN
+    LET result == ICS04_ChannelOpenTry(
+        chain,
+        chainId,
+        connectionId,
+        order,
+        counterpartyChannelId,
+        ConnectionProofHeight(chain, connectionId)
+    ) IN
+    \* update the chain
+    LET updatedChain == [chain EXCEPT
+        !.height = UpdateRevisionHeight(@, result, "Ics04ChannelOpenTryOk"),
+        !.channels = result.channels,
+        !.channelIdCounter = result.channelIdCounter
+    ] IN
+    \* update `chains` (including a proof on the counterparty chain), set the
+    \* `action` and its `actionOutcome`
+    /\ chains' = UpdateChainsWithChannelProof(
+        chainId,
+        updatedChain,
+        result,
+        "Ics04ChannelOpenTryOk",
+        chain.channelIdCounter
+    )
+    /\ action' = result.action
+    /\ actionOutcome' = result.outcome
+
+ChannelOpenAck(chainId, channelId, counterpartyChannelId) ==
+    LET chain == chains[chainId] IN
+    LET result == ICS04_ChannelOpenAck(
+        chain,
+        chainId,
+        channelId,
+        counterpartyChannelId,
+        ChannelProofHeight(chain, channelId)
+    ) IN
+    \* update the chain
+    LET updatedChain == [chain EXCEPT
+        !.height = UpdateRevisionHeight(@, result, "Ics04ChannelOpenAckOk"),
+        !.channels = result.channels
+    ] IN
+    \* update `chains` (including a proof on the counterparty chain), set the
+    \* `action` and its `actionOutcome`
+    /\ chains' = UpdateChainsWithChannelProof(
+        chainId,
+        updatedChain,
+        result,
+        "Ics04ChannelOpenAckOk",
+        channelId
+    )
+    /\ action' = result.action
+    /\ actionOutcome' = result.outcome
+
+ChannelOpenConfirm(chainId, channelId) ==
+    LET chain == chains[chainId] IN
+    LET result == ICS04_ChannelOpenConfirm(
+        chain,
+        chainId,
+        channelId,
+        ChannelProofHeight(chain, channelId)
+    ) IN
+    \* update the chain
+    LET updatedChain == [chain EXCEPT
+        !.height = UpdateRevisionHeight(@, result, "Ics04ChannelOpenConfirmOk"),
+        !.channels = result.channels
+    ] IN
+    \* no need to update the counterparty chain with a proof (as in the other
+    \* channel open handlers)
+    \* update `chains`, set the `action` and its `actionOutcome`
+    /\ chains' = [chains EXCEPT ![chainId] = updatedChain]
+    /\ action' = result.action
+    /\ actionOutcome' = result.outcome
+
+ChannelCloseInit(chainId, channelId) ==
+    LET chain == chains[chainId] IN
+    LET result == ICS04_ChannelCloseInit(
+        chain,
+        chainId,
+        channelId
+    ) IN
+    \* update the chain
+    LET updatedChain == [chain EXCEPT
+        !.height = UpdateRevisionHeight(@, result, "Ics04ChannelCloseInitOk"),
+        !.channels = result.channels
+    ] IN
+    \* update `chains` (including a proof on the counterparty chain), set the
+    \* `action` and its `actionOutcome`
+    /\ chains' = UpdateChainsWithChannelProof(
+        chainId,
+        updatedChain,
+        result,
+        "Ics04ChannelCloseInitOk",
+        channelId
+    )
+    /\ action' = result.action
+    /\ actionOutcome' = result.outcome
+
+ChannelCloseConfirm(chainId, channelId) ==
+    LET chain == chains[chainId] IN
+    LET result == ICS04_ChannelCloseConfirm(
+        chain,
+        chainId,
+        channelId,
+        ChannelProofHeight(chain, channelId)
+    ) IN
+    \* update the chain
+    LET updatedChain == [chain EXCEPT
+        !.height = UpdateRevisionHeight(@, result, "Ics04ChannelCloseConfirmOk"),
+        !.channels = result.channels
+    ] IN
+    \* update `chains`, set the `action` and its `actionOutcome`
+    /\ chains' = [chains EXCEPT ![chainId] = updatedChain]
+    /\ action' = result.action
+    /\ actionOutcome' = result.outcome
+
+SendPacket(chainId, channelId, timeoutHeight) ==
+    LET chain == chains[chainId] IN
+    LET result == ICS04_SendPacket(
+        chain,
+        chainId,
+        channelId,
+        timeoutHeight
+    ) IN
+    \* update the chain
+    LET updatedChain == [chain EXCEPT
+        !.height = UpdateRevisionHeight(@, result, "Ics04SendPacketOk"),
+        !.channels = result.channels,
+        !.packetCommitments = result.packetCommitments
+    ] IN
+    \* update `chains`, set the `action` and its `actionOutcome`
+    /\ chains' = [chains EXCEPT ![chainId] = updatedChain]
+    /\ action' = result.action
+    /\ actionOutcome' = result.outcome
+
+RecvPacket(chainId, packet) ==
+    LET chain == chains[chainId] IN
+    LET result == ICS04_RecvPacket(
+        chain,
+        chainId,
+        packet,
+        ChannelProofHeight(chain, packet.destChannelId)
+    ) IN
+    \* receiving a packet twice is a no-op, but it still succeeds
+    LET succeeded ==
+        result.outcome \in {"Ics04RecvPacketOk", "Ics04PacketAlreadyReceived"} IN
+    \* update the chain
+    LET updatedChain == [chain EXCEPT
+        !.height = IF succeeded THEN UpdateRevisionHeight(@, result, result.outcome) ELSE @,
+        !.channels = result.channels,
+        !.packetReceipts = result.packetReceipts
+    ] IN
+    \* update `chains`, set the `action` and its `actionOutcome`
+    /\ chains' = [chains EXCEPT ![chainId] = updatedChain]
+    /\ action' = result.action
+    /\ actionOutcome' = result.outcome
+
+Acknowledgement(chainId, packet) ==
+    LET chain == chains[chainId] IN
+    LET result == ICS04_Acknowledgement(
+        chain,
+        chainId,
+        packet,
+        ChannelProofHeight(chain, packet.sourceChannelId)
+    ) IN
+    \* update the chain
+    LET updatedChain == [chain EXCEPT
+        !.height = UpdateRevisionHeight(@, result, "Ics04AcknowledgementOk"),
+        !.channels = result.channels,
+        !.packetCommitments = result.packetCommitments
+    ] IN
+    \* update `chains`, set the `action` and its `actionOutcome`
+    /\ chains' = [chains EXCEPT ![chainId] = updatedChain]
+    /\ action' = result.action
+    /\ actionOutcome' = result.outcome
+
+Timeout(chainId, packet) ==
+    LET chain == chains[chainId] IN
+    \* the proofs of the counterparty chain are taken from its current state
+    LET counterpartyChain == chains[packet.destChainId] IN
+    LET counterpartyChannel ==
+        ICS04_GetChannel(counterpartyChain.channels, packet.destChannelId) IN
+    LET result == ICS04_Timeout(
+        chain,
+        chainId,
+        packet,
+        ChannelProofHeight(chain, packet.sourceChannelId),
+        counterpartyChannel.nextRecvSeq,
+        packet \in counterpartyChain.packetReceipts
+    ) IN
+    \* update the chain
+    LET updatedChain == [chain EXCEPT
+        !.height = UpdateRevisionHeight(@, result, "Ics04TimeoutOk"),
+        !.channels = result.channels,
+        !.packetCommitments = result.packetCommitments
+    ] IN
+    \* update `chains`, set the `action` and its `actionOutcome`
+    /\ chains' = [chains EXCEPT ![chainId] = updatedChain]
+    /\ action' = result.action
+    /\ actionOutcome' = result.outcome
+
+TimeoutOnClose(chainId, packet) ==
+    LET chain == chains[chainId] IN
+    \* the proofs of the counterparty chain are taken from its current state
+    LET counterpartyChain == chains[packet.destChainId] IN
+    LET counterpartyChannel ==
+        ICS04_GetChannel(counterpartyChain.channels, packet.destChannelId) IN
+    LET result == ICS04_TimeoutOnClose(
+        chain,
+        chainId,
+        packet,
+        ChannelProofHeight(chain, packet.sourceChannelId),
+        counterpartyChannel.nextRecvSeq,
+        packet \in counterpartyChain.packetReceipts,
+        counterpartyChannel.state = "Closed"
+    ) IN
+    \* update the chain
+    LET updatedChain == [chain EXCEPT
+        !.height = UpdateRevisionHeight(@, result, "Ics04TimeoutOnCloseOk"),
+        !.packetCommitments = result.packetCommitments
+    ] IN
+    \* update `chains`, set the `action` and its `actionOutcome`
+    /\ chains' = [chains EXCEPT ![chainId] = updatedChain]
+    /\ action' = result.action
+    /\ actionOutcome' = result.outcome
+
 CreateClientAction(chainId) ==
     \* select a height for the client to be created at
     \E height \in Heights:
@@ -525,6 +986,102 @@ ConnectionOpenConfirmAction(chainId) ==
         ELSE
             UNCHANGED vars
 
+ChannelOpenInitAction(chainId) ==
+    \* select a connection id
+    \E connectionId \in ConnectionIds:
+    \* select a channel ordering
+    \E order \in ChannelOrders:
+        \* only create channel if the model constant `MaxChannelsPerChain`
+        \* allows it
+        LET allowed == chains[chainId].channelIdCounter < MaxChannelsPerChain IN
+        IF allowed THEN
+            ChannelOpenInit(chainId, connectionId, order)
+        ELSE
+            UNCHANGED vars
+
+ChannelOpenTryAction(chainId) ==
+    \* select a connection id
+    \E connectionId \in ConnectionIds:
+    \* select a channel ordering
+    \E order \in ChannelOrders:
+    \* select a counterparty channel id
+    \E counterpartyChannelId \in ChannelIds:
+        \* only create channel if the model constant `MaxChannelsPerChain`
+        \* allows it
+        LET allowed == chains[chainId].channelIdCounter < MaxChannelsPerChain IN
+        IF allowed THEN
+            ChannelOpenTry(chainId, connectionId, order, counterpartyChannelId)
+        ELSE
+            UNCHANGED vars
+
+ChannelOpenAckAction(chainId) ==
+    \* select a channel id
+    \E channelId \in ChannelIds:
+    \* select a counterparty channel id
+    \E counterpartyChannelId \in ChannelIds:
+        ChannelOpenAck(chainId, channelId, counterpartyChannelId)
+
+ChannelOpenConfirmAction(chainId) ==
+    \* select a channel id
+    \E channelId \in ChannelIds:
+        ChannelOpenConfirm(chainId, channelId)
+
+ChannelCloseInitAction(chainId) ==
+    \* select a channel id
+    \E channelId \in ChannelIds:
+        ChannelCloseInit(chainId, channelId)
+
+ChannelCloseConfirmAction(chainId) ==
+    \* select a channel id
+    \E channelId \in ChannelIds:
+        ChannelCloseConfirm(chainId, channelId)
+
+SendPacketAction(chainId) ==
+    \* select a channel id
+    \E channelId \in ChannelIds:
+    \* select a timeout height
+    \* We only use heights at the same revision number to save on state space
+    \E timeoutHeight \in {height \in Heights: height.revision_number = chains[chainId].height.revision_number}:
+        \* only send packet if the model constant `MaxPacketsPerChannel`
+        \* allows it
+        LET channel == ICS04_GetChannel(chains[chainId].channels, channelId) IN
+        LET allowed == channel.nextSendSeq <= MaxPacketsPerChannel IN
+        IF allowed THEN
+            SendPacket(chainId, channelId, timeoutHeight)
+        ELSE
+            UNCHANGED vars
+
+RecvPacketAction(chainId) ==
+    \* select a chain that committed to a packet
+    \E counterpartyChainId \in ChainIds:
+    \* select one of its packets (there's no point in relaying packets that
+    \* were never sent, as their proofs would not verify)
+    \E packet \in chains[counterpartyChainId].packetCommitments:
+        IF packet.destChainId = chainId THEN
+            RecvPacket(chainId, packet)
+        ELSE
+            UNCHANGED vars
+
+AcknowledgementAction(chainId) ==
+    \* select a chain that received a packet
+    \E counterpartyChainId \in ChainIds:
+    \* select one of the packets it received
+    \E packet \in chains[counterpartyChainId].packetReceipts:
+        IF packet.sourceChainId = chainId THEN
+            Acknowledgement(chainId, packet)
+        ELSE
+            UNCHANGED vars
+
+TimeoutAction(chainId) ==
+    \* select one of the packets committed by this chain
+    \E packet \in chains[chainId].packetCommitments:
+        Timeout(chainId, packet)
+
+TimeoutOnCloseAction(chainId) ==
+    \* select one of the packets committed by this chain
+    \E packet \in chains[chainId].packetCommitments:
+        TimeoutOnClose(chainId, packet)
+
 Init ==
     \* create a client and a connection with none values
     LET 
@@ -543,6 +1100,19 @@ Init ==
         counterpartyClientId |-> ClientIdNone,
         counterpartyConnectionId |-> ConnectionIdNone
     ] IN
+    LET 
+       \* @type: CHANNEL;
+       channelNone == [
+        state |-> "Uninitialized",
+        order |-> "Unordered",
+        channelId |-> ChannelIdNone,
+        connectionId |-> ConnectionIdNone,
+        counterpartyChainId |-> ChainIdNone,
+        counterpartyChannelId |-> ChannelIdNone,
+        nextSendSeq |-> 1,
+        nextRecvSeq |-> 1,
+        nextAckSeq |-> 1
+    ] IN
     \* create an empty chain
     LET 
        \* @type: CHAIN;
@@ -552,12 +1122,87 @@ Init ==
         clientIdCounter |-> 0,
         connections |-> [connectionId \in ConnectionIds |-> connectionNone],
         connectionIdCounter |-> 0,
-        connectionProofs |-> {}
+        connectionProofs |-> {},
+        channels |-> [channelId \in ChannelIds |-> channelNone],
+        channelIdCounter |-> 0,
+        channelProofs |-> {},
+        packetCommitments |-> {},
+        packetReceipts |-> {}
     ] IN
     /\ chains = [chainId \in ChainIds |-> emptyChain]
     /\ action = [type |-> "None"]
     /\ actionOutcome = "None"
 
+\* Channels can only be established on top of open connections, which take
+\* several steps to reach with `Init`. To keep the state space small, the
+\* channel and packet tests start from two chains that already share a
+\* client and an open connection.
+ChannelInit ==
+    LET 
+      \* @type: HEIGHT;
+      initialHeight == [ revision_number |-> 1, revision_height |-> 1 ] IN
+    LET 
+      \* @type: CLIENT;
+      clientNone == [
+        heights |-> {}
+    ] IN
+    LET 
+      \* @type: CHANNEL;
+      channelNone == [
+        state |-> "Uninitialized",
+        order |-> "Unordered",
+        channelId |-> ChannelIdNone,
+        connectionId |-> ConnectionIdNone,
+        counterpartyChainId |-> ChainIdNone,
+        counterpartyChannelId |-> ChannelIdNone,
+        nextSendSeq |-> 1,
+        nextRecvSeq |-> 1,
+        nextAckSeq |-> 1
+    ] IN
+    \* the client and the connection of each chain point to the other chain,
+    \* and both always get the first identifier
+    LET 
+      \* @type: (CHAIN_ID) => CHAIN;
+      connectedChain(chainId) ==
+        LET counterpartyChainId == CHOOSE id \in ChainIds: id /= chainId IN
+        [
+            height |-> initialHeight,
+            clients |-> [clientId \in ClientIds |->
+                IF clientId = 0 THEN [heights |-> {initialHeight}] ELSE clientNone
+            ],
+            clientIdCounter |-> 1,
+            connections |-> [connectionId \in ConnectionIds |->
+                IF connectionId = 0 THEN [
+                    state |-> "Open",
+                    chainId |-> chainId,
+                    clientId |-> 0,
+                    connectionId |-> 0,
+                    counterpartyChainId |-> counterpartyChainId,
+                    counterpartyClientId |-> 0,
+                    counterpartyConnectionId |-> 0
+                ] ELSE [
+                    state |-> "Uninitialized",
+                    chainId |-> ChainIdNone,
+                    clientId |-> ClientIdNone,
+                    connectionId |-> ConnectionIdNone,
+                    counterpartyChainId |-> ChainIdNone,
+                    counterpartyClientId |-> ClientIdNone,
+                    counterpartyConnectionId |-> ConnectionIdNone
+                ]
+            ],
+            connectionIdCounter |-> 1,
+            connectionProofs |-> {},
+            channels |-> [channelId \in ChannelIds |-> channelNone],
+            channelIdCounter |-> 0,
+            channelProofs |-> {},
+            packetCommitments |-> {},
+            packetReceipts |-> {}
+        ] IN
+    /\ Cardinality(ChainIds) = 2
+    /\ chains = [chainId \in ChainIds |-> connectedChain(chainId)]
+    /\ action = [type |-> "None"]
+    /\ actionOutcome = "None"
+
 Next ==
     \* select a chain id
     \E chainId \in ChainIds:
@@ -576,6 +1221,31 @@ Next ==
         ELSE
             \/ UNCHANGED vars
 
+\* Same as `Next`, but only with the actions that make sense on top of
+\* `ChannelInit`: client updates (to advance the proof heights), and the
+\* channel and packet actions
+ChannelNext ==
+    \* select a chain id
+    \E chainId \in ChainIds:
+        \* perform action on chain if the model constant `MaxChainHeight` allows
+        \* it
+        IF chains[chainId].height.revision_number < MaxHeight.revision_number /\ chains[chainId].height.revision_height < MaxHeight.revision_height THEN
+            \/ UpdateClientAction(chainId)
+            \/ ChannelOpenInitAction(chainId)
+            \/ ChannelOpenTryAction(chainId)
+            \/ ChannelOpenAckAction(chainId)
+            \/ ChannelOpenConfirmAction(chainId)
+            \/ ChannelCloseInitAction(chainId)
+            \/ ChannelCloseConfirmAction(chainId)
+            \/ SendPacketAction(chainId)
+            \/ RecvPacketAction(chainId)
+            \/ AcknowledgementAction(chainId)
+            \/ TimeoutAction(chainId)
+            \/ TimeoutOnCloseAction(chainId)
+            \/ UNCHANGED vars
+        ELSE
+            \/ UNCHANGED vars
+
 (******************************** Invariants *********************************)
 
 TypeOK ==
@@ -16,12 +16,25 @@ EXTENDS Integers, FiniteSets, TLC
 \*   counterpartyClientId: CLIENT_ID, counterpartyConnectionId: CONNECTION_ID ];
 \* @typeAlias: CONNECTIONS = CONNECTION_ID -> CONNECTION;
 \*
+\* @typeAlias: CHANNEL_ID = Int;
+\* @typeAlias: CHANNEL = [ state: Str, order: Str, channelId: CHANNEL_ID,
+\*   connectionId: CONNECTION_ID, counterpartyChainId: CHAIN_ID,
+\*   counterpartyChannelId: CHANNEL_ID, nextSendSeq: Int, nextRecvSeq: Int, nextAckSeq: Int ];
+\* @typeAlias: CHANNELS = CHANNEL_ID -> CHANNEL;
+\* @typeAlias: CHANNEL_PROOF = [ chainId: CHAIN_ID, channel: CHANNEL ];
+\* @typeAlias: PACKET = [ sequence: Int, sourceChainId: CHAIN_ID, sourceChannelId: CHANNEL_ID,
+\*   destChainId: CHAIN_ID, destChannelId: CHANNEL_ID, timeoutHeight: HEIGHT ];
+\*
 \* @typeAlias: ACTION = [ type: Str, chainId: CHAIN_ID, clientState: HEIGHT, consensusState: HEIGHT,
 \*   clientId: CLIENT_ID, header: HEIGHT, previousConnectionId: Int, counterpartyChainId: CHAIN_ID,
-\*   counterpartyClientId: CLIENT_ID, counterpartyConnectionId: Int];
+\*   counterpartyClientId: CLIENT_ID, counterpartyConnectionId: Int, connectionId: CONNECTION_ID,
+\*   channelId: CHANNEL_ID, order: Str, counterpartyChannelId: CHANNEL_ID, proofHeight: HEIGHT,
+\*   packet: PACKET, nextRecvSeq: Int ];
 \*
 \* @typeAlias: CHAIN = [ height: HEIGHT, clients:  CLIENTS, clientIdCounter: Int,
-\*   connections: CONNECTIONS, connectionIdCounter: Int, connectionProofs: Set(ACTION) ];
+\*   connections: CONNECTIONS, connectionIdCounter: Int, connectionProofs: Set(ACTION),
+\*   channels: CHANNELS, channelIdCounter: Int, channelProofs: Set(CHANNEL_PROOF),
+\*   packetCommitments: Set(PACKET), packetReceipts: Set(PACKET) ];
 \* @typeAlias: CHAINS = CHAIN_ID -> CHAIN;
 \* 
 Typedefs == TRUE
@@ -85,5 +98,7 @@ ChainIdNone == "-1"
 ClientIdNone == -1
 \* if a connection identifier is not set then it is -1
 ConnectionIdNone == -1
+\* if a channel identifier is not set then it is -1
+ChannelIdNone == -1
 
 ===============================================================================
@@ -0,0 +1,877 @@
+------------------------------ MODULE ICS04 -----------------------------------
+
+EXTENDS ICS03
+
+\* retrieves `channelId`'s data
+\* @type: (CHANNELS, CHANNEL_ID) => CHANNEL;
+ICS04_GetChannel(channels, channelId) ==
+    channels[channelId]
+
+\* check if `channelId` exists
+\* @type: (CHANNELS, CHANNEL_ID) => Bool;
+ICS04_ChannelExists(channels, channelId) ==
+    ICS04_GetChannel(channels, channelId).state /= "Uninitialized"
+
+\* update `channelId`'s data
+\* @type: (CHANNELS, CHANNEL_ID, CHANNEL) => CHANNELS;
+ICS04_SetChannel(channels, channelId, channel) ==
+    [channels EXCEPT ![channelId] = channel]
+
+\* check if there's a proof that the counterparty chain `chainId` stored
+\* `channelId` in state `state` and with counterparty `counterpartyChannelId`
+\* @type: (Set(CHANNEL_PROOF), CHAIN_ID, CHANNEL_ID, Str, CHANNEL_ID) => Bool;
+ICS04_ChannelProofExists(channelProofs, chainId, channelId, state, counterpartyChannelId) ==
+    LET proofs == {
+        proof \in channelProofs :
+            /\ proof.chainId = chainId
+            /\ proof.channel.channelId = channelId
+            /\ proof.channel.state = state
+            /\ proof.channel.counterpartyChannelId = counterpartyChannelId
+    } IN
+    Cardinality(proofs) > 0
+
+\* @type: (CHAIN, CHAIN_ID, CONNECTION_ID, Str)
+\*   => [channels: CHANNELS, channelIdCounter: Int, action: ACTION, outcome: Str];
+ICS04_ChannelOpenInit(
+    chain,
+    chainId,
+    connectionId,
+    order
+) ==
+    LET action_ == [
+        type |-> "Ics04ChannelOpenInit",
+        chainId |-> chainId,
+        connectionId |-> connectionId,
+        order |-> order
+    ] IN
+    \* check if the connection exists
+    IF ~ICS03_ConnectionExists(chain.connections, connectionId) THEN
+        \* if the connection does not exist, then set an error outcome
+        [
+            channels |-> chain.channels,
+            channelIdCounter |-> chain.channelIdCounter,
+            action |-> action_,
+            outcome |-> "Ics03ConnectionNotFound"
+        ]
+    ELSE
+        \* if the connection exists,
+        \* then check if the channel exists (it shouldn't)
+        IF ICS04_ChannelExists(chain.channels, chain.channelIdCounter) THEN
+            \* if the channel to be created already exists,
+            \* then there's an error in the model
+            [
+                channels |-> chain.channels,
+                channelIdCounter |-> chain.channelIdCounter,
+                action |-> action_,
+                outcome |-> "ModelError"
+            ]
+        ELSE
+            \* if it doesn't, create it
+            LET connection == ICS03_GetConnection(chain.connections, connectionId) IN
+            LET channel == [
+                state |-> "Init",
+                order |-> order,
+                \* generate a new channel identifier
+                channelId |-> chain.channelIdCounter,
+                connectionId |-> connectionId,
+                counterpartyChainId |-> connection.counterpartyChainId,
+                counterpartyChannelId |-> ChannelIdNone,
+                nextSendSeq |-> 1,
+                nextRecvSeq |-> 1,
+                nextAckSeq |-> 1
+            ] IN
+            \* return result with updated state
+            [
+                channels |-> ICS04_SetChannel(
+                    chain.channels,
+                    chain.channelIdCounter,
+                    channel
+                ),
+                channelIdCounter |-> chain.channelIdCounter + 1,
+                action |-> action_,
+                outcome |-> "Ics04ChannelOpenInitOk"
+            ]
+
+\* @type: (CHAIN, CHAIN_ID, CONNECTION_ID, Str, CHANNEL_ID, HEIGHT)
+\*   => [channels: CHANNELS, channelIdCounter: Int, action: ACTION, outcome: Str];
+ICS04_ChannelOpenTry(
+    chain,
+    chainId,
+    connectionId,
+    order,
+    counterpartyChannelId,
+    proofHeight
+) ==
+    LET action_ == [
+        type |-> "Ics04ChannelOpenTry",
+        chainId |-> chainId,
+        connectionId |-> connectionId,
+        order |-> order,
+        counterpartyChannelId |-> counterpartyChannelId,
+        proofHeight |-> proofHeight
+    ] IN
+    \* check if the connection exists
+    IF ~ICS03_ConnectionExists(chain.connections, connectionId) THEN
+        \* if the connection does not exist, then set an error outcome
+        [
+            channels |-> chain.channels,
+            channelIdCounter |-> chain.channelIdCounter,
+            action |-> action_,
+            outcome |-> "Ics03ConnectionNotFound"
+        ]
+    ELSE
+        LET connection == ICS03_GetConnection(chain.connections, connectionId) IN
+        \* check if the connection is open
+        IF connection.state /= "Open" THEN
+            \* if the connection is not open, then set an error outcome
+            [
+                channels |-> chain.channels,
+                channelIdCounter |-> chain.channelIdCounter,
+                action |-> action_,
+                outcome |-> "Ics04ConnectionNotOpen"
+            ]
+        ELSE
+            \* check if there was an open init at the remote chain
+            LET openInitProofs == {
+                proof \in chain.channelProofs :
+                    /\ proof.chainId = connection.counterpartyChainId
+                    /\ proof.channel.channelId = counterpartyChannelId
+                    /\ proof.channel.state = "Init"
+                    /\ proof.channel.order = order
+            } IN
+            LET proofExists == Cardinality(openInitProofs) > 0 IN
+            IF ~proofExists THEN
+                \* if there wasn't an open init at the remote chain,
+                \* then set an error outcome
+                [
+                    channels |-> chain.channels,
+                    channelIdCounter |-> chain.channelIdCounter,
+                    action |-> action_,
+                    outcome |-> "Ics04InvalidProof"
+                ]
+            ELSE
+                \* verification passed; create channel
+                LET channel == [
+                    state |-> "TryOpen",
+                    order |-> order,
+                    \* generate a new channel identifier
+                    channelId |-> chain.channelIdCounter,
+                    connectionId |-> connectionId,
+                    counterpartyChainId |-> connection.counterpartyChainId,
+                    counterpartyChannelId |-> counterpartyChannelId,
+                    nextSendSeq |-> 1,
+                    nextRecvSeq |-> 1,
+                    nextAckSeq |-> 1
+                ] IN
+                \* return result with updated state
+                [
+                    channels |-> ICS04_SetChannel(
+                        chain.channels,
+                        chain.channelIdCounter,
+                        channel
+                    ),
+                    channelIdCounter |-> chain.channelIdCounter + 1,
+                    action |-> action_,
+                    outcome |-> "Ics04ChannelOpenTryOk"
+                ]
+
+\* @type: (CHAIN, CHAIN_ID, CHANNEL_ID, CHANNEL_ID, HEIGHT)
+\*   => [channels: CHANNELS, action: ACTION, outcome: Str];
+ICS04_ChannelOpenAck(
+    chain,
+    chainId,
+    channelId,
+    counterpartyChannelId,
+    proofHeight
+) ==
+    LET action_ == [
+        type |-> "Ics04ChannelOpenAck",
+        chainId |-> chainId,
+        channelId |-> channelId,
+        counterpartyChannelId |-> counterpartyChannelId,
+        proofHeight |-> proofHeight
+    ] IN
+    LET channels == chain.channels IN
+    \* check if the channel exists
+    IF ~ICS04_ChannelExists(channels, channelId) THEN
+        \* if the channel does not exist, then set an error outcome
+        [
+            channels |-> channels,
+            action |-> action_,
+            outcome |-> "Ics04ChannelNotFound"
+        ]
+    ELSE
+        \* if the channel exists, verify that is either Init or TryOpen
+        LET channel == ICS04_GetChannel(channels, channelId) IN
+        IF channel.state \notin {"Init", "TryOpen"} THEN
+            \* if the existing channel is in the wrong state, then set an
+            \* error outcome
+            [
+                channels |-> channels,
+                action |-> action_,
+                outcome |-> "Ics04InvalidChannelState"
+            ]
+        ELSE
+            \* check if the channel's connection is open
+            LET connection == ICS03_GetConnection(chain.connections, channel.connectionId) IN
+            IF connection.state /= "Open" THEN
+                \* if the connection is not open, then set an error outcome
+                [
+                    channels |-> channels,
+                    action |-> action_,
+                    outcome |-> "Ics04ConnectionNotOpen"
+                ]
+            ELSE
+                \* check if there was an open try at the remote chain
+                LET proofExists == ICS04_ChannelProofExists(
+                    chain.channelProofs,
+                    channel.counterpartyChainId,
+                    counterpartyChannelId,
+                    "TryOpen",
+                    channelId
+                ) IN
+                IF ~proofExists THEN
+                    \* if there wasn't an open try at the remote chain,
+                    \* then set an error outcome
+                    [
+                        channels |-> channels,
+                        action |-> action_,
+                        outcome |-> "Ics04InvalidProof"
+                    ]
+                ELSE
+                    \* verification passed; update the channel state to
+                    \* "Open"
+                    LET updatedChannel == [channel EXCEPT
+                        !.state = "Open",
+                        !.counterpartyChannelId = counterpartyChannelId
+                    ] IN
+                    \* return result with updated state
+                    [
+                        channels |-> ICS04_SetChannel(
+                            channels,
+                            channelId,
+                            updatedChannel
+                        ),
+                        action |-> action_,
+                        outcome |-> "Ics04ChannelOpenAckOk"
+                    ]
+
+\* @type: (CHAIN, CHAIN_ID, CHANNEL_ID, HEIGHT)
+\*   => [channels: CHANNELS, action: ACTION, outcome: Str];
+ICS04_ChannelOpenConfirm(
+    chain,
+    chainId,
+    channelId,
+    proofHeight
+) ==
+    LET action_ == [
+        type |-> "Ics04ChannelOpenConfirm",
+        chainId |-> chainId,
+        channelId |-> channelId,
+        proofHeight |-> proofHeight
+    ] IN
+    LET channels == chain.channels IN
+    \* check if the channel exists
+    IF ~ICS04_ChannelExists(channels, channelId) THEN
+        \* if the channel does not exist, then set an error outcome
+        [
+            channels |-> channels,
+            action |-> action_,
+            outcome |-> "Ics04ChannelNotFound"
+        ]
+    ELSE
+        \* if the channel exists, verify that is TryOpen
+        LET channel == ICS04_GetChannel(channels, channelId) IN
+        IF channel.state /= "TryOpen" THEN
+            \* if the existing channel is in the wrong state, then set an
+            \* error outcome
+            [
+                channels |-> channels,
+                action |-> action_,
+                outcome |-> "Ics04InvalidChannelState"
+            ]
+        ELSE
+            \* check if the channel's connection is open
+            LET connection == ICS03_GetConnection(chain.connections, channel.connectionId) IN
+            IF connection.state /= "Open" THEN
+                \* if the connection is not open, then set an error outcome
+                [
+                    channels |-> channels,
+                    action |-> action_,
+                    outcome |-> "Ics04ConnectionNotOpen"
+                ]
+            ELSE
+                \* check if there was an open ack at the remote chain
+                LET proofExists == ICS04_ChannelProofExists(
+                    chain.channelProofs,
+                    channel.counterpartyChainId,
+                    channel.counterpartyChannelId,
+                    "Open",
+                    channelId
+                ) IN
+                IF ~proofExists THEN
+                    \* if there wasn't an open ack at the remote chain,
+                    \* then set an error outcome
+                    [
+                        channels |-> channels,
+                        action |-> action_,
+                        outcome |-> "Ics04InvalidProof"
+                    ]
+                ELSE
+                    \* verification passed; update the channel state to
+                    \* "Open"
+                    LET updatedChannel == [channel EXCEPT
+                        !.state = "Open"
+                    ] IN
+                    \* return result with updated state
+                    [
+                        channels |-> ICS04_SetChannel(
+                            channels,
+                            channelId,
+                            updatedChannel
+                        ),
+                        action |-> action_,
+                        outcome |-> "Ics04ChannelOpenConfirmOk"
+                    ]
+
+\* @type: (CHAIN, CHAIN_ID, CHANNEL_ID)
+\*   => [channels: CHANNELS, action: ACTION, outcome: Str];
+ICS04_ChannelCloseInit(
+    chain,
+    chainId,
+    channelId
+) ==
+    LET action_ == [
+        type |-> "Ics04ChannelCloseInit",
+        chainId |-> chainId,
+        channelId |-> channelId
+    ] IN
+    LET channels == chain.channels IN
+    \* check if the channel exists
+    IF ~ICS04_ChannelExists(channels, channelId) THEN
+        \* if the channel does not exist, then set an error outcome
+        [
+            channels |-> channels,
+            action |-> action_,
+            outcome |-> "Ics04ChannelNotFound"
+        ]
+    ELSE
+        \* if the channel exists, verify that it is not yet closed
+        LET channel == ICS04_GetChannel(channels, channelId) IN
+        IF channel.state = "Closed" THEN
+            \* if the channel is already closed, then set an error outcome
+            [
+                channels |-> channels,
+                action |-> action_,
+                outcome |-> "Ics04InvalidChannelState"
+            ]
+        ELSE
+            \* check if the channel's connection is open
+            LET connection == ICS03_GetConnection(chain.connections, channel.connectionId) IN
+            IF connection.state /= "Open" THEN
+                \* if the connection is not open, then set an error outcome
+                [
+                    channels |-> channels,
+                    action |-> action_,
+                    outcome |-> "Ics04ConnectionNotOpen"
+                ]
+            ELSE
+                \* update the channel state to "Closed"
+                LET updatedChannel == [channel EXCEPT
+                    !.state = "Closed"
+                ] IN
+                \* return result with updated state
+                [
+                    channels |-> ICS04_SetChannel(
+                        channels,
+                        channelId,
+                        updatedChannel
+                    ),
+                    action |-> action_,
+                    outcome |-> "Ics04ChannelCloseInitOk"
+                ]
+
+\* @type: (CHAIN, CHAIN_ID, CHANNEL_ID, HEIGHT)
+\*   => [channels: CHANNELS, action: ACTION, outcome: Str];
+ICS04_ChannelCloseConfirm(
+    chain,
+    chainId,
+    channelId,
+    proofHeight
+) ==
+    LET action_ == [
+        type |-> "Ics04ChannelCloseConfirm",
+        chainId |-> chainId,
+        channelId |-> channelId,
+        proofHeight |-> proofHeight
+    ] IN
+    LET channels == chain.channels IN
+    \* check if the channel exists
+    IF ~ICS04_ChannelExists(channels, channelId) THEN
+        \* if the channel does not exist, then set an error outcome
+        [
+            channels |-> channels,
+            action |-> action_,
+            outcome |-> "Ics04ChannelNotFound"
+        ]
+    ELSE
+        \* if the channel exists, verify that it is not yet closed
+        LET channel == ICS04_GetChannel(channels, channelId) IN
+        IF channel.state = "Closed" THEN
+            \* if the channel is already closed, then set an error outcome
+            [
+                channels |-> channels,
+                action |-> action_,
+                outcome |-> "Ics04ChannelClosed"
+            ]
+        ELSE
+            \* check if the channel's connection is open
+            LET connection == ICS03_GetConnection(chain.connections, channel.connectionId) IN
+            IF connection.state /= "Open" THEN
+                \* if the connection is not open, then set an error outcome
+                [
+                    channels |-> channels,
+                    action |-> action_,
+                    outcome |-> "Ics04ConnectionNotOpen"
+                ]
+            ELSE
+                \* check if there was a close init at the remote chain
+                LET proofExists == ICS04_ChannelProofExists(
+                    chain.channelProofs,
+                    channel.counterpartyChainId,
+                    channel.counterpartyChannelId,
+                    "Closed",
+                    channelId
+                ) IN
+                IF ~proofExists THEN
+                    \* if there wasn't a close init at the remote chain,
+                    \* then set an error outcome
+                    [
+                        channels |-> channels,
+                        action |-> action_,
+                        outcome |-> "Ics04InvalidProof"
+                    ]
+                ELSE
+                    \* verification passed; update the channel state to
+                    \* "Closed"
+                    LET updatedChannel == [channel EXCEPT
+                        !.state = "Closed"
+                    ] IN
+                    \* return result with updated state
+                    [
+                        channels |-> ICS04_SetChannel(
+                            channels,
+                            channelId,
+                            updatedChannel
+                        ),
+                        action |-> action_,
+                        outcome |-> "Ics04ChannelCloseConfirmOk"
+                    ]
+
+\* Sending a packet is not a datagram processed by the routing module, but it
+\* is needed to produce the packet commitments that the remaining packet
+\* handlers act upon.
+\* @type: (CHAIN, CHAIN_ID, CHANNEL_ID, HEIGHT)
+\*   => [channels: CHANNELS, packetCommitments: Set(PACKET), action: ACTION, outcome: Str];
+ICS04_SendPacket(
+    chain,
+    chainId,
+    channelId,
+    timeoutHeight
+) ==
+    LET channel == ICS04_GetChannel(chain.channels, channelId) IN
+    LET packet == [
+        sequence |-> channel.nextSendSeq,
+        sourceChainId |-> chainId,
+        sourceChannelId |-> channelId,
+        destChainId |-> channel.counterpartyChainId,
+        destChannelId |-> channel.counterpartyChannelId,
+        timeoutHeight |-> timeoutHeight
+    ] IN
+    LET action_ == [
+        type |-> "Ics04SendPacket",
+        chainId |-> chainId,
+        packet |-> packet
+    ] IN
+    \* check if the channel exists
+    IF ~ICS04_ChannelExists(chain.channels, channelId) THEN
+        \* if the channel does not exist, then set an error outcome
+        [
+            channels |-> chain.channels,
+            packetCommitments |-> chain.packetCommitments,
+            action |-> action_,
+            outcome |-> "Ics04ChannelNotFound"
+        ]
+    ELSE IF channel.state = "Closed" THEN
+        \* if the channel is closed, then set an error outcome
+        [
+            channels |-> chain.channels,
+            packetCommitments |-> chain.packetCommitments,
+            action |-> action_,
+            outcome |-> "Ics04ChannelClosed"
+        ]
+    ELSE IF channel.counterpartyChannelId = ChannelIdNone THEN
+        \* if the channel does not know its counterparty yet, then the packet
+        \* cannot be addressed to it
+        [
+            channels |-> chain.channels,
+            packetCommitments |-> chain.packetCommitments,
+            action |-> action_,
+            outcome |-> "Ics04InvalidPacketCounterparty"
+        ]
+    ELSE
+        \* check if the packet has already timed out according to the latest
+        \* height of the client tracking the counterparty chain
+        LET connection == ICS03_GetConnection(chain.connections, channel.connectionId) IN
+        LET client == ICS02_GetClient(chain.clients, connection.clientId) IN
+        IF HeightGT(FindMaxHeight(client.heights), timeoutHeight) THEN
+            \* if the packet has timed out, then set an error outcome
+            [
+                channels |-> chain.channels,
+                packetCommitments |-> chain.packetCommitments,
+                action |-> action_,
+                outcome |-> "Ics04LowPacketHeight"
+            ]
+        ELSE
+            \* increment the send sequence and store the commitment
+            LET updatedChannel == [channel EXCEPT
+                !.nextSendSeq = @ + 1
+            ] IN
+            [
+                channels |-> ICS04_SetChannel(
+                    chain.channels,
+                    channelId,
+                    updatedChannel
+                ),
+                packetCommitments |-> chain.packetCommitments \union {packet},
+                action |-> action_,
+                outcome |-> "Ics04SendPacketOk"
+            ]
+
+\* @type: (CHAIN, CHAIN_ID, PACKET, HEIGHT)
+\*   => [channels: CHANNELS, packetReceipts: Set(PACKET), action: ACTION, outcome: Str];
+ICS04_RecvPacket(
+    chain,
+    chainId,
+    packet,
+    proofHeight
+) ==
+    LET action_ == [
+        type |-> "Ics04RecvPacket",
+        chainId |-> chainId,
+        packet |-> packet,
+        proofHeight |-> proofHeight
+    ] IN
+    LET channels == chain.channels IN
+    LET channelId == packet.destChannelId IN
+    \* check if the destination channel exists
+    IF ~ICS04_ChannelExists(channels, channelId) THEN
+        \* if the channel does not exist, then set an error outcome
+        [
+            channels |-> channels,
+            packetReceipts |-> chain.packetReceipts,
+            action |-> action_,
+            outcome |-> "Ics04ChannelNotFound"
+        ]
+    ELSE
+        \* if the channel exists, verify that it is open
+        LET channel == ICS04_GetChannel(channels, channelId) IN
+        IF channel.state /= "Open" THEN
+            \* if the channel is not open, then set an error outcome
+            [
+                channels |-> channels,
+                packetReceipts |-> chain.packetReceipts,
+                action |-> action_,
+                outcome |-> "Ics04InvalidChannelState"
+            ]
+        ELSE
+            \* check if the packet has already timed out on this chain
+            IF HeightGT(chain.height, packet.timeoutHeight) THEN
+                \* if the packet has timed out, then set an error outcome
+                [
+                    channels |-> channels,
+                    packetReceipts |-> chain.packetReceipts,
+                    action |-> action_,
+                    outcome |-> "Ics04LowPacketHeight"
+                ]
+            ELSE IF channel.order = "Ordered" THEN
+                \* ordered channels only accept the next expected sequence;
+                \* older sequences are acknowledged as no-ops
+                IF packet.sequence < channel.nextRecvSeq THEN
+                    [
+                        channels |-> channels,
+                        packetReceipts |-> chain.packetReceipts,
+                        action |-> action_,
+                        outcome |-> "Ics04PacketAlreadyReceived"
+                    ]
+                ELSE IF packet.sequence /= channel.nextRecvSeq THEN
+                    [
+                        channels |-> channels,
+                        packetReceipts |-> chain.packetReceipts,
+                        action |-> action_,
+                        outcome |-> "Ics04InvalidPacketSequence"
+                    ]
+                ELSE
+                    LET updatedChannel == [channel EXCEPT
+                        !.nextRecvSeq = @ + 1
+                    ] IN
+                    [
+                        channels |-> ICS04_SetChannel(
+                            channels,
+                            channelId,
+                            updatedChannel
+                        ),
+                        packetReceipts |-> chain.packetReceipts \union {packet},
+                        action |-> action_,
+                        outcome |-> "Ics04RecvPacketOk"
+                    ]
+            ELSE
+                \* unordered channels accept any sequence, but only once
+                IF packet \in chain.packetReceipts THEN
+                    [
+                        channels |-> channels,
+                        packetReceipts |-> chain.packetReceipts,
+                        action |-> action_,
+                        outcome |-> "Ics04PacketAlreadyReceived"
+                    ]
+                ELSE
+                    [
+                        channels |-> channels,
+                        packetReceipts |-> chain.packetReceipts \union {packet},
+                        action |-> action_,
+                        outcome |-> "Ics04RecvPacketOk"
+                    ]
+
+\* @type: (CHAIN, CHAIN_ID, PACKET, HEIGHT)
+\*   => [channels: CHANNELS, packetCommitments: Set(PACKET), action: ACTION, outcome: Str];
+ICS04_Acknowledgement(
+    chain,
+    chainId,
+    packet,
+    proofHeight
+) ==
+    LET action_ == [
+        type |-> "Ics04Acknowledgement",
+        chainId |-> chainId,
+        packet |-> packet,
+        proofHeight |-> proofHeight
+    ] IN
+    LET channels == chain.channels IN
+    LET channelId == packet.sourceChannelId IN
+    \* check if the source channel exists
+    IF ~ICS04_ChannelExists(channels, channelId) THEN
+        \* if the channel does not exist, then set an error outcome
+        [
+            channels |-> channels,
+            packetCommitments |-> chain.packetCommitments,
+            action |-> action_,
+            outcome |-> "Ics04ChannelNotFound"
+        ]
+    ELSE
+        \* if the channel exists, verify that it is open
+        LET channel == ICS04_GetChannel(channels, channelId) IN
+        IF channel.state /= "Open" THEN
+            \* if the channel is not open, then set an error outcome
+            [
+                channels |-> channels,
+                packetCommitments |-> chain.packetCommitments,
+                action |-> action_,
+                outcome |-> "Ics04ChannelClosed"
+            ]
+        ELSE
+            \* check if the packet was sent and not yet acknowledged or timed
+            \* out
+            IF packet \notin chain.packetCommitments THEN
+                [
+                    channels |-> channels,
+                    packetCommitments |-> chain.packetCommitments,
+                    action |-> action_,
+                    outcome |-> "Ics04PacketCommitmentNotFound"
+                ]
+            ELSE IF channel.order = "Ordered" /\ packet.sequence /= channel.nextAckSeq THEN
+                \* ordered channels must process acknowledgements in order
+                [
+                    channels |-> channels,
+                    packetCommitments |-> chain.packetCommitments,
+                    action |-> action_,
+                    outcome |-> "Ics04InvalidPacketSequence"
+                ]
+            ELSE
+                \* increment the ack sequence (only relevant for ordered
+                \* channels) and delete the commitment
+                LET updatedChannel == [channel EXCEPT
+                    !.nextAckSeq = IF channel.order = "Ordered" THEN @ + 1 ELSE @
+                ] IN
+                [
+                    channels |-> ICS04_SetChannel(
+                        channels,
+                        channelId,
+                        updatedChannel
+                    ),
+                    packetCommitments |-> chain.packetCommitments \ {packet},
+                    action |-> action_,
+                    outcome |-> "Ics04AcknowledgementOk"
+                ]
+
+\* @type: (CHAIN, CHAIN_ID, PACKET, HEIGHT, Int, Bool)
+\*   => [channels: CHANNELS, packetCommitments: Set(PACKET), action: ACTION, outcome: Str];
+ICS04_Timeout(
+    chain,
+    chainId,
+    packet,
+    proofHeight,
+    nextRecvSeq,
+    packetReceived
+) ==
+    LET action_ == [
+        type |-> "Ics04Timeout",
+        chainId |-> chainId,
+        packet |-> packet,
+        proofHeight |-> proofHeight,
+        nextRecvSeq |-> nextRecvSeq
+    ] IN
+    LET channels == chain.channels IN
+    LET channelId == packet.sourceChannelId IN
+    \* check if the source channel exists
+    IF ~ICS04_ChannelExists(channels, channelId) THEN
+        \* if the channel does not exist, then set an error outcome
+        [
+            channels |-> channels,
+            packetCommitments |-> chain.packetCommitments,
+            action |-> action_,
+            outcome |-> "Ics04ChannelNotFound"
+        ]
+    ELSE
+        \* if the channel exists, verify that it is open
+        LET channel == ICS04_GetChannel(channels, channelId) IN
+        IF channel.state /= "Open" THEN
+            \* if the channel is not open, then set an error outcome
+            [
+                channels |-> channels,
+                packetCommitments |-> chain.packetCommitments,
+                action |-> action_,
+                outcome |-> "Ics04ChannelClosed"
+            ]
+        ELSE
+            \* check that the timeout height has passed on the counterparty
+            \* chain, according to the proof height
+            IF ~HeightGT(proofHeight, packet.timeoutHeight) THEN
+                [
+                    channels |-> channels,
+                    packetCommitments |-> chain.packetCommitments,
+                    action |-> action_,
+                    outcome |-> "Ics04PacketTimeoutHeightNotReached"
+                ]
+            ELSE IF packet \notin chain.packetCommitments THEN
+                \* if the packet was already acknowledged or timed out, then
+                \* set an error outcome
+                [
+                    channels |-> channels,
+                    packetCommitments |-> chain.packetCommitments,
+                    action |-> action_,
+                    outcome |-> "Ics04PacketCommitmentNotFound"
+                ]
+            ELSE IF channel.order = "Ordered" /\ packet.sequence < nextRecvSeq THEN
+                \* if the counterparty already received the packet, then set
+                \* an error outcome
+                [
+                    channels |-> channels,
+                    packetCommitments |-> chain.packetCommitments,
+                    action |-> action_,
+                    outcome |-> "Ics04InvalidPacketSequence"
+                ]
+            ELSE IF channel.order = "Unordered" /\ packetReceived THEN
+                \* if the counterparty stored a receipt for the packet, then
+                \* the proof of its absence is invalid
+                [
+                    channels |-> channels,
+                    packetCommitments |-> chain.packetCommitments,
+                    action |-> action_,
+                    outcome |-> "Ics04InvalidProof"
+                ]
+            ELSE
+                \* verification passed; ordered channels are closed on timeout
+                LET updatedChannel == [channel EXCEPT
+                    !.state = IF channel.order = "Ordered" THEN "Closed" ELSE @
+                ] IN
+                [
+                    channels |-> ICS04_SetChannel(
+                        channels,
+                        channelId,
+                        updatedChannel
+                    ),
+                    packetCommitments |-> chain.packetCommitments \ {packet},
+                    action |-> action_,
+                    outcome |-> "Ics04TimeoutOk"
+                ]
+
+\* @type: (CHAIN, CHAIN_ID, PACKET, HEIGHT, Int, Bool, Bool)
+\*   => [packetCommitments: Set(PACKET), action: ACTION, outcome: Str];
+ICS04_TimeoutOnClose(
+    chain,
+    chainId,
+    packet,
+    proofHeight,
+    nextRecvSeq,
+    packetReceived,
+    counterpartyChannelClosed
+) ==
+    LET action_ == [
+        type |-> "Ics04TimeoutOnClose",
+        chainId |-> chainId,
+        packet |-> packet,
+        proofHeight |-> proofHeight,
+        nextRecvSeq |-> nextRecvSeq
+    ] IN
+    LET channels == chain.channels IN
+    LET channelId == packet.sourceChannelId IN
+    \* check if the source channel exists
+    IF ~ICS04_ChannelExists(channels, channelId) THEN
+        \* if the channel does not exist, then set an error outcome
+        [
+            packetCommitments |-> chain.packetCommitments,
+            action |-> action_,
+            outcome |-> "Ics04ChannelNotFound"
+        ]
+    ELSE
+        LET channel == ICS04_GetChannel(channels, channelId) IN
+        \* check if the packet was sent and not yet acknowledged or timed out
+        IF packet \notin chain.packetCommitments THEN
+            [
+                packetCommitments |-> chain.packetCommitments,
+                action |-> action_,
+                outcome |-> "Ics04PacketCommitmentNotFound"
+            ]
+        ELSE IF ~counterpartyChannelClosed THEN
+            \* if the counterparty channel is not closed, then the proof of
+            \* its closure is invalid
+            [
+                packetCommitments |-> chain.packetCommitments,
+                action |-> action_,
+                outcome |-> "Ics04InvalidProof"
+            ]
+        ELSE IF channel.order = "Ordered" /\ packet.sequence < nextRecvSeq THEN
+            \* if the counterparty already received the packet, then set an
+            \* error outcome
+            [
+                packetCommitments |-> chain.packetCommitments,
+                action |-> action_,
+                outcome |-> "Ics04InvalidPacketSequence"
+            ]
+        ELSE IF channel.order = "Unordered" /\ packetReceived THEN
+            \* if the counterparty stored a receipt for the packet, then the
+            \* proof of its absence is invalid
+            [
+                packetCommitments |-> chain.packetCommitments,
+                action |-> action_,
+                outcome |-> "Ics04InvalidProof"
+            ]
+        ELSE
+            \* verification passed; delete the commitment
+            [
+                packetCommitments |-> chain.packetCommitments \ {packet},
+                action |-> action_,
+                outcome |-> "Ics04TimeoutOnCloseOk"
+            ]
+
+===============================================================================
@@ -50,13 +50,23 @@ impl CompileCmd {
             out_dir.display()
         );
 
+        let root = env!("CARGO_MANIFEST_DIR");
+
         // Paths
         let proto_paths = [
             // ibc-go proto files
             format!("{}/proto/ibc", ibc_dir.display()),
+            // ICS31 cross-chain queries, which ibc-go does not ship
+            format!("{}/../proto/definitions/interchain_query", root),
         ];
 
+        // ibc-go proto files which replace the ones of the same path in the ibc-go
+        // repo, to backport changes made in later ibc-go releases
+        let overlay_dir = PathBuf::from(format!("{}/../proto/definitions/ibc-go", root));
+
         let proto_includes_paths = [
+            overlay_dir.display().to_string(),
+            format!("{}/../proto", root),
             format!("{}/proto", ibc_dir.display()),
             format!("{}/third_party/proto", ibc_dir.display()),
         ];
@@ -79,6 +89,32 @@ impl CompileCmd {
             );
         }
 
+        // Substitute the overlay files for the ibc-go ones, and add the new ones
+        let ibc_proto_dir = ibc_dir.join("proto");
+        for proto in protos.iter_mut() {
+            if let Ok(relative) = proto.strip_prefix(&ibc_proto_dir) {
+                let overlay = overlay_dir.join(relative);
+                if overlay.exists() {
+                    *proto = overlay;
+                }
+            }
+        }
+
+        println!("Looking for overlay proto files in {:?}", overlay_dir);
+        for entry in WalkDir::new(&overlay_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                e.file_type().is_file()
+                    && e.path().extension().is_some()
+                    && e.path().extension().unwrap() == "proto"
+            })
+        {
+            if !protos.iter().any(|proto| proto == entry.path()) {
+                protos.push(entry.into_path());
+            }
+        }
+
         println!("Found the following protos:");
         // Show which protos will be compiled
         for proto in &protos {
@@ -96,6 +132,7 @@ impl CompileCmd {
         let attrs_eq = "#[derive(Eq)]";
         let attrs_serde_default = r#"#[serde(default)]"#;
         let attrs_serde_base64 = r#"#[serde(with = "crate::base64")]"#;
+        let attrs_serde_default_omit_empty = r#"#[serde(default, skip_serializing_if = "::prost::alloc::string::String::is_empty")]"#;
         let attrs_jsonschema_str =
             r#"#[cfg_attr(feature = "json-schema", schemars(with = "String"))]"#;
 
@@ -143,10 +180,15 @@ impl CompileCmd {
             .type_attribute(".ibc.core.types.v1", attrs_serde)
             .type_attribute(".ibc.applications.transfer.v1", attrs_serde)
             .type_attribute(".ibc.applications.transfer.v2", attrs_serde)
+            .field_attribute(
+                ".ibc.applications.transfer.v2.FungibleTokenPacketData.memo",
+                attrs_serde_default_omit_empty,
+            )
             .type_attribute(
                 ".ibc.applications.interchain_accounts.controller.v1",
                 attrs_serde,
             )
+            .type_attribute(".ibc.applications.interchain_query.v1", attrs_serde)
             .type_attribute(".ics23", attrs_serde)
             .type_attribute(".ics23.LeafOp", attrs_eq)
             .type_attribute(".ics23.LeafOp", attrs_jsonschema)
@@ -192,7 +234,12 @@ impl CompileCmd {
         // Paths
         let proto_paths = vec![
             format!("{}/../proto/definitions/mock", root),
+            // the node service, which the Cosmos SDK only ships from v0.46
+            format!("{}/../proto/definitions/node", root),
+            // the x/feemarket module, which is not part of the Cosmos SDK
+            format!("{}/../proto/definitions/feemarket", root),
             format!("{}/proto/cosmos/auth", sdk_dir.display()),
+            format!("{}/proto/cosmos/authz", sdk_dir.display()),
             format!("{}/proto/cosmos/gov", sdk_dir.display()),
             format!("{}/proto/cosmos/tx", sdk_dir.display()),
             format!("{}/proto/cosmos/base", sdk_dir.display()),
@@ -49,6 +49,11 @@ pub mod cosmos {
             }
         }
     }
+    pub mod authz {
+        pub mod v1beta1 {
+            include_proto!("cosmos.authz.v1beta1.rs");
+        }
+    }
     pub mod staking {
         pub mod v1beta1 {
             include_proto!("cosmos.staking.v1beta1.rs");
@@ -70,6 +75,11 @@ pub mod cosmos {
                 include_proto!("cosmos.base.kv.v1beta1.rs");
             }
         }
+        pub mod node {
+            pub mod v1beta1 {
+                include_proto!("cosmos.base.node.v1beta1.rs");
+            }
+        }
         pub mod query {
             pub mod v1beta1 {
                 include_proto!("cosmos.base.query.v1beta1.rs");
@@ -123,6 +133,15 @@ pub mod cosmos {
     }
 }
 
+pub mod feemarket {
+    #[allow(clippy::module_inception)]
+    pub mod feemarket {
+        pub mod v1 {
+            include_proto!("feemarket.feemarket.v1.rs");
+        }
+    }
+}
+
 pub mod ibc {
     #[deprecated(since = "0.15.0", note = "Use `ibc_proto::ibc::applications` instead")]
     pub mod apps {
@@ -137,6 +156,16 @@ pub mod ibc {
                 include_proto!("ibc.applications.transfer.v2.rs");
             }
         }
+        pub mod fee {
+            pub mod v1 {
+                include_proto!("ibc.applications.fee.v1.rs");
+            }
+        }
+        pub mod interchain_query {
+            pub mod v1 {
+                include_proto!("ibc.applications.interchain_query.v1.rs");
+            }
+        }
         pub mod interchain_accounts {
             pub mod v1 {
                 include_proto!("ibc.applications.interchain_accounts.v1.rs");
@@ -0,0 +1,550 @@
+// The x/feemarket module of Skip, which implements an EIP-1559 fee market.
+// See <https://github.com/skip-mev/feemarket>
+
+/// Params contains the required set of parameters for the EIP1559 fee market
+/// plugin implementation.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Params {
+    /// Alpha is the amount we additively increase the learning rate
+    /// when it is above or below the target +/- threshold.
+    ///
+    /// Must be > 0.
+    #[prost(string, tag="1")]
+    pub alpha: ::prost::alloc::string::String,
+    /// Beta is the amount we multiplicatively decrease the learning rate
+    /// when it is within the target +/- threshold.
+    ///
+    /// Must be [0, 1].
+    #[prost(string, tag="2")]
+    pub beta: ::prost::alloc::string::String,
+    /// Gamma is the threshold for the learning rate. If the learning rate is
+    /// above or below the target +/- threshold, we additively increase the
+    /// learning rate by Alpha. Otherwise, we multiplicatively decrease the
+    /// learning rate by Beta.
+    ///
+    /// Must be [0, 0.5].
+    #[prost(string, tag="3")]
+    pub gamma: ::prost::alloc::string::String,
+    /// Delta is the amount we additively increase/decrease the gas price when the
+    /// utilization is above/below the target +/- threshold.
+    #[prost(string, tag="4")]
+    pub delta: ::prost::alloc::string::String,
+    /// MinBaseGasPrice determines the initial gas price of the module and the
+    /// global minimum for the network.
+    #[prost(string, tag="5")]
+    pub min_base_gas_price: ::prost::alloc::string::String,
+    /// MinLearningRate is the lower bound for the learning rate.
+    #[prost(string, tag="6")]
+    pub min_learning_rate: ::prost::alloc::string::String,
+    /// MaxLearningRate is the upper bound for the learning rate.
+    #[prost(string, tag="7")]
+    pub max_learning_rate: ::prost::alloc::string::String,
+    /// MaxBlockUtilization is the maximum block utilization.
+    #[prost(uint64, tag="8")]
+    pub max_block_utilization: u64,
+    /// Window defines the window size for calculating an adaptive learning rate
+    /// over a moving window of blocks.
+    #[prost(uint64, tag="9")]
+    pub window: u64,
+    /// FeeDenom is the denom that will be used for all fee payments.
+    #[prost(string, tag="10")]
+    pub fee_denom: ::prost::alloc::string::String,
+    /// Enabled is a boolean that determines whether the EIP1559 fee market is
+    /// enabled.
+    #[prost(bool, tag="11")]
+    pub enabled: bool,
+    /// DistributeFees is a boolean that determines whether the fees are burned or
+    /// distributed to all stakers.
+    #[prost(bool, tag="12")]
+    pub distribute_fees: bool,
+}
+/// GenesisState defines the feemarket module's genesis state.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GenesisState {
+    /// Params are the parameters for the feemarket module. These parameters
+    /// can be utilized to implement both the base EIP-1559 fee market and
+    /// and the AIMD EIP-1559 fee market.
+    #[prost(message, optional, tag="1")]
+    pub params: ::core::option::Option<Params>,
+    /// State contains the current state of the AIMD fee market.
+    #[prost(message, optional, tag="2")]
+    pub state: ::core::option::Option<State>,
+}
+/// State is utilized to track the current state of the fee market. This includes
+/// the current base fee, learning rate, and block utilization within the
+/// specified AIMD window.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct State {
+    /// BaseGasPrice is the current base fee. This is denominated in the fee per
+    /// gas unit.
+    #[prost(string, tag="1")]
+    pub base_gas_price: ::prost::alloc::string::String,
+    /// LearningRate is the current learning rate.
+    #[prost(string, tag="2")]
+    pub learning_rate: ::prost::alloc::string::String,
+    /// Window contains a list of the last blocks' utilization values. This is used
+    /// to calculate the next base fee. This stores the number of units of gas
+    /// consumed per block.
+    #[prost(uint64, repeated, tag="3")]
+    pub window: ::prost::alloc::vec::Vec<u64>,
+    /// Index is the index of the current block in the block utilization window.
+    #[prost(uint64, tag="4")]
+    pub index: u64,
+}
+/// ParamsRequest is the request type for the Query/Params RPC method.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ParamsRequest {
+}
+/// ParamsResponse is the response type for the Query/Params RPC method.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ParamsResponse {
+    #[prost(message, optional, tag="1")]
+    pub params: ::core::option::Option<Params>,
+}
+/// StateRequest is the request type for the Query/State RPC method.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StateRequest {
+}
+/// StateResponse is the response type for the Query/State RPC method.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StateResponse {
+    #[prost(message, optional, tag="1")]
+    pub state: ::core::option::Option<State>,
+}
+/// GasPriceRequest is the request type for the Query/GasPrice RPC method.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GasPriceRequest {
+    /// denom we are querying gas price in
+    #[prost(string, tag="1")]
+    pub denom: ::prost::alloc::string::String,
+}
+/// GasPriceResponse is the response type for the Query/GasPrice RPC method.
+/// Returns a gas price in specified denom.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GasPriceResponse {
+    #[prost(message, optional, tag="1")]
+    pub price: ::core::option::Option<super::super::super::cosmos::base::v1beta1::DecCoin>,
+}
+/// GasPricesRequest is the request type for the Query/GasPrices RPC method.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GasPricesRequest {
+}
+/// GasPricesResponse is the response type for the Query/GasPrices RPC method.
+/// Returns a gas price in all available denoms.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GasPricesResponse {
+    #[prost(message, repeated, tag="1")]
+    pub prices: ::prost::alloc::vec::Vec<super::super::super::cosmos::base::v1beta1::DecCoin>,
+}
+/// Generated client implementations.
+#[cfg(feature = "client")]
+pub mod query_client {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Query Service for the feemarket module.
+    #[derive(Debug, Clone)]
+    pub struct QueryClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl QueryClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: std::convert::TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> QueryClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> QueryClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + Send + Sync,
+        {
+            QueryClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with `gzip`.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_gzip(mut self) -> Self {
+            self.inner = self.inner.send_gzip();
+            self
+        }
+        /// Enable decompressing responses with `gzip`.
+        #[must_use]
+        pub fn accept_gzip(mut self) -> Self {
+            self.inner = self.inner.accept_gzip();
+            self
+        }
+        /// Params returns the current feemarket module parameters.
+        pub async fn params(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ParamsRequest>,
+        ) -> Result<tonic::Response<super::ParamsResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/feemarket.feemarket.v1.Query/Params",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// State returns the current feemarket module state.
+        pub async fn state(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StateRequest>,
+        ) -> Result<tonic::Response<super::StateResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/feemarket.feemarket.v1.Query/State",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// GasPrice returns the current feemarket module gas price
+        /// for specified denom.
+        pub async fn gas_price(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GasPriceRequest>,
+        ) -> Result<tonic::Response<super::GasPriceResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/feemarket.feemarket.v1.Query/GasPrice",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// GasPrices returns the current feemarket module list of gas prices
+        /// in all available denoms.
+        pub async fn gas_prices(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GasPricesRequest>,
+        ) -> Result<tonic::Response<super::GasPricesResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/feemarket.feemarket.v1.Query/GasPrices",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+#[cfg(feature = "server")]
+pub mod query_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    ///Generated trait containing gRPC methods that should be implemented for use with QueryServer.
+    #[async_trait]
+    pub trait Query: Send + Sync + 'static {
+        /// Params returns the current feemarket module parameters.
+        async fn params(
+            &self,
+            request: tonic::Request<super::ParamsRequest>,
+        ) -> Result<tonic::Response<super::ParamsResponse>, tonic::Status>;
+        /// State returns the current feemarket module state.
+        async fn state(
+            &self,
+            request: tonic::Request<super::StateRequest>,
+        ) -> Result<tonic::Response<super::StateResponse>, tonic::Status>;
+        /// GasPrice returns the current feemarket module gas price
+        /// for specified denom.
+        async fn gas_price(
+            &self,
+            request: tonic::Request<super::GasPriceRequest>,
+        ) -> Result<tonic::Response<super::GasPriceResponse>, tonic::Status>;
+        /// GasPrices returns the current feemarket module list of gas prices
+        /// in all available denoms.
+        async fn gas_prices(
+            &self,
+            request: tonic::Request<super::GasPricesRequest>,
+        ) -> Result<tonic::Response<super::GasPricesResponse>, tonic::Status>;
+    }
+    /// Query Service for the feemarket module.
+    #[derive(Debug)]
+    pub struct QueryServer<T: Query> {
+        inner: _Inner<T>,
+        accept_compression_encodings: (),
+        send_compression_encodings: (),
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: Query> QueryServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for QueryServer<T>
+    where
+        T: Query,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/feemarket.feemarket.v1.Query/Params" => {
+                    #[allow(non_camel_case_types)]
+                    struct ParamsSvc<T: Query>(pub Arc<T>);
+                    impl<T: Query> tonic::server::UnaryService<super::ParamsRequest>
+                    for ParamsSvc<T> {
+                        type Response = super::ParamsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ParamsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).params(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ParamsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/feemarket.feemarket.v1.Query/State" => {
+                    #[allow(non_camel_case_types)]
+                    struct StateSvc<T: Query>(pub Arc<T>);
+                    impl<T: Query> tonic::server::UnaryService<super::StateRequest>
+                    for StateSvc<T> {
+                        type Response = super::StateResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StateRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).state(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = StateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/feemarket.feemarket.v1.Query/GasPrice" => {
+                    #[allow(non_camel_case_types)]
+                    struct GasPriceSvc<T: Query>(pub Arc<T>);
+                    impl<T: Query> tonic::server::UnaryService<super::GasPriceRequest>
+                    for GasPriceSvc<T> {
+                        type Response = super::GasPriceResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GasPriceRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).gas_price(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GasPriceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/feemarket.feemarket.v1.Query/GasPrices" => {
+                    #[allow(non_camel_case_types)]
+                    struct GasPricesSvc<T: Query>(pub Arc<T>);
+                    impl<T: Query> tonic::server::UnaryService<super::GasPricesRequest>
+                    for GasPricesSvc<T> {
+                        type Response = super::GasPricesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GasPricesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).gas_prices(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GasPricesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T: Query> Clone for QueryServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+            }
+        }
+    }
+    impl<T: Query> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: Query> tonic::transport::NamedService for QueryServer<T> {
+        const NAME: &'static str = "feemarket.feemarket.v1.Query";
+    }
+}
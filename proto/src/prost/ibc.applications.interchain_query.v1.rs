@@ -0,0 +1,84 @@
+/// CrossChainQuery is a query of the value stored at a path of the state of the
+/// chain tracked by a light client of the querying chain.
+/// See ICS31 spec:
+/// <https://github.com/cosmos/ibc/tree/main/spec/app/ics-031-crosschain-queries>
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CrossChainQuery {
+    /// the identifier of the query on the querying chain
+    #[prost(string, tag="1")]
+    pub id: ::prost::alloc::string::String,
+    /// the client tracking the queried chain
+    #[prost(string, tag="2")]
+    pub client_id: ::prost::alloc::string::String,
+    /// the path of the queried value, i.e. the store name followed by the key
+    #[prost(string, tag="3")]
+    pub path: ::prost::alloc::string::String,
+    /// the height of the queried chain at which the value must be proven. Any
+    /// height is accepted when set to 0.
+    #[prost(message, optional, tag="4")]
+    pub query_height: ::core::option::Option<super::super::super::core::client::v1::Height>,
+    /// the height of the querying chain after which the query times out. The
+    /// timeout is disabled when set to 0.
+    #[prost(message, optional, tag="5")]
+    pub local_timeout_height: ::core::option::Option<super::super::super::core::client::v1::Height>,
+    /// the timestamp of the querying chain after which the query times out, in
+    /// absolute nanoseconds since unix epoch. The timeout is disabled when set
+    /// to 0.
+    #[prost(uint64, tag="6")]
+    pub local_timeout_timestamp: u64,
+}
+/// CrossChainQueryResult is the result of a cross-chain query, kept by the
+/// querying chain for the module that sent the query.
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CrossChainQueryResult {
+    #[prost(string, tag="1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(enumeration="QueryResult", tag="2")]
+    pub result: i32,
+    /// the queried value, empty if the path holds no value
+    #[prost(bytes="vec", tag="3")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    /// the height of the queried chain at which the value was proven
+    #[prost(message, optional, tag="4")]
+    pub proof_height: ::core::option::Option<super::super::super::core::client::v1::Height>,
+}
+/// MsgSubmitCrossChainQueryResult answers a pending cross-chain query with the
+/// value stored at its path and a proof of it.
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgSubmitCrossChainQueryResult {
+    #[prost(string, tag="1")]
+    pub id: ::prost::alloc::string::String,
+    /// the queried value, empty if the path holds no value
+    #[prost(bytes="vec", tag="2")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    /// the proof of the value, or of its absence
+    #[prost(bytes="vec", tag="3")]
+    pub proof: ::prost::alloc::vec::Vec<u8>,
+    /// the height of the consensus state of the client against which the proof
+    /// is verified
+    #[prost(message, optional, tag="4")]
+    pub proof_height: ::core::option::Option<super::super::super::core::client::v1::Height>,
+    #[prost(string, tag="5")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgSubmitCrossChainQueryResultResponse defines the
+/// Msg/SubmitCrossChainQueryResult response type.
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgSubmitCrossChainQueryResultResponse {
+}
+/// QueryResult is the outcome of a cross-chain query.
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum QueryResult {
+    /// Default result
+    Unspecified = 0,
+    /// The value was read and proven against the consensus state of the client.
+    Success = 1,
+    /// The query timed out before a relayer answered it.
+    Timeout = 2,
+}
@@ -16,4 +16,8 @@ pub struct FungibleTokenPacketData {
     /// the recipient address on the destination chain
     #[prost(string, tag="4")]
     pub receiver: ::prost::alloc::string::String,
+    /// optional memo
+    #[prost(string, tag="5")]
+    #[serde(default, skip_serializing_if = "::prost::alloc::string::String::is_empty")]
+    pub memo: ::prost::alloc::string::String,
 }
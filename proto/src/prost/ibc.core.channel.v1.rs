@@ -21,6 +21,10 @@ pub struct Channel {
     /// opaque channel version, which is agreed upon during the handshake
     #[prost(string, tag="5")]
     pub version: ::prost::alloc::string::String,
+    /// upgrade sequence indicates the latest upgrade attempt performed by this channel
+    /// the value of 0 indicates the channel has never been upgraded
+    #[prost(uint64, tag="6")]
+    pub upgrade_sequence: u64,
 }
 /// IdentifiedChannel defines a channel with additional port and channel
 /// identifier fields.
@@ -49,6 +53,10 @@ pub struct IdentifiedChannel {
     /// channel identifier
     #[prost(string, tag="7")]
     pub channel_id: ::prost::alloc::string::String,
+    /// upgrade sequence indicates the latest upgrade attempt performed by this channel
+    /// the value of 0 indicates the channel has never been upgraded
+    #[prost(uint64, tag="8")]
+    pub upgrade_sequence: u64,
 }
 /// Counterparty defines a channel end counterparty
 #[derive(::serde::Serialize, ::serde::Deserialize)]
@@ -155,8 +163,21 @@ pub mod acknowledgement {
         Error(::prost::alloc::string::String),
     }
 }
+/// Timeout defines an execution deadline structure for 04-channel handlers.
+/// This includes packet lifecycle handlers as well as the upgrade handshake handlers.
+/// A valid Timeout contains either one or both of a timestamp and block height (sequence).
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Timeout {
+    /// block height after which the packet or upgrade times out
+    #[prost(message, optional, tag="1")]
+    pub height: ::core::option::Option<super::super::client::v1::Height>,
+    /// block timestamp (in nanoseconds) after which the packet or upgrade times out
+    #[prost(uint64, tag="2")]
+    pub timestamp: u64,
+}
 /// State defines if a channel is in one of the following states:
-/// CLOSED, INIT, TRYOPEN, OPEN or UNINITIALIZED.
+/// CLOSED, INIT, TRYOPEN, OPEN, FLUSHING, FLUSHCOMPLETE or UNINITIALIZED.
 #[derive(::serde::Serialize, ::serde::Deserialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
@@ -173,6 +194,10 @@ pub enum State {
     /// A channel has been closed and can no longer be used to send or receive
     /// packets.
     Closed = 4,
+    /// A channel has just accepted the upgrade handshake attempt and is flushing in-flight packets.
+    Flushing = 5,
+    /// A channel has just completed flushing any in-flight packets.
+    Flushcomplete = 6,
 }
 /// Order defines if a channel is ORDERED or UNORDERED
 #[derive(::serde::Serialize, ::serde::Deserialize)]
@@ -186,6 +211,9 @@ pub enum Order {
     Unordered = 1,
     /// packets are delivered exactly in the order which they were sent
     Ordered = 2,
+    /// packets are delivered exactly in the order which they were sent, but a packet
+    /// that timed out is skipped by the receiving end instead of closing the channel
+    OrderedAllowTimeout = 3,
 }
 /// GenesisState defines the ibc channel submodule's genesis state.
 #[derive(::serde::Serialize, ::serde::Deserialize)]
@@ -221,6 +249,46 @@ pub struct PacketSequence {
     #[prost(uint64, tag="3")]
     pub sequence: u64,
 }
+/// Upgrade is a verifiable type which contains the relevant information
+/// for an attempted upgrade. It provides the proposed changes to the channel
+/// end, the timeout for this upgrade attempt and the next packet sequence
+/// which allows the counterparty to efficiently know the highest sequence it has received.
+/// The next sequence send is used for pruning and upgrading from unordered to ordered channels.
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Upgrade {
+    #[prost(message, optional, tag="1")]
+    pub fields: ::core::option::Option<UpgradeFields>,
+    #[prost(message, optional, tag="2")]
+    pub timeout: ::core::option::Option<Timeout>,
+    #[prost(uint64, tag="3")]
+    pub next_sequence_send: u64,
+}
+/// UpgradeFields are the fields in a channel end which may be changed
+/// during a channel upgrade.
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpgradeFields {
+    #[prost(enumeration="Order", tag="1")]
+    pub ordering: i32,
+    #[prost(string, repeated, tag="2")]
+    pub connection_hops: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, tag="3")]
+    pub version: ::prost::alloc::string::String,
+}
+/// ErrorReceipt defines a type which encapsulates the upgrade sequence and error associated with the
+/// upgrade handshake failure. When a channel upgrade handshake is aborted both chains are expected to increment to the
+/// next sequence.
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ErrorReceipt {
+    /// the channel upgrade sequence
+    #[prost(uint64, tag="1")]
+    pub sequence: u64,
+    /// the error message detailing the cause of failure
+    #[prost(string, tag="2")]
+    pub message: ::prost::alloc::string::String,
+}
 /// MsgChannelOpenInit defines an sdk.Msg to initialize a channel handshake. It
 /// is called by a relayer on Chain A.
 #[derive(::serde::Serialize, ::serde::Deserialize)]
@@ -447,6 +515,186 @@ pub struct MsgAcknowledgementResponse {
     #[prost(enumeration="ResponseResultType", tag="1")]
     pub result: i32,
 }
+/// MsgChannelUpgradeInit defines the request type for the ChannelUpgradeInit rpc
+/// WARNING: Initializing a channel upgrade in the same block as opening the channel
+/// may result in the counterparty being incapable of opening.
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeInit {
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub fields: ::core::option::Option<UpgradeFields>,
+    #[prost(string, tag="4")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeInitResponse defines the MsgChannelUpgradeInit response type
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeInitResponse {
+    #[prost(message, optional, tag="1")]
+    pub upgrade: ::core::option::Option<Upgrade>,
+    #[prost(uint64, tag="2")]
+    pub upgrade_sequence: u64,
+}
+/// MsgChannelUpgradeTry defines the request type for the ChannelUpgradeTry rpc
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeTry {
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag="3")]
+    pub proposed_upgrade_connection_hops: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, optional, tag="4")]
+    pub counterparty_upgrade_fields: ::core::option::Option<UpgradeFields>,
+    #[prost(uint64, tag="5")]
+    pub counterparty_upgrade_sequence: u64,
+    #[prost(bytes="vec", tag="6")]
+    pub proof_channel: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="7")]
+    pub proof_upgrade: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="8")]
+    pub proof_height: ::core::option::Option<super::super::client::v1::Height>,
+    #[prost(string, tag="9")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeTryResponse defines the MsgChannelUpgradeTry response type
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeTryResponse {
+    #[prost(message, optional, tag="1")]
+    pub upgrade: ::core::option::Option<Upgrade>,
+    #[prost(uint64, tag="2")]
+    pub upgrade_sequence: u64,
+    #[prost(enumeration="ResponseResultType", tag="3")]
+    pub result: i32,
+}
+/// MsgChannelUpgradeAck defines the request type for the ChannelUpgradeAck rpc
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeAck {
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub counterparty_upgrade: ::core::option::Option<Upgrade>,
+    #[prost(bytes="vec", tag="4")]
+    pub proof_channel: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="5")]
+    pub proof_upgrade: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="6")]
+    pub proof_height: ::core::option::Option<super::super::client::v1::Height>,
+    #[prost(string, tag="7")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeAckResponse defines MsgChannelUpgradeAck response type
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeAckResponse {
+    #[prost(enumeration="ResponseResultType", tag="1")]
+    pub result: i32,
+}
+/// MsgChannelUpgradeConfirm defines the request type for the ChannelUpgradeConfirm rpc
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeConfirm {
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(enumeration="State", tag="3")]
+    pub counterparty_channel_state: i32,
+    #[prost(message, optional, tag="4")]
+    pub counterparty_upgrade: ::core::option::Option<Upgrade>,
+    #[prost(bytes="vec", tag="5")]
+    pub proof_channel: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="6")]
+    pub proof_upgrade: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="7")]
+    pub proof_height: ::core::option::Option<super::super::client::v1::Height>,
+    #[prost(string, tag="8")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeConfirmResponse defines MsgChannelUpgradeConfirm response type
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeConfirmResponse {
+    #[prost(enumeration="ResponseResultType", tag="1")]
+    pub result: i32,
+}
+/// MsgChannelUpgradeOpen defines the request type for the ChannelUpgradeOpen rpc
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeOpen {
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(enumeration="State", tag="3")]
+    pub counterparty_channel_state: i32,
+    #[prost(uint64, tag="4")]
+    pub counterparty_upgrade_sequence: u64,
+    #[prost(bytes="vec", tag="5")]
+    pub proof_channel: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="6")]
+    pub proof_height: ::core::option::Option<super::super::client::v1::Height>,
+    #[prost(string, tag="7")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeOpenResponse defines the MsgChannelUpgradeOpen response type
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeOpenResponse {
+}
+/// MsgChannelUpgradeTimeout defines the request type for the ChannelUpgradeTimeout rpc
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeTimeout {
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub counterparty_channel: ::core::option::Option<Channel>,
+    #[prost(bytes="vec", tag="4")]
+    pub proof_channel: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="5")]
+    pub proof_height: ::core::option::Option<super::super::client::v1::Height>,
+    #[prost(string, tag="6")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeTimeoutResponse defines the MsgChannelUpgradeTimeout response type
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeTimeoutResponse {
+}
+/// MsgChannelUpgradeCancel defines the request type for the ChannelUpgradeCancel rpc
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeCancel {
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub error_receipt: ::core::option::Option<ErrorReceipt>,
+    #[prost(bytes="vec", tag="4")]
+    pub proof_error_receipt: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="5")]
+    pub proof_height: ::core::option::Option<super::super::client::v1::Height>,
+    #[prost(string, tag="6")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeCancelResponse defines the MsgChannelUpgradeCancel response type
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeCancelResponse {
+}
 /// ResponseResultType defines the possible outcomes of the execution of a message
 #[derive(::serde::Serialize, ::serde::Deserialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
@@ -732,6 +980,167 @@ pub mod msg_client {
             );
             self.inner.unary(request.into_request(), path, codec).await
         }
+        /// ChannelUpgradeInit defines a rpc handler method for MsgChannelUpgradeInit.
+        pub async fn channel_upgrade_init(
+            &mut self,
+            request: impl tonic::IntoRequest<super::MsgChannelUpgradeInit>,
+        ) -> Result<
+            tonic::Response<super::MsgChannelUpgradeInitResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/ibc.core.channel.v1.Msg/ChannelUpgradeInit",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// ChannelUpgradeTry defines a rpc handler method for MsgChannelUpgradeTry.
+        pub async fn channel_upgrade_try(
+            &mut self,
+            request: impl tonic::IntoRequest<super::MsgChannelUpgradeTry>,
+        ) -> Result<
+            tonic::Response<super::MsgChannelUpgradeTryResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/ibc.core.channel.v1.Msg/ChannelUpgradeTry",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// ChannelUpgradeAck defines a rpc handler method for MsgChannelUpgradeAck.
+        pub async fn channel_upgrade_ack(
+            &mut self,
+            request: impl tonic::IntoRequest<super::MsgChannelUpgradeAck>,
+        ) -> Result<
+            tonic::Response<super::MsgChannelUpgradeAckResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/ibc.core.channel.v1.Msg/ChannelUpgradeAck",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// ChannelUpgradeConfirm defines a rpc handler method for MsgChannelUpgradeConfirm.
+        pub async fn channel_upgrade_confirm(
+            &mut self,
+            request: impl tonic::IntoRequest<super::MsgChannelUpgradeConfirm>,
+        ) -> Result<
+            tonic::Response<super::MsgChannelUpgradeConfirmResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/ibc.core.channel.v1.Msg/ChannelUpgradeConfirm",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// ChannelUpgradeOpen defines a rpc handler method for MsgChannelUpgradeOpen.
+        pub async fn channel_upgrade_open(
+            &mut self,
+            request: impl tonic::IntoRequest<super::MsgChannelUpgradeOpen>,
+        ) -> Result<
+            tonic::Response<super::MsgChannelUpgradeOpenResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/ibc.core.channel.v1.Msg/ChannelUpgradeOpen",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// ChannelUpgradeTimeout defines a rpc handler method for MsgChannelUpgradeTimeout.
+        pub async fn channel_upgrade_timeout(
+            &mut self,
+            request: impl tonic::IntoRequest<super::MsgChannelUpgradeTimeout>,
+        ) -> Result<
+            tonic::Response<super::MsgChannelUpgradeTimeoutResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/ibc.core.channel.v1.Msg/ChannelUpgradeTimeout",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// ChannelUpgradeCancel defines a rpc handler method for MsgChannelUpgradeCancel.
+        pub async fn channel_upgrade_cancel(
+            &mut self,
+            request: impl tonic::IntoRequest<super::MsgChannelUpgradeCancel>,
+        ) -> Result<
+            tonic::Response<super::MsgChannelUpgradeCancelResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/ibc.core.channel.v1.Msg/ChannelUpgradeCancel",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -799,6 +1208,56 @@ pub mod msg_server {
             &self,
             request: tonic::Request<super::MsgAcknowledgement>,
         ) -> Result<tonic::Response<super::MsgAcknowledgementResponse>, tonic::Status>;
+        /// ChannelUpgradeInit defines a rpc handler method for MsgChannelUpgradeInit.
+        async fn channel_upgrade_init(
+            &self,
+            request: tonic::Request<super::MsgChannelUpgradeInit>,
+        ) -> Result<
+            tonic::Response<super::MsgChannelUpgradeInitResponse>,
+            tonic::Status,
+        >;
+        /// ChannelUpgradeTry defines a rpc handler method for MsgChannelUpgradeTry.
+        async fn channel_upgrade_try(
+            &self,
+            request: tonic::Request<super::MsgChannelUpgradeTry>,
+        ) -> Result<tonic::Response<super::MsgChannelUpgradeTryResponse>, tonic::Status>;
+        /// ChannelUpgradeAck defines a rpc handler method for MsgChannelUpgradeAck.
+        async fn channel_upgrade_ack(
+            &self,
+            request: tonic::Request<super::MsgChannelUpgradeAck>,
+        ) -> Result<tonic::Response<super::MsgChannelUpgradeAckResponse>, tonic::Status>;
+        /// ChannelUpgradeConfirm defines a rpc handler method for MsgChannelUpgradeConfirm.
+        async fn channel_upgrade_confirm(
+            &self,
+            request: tonic::Request<super::MsgChannelUpgradeConfirm>,
+        ) -> Result<
+            tonic::Response<super::MsgChannelUpgradeConfirmResponse>,
+            tonic::Status,
+        >;
+        /// ChannelUpgradeOpen defines a rpc handler method for MsgChannelUpgradeOpen.
+        async fn channel_upgrade_open(
+            &self,
+            request: tonic::Request<super::MsgChannelUpgradeOpen>,
+        ) -> Result<
+            tonic::Response<super::MsgChannelUpgradeOpenResponse>,
+            tonic::Status,
+        >;
+        /// ChannelUpgradeTimeout defines a rpc handler method for MsgChannelUpgradeTimeout.
+        async fn channel_upgrade_timeout(
+            &self,
+            request: tonic::Request<super::MsgChannelUpgradeTimeout>,
+        ) -> Result<
+            tonic::Response<super::MsgChannelUpgradeTimeoutResponse>,
+            tonic::Status,
+        >;
+        /// ChannelUpgradeCancel defines a rpc handler method for MsgChannelUpgradeCancel.
+        async fn channel_upgrade_cancel(
+            &self,
+            request: tonic::Request<super::MsgChannelUpgradeCancel>,
+        ) -> Result<
+            tonic::Response<super::MsgChannelUpgradeCancelResponse>,
+            tonic::Status,
+        >;
     }
     /// Msg defines the ibc/channel Msg service.
     #[derive(Debug)]
@@ -1228,6 +1687,282 @@ pub mod msg_server {
                     };
                     Box::pin(fut)
                 }
+                "/ibc.core.channel.v1.Msg/ChannelUpgradeInit" => {
+                    #[allow(non_camel_case_types)]
+                    struct ChannelUpgradeInitSvc<T: Msg>(pub Arc<T>);
+                    impl<
+                        T: Msg,
+                    > tonic::server::UnaryService<super::MsgChannelUpgradeInit>
+                    for ChannelUpgradeInitSvc<T> {
+                        type Response = super::MsgChannelUpgradeInitResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::MsgChannelUpgradeInit>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).channel_upgrade_init(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ChannelUpgradeInitSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/ibc.core.channel.v1.Msg/ChannelUpgradeTry" => {
+                    #[allow(non_camel_case_types)]
+                    struct ChannelUpgradeTrySvc<T: Msg>(pub Arc<T>);
+                    impl<T: Msg> tonic::server::UnaryService<super::MsgChannelUpgradeTry>
+                    for ChannelUpgradeTrySvc<T> {
+                        type Response = super::MsgChannelUpgradeTryResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::MsgChannelUpgradeTry>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).channel_upgrade_try(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ChannelUpgradeTrySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/ibc.core.channel.v1.Msg/ChannelUpgradeAck" => {
+                    #[allow(non_camel_case_types)]
+                    struct ChannelUpgradeAckSvc<T: Msg>(pub Arc<T>);
+                    impl<T: Msg> tonic::server::UnaryService<super::MsgChannelUpgradeAck>
+                    for ChannelUpgradeAckSvc<T> {
+                        type Response = super::MsgChannelUpgradeAckResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::MsgChannelUpgradeAck>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).channel_upgrade_ack(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ChannelUpgradeAckSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/ibc.core.channel.v1.Msg/ChannelUpgradeConfirm" => {
+                    #[allow(non_camel_case_types)]
+                    struct ChannelUpgradeConfirmSvc<T: Msg>(pub Arc<T>);
+                    impl<
+                        T: Msg,
+                    > tonic::server::UnaryService<super::MsgChannelUpgradeConfirm>
+                    for ChannelUpgradeConfirmSvc<T> {
+                        type Response = super::MsgChannelUpgradeConfirmResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::MsgChannelUpgradeConfirm>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).channel_upgrade_confirm(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ChannelUpgradeConfirmSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/ibc.core.channel.v1.Msg/ChannelUpgradeOpen" => {
+                    #[allow(non_camel_case_types)]
+                    struct ChannelUpgradeOpenSvc<T: Msg>(pub Arc<T>);
+                    impl<
+                        T: Msg,
+                    > tonic::server::UnaryService<super::MsgChannelUpgradeOpen>
+                    for ChannelUpgradeOpenSvc<T> {
+                        type Response = super::MsgChannelUpgradeOpenResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::MsgChannelUpgradeOpen>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).channel_upgrade_open(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ChannelUpgradeOpenSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/ibc.core.channel.v1.Msg/ChannelUpgradeTimeout" => {
+                    #[allow(non_camel_case_types)]
+                    struct ChannelUpgradeTimeoutSvc<T: Msg>(pub Arc<T>);
+                    impl<
+                        T: Msg,
+                    > tonic::server::UnaryService<super::MsgChannelUpgradeTimeout>
+                    for ChannelUpgradeTimeoutSvc<T> {
+                        type Response = super::MsgChannelUpgradeTimeoutResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::MsgChannelUpgradeTimeout>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).channel_upgrade_timeout(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ChannelUpgradeTimeoutSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/ibc.core.channel.v1.Msg/ChannelUpgradeCancel" => {
+                    #[allow(non_camel_case_types)]
+                    struct ChannelUpgradeCancelSvc<T: Msg>(pub Arc<T>);
+                    impl<
+                        T: Msg,
+                    > tonic::server::UnaryService<super::MsgChannelUpgradeCancel>
+                    for ChannelUpgradeCancelSvc<T> {
+                        type Response = super::MsgChannelUpgradeCancelResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::MsgChannelUpgradeCancel>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).channel_upgrade_cancel(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ChannelUpgradeCancelSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -4,6 +4,7 @@ mod clear;
 mod completions;
 mod config;
 mod create;
+mod fee;
 mod health;
 mod keys;
 mod listen;
@@ -17,9 +18,9 @@ mod version;
 
 use self::{
     clear::ClearCmds, completions::CompletionsCmd, config::ConfigCmd, create::CreateCmds,
-    health::HealthCheckCmd, keys::KeysCmd, listen::ListenCmd, misbehaviour::MisbehaviourCmd,
-    query::QueryCmd, start::StartCmd, tx::TxCmd, update::UpdateCmds, upgrade::UpgradeCmds,
-    version::VersionCmd,
+    fee::FeeCmd, health::HealthCheckCmd, keys::KeysCmd, listen::ListenCmd,
+    misbehaviour::MisbehaviourCmd, query::QueryCmd, start::StartCmd, tx::TxCmd, update::UpdateCmds,
+    upgrade::UpgradeCmds, version::VersionCmd,
 };
 
 use core::time::Duration;
@@ -64,6 +65,10 @@ pub enum CliCmd {
     #[clap(subcommand)]
     Clear(ClearCmds),
 
+    /// Register the accounts to which the ICS29 fees earned by the relayer are paid
+    #[clap(subcommand)]
+    Fee(FeeCmd),
+
     /// Start the relayer in multi-chain mode.
     ///
     /// Relays packets and open handshake messages between all chains in the config.
@@ -0,0 +1,18 @@
+//! `fee` subcommand
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+mod register_counterparty_payee;
+mod register_payee;
+
+/// `fee` subcommand
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum FeeCmd {
+    /// Register the account to which the ICS29 acknowledgement and timeout fees earned by the
+    /// relayer on a channel are paid
+    RegisterPayee(register_payee::RegisterPayeeCmd),
+
+    /// Register the account on the counterparty chain to which the ICS29 receive fees earned by
+    /// the relayer on a channel are paid
+    RegisterCounterpartyPayee(register_counterparty_payee::RegisterCounterpartyPayeeCmd),
+}
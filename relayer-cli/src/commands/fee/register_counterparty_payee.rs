@@ -0,0 +1,141 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::signer::Signer;
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::fee::register_counterparty_payee;
+
+use crate::application::app_config;
+use crate::cli_utils::spawn_chain_counterparty;
+use crate::conclude::Output;
+
+#[derive(Clone, Command, Debug, Parser, PartialEq)]
+pub struct RegisterCounterpartyPayeeCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain the packets are relayed to"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "port",
+        required = true,
+        value_name = "PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the port"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        long = "channel",
+        alias = "chan",
+        required = true,
+        value_name = "CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the channel"
+    )]
+    channel_id: ChannelId,
+
+    #[clap(
+        long = "counterparty-payee",
+        value_name = "COUNTERPARTY_PAYEE_ADDRESS",
+        help = "Address of the account on the counterparty chain to which the receive fees are paid (default: the address of the relayer's key on the counterparty chain)"
+    )]
+    counterparty_payee: Option<Signer>,
+}
+
+impl Runnable for RegisterCounterpartyPayeeCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chains = match spawn_chain_counterparty::<BaseChainHandle>(
+            &config,
+            &self.chain_id,
+            &self.port_id,
+            &self.channel_id,
+        ) {
+            Ok((chains, _)) => chains,
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        };
+
+        let counterparty_payee = match &self.counterparty_payee {
+            Some(counterparty_payee) => counterparty_payee.clone(),
+            None => match chains.dst.get_key_address() {
+                Ok(signer) => signer,
+                Err(e) => Output::error(format!("{}", e)).exit(),
+            },
+        };
+
+        match register_counterparty_payee(
+            &chains.src,
+            &self.port_id,
+            &self.channel_id,
+            counterparty_payee,
+        ) {
+            Ok(tx_hash) => Output::success(tx_hash).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RegisterCounterpartyPayeeCmd;
+
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+    #[test]
+    fn test_register_counterparty_payee_required_only() {
+        assert_eq!(
+            RegisterCounterpartyPayeeCmd {
+                chain_id: ChainId::from_string("chain_b"),
+                port_id: PortId::from_str("transfer").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                counterparty_payee: None,
+            },
+            RegisterCounterpartyPayeeCmd::parse_from(&[
+                "test",
+                "--chain",
+                "chain_b",
+                "--port",
+                "transfer",
+                "--chan",
+                "channel-07"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_register_counterparty_payee() {
+        assert_eq!(
+            RegisterCounterpartyPayeeCmd {
+                chain_id: ChainId::from_string("chain_b"),
+                port_id: PortId::from_str("transfer").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                counterparty_payee: Some(
+                    "cosmos1wxeyh7zgn4tctjzs0vtqpc6p5cxq5t2muzl7ng"
+                        .parse()
+                        .unwrap()
+                ),
+            },
+            RegisterCounterpartyPayeeCmd::parse_from(&[
+                "test",
+                "--chain",
+                "chain_b",
+                "--port",
+                "transfer",
+                "--channel",
+                "channel-07",
+                "--counterparty-payee",
+                "cosmos1wxeyh7zgn4tctjzs0vtqpc6p5cxq5t2muzl7ng"
+            ])
+        )
+    }
+}
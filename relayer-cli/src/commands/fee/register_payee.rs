@@ -0,0 +1,113 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::signer::Signer;
+use ibc_relayer::fee::register_payee;
+
+use crate::application::app_config;
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::{exit_with_unrecoverable_error, Output};
+
+#[derive(Clone, Command, Debug, Parser, PartialEq)]
+pub struct RegisterPayeeCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "port",
+        required = true,
+        value_name = "PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the port"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        long = "channel",
+        alias = "chan",
+        required = true,
+        value_name = "CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the channel"
+    )]
+    channel_id: ChannelId,
+
+    #[clap(
+        long = "payee",
+        required = true,
+        value_name = "PAYEE_ADDRESS",
+        help_heading = "REQUIRED",
+        help = "Address of the account to which the fees are paid"
+    )]
+    payee: Signer,
+}
+
+impl Runnable for RegisterPayeeCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        match register_payee(&chain, &self.port_id, &self.channel_id, self.payee.clone()) {
+            Ok(tx_hash) => Output::success(tx_hash).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RegisterPayeeCmd;
+
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+    #[test]
+    fn test_register_payee() {
+        assert_eq!(
+            RegisterPayeeCmd {
+                chain_id: ChainId::from_string("chain_a"),
+                port_id: PortId::from_str("transfer").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                payee: "cosmos1wxeyh7zgn4tctjzs0vtqpc6p5cxq5t2muzl7ng"
+                    .parse()
+                    .unwrap(),
+            },
+            RegisterPayeeCmd::parse_from(&[
+                "test",
+                "--chain",
+                "chain_a",
+                "--port",
+                "transfer",
+                "--channel",
+                "channel-07",
+                "--payee",
+                "cosmos1wxeyh7zgn4tctjzs0vtqpc6p5cxq5t2muzl7ng"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_register_payee_no_payee() {
+        assert!(RegisterPayeeCmd::try_parse_from(&[
+            "test",
+            "--chain",
+            "chain_a",
+            "--port",
+            "transfer",
+            "--channel",
+            "channel-07"
+        ])
+        .is_err())
+    }
+}
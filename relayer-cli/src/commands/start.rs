@@ -52,13 +52,13 @@ impl Runnable for StartCmd {
 }
 
 /// Register the SIGHUP and SIGUSR1 signals, and notify the supervisor.
-/// - [DEPRECATED] SIGHUP: Trigger a reload of the configuration.
+/// - SIGHUP: Trigger a reload of the configuration.
 /// - SIGUSR1: Ask the supervisor to dump its state and print it to the console.
 fn register_signals(tx_cmd: Sender<SupervisorCmd>) -> Result<(), io::Error> {
     use signal_hook::{consts::signal::*, iterator::Signals};
 
     let sigs = vec![
-        SIGHUP,  // Reload of configuration (disabled)
+        SIGHUP,  // Reload of configuration
         SIGUSR1, // Dump state
     ];
 
@@ -67,10 +67,25 @@ fn register_signals(tx_cmd: Sender<SupervisorCmd>) -> Result<(), io::Error> {
     std::thread::spawn(move || {
         for signal in &mut signals {
             match signal {
-                SIGHUP => warn!(
-                    "configuration reloading via SIGHUP has been disabled, \
-                     the signal handler will be removed in the future"
-                ),
+                SIGHUP => {
+                    info!("reloading configuration (triggered by SIGHUP)");
+
+                    let (tx, rx) = crossbeam_channel::bounded(1);
+                    tx_cmd.try_send(SupervisorCmd::ReloadConfig(tx)).unwrap();
+
+                    std::thread::spawn(move || match rx.recv() {
+                        Ok(Ok(diff)) => info!(
+                            added = ?diff.added,
+                            removed = ?diff.removed,
+                            updated = ?diff.updated,
+                            restarted = ?diff.restarted,
+                            mode_changed = diff.mode_changed,
+                            "configuration reloaded"
+                        ),
+                        Ok(Err(e)) => error!("failed to reload configuration: {}", e),
+                        Err(_) => (),
+                    });
+                }
                 SIGUSR1 => {
                     info!("dumping state (triggered by SIGUSR1)");
 
@@ -184,6 +199,7 @@ fn make_supervisor<Chain: ChainHandle>(
         SupervisorOptions {
             health_check: true,
             force_full_scan,
+            config_path: crate::config::config_path(),
         },
     )?)
 }
@@ -9,6 +9,7 @@ use std::path::PathBuf;
 
 use flex_error::{define_error, TraceError};
 use ibc::core::ics24_host::identifier::ChainId;
+use ibc_relayer::chain::cosmos::encode::bech32_prefix;
 use ibc_relayer::config::{ChainConfig, Config, ModeConfig};
 use tendermint_light_client_verifier::types::TrustThreshold;
 use tracing_subscriber::filter::ParseError;
@@ -86,6 +87,17 @@ define_error! {
                     e.chain_id, e.gas_adjustment, e.gas_multiplier
                 )
             },
+
+        InvalidAuthzGranter
+            {
+                granter: String,
+                chain_id: ChainId,
+                reason: String,
+            }
+            |e| {
+                format!("config file specifies an invalid `authz_granter` ({0}) for the chain '{1}', caused by: {2}",
+                    e.granter, e.chain_id, e.reason)
+            },
     }
 }
 
@@ -109,6 +121,8 @@ pub fn validate_config(config: &Config) -> Result<(), Diagnostic<Error>> {
 
         // Validate gas-related settings
         validate_gas_settings(&c.id, c)?;
+
+        validate_authz_granter(&c.id, c)?;
     }
 
     // Check for invalid mode config
@@ -193,3 +207,27 @@ fn validate_gas_settings(id: &ChainId, config: &ChainConfig) -> Result<(), Diagn
 
     Ok(())
 }
+
+/// Check that the authz granter, if any, is an account of the chain,
+/// i.e. a bech32 address with the `account_prefix` of the chain.
+fn validate_authz_granter(id: &ChainId, config: &ChainConfig) -> Result<(), Diagnostic<Error>> {
+    let granter = match &config.authz_granter {
+        Some(granter) => granter,
+        None => return Ok(()),
+    };
+
+    let reason = match bech32_prefix(granter) {
+        Ok(prefix) if prefix == config.account_prefix => return Ok(()),
+        Ok(prefix) => format!(
+            "the address prefix '{}' does not match the account prefix '{}' of the chain",
+            prefix, config.account_prefix
+        ),
+        Err(e) => format!("the address is not a valid bech32 address: {}", e),
+    };
+
+    Err(Diagnostic::Error(Error::invalid_authz_granter(
+        granter.clone(),
+        id.clone(),
+        reason,
+    )))
+}
@@ -7,7 +7,7 @@ use crossbeam_channel as channel;
 use ibc::core::ics24_host::identifier::ChainId;
 use ibc_relayer::supervisor::dump_state::SupervisorState;
 use ibc_relayer::{
-    config::ChainConfig,
+    config::{diff::ConfigDiff, ChainConfig},
     rest::{
         request::{reply_channel, ReplySender, Request, VersionInfo},
         RestApiError,
@@ -64,6 +64,10 @@ pub fn supervisor_state(
     submit_request(sender, |reply_to| Request::State { reply_to })
 }
 
+pub fn reload_config(sender: &channel::Sender<Request>) -> Result<ConfigDiff, RestApiError> {
+    submit_request(sender, |reply_to| Request::ReloadConfig { reply_to })
+}
+
 pub fn assemble_version_info(sender: &channel::Sender<Request>) -> Vec<VersionInfo> {
     // Fetch the relayer library version
     let lib_version = submit_request(sender, |reply_to| Request::Version { reply_to })
@@ -7,7 +7,7 @@ use tracing::{info, trace};
 use ibc_relayer::rest::request::Request;
 
 use crate::{
-    handle::{all_chain_ids, assemble_version_info, chain_config, supervisor_state},
+    handle::{all_chain_ids, assemble_version_info, chain_config, reload_config, supervisor_state},
     Config,
 };
 
@@ -82,6 +82,12 @@ fn run(config: Config, sender: channel::Sender<Request>) -> ServerHandle {
                 rouille::Response::json(&JsonResult::from(result))
             },
 
+            (POST) (/reload) => {
+                trace!("[rest] POST /reload");
+                let result = reload_config(&sender);
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
             _ => rouille::Response::empty_404(),
         )
     })
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use ibc::core::ics24_host::identifier::ChainId;
 use ibc_relayer::{
-    config::ChainConfig,
+    config::{diff::ConfigDiff, ChainConfig},
     rest::request::{Request, VersionInfo},
     supervisor::dump_state::SupervisorState,
 };
@@ -25,6 +25,14 @@ enum JsonResult<R, E> {
 }
 
 fn run_test<R, F>(port: u16, path: &str, expected: R, handler: F)
+where
+    R: Serialize,
+    F: FnOnce(Request) -> TestResult + Send + 'static,
+{
+    run_test_with_method(port, "GET", path, expected, handler)
+}
+
+fn run_test_with_method<R, F>(port: u16, method: &str, path: &str, expected: R, handler: F)
 where
     R: Serialize,
     F: FnOnce(Request) -> TestResult + Send + 'static,
@@ -41,7 +49,7 @@ where
         Err(e) => panic!("got an error: {}", e),
     });
 
-    let response = ureq::get(&format!("http://127.0.0.1:{}{}", port, path))
+    let response = ureq::request(method, &format!("http://127.0.0.1:{}{}", port, path))
         .call()
         .unwrap()
         .into_string()
@@ -137,3 +145,21 @@ fn state() {
         req => TestResult::WrongRequest(req),
     });
 }
+
+#[test]
+fn reload_config() {
+    let diff = ConfigDiff {
+        added: vec!["mock-1".parse().unwrap()],
+        removed: vec!["mock-0".parse().unwrap()],
+        ..ConfigDiff::default()
+    };
+    let result: JsonResult<_, ()> = JsonResult::Success(diff.clone());
+
+    run_test_with_method(19105, "POST", "/reload", result, |req| match req {
+        Request::ReloadConfig { reply_to } => {
+            reply_to.send(Ok(diff)).unwrap();
+            TestResult::Success
+        }
+        req => TestResult::WrongRequest(req),
+    });
+}
@@ -6,6 +6,7 @@ pub mod handle;
 pub mod requests;
 pub mod runtime;
 pub mod tracking;
+pub mod tx_sender;
 
 #[cfg(test)]
 pub mod mock;
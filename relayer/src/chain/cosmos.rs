@@ -7,9 +7,9 @@ use core::{
     time::Duration,
 };
 use num_bigint::BigInt;
+use std::collections::HashMap;
 use std::thread;
 
-use bitcoin::hashes::hex::ToHex;
 use tendermint::block::Height as TmHeight;
 use tendermint::{
     abci::{Event, Path as TendermintABCIPath},
@@ -18,12 +18,14 @@ use tendermint::{
 use tendermint_light_client_verifier::types::LightBlock as TmLightBlock;
 use tendermint_proto::Protobuf;
 use tendermint_rpc::{
-    endpoint::broadcast::tx_sync::Response, endpoint::status, Client, HttpClient, Order,
+    endpoint::broadcast::tx_sync::Response, endpoint::status, Client, HttpClient, Order, Url,
 };
 use tokio::runtime::Runtime as TokioRuntime;
 use tonic::{codegen::http::Uri, metadata::AsciiMetadataValue};
-use tracing::{error, span, warn, Level};
+use tracing::{error, warn};
 
+use ibc::applications::fee::error::Error as FeeError;
+use ibc::applications::fee::packet_fee::IdentifiedPacketFees;
 use ibc::clients::ics07_tendermint::consensus_state::ConsensusState as TMConsensusState;
 use ibc::clients::ics07_tendermint::header::Header as TmHeader;
 use ibc::core::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
@@ -34,11 +36,13 @@ use ibc::core::ics03_connection::connection::{ConnectionEnd, IdentifiedConnectio
 use ibc::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
 use ibc::core::ics04_channel::events as ChannelEvents;
 use ibc::core::ics04_channel::packet::{Packet, Sequence};
+use ibc::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use ibc::core::ics23_commitment::commitment::CommitmentPrefix;
 use ibc::core::ics24_host::identifier::{ChainId, ClientId, ConnectionId};
 use ibc::core::ics24_host::path::{
-    AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath, CommitmentsPath,
-    ConnectionsPath, ReceiptsPath, SeqRecvsPath,
+    AcksPath, ChannelEndsPath, ChannelUpgradeErrorsPath, ChannelUpgradesPath,
+    ClientConsensusStatePath, ClientStatePath, CommitmentsPath, ConnectionsPath, ReceiptsPath,
+    SeqRecvsPath,
 };
 use ibc::core::ics24_host::{ClientUpgradePath, Path, IBC_QUERY_PATH, SDK_UPGRADE_QUERY_PATH};
 use ibc::events::IbcEvent;
@@ -52,40 +56,43 @@ use ibc_proto::cosmos::staking::v1beta1::Params as StakingParams;
 
 use crate::account::Balance;
 use crate::chain::client::ClientSettings;
-use crate::chain::cosmos::batch::{
-    send_batched_messages_and_wait_check_tx, send_batched_messages_and_wait_commit,
-};
-use crate::chain::cosmos::encode::encode_to_bech32;
+use crate::chain::cosmos::encode::{key_address, msg_signer};
+use crate::chain::cosmos::endpoints::{Endpoints, PROBE_INTERVAL};
 use crate::chain::cosmos::gas::{calculate_fee, mul_ceil};
-use crate::chain::cosmos::query::account::get_or_fetch_account;
 use crate::chain::cosmos::query::balance::query_balance;
 use crate::chain::cosmos::query::denom_trace::query_denom_trace;
 use crate::chain::cosmos::query::status::query_status;
 use crate::chain::cosmos::query::tx::query_txs;
 use crate::chain::cosmos::query::{abci_query, fetch_version_specs, packet_query, QueryResponse};
-use crate::chain::cosmos::types::account::Account;
+use crate::chain::cosmos::sender::CosmosTxSender;
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::types::gas::{default_gas_from_config, max_gas_from_config};
 use crate::chain::endpoint::{ChainEndpoint, ChainStatus, HealthCheck};
 use crate::chain::tracking::TrackedMsgs;
-use crate::config::ChainConfig;
+use crate::chain::tx_sender::TxSender;
+use crate::config::{ChainConfig, EventSourceMode};
 use crate::denom::DenomTrace;
-use crate::error::Error;
+use crate::error::{Error, ErrorDetail};
 use crate::event::monitor::{EventMonitor, EventReceiver, TxMonitorCmd};
+use crate::event::pull::{PullEventSource, RpcBlockSource};
 use crate::keyring::{KeyEntry, KeyRing};
+use crate::light_client::tendermint::store::LightBlockStore;
 use crate::light_client::tendermint::LightClient as TmLightClient;
 use crate::light_client::{LightClient, Verified};
+use crate::store::EventCursorStore;
 
 use super::requests::{
     IncludeProof, QueryBlockRequest, QueryChannelClientStateRequest, QueryChannelRequest,
     QueryChannelsRequest, QueryClientConnectionsRequest, QueryClientStateRequest,
     QueryClientStatesRequest, QueryConnectionChannelsRequest, QueryConnectionRequest,
-    QueryConnectionsRequest, QueryConsensusStateRequest, QueryConsensusStatesRequest, QueryHeight,
-    QueryHostConsensusStateRequest, QueryNextSequenceReceiveRequest,
-    QueryPacketAcknowledgementRequest, QueryPacketAcknowledgementsRequest,
-    QueryPacketCommitmentRequest, QueryPacketCommitmentsRequest, QueryPacketEventDataRequest,
-    QueryPacketReceiptRequest, QueryTxRequest, QueryUnreceivedAcksRequest,
-    QueryUnreceivedPacketsRequest, QueryUpgradedClientStateRequest,
+    QueryConnectionsRequest, QueryConsensusStateRequest, QueryConsensusStatesRequest,
+    QueryCounterpartyPayeeRequest, QueryCrossChainRequest, QueryHeight,
+    QueryHostConsensusStateRequest, QueryIncentivizedPacketsRequest,
+    QueryNextSequenceReceiveRequest, QueryPacketAcknowledgementRequest,
+    QueryPacketAcknowledgementsRequest, QueryPacketCommitmentRequest,
+    QueryPacketCommitmentsRequest, QueryPacketEventDataRequest, QueryPacketReceiptRequest,
+    QueryTxRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
+    QueryUpgradeErrorRequest, QueryUpgradeRequest, QueryUpgradedClientStateRequest,
     QueryUpgradedConsensusStateRequest,
 };
 
@@ -93,10 +100,12 @@ pub mod batch;
 pub mod client;
 pub mod compatibility;
 pub mod encode;
+pub mod endpoints;
 pub mod estimate;
 pub mod gas;
 pub mod query;
 pub mod retry;
+pub mod sender;
 pub mod simulate;
 pub mod tx;
 pub mod types;
@@ -110,12 +119,19 @@ pub const GENESIS_MAX_BYTES_MAX_FRACTION: f64 = 0.9;
 pub struct CosmosSdkChain {
     config: ChainConfig,
     tx_config: TxConfig,
+    /// The full nodes of the chain, the active one of which
+    /// the clients and addresses below point to
+    endpoints: Endpoints,
     rpc_client: HttpClient,
+    rpc_addr: Url,
     grpc_addr: Uri,
     rt: Arc<TokioRuntime>,
     keybase: KeyRing,
-    /// A cached copy of the account information
-    account: Option<Account>,
+    /// The senders of the transactions of the keys of the key pool, by key name,
+    /// each of which tracks the sequence number of its account separately
+    senders: HashMap<String, CosmosTxSender>,
+    /// The persistent store of the light blocks verified by the light client, if enabled
+    light_store: Option<LightBlockStore>,
 }
 
 impl CosmosSdkChain {
@@ -182,7 +198,7 @@ impl CosmosSdkChain {
             .map_err(|e| {
                 Error::config_validation_json_rpc(
                     self.id().clone(),
-                    self.config.rpc_addr.to_string(),
+                    self.rpc_addr.to_string(),
                     "/consensus_params".to_string(),
                     e,
                 )
@@ -234,7 +250,7 @@ impl CosmosSdkChain {
                     self.grpc_addr.clone(),
                 ),
             )
-            .map_err(Error::grpc_transport)?;
+            .map_err(|e| self.request_error(Error::grpc_transport(e)))?;
 
         let request =
             tonic::Request::new(ibc_proto::cosmos::staking::v1beta1::QueryParamsRequest {});
@@ -272,6 +288,33 @@ impl CosmosSdkChain {
         self.query_staking_params().map(|p| p.historical_entries)
     }
 
+    /// Sends the queries and transactions to the active node from now on.
+    fn use_active_endpoint(&mut self) {
+        let endpoint = self.endpoints.active();
+
+        self.rpc_client = endpoint.rpc_client.clone();
+        self.rpc_addr = endpoint.config.rpc_addr.clone();
+        self.grpc_addr = endpoint.grpc_addr.clone();
+
+        self.tx_config.rpc_client = endpoint.rpc_client.clone();
+        self.tx_config.rpc_address = endpoint.config.rpc_addr.clone();
+        self.tx_config.grpc_address = endpoint.grpc_addr.clone();
+
+        self.senders.clear();
+    }
+
+    /// Marks the active node as unhealthy if the error `e`, returned by a request
+    /// to that node, shows that the node could not be reached.
+    fn request_error(&self, e: Error) -> Error {
+        if matches!(
+            e.detail(),
+            ErrorDetail::Rpc(_) | ErrorDetail::GrpcTransport(_)
+        ) {
+            self.endpoints.report_failure();
+        }
+        e
+    }
+
     /// Run a future to completion on the Tokio runtime.
     fn block_on<F: Future>(&self, f: F) -> F::Output {
         crate::time!("block_on");
@@ -302,14 +345,16 @@ impl CosmosSdkChain {
             return Err(Error::private_store());
         }
 
-        let response = self.block_on(abci_query(
-            &self.rpc_client,
-            &self.config.rpc_addr,
-            path,
-            data.to_string(),
-            height,
-            prove,
-        ))?;
+        let response = self
+            .block_on(abci_query(
+                &self.rpc_client,
+                &self.rpc_addr,
+                path,
+                data.to_string(),
+                height,
+                prove,
+            ))
+            .map_err(|e| self.request_error(e))?;
 
         // TODO - Verify response proof, if requested.
         if prove {}
@@ -342,14 +387,17 @@ impl CosmosSdkChain {
         // SAFETY: Creating a Path from a constant; this should never fail
         let path = TendermintABCIPath::from_str(SDK_UPGRADE_QUERY_PATH)
             .expect("Turning SDK upgrade query path constant into a Tendermint ABCI path");
-        let response: QueryResponse = self.block_on(abci_query(
-            &self.rpc_client,
-            &self.config.rpc_addr,
-            path,
-            Path::Upgrade(query_data).to_string(),
-            TmHeight::try_from(query_height.revision_height()).map_err(Error::invalid_height)?,
-            true,
-        ))?;
+        let response: QueryResponse = self
+            .block_on(abci_query(
+                &self.rpc_client,
+                &self.rpc_addr,
+                path,
+                Path::Upgrade(query_data).to_string(),
+                TmHeight::try_from(query_height.revision_height())
+                    .map_err(Error::invalid_height)?,
+                true,
+            ))
+            .map_err(|e| self.request_error(e))?;
 
         let proof = response.proof.ok_or_else(Error::empty_response_proof)?;
 
@@ -362,6 +410,18 @@ impl CosmosSdkChain {
             .map_err(Error::key_base)
     }
 
+    /// Get the given key of the key pool, or the default key if `None`.
+    fn pool_key(&self, key_name: Option<String>) -> Result<(String, KeyEntry), Error> {
+        let key_name = key_name.unwrap_or_else(|| self.config.key_name.clone());
+
+        let key = self
+            .keybase()
+            .get_key(&key_name)
+            .map_err(|e| Error::key_not_found(key_name.clone(), e))?;
+
+        Ok((key_name, key))
+    }
+
     fn trusting_period(&self, unbonding_period: Duration) -> Duration {
         self.config
             .trusting_period
@@ -375,11 +435,11 @@ impl CosmosSdkChain {
     fn chain_status(&self) -> Result<status::Response, Error> {
         let status = self
             .block_on(self.rpc_client.status())
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+            .map_err(|e| self.request_error(Error::rpc(self.rpc_addr.clone(), e)))?;
 
         if status.sync_info.catching_up {
             return Err(Error::chain_not_caught_up(
-                self.config.rpc_addr.to_string(),
+                self.rpc_addr.to_string(),
                 self.config().id.clone(),
             ));
         }
@@ -392,69 +452,29 @@ impl CosmosSdkChain {
         crate::time!("query_latest_height");
         crate::telemetry!(query, self.id(), "query_latest_height");
 
-        let status = self.rt.block_on(query_status(
-            self.id(),
-            &self.rpc_client,
-            &self.config.rpc_addr,
-        ))?;
+        let status = self
+            .rt
+            .block_on(query_status(self.id(), &self.rpc_client, &self.rpc_addr))
+            .map_err(|e| self.request_error(e))?;
 
         Ok(status.height)
     }
 
-    async fn do_send_messages_and_wait_commit(
-        &mut self,
-        tracked_msgs: TrackedMsgs,
-    ) -> Result<Vec<IbcEvent>, Error> {
-        crate::time!("send_messages_and_wait_commit");
-
-        let _span =
-            span!(Level::DEBUG, "send_tx_commit", id = %tracked_msgs.tracking_id()).entered();
-
-        let proto_msgs = tracked_msgs.msgs;
-
-        let key_entry = self.key()?;
-
-        let account =
-            get_or_fetch_account(&self.grpc_addr, &key_entry.account, &mut self.account).await?;
-
-        send_batched_messages_and_wait_commit(
-            &self.tx_config,
-            self.config.max_msg_num,
-            self.config.max_tx_size,
-            &key_entry,
-            account,
-            &self.config.memo_prefix,
-            proto_msgs,
-        )
-        .await
-    }
-
-    async fn do_send_messages_and_wait_check_tx(
-        &mut self,
-        tracked_msgs: TrackedMsgs,
-    ) -> Result<Vec<Response>, Error> {
-        crate::time!("send_messages_and_wait_check_tx");
+    /// Get the sender of the transactions signed with the given key of the key pool,
+    /// or with the default key if `None`.
+    fn key_sender(&mut self, key_name: Option<String>) -> Result<&mut CosmosTxSender, Error> {
+        let (key_name, key_entry) = self.pool_key(key_name)?;
 
-        let span = span!(Level::DEBUG, "send_tx_check", id = %tracked_msgs.tracking_id());
-        let _enter = span.enter();
-
-        let proto_msgs = tracked_msgs.msgs;
-
-        let key_entry = self.key()?;
-
-        let account =
-            get_or_fetch_account(&self.grpc_addr, &key_entry.account, &mut self.account).await?;
+        let sender = self.senders.entry(key_name).or_insert_with(|| {
+            CosmosTxSender::new(
+                self.rt.clone(),
+                self.tx_config.clone(),
+                &self.config,
+                key_entry,
+            )
+        });
 
-        send_batched_messages_and_wait_check_tx(
-            &self.tx_config,
-            self.config.max_msg_num,
-            self.config.max_tx_size,
-            &key_entry,
-            account,
-            &self.config.memo_prefix,
-            proto_msgs,
-        )
-        .await
+        Ok(sender)
     }
 }
 
@@ -466,28 +486,43 @@ impl ChainEndpoint for CosmosSdkChain {
     type LightClient = TmLightClient;
 
     fn bootstrap(config: ChainConfig, rt: Arc<TokioRuntime>) -> Result<Self, Error> {
-        let rpc_client = HttpClient::new(config.rpc_addr.clone())
-            .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))?;
+        let endpoints = Endpoints::from_config(&config)?;
+        endpoints.spawn_probes(&rt, config.rpc_timeout, PROBE_INTERVAL);
 
         // Initialize key store and load key
         let keybase = KeyRing::new(config.key_store_type, &config.account_prefix, &config.id)
             .map_err(Error::key_base)?;
 
-        let grpc_addr = Uri::from_str(&config.grpc_addr.to_string())
-            .map_err(|e| Error::invalid_uri(config.grpc_addr.to_string(), e))?;
-
         let tx_config = TxConfig::try_from(&config)?;
 
+        // The light client store is only a cache, so proceed without it if it cannot be
+        // opened, e.g. because it is already in use by another instance of the relayer.
+        let light_store = LightBlockStore::from_config(&config).unwrap_or_else(|e| {
+            warn!(
+                "[{}] the light client store is unavailable, proceeding without it: {}",
+                config.id, e
+            );
+            None
+        });
+
+        let active = endpoints.active();
+        let rpc_client = active.rpc_client.clone();
+        let rpc_addr = active.config.rpc_addr.clone();
+        let grpc_addr = active.grpc_addr.clone();
+
         // Retrieve the version specification of this chain
 
         let chain = Self {
             config,
+            endpoints,
             rpc_client,
+            rpc_addr,
             grpc_addr,
             rt,
             keybase,
-            account: None,
+            senders: HashMap::new(),
             tx_config,
+            light_store,
         };
 
         Ok(chain)
@@ -502,9 +537,14 @@ impl ChainEndpoint for CosmosSdkChain {
             .rt
             .block_on(self.rpc_client.status())
             .map(|s| s.node_info.id)
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+            .map_err(|e| self.request_error(Error::rpc(self.rpc_addr.clone(), e)))?;
 
-        let light_client = TmLightClient::from_config(&self.config, peer_id)?;
+        let light_client = TmLightClient::from_config(
+            &self.config,
+            self.rpc_addr.clone(),
+            peer_id,
+            self.light_store.clone(),
+        )?;
 
         Ok(light_client)
     }
@@ -515,24 +555,62 @@ impl ChainEndpoint for CosmosSdkChain {
     ) -> Result<(EventReceiver, TxMonitorCmd), Error> {
         crate::time!("init_event_monitor");
 
-        let (mut event_monitor, event_receiver, monitor_tx) = EventMonitor::new(
-            self.config.id.clone(),
-            self.config.websocket_addr.clone(),
-            rt,
-        )
-        .map_err(Error::event_monitor)?;
+        match self.config.event_source {
+            EventSourceMode::Push => {
+                let (mut event_monitor, event_receiver, monitor_tx) = EventMonitor::new(
+                    self.config.id.clone(),
+                    self.endpoints.active().config.websocket_addr.clone(),
+                    rt,
+                )
+                .map_err(Error::event_monitor)?;
+
+                event_monitor.subscribe().map_err(Error::event_monitor)?;
+
+                thread::spawn(move || event_monitor.run());
+
+                Ok((event_receiver, monitor_tx))
+            }
+            EventSourceMode::Pull { interval } => {
+                // The cursor only allows resuming from the last collected height after a restart,
+                // so proceed without it if it cannot be opened, e.g. because it is already in use
+                // by another instance of the relayer.
+                let cursor_store =
+                    EventCursorStore::from_config(&self.config).unwrap_or_else(|e| {
+                        warn!(
+                            "[{}] the event cursor store is unavailable, proceeding without it: {}",
+                            self.config.id, e
+                        );
+                        None
+                    });
 
-        event_monitor.subscribe().map_err(Error::event_monitor)?;
+                let (event_source, event_receiver, monitor_tx) = PullEventSource::new(
+                    self.config.id.clone(),
+                    RpcBlockSource::new(self.rpc_client.clone(), rt),
+                    interval,
+                    cursor_store,
+                );
 
-        thread::spawn(move || event_monitor.run());
+                thread::spawn(move || event_source.run());
 
-        Ok((event_receiver, monitor_tx))
+                Ok((event_receiver, monitor_tx))
+            }
+        }
     }
 
     fn shutdown(self) -> Result<(), Error> {
         Ok(())
     }
 
+    fn check_endpoints(&mut self) -> Result<Option<Url>, Error> {
+        if !self.endpoints.select() {
+            return Ok(None);
+        }
+
+        self.use_active_endpoint();
+
+        Ok(Some(self.rpc_addr.clone()))
+    }
+
     fn id(&self) -> &ChainId {
         &self.config().id
     }
@@ -587,35 +665,56 @@ impl ChainEndpoint for CosmosSdkChain {
     fn send_messages_and_wait_commit(
         &mut self,
         tracked_msgs: TrackedMsgs,
+        key_name: Option<String>,
     ) -> Result<Vec<IbcEvent>, Error> {
-        let runtime = self.rt.clone();
-
-        runtime.block_on(self.do_send_messages_and_wait_commit(tracked_msgs))
+        self.key_sender(key_name)?
+            .send_messages_and_wait_commit(tracked_msgs)
     }
 
     fn send_messages_and_wait_check_tx(
         &mut self,
         tracked_msgs: TrackedMsgs,
+        key_name: Option<String>,
     ) -> Result<Vec<Response>, Error> {
-        let runtime = self.rt.clone();
+        self.key_sender(key_name)?
+            .send_messages_and_wait_check_tx(tracked_msgs)
+    }
+
+    fn tx_sender(&mut self, key_name: &str) -> Result<Option<Box<dyn TxSender>>, Error> {
+        let (_, key_entry) = self.pool_key(Some(key_name.to_string()))?;
 
-        runtime.block_on(self.do_send_messages_and_wait_check_tx(tracked_msgs))
+        let sender = CosmosTxSender::new(
+            self.rt.clone(),
+            self.tx_config.clone(),
+            &self.config,
+            key_entry,
+        );
+
+        Ok(Some(Box::new(sender)))
     }
 
-    /// Get the account for the signer
-    fn get_signer(&mut self) -> Result<Signer, Error> {
+    /// Get the signer of the messages, i.e. the authz granter if one is configured
+    fn get_signer(&mut self, key_name: Option<String>) -> Result<Signer, Error> {
         crate::time!("get_signer");
 
         // Get the key from key seed file
-        let key = self
-            .keybase()
-            .get_key(&self.config.key_name)
-            .map_err(|e| Error::key_not_found(self.config.key_name.clone(), e))?;
+        let (_, key) = self.pool_key(key_name)?;
 
-        let bech32 = encode_to_bech32(&key.address.to_hex(), &self.config.account_prefix)?;
-        bech32
-            .parse()
-            .map_err(|e| Error::ics02(ClientError::signer(e)))
+        msg_signer(
+            &key,
+            &self.config.account_prefix,
+            self.config.authz_granter.as_deref(),
+        )
+    }
+
+    /// Get the account of the key, which signs and pays for the transactions
+    fn get_key_address(&mut self, key_name: Option<String>) -> Result<Signer, Error> {
+        crate::time!("get_key_address");
+
+        // Get the key from key seed file
+        let (_, key) = self.pool_key(key_name)?;
+
+        key_address(&key, &self.config.account_prefix)
     }
 
     /// Get the chain configuration
@@ -623,15 +722,21 @@ impl ChainEndpoint for CosmosSdkChain {
         self.config.clone()
     }
 
+    fn update_config(&mut self, config: ChainConfig) -> Result<(), Error> {
+        // The gas and fee settings are read from the transaction configuration.
+        self.tx_config = TxConfig::try_from(&config)?;
+        self.config = config;
+        self.senders.clear();
+
+        Ok(())
+    }
+
     /// Get the signing key
-    fn get_key(&mut self) -> Result<KeyEntry, Error> {
+    fn get_key(&mut self, key_name: Option<String>) -> Result<KeyEntry, Error> {
         crate::time!("get_key");
 
         // Get the key from key seed file
-        let key = self
-            .keybase()
-            .get_key(&self.config.key_name)
-            .map_err(|e| Error::key_not_found(self.config.key_name.clone(), e))?;
+        let (_, key) = self.pool_key(key_name)?;
 
         Ok(key)
     }
@@ -697,7 +802,7 @@ impl ChainEndpoint for CosmosSdkChain {
         // metadata at the given height via `/blockchain` endpoint.
         let abci_info = self
             .block_on(self.rpc_client.abci_info())
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+            .map_err(|e| self.request_error(Error::rpc(self.rpc_addr.clone(), e)))?;
 
         // Query `/blockchain` endpoint to pull the block metadata corresponding to
         // the latest block that the application committed.
@@ -708,7 +813,7 @@ impl ChainEndpoint for CosmosSdkChain {
                 self.rpc_client
                     .blockchain(abci_info.last_block_height, abci_info.last_block_height),
             )
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?
+            .map_err(|e| self.request_error(Error::rpc(self.rpc_addr.clone(), e)))?
             .block_metas;
 
         return if let Some(latest_app_block) = blocks.first() {
@@ -741,7 +846,7 @@ impl ChainEndpoint for CosmosSdkChain {
                     self.grpc_addr.clone(),
                 ),
             )
-            .map_err(Error::grpc_transport)?;
+            .map_err(|e| self.request_error(Error::grpc_transport(e)))?;
 
         let request = tonic::Request::new(request.into());
         let response = self
@@ -848,7 +953,7 @@ impl ChainEndpoint for CosmosSdkChain {
                     self.grpc_addr.clone(),
                 ),
             )
-            .map_err(Error::grpc_transport)?;
+            .map_err(|e| self.request_error(Error::grpc_transport(e)))?;
 
         let request = tonic::Request::new(request.into());
         let response = self
@@ -915,7 +1020,7 @@ impl ChainEndpoint for CosmosSdkChain {
                     self.grpc_addr.clone(),
                 ),
             )
-            .map_err(Error::grpc_transport)?;
+            .map_err(|e| self.request_error(Error::grpc_transport(e)))?;
 
         let request = tonic::Request::new(request.into());
 
@@ -950,7 +1055,7 @@ impl ChainEndpoint for CosmosSdkChain {
                     self.grpc_addr.clone(),
                 ),
             )
-            .map_err(Error::grpc_transport)?;
+            .map_err(|e| self.request_error(Error::grpc_transport(e)))?;
 
         let request = tonic::Request::new(request.into());
 
@@ -990,7 +1095,7 @@ impl ChainEndpoint for CosmosSdkChain {
             let mut client =
                 connection::query_client::QueryClient::connect(chain.grpc_addr.clone())
                     .await
-                    .map_err(Error::grpc_transport)?;
+                    .map_err(|e| chain.request_error(Error::grpc_transport(e)))?;
 
             let mut request = connection::QueryConnectionRequest {
                 connection_id: connection_id.to_string(),
@@ -1063,7 +1168,7 @@ impl ChainEndpoint for CosmosSdkChain {
                     self.grpc_addr.clone(),
                 ),
             )
-            .map_err(Error::grpc_transport)?;
+            .map_err(|e| self.request_error(Error::grpc_transport(e)))?;
 
         let request = tonic::Request::new(request.into());
 
@@ -1096,7 +1201,7 @@ impl ChainEndpoint for CosmosSdkChain {
                     self.grpc_addr.clone(),
                 ),
             )
-            .map_err(Error::grpc_transport)?;
+            .map_err(|e| self.request_error(Error::grpc_transport(e)))?;
 
         let request = tonic::Request::new(request.into());
 
@@ -1138,6 +1243,155 @@ impl ChainEndpoint for CosmosSdkChain {
         }
     }
 
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error> {
+        crate::time!("query_upgrade");
+        crate::telemetry!(query, self.id(), "query_upgrade");
+
+        let res = self.query(
+            ChannelUpgradesPath(request.port_id, request.channel_id),
+            request.height,
+            matches!(include_proof, IncludeProof::Yes),
+        )?;
+
+        let upgrade = Upgrade::decode_vec(&res.value).map_err(Error::decode)?;
+
+        match include_proof {
+            IncludeProof::Yes => {
+                let proof = res.proof.ok_or_else(Error::empty_response_proof)?;
+                Ok((upgrade, Some(proof)))
+            }
+            IncludeProof::No => Ok((upgrade, None)),
+        }
+    }
+
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        crate::time!("query_upgrade_error");
+        crate::telemetry!(query, self.id(), "query_upgrade_error");
+
+        let res = self.query(
+            ChannelUpgradeErrorsPath(request.port_id, request.channel_id),
+            request.height,
+            matches!(include_proof, IncludeProof::Yes),
+        )?;
+
+        let error_receipt = ErrorReceipt::decode_vec(&res.value).map_err(Error::decode)?;
+
+        match include_proof {
+            IncludeProof::Yes => {
+                let proof = res.proof.ok_or_else(Error::empty_response_proof)?;
+                Ok((error_receipt, Some(proof)))
+            }
+            IncludeProof::No => Ok((error_receipt, None)),
+        }
+    }
+
+    fn query_cross_chain(
+        &self,
+        request: QueryCrossChainRequest,
+    ) -> Result<(Vec<u8>, MerkleProof, ICSHeight), Error> {
+        crate::time!("query_cross_chain");
+        crate::telemetry!(query, self.id(), "query_cross_chain");
+
+        // Unlike the IBC queries, the value may be in any store, which is queried by key.
+        let path = TendermintABCIPath::from_str(&format!("store/{}/key", request.path.store))
+            .map_err(|_| Error::invalid_query_path(request.path.to_string()))?;
+
+        let res = self
+            .block_on(abci_query(
+                &self.rpc_client,
+                &self.rpc_addr,
+                path,
+                request.path.key,
+                TmHeight::try_from(request.height)?,
+                true,
+            ))
+            .map_err(|e| self.request_error(e))?;
+
+        let proof = res.proof.ok_or_else(Error::empty_response_proof)?;
+
+        // The state at the height of the response is committed to by the app hash of the
+        // next block, hence the proof can only be verified at the next height.
+        let proof_height = ICSHeight::new(self.id().version(), res.height.increment().value())
+            .map_err(|_| Error::invalid_height_no_source())?;
+
+        Ok((res.value, proof, proof_height))
+    }
+
+    fn query_incentivized_packets(
+        &self,
+        request: QueryIncentivizedPacketsRequest,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        crate::time!("query_incentivized_packets");
+        crate::telemetry!(query, self.id(), "query_incentivized_packets");
+
+        let mut client = self
+            .block_on(
+                ibc_proto::ibc::applications::fee::v1::query_client::QueryClient::connect(
+                    self.grpc_addr.clone(),
+                ),
+            )
+            .map_err(|e| self.request_error(Error::grpc_transport(e)))?;
+
+        let request = tonic::Request::new(request.into());
+
+        let response = match self.block_on(client.incentivized_packets_for_channel(request)) {
+            Ok(res) => res.into_inner(),
+            // The chain does not run the fee middleware.
+            Err(e) if matches!(e.code(), tonic::Code::NotFound | tonic::Code::Unimplemented) => {
+                return Ok(vec![])
+            }
+            Err(e) => return Err(Error::grpc_status(e)),
+        };
+
+        response
+            .incentivized_packets
+            .into_iter()
+            .map(|fees| fees.try_into().map_err(Error::ics29))
+            .collect()
+    }
+
+    fn query_counterparty_payee(
+        &self,
+        request: QueryCounterpartyPayeeRequest,
+    ) -> Result<Option<Signer>, Error> {
+        crate::time!("query_counterparty_payee");
+        crate::telemetry!(query, self.id(), "query_counterparty_payee");
+
+        let mut client = self
+            .block_on(
+                ibc_proto::ibc::applications::fee::v1::query_client::QueryClient::connect(
+                    self.grpc_addr.clone(),
+                ),
+            )
+            .map_err(|e| self.request_error(Error::grpc_transport(e)))?;
+
+        let request = tonic::Request::new(request.into());
+
+        let response = match self.block_on(client.counterparty_payee(request)) {
+            Ok(res) => res.into_inner(),
+            // No counterparty payee is registered, or the chain does not run the fee middleware.
+            Err(e) if matches!(e.code(), tonic::Code::NotFound | tonic::Code::Unimplemented) => {
+                return Ok(None)
+            }
+            Err(e) => return Err(Error::grpc_status(e)),
+        };
+
+        let counterparty_payee = response
+            .counterparty_payee
+            .parse()
+            .map_err(|e| Error::ics29(FeeError::signer(e)))?;
+
+        Ok(Some(counterparty_payee))
+    }
+
     fn query_channel_client_state(
         &self,
         request: QueryChannelClientStateRequest,
@@ -1151,7 +1405,7 @@ impl ChainEndpoint for CosmosSdkChain {
                     self.grpc_addr.clone(),
                 ),
             )
-            .map_err(Error::grpc_transport)?;
+            .map_err(|e| self.request_error(Error::grpc_transport(e)))?;
 
         let request = tonic::Request::new(request.into());
 
@@ -1206,7 +1460,7 @@ impl ChainEndpoint for CosmosSdkChain {
                     self.grpc_addr.clone(),
                 ),
             )
-            .map_err(Error::grpc_transport)?;
+            .map_err(|e| self.request_error(Error::grpc_transport(e)))?;
 
         let request = tonic::Request::new(request.into());
 
@@ -1269,7 +1523,7 @@ impl ChainEndpoint for CosmosSdkChain {
                     self.grpc_addr.clone(),
                 ),
             )
-            .map_err(Error::grpc_transport)?;
+            .map_err(|e| self.request_error(Error::grpc_transport(e)))?;
 
         let request = tonic::Request::new(request.into());
 
@@ -1325,7 +1579,7 @@ impl ChainEndpoint for CosmosSdkChain {
                     self.grpc_addr.clone(),
                 ),
             )
-            .map_err(Error::grpc_transport)?;
+            .map_err(|e| self.request_error(Error::grpc_transport(e)))?;
 
         let request = tonic::Request::new(request.into());
 
@@ -1362,7 +1616,7 @@ impl ChainEndpoint for CosmosSdkChain {
                     self.grpc_addr.clone(),
                 ),
             )
-            .map_err(Error::grpc_transport)?;
+            .map_err(|e| self.request_error(Error::grpc_transport(e)))?;
 
         let request = tonic::Request::new(request.into());
 
@@ -1413,7 +1667,7 @@ impl ChainEndpoint for CosmosSdkChain {
                             self.grpc_addr.clone(),
                         ),
                     )
-                    .map_err(Error::grpc_transport)?;
+                    .map_err(|e| self.request_error(Error::grpc_transport(e)))?;
 
                 let request = tonic::Request::new(request.into());
 
@@ -1445,9 +1699,10 @@ impl ChainEndpoint for CosmosSdkChain {
         self.block_on(query_txs(
             self.id(),
             &self.rpc_client,
-            &self.config.rpc_addr,
+            &self.rpc_addr,
             request,
         ))
+        .map_err(|e| self.request_error(e))
     }
 
     fn query_blocks(
@@ -1472,7 +1727,7 @@ impl ChainEndpoint for CosmosSdkChain {
                             1, // there should only be a single match for this query
                             Order::Ascending,
                         ))
-                        .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+                        .map_err(|e| self.request_error(Error::rpc(self.rpc_addr.clone(), e)))?;
 
                     assert!(
                         response.blocks.len() <= 1,
@@ -1492,7 +1747,9 @@ impl ChainEndpoint for CosmosSdkChain {
 
                         let response = self
                             .block_on(self.rpc_client.block_results(block.header.height))
-                            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+                            .map_err(|e| {
+                                self.request_error(Error::rpc(self.rpc_addr.clone(), e))
+                            })?;
 
                         begin_block_events.append(
                             &mut response
@@ -1536,7 +1793,7 @@ impl ChainEndpoint for CosmosSdkChain {
         };
         let response = self
             .block_on(rpc_call)
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+            .map_err(|e| self.request_error(Error::rpc(self.rpc_addr.clone(), e)))?;
         Ok(response.block.header.into())
     }
 
@@ -1644,7 +1901,7 @@ fn client_id_suffix(client_id: &ClientId) -> Option<u64> {
 fn do_health_check(chain: &CosmosSdkChain) -> Result<(), Error> {
     let chain_id = chain.id();
     let grpc_address = chain.grpc_addr.to_string();
-    let rpc_address = chain.config.rpc_addr.to_string();
+    let rpc_address = chain.rpc_addr.to_string();
 
     // Checkup on the self-reported health endpoint
     chain.block_on(chain.rpc_client.health()).map_err(|e| {
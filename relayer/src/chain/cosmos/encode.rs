@@ -1,6 +1,10 @@
 use bech32::{ToBase32, Variant};
+use bitcoin::hashes::hex::ToHex;
 use core::str::FromStr;
+use ibc::core::ics02_client::error::Error as ClientError;
 use ibc::core::ics24_host::identifier::ChainId;
+use ibc::signer::Signer;
+use ibc_proto::cosmos::authz::v1beta1::MsgExec;
 use ibc_proto::cosmos::tx::v1beta1::mode_info::{Single, Sum};
 use ibc_proto::cosmos::tx::v1beta1::{AuthInfo, Fee, ModeInfo, SignDoc, SignerInfo, TxBody, TxRaw};
 use ibc_proto::google::protobuf::Any;
@@ -14,6 +18,9 @@ use crate::config::AddressType;
 use crate::error::Error;
 use crate::keyring::{sign_message, KeyEntry};
 
+/// The type URL of the authz message executing messages on behalf of a granter.
+pub const MSG_EXEC_TYPE_URL: &str = "/cosmos.authz.v1beta1.MsgExec";
+
 pub fn sign_and_encode_tx(
     config: &TxConfig,
     key_entry: &KeyEntry,
@@ -45,6 +52,12 @@ pub fn sign_tx(
 
     let signer = encode_signer_info(&config.address_type, account.sequence, key_bytes)?;
 
+    // In authz mode, the key executes the messages on behalf of the granter.
+    let messages = match config.authz_granter {
+        Some(_) => vec![wrap_in_msg_exec(&key_entry.account, messages)?],
+        None => messages,
+    };
+
     let (body, body_bytes) = tx_body_and_bytes(messages, tx_memo)?;
 
     let (auth_info, auth_info_bytes) = auth_info_and_bytes(signer, fee.clone())?;
@@ -126,6 +139,24 @@ fn encode_signer_info(
     Ok(signer_info)
 }
 
+/// Wrap the messages in a `MsgExec`, which the grantee executes using the
+/// authorizations granted by the signer of the messages.
+pub fn wrap_in_msg_exec(grantee: &str, messages: Vec<Any>) -> Result<Any, Error> {
+    let msg_exec = MsgExec {
+        grantee: grantee.to_string(),
+        msgs: messages,
+    };
+
+    let mut value = Vec::new();
+    prost::Message::encode(&msg_exec, &mut value)
+        .map_err(|e| Error::protobuf_encode("MsgExec".to_string(), e))?;
+
+    Ok(Any {
+        type_url: MSG_EXEC_TYPE_URL.to_string(),
+        value,
+    })
+}
+
 fn encode_tx_raw(tx_raw: TxRaw) -> Result<Vec<u8>, Error> {
     let mut tx_bytes = Vec::new();
     prost::Message::encode(&tx_raw, &mut tx_bytes)
@@ -144,6 +175,38 @@ pub fn encode_to_bech32(address: &str, account_prefix: &str) -> Result<String, E
     Ok(encoded)
 }
 
+/// Returns the human-readable prefix of a bech32 account address.
+pub fn bech32_prefix(address: &str) -> Result<String, Error> {
+    let (prefix, _, _) = bech32::decode(address).map_err(Error::bech32_encoding)?;
+
+    Ok(prefix)
+}
+
+/// Returns the address of `key` on a chain whose accounts have the given prefix.
+/// This account signs the transactions and pays their fees.
+pub fn key_address(key: &KeyEntry, account_prefix: &str) -> Result<Signer, Error> {
+    let bech32 = encode_to_bech32(&key.address.to_hex(), account_prefix)?;
+
+    bech32
+        .parse()
+        .map_err(|e| Error::ics02(ClientError::signer(e)))
+}
+
+/// Returns the signer of the messages submitted with `key`: the authz granter on whose behalf
+/// the key executes them if one is configured, or else the address of the key.
+pub fn msg_signer(
+    key: &KeyEntry,
+    account_prefix: &str,
+    authz_granter: Option<&str>,
+) -> Result<Signer, Error> {
+    match authz_granter {
+        Some(granter) => granter
+            .parse()
+            .map_err(|e| Error::ics02(ClientError::signer(e))),
+        None => key_address(key, account_prefix),
+    }
+}
+
 fn auth_info_and_bytes(signer_info: SignerInfo, fee: Fee) -> Result<(AuthInfo, Vec<u8>), Error> {
     let auth_info = AuthInfo {
         signer_infos: vec![signer_info],
@@ -177,3 +240,131 @@ fn tx_body_and_bytes(proto_msgs: Vec<Any>, memo: &Memo) -> Result<(TxBody, Vec<u
 
     Ok((body, body_buf))
 }
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+    use core::time::Duration;
+
+    use ibc::core::ics24_host::identifier::ChainId;
+    use ibc_proto::cosmos::authz::v1beta1::MsgExec;
+    use ibc_proto::cosmos::tx::v1beta1::Fee;
+    use ibc_proto::google::protobuf::Any;
+    use prost::Message;
+    use tendermint_rpc::HttpClient;
+
+    use super::{
+        bech32_prefix, key_address, msg_signer, sign_tx, wrap_in_msg_exec, MSG_EXEC_TYPE_URL,
+    };
+    use crate::chain::cosmos::types::account::{Account, AccountNumber, AccountSequence};
+    use crate::chain::cosmos::types::config::TxConfig;
+    use crate::chain::cosmos::types::gas::{DynamicGasPrice, GasConfig};
+    use crate::config::types::Memo;
+    use crate::config::{AddressType, DynamicGasPriceConfig, GasPrice};
+    use crate::keyring::{HDPath, KeyEntry, KeyRing, Store};
+
+    const GRANTER: &str = "cosmos1granter";
+
+    fn key() -> KeyEntry {
+        let chain_id = ChainId::new("ibc".to_string(), 0);
+        let keyring = KeyRing::new(Store::Memory, "cosmos", &chain_id).unwrap();
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let hd_path = HDPath::from_str("m/44'/118'/0'/0/0").unwrap();
+
+        keyring
+            .key_from_mnemonic(mnemonic, &hd_path, &AddressType::Cosmos)
+            .unwrap()
+    }
+
+    fn tx_config(authz_granter: Option<&str>) -> TxConfig {
+        let gas_price = GasPrice::new(0.1, "stake".to_string());
+
+        TxConfig {
+            chain_id: ChainId::new("ibc".to_string(), 0),
+            gas_config: GasConfig {
+                default_gas: 100_000,
+                max_gas: 400_000,
+                gas_multiplier: 1.0,
+                gas_price: DynamicGasPrice::new(gas_price, DynamicGasPriceConfig::default()),
+                fee_granter: "".to_string(),
+            },
+            rpc_client: HttpClient::new("http://127.0.0.1:26657").unwrap(),
+            rpc_address: "http://127.0.0.1:26657".parse().unwrap(),
+            grpc_address: "http://127.0.0.1:9090".parse().unwrap(),
+            rpc_timeout: Duration::from_secs(10),
+            address_type: AddressType::Cosmos,
+            authz_granter: authz_granter.map(ToString::to_string),
+        }
+    }
+
+    fn messages() -> Vec<Any> {
+        vec![
+            Any {
+                type_url: "/ibc.core.channel.v1.MsgRecvPacket".to_string(),
+                value: vec![1, 2, 3],
+            },
+            Any {
+                type_url: "/ibc.core.channel.v1.MsgAcknowledgement".to_string(),
+                value: vec![4, 5, 6],
+            },
+        ]
+    }
+
+    #[test]
+    fn msg_exec() {
+        let messages = messages();
+
+        let wrapped = wrap_in_msg_exec("cosmos1grantee", messages.clone()).unwrap();
+        assert_eq!(wrapped.type_url, MSG_EXEC_TYPE_URL);
+
+        let msg_exec = MsgExec::decode(wrapped.value.as_slice()).unwrap();
+        assert_eq!(msg_exec.grantee, "cosmos1grantee");
+        assert_eq!(msg_exec.msgs, messages);
+    }
+
+    #[test]
+    fn signers() {
+        let key = key();
+
+        let key_address = key_address(&key, "cosmos").unwrap();
+        assert_eq!(key_address.as_ref(), key.account);
+        assert_eq!(bech32_prefix(key_address.as_ref()).unwrap(), "cosmos");
+
+        // The messages are signed by the granter in authz mode, and by the key otherwise
+        assert_eq!(msg_signer(&key, "cosmos", None).unwrap(), key_address);
+        assert_eq!(
+            msg_signer(&key, "cosmos", Some(GRANTER)).unwrap().as_ref(),
+            GRANTER
+        );
+    }
+
+    #[test]
+    fn authz_messages_are_executed_by_the_key() {
+        let key = key();
+        let account = Account {
+            number: AccountNumber::new(1),
+            sequence: AccountSequence::new(0),
+        };
+        let (memo, fee) = (Memo::default(), Fee::default());
+
+        let signed_tx = sign_tx(
+            &tx_config(Some(GRANTER)),
+            &key,
+            &account,
+            &memo,
+            messages(),
+            &fee,
+        )
+        .unwrap();
+
+        assert_eq!(signed_tx.body.messages.len(), 1);
+        assert_eq!(signed_tx.body.messages[0].type_url, MSG_EXEC_TYPE_URL);
+        let msg_exec = MsgExec::decode(signed_tx.body.messages[0].value.as_slice()).unwrap();
+        assert_eq!(msg_exec.grantee, key.account);
+        assert_eq!(msg_exec.msgs, messages());
+
+        // Without a granter, the messages are submitted as they are
+        let signed_tx = sign_tx(&tx_config(None), &key, &account, &memo, messages(), &fee).unwrap();
+        assert_eq!(signed_tx.body.messages, messages());
+    }
+}
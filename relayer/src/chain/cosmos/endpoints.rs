@@ -0,0 +1,386 @@
+//! Health tracking of the full nodes of a chain, so that the queries and transactions
+//! are sent to a healthy node, and the relayer fails over to another node when the
+//! one it uses becomes unreachable, slow or lags behind the rest of the chain.
+
+use alloc::sync::Arc;
+use core::str::FromStr;
+use core::time::Duration;
+use std::time::Instant;
+
+use futures::future::join_all;
+use tendermint_rpc::{Client, HttpClient};
+use tokio::runtime::Runtime as TokioRuntime;
+use tonic::codegen::http::Uri;
+use tracing::{info, warn};
+
+use ibc::core::ics24_host::identifier::ChainId;
+
+use crate::config::{ChainConfig, EndpointConfig};
+use crate::error::Error;
+use crate::telemetry;
+use crate::util::lock::{LockExt, RwArc};
+
+/// How many blocks a node may be behind the most advanced node of the chain
+/// before it is considered to be lagging.
+pub const MAX_HEIGHT_LAG: u64 = 5;
+
+/// Interval at which the health of the full nodes of a chain is probed.
+pub const PROBE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The health of a full node, as observed by the last probes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EndpointHealth {
+    /// The latency of the last successful probe.
+    pub latency: Option<Duration>,
+    /// The number of consecutive failed probes and requests.
+    pub errors: u64,
+    /// The latest height reported by the node.
+    pub latest_height: Option<u64>,
+    /// Whether the node is more than [`MAX_HEIGHT_LAG`] blocks behind the most advanced node.
+    pub lagging: bool,
+}
+
+impl EndpointHealth {
+    pub fn is_healthy(&self) -> bool {
+        self.errors == 0 && !self.lagging
+    }
+}
+
+/// A full node of the chain, with the client to send it RPC requests.
+#[derive(Clone, Debug)]
+pub struct Endpoint {
+    pub config: EndpointConfig,
+    pub rpc_client: HttpClient,
+    pub grpc_addr: Uri,
+}
+
+impl Endpoint {
+    fn new(config: EndpointConfig) -> Result<Self, Error> {
+        let rpc_client = HttpClient::new(config.rpc_addr.clone())
+            .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))?;
+
+        let grpc_addr = Uri::from_str(&config.grpc_addr.to_string())
+            .map_err(|e| Error::invalid_uri(config.grpc_addr.to_string(), e))?;
+
+        Ok(Self {
+            config,
+            rpc_client,
+            grpc_addr,
+        })
+    }
+
+    /// Queries the status of the node, and returns its latest height
+    /// along with the latency of the query.
+    async fn probe(&self, timeout: Duration) -> Result<(u64, Duration), String> {
+        let start = Instant::now();
+
+        let status = tokio::time::timeout(timeout, self.rpc_client.status())
+            .await
+            .map_err(|_| format!("no response after {:?}", timeout))?
+            .map_err(|e| e.to_string())?;
+
+        if status.sync_info.catching_up {
+            return Err("node is catching up".to_string());
+        }
+
+        Ok((
+            status.sync_info.latest_block_height.value(),
+            start.elapsed(),
+        ))
+    }
+}
+
+/// The full nodes of a chain, ordered by priority, one of which is the active node
+/// to which the queries and transactions are sent.
+///
+/// The health of the nodes is shared with the task spawned by [`Endpoints::spawn_probes`],
+/// which keeps it up to date in the background.
+#[derive(Clone, Debug)]
+pub struct Endpoints {
+    chain_id: ChainId,
+    endpoints: Vec<Endpoint>,
+    health: RwArc<Vec<EndpointHealth>>,
+    active: usize,
+}
+
+impl Endpoints {
+    /// The active node is initially the node with the highest priority.
+    pub fn from_config(config: &ChainConfig) -> Result<Self, Error> {
+        let endpoints: Vec<_> = config
+            .endpoints()
+            .into_iter()
+            .map(Endpoint::new)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            chain_id: config.id.clone(),
+            health: RwArc::new_lock(vec![EndpointHealth::default(); endpoints.len()]),
+            endpoints,
+            active: 0,
+        })
+    }
+
+    /// The node to which the queries and transactions are sent.
+    pub fn active(&self) -> &Endpoint {
+        &self.endpoints[self.active]
+    }
+
+    /// Spawns a task on `rt` which probes all the nodes concurrently every `interval`, and
+    /// updates their health. The task stops once these endpoints are dropped.
+    pub fn spawn_probes(&self, rt: &TokioRuntime, timeout: Duration, interval: Duration) {
+        let chain_id = self.chain_id.clone();
+        let endpoints = self.endpoints.clone();
+        let health = Arc::downgrade(&self.health);
+
+        rt.spawn(async move {
+            loop {
+                let results = join_all(endpoints.iter().map(|e| e.probe(timeout))).await;
+
+                match health.upgrade() {
+                    Some(health) => {
+                        update_health(&chain_id, &endpoints, &mut health.acquire_write(), results)
+                    }
+                    None => break,
+                }
+
+                tokio::time::sleep(interval).await;
+            }
+        });
+    }
+
+    /// Records the failure of a request to the active node, which is then considered
+    /// unhealthy until it answers a probe again.
+    pub fn report_failure(&self) {
+        let endpoint = self.active();
+        let mut health = self.health.acquire_write();
+        let health = &mut health[self.active];
+        health.errors += 1;
+
+        warn!(
+            chain = %self.chain_id,
+            endpoint = %endpoint.config.rpc_addr,
+            "request to the active node failed ({} failures in a row)",
+            health.errors,
+        );
+
+        telemetry!(
+            endpoint_error,
+            &self.chain_id,
+            &endpoint.config.rpc_addr.to_string()
+        );
+    }
+
+    #[cfg(test)]
+    fn update_health(&self, results: Vec<Result<(u64, Duration), String>>) {
+        update_health(
+            &self.chain_id,
+            &self.endpoints,
+            &mut self.health.acquire_write(),
+            results,
+        );
+    }
+
+    /// Fails over to the healthy node with the highest priority, and among those the
+    /// lowest latency, if the active node is unhealthy or if a healthy node has a higher
+    /// priority than the active node.
+    ///
+    /// Returns whether the active node changed.
+    pub fn select(&mut self) -> bool {
+        let health = self.health.acquire_read().clone();
+
+        let best = self
+            .endpoints
+            .iter()
+            .zip(&health)
+            .enumerate()
+            .filter(|(_, (_, h))| h.is_healthy())
+            .min_by_key(|(_, (e, h))| (e.config.priority, h.latency.unwrap_or(Duration::MAX)))
+            .map(|(index, _)| index);
+
+        let best = match best {
+            Some(best) if best != self.active => best,
+            // Keep the active node if no node is healthy, in the hope it recovers.
+            _ => return false,
+        };
+
+        let active = self.active();
+
+        // Do not switch away from a healthy node for one with the same priority,
+        // so that the relayer does not flip between nodes with similar latencies.
+        if health[self.active].is_healthy()
+            && self.endpoints[best].config.priority >= active.config.priority
+        {
+            return false;
+        }
+
+        info!(
+            chain = %self.chain_id,
+            "failing over from node {} to node {}",
+            active.config.rpc_addr,
+            self.endpoints[best].config.rpc_addr
+        );
+
+        self.active = best;
+
+        telemetry!(
+            endpoint_switch,
+            &self.chain_id,
+            &self.active().config.rpc_addr.to_string()
+        );
+
+        true
+    }
+}
+
+/// Updates the health of the `endpoints` with the `results` of their probes.
+fn update_health(
+    chain_id: &ChainId,
+    endpoints: &[Endpoint],
+    health: &mut [EndpointHealth],
+    results: Vec<Result<(u64, Duration), String>>,
+) {
+    for ((endpoint, health), result) in endpoints.iter().zip(health.iter_mut()).zip(results) {
+        match result {
+            Ok((height, latency)) => {
+                health.errors = 0;
+                health.latency = Some(latency);
+                health.latest_height = Some(height);
+
+                telemetry!(
+                    endpoint_latency,
+                    chain_id,
+                    &endpoint.config.rpc_addr.to_string(),
+                    latency
+                );
+                telemetry!(
+                    endpoint_height,
+                    chain_id,
+                    &endpoint.config.rpc_addr.to_string(),
+                    height
+                );
+            }
+            Err(reason) => {
+                health.errors += 1;
+
+                warn!(
+                    chain = %chain_id,
+                    endpoint = %endpoint.config.rpc_addr,
+                    "health probe failed ({} in a row): {}",
+                    health.errors,
+                    reason
+                );
+
+                telemetry!(
+                    endpoint_error,
+                    chain_id,
+                    &endpoint.config.rpc_addr.to_string()
+                );
+            }
+        }
+    }
+
+    let max_height = health.iter().filter_map(|h| h.latest_height).max();
+
+    if let Some(max_height) = max_height {
+        for health in health.iter_mut() {
+            health.lagging = health
+                .latest_height
+                .map_or(false, |height| height + MAX_HEIGHT_LAG < max_height);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Endpoints, MAX_HEIGHT_LAG};
+
+    use core::time::Duration;
+
+    use crate::config::{load, EndpointConfig};
+    use test_log::test;
+
+    fn endpoints() -> Endpoints {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let mut config = load(path).expect("could not parse config").chains[0].clone();
+
+        config.endpoints = vec![
+            EndpointConfig {
+                rpc_addr: "http://127.0.0.1:26757".parse().unwrap(),
+                websocket_addr: "ws://127.0.0.1:26757/websocket".parse().unwrap(),
+                grpc_addr: "http://127.0.0.1:9190".parse().unwrap(),
+                priority: 1,
+            },
+            EndpointConfig {
+                rpc_addr: "http://127.0.0.1:26857".parse().unwrap(),
+                websocket_addr: "ws://127.0.0.1:26857/websocket".parse().unwrap(),
+                grpc_addr: "http://127.0.0.1:9290".parse().unwrap(),
+                priority: 1,
+            },
+        ];
+
+        Endpoints::from_config(&config).unwrap()
+    }
+
+    fn active_port(endpoints: &Endpoints) -> u16 {
+        endpoints.active().config.rpc_addr.port()
+    }
+
+    #[test]
+    fn fail_over() {
+        let mut endpoints = endpoints();
+        let ms = Duration::from_millis;
+
+        endpoints.update_health(vec![
+            Ok((100, ms(10))),
+            Ok((100, ms(30))),
+            Ok((100, ms(20))),
+        ]);
+        assert!(!endpoints.select());
+        assert_eq!(active_port(&endpoints), 26657);
+
+        // The primary node is unreachable, fail over to the fastest backup node.
+        endpoints.update_health(vec![
+            Err("unreachable".to_string()),
+            Ok((101, ms(30))),
+            Ok((101, ms(20))),
+        ]);
+        assert!(endpoints.select());
+        assert_eq!(active_port(&endpoints), 26857);
+
+        // A healthy node with the same priority is not switched away from.
+        endpoints.update_health(vec![
+            Err("unreachable".to_string()),
+            Ok((102, ms(10))),
+            Ok((102, ms(20))),
+        ]);
+        assert!(!endpoints.select());
+        assert_eq!(active_port(&endpoints), 26857);
+
+        // The active node lags behind.
+        endpoints.update_health(vec![
+            Err("unreachable".to_string()),
+            Ok((103 + MAX_HEIGHT_LAG, ms(10))),
+            Ok((102, ms(20))),
+        ]);
+        assert!(endpoints.select());
+        assert_eq!(active_port(&endpoints), 26757);
+
+        // Switch back to the primary node once it recovers.
+        endpoints.update_health(vec![
+            Ok((110, ms(50))),
+            Ok((110, ms(10))),
+            Ok((110, ms(20))),
+        ]);
+        assert!(endpoints.select());
+        assert_eq!(active_port(&endpoints), 26657);
+
+        // A request to the active node fails before the next probes.
+        endpoints.report_failure();
+        assert!(endpoints.select());
+        assert_eq!(active_port(&endpoints), 26757);
+    }
+}
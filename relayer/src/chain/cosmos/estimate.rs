@@ -22,20 +22,11 @@ pub async fn estimate_tx_fees(
     messages: Vec<Any>,
 ) -> Result<Fee, Error> {
     let gas_config = &config.gas_config;
+    let max_fee = gas_config.max_fee();
 
-    debug!(
-        "max fee, for use in tx simulation: {}",
-        PrettyFee(&gas_config.max_fee)
-    );
+    debug!("max fee, for use in tx simulation: {}", PrettyFee(&max_fee));
 
-    let signed_tx = sign_tx(
-        config,
-        key_entry,
-        account,
-        tx_memo,
-        messages,
-        &gas_config.max_fee,
-    )?;
+    let signed_tx = sign_tx(config, key_entry, account, tx_memo, messages, &max_fee)?;
 
     let tx = Tx {
         body: Some(signed_tx.body),
@@ -17,8 +17,8 @@ pub fn gas_amount_to_fee(config: &GasConfig, gas_amount: u64) -> Fee {
         gas_amount,
     });
 
-    // The fee in coins based on gas amount
-    let amount = calculate_fee(adjusted_gas_limit, &config.gas_price);
+    // The fee in coins based on gas amount, at the current gas price
+    let amount = calculate_fee(adjusted_gas_limit, &config.gas_price.current());
 
     Fee {
         amount: vec![amount],
@@ -2,6 +2,7 @@ use http::uri::Uri;
 use ibc::core::ics04_channel::packet::Sequence;
 use ibc::core::ics23_commitment::merkle::{convert_tm_to_ics_merkle_proof, MerkleProof};
 use ibc::core::ics24_host::identifier::ChainId;
+use ibc::events::WithBlockDataType;
 use ibc_proto::cosmos::base::tendermint::v1beta1::service_client::ServiceClient;
 use ibc_proto::cosmos::base::tendermint::v1beta1::GetNodeInfoRequest;
 use tendermint::abci::Path as TendermintABCIPath;
@@ -10,12 +11,15 @@ use tendermint_rpc::query::Query;
 use tendermint_rpc::{Client, HttpClient, Url};
 
 use crate::chain::cosmos::version::Specs;
-use crate::chain::requests::{QueryClientEventRequest, QueryPacketEventDataRequest, QueryTxHash};
+use crate::chain::requests::{
+    QueryClientEventRequest, QueryPacketEventDataRequest, QueryPacketEventRangeRequest, QueryTxHash,
+};
 use crate::error::Error;
 
 pub mod account;
 pub mod balance;
 pub mod denom_trace;
+pub mod gas_price;
 pub mod status;
 pub mod tx;
 
@@ -50,6 +54,24 @@ pub fn packet_query(request: &QueryPacketEventDataRequest, seq: Sequence) -> Que
     )
 }
 
+pub fn packet_range_query(request: &QueryPacketEventRangeRequest) -> Query {
+    let (port_key, channel_key) = match request.event_id {
+        WithBlockDataType::WriteAck => ("packet_dst_port", "packet_dst_channel"),
+        _ => ("packet_src_port", "packet_src_channel"),
+    };
+
+    Query::eq(
+        format!("{}.{}", request.event_id.as_str(), channel_key),
+        request.channel_id.to_string(),
+    )
+    .and_eq(
+        format!("{}.{}", request.event_id.as_str(), port_key),
+        request.port_id.to_string(),
+    )
+    .and_gt("tx.height", request.from_height.revision_height())
+    .and_lte("tx.height", request.to_height.revision_height())
+}
+
 pub fn header_query(request: &QueryClientEventRequest) -> Query {
     Query::eq(
         format!("{}.client_id", request.event_id.as_str()),
@@ -0,0 +1,148 @@
+use http::uri::Uri;
+use tracing::{debug, warn};
+
+use ibc_proto::cosmos::base::node::v1beta1::{service_client::ServiceClient, ConfigRequest};
+use ibc_proto::cosmos::base::v1beta1::DecCoin;
+use ibc_proto::feemarket::feemarket::v1::{query_client::QueryClient, GasPriceRequest};
+
+use crate::chain::cosmos::types::gas::DynamicGasPrice;
+use crate::config::GasPriceSource;
+use crate::error::Error;
+
+/// The scale of the integer representation of the `sdk.Dec` amounts, which have 18 decimals.
+const DEC_SCALE: f64 = 1e18;
+
+/// Queries the gas price from its configured source, if it is due to be refreshed.
+///
+/// If the query fails, the previous price is kept until the next refresh.
+pub async fn refresh_gas_price(grpc_address: &Uri, gas_price: &DynamicGasPrice) {
+    if !gas_price.needs_refresh() {
+        return;
+    }
+
+    let result = match gas_price.source() {
+        Some(GasPriceSource::MinGasPrice) => {
+            query_min_gas_price(grpc_address, gas_price.denom()).await
+        }
+        Some(GasPriceSource::FeeMarket) => {
+            query_fee_market_gas_price(grpc_address, gas_price.denom()).await
+        }
+        None | Some(GasPriceSource::Static) => return,
+    };
+
+    match result {
+        Ok(price) => {
+            debug!("refreshed the gas price: {} {}", price, gas_price.denom());
+            gas_price.refreshed(Some(price));
+        }
+        Err(e) => {
+            warn!(
+                "failed to refresh the gas price, keeping the previous price: {}",
+                e
+            );
+            gas_price.refreshed(None);
+        }
+    }
+}
+
+/// Uses the GRPC client to retrieve the minimum gas price of the full node in the given denom
+pub async fn query_min_gas_price(grpc_address: &Uri, denom: &str) -> Result<f64, Error> {
+    let mut client = ServiceClient::connect(grpc_address.clone())
+        .await
+        .map_err(Error::grpc_transport)?;
+
+    let request = tonic::Request::new(ConfigRequest {});
+
+    let response = client
+        .config(request)
+        .await
+        .map(|r| r.into_inner())
+        .map_err(Error::grpc_status)?;
+
+    parse_min_gas_price(&response.minimum_gas_price, denom).ok_or_else(|| {
+        Error::invalid_gas_price_response(denom.to_string(), response.minimum_gas_price)
+    })
+}
+
+/// Uses the GRPC client to retrieve the gas price of the x/feemarket module in the given denom
+pub async fn query_fee_market_gas_price(grpc_address: &Uri, denom: &str) -> Result<f64, Error> {
+    let mut client = QueryClient::connect(grpc_address.clone())
+        .await
+        .map_err(Error::grpc_transport)?;
+
+    let request = tonic::Request::new(GasPriceRequest {
+        denom: denom.to_string(),
+    });
+
+    let response = client
+        .gas_price(request)
+        .await
+        .map(|r| r.into_inner())
+        .map_err(Error::grpc_status)?;
+
+    response
+        .price
+        .as_ref()
+        .filter(|price| price.denom == denom)
+        .and_then(parse_dec_coin)
+        .ok_or_else(|| {
+            Error::invalid_gas_price_response(denom.to_string(), format!("{:?}", response))
+        })
+}
+
+/// Parses the price in the given denom out of the minimum gas prices of a node,
+/// e.g. `0.025uatom,0.1stake`.
+fn parse_min_gas_price(min_gas_prices: &str, denom: &str) -> Option<f64> {
+    min_gas_prices.split(',').find_map(|coin| {
+        let coin = coin.trim();
+        let split = coin.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let (amount, coin_denom) = coin.split_at(split);
+
+        if coin_denom == denom {
+            amount.parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+/// The amount of a `DecCoin` is encoded as an integer with 18 decimals in protobuf,
+/// but some chains encode it as a decimal number.
+fn parse_dec_coin(coin: &DecCoin) -> Option<f64> {
+    let amount: f64 = coin.amount.parse().ok()?;
+
+    if coin.amount.contains('.') {
+        Some(amount)
+    } else {
+        Some(amount / DEC_SCALE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_dec_coin, parse_min_gas_price};
+
+    use ibc_proto::cosmos::base::v1beta1::DecCoin;
+
+    #[test]
+    fn min_gas_price() {
+        let prices = "0.025000000000000000uatom, 0.1stake";
+
+        assert_eq!(parse_min_gas_price(prices, "uatom"), Some(0.025));
+        assert_eq!(parse_min_gas_price(prices, "stake"), Some(0.1));
+        assert_eq!(parse_min_gas_price(prices, "uosmo"), None);
+        assert_eq!(parse_min_gas_price("", "stake"), None);
+    }
+
+    #[test]
+    fn dec_coin() {
+        let coin = |amount: &str| DecCoin {
+            denom: "stake".to_string(),
+            amount: amount.to_string(),
+        };
+
+        assert_eq!(parse_dec_coin(&coin("25000000000000000")), Some(0.025));
+        assert_eq!(parse_dec_coin(&coin("0.025")), Some(0.025));
+        assert_eq!(parse_dec_coin(&coin("invalid")), None);
+    }
+}
@@ -9,16 +9,23 @@ use tendermint::abci::Event;
 use tendermint_rpc::endpoint::tx::Response as TxResponse;
 use tendermint_rpc::{Client, HttpClient, Order, Url};
 
-use crate::chain::cosmos::query::{header_query, packet_query, tx_hash_query};
+use crate::chain::cosmos::query::{header_query, packet_query, packet_range_query, tx_hash_query};
 use crate::chain::requests::{
-    QueryClientEventRequest, QueryHeight, QueryPacketEventDataRequest, QueryTxHash, QueryTxRequest,
+    QueryClientEventRequest, QueryHeight, QueryPacketEventDataRequest,
+    QueryPacketEventRangeRequest, QueryTxHash, QueryTxRequest,
 };
 use crate::error::Error;
 
+/// The number of transactions fetched per page when searching for all the transactions
+/// matching a query.
+const TX_SEARCH_PAGE_SIZE: u8 = 100;
+
 /// This function queries transactions for events matching certain criteria.
 /// 1. Client Update request - returns a vector with at most one update client event
 /// 2. Packet event request - returns at most one packet event for each sequence specified
 ///    in the request.
+/// 3. Packet event range request - returns all the packet events of a channel end committed
+///    in a range of heights.
 ///    Note - there is no way to format the packet query such that it asks for Tx-es with either
 ///    sequence (the query conditions can only be AND-ed).
 ///    There is a possibility to include "<=" and ">=" conditions but it doesn't work with
@@ -75,6 +82,38 @@ pub async fn query_txs(
             Ok(result)
         }
 
+        QueryTxRequest::PacketRange(request) => {
+            crate::time!("query_txs: query packet events in a range of heights");
+
+            let mut result: Vec<IbcEvent> = vec![];
+
+            for page in 1.. {
+                let response = rpc_client
+                    .tx_search(
+                        packet_range_query(&request),
+                        false,
+                        page,
+                        TX_SEARCH_PAGE_SIZE,
+                        Order::Ascending,
+                    )
+                    .await
+                    .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
+
+                let is_last_page = response.txs.len() < TX_SEARCH_PAGE_SIZE as usize
+                    || page * u32::from(TX_SEARCH_PAGE_SIZE) >= response.total_count;
+
+                for tx in response.txs {
+                    result.extend(packets_from_tx_search_response(chain_id, &request, tx)?);
+                }
+
+                if is_last_page {
+                    break;
+                }
+            }
+
+            Ok(result)
+        }
+
         QueryTxRequest::Client(request) => {
             crate::time!("query_txs: single client update event");
 
@@ -204,6 +243,46 @@ fn packet_from_tx_search_response(
         .find_map(|ev| filter_matching_event(ev, request, seq)))
 }
 
+// Extract from the Tx all the packet events matching the range query, in the order in which
+// they were emitted. Failed transactions do not emit any packet event.
+fn packets_from_tx_search_response(
+    chain_id: &ChainId,
+    request: &QueryPacketEventRangeRequest,
+    response: TxResponse,
+) -> Result<Vec<IbcEvent>, Error> {
+    let height = ICSHeight::new(chain_id.version(), u64::from(response.height))
+        .map_err(|_| Error::invalid_height_no_source())?;
+
+    if response.tx_result.code.is_err() {
+        return Ok(vec![]);
+    }
+
+    Ok(response
+        .tx_result
+        .events
+        .iter()
+        .filter(|event| event.type_str == request.event_id.as_str())
+        .filter_map(ChannelEvents::try_from_tx)
+        .filter_map(|mut event| {
+            let packet = event.packet()?;
+            let (port_id, channel_id) = match event {
+                IbcEvent::SendPacket(_) => (&packet.source_port, &packet.source_channel),
+                IbcEvent::WriteAcknowledgement(_) => {
+                    (&packet.destination_port, &packet.destination_channel)
+                }
+                _ => return None,
+            };
+
+            if port_id != &request.port_id || channel_id != &request.channel_id {
+                return None;
+            }
+
+            event.set_height(height);
+            Some(event)
+        })
+        .collect())
+}
+
 fn filter_matching_event(
     event: Event,
     request: &QueryPacketEventDataRequest,
@@ -11,10 +11,11 @@ use crate::chain::cosmos::query::account::refresh_account;
 use crate::chain::cosmos::tx::estimate_fee_and_send_tx;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
+use crate::chain::cosmos::types::gas::DynamicGasPrice;
 use crate::config::types::Memo;
 use crate::error::Error;
 use crate::keyring::KeyEntry;
-use crate::sdk_error::sdk_error_from_tx_sync_error_code;
+use crate::sdk_error::{sdk_error_from_tx_sync_error_code, SdkErrorDetail};
 use crate::telemetry;
 
 // Maximum number of retries for send_tx in the case of
@@ -29,6 +30,10 @@ const BACKOFF_MULTIPLIER_ACCOUNT_SEQUENCE_RETRY: u64 = 300;
 // https://github.com/cosmos/cosmos-sdk/blob/v0.44.0/types/errors/errors.go#L115-L117
 const INCORRECT_ACCOUNT_SEQUENCE_ERR: u32 = 32;
 
+// Maximum number of retries for send_tx in the case of an insufficient fee
+// at broadcast step, each retry raising the gas price when it is dynamic.
+const MAX_INSUFFICIENT_FEE_RETRY: u64 = 3;
+
 /// Try to `send_tx` with retry on account sequence error.
 /// An account sequence error can occur if the account sequence that
 /// the relayer caches becomes outdated. This may happen if the relayer
@@ -55,20 +60,51 @@ pub async fn send_tx_with_account_sequence_retry(
 ) -> Result<Response, Error> {
     crate::time!("send_tx_with_account_sequence_retry");
 
-    let _span =
-        span!(Level::ERROR, "send_tx_with_account_sequence_retry", id = %config.chain_id).entered();
+    let _span = span!(
+        Level::ERROR,
+        "send_tx_with_account_sequence_retry",
+        id = %config.chain_id,
+        account = %key_entry.account
+    )
+    .entered();
 
     telemetry!(msg_num, &config.chain_id, messages.len() as u64);
 
-    do_send_tx_with_account_sequence_retry(
-        config,
-        key_entry,
-        account,
-        tx_memo,
-        messages,
-        retry_counter,
-    )
-    .await
+    let retries = RetryCounters {
+        account_sequence: retry_counter,
+        insufficient_fee: 0,
+    };
+
+    do_send_tx_with_account_sequence_retry(config, key_entry, account, tx_memo, messages, retries)
+        .await
+}
+
+/// How many times a transaction was retried after each kind of retry-able broadcast error.
+/// The errors are counted separately, so that the retries after one kind of error do not
+/// use up the retries after the other.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+struct RetryCounters {
+    account_sequence: u64,
+    insufficient_fee: u64,
+}
+
+impl RetryCounters {
+    /// Counts a retry after an account sequence mismatch, unless the retries are exhausted.
+    fn retry_account_sequence(self) -> Option<Self> {
+        (self.account_sequence < MAX_ACCOUNT_SEQUENCE_RETRY).then(|| Self {
+            account_sequence: self.account_sequence + 1,
+            ..self
+        })
+    }
+
+    /// Raises the gas price and counts a retry after an insufficient fee error, unless
+    /// the retries are exhausted or the price cannot be raised.
+    fn retry_insufficient_fee(self, gas_price: &DynamicGasPrice) -> Option<Self> {
+        (self.insufficient_fee < MAX_INSUFFICIENT_FEE_RETRY && gas_price.raise()).then(|| Self {
+            insufficient_fee: self.insufficient_fee + 1,
+            ..self
+        })
+    }
 }
 
 // We have to do explicit return of `Box<dyn Future>` because Rust
@@ -80,7 +116,7 @@ fn do_send_tx_with_account_sequence_retry<'a>(
     account: &'a mut Account,
     tx_memo: &'a Memo,
     messages: Vec<Any>,
-    retry_counter: u64,
+    retries: RetryCounters,
 ) -> Pin<Box<dyn Future<Output = Result<Response, Error>> + 'a>> {
     Box::pin(async move {
         debug!(
@@ -107,24 +143,19 @@ fn do_send_tx_with_account_sequence_retry<'a>(
 
             // Gas estimation succeeded. Broadcasting failed with a retry-able error.
             Ok(response) if response.code == Code::Err(INCORRECT_ACCOUNT_SEQUENCE_ERR) => {
-                if retry_counter < MAX_ACCOUNT_SEQUENCE_RETRY {
-                    let retry_counter = retry_counter + 1;
+                if let Some(retries) = retries.retry_account_sequence() {
                     warn!("failed at broadcast step with incorrect account sequence. retrying ({}/{})",
-                        retry_counter, MAX_ACCOUNT_SEQUENCE_RETRY);
+                        retries.account_sequence, MAX_ACCOUNT_SEQUENCE_RETRY);
                     // Backoff & re-fetch the account s.n.
-                    let backoff = retry_counter * BACKOFF_MULTIPLIER_ACCOUNT_SEQUENCE_RETRY;
+                    let backoff =
+                        retries.account_sequence * BACKOFF_MULTIPLIER_ACCOUNT_SEQUENCE_RETRY;
 
                     thread::sleep(Duration::from_millis(backoff));
                     refresh_account(&config.grpc_address, &key_entry.account, account).await?;
 
                     // Now retry.
                     do_send_tx_with_account_sequence_retry(
-                        config,
-                        key_entry,
-                        account,
-                        tx_memo,
-                        messages,
-                        retry_counter + 1,
+                        config, key_entry, account, tx_memo, messages, retries,
                     )
                     .await
                 } else {
@@ -137,6 +168,33 @@ fn do_send_tx_with_account_sequence_retry<'a>(
                 }
             }
 
+            // Gas estimation succeeded. Broadcasting failed because the fee is lower than the
+            // minimum fee of the node, retry with a higher gas price if it can be raised.
+            Ok(response) if is_insufficient_fee(response.code) => {
+                if let Some(retries) = retries.retry_insufficient_fee(&config.gas_config.gas_price)
+                {
+                    warn!(
+                        "failed at broadcast step with insufficient fee. retrying with gas price {} ({}/{})",
+                        config.gas_config.gas_price.current(),
+                        retries.insufficient_fee,
+                        MAX_INSUFFICIENT_FEE_RETRY
+                    );
+
+                    do_send_tx_with_account_sequence_retry(
+                        config, key_entry, account, tx_memo, messages, retries,
+                    )
+                    .await
+                } else {
+                    // As for the account sequence errors, let the caller handle the response.
+                    error!(
+                        "broadcast_tx_sync: {:?}: failed due to an insufficient fee at gas price {}",
+                        response,
+                        config.gas_config.gas_price.current()
+                    );
+                    Ok(response)
+                }
+            }
+
             // Catch-all arm for the Ok variant.
             // This is the case when gas estimation succeeded.
             Ok(response) => {
@@ -146,6 +204,7 @@ fn do_send_tx_with_account_sequence_retry<'a>(
                         debug!("broadcast_tx_sync: {:?}", response);
 
                         account.sequence.increment_mut();
+                        config.gas_config.gas_price.accepted();
                         Ok(response)
                     }
 
@@ -182,3 +241,147 @@ fn mismatch_account_sequence_number_error_requires_refresh(e: &Error) -> bool {
         _ => false,
     }
 }
+
+/// Determine whether the given code of a `broadcast_tx_sync` response indicates
+/// that the fee of the transaction is lower than the minimum fee of the node.
+fn is_insufficient_fee(code: Code) -> bool {
+    match code {
+        Code::Ok => false,
+        Code::Err(code) => matches!(
+            sdk_error_from_tx_sync_error_code(code).detail(),
+            SdkErrorDetail::InsufficientFee(_)
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tendermint::abci::Code;
+
+    use super::{
+        is_insufficient_fee, RetryCounters, INCORRECT_ACCOUNT_SEQUENCE_ERR,
+        MAX_ACCOUNT_SEQUENCE_RETRY, MAX_INSUFFICIENT_FEE_RETRY,
+    };
+    use crate::chain::cosmos::types::gas::{DynamicGasPrice, LOWER_AFTER_ACCEPTED_TXS};
+    use crate::config::{DynamicGasPriceConfig, GasPrice};
+
+    // The error "insufficient fee" is defined as the error code 13 in cosmos-sdk.
+    const INSUFFICIENT_FEE_ERR: u32 = 13;
+
+    #[test]
+    fn insufficient_fee_code() {
+        assert!(is_insufficient_fee(Code::Err(INSUFFICIENT_FEE_ERR)));
+        assert!(!is_insufficient_fee(Code::Err(
+            INCORRECT_ACCOUNT_SEQUENCE_ERR
+        )));
+        assert!(!is_insufficient_fee(Code::Ok));
+    }
+
+    fn dynamic_gas_price() -> DynamicGasPrice {
+        let config = DynamicGasPriceConfig {
+            enabled: true,
+            multiplier: 1.0,
+            insufficient_fee_multiplier: 2.0,
+            max_price: Some(1.0),
+            ..Default::default()
+        };
+
+        DynamicGasPrice::new(GasPrice::new(0.001, "stake".to_string()), config)
+    }
+
+    #[test]
+    fn separate_retry_counters() {
+        let gas_price = dynamic_gas_price();
+
+        // The account sequence retries are used up first ...
+        let mut retries = RetryCounters::default();
+        for _ in 0..MAX_ACCOUNT_SEQUENCE_RETRY {
+            retries = retries.retry_account_sequence().unwrap();
+        }
+        assert_eq!(retries.retry_account_sequence(), None);
+
+        // ... which leaves all the insufficient fee retries.
+        for _ in 0..MAX_INSUFFICIENT_FEE_RETRY {
+            retries = retries.retry_insufficient_fee(&gas_price).unwrap();
+        }
+        assert_eq!(retries.retry_insufficient_fee(&gas_price), None);
+        assert_eq!(
+            retries,
+            RetryCounters {
+                account_sequence: MAX_ACCOUNT_SEQUENCE_RETRY,
+                insufficient_fee: MAX_INSUFFICIENT_FEE_RETRY,
+            }
+        );
+
+        // Conversely, the insufficient fee retries leave all the account sequence retries.
+        let mut retries = RetryCounters::default();
+        for _ in 0..MAX_INSUFFICIENT_FEE_RETRY {
+            retries = retries.retry_insufficient_fee(&gas_price).unwrap();
+        }
+        assert!(retries.retry_account_sequence().is_some());
+
+        // With the errors interleaved, each kind of error is retried up to its own maximum.
+        let mut retries = RetryCounters::default();
+        retries = retries.retry_insufficient_fee(&gas_price).unwrap();
+        retries = retries.retry_account_sequence().unwrap();
+        retries = retries.retry_insufficient_fee(&gas_price).unwrap();
+        assert_eq!(retries.retry_account_sequence(), None);
+        assert!(retries.retry_insufficient_fee(&gas_price).is_some());
+    }
+
+    #[test]
+    fn insufficient_fee_retry_without_dynamic_gas_price() {
+        let gas_price = DynamicGasPrice::new(
+            GasPrice::new(0.001, "stake".to_string()),
+            DynamicGasPriceConfig::default(),
+        );
+
+        // The transaction is not retried if the price cannot be raised.
+        assert_eq!(
+            RetryCounters::default().retry_insufficient_fee(&gas_price),
+            None
+        );
+    }
+
+    #[test]
+    fn gas_price_across_retries() {
+        let gas_price = dynamic_gas_price();
+        let price = || gas_price.current().price;
+
+        // A transaction rejected for an insufficient fee is retried at a raised price,
+        // up to the maximum number of retries.
+        let mut retries = RetryCounters::default();
+        for _ in 0..MAX_INSUFFICIENT_FEE_RETRY {
+            retries = retries.retry_insufficient_fee(&gas_price).unwrap();
+        }
+        assert_eq!(retries.retry_insufficient_fee(&gas_price), None);
+        assert_eq!(price(), 0.008);
+
+        // The transactions accepted at the raised price do not lower it right away,
+        // which would get the next transactions rejected again.
+        for _ in 1..LOWER_AFTER_ACCEPTED_TXS {
+            gas_price.accepted();
+        }
+        assert_eq!(price(), 0.008);
+
+        // Another rejection restarts the count of the accepted transactions.
+        assert!(RetryCounters::default()
+            .retry_insufficient_fee(&gas_price)
+            .is_some());
+        assert_eq!(price(), 0.016);
+
+        for _ in 1..LOWER_AFTER_ACCEPTED_TXS {
+            gas_price.accepted();
+        }
+        assert_eq!(price(), 0.016);
+
+        gas_price.accepted();
+        assert_eq!(price(), 0.008);
+
+        // The price is lowered back one step at a time, down to the base price.
+        for _ in 0..3 * LOWER_AFTER_ACCEPTED_TXS {
+            gas_price.accepted();
+        }
+        assert_eq!(price(), 0.001);
+    }
+}
@@ -0,0 +1,132 @@
+use alloc::sync::Arc;
+
+use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
+use tokio::runtime::Runtime as TokioRuntime;
+use tracing::{span, Level};
+
+use ibc::events::IbcEvent;
+
+use crate::chain::cosmos::batch::{
+    send_batched_messages_and_wait_check_tx, send_batched_messages_and_wait_commit,
+};
+use crate::chain::cosmos::query::account::get_or_fetch_account;
+use crate::chain::cosmos::query::gas_price::refresh_gas_price;
+use crate::chain::cosmos::types::account::Account;
+use crate::chain::cosmos::types::config::TxConfig;
+use crate::chain::tracking::TrackedMsgs;
+use crate::chain::tx_sender::TxSender;
+use crate::config::types::{MaxMsgNum, MaxTxSize, Memo};
+use crate::config::ChainConfig;
+use crate::error::Error;
+use crate::keyring::KeyEntry;
+
+/// Submits the transactions signed with one key of a Cosmos chain,
+/// keeping its own cached copy of the key's account and its sequence number.
+pub struct CosmosTxSender {
+    rt: Arc<TokioRuntime>,
+    tx_config: TxConfig,
+    max_msg_num: MaxMsgNum,
+    max_tx_size: MaxTxSize,
+    memo_prefix: Memo,
+    key_entry: KeyEntry,
+    account: Option<Account>,
+}
+
+impl CosmosTxSender {
+    pub fn new(
+        rt: Arc<TokioRuntime>,
+        tx_config: TxConfig,
+        config: &ChainConfig,
+        key_entry: KeyEntry,
+    ) -> Self {
+        Self {
+            rt,
+            tx_config,
+            max_msg_num: config.max_msg_num,
+            max_tx_size: config.max_tx_size,
+            memo_prefix: config.memo_prefix.clone(),
+            key_entry,
+            account: None,
+        }
+    }
+
+    async fn do_send_messages_and_wait_commit(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        crate::time!("send_messages_and_wait_commit");
+
+        let _span =
+            span!(Level::DEBUG, "send_tx_commit", id = %tracked_msgs.tracking_id()).entered();
+
+        let proto_msgs = tracked_msgs.msgs;
+
+        let grpc_addr = &self.tx_config.grpc_address;
+
+        refresh_gas_price(grpc_addr, &self.tx_config.gas_config.gas_price).await;
+
+        let account =
+            get_or_fetch_account(grpc_addr, &self.key_entry.account, &mut self.account).await?;
+
+        send_batched_messages_and_wait_commit(
+            &self.tx_config,
+            self.max_msg_num,
+            self.max_tx_size,
+            &self.key_entry,
+            account,
+            &self.memo_prefix,
+            proto_msgs,
+        )
+        .await
+    }
+
+    async fn do_send_messages_and_wait_check_tx(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<Response>, Error> {
+        crate::time!("send_messages_and_wait_check_tx");
+
+        let span = span!(Level::DEBUG, "send_tx_check", id = %tracked_msgs.tracking_id());
+        let _enter = span.enter();
+
+        let proto_msgs = tracked_msgs.msgs;
+
+        let grpc_addr = &self.tx_config.grpc_address;
+
+        refresh_gas_price(grpc_addr, &self.tx_config.gas_config.gas_price).await;
+
+        let account =
+            get_or_fetch_account(grpc_addr, &self.key_entry.account, &mut self.account).await?;
+
+        send_batched_messages_and_wait_check_tx(
+            &self.tx_config,
+            self.max_msg_num,
+            self.max_tx_size,
+            &self.key_entry,
+            account,
+            &self.memo_prefix,
+            proto_msgs,
+        )
+        .await
+    }
+}
+
+impl TxSender for CosmosTxSender {
+    fn send_messages_and_wait_commit(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        let runtime = self.rt.clone();
+
+        runtime.block_on(self.do_send_messages_and_wait_commit(tracked_msgs))
+    }
+
+    fn send_messages_and_wait_check_tx(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<Response>, Error> {
+        let runtime = self.rt.clone();
+
+        runtime.block_on(self.do_send_messages_and_wait_check_tx(tracked_msgs))
+    }
+}
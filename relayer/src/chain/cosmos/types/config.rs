@@ -17,6 +17,7 @@ pub struct TxConfig {
     pub grpc_address: Uri,
     pub rpc_timeout: Duration,
     pub address_type: AddressType,
+    pub authz_granter: Option<String>,
 }
 
 impl<'a> TryFrom<&'a ChainConfig> for TxConfig {
@@ -39,6 +40,7 @@ impl<'a> TryFrom<&'a ChainConfig> for TxConfig {
             grpc_address,
             rpc_timeout: config.rpc_timeout,
             address_type: config.address_type.clone(),
+            authz_granter: config.authz_granter.clone(),
         })
     }
 }
@@ -1,7 +1,10 @@
+use std::time::Instant;
+
 use ibc_proto::cosmos::tx::v1beta1::Fee;
 
 use crate::chain::cosmos::calculate_fee;
-use crate::config::{ChainConfig, GasPrice};
+use crate::config::{ChainConfig, DynamicGasPriceConfig, GasPrice, GasPriceSource};
+use crate::util::lock::{LockExt, RwArc};
 
 /// Default gas limit when submitting a transaction.
 const DEFAULT_MAX_GAS: u64 = 400_000;
@@ -11,24 +14,46 @@ const DEFAULT_GAS_MULTIPLIER: f64 = 1.0;
 
 const DEFAULT_FEE_GRANTER: &str = "";
 
+/// The maximum gas price, relative to the configured gas price,
+/// when dynamic pricing is enabled without a `max_price`.
+const DEFAULT_MAX_PRICE_FACTOR: f64 = 10.0;
+
+/// How many transactions must be accepted in a row, without any insufficient fee
+/// error in between, before a raised gas price is lowered back by one step.
+pub const LOWER_AFTER_ACCEPTED_TXS: usize = 10;
+
 #[derive(Debug, Clone)]
 pub struct GasConfig {
     pub default_gas: u64,
     pub max_gas: u64,
     pub gas_multiplier: f64,
-    pub gas_price: GasPrice,
-    pub max_fee: Fee,
+    pub gas_price: DynamicGasPrice,
     pub fee_granter: String,
 }
 
+impl GasConfig {
+    /// The maximum fee the relayer pays for a transaction at the current gas price,
+    /// for use in tx simulation.
+    pub fn max_fee(&self) -> Fee {
+        Fee {
+            amount: vec![calculate_fee(self.max_gas, &self.gas_price.current())],
+            gas_limit: self.max_gas,
+            payer: "".to_string(),
+            granter: self.fee_granter.clone(),
+        }
+    }
+}
+
 impl<'a> From<&'a ChainConfig> for GasConfig {
     fn from(config: &'a ChainConfig) -> Self {
         Self {
             default_gas: default_gas_from_config(config),
             max_gas: max_gas_from_config(config),
             gas_multiplier: gas_multiplier_from_config(config),
-            gas_price: config.gas_price.clone(),
-            max_fee: max_fee_from_config(config),
+            gas_price: DynamicGasPrice::new(
+                config.gas_price.clone(),
+                config.dynamic_gas_price.clone(),
+            ),
             fee_granter: fee_granter_from_config(config),
         }
     }
@@ -61,18 +86,189 @@ fn fee_granter_from_config(config: &ChainConfig) -> String {
         .to_string()
 }
 
-fn max_fee_from_config(config: &ChainConfig) -> Fee {
-    let max_gas = max_gas_from_config(config);
+/// The gas price of a chain. When dynamic pricing is enabled, the price follows the
+/// price queried from the chain, and is raised after the transactions rejected for
+/// an insufficient fee, up to the maximum price.
+///
+/// The clones of a `DynamicGasPrice` share the same price.
+#[derive(Clone, Debug)]
+pub struct DynamicGasPrice {
+    config: DynamicGasPriceConfig,
+    denom: String,
+    max_price: f64,
+    state: RwArc<GasPriceState>,
+}
+
+#[derive(Debug)]
+struct GasPriceState {
+    /// The price queried from the chain, initially the configured price
+    base_price: f64,
+    /// The factor by which the base price is raised after insufficient fee errors
+    adjustment: f64,
+    /// How many transactions were accepted since the price was last raised or lowered
+    accepted_txs: usize,
+    /// When the price was last queried from the chain
+    refreshed_at: Option<Instant>,
+}
+
+impl DynamicGasPrice {
+    pub fn new(gas_price: GasPrice, config: DynamicGasPriceConfig) -> Self {
+        let max_price = config
+            .max_price
+            .unwrap_or(gas_price.price * DEFAULT_MAX_PRICE_FACTOR);
+
+        Self {
+            config,
+            denom: gas_price.denom,
+            max_price,
+            state: <RwArc<_>>::new_lock(GasPriceState {
+                base_price: gas_price.price,
+                adjustment: 1.0,
+                accepted_txs: 0,
+                refreshed_at: None,
+            }),
+        }
+    }
+
+    /// The source to query the price from, if dynamic pricing is enabled.
+    pub fn source(&self) -> Option<GasPriceSource> {
+        self.config.enabled.then(|| self.config.source)
+    }
+
+    pub fn denom(&self) -> &str {
+        &self.denom
+    }
+
+    /// The gas price to pay for the next transaction.
+    pub fn current(&self) -> GasPrice {
+        let state = self.state.acquire_read();
+
+        let price = if self.config.enabled {
+            let price = state.base_price * self.config.multiplier * state.adjustment;
+            price.min(self.max_price)
+        } else {
+            state.base_price
+        };
+
+        GasPrice::new(price, self.denom.clone())
+    }
+
+    /// Whether the price is due to be queried from the chain.
+    pub fn needs_refresh(&self) -> bool {
+        match self.source() {
+            None | Some(GasPriceSource::Static) => false,
+            Some(_) => self
+                .state
+                .acquire_read()
+                .refreshed_at
+                .map_or(true, |at| at.elapsed() >= self.config.refresh_interval),
+        }
+    }
+
+    /// Records the price queried from the chain, if the query succeeded.
+    pub fn refreshed(&self, base_price: Option<f64>) {
+        let mut state = self.state.acquire_write();
+
+        if let Some(base_price) = base_price {
+            state.base_price = base_price;
+        }
+
+        state.refreshed_at = Some(Instant::now());
+    }
+
+    /// Raises the price after a transaction was rejected for an insufficient fee.
+    ///
+    /// Returns `false` if the price cannot be raised, because dynamic pricing
+    /// is disabled or the price already reached the maximum price.
+    pub fn raise(&self) -> bool {
+        if !self.config.enabled || self.current().price >= self.max_price {
+            return false;
+        }
+
+        let mut state = self.state.acquire_write();
+        state.adjustment *= self.config.insufficient_fee_multiplier;
+        state.accepted_txs = 0;
+
+        true
+    }
+
+    /// Records a transaction accepted at the current price. The price is lowered back by
+    /// one step once [`LOWER_AFTER_ACCEPTED_TXS`] transactions were accepted in a row, so
+    /// that a single accepted transaction does not undo a raise which other transactions
+    /// still need.
+    pub fn accepted(&self) {
+        let mut state = self.state.acquire_write();
+
+        if state.adjustment <= 1.0 {
+            return;
+        }
+
+        state.accepted_txs += 1;
+
+        if state.accepted_txs >= LOWER_AFTER_ACCEPTED_TXS {
+            state.adjustment =
+                (state.adjustment / self.config.insufficient_fee_multiplier).max(1.0);
+            state.accepted_txs = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DynamicGasPrice, LOWER_AFTER_ACCEPTED_TXS};
+
+    use crate::config::{DynamicGasPriceConfig, GasPrice};
+
+    #[test]
+    fn dynamic_gas_price() {
+        let config = DynamicGasPriceConfig {
+            enabled: true,
+            multiplier: 1.0,
+            insufficient_fee_multiplier: 2.0,
+            max_price: Some(0.01),
+            ..Default::default()
+        };
+
+        let gas_price = DynamicGasPrice::new(GasPrice::new(0.001, "stake".to_string()), config);
+        let price = || gas_price.current().price;
+
+        assert_eq!(price(), 0.001);
 
-    // The maximum fee the relayer pays for a transaction
-    let max_fee_in_coins = calculate_fee(max_gas, &config.gas_price);
+        gas_price.refreshed(Some(0.002));
+        assert_eq!(price(), 0.002);
+
+        assert!(gas_price.raise());
+        assert!(gas_price.raise());
+        assert_eq!(price(), 0.008);
+
+        // The price is bounded by the maximum price.
+        assert!(gas_price.raise());
+        assert_eq!(price(), 0.01);
+        assert!(!gas_price.raise());
+
+        // The price is only lowered after enough transactions were accepted in a row.
+        for _ in 1..LOWER_AFTER_ACCEPTED_TXS {
+            gas_price.accepted();
+        }
+        assert_eq!(price(), 0.01);
+
+        gas_price.accepted();
+        assert_eq!(price(), 0.008);
+
+        // A failed query keeps the previous price.
+        gas_price.refreshed(None);
+        assert_eq!(price(), 0.008);
+    }
 
-    let fee_granter = fee_granter_from_config(config);
+    #[test]
+    fn static_gas_price() {
+        let gas_price = DynamicGasPrice::new(
+            GasPrice::new(0.001, "stake".to_string()),
+            DynamicGasPriceConfig::default(),
+        );
 
-    Fee {
-        amount: vec![max_fee_in_coins],
-        gas_limit: max_gas,
-        payer: "".to_string(),
-        granter: fee_granter,
+        assert!(!gas_price.raise());
+        assert!(!gas_price.needs_refresh());
+        assert_eq!(gas_price.current().price, 0.001);
     }
 }
@@ -1,5 +1,6 @@
 use alloc::sync::Arc;
 use core::convert::TryFrom;
+use ibc::applications::fee::packet_fee::IdentifiedPacketFees;
 use ibc::core::ics23_commitment::merkle::MerkleProof;
 
 use tokio::runtime::Runtime as TokioRuntime;
@@ -15,6 +16,7 @@ use ibc::core::ics03_connection::connection::{ConnectionEnd, IdentifiedConnectio
 use ibc::core::ics03_connection::version::{get_compatible_versions, Version};
 use ibc::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
 use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use ibc::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
 use ibc::events::IbcEvent;
@@ -23,6 +25,7 @@ use ibc::signer::Signer;
 use ibc::timestamp::Timestamp;
 use ibc::Height as ICSHeight;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response as TxResponse;
+use tendermint_rpc::Url;
 
 use crate::account::Balance;
 use crate::chain::client::ClientSettings;
@@ -30,12 +33,15 @@ use crate::chain::requests::{
     QueryChannelClientStateRequest, QueryChannelRequest, QueryChannelsRequest,
     QueryClientConnectionsRequest, QueryClientStateRequest, QueryClientStatesRequest,
     QueryConnectionChannelsRequest, QueryConnectionRequest, QueryConnectionsRequest,
-    QueryConsensusStateRequest, QueryConsensusStatesRequest, QueryHostConsensusStateRequest,
+    QueryConsensusStateRequest, QueryConsensusStatesRequest, QueryCounterpartyPayeeRequest,
+    QueryCrossChainRequest, QueryHostConsensusStateRequest, QueryIncentivizedPacketsRequest,
     QueryNextSequenceReceiveRequest, QueryPacketAcknowledgementsRequest,
     QueryPacketCommitmentsRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
-    QueryUpgradedClientStateRequest, QueryUpgradedConsensusStateRequest,
+    QueryUpgradeErrorRequest, QueryUpgradeRequest, QueryUpgradedClientStateRequest,
+    QueryUpgradedConsensusStateRequest,
 };
 use crate::chain::tracking::TrackedMsgs;
+use crate::chain::tx_sender::TxSender;
 use crate::config::ChainConfig;
 use crate::connection::ConnectionMsgType;
 use crate::denom::DenomTrace;
@@ -101,6 +107,14 @@ pub trait ChainEndpoint: Sized {
     /// Perform a health check
     fn health_check(&self) -> Result<HealthCheck, Error>;
 
+    /// Fails over to another full node of the chain if the active one is unhealthy, based on
+    /// the health of the nodes tracked in the background. Returns the RPC address of the node
+    /// failed over to, if any, in which case the light client and the event monitor must be
+    /// re-initialized.
+    fn check_endpoints(&mut self) -> Result<Option<Url>, Error> {
+        Ok(None)
+    }
+
     /// Returns the chain's keybase
     fn keybase(&self) -> &KeyRing;
 
@@ -109,9 +123,11 @@ pub trait ChainEndpoint: Sized {
 
     /// Sends one or more transactions with `msgs` to chain and
     /// synchronously wait for it to be committed.
+    /// The transactions are signed with the given key, or with the default key if `None`.
     fn send_messages_and_wait_commit(
         &mut self,
         tracked_msgs: TrackedMsgs,
+        key_name: Option<String>,
     ) -> Result<Vec<IbcEvent>, Error>;
 
     /// Sends one or more transactions with `msgs` to chain.
@@ -119,13 +135,34 @@ pub trait ChainEndpoint: Sized {
     fn send_messages_and_wait_check_tx(
         &mut self,
         tracked_msgs: TrackedMsgs,
+        key_name: Option<String>,
     ) -> Result<Vec<TxResponse>, Error>;
 
-    fn get_signer(&mut self) -> Result<Signer, Error>;
+    /// Returns a sender of the transactions signed with the given key, which tracks the sequence
+    /// of the key's account on its own, so that the runtime can submit the transactions of the
+    /// different keys of the chain concurrently. Returns `None` if the transactions can only be
+    /// submitted through the endpoint, one at a time.
+    fn tx_sender(&mut self, _key_name: &str) -> Result<Option<Box<dyn TxSender>>, Error> {
+        Ok(None)
+    }
+
+    /// Returns the signer of the messages submitted with the given key, or with the default key
+    /// if `None`. This is the account on whose behalf the key submits them, if any, or else the
+    /// address of the key.
+    fn get_signer(&mut self, key_name: Option<String>) -> Result<Signer, Error>;
+
+    /// Returns the address of the given key, or of the default key if `None`, which signs and
+    /// pays for the transactions submitted with it.
+    fn get_key_address(&mut self, key_name: Option<String>) -> Result<Signer, Error>;
 
     fn config(&self) -> ChainConfig;
 
-    fn get_key(&mut self) -> Result<KeyEntry, Error>;
+    /// Replaces the configuration of the chain, which must have the same endpoints and
+    /// key as the current one.
+    fn update_config(&mut self, config: ChainConfig) -> Result<(), Error>;
+
+    /// Returns the given key, or the default key if `None`.
+    fn get_key(&mut self, key_name: Option<String>) -> Result<KeyEntry, Error>;
 
     fn add_key(&mut self, key_name: &str, key: KeyEntry) -> Result<(), Error>;
 
@@ -231,6 +268,49 @@ pub trait ChainEndpoint: Sized {
         include_proof: IncludeProof,
     ) -> Result<(ChannelEnd, Option<MerkleProof>), Error>;
 
+    /// Performs a query to retrieve the upgrade proposed for the channel
+    /// associated with a given channel identifier. A proof can optionally be
+    /// returned along with the result.
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error>;
+
+    /// Performs a query to retrieve the receipt of the last aborted upgrade
+    /// of the channel associated with a given channel identifier. A proof can
+    /// optionally be returned along with the result.
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error>;
+
+    /// Performs a query to retrieve the value stored at a path of any store
+    /// of the chain, along with a proof of it (or of its absence, if the value
+    /// is empty) and the height at which the proof can be verified, i.e. the
+    /// height of the block following the queried state. Used to answer ICS31
+    /// cross-chain queries.
+    fn query_cross_chain(
+        &self,
+        request: QueryCrossChainRequest,
+    ) -> Result<(Vec<u8>, MerkleProof, ICSHeight), Error>;
+
+    /// Performs a query to retrieve the ICS29 fees escrowed for the packets
+    /// sent on a channel. Packets which are not incentivized are not returned,
+    /// and no packets are returned if the chain does not support ICS29.
+    fn query_incentivized_packets(
+        &self,
+        request: QueryIncentivizedPacketsRequest,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error>;
+
+    /// Performs a query to retrieve the ICS29 counterparty payee registered
+    /// by a relayer on a channel, if any.
+    fn query_counterparty_payee(
+        &self,
+        request: QueryCounterpartyPayeeRequest,
+    ) -> Result<Option<Signer>, Error>;
+
     /// Performs a query to retrieve the client state for the channel associated
     /// with a given channel identifier.
     fn query_channel_client_state(
@@ -4,6 +4,7 @@ use core::fmt::{self, Debug};
 use crossbeam_channel as channel;
 use serde::Serialize;
 
+use ibc::applications::fee::packet_fee::IdentifiedPacketFees;
 use ibc::{
     core::{
         ics02_client::{
@@ -20,6 +21,7 @@ use ibc::{
         ics04_channel::{
             channel::{ChannelEnd, IdentifiedChannelEnd},
             packet::{PacketMsgType, Sequence},
+            upgrade::{ErrorReceipt, Upgrade},
         },
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
@@ -48,11 +50,13 @@ use super::{
         QueryChannelsRequest, QueryClientConnectionsRequest, QueryClientStateRequest,
         QueryClientStatesRequest, QueryConnectionChannelsRequest, QueryConnectionRequest,
         QueryConnectionsRequest, QueryConsensusStateRequest, QueryConsensusStatesRequest,
-        QueryHostConsensusStateRequest, QueryNextSequenceReceiveRequest,
+        QueryCounterpartyPayeeRequest, QueryCrossChainRequest, QueryHostConsensusStateRequest,
+        QueryIncentivizedPacketsRequest, QueryNextSequenceReceiveRequest,
         QueryPacketAcknowledgementRequest, QueryPacketAcknowledgementsRequest,
         QueryPacketCommitmentRequest, QueryPacketCommitmentsRequest, QueryPacketReceiptRequest,
         QueryTxRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
-        QueryUpgradedClientStateRequest, QueryUpgradedConsensusStateRequest,
+        QueryUpgradeErrorRequest, QueryUpgradeRequest, QueryUpgradedClientStateRequest,
+        QueryUpgradedConsensusStateRequest,
     },
     tracking::TrackedMsgs,
 };
@@ -121,11 +125,13 @@ pub enum ChainRequest {
 
     SendMessagesAndWaitCommit {
         tracked_msgs: TrackedMsgs,
+        key_name: Option<String>,
         reply_to: ReplyTo<Vec<IbcEvent>>,
     },
 
     SendMessagesAndWaitCheckTx {
         tracked_msgs: TrackedMsgs,
+        key_name: Option<String>,
         reply_to: ReplyTo<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>>,
     },
 
@@ -133,11 +139,23 @@ pub enum ChainRequest {
         reply_to: ReplyTo<ChainConfig>,
     },
 
+    UpdateConfig {
+        config: ChainConfig,
+        reply_to: ReplyTo<()>,
+    },
+
     Signer {
+        key_name: Option<String>,
+        reply_to: ReplyTo<Signer>,
+    },
+
+    KeyAddress {
+        key_name: Option<String>,
         reply_to: ReplyTo<Signer>,
     },
 
     GetKey {
+        key_name: Option<String>,
         reply_to: ReplyTo<KeyEntry>,
     },
 
@@ -271,6 +289,33 @@ pub enum ChainRequest {
         reply_to: ReplyTo<(ChannelEnd, Option<MerkleProof>)>,
     },
 
+    QueryUpgrade {
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(Upgrade, Option<MerkleProof>)>,
+    },
+
+    QueryUpgradeError {
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(ErrorReceipt, Option<MerkleProof>)>,
+    },
+
+    QueryCrossChain {
+        request: QueryCrossChainRequest,
+        reply_to: ReplyTo<(Vec<u8>, MerkleProof, Height)>,
+    },
+
+    QueryIncentivizedPackets {
+        request: QueryIncentivizedPacketsRequest,
+        reply_to: ReplyTo<Vec<IdentifiedPacketFees>>,
+    },
+
+    QueryCounterpartyPayee {
+        request: QueryCounterpartyPayeeRequest,
+        reply_to: ReplyTo<Option<Signer>>,
+    },
+
     QueryChannelClientState {
         request: QueryChannelClientStateRequest,
         reply_to: ReplyTo<Option<IdentifiedAnyClientState>>,
@@ -358,6 +403,10 @@ pub trait ChainHandle: Clone + Send + Sync + Serialize + Debug + 'static {
     /// Get the [`ChainId`] of this chain.
     fn id(&self) -> ChainId;
 
+    /// Returns a handle to the same chain runtime which signs the transactions
+    /// with the given key of the chain's key pool, instead of the default key.
+    fn with_key(&self, key_name: String) -> Self;
+
     /// Shutdown the chain runtime.
     fn shutdown(&self) -> Result<(), Error>;
 
@@ -385,8 +434,16 @@ pub trait ChainHandle: Clone + Send + Sync + Serialize + Debug + 'static {
 
     fn get_signer(&self) -> Result<Signer, Error>;
 
+    /// Returns the address of the relayer's key, which differs from the signer of the messages
+    /// when they are submitted on behalf of an authz granter.
+    fn get_key_address(&self) -> Result<Signer, Error>;
+
     fn config(&self) -> Result<ChainConfig, Error>;
 
+    /// Replaces the configuration of the running chain, e.g. after it was reloaded.
+    /// The new configuration must have the same endpoints and key as the current one.
+    fn update_config(&self, config: ChainConfig) -> Result<(), Error>;
+
     fn get_key(&self) -> Result<KeyEntry, Error>;
 
     fn add_key(&self, key_name: String, key: KeyEntry) -> Result<(), Error>;
@@ -501,6 +558,49 @@ pub trait ChainHandle: Clone + Send + Sync + Serialize + Debug + 'static {
         include_proof: IncludeProof,
     ) -> Result<(ChannelEnd, Option<MerkleProof>), Error>;
 
+    /// Performs a query to retrieve the upgrade proposed for the channel
+    /// associated with a given channel identifier. A proof can optionally be
+    /// returned along with the result.
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error>;
+
+    /// Performs a query to retrieve the receipt of the last aborted upgrade
+    /// of the channel associated with a given channel identifier. A proof can
+    /// optionally be returned along with the result.
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error>;
+
+    /// Performs a query to retrieve the value stored at a path of any store
+    /// of the chain, along with a proof of it (or of its absence, if the value
+    /// is empty) and the height at which the proof can be verified, i.e. the
+    /// height of the block following the queried state. Used to answer ICS31
+    /// cross-chain queries.
+    fn query_cross_chain(
+        &self,
+        request: QueryCrossChainRequest,
+    ) -> Result<(Vec<u8>, MerkleProof, Height), Error>;
+
+    /// Performs a query to retrieve the ICS29 fees escrowed for the packets
+    /// sent on a channel. Packets which are not incentivized are not returned,
+    /// and no packets are returned if the chain does not support ICS29.
+    fn query_incentivized_packets(
+        &self,
+        request: QueryIncentivizedPacketsRequest,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error>;
+
+    /// Performs a query to retrieve the ICS29 counterparty payee registered
+    /// by a relayer on a channel, if any.
+    fn query_counterparty_payee(
+        &self,
+        request: QueryCounterpartyPayeeRequest,
+    ) -> Result<Option<Signer>, Error>;
+
     /// Performs a query to retrieve the client state for the channel associated
     /// with a given channel identifier.
     fn query_channel_client_state(
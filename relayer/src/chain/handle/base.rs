@@ -3,6 +3,7 @@ use core::fmt::Debug;
 use crossbeam_channel as channel;
 use serde::{Serialize, Serializer};
 
+use ibc::applications::fee::packet_fee::IdentifiedPacketFees;
 use ibc::{
     core::{
         ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight},
@@ -14,6 +15,7 @@ use ibc::{
         ics03_connection::version::Version,
         ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd},
         ics04_channel::packet::{PacketMsgType, Sequence},
+        ics04_channel::upgrade::{ErrorReceipt, Upgrade},
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::ChainId,
         ics24_host::identifier::ChannelId,
@@ -35,11 +37,13 @@ use crate::{
             QueryChannelsRequest, QueryClientConnectionsRequest, QueryClientStateRequest,
             QueryClientStatesRequest, QueryConnectionChannelsRequest, QueryConnectionRequest,
             QueryConnectionsRequest, QueryConsensusStateRequest, QueryConsensusStatesRequest,
-            QueryHostConsensusStateRequest, QueryNextSequenceReceiveRequest,
+            QueryCounterpartyPayeeRequest, QueryCrossChainRequest, QueryHostConsensusStateRequest,
+            QueryIncentivizedPacketsRequest, QueryNextSequenceReceiveRequest,
             QueryPacketAcknowledgementRequest, QueryPacketAcknowledgementsRequest,
             QueryPacketCommitmentRequest, QueryPacketCommitmentsRequest, QueryPacketReceiptRequest,
             QueryTxRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
-            QueryUpgradedClientStateRequest, QueryUpgradedConsensusStateRequest,
+            QueryUpgradeErrorRequest, QueryUpgradeRequest, QueryUpgradedClientStateRequest,
+            QueryUpgradedConsensusStateRequest,
         },
         tracking::TrackedMsgs,
     },
@@ -61,6 +65,9 @@ pub struct BaseChainHandle {
 
     /// The handle's channel for sending requests to the runtime
     runtime_sender: channel::Sender<ChainRequest>,
+
+    /// The key to sign the transactions with, or the default key of the chain if `None`
+    key_name: Option<String>,
 }
 
 impl BaseChainHandle {
@@ -68,6 +75,7 @@ impl BaseChainHandle {
         Self {
             chain_id,
             runtime_sender: sender,
+            key_name: None,
         }
     }
 
@@ -94,6 +102,13 @@ impl ChainHandle for BaseChainHandle {
         self.chain_id.clone()
     }
 
+    fn with_key(&self, key_name: String) -> Self {
+        Self {
+            key_name: Some(key_name),
+            ..self.clone()
+        }
+    }
+
     fn health_check(&self) -> Result<HealthCheck, Error> {
         self.send(|reply_to| ChainRequest::HealthCheck { reply_to })
     }
@@ -112,6 +127,7 @@ impl ChainHandle for BaseChainHandle {
     ) -> Result<Vec<IbcEvent>, Error> {
         self.send(|reply_to| ChainRequest::SendMessagesAndWaitCommit {
             tracked_msgs,
+            key_name: self.key_name.clone(),
             reply_to,
         })
     }
@@ -122,20 +138,38 @@ impl ChainHandle for BaseChainHandle {
     ) -> Result<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>, Error> {
         self.send(|reply_to| ChainRequest::SendMessagesAndWaitCheckTx {
             tracked_msgs,
+            key_name: self.key_name.clone(),
             reply_to,
         })
     }
 
     fn get_signer(&self) -> Result<Signer, Error> {
-        self.send(|reply_to| ChainRequest::Signer { reply_to })
+        self.send(|reply_to| ChainRequest::Signer {
+            key_name: self.key_name.clone(),
+            reply_to,
+        })
+    }
+
+    fn get_key_address(&self) -> Result<Signer, Error> {
+        self.send(|reply_to| ChainRequest::KeyAddress {
+            key_name: self.key_name.clone(),
+            reply_to,
+        })
     }
 
     fn config(&self) -> Result<ChainConfig, Error> {
         self.send(|reply_to| ChainRequest::Config { reply_to })
     }
 
+    fn update_config(&self, config: ChainConfig) -> Result<(), Error> {
+        self.send(|reply_to| ChainRequest::UpdateConfig { config, reply_to })
+    }
+
     fn get_key(&self) -> Result<KeyEntry, Error> {
-        self.send(|reply_to| ChainRequest::GetKey { reply_to })
+        self.send(|reply_to| ChainRequest::GetKey {
+            key_name: self.key_name.clone(),
+            reply_to,
+        })
     }
 
     fn add_key(&self, key_name: String, key: KeyEntry) -> Result<(), Error> {
@@ -286,6 +320,51 @@ impl ChainHandle for BaseChainHandle {
         })
     }
 
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error> {
+        self.send(|reply_to| ChainRequest::QueryUpgrade {
+            request,
+            include_proof,
+            reply_to,
+        })
+    }
+
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        self.send(|reply_to| ChainRequest::QueryUpgradeError {
+            request,
+            include_proof,
+            reply_to,
+        })
+    }
+
+    fn query_cross_chain(
+        &self,
+        request: QueryCrossChainRequest,
+    ) -> Result<(Vec<u8>, MerkleProof, Height), Error> {
+        self.send(|reply_to| ChainRequest::QueryCrossChain { request, reply_to })
+    }
+
+    fn query_incentivized_packets(
+        &self,
+        request: QueryIncentivizedPacketsRequest,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        self.send(|reply_to| ChainRequest::QueryIncentivizedPackets { request, reply_to })
+    }
+
+    fn query_counterparty_payee(
+        &self,
+        request: QueryCounterpartyPayeeRequest,
+    ) -> Result<Option<Signer>, Error> {
+        self.send(|reply_to| ChainRequest::QueryCounterpartyPayee { request, reply_to })
+    }
+
     fn query_channel_client_state(
         &self,
         request: QueryChannelClientStateRequest,
@@ -1,4 +1,5 @@
 use crossbeam_channel as channel;
+use ibc::applications::fee::packet_fee::IdentifiedPacketFees;
 use ibc::core::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
 use ibc::core::ics02_client::client_state::{AnyClientState, IdentifiedAnyClientState};
 use ibc::core::ics02_client::events::UpdateClient;
@@ -6,6 +7,7 @@ use ibc::core::ics02_client::misbehaviour::MisbehaviourEvidence;
 use ibc::core::ics03_connection::connection::IdentifiedConnectionEnd;
 use ibc::core::ics04_channel::channel::IdentifiedChannelEnd;
 use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use ibc::core::ics23_commitment::merkle::MerkleProof;
 use ibc::{
     core::ics02_client::header::AnyHeader,
@@ -32,12 +34,14 @@ use crate::chain::requests::{
     IncludeProof, QueryBlockRequest, QueryChannelClientStateRequest, QueryChannelRequest,
     QueryChannelsRequest, QueryClientConnectionsRequest, QueryClientStateRequest,
     QueryClientStatesRequest, QueryConnectionChannelsRequest, QueryConnectionRequest,
-    QueryConnectionsRequest, QueryConsensusStateRequest, QueryConsensusStatesRequest, QueryHeight,
-    QueryHostConsensusStateRequest, QueryNextSequenceReceiveRequest,
-    QueryPacketAcknowledgementRequest, QueryPacketAcknowledgementsRequest,
-    QueryPacketCommitmentRequest, QueryPacketCommitmentsRequest, QueryPacketReceiptRequest,
-    QueryTxRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
-    QueryUpgradedClientStateRequest, QueryUpgradedConsensusStateRequest,
+    QueryConnectionsRequest, QueryConsensusStateRequest, QueryConsensusStatesRequest,
+    QueryCounterpartyPayeeRequest, QueryCrossChainRequest, QueryHeight,
+    QueryHostConsensusStateRequest, QueryIncentivizedPacketsRequest,
+    QueryNextSequenceReceiveRequest, QueryPacketAcknowledgementRequest,
+    QueryPacketAcknowledgementsRequest, QueryPacketCommitmentRequest,
+    QueryPacketCommitmentsRequest, QueryPacketReceiptRequest, QueryTxRequest,
+    QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest, QueryUpgradeErrorRequest,
+    QueryUpgradeRequest, QueryUpgradedClientStateRequest, QueryUpgradedConsensusStateRequest,
 };
 use crate::chain::tracking::TrackedMsgs;
 use crate::config::ChainConfig;
@@ -86,6 +90,13 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().id()
     }
 
+    fn with_key(&self, key_name: String) -> Self {
+        Self {
+            inner: self.inner().with_key(key_name),
+            cache: self.cache.clone(),
+        }
+    }
+
     fn shutdown(&self) -> Result<(), Error> {
         self.inner().shutdown()
     }
@@ -116,10 +127,18 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().get_signer()
     }
 
+    fn get_key_address(&self) -> Result<Signer, Error> {
+        self.inner().get_key_address()
+    }
+
     fn config(&self) -> Result<ChainConfig, Error> {
         self.inner().config()
     }
 
+    fn update_config(&self, config: ChainConfig) -> Result<(), Error> {
+        self.inner().update_config(config)
+    }
+
     fn get_key(&self) -> Result<KeyEntry, Error> {
         self.inner().get_key()
     }
@@ -332,6 +351,43 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         }
     }
 
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error> {
+        self.inner().query_upgrade(request, include_proof)
+    }
+
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        self.inner().query_upgrade_error(request, include_proof)
+    }
+
+    fn query_cross_chain(
+        &self,
+        request: QueryCrossChainRequest,
+    ) -> Result<(Vec<u8>, MerkleProof, Height), Error> {
+        self.inner().query_cross_chain(request)
+    }
+
+    fn query_incentivized_packets(
+        &self,
+        request: QueryIncentivizedPacketsRequest,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        self.inner().query_incentivized_packets(request)
+    }
+
+    fn query_counterparty_payee(
+        &self,
+        request: QueryCounterpartyPayeeRequest,
+    ) -> Result<Option<Signer>, Error> {
+        self.inner().query_counterparty_payee(request)
+    }
+
     fn query_channel_client_state(
         &self,
         request: QueryChannelClientStateRequest,
@@ -1,4 +1,5 @@
 use crossbeam_channel as channel;
+use ibc::applications::fee::packet_fee::IdentifiedPacketFees;
 use ibc::core::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
 use ibc::core::ics02_client::client_state::{AnyClientState, IdentifiedAnyClientState};
 use ibc::core::ics02_client::events::UpdateClient;
@@ -6,6 +7,7 @@ use ibc::core::ics02_client::misbehaviour::MisbehaviourEvidence;
 use ibc::core::ics03_connection::connection::IdentifiedConnectionEnd;
 use ibc::core::ics04_channel::channel::IdentifiedChannelEnd;
 use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use ibc::core::ics23_commitment::merkle::MerkleProof;
 use ibc::{
     core::ics02_client::header::AnyHeader,
@@ -33,11 +35,13 @@ use crate::chain::requests::{
     QueryChannelsRequest, QueryClientConnectionsRequest, QueryClientStateRequest,
     QueryClientStatesRequest, QueryConnectionChannelsRequest, QueryConnectionRequest,
     QueryConnectionsRequest, QueryConsensusStateRequest, QueryConsensusStatesRequest,
-    QueryHostConsensusStateRequest, QueryNextSequenceReceiveRequest,
+    QueryCounterpartyPayeeRequest, QueryCrossChainRequest, QueryHostConsensusStateRequest,
+    QueryIncentivizedPacketsRequest, QueryNextSequenceReceiveRequest,
     QueryPacketAcknowledgementRequest, QueryPacketAcknowledgementsRequest,
     QueryPacketCommitmentRequest, QueryPacketCommitmentsRequest, QueryPacketReceiptRequest,
     QueryTxRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
-    QueryUpgradedClientStateRequest, QueryUpgradedConsensusStateRequest,
+    QueryUpgradeErrorRequest, QueryUpgradeRequest, QueryUpgradedClientStateRequest,
+    QueryUpgradedConsensusStateRequest,
 };
 use crate::chain::tracking::TrackedMsgs;
 use crate::config::ChainConfig;
@@ -96,6 +100,13 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().id()
     }
 
+    fn with_key(&self, key_name: String) -> Self {
+        Self {
+            inner: self.inner().with_key(key_name),
+            metrics: self.metrics.clone(),
+        }
+    }
+
     fn shutdown(&self) -> Result<(), Error> {
         debug!(
             "shutting down chain handle {}. usage metrics for chain: \n {:?}",
@@ -137,11 +148,21 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().get_signer()
     }
 
+    fn get_key_address(&self) -> Result<Signer, Error> {
+        self.inc_metric("get_key_address");
+        self.inner().get_key_address()
+    }
+
     fn config(&self) -> Result<ChainConfig, Error> {
         self.inc_metric("config");
         self.inner().config()
     }
 
+    fn update_config(&self, config: ChainConfig) -> Result<(), Error> {
+        self.inc_metric("update_config");
+        self.inner().update_config(config)
+    }
+
     fn get_key(&self) -> Result<KeyEntry, Error> {
         self.inc_metric("get_key");
         self.inner().get_key()
@@ -300,6 +321,48 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().query_channel(request, include_proof)
     }
 
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error> {
+        self.inc_metric("query_upgrade");
+        self.inner().query_upgrade(request, include_proof)
+    }
+
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        self.inc_metric("query_upgrade_error");
+        self.inner().query_upgrade_error(request, include_proof)
+    }
+
+    fn query_cross_chain(
+        &self,
+        request: QueryCrossChainRequest,
+    ) -> Result<(Vec<u8>, MerkleProof, Height), Error> {
+        self.inc_metric("query_cross_chain");
+        self.inner().query_cross_chain(request)
+    }
+
+    fn query_incentivized_packets(
+        &self,
+        request: QueryIncentivizedPacketsRequest,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        self.inc_metric("query_incentivized_packets");
+        self.inner().query_incentivized_packets(request)
+    }
+
+    fn query_counterparty_payee(
+        &self,
+        request: QueryCounterpartyPayeeRequest,
+    ) -> Result<Option<Signer>, Error> {
+        self.inc_metric("query_counterparty_payee");
+        self.inner().query_counterparty_payee(request)
+    }
+
     fn query_channel_client_state(
         &self,
         request: QueryChannelClientStateRequest,
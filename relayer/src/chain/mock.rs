@@ -1,6 +1,7 @@
 use alloc::sync::Arc;
 use core::ops::Add;
 use core::time::Duration;
+use ibc::applications::fee::packet_fee::IdentifiedPacketFees;
 use ibc::core::ics23_commitment::merkle::MerkleProof;
 
 use crossbeam_channel as channel;
@@ -18,6 +19,7 @@ use ibc::core::ics03_connection::connection::{ConnectionEnd, IdentifiedConnectio
 use ibc::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
 use ibc::core::ics04_channel::context::ChannelReader;
 use ibc::core::ics04_channel::packet::Sequence;
+use ibc::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use ibc::core::ics23_commitment::{commitment::CommitmentPrefix, specs::ProofSpecs};
 use ibc::core::ics24_host::identifier::{ChainId, ConnectionId};
 use ibc::events::IbcEvent;
@@ -33,6 +35,8 @@ use crate::chain::client::ClientSettings;
 use crate::chain::endpoint::{ChainEndpoint, ChainStatus, HealthCheck};
 use crate::chain::requests::{
     QueryChannelClientStateRequest, QueryChannelRequest, QueryClientStatesRequest,
+    QueryCounterpartyPayeeRequest, QueryCrossChainRequest, QueryIncentivizedPacketsRequest,
+    QueryUpgradeErrorRequest, QueryUpgradeRequest,
 };
 use crate::config::ChainConfig;
 use crate::denom::DenomTrace;
@@ -132,6 +136,7 @@ impl ChainEndpoint for MockChain {
     fn send_messages_and_wait_commit(
         &mut self,
         tracked_msgs: TrackedMsgs,
+        _key_name: Option<String>,
     ) -> Result<Vec<IbcEvent>, Error> {
         // Use the ICS18Context interface to submit the set of messages.
         let events = self.context.send(tracked_msgs.msgs).map_err(Error::ics18)?;
@@ -142,11 +147,16 @@ impl ChainEndpoint for MockChain {
     fn send_messages_and_wait_check_tx(
         &mut self,
         _tracked_msgs: TrackedMsgs,
+        _key_name: Option<String>,
     ) -> Result<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>, Error> {
         todo!()
     }
 
-    fn get_signer(&mut self) -> Result<Signer, Error> {
+    fn get_signer(&mut self, _key_name: Option<String>) -> Result<Signer, Error> {
+        Ok(get_dummy_account_id())
+    }
+
+    fn get_key_address(&mut self, _key_name: Option<String>) -> Result<Signer, Error> {
         Ok(get_dummy_account_id())
     }
 
@@ -154,7 +164,12 @@ impl ChainEndpoint for MockChain {
         self.config.clone()
     }
 
-    fn get_key(&mut self) -> Result<KeyEntry, Error> {
+    fn update_config(&mut self, config: ChainConfig) -> Result<(), Error> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn get_key(&mut self, _key_name: Option<String>) -> Result<KeyEntry, Error> {
         unimplemented!()
     }
 
@@ -257,6 +272,43 @@ impl ChainEndpoint for MockChain {
         unimplemented!()
     }
 
+    fn query_upgrade(
+        &self,
+        _request: QueryUpgradeRequest,
+        _include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error> {
+        unimplemented!()
+    }
+
+    fn query_upgrade_error(
+        &self,
+        _request: QueryUpgradeErrorRequest,
+        _include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        unimplemented!()
+    }
+
+    fn query_cross_chain(
+        &self,
+        _request: QueryCrossChainRequest,
+    ) -> Result<(Vec<u8>, MerkleProof, Height), Error> {
+        unimplemented!()
+    }
+
+    fn query_incentivized_packets(
+        &self,
+        _request: QueryIncentivizedPacketsRequest,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        unimplemented!()
+    }
+
+    fn query_counterparty_payee(
+        &self,
+        _request: QueryCounterpartyPayeeRequest,
+    ) -> Result<Option<Signer>, Error> {
+        unimplemented!()
+    }
+
     fn query_channel_client_state(
         &self,
         _request: QueryChannelClientStateRequest,
@@ -469,11 +521,13 @@ pub mod test_utils {
             store_prefix: "".to_string(),
             default_gas: None,
             key_store_type: Default::default(),
+            key_pool: Vec::new(),
             max_gas: None,
             gas_price: GasPrice::new(0.001, "uatom".to_string()),
             gas_adjustment: None,
             gas_multiplier: None,
             fee_granter: None,
+            authz_granter: None,
             max_msg_num: Default::default(),
             max_tx_size: Default::default(),
             clock_drift: Duration::from_secs(5),
@@ -481,7 +535,13 @@ pub mod test_utils {
             trusting_period: Some(Duration::from_secs(14 * 24 * 60 * 60)), // 14 days
             trust_threshold: Default::default(),
             packet_filter: PacketFilter::default(),
+            fee_filter: Default::default(),
             address_type: AddressType::default(),
+            event_source: Default::default(),
+            event_store: Default::default(),
+            endpoints: Vec::new(),
+            light_store: Default::default(),
+            dynamic_gas_price: Default::default(),
             memo_prefix: Default::default(),
             proof_specs: Default::default(),
         }
@@ -2,11 +2,17 @@ use core::fmt::Display;
 
 use crate::error::Error;
 
+use ibc::applications::interchain_query::query::QueryPath;
 use ibc::core::ics04_channel::packet::Sequence;
 use ibc::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
 use ibc::events::WithBlockDataType;
+use ibc::signer::Signer;
 use ibc::Height;
 use ibc_proto::cosmos::base::query::v1beta1::PageRequest as RawPageRequest;
+use ibc_proto::ibc::applications::fee::v1::{
+    QueryCounterpartyPayeeRequest as RawQueryCounterpartyPayeeRequest,
+    QueryIncentivizedPacketsForChannelRequest as RawQueryIncentivizedPacketsForChannelRequest,
+};
 use ibc_proto::ibc::core::channel::v1::{
     QueryChannelClientStateRequest as RawQueryChannelClientStateRequest,
     QueryChannelsRequest as RawQueryChannelsRequest,
@@ -246,6 +252,29 @@ pub struct QueryChannelRequest {
     pub height: QueryHeight,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueryUpgradeRequest {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub height: QueryHeight,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueryUpgradeErrorRequest {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub height: QueryHeight,
+}
+
+/// Queries the value stored at `path` in any store of the chain, for an ICS31 cross-chain
+/// query. The value is read from the state committed at `height`, whose proof is verified
+/// against the app hash of the next block.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueryCrossChainRequest {
+    pub path: QueryPath,
+    pub height: QueryHeight,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct QueryChannelClientStateRequest {
     pub port_id: PortId,
@@ -294,6 +323,45 @@ pub struct QueryPacketReceiptRequest {
     pub height: QueryHeight,
 }
 
+/// Queries the ICS29 fees escrowed for the packets sent on the given channel.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueryIncentivizedPacketsRequest {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub pagination: Option<PageRequest>,
+    pub height: QueryHeight,
+}
+
+impl From<QueryIncentivizedPacketsRequest> for RawQueryIncentivizedPacketsForChannelRequest {
+    fn from(request: QueryIncentivizedPacketsRequest) -> Self {
+        RawQueryIncentivizedPacketsForChannelRequest {
+            pagination: request.pagination.map(|pagination| pagination.into()),
+            port_id: request.port_id.to_string(),
+            channel_id: request.channel_id.to_string(),
+            query_height: match request.height {
+                QueryHeight::Latest => 0,
+                QueryHeight::Specific(height) => height.revision_height(),
+            },
+        }
+    }
+}
+
+/// Queries the ICS29 counterparty payee registered by `relayer` on the given channel.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueryCounterpartyPayeeRequest {
+    pub channel_id: ChannelId,
+    pub relayer: Signer,
+}
+
+impl From<QueryCounterpartyPayeeRequest> for RawQueryCounterpartyPayeeRequest {
+    fn from(request: QueryCounterpartyPayeeRequest) -> Self {
+        RawQueryCounterpartyPayeeRequest {
+            channel_id: request.channel_id.to_string(),
+            relayer: request.relayer.to_string(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct QueryUnreceivedPacketsRequest {
     pub port_id: PortId,
@@ -392,6 +460,7 @@ pub struct QueryHostConsensusStateRequest {
 #[derive(Clone, Debug)]
 pub enum QueryTxRequest {
     Packet(QueryPacketEventDataRequest),
+    PacketRange(QueryPacketEventRangeRequest),
     Client(QueryClientEventRequest),
     Transaction(QueryTxHash),
 }
@@ -412,6 +481,19 @@ pub struct QueryPacketEventDataRequest {
     pub height: QueryHeight,
 }
 
+/// Used to query the packet events, identified by `event_id`, emitted on a channel end in the
+/// transactions committed after `from_height`, up to `to_height` included. These are the events
+/// of the packets sent on the channel end for `SendPacket`, and of the packets received on it
+/// for `WriteAck`.
+#[derive(Clone, Debug)]
+pub struct QueryPacketEventRangeRequest {
+    pub event_id: WithBlockDataType,
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub from_height: Height,
+    pub to_height: Height,
+}
+
 /// Query request for a single client event, identified by `event_id`, for `client_id`.
 #[derive(Clone, Debug)]
 pub struct QueryClientEventRequest {
@@ -1,10 +1,12 @@
 use alloc::sync::Arc;
+use core::time::Duration;
 use std::thread;
 
 use crossbeam_channel as channel;
 use tokio::runtime::Runtime as TokioRuntime;
-use tracing::error;
+use tracing::{error, warn};
 
+use ibc::applications::fee::packet_fee::IdentifiedPacketFees;
 use ibc::{
     core::{
         ics02_client::{
@@ -21,6 +23,7 @@ use ibc::{
         ics04_channel::{
             channel::{ChannelEnd, IdentifiedChannelEnd},
             packet::{PacketMsgType, Sequence},
+            upgrade::{ErrorReceipt, Upgrade},
         },
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
@@ -39,7 +42,10 @@ use crate::{
     error::Error,
     event::{
         bus::EventBus,
-        monitor::{EventBatch, EventReceiver, MonitorCmd, Result as MonitorResult, TxMonitorCmd},
+        monitor::{
+            Error as EventError, EventBatch, EventReceiver, MonitorCmd, Result as MonitorResult,
+            TxMonitorCmd,
+        },
     },
     keyring::KeyEntry,
     light_client::LightClient,
@@ -54,15 +60,23 @@ use super::{
         QueryChannelsRequest, QueryClientConnectionsRequest, QueryClientStateRequest,
         QueryClientStatesRequest, QueryConnectionChannelsRequest, QueryConnectionRequest,
         QueryConnectionsRequest, QueryConsensusStateRequest, QueryConsensusStatesRequest,
-        QueryHostConsensusStateRequest, QueryNextSequenceReceiveRequest,
+        QueryCounterpartyPayeeRequest, QueryCrossChainRequest, QueryHostConsensusStateRequest,
+        QueryIncentivizedPacketsRequest, QueryNextSequenceReceiveRequest,
         QueryPacketAcknowledgementRequest, QueryPacketAcknowledgementsRequest,
         QueryPacketCommitmentRequest, QueryPacketCommitmentsRequest, QueryPacketReceiptRequest,
         QueryTxRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
-        QueryUpgradedClientStateRequest, QueryUpgradedConsensusStateRequest,
+        QueryUpgradeErrorRequest, QueryUpgradeRequest, QueryUpgradedClientStateRequest,
+        QueryUpgradedConsensusStateRequest,
     },
     tracking::TrackedMsgs,
+    tx_sender::{KeySenders, SendRequest},
 };
 
+/// Interval at which the chain fails over to another full node if the active one is
+/// unhealthy. The health of the nodes is probed in the background, see
+/// [`Endpoints::spawn_probes`](crate::chain::cosmos::endpoints::Endpoints::spawn_probes).
+const ENDPOINT_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
 pub struct Threads {
     pub chain_runtime: thread::JoinHandle<()>,
     pub event_monitor: Option<thread::JoinHandle<()>>,
@@ -147,6 +161,9 @@ pub struct ChainRuntime<Endpoint: ChainEndpoint> {
     /// A handle to the light client
     light_client: Endpoint::LightClient,
 
+    /// The threads submitting the transactions of each key of the chain concurrently
+    key_senders: KeySenders,
+
     #[allow(dead_code)]
     rt: Arc<TokioRuntime>, // Making this future-proof, so we keep the runtime around.
 }
@@ -197,6 +214,7 @@ where
     /// Basic constructor
     fn new(chain: Endpoint, light_client: Endpoint::LightClient, rt: Arc<TokioRuntime>) -> Self {
         let (request_sender, request_receiver) = channel::unbounded::<ChainRequest>();
+        let key_senders = KeySenders::new(ChainEndpoint::id(&chain).clone());
 
         Self {
             rt,
@@ -206,6 +224,7 @@ where
             event_bus: EventBus::new(),
             event_monitor_ctrl: EventMonitorCtrl::none(),
             light_client,
+            key_senders,
         }
     }
 
@@ -217,8 +236,13 @@ where
     }
 
     fn run(mut self) -> Result<(), Error> {
+        let endpoint_checks = channel::tick(ENDPOINT_CHECK_INTERVAL);
+
         loop {
             channel::select! {
+                recv(endpoint_checks) -> _ => {
+                    self.check_endpoints()
+                },
                 recv(self.event_monitor_ctrl.recv()) -> event_batch => {
                     match event_batch {
                         Ok(event_batch) => {
@@ -251,24 +275,32 @@ where
                             self.subscribe(reply_to)?
                         },
 
-                        Ok(ChainRequest::SendMessagesAndWaitCommit { tracked_msgs, reply_to }) => {
-                            self.send_messages_and_wait_commit(tracked_msgs, reply_to)?
+                        Ok(ChainRequest::SendMessagesAndWaitCommit { tracked_msgs, key_name, reply_to }) => {
+                            self.send_messages_and_wait_commit(tracked_msgs, key_name, reply_to)?
+                        },
+
+                        Ok(ChainRequest::SendMessagesAndWaitCheckTx { tracked_msgs, key_name, reply_to }) => {
+                            self.send_messages_and_wait_check_tx(tracked_msgs, key_name, reply_to)?
                         },
 
-                        Ok(ChainRequest::SendMessagesAndWaitCheckTx { tracked_msgs, reply_to }) => {
-                            self.send_messages_and_wait_check_tx(tracked_msgs, reply_to)?
+                        Ok(ChainRequest::Signer { key_name, reply_to }) => {
+                            self.get_signer(key_name, reply_to)?
                         },
 
-                        Ok(ChainRequest::Signer { reply_to }) => {
-                            self.get_signer(reply_to)?
+                        Ok(ChainRequest::KeyAddress { key_name, reply_to }) => {
+                            self.get_key_address(key_name, reply_to)?
                         },
 
                         Ok(ChainRequest::Config { reply_to }) => {
                             self.get_config(reply_to)?
                         },
 
-                        Ok(ChainRequest::GetKey { reply_to }) => {
-                            self.get_key(reply_to)?
+                        Ok(ChainRequest::UpdateConfig { config, reply_to }) => {
+                            self.update_config(config, reply_to)?
+                        },
+
+                        Ok(ChainRequest::GetKey { key_name, reply_to }) => {
+                            self.get_key(key_name, reply_to)?
                         },
 
                         Ok(ChainRequest::AddKey { key_name, key, reply_to }) => {
@@ -371,6 +403,26 @@ where
                             self.query_channel(request, include_proof, reply_to)?
                         },
 
+                        Ok(ChainRequest::QueryUpgrade { request, include_proof, reply_to }) => {
+                            self.query_upgrade(request, include_proof, reply_to)?
+                        },
+
+                        Ok(ChainRequest::QueryUpgradeError { request, include_proof, reply_to }) => {
+                            self.query_upgrade_error(request, include_proof, reply_to)?
+                        },
+
+                        Ok(ChainRequest::QueryCrossChain { request, reply_to }) => {
+                            self.query_cross_chain(request, reply_to)?
+                        },
+
+                        Ok(ChainRequest::QueryIncentivizedPackets { request, reply_to }) => {
+                            self.query_incentivized_packets(request, reply_to)?
+                        },
+
+                        Ok(ChainRequest::QueryCounterpartyPayee { request, reply_to }) => {
+                            self.query_counterparty_payee(request, reply_to)?
+                        },
+
                         Ok(ChainRequest::QueryChannelClientState { request, reply_to }) => {
                             self.query_channel_client_state(request, reply_to)?
                         },
@@ -446,6 +498,55 @@ where
         reply_to.send(Ok(subscription)).map_err(Error::send)
     }
 
+    /// Fails over to another full node of the chain if the active one is unhealthy, in which
+    /// case the light client and the event monitor are connected to that node. Errors are only
+    /// logged, as the chain keeps running on the previous node.
+    fn check_endpoints(&mut self) {
+        let address = match self.chain.check_endpoints() {
+            Ok(Some(address)) => address,
+            Ok(None) => return,
+            Err(e) => {
+                warn!("failed to check the endpoints of the chain: {}", e);
+                return;
+            }
+        };
+
+        // The transaction senders are spawned again to submit to the new node
+        self.key_senders.clear();
+
+        match self.chain.init_light_client() {
+            Ok(light_client) => self.light_client = light_client,
+            Err(e) => warn!("failed to connect the light client to {}: {}", address, e),
+        }
+
+        if !self.event_monitor_ctrl.is_live() {
+            return;
+        }
+
+        // Keep the current event monitor, which reconnects on its own,
+        // if the one connected to the new node cannot be started.
+        let monitor = self.chain.init_event_monitor(self.rt.clone());
+        let (event_receiver, tx_monitor_cmd) = match monitor {
+            Ok(monitor) => monitor,
+            Err(e) => {
+                warn!("failed to connect the event monitor to {}: {}", address, e);
+                return;
+            }
+        };
+
+        if let Err(e) = self.event_monitor_ctrl.shutdown() {
+            warn!("failed to shut down the previous event monitor: {}", e);
+        }
+
+        self.event_monitor_ctrl
+            .enable(event_receiver, tx_monitor_cmd);
+
+        // The events emitted while switching over may have been missed.
+        let chain_id = ChainEndpoint::id(&self.chain).clone();
+        let error = EventError::endpoint_switched(chain_id, address);
+        self.event_bus.broadcast(Arc::new(Err(error)));
+    }
+
     fn enable_event_monitor(&mut self) -> Result<(), Error> {
         let (event_receiver, tx_monitor_cmd) = self.chain.init_event_monitor(self.rt.clone())?;
 
@@ -458,19 +559,55 @@ where
     fn send_messages_and_wait_commit(
         &mut self,
         tracked_msgs: TrackedMsgs,
+        key_name: Option<String>,
         reply_to: ReplyTo<Vec<IbcEvent>>,
     ) -> Result<(), Error> {
-        let result = self.chain.send_messages_and_wait_commit(tracked_msgs);
-        reply_to.send(result).map_err(Error::send)
+        let key_name = key_name.unwrap_or_else(|| self.chain.config().key_name);
+        let chain = &mut self.chain;
+
+        match self
+            .key_senders
+            .get_or_spawn(&key_name, || chain.tx_sender(&key_name))
+        {
+            Ok(Some(sender)) => sender
+                .send(SendRequest::WaitCommit {
+                    tracked_msgs,
+                    reply_to,
+                })
+                .map_err(Error::send),
+            Ok(None) => {
+                let result = chain.send_messages_and_wait_commit(tracked_msgs, Some(key_name));
+                reply_to.send(result).map_err(Error::send)
+            }
+            Err(e) => reply_to.send(Err(e)).map_err(Error::send),
+        }
     }
 
     fn send_messages_and_wait_check_tx(
         &mut self,
         tracked_msgs: TrackedMsgs,
+        key_name: Option<String>,
         reply_to: ReplyTo<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>>,
     ) -> Result<(), Error> {
-        let result = self.chain.send_messages_and_wait_check_tx(tracked_msgs);
-        reply_to.send(result).map_err(Error::send)
+        let key_name = key_name.unwrap_or_else(|| self.chain.config().key_name);
+        let chain = &mut self.chain;
+
+        match self
+            .key_senders
+            .get_or_spawn(&key_name, || chain.tx_sender(&key_name))
+        {
+            Ok(Some(sender)) => sender
+                .send(SendRequest::WaitCheckTx {
+                    tracked_msgs,
+                    reply_to,
+                })
+                .map_err(Error::send),
+            Ok(None) => {
+                let result = chain.send_messages_and_wait_check_tx(tracked_msgs, Some(key_name));
+                reply_to.send(result).map_err(Error::send)
+            }
+            Err(e) => reply_to.send(Err(e)).map_err(Error::send),
+        }
     }
 
     fn query_balance(
@@ -492,8 +629,21 @@ where
         reply_to.send(latest_timestamp).map_err(Error::send)
     }
 
-    fn get_signer(&mut self, reply_to: ReplyTo<Signer>) -> Result<(), Error> {
-        let result = self.chain.get_signer();
+    fn get_signer(
+        &mut self,
+        key_name: Option<String>,
+        reply_to: ReplyTo<Signer>,
+    ) -> Result<(), Error> {
+        let result = self.chain.get_signer(key_name);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn get_key_address(
+        &mut self,
+        key_name: Option<String>,
+        reply_to: ReplyTo<Signer>,
+    ) -> Result<(), Error> {
+        let result = self.chain.get_key_address(key_name);
         reply_to.send(result).map_err(Error::send)
     }
 
@@ -502,8 +652,21 @@ where
         reply_to.send(result).map_err(Error::send)
     }
 
-    fn get_key(&mut self, reply_to: ReplyTo<KeyEntry>) -> Result<(), Error> {
-        let result = self.chain.get_key();
+    fn update_config(&mut self, config: ChainConfig, reply_to: ReplyTo<()>) -> Result<(), Error> {
+        let result = self.chain.update_config(config);
+
+        // The transaction senders are spawned again with the new configuration
+        self.key_senders.clear();
+
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn get_key(
+        &mut self,
+        key_name: Option<String>,
+        reply_to: ReplyTo<KeyEntry>,
+    ) -> Result<(), Error> {
+        let result = self.chain.get_key(key_name);
         reply_to.send(result).map_err(Error::send)
     }
 
@@ -749,6 +912,53 @@ where
         reply_to.send(result).map_err(Error::send)
     }
 
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(Upgrade, Option<MerkleProof>)>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_upgrade(request, include_proof);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(ErrorReceipt, Option<MerkleProof>)>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_upgrade_error(request, include_proof);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn query_cross_chain(
+        &self,
+        request: QueryCrossChainRequest,
+        reply_to: ReplyTo<(Vec<u8>, MerkleProof, Height)>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_cross_chain(request);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn query_incentivized_packets(
+        &self,
+        request: QueryIncentivizedPacketsRequest,
+        reply_to: ReplyTo<Vec<IdentifiedPacketFees>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_incentivized_packets(request);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn query_counterparty_payee(
+        &self,
+        request: QueryCounterpartyPayeeRequest,
+        reply_to: ReplyTo<Option<Signer>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_counterparty_payee(request);
+        reply_to.send(result).map_err(Error::send)
+    }
+
     fn query_channel_client_state(
         &self,
         request: QueryChannelClientStateRequest,
@@ -0,0 +1,252 @@
+//! Concurrent submission of the transactions signed with the different keys of a chain.
+//!
+//! The chain runtime handles its requests one at a time, so it hands the transactions over to
+//! one thread per key of the chain's key pool. Each thread submits the transactions of its key
+//! in order, tracking the sequence of the key's account on its own, while the transactions of
+//! different keys are submitted concurrently.
+
+use std::collections::HashMap;
+use std::thread;
+
+use crossbeam_channel as channel;
+use tendermint_rpc::endpoint::broadcast::tx_sync::Response as TxResponse;
+use tracing::{debug, warn};
+
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc::events::IbcEvent;
+
+use crate::chain::handle::ReplyTo;
+use crate::chain::tracking::TrackedMsgs;
+use crate::error::Error;
+
+/// Submits the transactions signed with one key of a chain, independently of the chain's
+/// [`ChainEndpoint`](crate::chain::endpoint::ChainEndpoint).
+pub trait TxSender: Send {
+    fn send_messages_and_wait_commit(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEvent>, Error>;
+
+    fn send_messages_and_wait_check_tx(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<TxResponse>, Error>;
+}
+
+/// A request to submit messages with the key of a [`KeySenders`] thread.
+pub enum SendRequest {
+    WaitCommit {
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<Vec<IbcEvent>>,
+    },
+
+    WaitCheckTx {
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<Vec<TxResponse>>,
+    },
+}
+
+/// The threads submitting the transactions of the keys of a chain, by key name.
+pub struct KeySenders {
+    chain_id: ChainId,
+    senders: HashMap<String, channel::Sender<SendRequest>>,
+}
+
+impl KeySenders {
+    pub fn new(chain_id: ChainId) -> Self {
+        Self {
+            chain_id,
+            senders: HashMap::new(),
+        }
+    }
+
+    /// Returns the channel to the thread submitting the transactions of `key_name`, spawning it
+    /// with the sender built by `tx_sender` on first use, or `None` if there is no such sender.
+    pub fn get_or_spawn(
+        &mut self,
+        key_name: &str,
+        tx_sender: impl FnOnce() -> Result<Option<Box<dyn TxSender>>, Error>,
+    ) -> Result<Option<&channel::Sender<SendRequest>>, Error> {
+        if !self.senders.contains_key(key_name) {
+            let tx_sender = match tx_sender()? {
+                Some(tx_sender) => tx_sender,
+                None => return Ok(None),
+            };
+
+            let sender = spawn_key_sender(self.chain_id.clone(), key_name.to_string(), tx_sender);
+            self.senders.insert(key_name.to_string(), sender);
+        }
+
+        Ok(self.senders.get(key_name))
+    }
+
+    /// Stops all the threads, once they have handled the requests sent to them so far,
+    /// e.g. so that the new threads pick up an updated configuration of the chain.
+    pub fn clear(&mut self) {
+        self.senders.clear();
+    }
+}
+
+fn spawn_key_sender(
+    chain_id: ChainId,
+    key_name: String,
+    mut tx_sender: Box<dyn TxSender>,
+) -> channel::Sender<SendRequest> {
+    let (sender, receiver) = channel::unbounded::<SendRequest>();
+
+    debug!(chain = %chain_id, key = %key_name, "spawning transaction sender");
+
+    // The thread stops once the runtime drops its end of the channel
+    thread::spawn(move || {
+        for request in receiver {
+            let sent = match request {
+                SendRequest::WaitCommit {
+                    tracked_msgs,
+                    reply_to,
+                } => reply_to
+                    .send(tx_sender.send_messages_and_wait_commit(tracked_msgs))
+                    .map_err(Error::send),
+
+                SendRequest::WaitCheckTx {
+                    tracked_msgs,
+                    reply_to,
+                } => reply_to
+                    .send(tx_sender.send_messages_and_wait_check_tx(tracked_msgs))
+                    .map_err(Error::send),
+            };
+
+            if let Err(e) = sent {
+                warn!(chain = %chain_id, key = %key_name, "failed to reply to request: {}", e);
+            }
+        }
+    });
+
+    sender
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Barrier};
+    use std::time::Duration;
+
+    use super::*;
+
+    /// Tracks a sequence per key, and waits on `barrier` before each submission.
+    struct SequenceSender {
+        key_name: String,
+        sequence: u64,
+        barrier: Option<Arc<Barrier>>,
+    }
+
+    impl TxSender for SequenceSender {
+        fn send_messages_and_wait_commit(
+            &mut self,
+            _tracked_msgs: TrackedMsgs,
+        ) -> Result<Vec<IbcEvent>, Error> {
+            if let Some(barrier) = &self.barrier {
+                barrier.wait();
+            }
+
+            self.sequence += 1;
+            let sent = format!("{}/{}", self.key_name, self.sequence);
+
+            Ok(vec![IbcEvent::ChainError(sent)])
+        }
+
+        fn send_messages_and_wait_check_tx(
+            &mut self,
+            _tracked_msgs: TrackedMsgs,
+        ) -> Result<Vec<TxResponse>, Error> {
+            Ok(vec![])
+        }
+    }
+
+    fn sequence_sender(
+        key_name: &str,
+        barrier: Option<Arc<Barrier>>,
+    ) -> impl FnOnce() -> Result<Option<Box<dyn TxSender>>, Error> {
+        let key_name = key_name.to_string();
+
+        move || {
+            let sender = SequenceSender {
+                key_name,
+                sequence: 0,
+                barrier,
+            };
+
+            Ok(Some(Box::new(sender) as Box<dyn TxSender>))
+        }
+    }
+
+    fn send(
+        senders: &mut KeySenders,
+        key_name: &str,
+        barrier: Option<Arc<Barrier>>,
+    ) -> channel::Receiver<Result<Vec<IbcEvent>, Error>> {
+        let (reply_to, reply) = channel::bounded(1);
+
+        senders
+            .get_or_spawn(key_name, sequence_sender(key_name, barrier))
+            .unwrap()
+            .unwrap()
+            .send(SendRequest::WaitCommit {
+                tracked_msgs: TrackedMsgs::new_static(vec![], "test"),
+                reply_to,
+            })
+            .unwrap();
+
+        reply
+    }
+
+    fn sent(reply: channel::Receiver<Result<Vec<IbcEvent>, Error>>) -> String {
+        let events = reply
+            .recv_timeout(Duration::from_secs(10))
+            .expect("no reply from the sender")
+            .unwrap();
+
+        match &events[..] {
+            [IbcEvent::ChainError(sent)] => sent.clone(),
+            _ => panic!("unexpected events: {:?}", events),
+        }
+    }
+
+    #[test]
+    fn sequences_are_tracked_per_key() {
+        let mut senders = KeySenders::new(ChainId::new("ibc".to_string(), 0));
+
+        let replies = [
+            send(&mut senders, "key-a", None),
+            send(&mut senders, "key-b", None),
+            send(&mut senders, "key-a", None),
+            send(&mut senders, "key-a", None),
+            send(&mut senders, "key-b", None),
+        ];
+
+        let sent: Vec<_> = replies.into_iter().map(sent).collect();
+        assert_eq!(
+            sent,
+            ["key-a/1", "key-b/1", "key-a/2", "key-a/3", "key-b/2"]
+        );
+    }
+
+    #[test]
+    fn keys_are_sent_concurrently() {
+        let mut senders = KeySenders::new(ChainId::new("ibc".to_string(), 0));
+
+        // Neither submission completes before the other one has started
+        let barrier = Arc::new(Barrier::new(2));
+        let reply_a = send(&mut senders, "key-a", Some(barrier.clone()));
+        let reply_b = send(&mut senders, "key-b", Some(barrier));
+
+        assert_eq!(sent(reply_a), "key-a/1");
+        assert_eq!(sent(reply_b), "key-b/1");
+    }
+
+    #[test]
+    fn no_thread_without_sender() {
+        let mut senders = KeySenders::new(ChainId::new("ibc".to_string(), 0));
+
+        let sender = senders.get_or_spawn("key-a", || Ok(None)).unwrap();
+        assert!(sender.is_none());
+    }
+}
@@ -14,9 +14,19 @@ use ibc::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
 use ibc::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
 use ibc::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
 use ibc::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use ibc::core::ics04_channel::msgs::chan_upgrade_ack::MsgChannelUpgradeAck;
+use ibc::core::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+use ibc::core::ics04_channel::msgs::chan_upgrade_confirm::MsgChannelUpgradeConfirm;
+use ibc::core::ics04_channel::msgs::chan_upgrade_init::MsgChannelUpgradeInit;
+use ibc::core::ics04_channel::msgs::chan_upgrade_open::MsgChannelUpgradeOpen;
+use ibc::core::ics04_channel::msgs::chan_upgrade_timeout::MsgChannelUpgradeTimeout;
+use ibc::core::ics04_channel::msgs::chan_upgrade_try::MsgChannelUpgradeTry;
+use ibc::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade, UpgradeFields};
 use ibc::core::ics04_channel::Version;
+use ibc::core::ics23_commitment::commitment::CommitmentProofBytes;
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
 use ibc::events::IbcEvent;
+use ibc::proofs::Proofs;
 use ibc::tx_msg::Msg;
 use ibc::Height;
 
@@ -24,10 +34,11 @@ use crate::chain::counterparty::{channel_connection_client, channel_state_on_des
 use crate::chain::handle::ChainHandle;
 use crate::chain::requests::{
     IncludeProof, PageRequest, QueryChannelRequest, QueryConnectionChannelsRequest,
-    QueryConnectionRequest, QueryHeight,
+    QueryConnectionRequest, QueryHeight, QueryUpgradeErrorRequest, QueryUpgradeRequest,
 };
 use crate::chain::tracking::TrackedMsgs;
 use crate::connection::Connection;
+use crate::error::{Error as RelayerError, QUERY_PROOF_EXPECT_MSG};
 use crate::foreign_client::{ForeignClient, HasExpiredOrFrozenError};
 use crate::object::Channel as WorkerChannelObject;
 use crate::supervisor::error::Error as SupervisorError;
@@ -722,6 +733,10 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         &mut self,
         state: State,
     ) -> Result<(Option<IbcEvent>, Next), ChannelError> {
+        if state.is_upgrading() {
+            return self.upgrade_step();
+        }
+
         let res = match (state, self.counterparty_state()?) {
             (State::Init, State::Uninitialized) => Some(self.build_chan_open_try_and_send()?),
             (State::Init, State::Init) => Some(self.build_chan_open_try_and_send()?),
@@ -763,6 +778,10 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
     }
 
     pub fn step_event(&mut self, event: IbcEvent, index: u64) -> RetryResult<Next, u64> {
+        if event.upgrade_attributes().is_some() {
+            return self.step_upgrade(index);
+        }
+
         let state = match event {
             IbcEvent::OpenInitChannel(_) => State::Init,
             IbcEvent::OpenTryChannel(_) => State::TryOpen,
@@ -774,6 +793,92 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         self.step_state(state, index)
     }
 
+    /// Sends the next message of the channel upgrade handshake to the destination chain, as
+    /// determined by the state of the upgrade on both channel ends.
+    pub fn upgrade_step(&mut self) -> Result<(Option<IbcEvent>, Next), ChannelError> {
+        let src_channel = self.a_channel(self.src_channel_id())?;
+        let dst_channel = self.b_channel(self.dst_channel_id())?;
+        let src_upgrade = self.src_upgrade();
+        let dst_upgrade = self.dst_upgrade();
+
+        // An upgrade in progress on the destination is aborted first if the source
+        // chain already aborted it, or if it timed out on the source chain.
+        if let Some(dst_upgrade) = &dst_upgrade {
+            if matches!(dst_channel.state, State::Open | State::Flushing) {
+                if let Some(error_receipt) = self.src_upgrade_error() {
+                    if error_receipt.sequence >= dst_channel.upgrade_sequence() {
+                        let event = self.build_chan_upgrade_cancel_and_send()?;
+                        return Ok((Some(event), Next::Continue));
+                    }
+                }
+            }
+
+            let src_opened = src_channel.state == State::Open
+                && src_channel.upgrade_sequence() == dst_channel.upgrade_sequence();
+
+            if dst_channel.state.is_upgrading()
+                && src_channel.state != State::FlushComplete
+                && !src_opened
+            {
+                let status = self
+                    .src_chain()
+                    .query_application_status()
+                    .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+                if dst_upgrade.timed_out(status.height, &status.timestamp) {
+                    let event = self.build_chan_upgrade_timeout_and_send()?;
+                    return Ok((Some(event), Next::Continue));
+                }
+            }
+        }
+
+        let res = match (src_channel.state, dst_channel.state) {
+            (State::Open, State::Open) if src_upgrade.is_some() && dst_upgrade.is_none() => {
+                Some(self.build_chan_upgrade_try_and_send()?)
+            }
+            (State::Open, State::Open) if src_upgrade.is_none() && dst_upgrade.is_none() => {
+                return Ok((None, Next::Abort))
+            }
+            (State::Flushing, State::Open) if dst_upgrade.is_some() => {
+                Some(self.build_chan_upgrade_ack_and_send()?)
+            }
+            (State::Flushing | State::FlushComplete, State::Flushing) => {
+                Some(self.build_chan_upgrade_confirm_and_send()?)
+            }
+            (State::FlushComplete, State::FlushComplete) => {
+                Some(self.build_chan_upgrade_open_and_send()?)
+            }
+            // The source already opened with the upgraded fields.
+            (State::Open, State::FlushComplete)
+                if src_channel.upgrade_sequence() == dst_channel.upgrade_sequence() =>
+            {
+                Some(self.build_chan_upgrade_open_and_send()?)
+            }
+            _ => None,
+        };
+
+        Ok((res, Next::Continue))
+    }
+
+    pub fn step_upgrade(&mut self, index: u64) -> RetryResult<Next, u64> {
+        match self.upgrade_step() {
+            Err(e) => {
+                if e.is_expired_or_frozen_error() {
+                    error!("failed to upgrade channel on frozen client: {}", e);
+                    RetryResult::Err(index)
+                } else {
+                    error!("failed channel upgrade step with error: {}", e);
+                    RetryResult::Retry(index)
+                }
+            }
+            Ok((Some(ev), next)) => {
+                info!("channel upgrade step completed with events: {:#?}\n", ev);
+                RetryResult::Ok(next)
+            }
+            Ok((None, next)) => RetryResult::Ok(next),
+        }
+    }
+
     pub fn build_update_client_on_dst(&self, height: Height) -> Result<Vec<Any>, ChannelError> {
         let client = ForeignClient::restore(
             self.dst_client_id().clone(),
@@ -1424,6 +1529,462 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         }
     }
 
+    /// Returns the upgrade proposed for the source channel end, if any.
+    fn src_upgrade(&self) -> Option<Upgrade> {
+        let channel_id = self.src_channel_id()?;
+
+        // A missing upgrade is reported as a query error by the chain.
+        self.src_chain()
+            .query_upgrade(
+                QueryUpgradeRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: channel_id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .ok()
+            .map(|(upgrade, _)| upgrade)
+    }
+
+    /// Returns the upgrade proposed for the destination channel end, if any.
+    fn dst_upgrade(&self) -> Option<Upgrade> {
+        let channel_id = self.dst_channel_id()?;
+
+        self.dst_chain()
+            .query_upgrade(
+                QueryUpgradeRequest {
+                    port_id: self.dst_port_id().clone(),
+                    channel_id: channel_id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .ok()
+            .map(|(upgrade, _)| upgrade)
+    }
+
+    /// Returns the receipt of the last upgrade aborted by the source channel end, if any.
+    fn src_upgrade_error(&self) -> Option<ErrorReceipt> {
+        let channel_id = self.src_channel_id()?;
+
+        self.src_chain()
+            .query_upgrade_error(
+                QueryUpgradeErrorRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: channel_id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .ok()
+            .map(|(error_receipt, _)| error_receipt)
+    }
+
+    /// Queries the source channel end at the latest height, along with the proof
+    /// of the channel end.
+    fn build_src_channel_proofs(&self) -> Result<(ChannelEnd, Proofs), ChannelError> {
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+
+        let query_height = self
+            .src_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let (src_channel, _) = self
+            .src_chain()
+            .query_channel(
+                QueryChannelRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: src_channel_id.clone(),
+                    height: QueryHeight::Specific(query_height),
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let proofs = self
+            .src_chain()
+            .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
+            .map_err(ChannelError::channel_proof)?;
+
+        Ok((src_channel, proofs))
+    }
+
+    /// Queries the source channel end and its proposed upgrade at the latest height,
+    /// along with the proofs of both.
+    fn build_src_upgrade_proofs(&self) -> Result<(ChannelEnd, Upgrade, Proofs), ChannelError> {
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+
+        let query_height = self
+            .src_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let (src_channel, maybe_channel_proof) = self
+            .src_chain()
+            .query_channel(
+                QueryChannelRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: src_channel_id.clone(),
+                    height: QueryHeight::Specific(query_height),
+                },
+                IncludeProof::Yes,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let (src_upgrade, maybe_upgrade_proof) = self
+            .src_chain()
+            .query_upgrade(
+                QueryUpgradeRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: src_channel_id.clone(),
+                    height: QueryHeight::Specific(query_height),
+                },
+                IncludeProof::Yes,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let channel_proof =
+            CommitmentProofBytes::try_from(maybe_channel_proof.expect(QUERY_PROOF_EXPECT_MSG))
+                .map_err(|e| ChannelError::channel_proof(RelayerError::malformed_proof(e)))?;
+        let upgrade_proof =
+            CommitmentProofBytes::try_from(maybe_upgrade_proof.expect(QUERY_PROOF_EXPECT_MSG))
+                .map_err(|e| ChannelError::channel_proof(RelayerError::malformed_proof(e)))?;
+
+        let proofs = Proofs::new(
+            channel_proof,
+            None,
+            None,
+            Some(upgrade_proof),
+            query_height.increment(),
+        )
+        .map_err(|e| ChannelError::channel_proof(RelayerError::malformed_proof(e)))?;
+
+        Ok((src_channel, src_upgrade, proofs))
+    }
+
+    /// Submits the channel upgrade messages to the destination chain, and returns
+    /// the first event in the response that satisfies `is_expected`.
+    fn send_chan_upgrade_msgs(
+        &self,
+        dst_msgs: Vec<Any>,
+        tracking_id: &'static str,
+        is_expected: fn(&IbcEvent) -> bool,
+    ) -> Result<IbcEvent, ChannelError> {
+        let tm = TrackedMsgs::new_static(dst_msgs, tracking_id);
+
+        let events = self
+            .dst_chain()
+            .send_messages_and_wait_commit(tm)
+            .map_err(|e| ChannelError::submit(self.dst_chain().id(), e))?;
+
+        let result = events
+            .into_iter()
+            .find(|event| is_expected(event) || matches!(event, IbcEvent::ChainError(_)))
+            .ok_or_else(|| {
+                ChannelError::missing_event(format!("no {} event was in the response", tracking_id))
+            })?;
+
+        match result {
+            IbcEvent::ChainError(e) => Err(ChannelError::tx_response(e)),
+            _ => {
+                info!("🎊  {} => {:#?}\n", self.dst_chain().id(), result);
+                Ok(result)
+            }
+        }
+    }
+
+    /// Proposes to upgrade the destination channel end to the given fields.
+    pub fn build_chan_upgrade_init(&self, fields: UpgradeFields) -> Result<Vec<Any>, ChannelError> {
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        let new_msg = MsgChannelUpgradeInit {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            fields,
+            signer,
+        };
+
+        Ok(vec![new_msg.to_any()])
+    }
+
+    pub fn build_chan_upgrade_init_and_send(
+        &self,
+        fields: UpgradeFields,
+    ) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_init(fields)?;
+
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeInit", |event| {
+            matches!(event, IbcEvent::UpgradeInitChannel(_))
+        })
+    }
+
+    pub fn build_chan_upgrade_try(&self) -> Result<Vec<Any>, ChannelError> {
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let (src_channel, src_upgrade, proofs) = self.build_src_upgrade_proofs()?;
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        // The destination keeps its connection, only the ordering and version are negotiated.
+        let new_msg = MsgChannelUpgradeTry {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            proposed_upgrade_connection_hops: vec![self.dst_connection_id().clone()],
+            counterparty_upgrade_fields: src_upgrade.fields,
+            counterparty_upgrade_sequence: src_channel.upgrade_sequence(),
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_try_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_try()?;
+
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeTry", |event| {
+            matches!(event, IbcEvent::UpgradeTryChannel(_))
+        })
+    }
+
+    pub fn build_chan_upgrade_ack(&self) -> Result<Vec<Any>, ChannelError> {
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let (_, src_upgrade, proofs) = self.build_src_upgrade_proofs()?;
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        let new_msg = MsgChannelUpgradeAck {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            counterparty_upgrade: src_upgrade,
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_ack_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_ack()?;
+
+        // The destination aborts the upgrade if it does not agree with the source.
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeAck", |event| {
+            matches!(
+                event,
+                IbcEvent::UpgradeAckChannel(_) | IbcEvent::UpgradeErrorChannel(_)
+            )
+        })
+    }
+
+    pub fn build_chan_upgrade_confirm(&self) -> Result<Vec<Any>, ChannelError> {
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let (src_channel, src_upgrade, proofs) = self.build_src_upgrade_proofs()?;
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        let new_msg = MsgChannelUpgradeConfirm {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            counterparty_channel_state: src_channel.state,
+            counterparty_upgrade: src_upgrade,
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_confirm_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_confirm()?;
+
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeConfirm", |event| {
+            matches!(
+                event,
+                IbcEvent::UpgradeConfirmChannel(_) | IbcEvent::UpgradeErrorChannel(_)
+            )
+        })
+    }
+
+    pub fn build_chan_upgrade_open(&self) -> Result<Vec<Any>, ChannelError> {
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let (src_channel, proofs) = self.build_src_channel_proofs()?;
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        let new_msg = MsgChannelUpgradeOpen {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            counterparty_channel_state: src_channel.state,
+            counterparty_upgrade_sequence: src_channel.upgrade_sequence(),
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_open_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_open()?;
+
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeOpen", |event| {
+            matches!(event, IbcEvent::UpgradeOpenChannel(_))
+        })
+    }
+
+    pub fn build_chan_upgrade_timeout(&self) -> Result<Vec<Any>, ChannelError> {
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let (src_channel, proofs) = self.build_src_channel_proofs()?;
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        let new_msg = MsgChannelUpgradeTimeout {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            counterparty_channel: src_channel,
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_timeout_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_timeout()?;
+
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeTimeout", |event| {
+            matches!(event, IbcEvent::UpgradeTimeoutChannel(_))
+        })
+    }
+
+    pub fn build_chan_upgrade_cancel(&self) -> Result<Vec<Any>, ChannelError> {
+        let src_channel_id = self
+            .src_channel_id()
+            .ok_or_else(ChannelError::missing_local_channel_id)?;
+        let dst_channel_id = self
+            .dst_channel_id()
+            .ok_or_else(ChannelError::missing_counterparty_channel_id)?;
+
+        let query_height = self
+            .src_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let (error_receipt, maybe_error_receipt_proof) = self
+            .src_chain()
+            .query_upgrade_error(
+                QueryUpgradeErrorRequest {
+                    port_id: self.src_port_id().clone(),
+                    channel_id: src_channel_id.clone(),
+                    height: QueryHeight::Specific(query_height),
+                },
+                IncludeProof::Yes,
+            )
+            .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+        let error_receipt_proof = CommitmentProofBytes::try_from(
+            maybe_error_receipt_proof.expect(QUERY_PROOF_EXPECT_MSG),
+        )
+        .map_err(|e| ChannelError::channel_proof(RelayerError::malformed_proof(e)))?;
+
+        let proofs = Proofs::new(
+            error_receipt_proof,
+            None,
+            None,
+            None,
+            query_height.increment(),
+        )
+        .map_err(|e| ChannelError::channel_proof(RelayerError::malformed_proof(e)))?;
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        let signer = self
+            .dst_chain()
+            .get_signer()
+            .map_err(|e| ChannelError::fetch_signer(self.dst_chain().id(), e))?;
+
+        let new_msg = MsgChannelUpgradeCancel {
+            port_id: self.dst_port_id().clone(),
+            channel_id: dst_channel_id.clone(),
+            error_receipt,
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_cancel_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_cancel()?;
+
+        self.send_chan_upgrade_msgs(dst_msgs, "ChannelUpgradeCancel", |event| {
+            matches!(event, IbcEvent::UpgradeCancelChannel(_))
+        })
+    }
+
     pub fn map_chain<ChainC: ChainHandle, ChainD: ChainHandle>(
         self,
         mapper_a: impl Fn(ChainA) -> ChainC,
@@ -1,5 +1,6 @@
 //! Relayer configuration
 
+pub mod diff;
 pub mod error;
 pub mod filter;
 pub mod proof_specs;
@@ -7,7 +8,12 @@ pub mod types;
 
 use alloc::collections::BTreeMap;
 use core::{fmt, time::Duration};
-use std::{fs, fs::File, io::Write, path::Path};
+use std::{
+    fs,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
 
 use serde_derive::{Deserialize, Serialize};
 use tendermint_light_client_verifier::types::TrustThreshold;
@@ -22,7 +28,7 @@ use crate::keyring::Store;
 
 pub use error::Error;
 
-pub use filter::PacketFilter;
+pub use filter::{FeeFilter, PacketFilter};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GasPrice {
@@ -70,6 +76,18 @@ pub mod default {
         Duration::from_secs(30)
     }
 
+    pub fn poll_interval() -> Duration {
+        Duration::from_secs(1)
+    }
+
+    pub fn endpoint_priority() -> u32 {
+        1
+    }
+
+    pub fn gas_price_refresh_interval() -> Duration {
+        Duration::from_secs(60)
+    }
+
     pub fn connection_delay() -> Duration {
         ZERO_DURATION
     }
@@ -86,6 +104,8 @@ pub struct Config {
     pub rest: RestConfig,
     #[serde(default)]
     pub telemetry: TelemetryConfig,
+    #[serde(default)]
+    pub store: StoreConfig,
     #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
     pub chains: Vec<ChainConfig>,
 }
@@ -130,6 +150,8 @@ pub struct ModeConfig {
     pub connections: Connections,
     pub channels: Channels,
     pub packets: Packets,
+    #[serde(default)]
+    pub cross_chain_queries: CrossChainQueries,
 }
 
 impl ModeConfig {
@@ -138,6 +160,7 @@ impl ModeConfig {
             && !self.connections.enabled
             && !self.channels.enabled
             && !self.packets.enabled
+            && !self.cross_chain_queries.enabled
     }
 }
 
@@ -156,7 +179,9 @@ impl Default for ModeConfig {
                 clear_interval: default::clear_packets_interval(),
                 clear_on_start: true,
                 tx_confirmation: true,
+                auto_register_counterparty_payee: false,
             },
+            cross_chain_queries: CrossChainQueries { enabled: false },
         }
     }
 }
@@ -183,6 +208,14 @@ pub struct Channels {
     pub enabled: bool,
 }
 
+/// Whether to answer the ICS31 cross-chain queries sent by the chains, about
+/// the state of the other chains they track with a light client.
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CrossChainQueries {
+    pub enabled: bool,
+}
+
 #[derive(Copy, Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Packets {
@@ -193,6 +226,10 @@ pub struct Packets {
     pub clear_on_start: bool,
     #[serde(default = "default::tx_confirmation")]
     pub tx_confirmation: bool,
+    /// Register the relayer's address on the counterparty chain as its counterparty payee on
+    /// the ICS29 fee-enabled channels where none is registered, when starting up.
+    #[serde(default)]
+    pub auto_register_counterparty_payee: bool,
 }
 
 impl Default for Packets {
@@ -202,6 +239,7 @@ impl Default for Packets {
             clear_interval: default::clear_packets_interval(),
             clear_on_start: false,
             tx_confirmation: default::tx_confirmation(),
+            auto_register_counterparty_payee: false,
         }
     }
 }
@@ -279,6 +317,18 @@ impl Default for RestConfig {
     }
 }
 
+/// The configuration of an on-disk store: the store of the relaying state, see
+/// [`RelayStore`](crate::store::RelayStore), the event cursor of a chain, see
+/// [`EventCursorStore`](crate::store::EventCursorStore), or the light client store
+/// of a chain, see [`LightBlockStore`](crate::light_client::tendermint::store::LightBlockStore).
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StoreConfig {
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+}
+
 /// It defines the address generation method
 /// TODO: Ethermint `pk_type` to be restricted
 /// after the Cosmos SDK release with ethsecp256k1
@@ -325,6 +375,10 @@ pub struct ChainConfig {
     pub key_name: String,
     #[serde(default)]
     pub key_store_type: Store,
+    /// Additional keys to sign the transactions with, besides `key_name`, so that
+    /// the workers of the chain can submit transactions concurrently.
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    pub key_pool: Vec<String>,
     pub store_prefix: String,
     pub default_gas: Option<u64>,
     pub max_gas: Option<u64>,
@@ -334,6 +388,10 @@ pub struct ChainConfig {
     pub gas_multiplier: Option<f64>,
 
     pub fee_granter: Option<String>,
+    /// The account on whose behalf the relayer submits the messages, wrapped in an authz
+    /// `MsgExec` signed by the relayer's key, which must have been granted the authorizations
+    /// to execute them. The messages then name the granter as their signer.
+    pub authz_granter: Option<String>,
     #[serde(default)]
     pub max_msg_num: MaxMsgNum,
     #[serde(default)]
@@ -369,8 +427,148 @@ pub struct ChainConfig {
     pub gas_price: GasPrice,
     #[serde(default)]
     pub packet_filter: PacketFilter,
+    /// The minimum ICS29 fees of the packets sent from this chain, for them to be relayed.
+    #[serde(default)]
+    pub fee_filter: FeeFilter,
     #[serde(default)]
     pub address_type: AddressType,
+    #[serde(default)]
+    pub event_source: EventSourceMode,
+    /// The persistent store of the last height whose events were collected by the
+    /// pull event source of this chain.
+    #[serde(default)]
+    pub event_store: StoreConfig,
+    /// The addresses of additional full nodes of the chain, to which the relayer
+    /// fails over when the node at `rpc_addr`, `websocket_addr` and `grpc_addr`
+    /// is unhealthy.
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    pub endpoints: Vec<EndpointConfig>,
+    /// The persistent store of the light blocks verified by the light client of this chain.
+    #[serde(default)]
+    pub light_store: StoreConfig,
+    /// The discovery and adaptation of the gas price, which otherwise is the static `gas_price`.
+    #[serde(default)]
+    pub dynamic_gas_price: DynamicGasPriceConfig,
+}
+
+impl ChainConfig {
+    /// Returns the names of the keys the relayer signs the transactions with,
+    /// starting with the default key `key_name`, followed by the `key_pool`.
+    pub fn key_names(&self) -> Vec<String> {
+        let mut names = vec![self.key_name.clone()];
+
+        for name in &self.key_pool {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+
+        names
+    }
+
+    /// Returns the full nodes of the chain, ordered by priority. Among the nodes with
+    /// the same priority, the node given by `rpc_addr`, `websocket_addr` and `grpc_addr`
+    /// comes first, followed by the `endpoints` in the order in which they are listed.
+    pub fn endpoints(&self) -> Vec<EndpointConfig> {
+        let primary = EndpointConfig {
+            rpc_addr: self.rpc_addr.clone(),
+            websocket_addr: self.websocket_addr.clone(),
+            grpc_addr: self.grpc_addr.clone(),
+            priority: 0,
+        };
+
+        let mut endpoints: Vec<_> = core::iter::once(primary)
+            .chain(self.endpoints.iter().cloned())
+            .collect();
+
+        // The sort is stable, so the order of the nodes with the same priority is preserved.
+        endpoints.sort_by_key(|endpoint| endpoint.priority);
+        endpoints
+    }
+}
+
+/// The addresses of a full node of a chain.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EndpointConfig {
+    pub rpc_addr: tendermint_rpc::Url,
+    pub websocket_addr: tendermint_rpc::Url,
+    pub grpc_addr: tendermint_rpc::Url,
+    /// The relayer prefers the healthy nodes with the lowest priority value.
+    /// The node given by the top-level addresses of the chain has priority 0.
+    #[serde(default = "default::endpoint_priority")]
+    pub priority: u32,
+}
+
+/// The dynamic pricing of the gas of a chain, which starts from the configured `gas_price`,
+/// follows the price queried from the chain, and is raised after transactions are rejected
+/// for an insufficient fee, up to `max_price`.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DynamicGasPriceConfig {
+    pub enabled: bool,
+    /// Where the gas price is queried from.
+    pub source: GasPriceSource,
+    /// How often the gas price is queried from the chain.
+    #[serde(with = "humantime_serde")]
+    pub refresh_interval: Duration,
+    /// The factor applied to the price queried from the chain, to absorb the
+    /// price increases between two queries.
+    pub multiplier: f64,
+    /// The factor by which the gas price is raised after a transaction is rejected
+    /// for an insufficient fee, and lowered back after several transactions in a row
+    /// are accepted.
+    pub insufficient_fee_multiplier: f64,
+    /// The maximum gas price the relayer is willing to pay, in the denomination
+    /// of `gas_price`. Defaults to ten times the price of `gas_price`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_price: Option<f64>,
+}
+
+impl Default for DynamicGasPriceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            source: GasPriceSource::Static,
+            refresh_interval: default::gas_price_refresh_interval(),
+            multiplier: 1.0,
+            insufficient_fee_multiplier: 1.2,
+            max_price: None,
+        }
+    }
+}
+
+/// Where the gas price of a chain is queried from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GasPriceSource {
+    /// The price is not queried, only `gas_price` is adapted to the insufficient fee errors.
+    Static,
+    /// The minimum gas price of the full node, which it requires to accept a transaction.
+    MinGasPrice,
+    /// The gas price of the x/feemarket module, which follows the base fee of the chain.
+    FeeMarket,
+}
+
+/// How the relayer receives the events emitted by a chain.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "mode", rename_all = "lowercase")]
+pub enum EventSourceMode {
+    /// Subscribe to the events pushed by the node over a WebSocket connection
+    /// to `websocket_addr`.
+    Push,
+    /// Poll the `/block` and `/block_results` RPC endpoints at the given interval,
+    /// and collect the events of every new block.
+    Pull {
+        #[serde(default = "default::poll_interval", with = "humantime_serde")]
+        interval: Duration,
+    },
+}
+
+impl Default for EventSourceMode {
+    fn default() -> Self {
+        Self::Push
+    }
 }
 
 /// Attempt to load and parse the TOML config file as a `Config`.
@@ -405,7 +603,9 @@ pub(crate) fn store_writer(config: &Config, mut writer: impl Write) -> Result<()
 
 #[cfg(test)]
 mod tests {
-    use super::{load, store_writer};
+    use core::time::Duration;
+
+    use super::{load, store_writer, EndpointConfig, EventSourceMode};
     use test_log::test;
 
     #[test]
@@ -432,4 +632,77 @@ mod tests {
         let mut buffer = Vec::new();
         store_writer(&config, &mut buffer).unwrap();
     }
+
+    #[test]
+    fn parse_event_source_mode() {
+        #[derive(serde_derive::Deserialize)]
+        struct Wrapper {
+            event_source: EventSourceMode,
+        }
+
+        let parse = |s: &str| toml::from_str::<Wrapper>(s).unwrap().event_source;
+
+        assert_eq!(
+            parse("event_source = { mode = 'push' }"),
+            EventSourceMode::Push
+        );
+        assert_eq!(
+            parse("event_source = { mode = 'pull' }"),
+            EventSourceMode::Pull {
+                interval: Duration::from_secs(1)
+            }
+        );
+        assert_eq!(
+            parse("event_source = { mode = 'pull', interval = '500ms' }"),
+            EventSourceMode::Pull {
+                interval: Duration::from_millis(500)
+            }
+        );
+    }
+
+    #[test]
+    fn endpoints_by_priority() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let mut config = load(path).expect("could not parse config").chains[0].clone();
+
+        let endpoint = |port: u16, priority: u32| EndpointConfig {
+            rpc_addr: format!("http://127.0.0.1:{}", port).parse().unwrap(),
+            websocket_addr: format!("ws://127.0.0.1:{}/websocket", port)
+                .parse()
+                .unwrap(),
+            grpc_addr: format!("http://127.0.0.1:{}", port + 1).parse().unwrap(),
+            priority,
+        };
+
+        config.endpoints = vec![endpoint(26757, 2), endpoint(26857, 0), endpoint(26957, 1)];
+
+        let ports: Vec<_> = config
+            .endpoints()
+            .iter()
+            .map(|endpoint| endpoint.rpc_addr.port())
+            .collect();
+
+        assert_eq!(ports, vec![26657, 26857, 26957, 26757]);
+    }
+
+    #[test]
+    fn key_names() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let mut config = load(path).expect("could not parse config").chains[0].clone();
+        assert_eq!(config.key_names(), vec![config.key_name.clone()]);
+
+        config.key_pool = vec!["key-1".to_string(), config.key_name.clone()];
+        assert_eq!(
+            config.key_names(),
+            vec![config.key_name, "key-1".to_string()]
+        );
+    }
 }
@@ -0,0 +1,181 @@
+//! Differences between the running configuration and a reloaded one.
+
+use serde::Serialize;
+
+use ibc::core::ics24_host::identifier::ChainId;
+
+use crate::config::{ChainConfig, Config};
+
+/// The changes made to the configuration of the relayer, which determine the chain runtimes
+/// and workers to spawn, restart or shut down when the configuration is reloaded.
+///
+/// Changes to the global, REST, telemetry and store sections are not hot-reloadable, and are
+/// only reported in `restart_required`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ConfigDiff {
+    /// The chains which were added to the configuration.
+    pub added: Vec<ChainId>,
+    /// The chains which were removed from the configuration.
+    pub removed: Vec<ChainId>,
+    /// The chains whose configuration changed, but not their endpoints or key.
+    /// The new configuration is applied to their running runtime.
+    pub updated: Vec<ChainId>,
+    /// The updated chains whose packet filter changed, which are scanned again
+    /// for the channels to relay on.
+    pub filter_changed: Vec<ChainId>,
+    /// The chains whose endpoints, event source, stores or key changed, which are restarted.
+    pub restarted: Vec<ChainId>,
+    /// Whether the mode section changed, which affects the workers of all chains.
+    pub mode_changed: bool,
+    /// Whether sections which only take effect after a restart changed.
+    pub restart_required: bool,
+}
+
+impl ConfigDiff {
+    pub fn new(current: &Config, new: &Config) -> Self {
+        let mut diff = Self::default();
+
+        for chain_config in &new.chains {
+            match current.find_chain(&chain_config.id) {
+                None => diff.added.push(chain_config.id.clone()),
+                Some(current_config) if requires_restart(current_config, chain_config) => {
+                    diff.restarted.push(chain_config.id.clone())
+                }
+                Some(current_config) if !same(current_config, chain_config) => {
+                    if !same(&current_config.packet_filter, &chain_config.packet_filter) {
+                        diff.filter_changed.push(chain_config.id.clone());
+                    }
+                    diff.updated.push(chain_config.id.clone())
+                }
+                Some(_) => {}
+            }
+        }
+
+        for chain_config in &current.chains {
+            if new.find_chain(&chain_config.id).is_none() {
+                diff.removed.push(chain_config.id.clone());
+            }
+        }
+
+        diff.mode_changed = !same(&current.mode, &new.mode);
+        diff.restart_required = !same(&current.global, &new.global)
+            || !same(&current.rest, &new.rest)
+            || !same(&current.telemetry, &new.telemetry)
+            || !same(&current.store, &new.store);
+
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.updated.is_empty()
+            && self.restarted.is_empty()
+            && !self.mode_changed
+            && !self.restart_required
+    }
+}
+
+/// Whether the changes to the configuration of a chain require restarting its runtime,
+/// i.e. whether they change how the relayer connects to the chain, receives its events
+/// or signs transactions.
+fn requires_restart(current: &ChainConfig, new: &ChainConfig) -> bool {
+    let connection = |config: &ChainConfig| {
+        (
+            config.r#type,
+            config.rpc_addr.clone(),
+            config.websocket_addr.clone(),
+            config.grpc_addr.clone(),
+            config.endpoints.clone(),
+            config.rpc_timeout,
+            config.account_prefix.clone(),
+            config.key_name.clone(),
+            config.key_pool.clone(),
+            config.key_store_type,
+            config.event_source.clone(),
+            config.event_store.clone(),
+            config.light_store.clone(),
+        )
+    };
+
+    !same(&connection(current), &connection(new))
+}
+
+/// Compares two configuration sections by their serialization, as not all the types
+/// found in the configuration can be compared for equality, e.g. the wildcards of
+/// the packet filters.
+fn same<T: Serialize>(a: &T, b: &T) -> bool {
+    match (serde_json::to_value(a), serde_json::to_value(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConfigDiff;
+    use crate::config::filter::PacketFilter;
+    use crate::config::load;
+    use crate::config::types::Memo;
+    use ibc::core::ics24_host::identifier::ChainId;
+    use test_log::test;
+
+    #[test]
+    fn config_diff() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let current = load(path).expect("could not parse config");
+        assert!(ConfigDiff::new(&current, &current).is_empty());
+
+        let mut new = current.clone();
+        let removed = new.chains.remove(0).id;
+        let restarted = new.chains[0].id.clone();
+        new.chains[0].key_name = "other_key".to_string();
+
+        let mut added = new.chains[0].clone();
+        added.id = ChainId::new("ibc".to_string(), 9);
+        new.chains.push(added.clone());
+
+        new.mode.packets.clear_interval += 1;
+
+        let diff = ConfigDiff::new(&current, &new);
+        assert_eq!(
+            diff,
+            ConfigDiff {
+                added: vec![added.id],
+                removed: vec![removed],
+                restarted: vec![restarted],
+                mode_changed: true,
+                ..ConfigDiff::default()
+            }
+        );
+    }
+
+    #[test]
+    fn config_diff_without_restart() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let current = load(path).expect("could not parse config");
+
+        let mut new = current.clone();
+        new.chains[0].packet_filter = PacketFilter::default();
+        new.chains[1].gas_multiplier = Some(1.5);
+        new.chains[1].memo_prefix = Memo::new("relayed".to_string()).unwrap();
+
+        let diff = ConfigDiff::new(&current, &new);
+        assert_eq!(
+            diff,
+            ConfigDiff {
+                updated: vec![current.chains[0].id.clone(), current.chains[1].id.clone()],
+                filter_changed: vec![current.chains[0].id.clone()],
+                ..ConfigDiff::default()
+            }
+        );
+    }
+}
@@ -3,6 +3,8 @@
 use core::fmt;
 use core::str::FromStr;
 
+use ibc::applications::fee::packet_fee::{Fee, PacketFee};
+use ibc::applications::transfer::{Amount, BaseCoin, BaseDenom};
 use ibc::core::ics24_host::identifier::{ChannelId, PortId};
 use itertools::Itertools;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
@@ -43,6 +45,125 @@ impl PacketFilter {
     }
 }
 
+/// The ICS29 fees paid for relaying a packet, each to the relayer of one of the messages
+/// of its lifecycle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FeeKind {
+    Recv,
+    Ack,
+    Timeout,
+}
+
+impl FeeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FeeKind::Recv => "recv",
+            FeeKind::Ack => "ack",
+            FeeKind::Timeout => "timeout",
+        }
+    }
+
+    /// Returns the fee of this kind out of the given fee.
+    pub fn of(self, fee: &Fee) -> &[BaseCoin] {
+        match self {
+            FeeKind::Recv => &fee.recv_fee,
+            FeeKind::Ack => &fee.ack_fee,
+            FeeKind::Timeout => &fee.timeout_fee,
+        }
+    }
+
+    /// Returns the total amount of `denom` paid as fees of this kind, summed over all the fees
+    /// escrowed for a packet, or `None` if the total overflows.
+    pub fn total_amount(self, packet_fees: &[PacketFee], denom: &BaseDenom) -> Option<Amount> {
+        packet_fees
+            .iter()
+            .flat_map(|packet_fee| self.of(&packet_fee.fee))
+            .filter(|coin| &coin.denom == denom)
+            .try_fold(Amount::from(0), |total, coin| {
+                total.checked_add(coin.amount)
+            })
+    }
+}
+
+impl fmt::Display for FeeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// What to do with the packets which are not incentivized enough to be relayed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeePolicy {
+    /// Do not relay the packets.
+    Skip,
+    /// Relay the packets after the incentivized ones.
+    Deprioritize,
+}
+
+impl Default for FeePolicy {
+    fn default() -> Self {
+        Self::Skip
+    }
+}
+
+/// Filters the packets to relay by their ICS29 fees, on the chains they are sent from.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeeFilter {
+    #[serde(default)]
+    pub policy: FeePolicy,
+    /// The minimum fees of the packets sent on the matching channels. The packets sent on
+    /// channels without minimum fees are relayed regardless of their fees.
+    #[serde(default)]
+    pub min_fees: Vec<ChannelMinFees>,
+}
+
+impl FeeFilter {
+    /// Returns the minimum fees of the packets sent on the channel with [`PortId`] and
+    /// [`ChannelId`], from the first matching entry, if any.
+    pub fn min_fees(&self, port_id: &PortId, channel_id: &ChannelId) -> Option<&ChannelMinFees> {
+        self.min_fees.iter().find(|min_fees| {
+            min_fees.port_id.matches(port_id) && min_fees.channel_id.matches(channel_id)
+        })
+    }
+}
+
+/// The minimum fees of each kind the packets sent on the channels matching the port and channel
+/// patterns must be incentivized with, for the corresponding message to be relayed. Each fee may be
+/// made of several denominations, all of which are required, while fees in other denominations do
+/// not count. An empty minimum fee is always met.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelMinFees {
+    pub port_id: PortFilterMatch,
+    pub channel_id: ChannelFilterMatch,
+    #[serde(default)]
+    pub recv: Vec<BaseCoin>,
+    #[serde(default)]
+    pub ack: Vec<BaseCoin>,
+    #[serde(default)]
+    pub timeout: Vec<BaseCoin>,
+}
+
+impl ChannelMinFees {
+    pub fn min_fee(&self, kind: FeeKind) -> &[BaseCoin] {
+        match kind {
+            FeeKind::Recv => &self.recv,
+            FeeKind::Ack => &self.ack,
+            FeeKind::Timeout => &self.timeout,
+        }
+    }
+
+    /// Returns true if the fees escrowed for a packet meet the minimum fee of the given kind.
+    pub fn is_met_by(&self, kind: FeeKind, packet_fees: &[PacketFee]) -> bool {
+        self.min_fee(kind).iter().all(|min_fee| {
+            kind.total_amount(packet_fees, &min_fee.denom)
+                .map_or(true, |total| total >= min_fee.amount)
+        })
+    }
+}
+
 /// The internal representation of channel filter policies.
 #[derive(Clone, Debug, Default, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -498,4 +619,59 @@ mod tests {
         let wildcard = "ica*".parse::<Wildcard>().unwrap();
         assert_eq!(wildcard.to_string(), "ica*".to_string());
     }
+
+    #[test]
+    fn fee_filter_min_fees() {
+        let fee_filter = r#"
+            policy = 'deprioritize'
+            min_fees = [
+              { port_id = 'transfer', channel_id = 'channel-0', recv = [{ denom = 'uatom', amount = '100' }] },
+              { port_id = 'ica*', channel_id = '*', ack = [{ denom = 'uatom', amount = '10' }, { denom = 'stake', amount = '10' }] },
+            ]
+            "#;
+
+        let ff: FeeFilter = toml::from_str(fee_filter).expect("could not parse fee filter");
+        assert_eq!(ff.policy, FeePolicy::Deprioritize);
+
+        let transfer = PortId::from_str("transfer").unwrap();
+        let ica = PortId::from_str("ica-1").unwrap();
+        let channel_0 = ChannelId::from_str("channel-0").unwrap();
+        let channel_1 = ChannelId::from_str("channel-1").unwrap();
+
+        assert!(ff.min_fees(&transfer, &channel_1).is_none());
+
+        let coin = |denom: &str, amount: u64| BaseCoin {
+            denom: denom.parse().unwrap(),
+            amount: amount.into(),
+        };
+        let packet_fee = |recv_fee, ack_fee| PacketFee {
+            fee: Fee {
+                recv_fee,
+                ack_fee,
+                timeout_fee: vec![],
+            },
+            refund_address: "cosmos1wxeyh7zgn4tctjzs0vtqpc6p5cxq5t2muzl7ng"
+                .parse()
+                .unwrap(),
+            relayers: vec![],
+        };
+
+        let transfer_min_fees = ff.min_fees(&transfer, &channel_0).unwrap();
+        let packet_fees = [
+            packet_fee(vec![coin("uatom", 60)], vec![]),
+            packet_fee(vec![coin("uatom", 40), coin("stake", 1)], vec![]),
+        ];
+        assert!(transfer_min_fees.is_met_by(FeeKind::Recv, &packet_fees));
+        assert!(!transfer_min_fees.is_met_by(FeeKind::Recv, &packet_fees[..1]));
+        assert!(transfer_min_fees.is_met_by(FeeKind::Ack, &[]));
+
+        let ica_min_fees = ff.min_fees(&ica, &channel_1).unwrap();
+        let packet_fees = [packet_fee(vec![], vec![coin("uatom", 10)])];
+        assert!(!ica_min_fees.is_met_by(FeeKind::Ack, &packet_fees));
+        let packet_fees = [packet_fee(
+            vec![],
+            vec![coin("uatom", 10), coin("stake", 20)],
+        )];
+        assert!(ica_min_fees.is_met_by(FeeKind::Ack, &packet_fees));
+    }
 }
@@ -1,4 +1,4 @@
-//! Custom `serde` deserializer for `ProofSpecs`
+//! Custom `serde` deserializer for `ProofSpecs`, which rejects incoherent specs
 
 use core::fmt;
 use ibc::core::ics23_commitment::specs::ProofSpecs;
@@ -22,7 +22,9 @@ impl<'de> de::Visitor<'de> for ProofSpecsVisitor {
     }
 
     fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
-        serde_json::from_str(v).map_err(E::custom)
+        let proof_specs: ProofSpecs = serde_json::from_str(v).map_err(E::custom)?;
+        proof_specs.validate().map_err(E::custom)?;
+        Ok(proof_specs)
     }
 
     fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
@@ -22,6 +22,7 @@ use tonic::{
 };
 
 use ibc::{
+    applications::fee::error as fee_error,
     clients::ics07_tendermint::error as tendermint_error,
     core::{
         ics02_client::{client_type::ClientType, error as client_error},
@@ -119,6 +120,10 @@ define_error! {
         PrivateStore
             |_| { "requested proof for a path in the private store" },
 
+        InvalidQueryPath
+            { path: String }
+            |e| { format!("invalid path for a cross-chain query: {}", e.path) },
+
         Event
             |_| { "bad notification" },
 
@@ -266,6 +271,10 @@ define_error! {
             [ commitment_error::Error ]
             |_| { "ICS 23 error" },
 
+        Ics29
+            [ fee_error::Error ]
+            |_| { "ICS 29 error" },
+
         InvalidUri
             { uri: String }
             [ TraceError<InvalidUri> ]
@@ -511,6 +520,14 @@ define_error! {
             format_args!(
                 "Query/DenomTrace RPC returned an empty denom trace for trace hash: {}", e.hash)
         },
+
+        InvalidGasPriceResponse
+            { denom: String, response: String }
+            |e| {
+                format_args!(
+                    "failed to find a valid gas price in denom '{}' in the node response: '{}'",
+                    e.denom, e.response)
+            },
     }
 }
 
@@ -112,6 +112,7 @@ pub mod queries {
             ibc_client(),
             ibc_connection(),
             ibc_channel(),
+            interchain_query(),
             // This will be needed when we send misbehavior evidence to full node
             // Query::eq("message.module", "evidence"),
         ]
@@ -132,6 +133,10 @@ pub mod queries {
     pub fn ibc_channel() -> Query {
         Query::eq("message.module", "ibc_channel")
     }
+
+    pub fn interchain_query() -> Query {
+        Query::eq("message.module", "interchainquery")
+    }
 }
 
 impl EventMonitor {
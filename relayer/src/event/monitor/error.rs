@@ -45,6 +45,14 @@ define_error! {
         Rpc
             [ TraceError<RpcError> ]
             |_| { "RPC error" },
+
+        MissedHeights
+            { chain_id: ChainId, from: u64, to: u64 }
+            |e| { format!("failed to collect the events of chain {0} from height {1} to {2}", e.chain_id, e.from, e.to) },
+
+        EndpointSwitched
+            { chain_id: ChainId, address: Url }
+            |e| { format!("the event source of chain {0} failed over to node {1}", e.chain_id, e.address) },
     }
 }
 
@@ -0,0 +1,446 @@
+use alloc::sync::Arc;
+use core::convert::TryFrom;
+use core::time::Duration;
+
+use crossbeam_channel as channel;
+use tokio::runtime::Runtime as TokioRuntime;
+use tracing::{debug, error, trace, warn};
+
+use tendermint::abci::Event as AbciEvent;
+use tendermint::block::Height as TmHeight;
+use tendermint_rpc::{Client, HttpClient};
+
+use ibc::{
+    core::ics02_client::{events::NewBlock, height::Height},
+    core::ics24_host::identifier::ChainId,
+    events::IbcEvent,
+};
+
+use crate::{
+    chain::tracking::TrackingId,
+    event::monitor::{Error, EventBatch, EventReceiver, MonitorCmd, Next, Result, TxMonitorCmd},
+    event::rpc::ibc_event_from_abci_event,
+    store::EventCursorStore,
+};
+
+/// How many consecutive polls may fail to collect the events at a height which
+/// the node reports as committed, before giving up on the missed heights.
+const MAX_FAILURES: usize = 10;
+
+/// The queries made by the [`PullEventSource`] to a node.
+pub trait BlockSource {
+    /// The height of the latest block committed by the node.
+    fn latest_height(&self) -> Result<u64>;
+
+    /// The ABCI events emitted at the given height by the begin block, the successful
+    /// transactions and the end block, in the order in which they were emitted.
+    fn block_events(&self, height: u64) -> Result<Vec<AbciEvent>>;
+}
+
+/// A [`BlockSource`] querying the `/block` and `/block_results` RPC endpoints of a Tendermint node.
+pub struct RpcBlockSource {
+    rpc_client: HttpClient,
+    /// Tokio runtime
+    rt: Arc<TokioRuntime>,
+}
+
+impl RpcBlockSource {
+    pub fn new(rpc_client: HttpClient, rt: Arc<TokioRuntime>) -> Self {
+        Self { rpc_client, rt }
+    }
+}
+
+impl BlockSource for RpcBlockSource {
+    fn latest_height(&self) -> Result<u64> {
+        let response = self
+            .rt
+            .block_on(self.rpc_client.latest_block())
+            .map_err(Error::rpc)?;
+
+        Ok(response.block.header.height.value())
+    }
+
+    fn block_events(&self, height: u64) -> Result<Vec<AbciEvent>> {
+        let height =
+            TmHeight::try_from(height).map_err(|e| Error::collect_events_failed(e.to_string()))?;
+
+        let response = self
+            .rt
+            .block_on(self.rpc_client.block_results(height))
+            .map_err(Error::rpc)?;
+
+        let mut events = response.begin_block_events.unwrap_or_default();
+
+        // The events of failed transactions were reverted.
+        events.extend(
+            response
+                .txs_results
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|tx_result| tx_result.code.is_ok())
+                .flat_map(|tx_result| tx_result.events),
+        );
+
+        events.extend(response.end_block_events.unwrap_or_default());
+
+        Ok(events)
+    }
+}
+
+/// Poll a Tendermint node over RPC for the events of each new block, and
+/// send them to the event handler as batches, one per height.
+///
+/// This is an alternative to the [`EventMonitor`](crate::event::monitor::EventMonitor),
+/// for nodes whose WebSocket endpoint is disabled or unreliable. As the events are pulled
+/// height by height, the events of the blocks committed while the node was unreachable
+/// are collected once it is reachable again. Likewise, if the last collected height is
+/// recorded in an [`EventCursorStore`], the events of the blocks committed while the
+/// relayer was stopped are collected after a restart.
+pub struct PullEventSource<Source = RpcBlockSource> {
+    chain_id: ChainId,
+    /// The node to poll
+    source: Source,
+    /// Interval at which the node is polled for new blocks
+    poll_interval: Duration,
+    /// Channel to handler where the event source for this chain sends the events
+    tx_batch: channel::Sender<Result<EventBatch>>,
+    /// Channel where to receive commands
+    rx_cmd: channel::Receiver<MonitorCmd>,
+    /// The last height whose events were collected
+    last_height: Option<u64>,
+    /// The persistent record of `last_height`, if enabled
+    cursor_store: Option<EventCursorStore>,
+    /// How many consecutive polls failed to collect the events at `last_height + 1`
+    failures: usize,
+}
+
+impl<Source: BlockSource> PullEventSource<Source> {
+    /// Create an event source polling the given node, which resumes after the height
+    /// recorded in `cursor_store` if any, or else starts from the latest height.
+    pub fn new(
+        chain_id: ChainId,
+        source: Source,
+        poll_interval: Duration,
+        cursor_store: Option<EventCursorStore>,
+    ) -> (Self, EventReceiver, TxMonitorCmd) {
+        let (tx_batch, rx_batch) = channel::unbounded();
+        let (tx_cmd, rx_cmd) = channel::unbounded();
+
+        let last_height = cursor_store.as_ref().and_then(|store| {
+            store.last_height().unwrap_or_else(|e| {
+                warn!(
+                    "[{}] failed to read the last collected height, starting from the latest height: {}",
+                    chain_id, e
+                );
+                None
+            })
+        });
+
+        if let Some(height) = last_height {
+            debug!(
+                "[{}] resuming the collection of the events after height {}",
+                chain_id, height
+            );
+        }
+
+        let event_source = Self {
+            chain_id,
+            source,
+            poll_interval,
+            tx_batch,
+            rx_cmd,
+            last_height,
+            cursor_store,
+            failures: 0,
+        };
+
+        (event_source, rx_batch, tx_cmd)
+    }
+
+    /// Event source loop
+    pub fn run(mut self) {
+        debug!(chain = %self.chain_id, "starting pull event source");
+
+        loop {
+            if let Next::Abort = self.poll() {
+                break;
+            }
+
+            match self.rx_cmd.recv_timeout(self.poll_interval) {
+                Ok(MonitorCmd::Shutdown) | Err(channel::RecvTimeoutError::Disconnected) => break,
+                Err(channel::RecvTimeoutError::Timeout) => {}
+            }
+        }
+
+        debug!("[{}] pull event source is shutting down", self.chain_id);
+    }
+
+    /// Collect the events of all the blocks committed since the last poll.
+    fn poll(&mut self) -> Next {
+        let latest_height = match self.source.latest_height() {
+            Ok(height) => height,
+            Err(e) => {
+                error!(
+                    "[{}] failed to query the latest height: {}",
+                    self.chain_id, e
+                );
+                return Next::Continue;
+            }
+        };
+
+        // Start from the latest height if no height was collected nor recorded yet.
+        let from = self.last_height.map_or(latest_height, |h| h + 1);
+
+        for height in from..=latest_height {
+            if let Ok(MonitorCmd::Shutdown) = self.rx_cmd.try_recv() {
+                return Next::Abort;
+            }
+
+            match self.collect_batch(height) {
+                Ok(batch) => {
+                    self.failures = 0;
+                    self.update_last_height(height);
+
+                    if let Err(e) = self.process_batch(batch) {
+                        error!("[{}] {}", self.chain_id, e);
+                        return Next::Abort;
+                    }
+                }
+                Err(e) => {
+                    self.failures += 1;
+
+                    warn!(
+                        "[{}] failed to collect the events at height {} ({}/{}): {}",
+                        self.chain_id, height, self.failures, MAX_FAILURES, e
+                    );
+
+                    if self.failures >= MAX_FAILURES {
+                        self.skip_to(height, latest_height);
+                    }
+
+                    // Retry from the same height at the next poll.
+                    return Next::Continue;
+                }
+            }
+        }
+
+        Next::Continue
+    }
+
+    /// Give up on collecting the events from height `from` to `to`, e.g. because the
+    /// node pruned them, and notify the subscribers that these events were missed.
+    fn skip_to(&mut self, from: u64, to: u64) {
+        error!(
+            "[{}] giving up on collecting the events from height {} to {}",
+            self.chain_id, from, to
+        );
+
+        self.failures = 0;
+        self.update_last_height(to);
+
+        let error = Error::missed_heights(self.chain_id.clone(), from, to);
+
+        if self.tx_batch.send(Err(error)).is_err() {
+            error!("[{}] {}", self.chain_id, Error::channel_send_failed());
+        }
+    }
+
+    fn update_last_height(&mut self, height: u64) {
+        self.last_height = Some(height);
+
+        if let Some(store) = &self.cursor_store {
+            if let Err(e) = store.update_last_height(height) {
+                warn!(
+                    "[{}] failed to record the last collected height {}: {}",
+                    self.chain_id, height, e
+                );
+            }
+        }
+    }
+
+    /// Collect the IBC events emitted at the given height, in the order in which they
+    /// were emitted, preceded by the `NewBlock` event.
+    fn collect_batch(&self, height: u64) -> Result<EventBatch> {
+        let abci_events = self.source.block_events(height)?;
+
+        let height = Height::new(self.chain_id.version(), height)
+            .map_err(|e| Error::collect_events_failed(e.to_string()))?;
+
+        let mut events: Vec<IbcEvent> = vec![NewBlock::new(height).into()];
+
+        events.extend(
+            abci_events
+                .iter()
+                .filter_map(|abci_event| ibc_event_from_abci_event(height, abci_event)),
+        );
+
+        trace!(
+            "[{}] collected {} events at height {}",
+            self.chain_id,
+            events.len(),
+            height
+        );
+
+        Ok(EventBatch {
+            chain_id: self.chain_id.clone(),
+            tracking_id: TrackingId::new_uuid(),
+            height,
+            events,
+        })
+    }
+
+    fn process_batch(&self, batch: EventBatch) -> Result<()> {
+        self.tx_batch
+            .send(Ok(batch))
+            .map_err(|_| Error::channel_send_failed())?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use tendermint::abci::Event as AbciEvent;
+    use test_log::test;
+
+    use ibc::core::ics24_host::identifier::ChainId;
+
+    use super::{BlockSource, PullEventSource, MAX_FAILURES};
+    use crate::event::monitor::{Error, ErrorDetail, EventReceiver, Result};
+    use crate::store::EventCursorStore;
+
+    /// A node whose results are unavailable at the heights in `unavailable`.
+    struct StubSource {
+        latest_height: u64,
+        unavailable: Vec<u64>,
+    }
+
+    impl BlockSource for StubSource {
+        fn latest_height(&self) -> Result<u64> {
+            Ok(self.latest_height)
+        }
+
+        fn block_events(&self, height: u64) -> Result<Vec<AbciEvent>> {
+            if self.unavailable.contains(&height) {
+                Err(Error::collect_events_failed(format!(
+                    "no results at height {}",
+                    height
+                )))
+            } else {
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    fn event_source(
+        latest_height: u64,
+        cursor_store: Option<EventCursorStore>,
+    ) -> (PullEventSource<StubSource>, EventReceiver) {
+        let source = StubSource {
+            latest_height,
+            unavailable: Vec::new(),
+        };
+
+        let (event_source, rx_batch, _tx_cmd) = PullEventSource::new(
+            ChainId::new("ibc".to_string(), 0),
+            source,
+            Duration::from_secs(1),
+            cursor_store,
+        );
+
+        (event_source, rx_batch)
+    }
+
+    /// The heights of the batches received, or the heights reported as missed.
+    fn received(rx_batch: &EventReceiver) -> Vec<core::result::Result<u64, (u64, u64)>> {
+        rx_batch
+            .try_iter()
+            .map(|batch| match batch {
+                Ok(batch) => Ok(batch.height.revision_height()),
+                Err(Error(ErrorDetail::MissedHeights(e), _)) => Err((e.from, e.to)),
+                Err(e) => panic!("unexpected error: {}", e),
+            })
+            .collect()
+    }
+
+    fn temp_cursor_store() -> (EventCursorStore, std::path::PathBuf) {
+        let dir = std::env::temp_dir().join(format!("hermes-event-store-{}", uuid::Uuid::new_v4()));
+        (EventCursorStore::open(&dir).unwrap(), dir)
+    }
+
+    #[test]
+    fn poll_starts_from_the_latest_height() {
+        let (mut event_source, rx_batch) = event_source(5, None);
+
+        event_source.poll();
+        assert_eq!(received(&rx_batch), vec![Ok(5)]);
+
+        event_source.source.latest_height = 7;
+        event_source.poll();
+        assert_eq!(received(&rx_batch), vec![Ok(6), Ok(7)]);
+    }
+
+    #[test]
+    fn poll_resumes_after_the_recorded_height() {
+        let (cursor_store, dir) = temp_cursor_store();
+        cursor_store.update_last_height(2).unwrap();
+
+        let (mut event_source, rx_batch) = event_source(5, Some(cursor_store.clone()));
+        assert_eq!(event_source.last_height, Some(2));
+
+        event_source.poll();
+        assert_eq!(received(&rx_batch), vec![Ok(3), Ok(4), Ok(5)]);
+        assert_eq!(cursor_store.last_height().unwrap(), Some(5));
+
+        drop(event_source);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn poll_skips_the_heights_failing_repeatedly() {
+        let (mut event_source, rx_batch) = event_source(3, None);
+
+        event_source.poll();
+        assert_eq!(received(&rx_batch), vec![Ok(3)]);
+
+        event_source.source.latest_height = 6;
+        event_source.source.unavailable = vec![4];
+
+        // The failing height is retried at each poll, without collecting the next ones.
+        for _ in 1..MAX_FAILURES {
+            event_source.poll();
+        }
+        assert!(received(&rx_batch).is_empty());
+        assert_eq!(event_source.last_height, Some(3));
+
+        event_source.poll();
+        assert_eq!(received(&rx_batch), vec![Err((4, 6))]);
+        assert_eq!(event_source.last_height, Some(6));
+        assert_eq!(event_source.failures, 0);
+
+        event_source.source.latest_height = 7;
+        event_source.poll();
+        assert_eq!(received(&rx_batch), vec![Ok(7)]);
+    }
+
+    #[test]
+    fn skip_to_reports_the_missed_heights() {
+        let (cursor_store, dir) = temp_cursor_store();
+
+        let (mut event_source, rx_batch) = event_source(10, Some(cursor_store.clone()));
+        assert_eq!(event_source.last_height, None);
+
+        event_source.failures = 3;
+        event_source.skip_to(2, 8);
+
+        assert_eq!(received(&rx_batch), vec![Err((2, 8))]);
+        assert_eq!(event_source.last_height, Some(8));
+        assert_eq!(event_source.failures, 0);
+        assert_eq!(cursor_store.last_height().unwrap(), Some(8));
+
+        drop(event_source);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
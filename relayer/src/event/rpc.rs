@@ -1,8 +1,10 @@
 use alloc::collections::BTreeMap as HashMap;
 use core::convert::TryFrom;
 
+use tendermint::abci::Event as AbciEvent;
 use tendermint_rpc::{event::Event as RpcEvent, event::EventData as RpcEventData};
 
+use ibc::applications::interchain_query::events as QueryEvents;
 use ibc::core::ics02_client::{events as ClientEvents, height::Height};
 use ibc::core::ics03_connection::events as ConnectionEvents;
 use ibc::core::ics04_channel::events as ChannelEvents;
@@ -110,7 +112,8 @@ use crate::event::monitor::queries;
 /// ```
 /// {Begin,End}Block events however do not have any such `message.action` associated with them, so
 /// this doesn't work. For this reason, we extract block events in the following order ->
-/// OpenInit -> OpenTry -> OpenAck -> OpenConfirm -> SendPacket -> CloseInit -> CloseConfirm.
+/// OpenInit -> OpenTry -> OpenAck -> OpenConfirm -> SendPacket -> CloseInit -> CloseConfirm ->
+/// CrossChainQuery.
 pub fn get_all_events(
     chain_id: &ChainId,
     result: RpcEvent,
@@ -172,6 +175,13 @@ pub fn get_all_events(
                         vals.push((height, chan_event));
                     }
                 }
+                if query == queries::interchain_query().to_string() {
+                    if let Some(mut query_event) = QueryEvents::try_from_tx(abci_event) {
+                        query_event.set_height(height);
+                        tracing::trace!("extracted interchain_query event {}", query_event);
+                        vals.push((height, query_event));
+                    }
+                }
             }
         }
         _ => {}
@@ -180,6 +190,20 @@ pub fn get_all_events(
     Ok(vals)
 }
 
+/// Extract the IBC event, if any, from an ABCI event emitted at the given height,
+/// either by a transaction or in `BeginBlock` or `EndBlock`, as returned by
+/// the `/block_results` RPC endpoint.
+pub fn ibc_event_from_abci_event(height: Height, abci_event: &AbciEvent) -> Option<IbcEvent> {
+    let mut event = ClientEvents::try_from_tx(abci_event)
+        .or_else(|| ConnectionEvents::try_from_tx(abci_event))
+        .or_else(|| ChannelEvents::try_from_tx(abci_event))
+        .or_else(|| QueryEvents::try_from_tx(abci_event))?;
+
+    event.set_height(height);
+
+    Some(event)
+}
+
 fn extract_block_events(
     height: Height,
     block_events: &HashMap<String, Vec<String>>,
@@ -253,5 +277,10 @@ fn extract_block_events(
         extract_events(height, block_events, "channel_close_confirm", "channel_id"),
         height,
     );
+    append_events::<QueryEvents::SendQuery>(
+        &mut events,
+        extract_events(height, block_events, "cross_chain_query", "query_id"),
+        height,
+    );
     events
 }
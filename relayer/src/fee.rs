@@ -0,0 +1,162 @@
+//! Registration of the accounts to which the ICS29 fees earned by the relayer are paid.
+
+use flex_error::define_error;
+use tendermint::abci::transaction::Hash as TxHash;
+use tracing::{debug, info};
+
+use ibc::applications::fee::msgs::register_counterparty_payee::MsgRegisterCounterpartyPayee;
+use ibc::applications::fee::msgs::register_payee::MsgRegisterPayee;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::signer::Signer;
+use ibc::tx_msg::Msg;
+use ibc_proto::google::protobuf::Any;
+
+use crate::chain::handle::ChainHandle;
+use crate::chain::requests::QueryCounterpartyPayeeRequest;
+use crate::chain::tracking::TrackedMsgs;
+use crate::error::Error;
+
+define_error! {
+    FeeError {
+        Query
+            { chain_id: ChainId }
+            [ Error ]
+            |e| { format!("failed during query to chain {0}", e.chain_id) },
+
+        Key
+            { chain_id: ChainId }
+            [ Error ]
+            |e| { format!("failed to get the signer of chain {0}", e.chain_id) },
+
+        Submit
+            { chain_id: ChainId }
+            [ Error ]
+            |e| { format!("failed while submitting the registration message to chain {0}", e.chain_id) },
+
+        TxResponse
+            { chain_id: ChainId, log: String }
+            |e| { format!("registration transaction on chain {0} failed: {1}", e.chain_id, e.log) },
+
+        EmptyResponse
+            { chain_id: ChainId }
+            |e| { format!("no response to the registration transaction on chain {0}", e.chain_id) },
+    }
+}
+
+/// Registers `payee` as the account to which the acknowledgement and timeout fees earned by the
+/// relayer on the given channel of `chain` are paid.
+pub fn register_payee<Chain: ChainHandle>(
+    chain: &Chain,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    payee: Signer,
+) -> Result<TxHash, FeeError> {
+    let relayer = chain
+        .get_signer()
+        .map_err(|e| FeeError::key(chain.id(), e))?;
+
+    let msg = MsgRegisterPayee {
+        port_id: port_id.clone(),
+        channel_id: channel_id.clone(),
+        relayer,
+        payee,
+    };
+
+    send_registration(chain, msg.to_any(), "RegisterPayee")
+}
+
+/// Registers `counterparty_payee`, an account on the counterparty chain, as the account to which
+/// the receive fees earned by the relayer for the packets it relays to the given channel of
+/// `chain` are paid.
+pub fn register_counterparty_payee<Chain: ChainHandle>(
+    chain: &Chain,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    counterparty_payee: Signer,
+) -> Result<TxHash, FeeError> {
+    let relayer = chain
+        .get_signer()
+        .map_err(|e| FeeError::key(chain.id(), e))?;
+
+    let msg = MsgRegisterCounterpartyPayee {
+        port_id: port_id.clone(),
+        channel_id: channel_id.clone(),
+        relayer,
+        counterparty_payee,
+    };
+
+    send_registration(chain, msg.to_any(), "RegisterCounterpartyPayee")
+}
+
+/// Registers the relayer's address on `counterparty_chain` as its counterparty payee on the given
+/// channel of `chain`, unless a counterparty payee is already registered.
+///
+/// Returns whether a registration was submitted.
+pub fn register_missing_counterparty_payee<Chain: ChainHandle, Counterparty: ChainHandle>(
+    chain: &Chain,
+    counterparty_chain: &Counterparty,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<bool, FeeError> {
+    let relayer = chain
+        .get_signer()
+        .map_err(|e| FeeError::key(chain.id(), e))?;
+
+    let registered = chain
+        .query_counterparty_payee(QueryCounterpartyPayeeRequest {
+            channel_id: channel_id.clone(),
+            relayer,
+        })
+        .map_err(|e| FeeError::query(chain.id(), e))?;
+
+    if let Some(counterparty_payee) = registered {
+        debug!(
+            chain = %chain.id(),
+            channel = %channel_id,
+            "counterparty payee {} already registered",
+            counterparty_payee
+        );
+
+        return Ok(false);
+    }
+
+    // The fees are paid to the relayer's key, even if it submits the messages on behalf of an
+    // authz granter.
+    let counterparty_payee = counterparty_chain
+        .get_key_address()
+        .map_err(|e| FeeError::key(counterparty_chain.id(), e))?;
+
+    let tx_hash = register_counterparty_payee(chain, port_id, channel_id, counterparty_payee)?;
+
+    info!(
+        chain = %chain.id(),
+        channel = %channel_id,
+        "registered counterparty payee on {} in transaction {}",
+        counterparty_chain.id(),
+        tx_hash
+    );
+
+    Ok(true)
+}
+
+fn send_registration<Chain: ChainHandle>(
+    chain: &Chain,
+    msg: Any,
+    tracking_id: &'static str,
+) -> Result<TxHash, FeeError> {
+    // The fee module emits no IBC events which could confirm the registration,
+    // so only wait for the transaction to pass the mempool checks.
+    let responses = chain
+        .send_messages_and_wait_check_tx(TrackedMsgs::new_single(msg, tracking_id))
+        .map_err(|e| FeeError::submit(chain.id(), e))?;
+
+    let response = responses
+        .first()
+        .ok_or_else(|| FeeError::empty_response(chain.id()))?;
+
+    if response.code.is_err() {
+        return Err(FeeError::tx_response(chain.id(), response.log.to_string()));
+    }
+
+    Ok(response.hash)
+}
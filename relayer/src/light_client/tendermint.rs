@@ -29,16 +29,21 @@ use ibc::{
     },
     downcast,
 };
-use tracing::trace;
+use tracing::{trace, warn};
 
 use crate::{chain::cosmos::CosmosSdkChain, config::ChainConfig, error::Error};
 
+use self::store::LightBlockStore;
 use super::Verified;
 
+pub mod store;
+
 pub struct LightClient {
     chain_id: ChainId,
     peer_id: PeerId,
     io: components::io::ProdIo,
+    /// The persistent store of the verified light blocks, if enabled
+    store: Option<LightBlockStore>,
 }
 
 impl super::LightClient<CosmosSdkChain> for LightClient {
@@ -65,7 +70,7 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
             TMHeight::try_from(target.revision_height()).map_err(Error::invalid_height)?;
 
         let client = self.prepare_client(client_state)?;
-        let mut state = self.prepare_state(trusted)?;
+        let mut state = self.prepare_state(trusted, target_height)?;
 
         // Verify the target header
         let target = client
@@ -83,6 +88,8 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
             .sorted_by_key(LightBlock::height)
             .collect_vec();
 
+        self.store_verified(&target, &supporting, client_state);
+
         Ok(Verified { target, supporting })
     }
 
@@ -166,9 +173,16 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
 }
 
 impl LightClient {
-    pub fn from_config(config: &ChainConfig, peer_id: PeerId) -> Result<Self, Error> {
-        let rpc_client = rpc::HttpClient::new(config.rpc_addr.clone())
-            .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))?;
+    /// Creates a light client fetching the light blocks from the node at `rpc_addr`.
+    /// The verified light blocks are cached in `store`, if any.
+    pub fn from_config(
+        config: &ChainConfig,
+        rpc_addr: rpc::Url,
+        peer_id: PeerId,
+        store: Option<LightBlockStore>,
+    ) -> Result<Self, Error> {
+        let rpc_client =
+            rpc::HttpClient::new(rpc_addr.clone()).map_err(|e| Error::rpc(rpc_addr, e))?;
 
         let io = components::io::ProdIo::new(peer_id, rpc_client, Some(config.rpc_timeout));
 
@@ -176,6 +190,7 @@ impl LightClient {
             chain_id: config.id.clone(),
             peer_id,
             io,
+            store,
         })
     }
 
@@ -210,7 +225,11 @@ impl LightClient {
         ))
     }
 
-    fn prepare_state(&self, trusted: ibc::Height) -> Result<LightClientState, Error> {
+    fn prepare_state(
+        &self,
+        trusted: ibc::Height,
+        target_height: TMHeight,
+    ) -> Result<LightClientState, Error> {
         let trusted_height =
             TMHeight::try_from(trusted.revision_height()).map_err(Error::invalid_height)?;
 
@@ -219,12 +238,64 @@ impl LightClient {
         let mut store = MemoryStore::new();
         store.insert(trusted_block, Status::Trusted);
 
+        // Reuse the blocks verified by the previous verifications, so that the bisection
+        // does not fetch them again. They must nonetheless be verified again starting from
+        // the trusted block, as the on-chain client only trusts its own consensus states,
+        // hence they are inserted as unverified.
+        if let Some(light_store) = &self.store {
+            match light_store.range(trusted_height, target_height) {
+                Ok(blocks) => {
+                    for block in blocks {
+                        store.insert(block, Status::Unverified);
+                    }
+                }
+                Err(e) => {
+                    warn!(chain = %self.chain_id, "failed to read the light client store: {}", e)
+                }
+            }
+        }
+
         Ok(LightClientState::new(store))
     }
 
+    /// Records the verified blocks in the persistent store, if any, and prunes the
+    /// blocks which are out of the trusting period of the client.
+    fn store_verified(
+        &self,
+        target: &LightBlock,
+        supporting: &[LightBlock],
+        client_state: &AnyClientState,
+    ) {
+        let light_store = match &self.store {
+            Some(light_store) => light_store,
+            None => return,
+        };
+
+        let result = supporting
+            .iter()
+            .chain(core::iter::once(target))
+            .try_for_each(|block| light_store.insert(block));
+
+        if let Err(e) = result {
+            warn!(chain = %self.chain_id, "failed to record the verified light blocks: {}", e);
+        }
+
+        if let Some(client_state) = downcast!(client_state => AnyClientState::Tendermint) {
+            if let Err(e) = light_store.prune(client_state.trusting_period) {
+                warn!(chain = %self.chain_id, "failed to prune the light client store: {}", e);
+            }
+        }
+    }
+
     fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, Error> {
         use tendermint_light_client::components::io::Io;
 
+        if let (AtHeight::At(height), Some(light_store)) = (&height, &self.store) {
+            if let Ok(Some(block)) = light_store.get(*height) {
+                return Ok(block);
+            }
+        }
+
         self.io
             .fetch_light_block(height)
             .map_err(|e| Error::light_client_io(self.chain_id.to_string(), e))
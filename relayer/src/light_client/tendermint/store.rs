@@ -0,0 +1,196 @@
+//! Persistent store of the light blocks verified by the Tendermint light client,
+//! so that the blocks verified during a client update are not fetched again
+//! from the full node by the next updates, including after a restart.
+
+use core::time::Duration;
+use std::path::{Path, PathBuf};
+
+use tendermint::Time;
+use tendermint_light_client_verifier::types::{Height as TMHeight, LightBlock};
+use tracing::debug;
+
+use ibc::core::ics24_host::identifier::ChainId;
+
+use crate::config::ChainConfig;
+use crate::store::StoreError;
+
+/// The default folder of the light block stores, relative to the home directory.
+/// Each chain has its own store in a sub-folder named after the chain identifier.
+pub const LIGHT_STORE_DEFAULT_FOLDER: &str = ".hermes/light_store/";
+
+/// An on-disk store of verified light blocks, indexed by height.
+///
+/// The store can be cloned cheaply, all the clones share the same database.
+#[derive(Clone, Debug)]
+pub struct LightBlockStore {
+    blocks: sled::Tree,
+}
+
+impl LightBlockStore {
+    /// Opens the light block store configured for the given chain, if it is enabled.
+    pub fn from_config(config: &ChainConfig) -> Result<Option<Self>, StoreError> {
+        if !config.light_store.enabled {
+            return Ok(None);
+        }
+
+        let path = match &config.light_store.path {
+            Some(path) => path.clone(),
+            None => default_light_store_path(&config.id)?,
+        };
+
+        Self::open(&path).map(Some)
+    }
+
+    /// Opens the store at the given path, creating it if it does not exist.
+    pub fn open(path: &Path) -> Result<Self, StoreError> {
+        let db = sled::open(path).map_err(|e| StoreError::open(path.to_path_buf(), e))?;
+
+        Ok(Self {
+            blocks: db.open_tree("light_blocks").map_err(StoreError::db)?,
+        })
+    }
+
+    /// Returns the light block at the given height, if it is in the store.
+    pub fn get(&self, height: TMHeight) -> Result<Option<LightBlock>, StoreError> {
+        let key = key(height);
+
+        match self.blocks.get(key).map_err(StoreError::db)? {
+            Some(value) => decode(&key, &value).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the light blocks with a height greater than `from` and lower
+    /// than or equal to `to`, by ascending height.
+    pub fn range(&self, from: TMHeight, to: TMHeight) -> Result<Vec<LightBlock>, StoreError> {
+        self.blocks
+            .range(key(from.increment())..=key(to))
+            .map(|entry| {
+                let (k, value) = entry.map_err(StoreError::db)?;
+                decode(&k, &value)
+            })
+            .collect()
+    }
+
+    /// Records a verified light block.
+    pub fn insert(&self, block: &LightBlock) -> Result<(), StoreError> {
+        let value = serde_json::to_vec(block).map_err(StoreError::encode)?;
+
+        self.blocks
+            .insert(key(block.height()), value)
+            .map_err(StoreError::db)?;
+
+        Ok(())
+    }
+
+    /// Removes the light blocks whose header is older than `trusting_period`,
+    /// as they can no longer be trusted.
+    pub fn prune(&self, trusting_period: Duration) -> Result<usize, StoreError> {
+        let now = Time::now();
+        let mut pruned = 0;
+
+        // The blocks are ordered by height, hence by time,
+        // so stop at the first block within the trusting period.
+        for entry in self.blocks.iter() {
+            let (k, value) = entry.map_err(StoreError::db)?;
+            let block = decode(&k, &value)?;
+
+            let expired = now
+                .duration_since(block.signed_header.header.time)
+                .map_or(false, |elapsed| elapsed > trusting_period);
+
+            if !expired {
+                break;
+            }
+
+            self.blocks.remove(k).map_err(StoreError::db)?;
+            pruned += 1;
+        }
+
+        if pruned > 0 {
+            debug!("pruned {} light blocks from the light client store", pruned);
+        }
+
+        Ok(pruned)
+    }
+}
+
+/// The keys are the big endian heights, so that the blocks are ordered by height.
+fn key(height: TMHeight) -> [u8; 8] {
+    height.value().to_be_bytes()
+}
+
+fn decode(key: &[u8], value: &[u8]) -> Result<LightBlock, StoreError> {
+    serde_json::from_slice(value).map_err(|e| {
+        let height = key.try_into().map(u64::from_be_bytes).unwrap_or_default();
+        StoreError::decode(height.to_string(), e)
+    })
+}
+
+fn default_light_store_path(chain_id: &ChainId) -> Result<PathBuf, StoreError> {
+    let home = dirs_next::home_dir().ok_or_else(StoreError::home_location_unavailable)?;
+
+    Ok(home
+        .join(LIGHT_STORE_DEFAULT_FOLDER)
+        .join(chain_id.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LightBlockStore;
+
+    use core::time::Duration;
+
+    use tendermint::Time;
+    use tendermint_light_client_verifier::types::{Height as TMHeight, LightBlock};
+    use tendermint_testgen::{Generator, LightBlock as TestgenLightBlock};
+    use test_log::test;
+
+    fn light_block(height: u64) -> LightBlock {
+        let time = Time::from_unix_timestamp(height as i64, 0).unwrap();
+        let block = TestgenLightBlock::new_default_with_time_and_chain_id(
+            "ibc-0".to_string(),
+            time,
+            height,
+        )
+        .generate()
+        .unwrap();
+
+        LightBlock::new(
+            block.signed_header,
+            block.validators,
+            block.next_validators,
+            block.provider,
+        )
+    }
+
+    #[test]
+    fn light_block_store() {
+        let dir = std::env::temp_dir().join(format!("hermes-light-store-{}", uuid::Uuid::new_v4()));
+        let store = LightBlockStore::open(&dir).unwrap();
+
+        let height = |h: u64| TMHeight::try_from(h).unwrap();
+
+        for h in 1..=5 {
+            store.insert(&light_block(h)).unwrap();
+        }
+
+        assert_eq!(store.get(height(3)).unwrap(), Some(light_block(3)));
+        assert_eq!(store.get(height(6)).unwrap(), None);
+
+        let heights: Vec<_> = store
+            .range(height(2), height(4))
+            .unwrap()
+            .iter()
+            .map(|block| block.height().value())
+            .collect();
+        assert_eq!(heights, vec![3, 4]);
+
+        // The blocks are timestamped shortly after the Unix epoch.
+        assert_eq!(store.prune(Duration::MAX).unwrap(), 0);
+        assert_eq!(store.prune(Duration::from_secs(60)).unwrap(), 5);
+        assert_eq!(store.get(height(3)).unwrap(), None);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -8,6 +8,7 @@ use crate::channel::ChannelError;
 use crate::connection::ConnectionError;
 use crate::error::Error;
 use crate::foreign_client::{ForeignClientError, HasExpiredOrFrozenError};
+use crate::store::StoreError;
 use crate::supervisor::Error as SupervisorError;
 use crate::transfer::TransferError;
 
@@ -17,6 +18,10 @@ define_error! {
             [ Error ]
             |_| { "link failed with underlying error" },
 
+        Store
+            [ StoreError ]
+            |_| { "failed to access the relayer store" },
+
         Supervisor
             [ SupervisorError ]
             |_| { "error originating from the supervisor" },
@@ -1,8 +1,10 @@
 use core::iter::Iterator;
+use core::str::FromStr;
 use core::time::Duration;
-use std::time::Instant;
+use std::time::{Instant, SystemTime};
 
-use tracing::{debug, error, trace, trace_span};
+use tendermint::abci::transaction::Hash as TxHash;
+use tracing::{debug, error, trace, trace_span, warn};
 
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc::events::IbcEvent;
@@ -11,6 +13,7 @@ use crate::chain::requests::{QueryTxHash, QueryTxRequest};
 use crate::chain::tracking::TrackingId;
 use crate::error::Error as RelayerError;
 use crate::link::{error::LinkError, RelayPath};
+use crate::store::{PacketOutcome, PathStore, PendingTx};
 use crate::telemetry;
 use crate::util::queue::Queue;
 use crate::{
@@ -49,6 +52,11 @@ pub struct PendingTxs<Chain> {
     pub port_id: PortId,
     pub counterparty_chain_id: ChainId,
     pub pending_queue: Queue<PendingData>,
+    /// The store in which the pending transactions are persisted, if any.
+    store: Option<PathStore>,
+    /// The pending transactions restored from the store, which were submitted
+    /// before the relayer restarted.
+    restored_queue: Queue<PendingTx>,
 }
 
 impl<Chain> PendingTxs<Chain> {
@@ -64,6 +72,8 @@ impl<Chain> PendingTxs<Chain> {
             port_id,
             counterparty_chain_id,
             pending_queue: Queue::new(),
+            store: None,
+            restored_queue: Queue::new(),
         }
     }
 }
@@ -73,6 +83,69 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
         self.chain.id()
     }
 
+    /// Persist the pending transactions to `store` from now on, and restore the
+    /// transactions submitted to this chain before the relayer restarted.
+    ///
+    /// The restored transactions which were committed in the meantime are reconciled
+    /// right away. The others are only confirmed later on if `confirm` is true, and
+    /// are otherwise dropped, such that packet clearing may relay their packets again.
+    pub fn restore(&mut self, store: PathStore, confirm: bool) -> Result<(), LinkError> {
+        let chain_id = self.chain_id();
+
+        for pending in store.pending_txs() {
+            if pending.chain_id != chain_id {
+                continue;
+            }
+
+            let first_tx_hash = pending.tx_hashes[0].clone();
+
+            match self.check_stored_tx_events(&pending) {
+                Ok(Some(events)) => {
+                    debug!(
+                        chain = %chain_id,
+                        "restored pending transaction {} was committed",
+                        first_tx_hash
+                    );
+
+                    record_outcomes(&store, &events);
+                }
+                Ok(None) | Err(_) if confirm && !pending.is_expired(TIMEOUT) => {
+                    debug!(
+                        chain = %chain_id,
+                        "restored pending transaction {} is not yet confirmed",
+                        first_tx_hash
+                    );
+
+                    self.restored_queue.push_back(pending);
+                    continue;
+                }
+                Ok(None) => {
+                    warn!(
+                        chain = %chain_id,
+                        "dropping restored pending transaction {}, its packets will be cleared again",
+                        first_tx_hash
+                    );
+                }
+                Err(e) => {
+                    warn!(
+                        chain = %chain_id,
+                        "dropping restored pending transaction {} which could not be queried, \
+                         its packets will be cleared again: {}",
+                        first_tx_hash, e
+                    );
+                }
+            }
+
+            store
+                .remove_pending_tx(&first_tx_hash)
+                .map_err(LinkError::store)?;
+        }
+
+        self.store = Some(store);
+
+        Ok(())
+    }
+
     /// Insert a new pending transaction to the back of the queue.
     pub fn insert_new_pending_tx(&self, r: AsyncReply, od: OperationalData) {
         let mut tx_hashes = Vec::new();
@@ -123,9 +196,123 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
             error_events,
         };
 
+        if let Some(store) = &self.store {
+            if let Err(e) = store.insert_pending_tx(&self.to_stored(&u)) {
+                error!("failed to persist pending transaction: {}", e);
+            }
+        }
+
         self.pending_queue.push_back(u);
     }
 
+    fn to_stored(&self, pending: &PendingData) -> PendingTx {
+        let mut sent_sequences = Vec::new();
+        let mut acked_sequences = Vec::new();
+
+        for msg in &pending.original_od.batch {
+            match &msg.event {
+                IbcEvent::WriteAcknowledgement(e) => acked_sequences.push(e.packet.sequence),
+                event => {
+                    if let Some(packet) = event.packet() {
+                        sent_sequences.push(packet.sequence);
+                    }
+                }
+            }
+        }
+
+        PendingTx {
+            chain_id: self.chain_id(),
+            tx_hashes: pending
+                .tx_hashes
+                .0
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            sent_sequences,
+            acked_sequences,
+            submitted_at: SystemTime::now(),
+        }
+    }
+
+    /// Remove the given pending transaction from the store, recording the outcome
+    /// of its packets if it was committed.
+    fn forget_stored(&self, pending: &PendingData, events: Option<&[IbcEvent]>) {
+        let store = match &self.store {
+            Some(store) => store,
+            None => return,
+        };
+
+        if let Some(events) = events {
+            record_outcomes(store, events);
+        }
+
+        if let Some(first_tx_hash) = pending.tx_hashes.0.first() {
+            if let Err(e) = store.remove_pending_tx(&first_tx_hash.to_string()) {
+                error!("failed to remove pending transaction from the store: {}", e);
+            }
+        }
+    }
+
+    fn check_stored_tx_events(
+        &self,
+        pending: &PendingTx,
+    ) -> Result<Option<Vec<IbcEvent>>, RelayerError> {
+        // Unparsable hashes can never be confirmed, consider them as not committed.
+        let tx_hashes = match pending
+            .tx_hashes
+            .iter()
+            .map(|hash| TxHash::from_str(hash))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(tx_hashes) => TxHashes(tx_hashes),
+            Err(_) => return Ok(None),
+        };
+
+        self.check_tx_events(&tx_hashes)
+    }
+
+    /// Try and confirm one of the pending transactions restored from the store,
+    /// dropping it from the store once it is committed or timed out.
+    fn process_restored(&self, timeout: Duration) {
+        let store = match &self.store {
+            Some(store) => store,
+            None => return,
+        };
+
+        if let Some(pending) = self.restored_queue.pop_front() {
+            let first_tx_hash = pending.tx_hashes[0].clone();
+
+            let done = match self.check_stored_tx_events(&pending) {
+                Ok(Some(events)) => {
+                    debug!("restored pending transaction {} confirmed", first_tx_hash);
+                    record_outcomes(store, &events);
+                    true
+                }
+                Ok(None) if pending.is_expired(timeout) => {
+                    error!(
+                        "timed out while confirming restored transaction {}",
+                        first_tx_hash
+                    );
+                    true
+                }
+                Ok(None) => false,
+                Err(e) => {
+                    error!(
+                        "error querying for restored transaction {}: {}. will retry again later",
+                        first_tx_hash, e
+                    );
+                    false
+                }
+            };
+
+            if !done {
+                self.restored_queue.push_back(pending);
+            } else if let Err(e) = store.remove_pending_tx(&first_tx_hash) {
+                error!("failed to remove pending transaction from the store: {}", e);
+            }
+        }
+    }
+
     fn check_tx_events(&self, tx_hashes: &TxHashes) -> Result<Option<Vec<IbcEvent>>, RelayerError> {
         let mut all_events = Vec::new();
         for hash in &tx_hashes.0 {
@@ -154,6 +341,8 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
         relay_path: &RelayPath<ChainA, ChainB>,
         resubmit: Option<impl FnOnce(OperationalData) -> Result<AsyncReply, LinkError>>,
     ) -> Result<Option<RelaySummary>, LinkError> {
+        self.process_restored(timeout);
+
         // We process pending transactions in a FIFO manner, so take from
         // the front of the queue.
         if let Some(pending) = self.pending_queue.pop_front() {
@@ -191,6 +380,8 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                         // relayer to resubmit the transaction to the chain again.
                         error!("timed out while confirming {}", tx_hashes);
 
+                        self.forget_stored(&pending, None);
+
                         match resubmit {
                             Some(f) => {
                                 // The pending tx needs to be resubmitted. This involves replacing the tx's
@@ -251,6 +442,8 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                         &self.counterparty_chain_id
                     );
 
+                    self.forget_stored(&pending, Some(&events));
+
                     // Append the events corresponding to errors from the pending tx.
                     events.extend(pending.error_events);
 
@@ -288,3 +481,25 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
         }
     }
 }
+
+/// Record the outcome of the packets whose relaying is confirmed by the given events.
+fn record_outcomes(store: &PathStore, events: &[IbcEvent]) {
+    for event in events {
+        let outcome = match event {
+            IbcEvent::ReceivePacket(_) | IbcEvent::WriteAcknowledgement(_) => {
+                PacketOutcome::Received
+            }
+            IbcEvent::AcknowledgePacket(_) => PacketOutcome::Acknowledged,
+            IbcEvent::TimeoutPacket(_) | IbcEvent::TimeoutOnClosePacket(_) => {
+                PacketOutcome::TimedOut
+            }
+            _ => continue,
+        };
+
+        if let Some(packet) = event.packet() {
+            if let Err(e) = store.record_outcome(packet.sequence, outcome, event.height()) {
+                error!("failed to record packet outcome: {}", e);
+            }
+        }
+    }
+}
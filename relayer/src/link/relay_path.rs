@@ -7,17 +7,23 @@ use ibc_proto::google::protobuf::Any;
 use itertools::Itertools;
 use tracing::{debug, error, info, span, trace, warn, Level};
 
+use crate::chain::counterparty::commitments_on_chain;
+use crate::chain::counterparty::packet_acknowledgements;
 use crate::chain::counterparty::unreceived_acknowledgements;
 use crate::chain::counterparty::unreceived_packets;
 use crate::chain::endpoint::ChainStatus;
 use crate::chain::handle::ChainHandle;
 use crate::chain::requests::IncludeProof;
+use crate::chain::requests::PageRequest;
 use crate::chain::requests::QueryChannelRequest;
 use crate::chain::requests::QueryClientEventRequest;
 use crate::chain::requests::QueryHeight;
 use crate::chain::requests::QueryHostConsensusStateRequest;
+use crate::chain::requests::QueryIncentivizedPacketsRequest;
 use crate::chain::requests::QueryNextSequenceReceiveRequest;
 use crate::chain::requests::QueryPacketCommitmentRequest;
+use crate::chain::requests::QueryPacketEventRangeRequest;
+use crate::chain::requests::QueryPacketReceiptRequest;
 use crate::chain::requests::QueryTxRequest;
 use crate::chain::requests::QueryUnreceivedAcksRequest;
 use crate::chain::requests::QueryUnreceivedPacketsRequest;
@@ -25,6 +31,8 @@ use crate::chain::tracking::TrackedMsgs;
 use crate::chain::tracking::TrackingId;
 use crate::channel::error::ChannelError;
 use crate::channel::Channel;
+use crate::config::filter::{FeeKind, FeePolicy};
+use crate::config::FeeFilter;
 use crate::event::monitor::EventBatch;
 use crate::foreign_client::{ForeignClient, ForeignClientError};
 use crate::link::error::{self, LinkError};
@@ -39,9 +47,11 @@ use crate::link::relay_sender::{AsyncReply, SubmitReply};
 use crate::link::relay_summary::RelaySummary;
 use crate::link::{pending, relay_sender};
 use crate::path::PathIdentifiers;
+use crate::store::{PathStore, RelayStore};
 use crate::telemetry;
 use crate::util::queue::Queue;
 use ibc::{
+    applications::fee::packet_fee::PacketFee,
     core::{
         ics02_client::{
             events::ClientMisbehaviour as ClientMisbehaviourEvent,
@@ -55,7 +65,7 @@ use ibc::{
                 recv_packet::MsgRecvPacket, timeout::MsgTimeout,
                 timeout_on_close::MsgTimeoutOnClose,
             },
-            packet::{Packet, PacketMsgType},
+            packet::{Packet, PacketMsgType, Receipt, Sequence},
         },
         ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
     },
@@ -68,6 +78,11 @@ use ibc::{
 
 const MAX_RETRIES: usize = 5;
 
+/// How many blocks behind the latest height of the source chain the last batch of events
+/// processed for a path may be, for the events committed since then to be backfilled when
+/// resuming from the store, rather than clearing all the pending packets of the path.
+pub const MAX_BACKFILL_BLOCKS: u64 = 1_000;
+
 /// Whether or not to resubmit packets when pending transactions
 /// fail to process within the given timeout duration.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -112,6 +127,9 @@ pub struct RelayPath<ChainA: ChainHandle, ChainB: ChainHandle> {
     // transactions if [`confirm_txes`] is true.
     pending_txs_src: PendingTxs<ChainA>,
     pending_txs_dst: PendingTxs<ChainB>,
+
+    // The store in which the relaying state of this path is persisted, if any.
+    store: Option<PathStore>,
 }
 
 impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
@@ -156,9 +174,121 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             confirm_txes: with_tx_confirmation,
             pending_txs_src: PendingTxs::new(src_chain, src_channel_id, src_port_id, dst_chain_id),
             pending_txs_dst: PendingTxs::new(dst_chain, dst_channel_id, dst_port_id, src_chain_id),
+
+            store: None,
         })
     }
 
+    /// Persists the relaying state of this path to `store` from now on, and resumes
+    /// from the state recorded before the relayer restarted.
+    ///
+    /// The transactions which were pending when the relayer stopped are reconciled
+    /// against the state of the chains, and the packets of those still in flight are
+    /// not cleared again. Returns the height of the last batch of events processed
+    /// for this path, if any.
+    pub fn resume_from_store(&mut self, store: &RelayStore) -> Result<Option<Height>, LinkError> {
+        let path_store = store
+            .for_path(
+                &self.src_chain().id(),
+                self.src_port_id(),
+                self.src_channel_id(),
+            )
+            .map_err(LinkError::store)?;
+
+        self.pending_txs_src
+            .restore(path_store.clone(), self.confirm_txes)?;
+        self.pending_txs_dst
+            .restore(path_store.clone(), self.confirm_txes)?;
+
+        let cursor = path_store.cursor().map_err(LinkError::store)?;
+
+        if let Some(height) = cursor {
+            info!(
+                "resuming relaying, last batch of events processed at height {}",
+                height
+            );
+        }
+
+        self.store = Some(path_store);
+
+        Ok(cursor)
+    }
+
+    /// Returns the sequences of the packets whose receive or timeout messages,
+    /// resp. acknowledgement messages, are in flight according to the store.
+    fn in_flight_sequences(&self) -> (Vec<Sequence>, Vec<Sequence>) {
+        self.store
+            .as_ref()
+            .map(PathStore::in_flight_sequences)
+            .unwrap_or_default()
+    }
+
+    /// Schedules the packets sent, and the acknowledgements written, on the source chain in
+    /// the blocks committed after `height`, e.g. while the relayer was stopped.
+    ///
+    /// Returns `false` without collecting any events if `height` is more than
+    /// [`MAX_BACKFILL_BLOCKS`] behind the latest height of the source chain, in which case
+    /// the pending packets of this path should be cleared instead.
+    pub fn backfill_from(&self, height: Height) -> Result<bool, LinkError> {
+        let latest_height = self
+            .src_chain()
+            .query_latest_height()
+            .map_err(|e| LinkError::query(self.src_chain().id(), e))?;
+
+        if latest_height.revision_number() != height.revision_number()
+            || latest_height.revision_height() > height.revision_height() + MAX_BACKFILL_BLOCKS
+        {
+            return Ok(false);
+        }
+
+        if latest_height <= height {
+            return Ok(true);
+        }
+
+        let mut batches: HashMap<Height, Vec<IbcEvent>> = HashMap::new();
+
+        for event_id in [WithBlockDataType::SendPacket, WithBlockDataType::WriteAck] {
+            let events = self
+                .src_chain()
+                .query_txs(QueryTxRequest::PacketRange(QueryPacketEventRangeRequest {
+                    event_id,
+                    port_id: self.src_port_id().clone(),
+                    channel_id: self.src_channel_id().clone(),
+                    from_height: height,
+                    to_height: latest_height,
+                }))
+                .map_err(|e| LinkError::query(self.src_chain().id(), e))?;
+
+            for event in events {
+                batches.entry(event.height()).or_default().push(event);
+            }
+        }
+
+        info!(
+            "backfilling the events of {} blocks, from height {} to {}",
+            batches.len(),
+            height,
+            latest_height
+        );
+
+        for (height, events) in batches {
+            self.update_schedule(EventBatch {
+                chain_id: self.src_chain().id(),
+                tracking_id: TrackingId::new_static("backfill"),
+                height,
+                events,
+            })?;
+        }
+
+        if let Some(store) = &self.store {
+            store
+                .update_cursor(latest_height)
+                .map_err(LinkError::store)?;
+        }
+
+        Ok(true)
+    }
+
     pub fn src_chain(&self) -> &ChainA {
         self.channel.src_chain()
     }
@@ -309,14 +439,14 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             .max_block_time)
     }
 
-    fn unordered_channel(&self) -> bool {
-        self.channel.ordering == Order::Unordered
-    }
-
     fn ordered_channel(&self) -> bool {
         self.channel.ordering == Order::Ordered
     }
 
+    fn ordered_allow_timeout_channel(&self) -> bool {
+        self.channel.ordering == Order::OrderedAllowTimeout
+    }
+
     pub fn build_update_client_on_dst(&self, height: Height) -> Result<Vec<Any>, LinkError> {
         let client = self.restore_dst_client();
         client
@@ -436,6 +566,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
     /// Generate & schedule operational data from the input `batch` of IBC events.
     pub fn update_schedule(&self, batch: EventBatch) -> Result<(), LinkError> {
+        let height = batch.height;
+
         // Collect relevant events from the incoming batch & adjust their height.
         let events = self.filter_relaying_events(batch.events, batch.tracking_id);
 
@@ -447,7 +579,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         });
 
         // Transform the events into operational data items
-        self.events_to_operational_data(events)
+        self.events_to_operational_data(events)?;
+
+        if let Some(store) = &self.store {
+            store.update_cursor(height).map_err(LinkError::store)?;
+        }
+
+        Ok(())
     }
 
     /// Produces and schedules operational data for this relaying path based on the input events.
@@ -513,6 +651,44 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             self.channel.connection_delay,
         );
 
+        let src_fee_filter = self
+            .src_chain()
+            .config()
+            .map_err(LinkError::relayer)?
+            .fee_filter;
+        let dst_fee_filter = self
+            .dst_chain()
+            .config()
+            .map_err(LinkError::relayer)?
+            .fee_filter;
+
+        // The fees escrowed for the packets sent on either end of the channel, queried at most
+        // once per batch, and only if the fee filter of that end sets minimum fees.
+        let mut src_packet_fees = None;
+        let mut dst_packet_fees = None;
+
+        // Packets on ordered channels have to be relayed in order, so skipping or deprioritizing
+        // one of them would block all the packets sent after it on the channel.
+        let ordered = self.ordered_channel();
+        if ordered
+            && (src_fee_filter
+                .min_fees(self.src_port_id(), self.src_channel_id())
+                .is_some()
+                || dst_fee_filter
+                    .min_fees(self.dst_port_id(), self.dst_channel_id())
+                    .is_some())
+        {
+            warn!(
+                "ignoring the fee filter on ordered channel {}/{}: its packets are relayed regardless of their fees",
+                self.src_port_id(),
+                self.src_channel_id()
+            );
+        }
+
+        // Messages of packets which are not incentivized enough, to be relayed after the others.
+        let mut dst_deprioritized = vec![];
+        let mut src_deprioritized = vec![];
+
         for event in input {
             trace!("processing event: {}", event);
             let (dst_msg, src_msg) = match event {
@@ -562,29 +738,84 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 _ => (None, None),
             };
 
+            // Packets sent from source are incentivized on source, while the acknowledgements
+            // written on source are for packets sent from, and incentivized on, destination.
+            let fee_check = match event {
+                _ if ordered => None,
+                IbcEvent::SendPacket(ref send_packet_ev) if dst_msg.is_some() => self
+                    .unincentivized_policy(
+                        self.src_chain(),
+                        &src_fee_filter,
+                        &mut src_packet_fees,
+                        &send_packet_ev.packet,
+                        FeeKind::Recv,
+                    )?,
+                IbcEvent::SendPacket(ref send_packet_ev) if src_msg.is_some() => self
+                    .unincentivized_policy(
+                        self.src_chain(),
+                        &src_fee_filter,
+                        &mut src_packet_fees,
+                        &send_packet_ev.packet,
+                        FeeKind::Timeout,
+                    )?,
+                IbcEvent::WriteAcknowledgement(ref write_ack_ev) if dst_msg.is_some() => self
+                    .unincentivized_policy(
+                        self.dst_chain(),
+                        &dst_fee_filter,
+                        &mut dst_packet_fees,
+                        &write_ack_ev.packet,
+                        FeeKind::Ack,
+                    )?,
+                _ => None,
+            };
+
+            let deprioritized = match fee_check {
+                Some(FeePolicy::Skip) => {
+                    debug!("skipping {}: not incentivized enough", event);
+                    continue;
+                }
+                Some(FeePolicy::Deprioritize) => true,
+                None => false,
+            };
+
             // Collect messages to be sent to the destination chain (e.g., RecvPacket)
             if let Some(msg) = dst_msg {
                 debug!("{} from {}", msg.type_url, event);
-                dst_od.batch.push(TransitMessage {
+                let transit_msg = TransitMessage {
                     event: event.clone(),
                     msg,
-                });
+                };
+
+                if deprioritized {
+                    dst_deprioritized.push(transit_msg);
+                } else {
+                    dst_od.batch.push(transit_msg);
+                }
             }
 
             // Collect timeout messages, to be sent to the source chain
             if let Some(msg) = src_msg {
                 // For Ordered channels a single timeout event should be sent as this closes the channel.
                 // Otherwise a multi message transaction will fail.
-                if self.unordered_channel() || src_od.batch.is_empty() {
+                if !self.ordered_channel() || src_od.batch.is_empty() {
                     debug!("{} from {}", msg.type_url, event);
-                    src_od.batch.push(TransitMessage {
+                    let transit_msg = TransitMessage {
                         event: event.clone(),
                         msg,
-                    });
+                    };
+
+                    if deprioritized {
+                        src_deprioritized.push(transit_msg);
+                    } else {
+                        src_od.batch.push(transit_msg);
+                    }
                 }
             }
         }
 
+        dst_od.batch.extend(dst_deprioritized);
+        src_od.batch.extend(src_deprioritized);
+
         let src_od_res = if src_od.batch.is_empty() {
             None
         } else {
@@ -809,10 +1040,172 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         Ok(bytes.is_empty())
     }
 
+    /// Checks if a sent packet was skipped on destination, i.e. it was received after it timed out
+    /// on an `ORDERED_ALLOW_TIMEOUT` channel and a timeout receipt was stored in its place.
+    fn send_packet_timeout_receipt_on_dst(
+        &self,
+        sequence: Sequence,
+        height: QueryHeight,
+    ) -> Result<bool, LinkError> {
+        let (bytes, _) = self
+            .dst_chain()
+            .query_packet_receipt(
+                QueryPacketReceiptRequest {
+                    port_id: self.dst_port_id().clone(),
+                    channel_id: self.dst_channel_id().clone(),
+                    sequence,
+                    height,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| LinkError::query(self.dst_chain().id(), e))?;
+
+        Ok(bytes == Receipt::Timeout.as_bytes())
+    }
+
+    /// Checks the ICS29 fees escrowed on `chain` for the given packet, sent from `chain`, against
+    /// the minimum fee of the given kind configured in its fee filter for the channel the packet
+    /// was sent on. Returns the policy of the fee filter if the fees do not meet the minimum, and
+    /// `None` if they do or if no minimum fees are configured for the channel.
+    ///
+    /// The fees of all the packets sent on the channel are queried on the first call only, and
+    /// kept in `channel_fees` for the next ones.
+    fn unincentivized_policy<Chain: ChainHandle>(
+        &self,
+        chain: &Chain,
+        fee_filter: &FeeFilter,
+        channel_fees: &mut Option<HashMap<Sequence, Vec<PacketFee>>>,
+        packet: &Packet,
+        kind: FeeKind,
+    ) -> Result<Option<FeePolicy>, LinkError> {
+        let min_fees = match fee_filter.min_fees(&packet.source_port, &packet.source_channel) {
+            Some(min_fees) => min_fees,
+            None => return Ok(None),
+        };
+
+        if channel_fees.is_none() {
+            let incentivized_packets = chain
+                .query_incentivized_packets(QueryIncentivizedPacketsRequest {
+                    port_id: packet.source_port.clone(),
+                    channel_id: packet.source_channel.clone(),
+                    pagination: Some(PageRequest::all()),
+                    height: QueryHeight::Latest,
+                })
+                .map_err(|e| LinkError::query(chain.id(), e))?;
+
+            *channel_fees = Some(
+                incentivized_packets
+                    .into_iter()
+                    .map(|packet| (packet.sequence, packet.packet_fees))
+                    .collect(),
+            );
+        }
+
+        let packet_fees = channel_fees
+            .as_ref()
+            .and_then(|fees| fees.get(&packet.sequence))
+            .map_or(&[][..], Vec::as_slice);
+
+        if !min_fees.is_met_by(kind, packet_fees) {
+            telemetry!(
+                unincentivized_packets,
+                &chain.id(),
+                &packet.source_channel,
+                &packet.source_port,
+                kind.as_str(),
+                1,
+            );
+
+            return Ok(Some(fee_filter.policy));
+        }
+
+        telemetry!({
+            for coin in packet_fees
+                .iter()
+                .flat_map(|packet_fee| kind.of(&packet_fee.fee))
+            {
+                // Amounts which do not fit into a u64 cannot be reported via the Prometheus exporter.
+                if let Ok(amount) = coin.amount.to_string().parse::<u64>() {
+                    ibc_telemetry::global().fee_amounts(
+                        &chain.id(),
+                        &packet.source_channel,
+                        &packet.source_port,
+                        kind.as_str(),
+                        &coin.denom.to_string(),
+                        amount,
+                    );
+                }
+            }
+        });
+
+        Ok(None)
+    }
+
     /// Checks if a send packet event has already been handled (e.g. by another relayer).
     fn send_packet_event_handled(&self, sp: &SendPacket) -> Result<bool, LinkError> {
+        if self.send_packet_commitment_cleared_on_src(&sp.packet)? {
+            return Ok(true);
+        }
+
+        // A packet skipped on an `ORDERED_ALLOW_TIMEOUT` channel counts as received on destination,
+        // but it still has to be timed out on source.
         Ok(self.send_packet_received_on_dst(&sp.packet)?
-            || self.send_packet_commitment_cleared_on_src(&sp.packet)?)
+            && !(self.ordered_allow_timeout_channel()
+                && self
+                    .send_packet_timeout_receipt_on_dst(sp.packet.sequence, QueryHeight::Latest)?))
+    }
+
+    /// Returns the sequences of the packets with a commitment on source that were skipped on
+    /// destination, out of the ones that are not in the given list of unreceived packets.
+    fn skipped_packets_on_dst(&self, unreceived: &[Sequence]) -> Result<Vec<Sequence>, LinkError> {
+        let (commitments, _) =
+            commitments_on_chain(self.src_chain(), self.src_port_id(), self.src_channel_id())
+                .map_err(LinkError::supervisor)?;
+
+        let (next_sequence_recv, _) = self
+            .dst_chain()
+            .query_next_sequence_receive(
+                QueryNextSequenceReceiveRequest {
+                    port_id: self.dst_port_id().clone(),
+                    channel_id: self.dst_channel_id().clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| LinkError::query(self.dst_chain().id(), e))?;
+
+        // Skipped packets are below the next sequence to be received on destination and, unlike
+        // the packets received there, have no acknowledgement written for them.
+        let candidates: Vec<Sequence> = commitments
+            .into_iter()
+            .filter(|sequence| *sequence < next_sequence_recv && !unreceived.contains(sequence))
+            .collect();
+        if candidates.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let (acked, _) = packet_acknowledgements(
+            self.dst_chain(),
+            self.dst_port_id(),
+            self.dst_channel_id(),
+            candidates.clone(),
+        )
+        .map_err(LinkError::supervisor)?;
+
+        // Packets acknowledged asynchronously may not have an acknowledgement yet either, so the
+        // timeout receipt is only checked for the few packets left.
+        let mut skipped = vec![];
+        for sequence in candidates {
+            if acked.contains(&sequence) {
+                continue;
+            }
+
+            if self.send_packet_timeout_receipt_on_dst(sequence, QueryHeight::Latest)? {
+                skipped.push(sequence);
+            }
+        }
+
+        Ok(skipped)
     }
 
     /// Checks if an acknowledgement for the given packet has been received on
@@ -1060,10 +1453,22 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 .entered();
 
         // Pull the s.n. of all packets that the destination chain has not yet received.
-        let (sequences, src_response_height) =
+        let (mut sequences, src_response_height) =
             unreceived_packets(self.dst_chain(), self.src_chain(), &self.path_id)
                 .map_err(LinkError::supervisor)?;
 
+        // Packets skipped on an `ORDERED_ALLOW_TIMEOUT` channel are received on destination,
+        // but their commitments on source are only cleared once they are timed out.
+        if self.ordered_allow_timeout_channel() {
+            let skipped = self.skipped_packets_on_dst(&sequences)?;
+            sequences.extend(skipped);
+            sequences.sort_unstable();
+        }
+
+        // Do not submit again the packets whose transactions are still in flight.
+        let (in_flight, _) = self.in_flight_sequences();
+        sequences.retain(|sequence| !in_flight.contains(sequence));
+
         let query_height = opt_query_height.unwrap_or(src_response_height);
 
         // Skip: no relevant events found.
@@ -1111,10 +1516,14 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     ) -> Result<(), LinkError> {
         let _span = span!(Level::DEBUG, "build_packet_ack_msgs", h = ?opt_query_height).entered();
 
-        let (sequences, src_response_height) =
+        let (mut sequences, src_response_height) =
             unreceived_acknowledgements(self.dst_chain(), self.src_chain(), &self.path_id)
                 .map_err(LinkError::supervisor)?;
 
+        // Do not submit again the acknowledgements whose transactions are still in flight.
+        let (_, in_flight) = self.in_flight_sequences();
+        sequences.retain(|sequence| !in_flight.contains(sequence));
+
         let query_height = opt_query_height.unwrap_or(src_response_height);
 
         // Skip: no relevant events found.
@@ -1222,6 +1631,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
             (PacketMsgType::TimeoutOrdered, next_seq)
         } else {
+            // On `ORDERED_ALLOW_TIMEOUT` channels this proves the timeout receipt on destination.
             (PacketMsgType::TimeoutUnordered, packet.sequence)
         };
 
@@ -1296,6 +1706,17 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         {
             Ok(self.build_timeout_on_close_packet(&event.packet, dst_info.height)?)
         } else if packet.timed_out(&dst_info.timestamp, dst_info.height) {
+            // On `ORDERED_ALLOW_TIMEOUT` channels the packet has to be received on destination
+            // first, so that it is skipped and a timeout receipt is stored for it.
+            if self.ordered_allow_timeout_channel()
+                && !self.send_packet_timeout_receipt_on_dst(
+                    packet.sequence,
+                    QueryHeight::Specific(dst_info.height),
+                )?
+            {
+                return Ok(None);
+            }
+
             Ok(self.build_timeout_packet(&event.packet, dst_info.height)?)
         } else {
             Ok(None)
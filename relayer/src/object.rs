@@ -1,11 +1,12 @@
 use flex_error::define_error;
 use serde::{Deserialize, Serialize};
 
+use ibc::applications::interchain_query::events::SendQuery;
 use ibc::core::{
     ics02_client::{client_state::ClientState, events::UpdateClient},
     ics03_connection::events::Attributes as ConnectionAttributes,
     ics04_channel::events::{
-        Attributes, CloseInit, SendPacket, TimeoutPacket, WriteAcknowledgement,
+        Attributes, CloseInit, SendPacket, TimeoutPacket, UpgradeAttributes, WriteAcknowledgement,
     },
     ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
 };
@@ -130,6 +131,31 @@ impl Wallet {
     }
 }
 
+/// A cross-chain query worker, which answers the ICS31 queries sent by a chain about the state of
+/// another chain, tracked by the given client.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct CrossChainQuery {
+    /// Source chain identifier.
+    /// This is the chain sending the queries and hosting the client.
+    pub src_chain_id: ChainId,
+
+    /// Destination chain identifier.
+    /// This is the queried chain, tracked by the client.
+    pub dst_chain_id: ChainId,
+
+    /// Client identifier (allocated on the source chain `src_chain_id`).
+    pub src_client_id: ClientId,
+}
+
+impl CrossChainQuery {
+    pub fn short_name(&self) -> String {
+        format!(
+            "cross_chain_query::{}:{}->{}",
+            self.src_client_id, self.src_chain_id, self.dst_chain_id
+        )
+    }
+}
+
 /// An object determines the amount of parallelism that can
 /// be exercised when processing [`IbcEvent`](ibc::events::IbcEvent)
 /// between two chains. For each [`Object`], a corresponding
@@ -149,6 +175,8 @@ pub enum Object {
     Packet(Packet),
     /// See [`Wallet`]
     Wallet(Wallet),
+    /// See [`CrossChainQuery`]
+    CrossChainQuery(CrossChainQuery),
 }
 
 define_error! {
@@ -198,6 +226,7 @@ impl Object {
             Object::Channel(c) => &c.src_chain_id == src_chain_id,
             Object::Packet(p) => &p.src_chain_id == src_chain_id,
             Object::Wallet(_) => false,
+            Object::CrossChainQuery(_) => false,
         }
     }
 
@@ -209,6 +238,9 @@ impl Object {
             Object::Channel(c) => &c.src_chain_id == chain_id || &c.dst_chain_id == chain_id,
             Object::Packet(p) => &p.src_chain_id == chain_id || &p.dst_chain_id == chain_id,
             Object::Wallet(w) => &w.chain_id == chain_id,
+            Object::CrossChainQuery(q) => {
+                &q.src_chain_id == chain_id || &q.dst_chain_id == chain_id
+            }
         }
     }
 
@@ -220,6 +252,7 @@ impl Object {
             Object::Connection(_) => ObjectType::Connection,
             Object::Packet(_) => ObjectType::Packet,
             Object::Wallet(_) => ObjectType::Wallet,
+            Object::CrossChainQuery(_) => ObjectType::CrossChainQuery,
         }
     }
 }
@@ -232,6 +265,7 @@ pub enum ObjectType {
     Connection,
     Packet,
     Wallet,
+    CrossChainQuery,
 }
 
 impl From<Client> for Object {
@@ -264,6 +298,12 @@ impl From<Wallet> for Object {
     }
 }
 
+impl From<CrossChainQuery> for Object {
+    fn from(q: CrossChainQuery) -> Self {
+        Self::CrossChainQuery(q)
+    }
+}
+
 impl Object {
     pub fn src_chain_id(&self) -> &ChainId {
         match self {
@@ -272,6 +312,7 @@ impl Object {
             Self::Channel(ref channel) => &channel.src_chain_id,
             Self::Packet(ref path) => &path.src_chain_id,
             Self::Wallet(ref wallet) => &wallet.chain_id,
+            Self::CrossChainQuery(ref query) => &query.src_chain_id,
         }
     }
 
@@ -282,6 +323,7 @@ impl Object {
             Self::Channel(ref channel) => &channel.dst_chain_id,
             Self::Packet(ref path) => &path.dst_chain_id,
             Self::Wallet(ref wallet) => &wallet.chain_id,
+            Self::CrossChainQuery(ref query) => &query.dst_chain_id,
         }
     }
 
@@ -292,6 +334,7 @@ impl Object {
             Self::Channel(ref channel) => channel.short_name(),
             Self::Packet(ref path) => path.short_name(),
             Self::Wallet(ref wallet) => wallet.short_name(),
+            Self::CrossChainQuery(ref query) => query.short_name(),
         }
     }
 
@@ -326,6 +369,30 @@ impl Object {
         .into())
     }
 
+    /// Build the object associated with the given [`SendQuery`] event, whose
+    /// queried chain is the one tracked by the client of the query.
+    pub fn for_cross_chain_query(
+        e: &SendQuery,
+        src_chain: &impl ChainHandle,
+    ) -> Result<Self, ObjectError> {
+        let (client_state, _) = src_chain
+            .query_client_state(
+                QueryClientStateRequest {
+                    client_id: e.client_id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(ObjectError::relayer)?;
+
+        Ok(CrossChainQuery {
+            src_chain_id: src_chain.id(),
+            dst_chain_id: client_state.chain_id(),
+            src_client_id: e.client_id.clone(),
+        }
+        .into())
+    }
+
     /// Build the client object associated with the given channel event attributes.
     pub fn client_from_chan_open_events(
         e: &Attributes,           // The attributes of the emitted event
@@ -400,6 +467,24 @@ impl Object {
         .into())
     }
 
+    /// Build the Channel object associated with the given channel upgrade event.
+    pub fn channel_from_chan_upgrade_events(
+        attributes: &UpgradeAttributes,
+        src_chain: &impl ChainHandle,
+    ) -> Result<Self, ObjectError> {
+        let dst_chain_id =
+            counterparty_chain_from_channel(src_chain, &attributes.channel_id, &attributes.port_id)
+                .map_err(ObjectError::supervisor)?;
+
+        Ok(Channel {
+            dst_chain_id,
+            src_chain_id: src_chain.id(),
+            src_channel_id: attributes.channel_id.clone(),
+            src_port_id: attributes.port_id.clone(),
+        }
+        .into())
+    }
+
     /// Build the object associated with the given [`SendPacket`] event.
     pub fn for_send_packet(
         e: &SendPacket,
@@ -81,6 +81,12 @@ impl<Chain: ChainHandle> Registry<Chain> {
         }
     }
 
+    /// Replace the configuration from which the chain runtimes are spawned,
+    /// e.g. after it was reloaded. Running chain runtimes are left untouched.
+    pub fn update_config(&mut self, config: Config) {
+        self.config = config;
+    }
+
     /// Shutdown the runtime associated with the given chain identifier.
     pub fn shutdown(&mut self, chain_id: &ChainId) {
         if let Some(handle) = self.handles.remove(chain_id) {
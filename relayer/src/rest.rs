@@ -2,7 +2,7 @@ use crossbeam_channel::TryRecvError;
 use tracing::{error, trace};
 
 use crate::{
-    config::Config,
+    config::{diff::ConfigDiff, Config},
     rest::request::ReplySender,
     rest::request::{Request, VersionInfo},
     supervisor::dump_state::SupervisorState,
@@ -25,12 +25,9 @@ pub const VER: &str = env!(
 pub type Receiver = crossbeam_channel::Receiver<Request>;
 
 // TODO: Unify this enum with `SupervisorCmd`
-//  We won't unify yet as it is possible we will never implement
-//  REST API `/chain` adding endpoint; instead of `/chain` we might
-//  implement `/reload` for supporting a broader range of functionality
-//  e.g., adjusting chain config, removing chains, etc.
 pub enum Command {
     DumpState(ReplySender<SupervisorState>),
+    ReloadConfig(ReplySender<ConfigDiff>),
 }
 
 /// Process incoming REST requests.
@@ -82,6 +79,12 @@ pub fn process_incoming_requests(config: &Config, channel: &Receiver) -> Option<
 
                 return Some(Command::DumpState(reply_to));
             }
+
+            Request::ReloadConfig { reply_to } => {
+                trace!("ReloadConfig");
+
+                return Some(Command::ReloadConfig(reply_to));
+            }
         },
         Err(e) => {
             if !matches!(e, TryRecvError::Empty) {
@@ -23,6 +23,9 @@ pub enum RestApiError {
     #[error("failed while parsing the request body into a chain configuration: {0}")]
     InvalidChainConfig(String),
 
+    #[error("failed to reload the configuration: {0}")]
+    ConfigReload(String),
+
     #[error("not implemented")]
     Unimplemented,
 }
@@ -36,6 +39,7 @@ impl RestApiError {
             RestApiError::ChainConfigNotFound(_) => "ChainConfigNotFound",
             RestApiError::InvalidChainId(_, _) => "InvalidChainId",
             RestApiError::InvalidChainConfig(_) => "InvalidChainConfig",
+            RestApiError::ConfigReload(_) => "ConfigReload",
             RestApiError::Unimplemented => "Unimplemented",
         }
     }
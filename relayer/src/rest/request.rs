@@ -2,7 +2,11 @@ use serde::Serialize;
 
 use ibc::core::ics24_host::identifier::ChainId;
 
-use crate::{config::ChainConfig, rest::RestApiError, supervisor::dump_state::SupervisorState};
+use crate::{
+    config::{diff::ConfigDiff, ChainConfig},
+    rest::RestApiError,
+    supervisor::dump_state::SupervisorState,
+};
 
 pub type ReplySender<T> = crossbeam_channel::Sender<Result<T, RestApiError>>;
 pub type ReplyReceiver<T> = crossbeam_channel::Receiver<Result<T, RestApiError>>;
@@ -36,4 +40,8 @@ pub enum Request {
         chain_id: ChainId,
         reply_to: ReplySender<ChainConfig>,
     },
+
+    ReloadConfig {
+        reply_to: ReplySender<ConfigDiff>,
+    },
 }
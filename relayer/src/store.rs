@@ -0,0 +1,459 @@
+//! Persistent store of the relaying state, which allows the relayer to resume
+//! relaying after a restart instead of starting from scratch.
+//!
+//! For each relaying path, the store records:
+//! - the transactions which were submitted but not yet confirmed,
+//! - the height of the last batch of events processed,
+//! - the outcome of the packets whose relaying was confirmed.
+//!
+//! The pull event source of a chain records the last height whose events it
+//! collected in a store of its own, see [`EventCursorStore`].
+
+use alloc::collections::BTreeMap;
+use core::time::Duration;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use flex_error::{define_error, TraceError};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use ibc::core::ics04_channel::packet::Sequence;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::Height;
+
+use crate::config::{ChainConfig, StoreConfig};
+use crate::util::lock::{LockExt, RwArc};
+
+/// The default folder of the store, relative to the home directory.
+pub const STORE_DEFAULT_FOLDER: &str = ".hermes/store/";
+
+/// The default folder of the event cursor stores, relative to the home directory.
+/// Each chain has its own store in a sub-folder named after the chain identifier.
+pub const EVENT_STORE_DEFAULT_FOLDER: &str = ".hermes/event_store/";
+
+/// How long the outcomes of the relayed packets are kept in the store.
+pub const OUTCOME_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+const PENDING_TREE: &str = "pending_txs";
+const CURSOR_TREE: &str = "cursors";
+const OUTCOME_TREE: &str = "packet_outcomes";
+const LAST_HEIGHT_KEY: &str = "last_height";
+
+define_error! {
+    StoreError {
+        Open
+            { path: PathBuf }
+            [ TraceError<sled::Error> ]
+            |e| { format!("failed to open the relayer store at {}", e.path.display()) },
+
+        Db
+            [ TraceError<sled::Error> ]
+            |_| { "relayer store error" },
+
+        Encode
+            [ TraceError<serde_json::Error> ]
+            |_| { "failed to encode a relayer store entry" },
+
+        Decode
+            { key: String }
+            [ TraceError<serde_json::Error> ]
+            |e| { format!("failed to decode the relayer store entry {}", e.key) },
+
+        HomeLocationUnavailable
+            |_| { "home location is unavailable" },
+    }
+}
+
+/// A transaction submitted by the relayer which was not yet confirmed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingTx {
+    /// The chain the transactions were submitted to.
+    pub chain_id: ChainId,
+    /// The hashes of the transactions, in upper case hexadecimal.
+    pub tx_hashes: Vec<String>,
+    /// The sequences of the packets whose receive or timeout messages
+    /// are included in the transactions.
+    pub sent_sequences: Vec<Sequence>,
+    /// The sequences of the packets whose acknowledgement messages
+    /// are included in the transactions.
+    pub acked_sequences: Vec<Sequence>,
+    /// When the transactions were submitted.
+    pub submitted_at: SystemTime,
+}
+
+impl PendingTx {
+    /// Whether the transactions were submitted more than `timeout` ago.
+    pub fn is_expired(&self, timeout: Duration) -> bool {
+        self.submitted_at
+            .elapsed()
+            .map_or(false, |elapsed| elapsed > timeout)
+    }
+}
+
+/// The outcome of relaying a packet, as confirmed by the events of the transactions.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PacketOutcome {
+    Received,
+    Acknowledged,
+    TimedOut,
+}
+
+/// The latest outcome recorded for a packet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PacketRecord {
+    pub outcome: PacketOutcome,
+    /// The height of the event confirming the outcome.
+    pub height: Height,
+    pub recorded_at: SystemTime,
+}
+
+/// An embedded on-disk store of the relaying state of all the paths.
+///
+/// The store can be cloned cheaply, all the clones share the same database.
+#[derive(Clone, Debug)]
+pub struct RelayStore {
+    pending_txs: sled::Tree,
+    cursors: sled::Tree,
+    outcomes: sled::Tree,
+}
+
+impl RelayStore {
+    /// Opens the store configured in `config`, if it is enabled.
+    pub fn from_config(config: &StoreConfig) -> Result<Option<Self>, StoreError> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let path = match &config.path {
+            Some(path) => path.clone(),
+            None => default_path(STORE_DEFAULT_FOLDER)?,
+        };
+
+        Self::open(&path).map(Some)
+    }
+
+    /// Opens the store at the given path, creating it if it does not exist.
+    ///
+    /// The outcomes recorded more than [`OUTCOME_RETENTION`] ago are pruned.
+    pub fn open(path: &Path) -> Result<Self, StoreError> {
+        let db = sled::open(path).map_err(|e| StoreError::open(path.to_path_buf(), e))?;
+
+        let store = Self {
+            pending_txs: db.open_tree(PENDING_TREE).map_err(StoreError::db)?,
+            cursors: db.open_tree(CURSOR_TREE).map_err(StoreError::db)?,
+            outcomes: db.open_tree(OUTCOME_TREE).map_err(StoreError::db)?,
+        };
+
+        store.prune_outcomes(OUTCOME_RETENTION)?;
+
+        Ok(store)
+    }
+
+    /// Returns a view of the store restricted to the path relaying the packets
+    /// sent on the given channel of `src_chain_id`.
+    ///
+    /// The pending transactions of the path are read from the store once, and are
+    /// then only written to it.
+    pub fn for_path(
+        &self,
+        src_chain_id: &ChainId,
+        src_port_id: &PortId,
+        src_channel_id: &ChannelId,
+    ) -> Result<PathStore, StoreError> {
+        let prefix = format!("{}/{}/{}/", src_chain_id, src_port_id, src_channel_id);
+
+        let pending_txs = self
+            .pending_txs
+            .scan_prefix(&prefix)
+            .map(|entry| {
+                let (key, value) = entry.map_err(StoreError::db)?;
+                let pending: PendingTx = decode(&key, &value)?;
+                Ok((String::from_utf8_lossy(&key).to_string(), pending))
+            })
+            .collect::<Result<BTreeMap<_, _>, StoreError>>()?;
+
+        Ok(PathStore {
+            store: self.clone(),
+            prefix,
+            pending_txs: RwArc::new_lock(pending_txs),
+        })
+    }
+
+    /// Removes the outcomes recorded more than `retention` ago.
+    pub fn prune_outcomes(&self, retention: Duration) -> Result<usize, StoreError> {
+        let mut pruned = 0;
+
+        for entry in self.outcomes.iter() {
+            let (key, value) = entry.map_err(StoreError::db)?;
+            let record: PacketRecord = decode(&key, &value)?;
+
+            let expired = record
+                .recorded_at
+                .elapsed()
+                .map_or(false, |elapsed| elapsed > retention);
+
+            if expired {
+                self.outcomes.remove(key).map_err(StoreError::db)?;
+                pruned += 1;
+            }
+        }
+
+        if pruned > 0 {
+            debug!("pruned {} packet outcomes from the relayer store", pruned);
+        }
+
+        Ok(pruned)
+    }
+}
+
+/// A view of the [`RelayStore`] restricted to a single relaying path.
+///
+/// The clones of a view share the same in-memory copy of the pending transactions.
+#[derive(Clone, Debug)]
+pub struct PathStore {
+    store: RelayStore,
+    prefix: String,
+    /// The pending transactions of this path, keyed like in the store.
+    pending_txs: RwArc<BTreeMap<String, PendingTx>>,
+}
+
+impl PathStore {
+    /// Records a pending transaction, keyed by its first transaction hash.
+    pub fn insert_pending_tx(&self, pending: &PendingTx) -> Result<(), StoreError> {
+        let key = match pending.tx_hashes.first() {
+            Some(hash) => self.key(hash),
+            None => return Ok(()),
+        };
+
+        insert(&self.store.pending_txs, key.clone(), pending)?;
+        self.pending_txs
+            .acquire_write()
+            .insert(key, pending.clone());
+
+        Ok(())
+    }
+
+    /// Removes the pending transaction whose first transaction hash is `first_tx_hash`.
+    pub fn remove_pending_tx(&self, first_tx_hash: &str) -> Result<(), StoreError> {
+        let key = self.key(first_tx_hash);
+
+        self.store
+            .pending_txs
+            .remove(&key)
+            .map_err(StoreError::db)?;
+        self.pending_txs.acquire_write().remove(&key);
+
+        Ok(())
+    }
+
+    /// Returns all the pending transactions of this path.
+    pub fn pending_txs(&self) -> Vec<PendingTx> {
+        self.pending_txs.acquire_read().values().cloned().collect()
+    }
+
+    /// Returns the sequences of the packets with a pending receive or timeout message,
+    /// and the sequences of the packets with a pending acknowledgement message.
+    pub fn in_flight_sequences(&self) -> (Vec<Sequence>, Vec<Sequence>) {
+        let mut sent = Vec::new();
+        let mut acked = Vec::new();
+
+        for pending in self.pending_txs.acquire_read().values() {
+            sent.extend(&pending.sent_sequences);
+            acked.extend(&pending.acked_sequences);
+        }
+
+        (sent, acked)
+    }
+
+    /// Returns the height of the last batch of events processed for this path, if any.
+    pub fn cursor(&self) -> Result<Option<Height>, StoreError> {
+        let key = self.prefix.clone();
+
+        match self.store.cursors.get(&key).map_err(StoreError::db)? {
+            Some(value) => decode(key.as_bytes(), &value).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Records `height` as the height of the last batch of events processed for this path.
+    pub fn update_cursor(&self, height: Height) -> Result<(), StoreError> {
+        insert(&self.store.cursors, self.prefix.clone(), &height)
+    }
+
+    /// Records the outcome of relaying the packet with the given sequence.
+    pub fn record_outcome(
+        &self,
+        sequence: Sequence,
+        outcome: PacketOutcome,
+        height: Height,
+    ) -> Result<(), StoreError> {
+        let record = PacketRecord {
+            outcome,
+            height,
+            recorded_at: SystemTime::now(),
+        };
+
+        // Pad the sequence so that the outcomes are ordered by sequence.
+        let key = self.key(&format!("{:020}", u64::from(sequence)));
+
+        insert(&self.store.outcomes, key, &record)
+    }
+
+    /// Returns the latest outcome recorded for the packet with the given sequence.
+    pub fn outcome(&self, sequence: Sequence) -> Result<Option<PacketRecord>, StoreError> {
+        let key = self.key(&format!("{:020}", u64::from(sequence)));
+
+        match self.store.outcomes.get(&key).map_err(StoreError::db)? {
+            Some(value) => decode(key.as_bytes(), &value).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn key(&self, suffix: &str) -> String {
+        format!("{}{}", self.prefix, suffix)
+    }
+}
+
+/// An on-disk record of the last height whose events were collected from a chain by its
+/// [`PullEventSource`](crate::event::pull::PullEventSource), which resumes from the next
+/// height after a restart.
+///
+/// The store can be cloned cheaply, all the clones share the same database.
+#[derive(Clone, Debug)]
+pub struct EventCursorStore {
+    db: sled::Db,
+}
+
+impl EventCursorStore {
+    /// Opens the event cursor store configured for the given chain, if it is enabled.
+    pub fn from_config(config: &ChainConfig) -> Result<Option<Self>, StoreError> {
+        if !config.event_store.enabled {
+            return Ok(None);
+        }
+
+        let path = match &config.event_store.path {
+            Some(path) => path.clone(),
+            None => default_path(EVENT_STORE_DEFAULT_FOLDER)?.join(config.id.as_str()),
+        };
+
+        Self::open(&path).map(Some)
+    }
+
+    /// Opens the store at the given path, creating it if it does not exist.
+    pub fn open(path: &Path) -> Result<Self, StoreError> {
+        let db = sled::open(path).map_err(|e| StoreError::open(path.to_path_buf(), e))?;
+
+        Ok(Self { db })
+    }
+
+    /// Returns the last height whose events were collected, if any.
+    pub fn last_height(&self) -> Result<Option<u64>, StoreError> {
+        match self.db.get(LAST_HEIGHT_KEY).map_err(StoreError::db)? {
+            Some(value) => decode(LAST_HEIGHT_KEY.as_bytes(), &value).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Records `height` as the last height whose events were collected.
+    pub fn update_last_height(&self, height: u64) -> Result<(), StoreError> {
+        let value = serde_json::to_vec(&height).map_err(StoreError::encode)?;
+        self.db
+            .insert(LAST_HEIGHT_KEY, value)
+            .map_err(StoreError::db)?;
+
+        Ok(())
+    }
+}
+
+fn insert<T: Serialize>(tree: &sled::Tree, key: String, value: &T) -> Result<(), StoreError> {
+    let value = serde_json::to_vec(value).map_err(StoreError::encode)?;
+    tree.insert(key, value).map_err(StoreError::db)?;
+
+    Ok(())
+}
+
+fn decode<T: DeserializeOwned>(key: &[u8], value: &[u8]) -> Result<T, StoreError> {
+    serde_json::from_slice(value).map_err(|e| {
+        let key = String::from_utf8_lossy(key).to_string();
+        warn!("corrupted entry {} in the relayer store", key);
+        StoreError::decode(key, e)
+    })
+}
+
+fn default_path(folder: &str) -> Result<PathBuf, StoreError> {
+    let home = dirs_next::home_dir().ok_or_else(StoreError::home_location_unavailable)?;
+
+    Ok(home.join(folder))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PacketOutcome, PendingTx, RelayStore};
+
+    use std::time::SystemTime;
+
+    use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+    use ibc::Height;
+    use test_log::test;
+
+    #[test]
+    fn path_store() {
+        let dir = std::env::temp_dir().join(format!("hermes-store-{}", uuid::Uuid::new_v4()));
+        let store = RelayStore::open(&dir).unwrap();
+
+        let chain_id = ChainId::new("ibc".to_string(), 0);
+        let path = store
+            .for_path(&chain_id, &PortId::transfer(), &ChannelId::new(0))
+            .unwrap();
+        let other = store
+            .for_path(&chain_id, &PortId::transfer(), &ChannelId::new(1))
+            .unwrap();
+
+        let pending = PendingTx {
+            chain_id: ChainId::new("ibc".to_string(), 1),
+            tx_hashes: vec!["AB".to_string(), "CD".to_string()],
+            sent_sequences: vec![1.into(), 2.into()],
+            acked_sequences: vec![3.into()],
+            submitted_at: SystemTime::now(),
+        };
+
+        path.insert_pending_tx(&pending).unwrap();
+        assert_eq!(path.pending_txs(), vec![pending.clone()]);
+        assert!(other.pending_txs().is_empty());
+        assert_eq!(
+            path.in_flight_sequences(),
+            (vec![1.into(), 2.into()], vec![3.into()])
+        );
+
+        // The pending transactions are read back from the store by a new view of the path.
+        let reopened = store
+            .for_path(&chain_id, &PortId::transfer(), &ChannelId::new(0))
+            .unwrap();
+        assert_eq!(reopened.pending_txs(), vec![pending]);
+
+        path.remove_pending_tx("AB").unwrap();
+        assert!(path.pending_txs().is_empty());
+        assert!(path.in_flight_sequences().0.is_empty());
+
+        let reopened = store
+            .for_path(&chain_id, &PortId::transfer(), &ChannelId::new(0))
+            .unwrap();
+        assert!(reopened.pending_txs().is_empty());
+
+        let height = Height::new(0, 10).unwrap();
+        assert_eq!(path.cursor().unwrap(), None);
+        path.update_cursor(height).unwrap();
+        assert_eq!(path.cursor().unwrap(), Some(height));
+        assert_eq!(other.cursor().unwrap(), None);
+
+        path.record_outcome(1.into(), PacketOutcome::Received, height)
+            .unwrap();
+        let record = path.outcome(1.into()).unwrap().unwrap();
+        assert_eq!(record.outcome, PacketOutcome::Received);
+        assert_eq!(record.height, height);
+        assert_eq!(other.outcome(1.into()).unwrap(), None);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
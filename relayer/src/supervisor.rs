@@ -3,6 +3,7 @@ use alloc::sync::Arc;
 use core::convert::Infallible;
 use core::ops::Deref;
 use core::time::Duration;
+use std::path::PathBuf;
 use std::sync::RwLock;
 
 use crossbeam_channel::{unbounded, Receiver, Sender};
@@ -17,11 +18,12 @@ use ibc::{
 
 use crate::{
     chain::{endpoint::HealthCheck, handle::ChainHandle, tracking::TrackingId},
-    config::Config,
+    config::{self, diff::ConfigDiff, Config},
     event::monitor::{self, Error as EventError, ErrorDetail as EventErrorDetail, EventBatch},
     object::Object,
     registry::{Registry, SharedRegistry},
     rest,
+    store::RelayStore,
     supervisor::scan::ScanMode,
     telemetry,
     util::{
@@ -72,6 +74,10 @@ pub struct SupervisorOptions {
     /// even when an allow list is configured for a chain and the full scan could
     /// be omitted.
     pub force_full_scan: bool,
+
+    /// The path to the configuration file, from which the configuration
+    /// is reloaded. Reloading is not supported if `None`.
+    pub config_path: Option<PathBuf>,
 }
 
 /**
@@ -128,7 +134,8 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
         health_check(&config, &mut registry.write());
     }
 
-    let workers = Arc::new(RwLock::new(WorkerMap::new()));
+    let store = RelayStore::from_config(&config.store).map_err(Error::store)?;
+    let workers = Arc::new(RwLock::new(WorkerMap::with_store(store)));
     let client_state_filter = Arc::new(RwLock::new(FilterPolicy::default()));
 
     let scan = chain_scanner(
@@ -150,41 +157,79 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
 
     let subscriptions = init_subscriptions(&config, &mut registry.write())?;
 
+    let config = Arc::new(RwLock::new(config));
+
     let batch_tasks = spawn_batch_workers(
         &config,
         registry.clone(),
-        client_state_filter,
+        client_state_filter.clone(),
         workers.clone(),
         subscriptions,
     );
 
-    let cmd_task = spawn_cmd_worker(registry.clone(), workers.clone(), cmd_rx);
+    let reload = ReloadContext {
+        config_path: options.config_path,
+        config,
+        registry,
+        client_state_filter,
+        workers,
+        batch_tasks: Arc::new(RwLock::new(batch_tasks)),
+    };
+
+    let cmd_task = spawn_cmd_worker(reload.clone(), cmd_rx);
 
     let mut tasks = vec![cmd_task];
-    tasks.extend(batch_tasks);
 
     if let Some(rest_rx) = rest_rx {
-        let rest_task = spawn_rest_worker(config, registry, workers, rest_rx);
+        let rest_task = spawn_rest_worker(reload, rest_rx);
         tasks.push(rest_task);
     }
 
     Ok(tasks)
 }
 
+/// The state of the supervisor which is updated when its configuration is reloaded.
+///
+/// The batch workers are owned by this context rather than returned as supervisor tasks,
+/// as they are spawned and shut down along with the chains they receive events from.
+/// They are shut down when the last task holding the context terminates.
+struct ReloadContext<Chain: ChainHandle> {
+    config_path: Option<PathBuf>,
+    config: Arc<RwLock<Config>>,
+    registry: SharedRegistry<Chain>,
+    client_state_filter: Arc<RwLock<FilterPolicy>>,
+    workers: Arc<RwLock<WorkerMap>>,
+    batch_tasks: Arc<RwLock<HashMap<ChainId, TaskHandle>>>,
+}
+
+impl<Chain: ChainHandle> Clone for ReloadContext<Chain> {
+    fn clone(&self) -> Self {
+        Self {
+            config_path: self.config_path.clone(),
+            config: self.config.clone(),
+            registry: self.registry.clone(),
+            client_state_filter: self.client_state_filter.clone(),
+            workers: self.workers.clone(),
+            batch_tasks: self.batch_tasks.clone(),
+        }
+    }
+}
+
 fn spawn_batch_workers<Chain: ChainHandle>(
-    config: &Config,
+    config: &Arc<RwLock<Config>>,
     registry: SharedRegistry<Chain>,
     client_state_filter: Arc<RwLock<FilterPolicy>>,
     workers: Arc<RwLock<WorkerMap>>,
     subscriptions: Vec<(Chain, Subscription)>,
-) -> Vec<TaskHandle> {
-    let mut handles = Vec::with_capacity(subscriptions.len());
+) -> HashMap<ChainId, TaskHandle> {
+    let mut handles = HashMap::new();
 
     for (chain, subscription) in subscriptions {
         let config = config.clone();
         let registry = registry.clone();
         let client_state_filter = client_state_filter.clone();
         let workers = workers.clone();
+        let chain_id = chain.id();
 
         let handle = spawn_background_task(
             tracing::Span::none(),
@@ -192,7 +237,7 @@ fn spawn_batch_workers<Chain: ChainHandle>(
             move || -> Result<Next, TaskError<Infallible>> {
                 if let Ok(batch) = subscription.try_recv() {
                     handle_batch(
-                        &config,
+                        &config.acquire_read(),
                         &mut registry.write(),
                         &mut client_state_filter.acquire_write(),
                         &mut workers.acquire_write(),
@@ -205,15 +250,14 @@ fn spawn_batch_workers<Chain: ChainHandle>(
             },
         );
 
-        handles.push(handle);
+        handles.insert(chain_id, handle);
     }
 
     handles
 }
 
-pub fn spawn_cmd_worker<Chain: ChainHandle>(
-    registry: SharedRegistry<Chain>,
-    workers: Arc<RwLock<WorkerMap>>,
+fn spawn_cmd_worker<Chain: ChainHandle>(
+    reload: ReloadContext<Chain>,
     cmd_rx: Receiver<SupervisorCmd>,
 ) -> TaskHandle {
     spawn_background_task(
@@ -223,7 +267,14 @@ pub fn spawn_cmd_worker<Chain: ChainHandle>(
             if let Ok(cmd) = cmd_rx.try_recv() {
                 match cmd {
                     SupervisorCmd::DumpState(reply_to) => {
-                        dump_state(&registry.read(), &workers.acquire_read(), reply_to);
+                        dump_state(
+                            &reload.registry.read(),
+                            &reload.workers.acquire_read(),
+                            reply_to,
+                        );
+                    }
+                    SupervisorCmd::ReloadConfig(reply_to) => {
+                        let _ = reply_to.try_send(reload_config(&reload));
                     }
                 }
             }
@@ -233,23 +284,234 @@ pub fn spawn_cmd_worker<Chain: ChainHandle>(
     )
 }
 
-pub fn spawn_rest_worker<Chain: ChainHandle>(
-    config: Config,
-    registry: SharedRegistry<Chain>,
-    workers: Arc<RwLock<WorkerMap>>,
+fn spawn_rest_worker<Chain: ChainHandle>(
+    reload: ReloadContext<Chain>,
     rest_rx: rest::Receiver,
 ) -> TaskHandle {
     spawn_background_task(
         error_span!("rest"),
         Some(Duration::from_millis(500)),
         move || -> Result<Next, TaskError<Infallible>> {
-            handle_rest_requests(&config, &registry.read(), &workers.acquire_read(), &rest_rx);
+            handle_rest_requests(&reload, &rest_rx);
 
             Ok(Next::Continue)
         },
     )
 }
 
+/// Reloads the configuration file and applies the changes to the running supervisor:
+///
+/// - the chain runtimes and event subscriptions of the removed and restarted chains are shut
+///   down, along with the workers relaying to or from these chains,
+/// - the chain runtimes and event subscriptions of the added and restarted chains are spawned,
+///   these chains are scanned and their workers spawned,
+/// - the workers which were shut down and still exist in the new configuration are respawned.
+///
+/// The new configuration of the updated chains is applied to their running runtime, leaving
+/// their workers untouched, except for the workers on the channels which their new packet
+/// filter denies. The updated chains whose packet filter changed are scanned again, to spawn
+/// the workers on the channels which it now allows.
+///
+/// If the mode changed, all the workers are restarted, but the chain runtimes of the chains
+/// which were not restarted are kept running.
+fn reload_config<Chain: ChainHandle>(reload: &ReloadContext<Chain>) -> Result<ConfigDiff, Error> {
+    let config_path = reload
+        .config_path
+        .as_ref()
+        .ok_or_else(Error::missing_config_path)?;
+
+    let new_config = config::load(config_path).map_err(Error::config_load)?;
+    let diff = ConfigDiff::new(&reload.config.acquire_read(), &new_config);
+
+    if diff.is_empty() {
+        info!("configuration reloaded, no changes found");
+        return Ok(diff);
+    }
+
+    info!(
+        added = ?diff.added,
+        removed = ?diff.removed,
+        updated = ?diff.updated,
+        restarted = ?diff.restarted,
+        mode_changed = diff.mode_changed,
+        "configuration reloaded, applying changes"
+    );
+
+    if diff.restart_required {
+        warn!(
+            "changes to the global, REST, telemetry and store sections only take effect after a restart"
+        );
+    }
+
+    let stopped_chains = diff
+        .removed
+        .iter()
+        .chain(diff.restarted.iter())
+        .collect_vec();
+    let started_chains = diff.added.iter().chain(diff.restarted.iter()).collect_vec();
+
+    // Shut down the batch workers first, as they may be waiting for the locks held below.
+    for chain_id in &stopped_chains {
+        reload.batch_tasks.acquire_write().remove(*chain_id);
+    }
+
+    let mut config = reload.config.acquire_write();
+    let mut registry = reload.registry.write();
+    let mut client_state_filter = reload.client_state_filter.acquire_write();
+    let mut workers = reload.workers.acquire_write();
+
+    let affected_objects = affected_objects(&diff, workers.handles().map(|handle| handle.object()));
+
+    for object in &affected_objects {
+        workers.shutdown_worker(object);
+    }
+
+    for chain_id in &stopped_chains {
+        registry.shutdown(chain_id);
+    }
+
+    *config = new_config;
+    registry.update_config(config.clone());
+
+    // Apply the new configuration to the runtimes of the updated chains. Those which are not
+    // running yet are spawned from the new configuration of the registry.
+    for chain in registry
+        .chains()
+        .filter(|chain| diff.updated.contains(&chain.id()))
+    {
+        if let Some(chain_config) = config.find_chain(&chain.id()) {
+            if let Err(e) = chain.update_config(chain_config.clone()) {
+                error!(
+                    "failed to update the configuration of {}: {}",
+                    chain.id(),
+                    e
+                );
+            }
+        }
+    }
+
+    // Shut down the workers on the channels which the new packet filters deny.
+    let denied_objects = diff
+        .filter_changed
+        .iter()
+        .flat_map(|chain_id| workers.objects_for_chain(chain_id))
+        .unique()
+        .filter(|object| {
+            !relay_on_object(
+                &config,
+                &mut registry,
+                &mut client_state_filter,
+                object.src_chain_id(),
+                object,
+            )
+        })
+        .collect_vec();
+
+    for object in &denied_objects {
+        workers.shutdown_worker(object);
+    }
+
+    let mut scanner = chain_scanner(
+        &config,
+        &mut registry,
+        &mut client_state_filter,
+        ScanMode::Auto,
+    );
+
+    let scans = started_chains
+        .iter()
+        .copied()
+        .chain(diff.filter_changed.iter())
+        .filter_map(|chain_id| config.find_chain(chain_id))
+        .map(|chain_config| scanner.scan_chain(chain_config))
+        .collect_vec();
+
+    spawn_context(&config, &mut registry, &mut workers)
+        .spawn_workers(scan::ChainsScan { chains: scans });
+
+    for object in affected_objects {
+        let src_chain_id = object.src_chain_id().clone();
+        let dst_chain_id = object.dst_chain_id().clone();
+
+        if !config.has_chain(&src_chain_id)
+            || !config.has_chain(&dst_chain_id)
+            || !relay_on_object(
+                &config,
+                &mut registry,
+                &mut client_state_filter,
+                &src_chain_id,
+                &object,
+            )
+        {
+            continue;
+        }
+
+        match (
+            registry.get_or_spawn(&src_chain_id),
+            registry.get_or_spawn(&dst_chain_id),
+        ) {
+            (Ok(src), Ok(dst)) => {
+                workers.spawn(src, dst, &object, &config);
+            }
+            (Err(e), _) | (_, Err(e)) => {
+                error!("failed to respawn worker {}: {}", object.short_name(), e)
+            }
+        }
+    }
+
+    let mut subscriptions = Vec::with_capacity(started_chains.len());
+    for chain_id in &started_chains {
+        match registry
+            .get_or_spawn(chain_id)
+            .map_err(Error::spawn)
+            .and_then(|chain| {
+                let subscription = chain.subscribe().map_err(Error::relayer)?;
+                Ok((chain, subscription))
+            }) {
+            Ok(subscription) => subscriptions.push(subscription),
+            Err(e) => error!("failed to subscribe to events of {}: {}", chain_id, e),
+        }
+    }
+
+    drop(workers);
+    drop(client_state_filter);
+    drop(registry);
+    drop(config);
+
+    let batch_tasks = spawn_batch_workers(
+        &reload.config,
+        reload.registry.clone(),
+        reload.client_state_filter.clone(),
+        reload.workers.clone(),
+        subscriptions,
+    );
+
+    reload.batch_tasks.acquire_write().extend(batch_tasks);
+
+    Ok(diff)
+}
+
+/// Returns the objects whose workers have to be shut down, and respawned if they are still
+/// relayed on, to apply the given changes to the configuration: the workers of all chains if
+/// the mode changed, and otherwise the workers relaying to or from the removed and restarted
+/// chains only.
+fn affected_objects<'a>(
+    diff: &ConfigDiff,
+    objects: impl Iterator<Item = &'a Object>,
+) -> Vec<Object> {
+    objects
+        .filter(|object| {
+            diff.mode_changed
+                || diff
+                    .removed
+                    .iter()
+                    .chain(diff.restarted.iter())
+                    .any(|chain_id| object.for_chain(chain_id))
+        })
+        .cloned()
+        .collect()
+}
+
 /// Returns `true` if the relayer should filter based on
 /// client state attributes, e.g., trust threshold.
 /// Returns `false` otherwise.
@@ -319,6 +581,7 @@ fn relay_on_object<Chain: ChainHandle>(
         Object::Channel(chan) => client_state_filter.control_chan_object(registry, chan),
         Object::Packet(packet) => client_state_filter.control_packet_object(registry, packet),
         Object::Wallet(_wallet) => Ok(Permission::Allow),
+        Object::CrossChainQuery(_query) => Ok(Permission::Allow),
     };
 
     match client_filter_outcome {
@@ -434,6 +697,20 @@ pub fn collect_events(
                     Object::client_from_chan_open_events(&attributes, src_chain).ok()
                 });
             }
+            IbcEvent::UpgradeInitChannel(..)
+            | IbcEvent::UpgradeTryChannel(..)
+            | IbcEvent::UpgradeAckChannel(..)
+            | IbcEvent::UpgradeConfirmChannel(..)
+            | IbcEvent::UpgradeOpenChannel(..)
+            | IbcEvent::UpgradeTimeoutChannel(..)
+            | IbcEvent::UpgradeCancelChannel(..)
+            | IbcEvent::UpgradeErrorChannel(..) => {
+                collect_event(&mut collected, event, mode.channels.enabled, || {
+                    event.upgrade_attributes().and_then(|attr| {
+                        Object::channel_from_chan_upgrade_events(attr, src_chain).ok()
+                    })
+                });
+            }
             IbcEvent::SendPacket(ref packet) => {
                 collect_event(&mut collected, event, mode.packets.enabled, || {
                     Object::for_send_packet(packet, src_chain).ok()
@@ -454,6 +731,14 @@ pub fn collect_events(
                     Object::for_close_init_channel(packet, src_chain).ok()
                 });
             }
+            IbcEvent::CrossChainQuery(ref query) => {
+                collect_event(
+                    &mut collected,
+                    event,
+                    mode.cross_chain_queries.enabled,
+                    || Object::for_cross_chain_query(query, src_chain).ok(),
+                );
+            }
             _ => (),
         }
     }
@@ -565,28 +850,31 @@ fn state<Chain: ChainHandle>(registry: &Registry<Chain>, workers: &WorkerMap) ->
 }
 
 fn handle_rest_requests<Chain: ChainHandle>(
-    config: &Config,
-    registry: &Registry<Chain>,
-    workers: &WorkerMap,
+    reload: &ReloadContext<Chain>,
     rest_rx: &rest::Receiver,
 ) {
-    if let Some(cmd) = rest::process_incoming_requests(config, rest_rx) {
-        handle_rest_cmd(registry, workers, cmd);
+    let cmd = rest::process_incoming_requests(&reload.config.acquire_read(), rest_rx);
+
+    if let Some(cmd) = cmd {
+        handle_rest_cmd(reload, cmd);
     }
 }
 
-fn handle_rest_cmd<Chain: ChainHandle>(
-    registry: &Registry<Chain>,
-    workers: &WorkerMap,
-    m: rest::Command,
-) {
+fn handle_rest_cmd<Chain: ChainHandle>(reload: &ReloadContext<Chain>, m: rest::Command) {
     match m {
         rest::Command::DumpState(reply) => {
-            let state = state(registry, workers);
+            let state = state(&reload.registry.read(), &reload.workers.acquire_read());
             reply
                 .send(Ok(state))
                 .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
         }
+        rest::Command::ReloadConfig(reply) => {
+            let result =
+                reload_config(reload).map_err(|e| rest::RestApiError::ConfigReload(e.to_string()));
+            reply
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
     }
 }
 
@@ -722,6 +1010,17 @@ fn handle_batch<Chain: ChainHandle>(
                 )
             });
         }
+        Err(EventError(EventErrorDetail::MissedHeights(_), _))
+        | Err(EventError(EventErrorDetail::EndpointSwitched(_), _)) => {
+            warn!(chain.id = %chain_id, "events may have been missed, clearing pending packets");
+
+            let _ = clear_pending_packets(workers, &chain_id).map_err(|e| {
+                error!(
+                    "[{}] error during clearing pending packets: {}",
+                    chain_id, e
+                )
+            });
+        }
         Err(e) => {
             error!("[{}] error in receiving event batch: {}", chain_id, e)
         }
@@ -761,3 +1060,77 @@ impl CollectedEvents {
         self.new_block.is_some()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+
+    use test_log::test;
+    use tokio::runtime::Runtime as TokioRuntime;
+
+    use ibc::core::ics24_host::identifier::{ChainId, ClientId};
+
+    use super::affected_objects;
+    use crate::chain::handle::{BaseChainHandle, ChainHandle};
+    use crate::chain::mock::test_utils::get_basic_chain_config;
+    use crate::chain::mock::MockChain;
+    use crate::chain::runtime::ChainRuntime;
+    use crate::config::diff::ConfigDiff;
+    use crate::object::{Client, Object};
+
+    fn client(dst_chain_id: &ChainId, src_chain_id: &ChainId) -> Object {
+        Object::Client(Client {
+            dst_chain_id: dst_chain_id.clone(),
+            dst_client_id: ClientId::default(),
+            src_chain_id: src_chain_id.clone(),
+        })
+    }
+
+    #[test]
+    fn reload_keeps_the_workers_of_unaffected_chains() {
+        let [a, b, c, d] =
+            ["chain_a", "chain_b", "chain_c", "chain_d"].map(|id| ChainId::new(id.to_string(), 0));
+
+        let objects = vec![client(&a, &b), client(&c, &d), client(&d, &c)];
+
+        // Only the gas multiplier or memo of `c` changed.
+        let diff = ConfigDiff {
+            updated: vec![c.clone()],
+            ..ConfigDiff::default()
+        };
+        assert!(affected_objects(&diff, objects.iter()).is_empty());
+
+        // The key of `a` changed as well.
+        let diff = ConfigDiff {
+            updated: vec![c],
+            restarted: vec![a],
+            ..ConfigDiff::default()
+        };
+        assert_eq!(
+            affected_objects(&diff, objects.iter()),
+            vec![objects[0].clone()]
+        );
+
+        let diff = ConfigDiff {
+            mode_changed: true,
+            ..ConfigDiff::default()
+        };
+        assert_eq!(affected_objects(&diff, objects.iter()), objects);
+    }
+
+    #[test]
+    fn reload_updates_the_running_chain() {
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+        let chain = ChainRuntime::<MockChain>::spawn::<BaseChainHandle>(
+            get_basic_chain_config("chain_a"),
+            rt,
+        )
+        .unwrap();
+
+        let mut config = chain.config().unwrap();
+        config.gas_multiplier = Some(1.5);
+        chain.update_config(config).unwrap();
+
+        assert_eq!(chain.config().unwrap().gas_multiplier, Some(1.5));
+    }
+}
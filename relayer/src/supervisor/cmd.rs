@@ -1,8 +1,13 @@
 use crossbeam_channel::Sender;
 
+use crate::config::diff::ConfigDiff;
+
 use super::dump_state::SupervisorState;
+use super::Error;
 
 #[derive(Clone, Debug)]
 pub enum SupervisorCmd {
     DumpState(Sender<SupervisorState>),
+    /// Reload the configuration file, and reply with the changes applied.
+    ReloadConfig(Sender<Result<ConfigDiff, Error>>),
 }
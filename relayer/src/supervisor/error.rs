@@ -3,8 +3,10 @@ use flex_error::define_error;
 use ibc::core::ics03_connection::connection::Counterparty;
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ConnectionId, PortId};
 
+use crate::config::Error as ConfigError;
 use crate::error::Error as RelayerError;
 use crate::spawn::SpawnError;
+use crate::store::StoreError;
 use crate::supervisor::scan::Error as ScanError;
 
 define_error! {
@@ -69,6 +71,17 @@ define_error! {
         Scan
             [ ScanError ]
             |_| { "supervisor encountered an error when scanning chains" },
+
+        MissingConfigPath
+            |_| { "supervisor cannot reload its configuration without the path to the configuration file" },
+
+        ConfigLoad
+            [ ConfigError ]
+            |_| { "supervisor failed to load the configuration" },
+
+        Store
+            [ StoreError ]
+            |_| { "supervisor failed to open the relayer store" },
     }
 }
 
@@ -10,6 +10,7 @@ use ibc::core::{
 use crate::{
     chain::{counterparty::connection_state_on_destination, handle::ChainHandle},
     config::Config,
+    fee::register_missing_counterparty_payee,
     object::{Channel, Client, Connection, Object, Packet, Wallet},
     registry::Registry,
     supervisor::error::Error as SupervisorError,
@@ -257,6 +258,25 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
                     .then(|| info!("spawned client worker: {}", client_object.short_name()));
             }
 
+            if mode.packets.enabled
+                && mode.packets.auto_register_counterparty_payee
+                && channel_scan.channel.channel_end.version().supports_fee()
+            {
+                if let Err(e) = register_missing_counterparty_payee(
+                    &chain,
+                    &counterparty_chain,
+                    &channel_scan.channel.port_id,
+                    &channel_scan.channel.channel_id,
+                ) {
+                    error!(
+                        chain = %chain.id(),
+                        channel = %channel_scan.id(),
+                        "failed to register counterparty payee: {}",
+                        e
+                    );
+                }
+            }
+
             if mode.packets.enabled {
                 let has_packets = || {
                     !channel_scan
@@ -141,6 +141,7 @@ pub fn build_transfer_message(
         receiver,
         timeout_height,
         timeout_timestamp,
+        memo: Default::default(),
     };
 
     msg.to_any()
@@ -176,6 +177,7 @@ pub fn build_and_send_transfer_messages<SrcChain: ChainHandle, DstChain: ChainHa
         receiver,
         timeout_height: timeout.timeout_height,
         timeout_timestamp: timeout.timeout_timestamp,
+        memo: Default::default(),
     };
 
     let raw_msg = msg.to_any();
@@ -11,6 +11,7 @@ use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
     config::Config,
     object::Object,
+    store::RelayStore,
 };
 
 pub mod retry_strategy;
@@ -30,6 +31,7 @@ pub use map::WorkerMap;
 pub mod channel;
 pub mod client;
 pub mod connection;
+pub mod cross_chain_query;
 pub mod packet;
 pub mod wallet;
 
@@ -58,6 +60,7 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
     id: WorkerId,
     object: Object,
     config: &Config,
+    store: Option<&RelayStore>,
 ) -> WorkerHandle {
     let mut task_handles = Vec::new();
 
@@ -121,10 +124,26 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
             );
 
             match link_res {
-                Ok(link) => {
+                Ok(mut link) => {
+                    // The events emitted while the relayer was stopped were missed, they are
+                    // backfilled from the height of the last batch of events processed.
+                    let resume_height = match store {
+                        Some(store) => match link.a_to_b.resume_from_store(store) {
+                            Ok(cursor) => cursor,
+                            Err(e) => {
+                                error!("failed to resume relaying from the store: {}", e);
+                                None
+                            }
+                        },
+                        None => None,
+                    };
+
                     let channel_ordering = link.a_to_b.channel().ordering;
-                    let should_clear_on_start =
-                        packets_config.clear_on_start || channel_ordering == Order::Ordered;
+                    let should_clear_on_start = packets_config.clear_on_start
+                        || matches!(
+                            channel_ordering,
+                            Order::Ordered | Order::OrderedAllowTimeout
+                        );
 
                     let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
                     let link = Arc::new(Mutex::new(link));
@@ -134,6 +153,7 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
                         cmd_rx,
                         link.clone(),
                         should_clear_on_start,
+                        resume_height,
                         packets_config.clear_interval,
                         path.clone(),
                     );
@@ -159,6 +179,15 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
 
             (None, None)
         }
+
+        Object::CrossChainQuery(query) => {
+            let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
+            let query_task =
+                cross_chain_query::spawn_cross_chain_query_worker(query.clone(), chains, cmd_rx);
+            task_handles.push(query_task);
+
+            (Some(cmd_tx), None)
+        }
     };
 
     WorkerHandle::new(id, object, data, cmd_tx, task_handles)
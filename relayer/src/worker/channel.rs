@@ -32,11 +32,23 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
                         debug!("starts processing {:#?}", last_event);
 
                         if let Some(event) = last_event {
-                            let mut handshake_channel = RelayChannel::restore_from_event(
-                                chains.a.clone(),
-                                chains.b.clone(),
-                                event.clone(),
-                            )
+                            // Upgrade events do not carry the connection of the channel,
+                            // which is restored from the chain state instead.
+                            let mut handshake_channel = if event.upgrade_attributes().is_some() {
+                                RelayChannel::restore_from_state(
+                                    chains.a.clone(),
+                                    chains.b.clone(),
+                                    channel.clone(),
+                                    event.height(),
+                                )
+                                .map(|(handshake_channel, _)| handshake_channel)
+                            } else {
+                                RelayChannel::restore_from_event(
+                                    chains.a.clone(),
+                                    chains.b.clone(),
+                                    event.clone(),
+                                )
+                            }
                             .map_err(|e| TaskError::Fatal(RunError::channel(e)))?;
 
                             retry_with_index(retry_strategy::worker_default_strategy(), |index| {
@@ -0,0 +1,123 @@
+use core::time::Duration;
+use crossbeam_channel::Receiver;
+use tracing::{debug, error, error_span, info};
+
+use ibc::applications::interchain_query::events::SendQuery;
+use ibc::applications::interchain_query::msgs::MsgSubmitCrossChainQueryResult;
+use ibc::events::IbcEvent;
+use ibc::tx_msg::Msg;
+
+use crate::chain::handle::{ChainHandle, ChainHandlePair};
+use crate::chain::requests::{QueryCrossChainRequest, QueryHeight};
+use crate::chain::tracking::TrackedMsgs;
+use crate::foreign_client::ForeignClient;
+use crate::object::CrossChainQuery;
+use crate::util::task::{spawn_background_task, Next, TaskError, TaskHandle};
+
+use super::WorkerCmd;
+
+/// Spawns a worker answering the cross-chain queries sent by chain `a` about the state of chain
+/// `b`. For each query, the value is read from chain `b` along with its proof, the client on
+/// chain `a` is updated to the proof height and the result is submitted to chain `a`.
+pub fn spawn_cross_chain_query_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
+    query: CrossChainQuery,
+    chains: ChainHandlePair<ChainA, ChainB>,
+    cmd_rx: Receiver<WorkerCmd>,
+) -> TaskHandle {
+    spawn_background_task(
+        error_span!("cross_chain_query", query = %query.short_name()),
+        Some(Duration::from_millis(200)),
+        move || -> Result<Next, TaskError<String>> {
+            if let Ok(cmd) = cmd_rx.try_recv() {
+                match cmd {
+                    WorkerCmd::IbcEvents { batch } => {
+                        for event in batch.events {
+                            if let IbcEvent::CrossChainQuery(send_query) = event {
+                                // A failed query is not retried here, it will either be
+                                // answered by another relayer or eventually time out.
+                                if let Err(e) = answer_query(&chains, &send_query) {
+                                    error!(query_id = %send_query.query_id, "{}", e);
+                                }
+                            }
+                        }
+
+                        Ok(Next::Continue)
+                    }
+
+                    // nothing to do
+                    WorkerCmd::NewBlock { .. } | WorkerCmd::ClearPendingPackets => {
+                        Ok(Next::Continue)
+                    }
+                }
+            } else {
+                Ok(Next::Continue)
+            }
+        },
+    )
+}
+
+fn answer_query<ChainA: ChainHandle, ChainB: ChainHandle>(
+    chains: &ChainHandlePair<ChainA, ChainB>,
+    query: &SendQuery,
+) -> Result<(), String> {
+    debug!("answering query {} of {}", query.query_id, query.path);
+
+    // The value proven at height `h` is the one committed at height `h - 1`.
+    let height = match query.query_height {
+        Some(query_height) => QueryHeight::Specific(
+            query_height
+                .decrement()
+                .map_err(|e| format!("invalid query height {}: {}", query_height, e))?,
+        ),
+        None => QueryHeight::Latest,
+    };
+
+    let (data, proof, proof_height) = chains
+        .b
+        .query_cross_chain(QueryCrossChainRequest {
+            path: query.path.clone(),
+            height,
+        })
+        .map_err(|e| format!("failed to query {}: {}", query.path, e))?;
+
+    let client =
+        ForeignClient::restore(query.client_id.clone(), chains.a.clone(), chains.b.clone());
+    let mut msgs = client
+        .wait_and_build_update_client(proof_height)
+        .map_err(|e| format!("failed to build client update: {}", e))?;
+
+    let signer = chains
+        .a
+        .get_signer()
+        .map_err(|e| format!("failed to get signer: {}", e))?;
+
+    let msg = MsgSubmitCrossChainQueryResult {
+        id: query.query_id.clone(),
+        data,
+        proof: proof
+            .try_into()
+            .map_err(|e| format!("invalid proof: {}", e))?,
+        proof_height,
+        signer,
+    };
+    msgs.push(msg.to_any());
+
+    let events = chains
+        .a
+        .send_messages_and_wait_commit(TrackedMsgs::new_static(msgs, "CrossChainQuery"))
+        .map_err(|e| format!("failed to submit query result: {}", e))?;
+
+    if let Some(IbcEvent::ChainError(e)) = events
+        .iter()
+        .find(|event| matches!(event, IbcEvent::ChainError(_)))
+    {
+        return Err(format!("failed to submit query result: {}", e));
+    }
+
+    info!(
+        "answered query {} with a proof at height {}",
+        query.query_id, proof_height
+    );
+
+    Ok(())
+}
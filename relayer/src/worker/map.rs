@@ -10,6 +10,7 @@ use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
     config::Config,
     object::Object,
+    store::RelayStore,
     telemetry,
 };
 
@@ -20,14 +21,14 @@ use super::{spawn_worker_tasks, WorkerHandle, WorkerId};
 pub struct WorkerMap {
     workers: HashMap<Object, WorkerHandle>,
     latest_worker_id: WorkerId,
+    store: Option<RelayStore>,
+    /// The number of packet workers assigned a key of each chain's key pool so far
+    assigned_keys: HashMap<ChainId, usize>,
 }
 
 impl Default for WorkerMap {
     fn default() -> Self {
-        Self {
-            workers: HashMap::new(),
-            latest_worker_id: WorkerId::new(0),
-        }
+        Self::with_store(None)
     }
 }
 
@@ -39,6 +40,17 @@ impl WorkerMap {
         Self::default()
     }
 
+    /// Create a new worker map, whose packet workers persist their
+    /// relaying state to the given store, if any.
+    pub fn with_store(store: Option<RelayStore>) -> Self {
+        Self {
+            workers: HashMap::new(),
+            latest_worker_id: WorkerId::new(0),
+            store,
+            assigned_keys: HashMap::new(),
+        }
+    }
+
     /// Returns `true` if there is a spawned [`WorkerHandle`] associated with the given [`Object`].
     pub fn contains(&self, object: &Object) -> bool {
         self.workers.contains_key(object)
@@ -161,14 +173,50 @@ impl WorkerMap {
     ) -> WorkerHandle {
         telemetry!(worker, metric_type(object), 1);
 
+        // The packet workers submit most of the transactions, so they take turns
+        // with the keys of the key pools in order to send transactions concurrently.
+        let (src, dst) = match object {
+            Object::Packet(_) => (self.assign_key(src, config), self.assign_key(dst, config)),
+            _ => (src, dst),
+        };
+
         spawn_worker_tasks(
             ChainHandlePair { a: src, b: dst },
             self.next_worker_id(),
             object.clone(),
             config,
+            self.store.as_ref(),
         )
     }
 
+    /// Returns a handle to the given chain signing with the next key of its key pool,
+    /// or the given handle if the chain has no key pool.
+    fn assign_key<Chain: ChainHandle>(&mut self, chain: Chain, config: &Config) -> Chain {
+        match self.next_key(&chain.id(), config) {
+            Some(key_name) => {
+                debug!(chain = %chain.id(), key = %key_name, "assigning key to packet worker");
+
+                chain.with_key(key_name)
+            }
+            None => chain,
+        }
+    }
+
+    /// Returns the next key of the key pool of the given chain, in a round-robin fashion,
+    /// or `None` if the chain has no key pool.
+    fn next_key(&mut self, chain_id: &ChainId, config: &Config) -> Option<String> {
+        let key_names = match config.find_chain(chain_id) {
+            Some(chain_config) if !chain_config.key_pool.is_empty() => chain_config.key_names(),
+            _ => return None,
+        };
+
+        let assigned = self.assigned_keys.entry(chain_id.clone()).or_insert(0);
+        let key_name = key_names[*assigned % key_names.len()].clone();
+        *assigned += 1;
+
+        Some(key_name)
+    }
+
     /// Compute the next worker id
     fn next_worker_id(&mut self) -> WorkerId {
         let id = self.latest_worker_id.next();
@@ -237,5 +285,49 @@ fn metric_type(o: &Object) -> ibc_telemetry::state::WorkerType {
         Object::Channel(_) => WorkerType::Channel,
         Object::Packet(_) => WorkerType::Packet,
         Object::Wallet(_) => WorkerType::Wallet,
+        Object::CrossChainQuery(_) => WorkerType::CrossChainQuery,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        crate::config::load(path).expect("could not parse config")
+    }
+
+    #[test]
+    fn keys_are_assigned_round_robin() {
+        let mut config = config();
+        config.chains[0].key_pool = vec!["key-1".to_string(), "key-2".to_string()];
+
+        let chain_a = config.chains[0].id.clone();
+        let chain_b = config.chains[1].id.clone();
+        let default_key = config.chains[0].key_name.clone();
+
+        let mut workers = WorkerMap::new();
+
+        let assigned: Vec<_> = (0..4)
+            .map(|_| workers.next_key(&chain_a, &config))
+            .collect();
+
+        assert_eq!(
+            assigned,
+            vec![
+                Some(default_key.clone()),
+                Some("key-1".to_string()),
+                Some("key-2".to_string()),
+                Some(default_key),
+            ]
+        );
+
+        // The other chain has no key pool, so its workers sign with the default key
+        assert_eq!(workers.next_key(&chain_b, &config), None);
     }
 }
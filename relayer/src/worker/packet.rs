@@ -2,7 +2,7 @@ use core::time::Duration;
 use std::sync::{Arc, Mutex};
 
 use crossbeam_channel::Receiver;
-use tracing::{error, error_span, trace};
+use tracing::{debug, error, error_span, trace};
 
 use ibc::Height;
 
@@ -58,6 +58,7 @@ pub fn spawn_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     // Mutex is used to prevent race condition between the packet workers
     link: Arc<Mutex<Link<ChainA, ChainB>>>,
     mut should_clear_on_start: bool,
+    mut resume_height: Option<Height>,
     clear_interval: u64,
     path: Packet,
 ) -> TaskHandle {
@@ -73,6 +74,28 @@ pub fn spawn_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     };
 
     spawn_background_task(span, Some(Duration::from_millis(200)), move || {
+        // Backfill the events missed since the path was last relayed on, or clear its
+        // pending packets instead if they cannot be backfilled.
+        if let Some(height) = resume_height.take() {
+            match link.lock().unwrap().a_to_b.backfill_from(height) {
+                Ok(true) => {}
+                Ok(false) => {
+                    debug!(
+                        "last relayed at height {}, clearing the pending packets instead of backfilling",
+                        height
+                    );
+                    should_clear_on_start = true;
+                }
+                Err(e) => {
+                    error!(
+                        "failed to backfill the events since height {}, clearing the pending packets instead: {}",
+                        height, e
+                    );
+                    should_clear_on_start = true;
+                }
+            }
+        }
+
         if let Ok(cmd) = cmd_rx.try_recv() {
             // Try to clear pending packets. At different levels down in `handle_packet_cmd` there
             // are retries mechanisms for MAX_RETRIES (current value hardcoded at 5).
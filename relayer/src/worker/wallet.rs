@@ -1,6 +1,6 @@
 use std::{ops::Div, time::Duration};
 
-use tracing::{error_span, trace};
+use tracing::{error_span, trace, warn};
 
 use ibc::bigint::U256;
 
@@ -14,52 +14,77 @@ pub fn spawn_wallet_worker<Chain: ChainHandle>(chain: Chain) -> TaskHandle {
     let span = error_span!("wallet", chain = %chain.id());
 
     spawn_background_task(span, Some(Duration::from_secs(5)), move || {
-        let key = chain.get_key().map_err(|e| {
-            TaskError::Fatal(format!("failed to get key in use by the relayer: {e}"))
+        let config = chain.config().map_err(|e| {
+            TaskError::Ignore(format!("failed to get the configuration of the chain: {e}"))
         })?;
 
-        let balance = chain.query_balance(None).map_err(|e| {
-            TaskError::Ignore(format!("failed to query balance for the account: {e}"))
-        })?;
-
-        let amount: U256 = U256::from_dec_str(&balance.amount).map_err(|_| {
-            TaskError::Ignore(format!(
-                "failed to parse amount into U256: {}",
-                balance.amount
-            ))
-        })?;
-
-        trace!(%amount, denom = %balance.denom, account = %key.account, "wallet balance");
-
-        // The input domain `balance.amount` may exceed u64::MAX, which is the
-        // largest value that can be reported via the Prometheus exporter.
-        //
-        // To work around this, we scale down the amount by 10^6 in an attempt
-        // to fit it into a u64, effectively turning the denomination from
-        // eg. uatom to atom. If the scaled down amount does not fit, we do
-        // not report it.
-        //
-        // Example input that overflows, from sifchain-1: `349999631379421794336`.
-        //
-        if let Some(_scaled_amount) = scale_down(amount) {
-            telemetry!(
-                wallet_balance,
-                &chain.id(),
-                &key.account,
-                _scaled_amount,
-                &balance.denom,
-            );
-        } else {
-            trace!(
-                %amount, denom = %balance.denom, account = %key.account,
-                "amount cannot be scaled down to fit into u64 and therefore won't be reported to telemetry"
-            );
+        // Report the balance of every key of the key pool, so that
+        // an account running low on funds does not go unnoticed.
+        for key_name in config.key_names() {
+            match report_balance(&chain, key_name) {
+                Err(TaskError::Ignore(e)) => warn!("{}", e),
+                result => result?,
+            }
         }
 
         Ok(Next::Continue)
     })
 }
 
+fn report_balance<Chain: ChainHandle>(
+    chain: &Chain,
+    key_name: String,
+) -> Result<(), TaskError<String>> {
+    let key = chain.with_key(key_name.clone()).get_key().map_err(|e| {
+        TaskError::Fatal(format!(
+            "failed to get key '{key_name}' in use by the relayer: {e}"
+        ))
+    })?;
+
+    let balance = chain.query_balance(Some(key_name)).map_err(|e| {
+        TaskError::Ignore(format!(
+            "failed to query balance for the account {}: {e}",
+            key.account
+        ))
+    })?;
+
+    let amount: U256 = U256::from_dec_str(&balance.amount).map_err(|_| {
+        TaskError::Ignore(format!(
+            "failed to parse amount into U256: {}",
+            balance.amount
+        ))
+    })?;
+
+    trace!(%amount, denom = %balance.denom, account = %key.account, "wallet balance");
+
+    // The input domain `balance.amount` may exceed u64::MAX, which is the
+    // largest value that can be reported via the Prometheus exporter.
+    //
+    // To work around this, we scale down the amount by 10^6 in an attempt
+    // to fit it into a u64, effectively turning the denomination from
+    // eg. uatom to atom. If the scaled down amount does not fit, we do
+    // not report it.
+    //
+    // Example input that overflows, from sifchain-1: `349999631379421794336`.
+    //
+    if let Some(_scaled_amount) = scale_down(amount) {
+        telemetry!(
+            wallet_balance,
+            &chain.id(),
+            &key.account,
+            _scaled_amount,
+            &balance.denom,
+        );
+    } else {
+        trace!(
+            %amount, denom = %balance.denom, account = %key.account,
+            "amount cannot be scaled down to fit into u64 and therefore won't be reported to telemetry"
+        );
+    }
+
+    Ok(())
+}
+
 /// Scale down the given amount by a factor of 10^6,
 /// and return it as a `u64` if it fits.
 fn scale_down(amount: U256) -> Option<u64> {
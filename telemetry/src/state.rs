@@ -27,6 +27,7 @@ pub enum WorkerType {
     Channel,
     Packet,
     Wallet,
+    CrossChainQuery,
 }
 
 impl fmt::Display for WorkerType {
@@ -37,6 +38,7 @@ impl fmt::Display for WorkerType {
             Self::Channel => write!(f, "channel"),
             Self::Packet => write!(f, "packet"),
             Self::Wallet => write!(f, "wallet"),
+            Self::CrossChainQuery => write!(f, "cross_chain_query"),
         }
     }
 }
@@ -115,6 +117,26 @@ pub struct TelemetryState {
 
     /// History of SendPacket sequence numbers received and not yet Acknowledged.
     sequences_histories: DashMap<PathIdentifier, DashMap<u64, u64>>,
+
+    /// Sum of the ICS29 fees of the packets relayed, per fee kind and denom.
+    fee_amounts: Counter<u64>,
+
+    /// Number of packets which were not incentivized enough to be relayed,
+    /// or to be relayed in priority.
+    unincentivized_packets: Counter<u64>,
+
+    /// Latency of the health probes of the full nodes, per chain and node. Milliseconds.
+    endpoint_latency: ValueRecorder<u64>,
+
+    /// Number of failed health probes of the full nodes, per chain and node.
+    endpoint_errors: Counter<u64>,
+
+    /// The latest height reported by the full nodes, per chain and node.
+    endpoint_height: ValueRecorder<u64>,
+
+    /// Number of times the relayer failed over to another full node, per chain
+    /// and node failed over to.
+    endpoint_switches: Counter<u64>,
 }
 
 impl TelemetryState {
@@ -371,6 +393,86 @@ impl TelemetryState {
         self.cleared_count.add(1, labels);
     }
 
+    pub fn fee_amounts(
+        &self,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        fee_kind: &'static str,
+        denom: &str,
+        amount: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("channel", channel_id.to_string()),
+            KeyValue::new("port", port_id.to_string()),
+            KeyValue::new("fee_kind", fee_kind),
+            KeyValue::new("denom", denom.to_string()),
+        ];
+
+        self.fee_amounts.add(amount, labels);
+    }
+
+    pub fn unincentivized_packets(
+        &self,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        fee_kind: &'static str,
+        count: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("channel", channel_id.to_string()),
+            KeyValue::new("port", port_id.to_string()),
+            KeyValue::new("fee_kind", fee_kind),
+        ];
+
+        self.unincentivized_packets.add(count, labels);
+    }
+
+    /// Latency of a health probe of a full node, per chain and node. Milliseconds.
+    pub fn endpoint_latency(&self, chain_id: &ChainId, endpoint: &str, latency: Duration) {
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("endpoint", endpoint.to_string()),
+        ];
+
+        self.endpoint_latency
+            .record(latency.as_millis() as u64, labels);
+    }
+
+    /// Number of failed health probes of the full nodes, per chain and node.
+    pub fn endpoint_error(&self, chain_id: &ChainId, endpoint: &str) {
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("endpoint", endpoint.to_string()),
+        ];
+
+        self.endpoint_errors.add(1, labels);
+    }
+
+    /// The latest height reported by a full node, per chain and node.
+    pub fn endpoint_height(&self, chain_id: &ChainId, endpoint: &str, height: u64) {
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("endpoint", endpoint.to_string()),
+        ];
+
+        self.endpoint_height.record(height, labels);
+    }
+
+    /// Number of times the relayer failed over to another full node, per chain
+    /// and node failed over to.
+    pub fn endpoint_switch(&self, chain_id: &ChainId, endpoint: &str) {
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("endpoint", endpoint.to_string()),
+        ];
+
+        self.endpoint_switches.add(1, labels);
+    }
+
     pub fn record_send_history(
         &self,
         seq_nr: u64,
@@ -491,12 +593,14 @@ impl AggregatorSelector for CustomAggregatorSelector {
             "wallet_balance" => Some(Arc::new(last_value())),
             "oldest_sequence" => Some(Arc::new(last_value())),
             "oldest_timestamp" => Some(Arc::new(last_value())),
+            "endpoint_height" => Some(Arc::new(last_value())),
             // Prometheus' supports only collector for histogram, sum, and last value aggregators.
             // https://docs.rs/opentelemetry-prometheus/0.10.0/src/opentelemetry_prometheus/lib.rs.html#411-418
             // TODO: Once quantile sketches are supported, replace histograms with that.
             // For the moment, disable histogram buckets since no values make sense for all use-cases.
             "tx_latency_submitted" => Some(Arc::new(histogram(descriptor, &[]))),
             "tx_latency_confirmed" => Some(Arc::new(histogram(descriptor, &[]))),
+            "endpoint_latency" => Some(Arc::new(histogram(descriptor, &[]))),
             _ => Some(Arc::new(sum())),
         }
     }
@@ -623,6 +727,37 @@ impl Default for TelemetryState {
                 .with_unit(Unit::new("seconds"))
                 .with_description("The timestamp of the oldest sequence number in seconds")
                 .init(),
+
+            fee_amounts: meter
+                .u64_counter("fee_amounts")
+                .with_description("Sum of the ICS29 fees of the packets relayed, per fee kind and denom")
+                .init(),
+
+            unincentivized_packets: meter
+                .u64_counter("unincentivized_packets")
+                .with_description("Number of packets skipped or deprioritized because their ICS29 fees were below the configured minimum")
+                .init(),
+
+            endpoint_latency: meter
+                .u64_value_recorder("endpoint_latency")
+                .with_unit(Unit::new("milliseconds"))
+                .with_description("The latency of the health probes of the full nodes, per chain and node. Milliseconds.")
+                .init(),
+
+            endpoint_errors: meter
+                .u64_counter("endpoint_errors")
+                .with_description("Number of failed health probes of the full nodes, per chain and node")
+                .init(),
+
+            endpoint_height: meter
+                .u64_value_recorder("endpoint_height")
+                .with_description("The latest height reported by the full nodes, per chain and node")
+                .init(),
+
+            endpoint_switches: meter
+                .u64_counter("endpoint_switches")
+                .with_description("Number of times the relayer failed over to another full node, per chain and node failed over to")
+                .init(),
         }
     }
 }
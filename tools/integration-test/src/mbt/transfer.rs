@@ -3,7 +3,7 @@ use std::panic::{RefUnwindSafe, UnwindSafe};
 
 use ibc_relayer::config::{
     Channels as ConfigChannels, Clients as ConfigClients, Connections as ConfigConnections,
-    ModeConfig, Packets as ConfigPackets,
+    CrossChainQueries as ConfigCrossChainQueries, ModeConfig, Packets as ConfigPackets,
 };
 
 use ibc_test_framework::prelude::*;
@@ -157,7 +157,9 @@ impl TestOverrides for IbcTransferMBT {
                 clear_interval: 10,
                 clear_on_start: true,
                 tx_confirmation: true,
+                auto_register_counterparty_payee: false,
             },
+            cross_chain_queries: ConfigCrossChainQueries { enabled: false },
         };
 
         for mut chain_config in config.chains.iter_mut() {
@@ -115,7 +115,9 @@ impl TestOverrides for ExpirationTestOverrides {
                 clear_interval: 10,
                 clear_on_start: true,
                 tx_confirmation: true,
+                auto_register_counterparty_payee: false,
             },
+            cross_chain_queries: config::CrossChainQueries { enabled: false },
         };
 
         for mut chain_config in config.chains.iter_mut() {
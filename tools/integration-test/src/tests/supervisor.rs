@@ -29,7 +29,9 @@ impl TestOverrides for SupervisorTest {
                 clear_interval: 10,
                 clear_on_start: true,
                 tx_confirmation: true,
+                auto_register_counterparty_payee: false,
             },
+            cross_chain_queries: config::CrossChainQueries { enabled: false },
         };
     }
 }
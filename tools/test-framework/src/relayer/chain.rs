@@ -21,6 +21,7 @@
 */
 
 use crossbeam_channel as channel;
+use ibc::applications::fee::packet_fee::IdentifiedPacketFees;
 use ibc::core::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
 use ibc::core::ics02_client::client_state::{AnyClientState, IdentifiedAnyClientState};
 use ibc::core::ics02_client::events::UpdateClient;
@@ -28,6 +29,7 @@ use ibc::core::ics02_client::misbehaviour::MisbehaviourEvidence;
 use ibc::core::ics03_connection::connection::IdentifiedConnectionEnd;
 use ibc::core::ics04_channel::channel::IdentifiedChannelEnd;
 use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use ibc::core::ics23_commitment::merkle::MerkleProof;
 use ibc::{
     core::ics02_client::header::AnyHeader,
@@ -52,11 +54,13 @@ use ibc_relayer::chain::requests::{
     QueryChannelsRequest, QueryClientConnectionsRequest, QueryClientStateRequest,
     QueryClientStatesRequest, QueryConnectionChannelsRequest, QueryConnectionRequest,
     QueryConnectionsRequest, QueryConsensusStateRequest, QueryConsensusStatesRequest,
-    QueryHostConsensusStateRequest, QueryNextSequenceReceiveRequest,
+    QueryCounterpartyPayeeRequest, QueryCrossChainRequest, QueryHostConsensusStateRequest,
+    QueryIncentivizedPacketsRequest, QueryNextSequenceReceiveRequest,
     QueryPacketAcknowledgementRequest, QueryPacketAcknowledgementsRequest,
     QueryPacketCommitmentRequest, QueryPacketCommitmentsRequest, QueryPacketReceiptRequest,
     QueryTxRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
-    QueryUpgradedClientStateRequest, QueryUpgradedConsensusStateRequest,
+    QueryUpgradeErrorRequest, QueryUpgradeRequest, QueryUpgradedClientStateRequest,
+    QueryUpgradedConsensusStateRequest,
 };
 use ibc_relayer::chain::tracking::TrackedMsgs;
 use ibc_relayer::config::ChainConfig;
@@ -85,6 +89,10 @@ where
         self.value().id()
     }
 
+    fn with_key(&self, key_name: String) -> Self {
+        Self::new(self.value().with_key(key_name))
+    }
+
     fn shutdown(&self) -> Result<(), Error> {
         self.value().shutdown()
     }
@@ -115,10 +123,18 @@ where
         self.value().get_signer()
     }
 
+    fn get_key_address(&self) -> Result<Signer, Error> {
+        self.value().get_key_address()
+    }
+
     fn config(&self) -> Result<ChainConfig, Error> {
         self.value().config()
     }
 
+    fn update_config(&self, config: ChainConfig) -> Result<(), Error> {
+        self.value().update_config(config)
+    }
+
     fn get_key(&self) -> Result<KeyEntry, Error> {
         self.value().get_key()
     }
@@ -244,6 +260,43 @@ where
         self.value().query_channel(request, include_proof)
     }
 
+    fn query_upgrade(
+        &self,
+        request: QueryUpgradeRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error> {
+        self.value().query_upgrade(request, include_proof)
+    }
+
+    fn query_upgrade_error(
+        &self,
+        request: QueryUpgradeErrorRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        self.value().query_upgrade_error(request, include_proof)
+    }
+
+    fn query_cross_chain(
+        &self,
+        request: QueryCrossChainRequest,
+    ) -> Result<(Vec<u8>, MerkleProof, Height), Error> {
+        self.value().query_cross_chain(request)
+    }
+
+    fn query_incentivized_packets(
+        &self,
+        request: QueryIncentivizedPacketsRequest,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        self.value().query_incentivized_packets(request)
+    }
+
+    fn query_counterparty_payee(
+        &self,
+        request: QueryCounterpartyPayeeRequest,
+    ) -> Result<Option<Signer>, Error> {
+        self.value().query_counterparty_payee(request)
+    }
+
     fn query_channel_client_state(
         &self,
         request: QueryChannelClientStateRequest,
@@ -4,13 +4,11 @@ use eyre::eyre;
 use http::uri::Uri;
 use ibc::core::ics24_host::identifier::ChainId;
 use ibc::events::IbcEvent;
-use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_proto::google::protobuf::Any;
-use ibc_relayer::chain::cosmos::gas::calculate_fee;
 use ibc_relayer::chain::cosmos::query::account::query_account;
 use ibc_relayer::chain::cosmos::tx::estimate_fee_and_send_tx;
 use ibc_relayer::chain::cosmos::types::config::TxConfig;
-use ibc_relayer::chain::cosmos::types::gas::GasConfig;
+use ibc_relayer::chain::cosmos::types::gas::{DynamicGasPrice, GasConfig};
 use ibc_relayer::chain::cosmos::types::tx::{TxStatus, TxSyncResult};
 use ibc_relayer::chain::cosmos::wait::wait_for_block_commits;
 use ibc_relayer::config::GasPrice;
@@ -27,19 +25,11 @@ pub fn gas_config_for_test() -> GasConfig {
     let default_gas = max_gas;
     let fee_granter = "".to_string();
 
-    let max_fee = Fee {
-        amount: vec![calculate_fee(max_gas, &gas_price)],
-        gas_limit: max_gas,
-        payer: "".to_string(),
-        granter: fee_granter.clone(),
-    };
-
     GasConfig {
         default_gas,
         max_gas,
         gas_multiplier,
-        gas_price,
-        max_fee,
+        gas_price: DynamicGasPrice::new(gas_price, Default::default()),
         fee_granter,
     }
 }
@@ -69,6 +59,7 @@ pub fn new_tx_config_for_test(
         grpc_address,
         rpc_timeout,
         address_type,
+        authz_granter: None,
     })
 }
 
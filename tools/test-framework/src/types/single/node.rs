@@ -133,6 +133,7 @@ impl FullNode {
             // ~/.hermes/keys. See
             // https://github.com/informalsystems/ibc-rs/issues/1541
             key_store_type: Store::Memory,
+            key_pool: Vec::new(),
 
             store_prefix: "ibc".to_string(),
             default_gas: None,
@@ -140,6 +141,7 @@ impl FullNode {
             gas_adjustment: None,
             gas_multiplier: None,
             fee_granter: None,
+            authz_granter: None,
             max_msg_num: Default::default(),
             max_tx_size: Default::default(),
             max_block_time: Duration::from_secs(30),
@@ -148,7 +150,13 @@ impl FullNode {
             trust_threshold: Default::default(),
             gas_price: config::GasPrice::new(0.001, "stake".to_string()),
             packet_filter: Default::default(),
+            fee_filter: Default::default(),
             address_type: Default::default(),
+            event_source: Default::default(),
+            event_store: Default::default(),
+            endpoints: Vec::new(),
+            light_store: Default::default(),
+            dynamic_gas_price: Default::default(),
             memo_prefix: Default::default(),
             proof_specs: Default::default(),
         })